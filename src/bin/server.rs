@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use clap::Parser;
-use fnck_sql::db::{DBTransaction, DataBaseBuilder, Database};
+use fnck_sql::db::{DataBaseBuilder, Database, Session};
 use fnck_sql::errors::DatabaseError;
 use fnck_sql::storage::rocksdb::RocksStorage;
 use fnck_sql::types::tuple::{Schema, Tuple};
@@ -20,10 +20,7 @@ use pgwire::error::{ErrorInfo, PgWireError, PgWireResult};
 use pgwire::tokio::process_socket;
 use std::fmt::Debug;
 use std::io;
-use std::mem::transmute;
-use std::ops::{Deref, DerefMut};
 use std::path::PathBuf;
-use std::ptr::NonNull;
 use std::sync::Arc;
 use tokio::net::TcpListener;
 
@@ -60,41 +57,29 @@ struct Args {
     path: String,
 }
 
-struct TransactionPtr(NonNull<DBTransaction<'static, RocksStorage>>);
-
-impl Deref for TransactionPtr {
-    type Target = NonNull<DBTransaction<'static, RocksStorage>>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-impl DerefMut for TransactionPtr {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
-}
-
-unsafe impl Send for TransactionPtr {}
-unsafe impl Sync for TransactionPtr {}
-
 pub struct FnckSQLBackend {
     inner: Arc<Database<RocksStorage>>,
 }
 
 pub struct SessionBackend {
-    inner: Arc<Database<RocksStorage>>,
-    tx: Mutex<Option<TransactionPtr>>,
+    // Tips: declared before `_database`, so that the session is dropped before the database it
+    // borrows
+    session: Mutex<Session<'static, RocksStorage>>,
+    _database: Arc<Database<RocksStorage>>,
 }
 
 impl MakeHandler for FnckSQLBackend {
     type Handler = Arc<SessionBackend>;
 
     fn make(&self) -> Self::Handler {
+        let database = Arc::clone(&self.inner);
+        // SAFETY: the database is kept alive by `_database` of the same `SessionBackend`, which
+        // outlives its session
+        let session = unsafe { &*Arc::as_ptr(&database) }.session();
+
         Arc::new(SessionBackend {
-            inner: Arc::clone(&self.inner),
-            tx: Mutex::new(None),
+            session: Mutex::new(session),
+            _database: database,
         })
     }
 }
@@ -119,68 +104,18 @@ impl SimpleQueryHandler for SessionBackend {
     where
         C: ClientInfo + Unpin + Send + Sync,
     {
-        match query.to_uppercase().as_str() {
-            "BEGIN;" | "BEGIN" | "START TRANSACTION;" | "START TRANSACTION" => {
-                let mut guard = self.tx.lock();
-
-                if guard.is_some() {
-                    return Err(PgWireError::ApiError(Box::new(
-                        DatabaseError::TransactionAlreadyExists,
-                    )));
-                }
-                let transaction = self
-                    .inner
-                    .new_transaction()
-                    .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
-                guard.replace(TransactionPtr(
-                    Box::leak(Box::<DBTransaction<'static, RocksStorage>>::new(unsafe {
-                        transmute(transaction)
-                    }))
-                    .into(),
-                ));
-
-                Ok(vec![Response::Execution(Tag::new("OK"))])
-            }
-            "COMMIT;" | "COMMIT" | "COMMIT WORK;" | "COMMIT WORK" => {
-                let mut guard = self.tx.lock();
-
-                if let Some(transaction) = guard.take() {
-                    unsafe { Box::from_raw(transaction.as_ptr()) }
-                        .commit()
-                        .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
-
-                    Ok(vec![Response::Execution(Tag::new("OK"))])
-                } else {
-                    Err(PgWireError::ApiError(Box::new(
-                        DatabaseError::NoTransactionBegin,
-                    )))
-                }
-            }
-            "ROLLBACK;" | "ROLLBACK" => {
-                let mut guard = self.tx.lock();
-
-                if let Some(transaction) = guard.take() {
-                    unsafe { drop(Box::from_raw(transaction.as_ptr())) }
-                } else {
-                    return Err(PgWireError::ApiError(Box::new(
-                        DatabaseError::NoTransactionBegin,
-                    )));
-                }
+        let (schema, tuples) = self
+            .session
+            .lock()
+            .run(query)
+            .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
 
+        match query.to_uppercase().as_str() {
+            "BEGIN;" | "BEGIN" | "START TRANSACTION;" | "START TRANSACTION" | "COMMIT;"
+            | "COMMIT" | "COMMIT WORK;" | "COMMIT WORK" | "ROLLBACK;" | "ROLLBACK" => {
                 Ok(vec![Response::Execution(Tag::new("OK"))])
             }
-            _ => {
-                let mut guard = self.tx.lock();
-
-                let (schema, tuples) = if let Some(transaction) = guard.as_mut() {
-                    unsafe { transaction.as_mut().run(query) }
-                } else {
-                    self.inner.run(query)
-                }
-                .map_err(|e| PgWireError::ApiError(Box::new(e)))?;
-
-                Ok(vec![Response::Query(encode_tuples(&schema, tuples)?)])
-            }
+            _ => Ok(vec![Response::Query(encode_tuples(&schema, tuples)?)]),
         }
     }
 }
@@ -230,6 +165,12 @@ fn encode_tuples<'a>(schema: &Schema, tuples: Vec<Tuple>) -> PgWireResult<QueryR
                 LogicalType::Date => encoder.encode_field(&value.date()),
                 LogicalType::DateTime => encoder.encode_field(&value.datetime()),
                 LogicalType::Time => encoder.encode_field(&value.time()),
+                LogicalType::TimestampTz => {
+                    encoder.encode_field(&(!value.is_null()).then(|| value.to_string()))
+                }
+                LogicalType::Json => encoder.encode_field(&value.json()),
+                LogicalType::Uuid => encoder.encode_field(&value.uuid().map(|v| v.to_string())),
+                LogicalType::Bytea => encoder.encode_field(&value.bytea()),
                 LogicalType::Decimal(_, _) => todo!(),
                 _ => unreachable!(),
             }?;
@@ -255,6 +196,10 @@ fn into_pg_type(data_type: &LogicalType) -> PgWireResult<Type> {
         LogicalType::Date | LogicalType::DateTime => Type::DATE,
         LogicalType::Char(..) => Type::CHAR,
         LogicalType::Time => Type::TIME,
+        LogicalType::TimestampTz => Type::TIMESTAMPTZ,
+        LogicalType::Json => Type::JSON,
+        LogicalType::Uuid => Type::UUID,
+        LogicalType::Bytea => Type::BYTEA,
         LogicalType::Decimal(_, _) => todo!(),
         _ => {
             return Err(PgWireError::UserError(Box::new(ErrorInfo::new(
@@ -1,12 +1,19 @@
 use ahash::RandomState;
 use itertools::Itertools;
-use sqlparser::ast::{Expr, OrderByExpr};
+use sqlparser::ast::{Expr, OrderByExpr, UnaryOperator, Value};
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use crate::errors::DatabaseError;
+use crate::expression::agg::AggKind;
 use crate::expression::function::scala::ScalarFunction;
+use crate::expression::AliasType;
+use crate::planner::operator::project::ProjectOperator;
+use crate::planner::operator::union::UnionOperator;
+use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
+use crate::types::value::DataValue;
 use crate::{
     expression::ScalarExpression,
     planner::operator::{aggregate::AggregateOperator, sort::SortField},
@@ -41,17 +48,142 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
         select_list: &mut [ScalarExpression],
         groupby: &[Expr],
     ) -> Result<(), DatabaseError> {
-        self.validate_groupby_illegal_column(select_list, groupby)?;
+        let (groupby, grouping_sets) = expand_grouping_sets(groupby);
+        self.validate_groupby_illegal_column(select_list, &groupby)?;
 
-        for gb in groupby {
-            let mut expr = self.bind_expr(gb)?;
+        // the position of each expression of `groupby` in `group_by_exprs`
+        let mut positions = Vec::with_capacity(groupby.len());
+        for gb in groupby.iter() {
+            let mut expr = self.bind_group_by_expr(select_list, gb)?;
+            let len = self.context.group_by_exprs.len();
             self.visit_group_by_expr(select_list, &mut expr);
+            positions.push((self.context.group_by_exprs.len() > len).then_some(len));
         }
+        self.context.grouping_sets = grouping_sets.map(|grouping_sets| {
+            grouping_sets
+                .into_iter()
+                .map(|set| set.into_iter().filter_map(|i| positions[i]).collect_vec())
+                .collect_vec()
+        });
         Ok(())
     }
 
+    /// Binds the aggregation of each grouping set and the union of them, where the grouping
+    /// expressions not in the set are `NULL` and `GROUPING(...)` is the bitmask of its arguments
+    /// not in the set, the first argument as the most significant bit.
+    pub fn bind_grouping_sets(
+        &mut self,
+        children: LogicalPlan,
+        agg_calls: Vec<ScalarExpression>,
+        groupby_exprs: Vec<ScalarExpression>,
+        grouping_sets: Vec<Vec<usize>>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        self.context.step(QueryBindStep::Agg);
+
+        let (grouping_calls, agg_calls): (Vec<_>, Vec<_>) =
+            agg_calls.into_iter().partition(|expr| {
+                matches!(
+                    expr,
+                    ScalarExpression::AggCall {
+                        kind: AggKind::Grouping,
+                        ..
+                    }
+                )
+            });
+        // Tips: the expressions out of the set keep the output column, so that they are
+        // referenced the same way in every branch of the union
+        let constant_as = |value: DataValue, expr: &ScalarExpression| ScalarExpression::Alias {
+            expr: Box::new(ScalarExpression::Constant(Arc::new(value))),
+            alias: AliasType::Expr(Box::new(expr.clone())),
+        };
+        let mut plan: Option<LogicalPlan> = None;
+
+        for grouping_set in grouping_sets {
+            let in_set = |expr: &ScalarExpression| {
+                grouping_set
+                    .iter()
+                    .any(|i| groupby_exprs[*i].unpack_alias_ref() == expr.unpack_alias_ref())
+            };
+            let mut exprs = agg_calls.clone();
+
+            for expr in groupby_exprs.iter() {
+                exprs.push(if in_set(expr) {
+                    expr.clone()
+                } else {
+                    constant_as(DataValue::none(&expr.return_type()), expr)
+                });
+            }
+            for grouping_call in grouping_calls.iter() {
+                let ScalarExpression::AggCall { args, .. } = grouping_call else {
+                    unreachable!()
+                };
+                let mut bits = 0;
+
+                for arg in args {
+                    if !groupby_exprs
+                        .iter()
+                        .any(|expr| expr.unpack_alias_ref() == arg.unpack_alias_ref())
+                    {
+                        return Err(DatabaseError::AggMiss(format!(
+                            "arguments to GROUPING must be grouping expressions: `{}`",
+                            arg
+                        )));
+                    }
+                    bits = (bits << 1) | i32::from(!in_set(arg));
+                }
+                exprs.push(constant_as(DataValue::Int32(Some(bits)), grouping_call));
+            }
+            let set_exprs = grouping_set
+                .iter()
+                .map(|i| groupby_exprs[*i].clone())
+                .collect_vec();
+            let mut branch = LogicalPlan::new(
+                Operator::Project(ProjectOperator { exprs }),
+                vec![AggregateOperator::build(
+                    children.clone(),
+                    agg_calls.clone(),
+                    set_exprs,
+                    false,
+                )],
+            );
+
+            plan = Some(match plan {
+                Some(mut left_plan) => {
+                    let left_schema = left_plan.output_schema().clone();
+                    let right_schema = branch.output_schema().clone();
+
+                    UnionOperator::build(left_schema, right_schema, left_plan, branch)
+                }
+                None => branch,
+            });
+        }
+        Ok(plan.unwrap())
+    }
+
+    /// Binds the expression of `GROUP BY`, which may be the position of an item in the select list.
+    fn bind_group_by_expr(
+        &mut self,
+        select_list: &[ScalarExpression],
+        expr: &Expr,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        if let Some(expr) = bind_ordinal("GROUP BY", select_list, expr)? {
+            return Ok(expr);
+        }
+        let expr = self.bind_expr(expr)?;
+
+        // e.g. `a + 1` of `SELECT a + 1 AS r ... GROUP BY a + 1`, which is grouped as `r`
+        Ok(select_list
+            .iter()
+            .find(|item| {
+                matches!(item, ScalarExpression::Alias { expr: inner_expr, .. } if inner_expr.as_ref() == &expr)
+            })
+            .cloned()
+            .unwrap_or(expr))
+    }
+
     pub fn extract_having_orderby_aggregate(
         &mut self,
+        select_list: &[ScalarExpression],
         having: &Option<Expr>,
         orderbys: &[OrderByExpr],
     ) -> Result<(Option<ScalarExpression>, Option<Vec<SortField>>), DatabaseError> {
@@ -74,13 +206,24 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                     asc,
                     nulls_first,
                 } = orderby;
-                let mut expr = self.bind_expr(expr)?;
+                let mut expr = match bind_ordinal("ORDER BY", select_list, expr)? {
+                    Some(expr) => expr,
+                    None => {
+                        self.context.allow_window = true;
+                        let expr = self.bind_expr(expr)?;
+                        self.context.allow_window = false;
+                        expr
+                    }
+                };
                 self.visit_column_agg_expr(&mut expr)?;
 
+                if !expr.return_type().is_orderable() {
+                    return Err(DatabaseError::Unorderable(expr.return_type()));
+                }
                 return_orderby.push(SortField::new(
                     expr,
                     asc.map_or(true, |asc| asc),
-                    nulls_first.map_or(false, |first| first),
+                    nulls_first.unwrap_or(self.nulls_first),
                 ));
             }
             Some(return_orderby)
@@ -90,12 +233,49 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
         Ok((return_having, return_orderby))
     }
 
+    /// Binds the expressions of `DISTINCT ON`, which may be the positions of items in the select
+    /// list like `ORDER BY`.
+    pub fn extract_distinct_on_aggregate(
+        &mut self,
+        select_list: &[ScalarExpression],
+        on_exprs: &[Expr],
+    ) -> Result<Vec<ScalarExpression>, DatabaseError> {
+        let mut return_on_exprs = Vec::with_capacity(on_exprs.len());
+
+        for expr in on_exprs {
+            let mut expr = match bind_ordinal("DISTINCT ON", select_list, expr)? {
+                Some(expr) => expr,
+                None => self.bind_expr(expr)?,
+            };
+            self.visit_column_agg_expr(&mut expr)?;
+
+            return_on_exprs.push(expr);
+        }
+        Ok(return_on_exprs)
+    }
+
     fn visit_column_agg_expr(&mut self, expr: &mut ScalarExpression) -> Result<(), DatabaseError> {
         match expr {
             ScalarExpression::AggCall { .. } => {
                 self.context.agg_calls.push(expr.clone());
             }
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                for expr in args
+                    .iter_mut()
+                    .chain(partition_by.iter_mut())
+                    .chain(order_by.iter_mut().map(|field| &mut field.expr))
+                {
+                    self.visit_column_agg_expr(expr)?;
+                }
+            }
             ScalarExpression::TypeCast { expr, .. } => self.visit_column_agg_expr(expr)?,
+            ScalarExpression::FieldAccess { expr, .. } => self.visit_column_agg_expr(expr)?,
+            ScalarExpression::Collate { expr, .. } => self.visit_column_agg_expr(expr)?,
             ScalarExpression::IsNull { expr, .. } => self.visit_column_agg_expr(expr)?,
             ScalarExpression::Unary { expr, .. } => self.visit_column_agg_expr(expr)?,
             ScalarExpression::Alias { expr, .. } => self.visit_column_agg_expr(expr)?,
@@ -154,7 +334,9 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             ScalarExpression::Reference { .. } | ScalarExpression::Empty => unreachable!(),
             ScalarExpression::Tuple(args)
             | ScalarExpression::ScalaFunction(ScalarFunction { args, .. })
-            | ScalarExpression::Coalesce { exprs: args, .. } => {
+            | ScalarExpression::Coalesce { exprs: args, .. }
+            | ScalarExpression::Greatest { exprs: args, .. }
+            | ScalarExpression::Least { exprs: args, .. } => {
                 for expr in args {
                     self.visit_column_agg_expr(expr)?;
                 }
@@ -217,7 +399,7 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
     ) -> Result<(), DatabaseError> {
         let mut group_raw_exprs = vec![];
         for expr in groupby {
-            let expr = self.bind_expr(expr)?;
+            let expr = self.bind_group_by_expr(select_items, expr)?;
 
             if let ScalarExpression::Alias { alias, .. } = expr {
                 let alias_expr = select_items.iter().find(|column| {
@@ -242,7 +424,9 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             HashSet::from_iter(group_raw_exprs.iter());
 
         for expr in select_items {
-            if expr.has_agg_call() {
+            if expr.has_agg_call()
+                || matches!(expr.unpack_alias_ref(), ScalarExpression::WindowCall { .. })
+            {
                 continue;
             }
             group_raw_set.remove(expr);
@@ -327,7 +511,24 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 ))
             }
 
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                for expr in args
+                    .iter()
+                    .chain(partition_by.iter())
+                    .chain(order_by.iter().map(|field| &field.expr))
+                {
+                    self.validate_having_orderby(expr)?;
+                }
+                Ok(())
+            }
             ScalarExpression::TypeCast { expr, .. } => self.validate_having_orderby(expr),
+            ScalarExpression::FieldAccess { expr, .. } => self.validate_having_orderby(expr),
+            ScalarExpression::Collate { expr, .. } => self.validate_having_orderby(expr),
             ScalarExpression::IsNull { expr, .. } => self.validate_having_orderby(expr),
             ScalarExpression::Unary { expr, .. } => self.validate_having_orderby(expr),
             ScalarExpression::In { expr, args, .. } => {
@@ -391,7 +592,9 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             ScalarExpression::Reference { .. } | ScalarExpression::Empty => unreachable!(),
             ScalarExpression::Tuple(args)
             | ScalarExpression::ScalaFunction(ScalarFunction { args, .. })
-            | ScalarExpression::Coalesce { exprs: args, .. } => {
+            | ScalarExpression::Coalesce { exprs: args, .. }
+            | ScalarExpression::Greatest { exprs: args, .. }
+            | ScalarExpression::Least { exprs: args, .. } => {
                 for expr in args {
                     self.validate_having_orderby(expr)?;
                 }
@@ -447,3 +650,100 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
         }
     }
 }
+
+/// Resolves the integer `n` of `GROUP BY` or `ORDER BY` into the `n`-th item of the select list,
+/// `None` if `expr` is not a number, e.g. `ORDER BY 'x'` sorts by the constant.
+fn bind_ordinal(
+    clause: &'static str,
+    select_list: &[ScalarExpression],
+    expr: &Expr,
+) -> Result<Option<ScalarExpression>, DatabaseError> {
+    let (sign, number) = match expr {
+        Expr::Value(Value::Number(number, _)) => ("", number),
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+        } => match expr.as_ref() {
+            Expr::Value(Value::Number(number, _)) => ("-", number),
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+    match number.parse::<usize>() {
+        Ok(position) if sign.is_empty() && (1..=select_list.len()).contains(&position) => {
+            Ok(Some(select_list[position - 1].clone()))
+        }
+        _ => Err(DatabaseError::InvalidOrdinal(
+            clause,
+            format!("{}{}", sign, number),
+            select_list.len(),
+        )),
+    }
+}
+
+/// Expands `ROLLUP`, `CUBE` and `GROUPING SETS` of `GROUP BY` into the distinct grouping
+/// expressions and the grouping sets as their positions, the cross product of the sets of
+/// each item, e.g. `GROUP BY a, ROLLUP(b, c)` => `(a, b, c), (a, b), (a)`.
+///
+/// There are no grouping sets for a plain `GROUP BY`, which only groups by all expressions.
+fn expand_grouping_sets(groupby: &[Expr]) -> (Vec<Expr>, Option<Vec<Vec<usize>>>) {
+    let mut exprs: Vec<Expr> = Vec::with_capacity(groupby.len());
+    let mut grouping_sets: Vec<Vec<usize>> = vec![vec![]];
+    let mut has_grouping_sets = false;
+
+    for gb in groupby {
+        let item_sets: Vec<Vec<&Expr>> = match gb {
+            Expr::Rollup(columns) => {
+                has_grouping_sets = true;
+                (0..=columns.len())
+                    .rev()
+                    .map(|len| columns[..len].iter().flatten().collect())
+                    .collect()
+            }
+            Expr::Cube(columns) => {
+                has_grouping_sets = true;
+                (0..1usize << columns.len())
+                    .rev()
+                    .map(|mask| {
+                        columns
+                            .iter()
+                            .enumerate()
+                            .filter(|(i, _)| mask & (1 << i) != 0)
+                            .flat_map(|(_, column)| column)
+                            .collect()
+                    })
+                    .collect()
+            }
+            Expr::GroupingSets(sets) => {
+                has_grouping_sets = true;
+                sets.iter().map(|set| set.iter().collect()).collect()
+            }
+            expr => vec![vec![expr]],
+        };
+        let mut item_positions = Vec::with_capacity(item_sets.len());
+        for set in item_sets {
+            let mut positions = Vec::with_capacity(set.len());
+            for expr in set {
+                let position = match exprs.iter().position(|e| e == expr) {
+                    Some(position) => position,
+                    None => {
+                        exprs.push(expr.clone());
+                        exprs.len() - 1
+                    }
+                };
+                positions.push(position);
+            }
+            item_positions.push(positions);
+        }
+        grouping_sets = grouping_sets
+            .iter()
+            .flat_map(|set| {
+                item_positions.iter().map(move |positions| {
+                    set.iter().chain(positions).copied().unique().collect_vec()
+                })
+            })
+            .collect();
+    }
+
+    (exprs, has_grouping_sets.then_some(grouping_sets))
+}
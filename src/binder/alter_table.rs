@@ -1,16 +1,27 @@
-use sqlparser::ast::{AlterTableOperation, ObjectName};
-
-use std::sync::Arc;
+use itertools::Itertools;
+use sqlparser::ast::{AlterColumnOperation, AlterTableOperation, ObjectName, TableConstraint};
 
 use super::{is_valid_identifier, Binder};
-use crate::binder::lower_case_name;
+use crate::binder::create_table::{
+    column_check_defs, column_foreign_key_defs, table_foreign_key_def,
+};
+use crate::binder::new_table_name;
+use crate::catalog::is_temp_table;
 use crate::errors::DatabaseError;
+use crate::planner::operator::alter_table::add_check::AddCheckOperator;
 use crate::planner::operator::alter_table::add_column::AddColumnOperator;
+use crate::planner::operator::alter_table::add_foreign_key::AddForeignKeyOperator;
+use crate::planner::operator::alter_table::alter_column::AlterColumnOperator;
 use crate::planner::operator::alter_table::drop_column::DropColumnOperator;
+use crate::planner::operator::alter_table::drop_constraint::DropConstraintOperator;
+use crate::planner::operator::alter_table::rename_table::RenameTableOperator;
+use crate::planner::operator::create_index::CreateIndexOperator;
 use crate::planner::operator::table_scan::TableScanOperator;
 use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
+use crate::types::index::IndexType;
+use crate::types::LogicalType;
 
 impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
     pub(crate) fn bind_alter_table(
@@ -18,7 +29,7 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
         name: &ObjectName,
         operation: &AlterTableOperation,
     ) -> Result<LogicalPlan, DatabaseError> {
-        let table_name: Arc<String> = Arc::new(lower_case_name(name)?);
+        let table_name = self.bind_table_name(name)?;
         let table = self
             .context
             .table(table_name.clone())
@@ -30,6 +41,7 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 column_def,
             } => {
                 let plan = TableScanOperator::build(table_name.clone(), table);
+                let mut table = table.clone();
                 let column = self.bind_column(column_def)?;
 
                 if !is_valid_identifier(column.name()) {
@@ -37,11 +49,31 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                         "illegal column naming".to_string(),
                     ));
                 }
+                if !column_foreign_key_defs(column_def).is_empty() {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "foreign key in add column".to_string(),
+                    ));
+                }
+                if column.desc.is_auto_increment {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "auto-increment in add column".to_string(),
+                    ));
+                }
+                let check_defs = column_check_defs(&table_name, column_def);
+                // Tips: the existing column is left to `AddColumn`, which ignores it or fails
+                let checks = if check_defs.is_empty() || table.contains_column(column.name()) {
+                    vec![]
+                } else {
+                    table.add_column(column.clone())?;
+
+                    self.bind_check_defs(&table, check_defs)?
+                };
                 LogicalPlan::new(
                     Operator::AddColumn(AddColumnOperator {
                         table_name,
                         if_not_exists: *if_not_exists,
                         column,
+                        checks,
                     }),
                     vec![plan],
                 )
@@ -54,6 +86,20 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 let plan = TableScanOperator::build(table_name.clone(), table);
                 let column_name = column_name.value.clone();
 
+                for (i, expr) in self.bind_generated_columns(table)? {
+                    if expr
+                        .referenced_columns(true)
+                        .iter()
+                        .any(|column| column.name() == column_name)
+                    {
+                        return Err(DatabaseError::InvalidGenerated(format!(
+                            "column `{}` is used by generated column `{}`",
+                            column_name,
+                            table.schema_ref()[i].name()
+                        )));
+                    }
+                }
+
                 LogicalPlan::new(
                     Operator::DropColumn(DropColumnOperator {
                         table_name,
@@ -63,12 +109,122 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                     vec![plan],
                 )
             }
+            AlterTableOperation::AddConstraint(TableConstraint::Unique {
+                name,
+                columns,
+                is_primary: false,
+            }) => {
+                let columns: Vec<_> = columns
+                    .iter()
+                    .map(|column| {
+                        table
+                            .get_column_by_name(&column.value.to_lowercase())
+                            .cloned()
+                            .ok_or_else(|| DatabaseError::NotFound("column", column.value.clone()))
+                    })
+                    .try_collect()?;
+                if let Some(column) = columns
+                    .iter()
+                    .find(|column| !column.datatype().is_orderable())
+                {
+                    return Err(DatabaseError::Unorderable(column.datatype().clone()));
+                }
+                // e.g. `uk_c1_c2` of `UNIQUE (c1, c2)`
+                let index_name = name
+                    .as_ref()
+                    .map(|name| name.value.to_lowercase())
+                    .unwrap_or_else(|| {
+                        format!(
+                            "uk_{}",
+                            columns.iter().map(|column| column.name()).join("_")
+                        )
+                    });
+                let plan = TableScanOperator::build(table_name.clone(), table);
+
+                LogicalPlan::new(
+                    Operator::CreateIndex(CreateIndexOperator {
+                        table_name,
+                        columns,
+                        index_name,
+                        if_not_exists: false,
+                        ty: IndexType::Unique {
+                            nulls_not_distinct: false,
+                        },
+                        json_path: vec![],
+                    }),
+                    vec![plan],
+                )
+            }
+            AlterTableOperation::AddConstraint(TableConstraint::Check { name, expr }) => {
+                let check = self
+                    .bind_check_defs(
+                        table,
+                        vec![(
+                            name.as_ref(),
+                            format!("{}_check", table_name),
+                            expr.as_ref(),
+                        )],
+                    )?
+                    .remove(0);
+                let plan = TableScanOperator::build(table_name.clone(), table);
+
+                LogicalPlan::new(
+                    Operator::AddCheck(AddCheckOperator { table_name, check }),
+                    vec![plan],
+                )
+            }
+            AlterTableOperation::AddConstraint(constraint @ TableConstraint::ForeignKey { .. }) => {
+                let foreign_key = self
+                    .bind_foreign_key_defs(
+                        table,
+                        table_foreign_key_def(constraint).into_iter().collect(),
+                    )?
+                    .remove(0);
+                let plan = TableScanOperator::build(table_name.clone(), table);
+
+                LogicalPlan::new(
+                    Operator::AddForeignKey(AddForeignKeyOperator {
+                        table_name,
+                        foreign_key,
+                    }),
+                    vec![plan],
+                )
+            }
+            AlterTableOperation::AddConstraint(constraint) => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "add constraint: {}",
+                    constraint
+                )))
+            }
+            AlterTableOperation::DropConstraint {
+                if_exists, name, ..
+            } => LogicalPlan::new(
+                Operator::DropConstraint(DropConstraintOperator {
+                    table_name,
+                    constraint_name: name.value.to_lowercase(),
+                    if_exists: *if_exists,
+                }),
+                vec![],
+            ),
             AlterTableOperation::DropPrimaryKey => todo!(),
             AlterTableOperation::RenameColumn {
                 old_column_name: _,
                 new_column_name: _,
             } => todo!(),
-            AlterTableOperation::RenameTable { table_name: _ } => todo!(),
+            AlterTableOperation::RenameTable {
+                table_name: new_name,
+            } => LogicalPlan::new(
+                Operator::RenameTable(RenameTableOperator {
+                    // a temporary table is still temporary once renamed
+                    new_table_name: new_table_name(
+                        new_name,
+                        is_temp_table(&table_name),
+                        self.temp_schema.as_deref(),
+                    )?,
+                    table_name,
+                }),
+                vec![],
+            ),
             AlterTableOperation::ChangeColumn {
                 old_name: _,
                 new_name: _,
@@ -76,9 +232,36 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 options: _,
             } => todo!(),
             AlterTableOperation::AlterColumn {
-                column_name: _,
-                op: _,
-            } => todo!(),
+                column_name,
+                op:
+                    AlterColumnOperation::SetDataType {
+                        data_type,
+                        using: None,
+                    },
+            } => {
+                let column_name = column_name.value.to_lowercase();
+
+                if table.get_column_by_name(&column_name).is_none() {
+                    return Err(DatabaseError::NotFound("column", column_name));
+                }
+                let ty = LogicalType::try_from(data_type.clone())?;
+                let plan = TableScanOperator::build(table_name.clone(), table);
+
+                LogicalPlan::new(
+                    Operator::AlterColumn(AlterColumnOperator {
+                        table_name,
+                        column_name,
+                        ty,
+                    }),
+                    vec![plan],
+                )
+            }
+            AlterTableOperation::AlterColumn { op, .. } => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "alter column: {}",
+                    op
+                )))
+            }
             _ => todo!(),
         };
 
@@ -1,4 +1,4 @@
-use crate::binder::{lower_case_name, Binder};
+use crate::binder::Binder;
 use crate::errors::DatabaseError;
 use crate::planner::operator::analyze::AnalyzeOperator;
 use crate::planner::operator::table_scan::TableScanOperator;
@@ -6,11 +6,10 @@ use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
 use sqlparser::ast::ObjectName;
-use std::sync::Arc;
 
 impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
     pub(crate) fn bind_analyze(&mut self, name: &ObjectName) -> Result<LogicalPlan, DatabaseError> {
-        let table_name = Arc::new(lower_case_name(name)?);
+        let table_name = self.bind_table_name(name)?;
 
         let table_catalog = self
             .context
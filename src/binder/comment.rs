@@ -0,0 +1,55 @@
+use crate::binder::{lower_ident, Binder};
+use crate::errors::DatabaseError;
+use crate::planner::operator::comment::CommentOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use sqlparser::ast::{CommentObject, ObjectName};
+
+impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
+    /// `COMMENT ON TABLE t1 IS '...'` or `COMMENT ON COLUMN t1.c1 IS '...'`, `IS NULL` or an empty
+    /// comment removes the comment.
+    pub(crate) fn bind_comment(
+        &mut self,
+        object_type: &CommentObject,
+        object_name: &ObjectName,
+        comment: &Option<String>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let (table_name, column_name) = match (object_type, object_name.0.as_slice()) {
+            (CommentObject::Table, _) => (self.bind_table_name(object_name)?, None),
+            // e.g. `t1.c1`
+            (CommentObject::Column, [table_idents @ .., column_ident])
+                if !table_idents.is_empty() =>
+            {
+                let table_name = self.bind_table_name(&ObjectName(table_idents.to_vec()))?;
+
+                (table_name, Some(lower_ident(column_ident)))
+            }
+            (CommentObject::Column, _) => {
+                return Err(DatabaseError::InvalidColumn(format!(
+                    "the table of column {} is missing",
+                    object_name
+                )))
+            }
+        };
+        let table = self
+            .context
+            .table(table_name.clone())
+            .ok_or(DatabaseError::TableNotFound)?;
+        if let Some(column_name) = &column_name {
+            if table.get_column_by_name(column_name).is_none() {
+                return Err(DatabaseError::NotFound("column", column_name.clone()));
+            }
+        }
+        let comment = comment.clone().filter(|comment| !comment.is_empty());
+
+        Ok(LogicalPlan::new(
+            Operator::Comment(CommentOperator {
+                table_name,
+                column_name,
+                comment,
+            }),
+            vec![],
+        ))
+    }
+}
@@ -5,6 +5,7 @@ use std::sync::Arc;
 use crate::errors::DatabaseError;
 use crate::planner::operator::copy_from_file::CopyFromFileOperator;
 use crate::planner::operator::copy_to_file::CopyToFileOperator;
+use crate::planner::operator::table_scan::TableScanOperator;
 use crate::planner::operator::Operator;
 use serde::{Deserialize, Serialize};
 use sqlparser::ast::{CopyOption, CopySource, CopyTarget};
@@ -30,6 +31,15 @@ pub enum FileFormat {
         /// Whether or not the file has a header line.
         header: bool,
     },
+    /// One JSON object per line, mapped to the columns by key.
+    ///
+    /// On import a missing key is the default of the column, or `NULL` without a default.
+    /// JSON strings and numbers are parsed like the fields of a CSV file, so `1.5` cannot be
+    /// imported into an integer column, booleans only fit boolean columns and arrays or
+    /// objects can only be imported into JSON columns, which take any JSON value but `null`.
+    /// On export numbers, booleans and JSON values are written as they are, the other values
+    /// as strings.
+    Ndjson,
 }
 
 impl std::fmt::Display for ExtSource {
@@ -59,61 +69,80 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
         target: CopyTarget,
         options: &[CopyOption],
     ) -> Result<LogicalPlan, DatabaseError> {
-        let (table_name, ..) = match source {
-            CopySource::Table {
-                table_name,
-                columns,
-            } => (table_name, columns),
-            CopySource::Query(_) => {
-                return Err(DatabaseError::UnsupportedStmt("'COPY SOURCE'".to_string()));
-            }
+        let ext_source = ExtSource {
+            path: match target {
+                CopyTarget::File { filename } => filename.into(),
+                t => {
+                    return Err(DatabaseError::UnsupportedStmt(format!(
+                        "copy target: {}",
+                        t
+                    )))
+                }
+            },
+            format: FileFormat::from_options(options)?,
         };
+        let table_name = match source {
+            CopySource::Table { table_name, .. } => Arc::new(table_name.to_string()),
+            CopySource::Query(query) => {
+                if !to {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "'COPY (SELECT ...) FROM' is not allowed".to_string(),
+                    ));
+                }
+                // COPY (<query>) TO <dest_file>
+                let input = self.bind_query(&query)?;
 
-        if let Some(table) = self.context.table(Arc::new(table_name.to_string())) {
-            let schema_ref = table.schema_ref().clone();
-            let ext_source = ExtSource {
-                path: match target {
-                    CopyTarget::File { filename } => filename.into(),
-                    t => todo!("unsupported copy target: {:?}", t),
-                },
-                format: FileFormat::from_options(options),
-            };
-
-            if to {
-                // COPY <source_table> TO <dest_file>
-                Ok(LogicalPlan::new(
-                    Operator::CopyToFile(CopyToFileOperator { source: ext_source }),
-                    vec![],
-                ))
-            } else {
-                // COPY <dest_table> FROM <source_file>
-                Ok(LogicalPlan::new(
-                    Operator::CopyFromFile(CopyFromFileOperator {
-                        source: ext_source,
-                        schema_ref,
-                        table: table_name.to_string(),
-                    }),
-                    vec![],
-                ))
+                return Ok(LogicalPlan::new(
+                    Operator::CopyToFile(CopyToFileOperator { target: ext_source }),
+                    vec![input],
+                ));
             }
+        };
+        let table = self
+            .context
+            .table(table_name.clone())
+            .ok_or(DatabaseError::TableNotFound)?;
+
+        if to {
+            // COPY <source_table> TO <dest_file>
+            Ok(LogicalPlan::new(
+                Operator::CopyToFile(CopyToFileOperator { target: ext_source }),
+                vec![TableScanOperator::build(table_name, table)],
+            ))
         } else {
-            Err(DatabaseError::TableNotFound)
+            // COPY <dest_table> FROM <source_file>
+            Ok(LogicalPlan::new(
+                Operator::CopyFromFile(CopyFromFileOperator {
+                    source: ext_source,
+                    schema_ref: table.schema_ref().clone(),
+                    table: table_name.to_string(),
+                }),
+                vec![],
+            ))
         }
     }
 }
 
 impl FileFormat {
     /// Create from copy options.
-    pub fn from_options(options: &[CopyOption]) -> Self {
+    pub fn from_options(options: &[CopyOption]) -> Result<Self, DatabaseError> {
+        let mut ndjson = false;
         let mut delimiter = ',';
         let mut quote = '"';
         let mut escape = None;
         let mut header = false;
         for opt in options {
             match opt {
-                CopyOption::Format(fmt) => {
-                    debug_assert_eq!(fmt.value.to_lowercase(), "csv", "only support CSV format")
-                }
+                CopyOption::Format(fmt) => match fmt.value.to_lowercase().as_str() {
+                    "csv" => ndjson = false,
+                    "ndjson" => ndjson = true,
+                    _ => {
+                        return Err(DatabaseError::UnsupportedStmt(format!(
+                            "copy format: {}",
+                            fmt
+                        )))
+                    }
+                },
                 CopyOption::Delimiter(c) => delimiter = *c,
                 CopyOption::Header(b) => header = *b,
                 CopyOption::Quote(c) => quote = *c,
@@ -121,11 +150,22 @@ impl FileFormat {
                 o => panic!("unsupported copy option: {:?}", o),
             }
         }
-        FileFormat::Csv {
+        if ndjson {
+            if options
+                .iter()
+                .any(|opt| !matches!(opt, CopyOption::Format(_)))
+            {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "the options of CSV with format NDJSON".to_string(),
+                ));
+            }
+            return Ok(FileFormat::Ndjson);
+        }
+        Ok(FileFormat::Csv {
             delimiter,
             quote,
             escape,
             header,
-        }
+        })
     }
 }
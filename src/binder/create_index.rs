@@ -1,6 +1,7 @@
 use crate::binder::{lower_case_name, Binder};
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
+use crate::function::json::unpack_json_path;
 use crate::planner::operator::create_index::CreateIndexOperator;
 use crate::planner::operator::table_scan::TableScanOperator;
 use crate::planner::operator::Operator;
@@ -8,7 +9,6 @@ use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
 use crate::types::index::IndexType;
 use sqlparser::ast::{ObjectName, OrderByExpr};
-use std::sync::Arc;
 
 impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
     pub(crate) fn bind_create_index(
@@ -19,10 +19,12 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
         if_not_exists: bool,
         is_unique: bool,
     ) -> Result<LogicalPlan, DatabaseError> {
-        let table_name = Arc::new(lower_case_name(table_name)?);
+        let table_name = self.bind_table_name(table_name)?;
         let index_name = lower_case_name(name)?;
         let ty = if is_unique {
-            IndexType::Unique
+            IndexType::Unique {
+                nulls_not_distinct: false,
+            }
         } else if exprs.len() == 1 {
             IndexType::Normal
         } else {
@@ -34,16 +36,31 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             .table_and_bind(table_name.clone(), None, None)?;
         let plan = TableScanOperator::build(table_name.clone(), table);
         let mut columns = Vec::with_capacity(exprs.len());
+        let mut json_path = Vec::new();
 
         for expr in exprs {
             // TODO: Expression Index
             match self.bind_expr(&expr.expr)? {
-                ScalarExpression::ColumnRef(column) => columns.push(column),
+                ScalarExpression::ColumnRef(column) => {
+                    if !column.datatype().is_orderable() {
+                        return Err(DatabaseError::Unorderable(column.datatype().clone()));
+                    }
+                    columns.push(column)
+                }
                 expr => {
-                    return Err(DatabaseError::UnsupportedStmt(format!(
-                        "'CREATE INDEX' by {}",
-                        expr
-                    )))
+                    // e.g. `CREATE INDEX idx ON docs ((data ->> 'email'))`
+                    let Some((column, path)) = unpack_json_path(&expr).filter(|_| exprs.len() == 1)
+                    else {
+                        return Err(DatabaseError::UnsupportedStmt(format!(
+                            "'CREATE INDEX' by {}",
+                            expr
+                        )));
+                    };
+                    if !expr.return_type().is_orderable() {
+                        return Err(DatabaseError::Unorderable(expr.return_type()));
+                    }
+                    columns.push(column);
+                    json_path = path;
                 }
             }
         }
@@ -55,6 +72,7 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 index_name,
                 if_not_exists,
                 ty,
+                json_path,
             }),
             vec![plan],
         ))
@@ -1,13 +1,25 @@
 use itertools::Itertools;
-use sqlparser::ast::{ColumnDef, ColumnOption, ObjectName, TableConstraint};
+use sqlparser::ast::{
+    ColumnDef, ColumnOption, DataType, Expr, FunctionArg, FunctionArgExpr, Ident, ObjectName,
+    Query, ReferentialAction, TableConstraint,
+};
 use std::collections::HashSet;
+use std::slice;
+use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 
-use super::{is_valid_identifier, Binder};
-use crate::binder::lower_case_name;
-use crate::catalog::{ColumnCatalog, ColumnDesc};
+use super::{is_valid_identifier, Binder, BinderContext};
+use crate::binder::lower_ident;
+use crate::catalog::{
+    is_temp_table, temp_table_name, CheckConstraint, ColumnCatalog, ColumnCompression, ColumnDesc,
+    ForeignKey, TableCatalog, TableName, UniqueKey,
+};
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
+use crate::parser::{
+    auto_increment_tokens, deferred_tokens, nulls_distinct_tokens, parse_compression, parse_expr,
+    parse_generated,
+};
 use crate::planner::operator::create_table::CreateTableOperator;
 use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
@@ -18,14 +30,12 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
     // TODO: TableConstraint
     pub(crate) fn bind_create_table(
         &mut self,
-        name: &ObjectName,
+        table_name: TableName,
         columns: &[ColumnDef],
         constraints: &[TableConstraint],
         if_not_exists: bool,
     ) -> Result<LogicalPlan, DatabaseError> {
-        let table_name = Arc::new(lower_case_name(name)?);
-
-        if !is_valid_identifier(&table_name) {
+        if !table_name.split('.').all(is_valid_identifier) {
             return Err(DatabaseError::InvalidTable(
                 "illegal table naming".to_string(),
             ));
@@ -45,45 +55,162 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 }
             }
         }
+        let mut check_defs = columns
+            .iter()
+            .flat_map(|column_def| column_check_defs(&table_name, column_def))
+            .collect_vec();
+        let mut foreign_key_defs = columns
+            .iter()
+            .flat_map(column_foreign_key_defs)
+            .collect_vec();
         let mut columns: Vec<ColumnCatalog> = columns
             .iter()
             .map(|col| self.bind_column(col))
             .try_collect()?;
+        let multiple_primary_keys = || {
+            DatabaseError::InvalidTable(format!(
+                "multiple primary keys for table {} are not allowed",
+                table_name
+            ))
+        };
+        // e.g. `c1 INT PRIMARY KEY, c2 INT PRIMARY KEY`
+        let mut unique_keys = Vec::new();
+        let mut has_primary_key = match columns
+            .iter()
+            .filter(|column| column.desc.is_primary())
+            .count()
+        {
+            0 => false,
+            1 => true,
+            _ => return Err(multiple_primary_keys()),
+        };
+        if columns
+            .iter()
+            .filter(|column| column.desc.is_auto_increment)
+            .count()
+            > 1
+        {
+            return Err(DatabaseError::InvalidTable(format!(
+                "there can be only one auto-increment column in table {}",
+                table_name
+            )));
+        }
+
         for constraint in constraints {
             match constraint {
                 TableConstraint::Unique {
                     columns: column_names,
-                    is_primary,
+                    is_primary: true,
+                    ..
+                } => {
+                    if has_primary_key {
+                        return Err(multiple_primary_keys());
+                    }
+                    has_primary_key = true;
+
+                    // e.g. `PRIMARY KEY (c1, c2)` is ordered by `c1` and then by `c2`
+                    for (i, column_name) in column_names
+                        .iter()
+                        .map(|ident| ident.value.to_lowercase())
+                        .enumerate()
+                    {
+                        let column = columns
+                            .iter_mut()
+                            .find(|column| column.name() == column_name)
+                            .ok_or_else(|| {
+                                DatabaseError::NotFound("column", column_name.clone())
+                            })?;
+                        if column.desc.is_primary() {
+                            return Err(DatabaseError::DuplicateColumn(column_name));
+                        }
+                        column.desc.primary = Some(i);
+                        column.nullable = false;
+                    }
+                }
+                TableConstraint::Unique {
+                    name,
+                    columns: column_names,
                     ..
                 } => {
-                    for column_name in column_names.iter().map(|ident| ident.value.to_lowercase()) {
+                    let column_names = column_names
+                        .iter()
+                        .map(|ident| ident.value.to_lowercase())
+                        .collect_vec();
+
+                    for (i, column_name) in column_names.iter().enumerate() {
+                        if !columns.iter().any(|column| column.name() == column_name) {
+                            return Err(DatabaseError::NotFound("column", column_name.clone()));
+                        }
+                        if column_names[..i].contains(column_name) {
+                            return Err(DatabaseError::DuplicateColumn(column_name.clone()));
+                        }
+                    }
+                    if let [column_name] = column_names.as_slice() {
                         if let Some(column) = columns
                             .iter_mut()
                             .find(|column| column.name() == column_name)
                         {
-                            if *is_primary {
-                                column.desc.is_primary = true;
-                            } else {
-                                column.desc.is_unique = true;
-                            }
+                            column.desc.is_unique = true;
                         }
+                    } else {
+                        // e.g. `uk_c1_c2` of `UNIQUE (c1, c2)`
+                        let name = name
+                            .as_ref()
+                            .map(|name| name.value.to_lowercase())
+                            .unwrap_or_else(|| format!("uk_{}", column_names.join("_")));
+                        unique_keys.push(UniqueKey {
+                            name,
+                            columns: column_names,
+                        });
                     }
                 }
+                TableConstraint::Check { name, expr } => check_defs.push((
+                    name.as_ref(),
+                    format!("{}_check", table_name),
+                    expr.as_ref(),
+                )),
+                TableConstraint::ForeignKey { .. } => {
+                    foreign_key_defs.extend(table_foreign_key_def(constraint))
+                }
                 _ => todo!(),
             }
         }
 
-        if columns.iter().filter(|col| col.desc.is_primary).count() != 1 {
+        if !has_primary_key {
             return Err(DatabaseError::InvalidTable(
                 "The primary key field must exist and have at least one".to_string(),
             ));
         }
+        // Tips: the keys of the primary key and the unique indexes are ordered
+        if let Some(column) = columns.iter().find(|column| {
+            (column.desc.is_primary()
+                || column.desc.is_unique
+                || unique_keys
+                    .iter()
+                    .any(|unique_key| unique_key.columns.contains(&column.name().to_string())))
+                && !column.datatype().is_orderable()
+        }) {
+            return Err(DatabaseError::Unorderable(column.datatype().clone()));
+        }
+        // the table to be created, which its constraints are bound against
+        let table = TableCatalog::new(table_name.clone(), columns.clone())?;
+        let checks = self
+            .bind_check_defs(&table, check_defs)?
+            .into_iter()
+            .map(|(check, _)| check)
+            .collect_vec();
+        let foreign_keys = self.bind_foreign_key_defs(&table, foreign_key_defs)?;
+        self.bind_generated_columns(&table)?;
 
         Ok(LogicalPlan::new(
             Operator::CreateTable(CreateTableOperator {
                 table_name,
                 columns,
+                checks,
+                unique_keys,
+                foreign_keys,
                 if_not_exists,
+                view_query: None,
             }),
             vec![],
         ))
@@ -91,12 +218,29 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
 
     pub fn bind_column(&mut self, column_def: &ColumnDef) -> Result<ColumnCatalog, DatabaseError> {
         let column_name = column_def.name.value.to_lowercase();
+        // e.g. `SERIAL` is `INT AUTO_INCREMENT`
+        let serial_type = match &column_def.data_type {
+            DataType::Custom(name, modifiers) if modifiers.is_empty() => {
+                match name.to_string().to_lowercase().as_str() {
+                    "smallserial" => Some(LogicalType::Smallint),
+                    "serial" => Some(LogicalType::Integer),
+                    "bigserial" => Some(LogicalType::Bigint),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        let is_auto_increment = serial_type.is_some();
         let mut column_desc = ColumnDesc::new(
-            LogicalType::try_from(column_def.data_type.clone())?,
+            match serial_type {
+                Some(ty) => ty,
+                None => LogicalType::try_from(column_def.data_type.clone())?,
+            },
             false,
             false,
             None,
         )?;
+        column_desc.is_auto_increment = is_auto_increment;
         let mut nullable = true;
 
         // TODO: 这里可以对更多字段可设置内容进行补充
@@ -106,36 +250,623 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 ColumnOption::NotNull => nullable = false,
                 ColumnOption::Unique { is_primary, .. } => {
                     if *is_primary {
-                        column_desc.is_primary = true;
-                        nullable = false;
-                        // Skip other options when using primary key
-                        break;
+                        column_desc.primary = Some(0);
                     } else {
                         column_desc.is_unique = true;
                     }
                 }
                 ColumnOption::Default(expr) => {
-                    let mut expr = self.bind_expr(expr)?;
+                    let default_text = expr.to_string();
 
-                    if !expr.referenced_columns(true).is_empty() {
-                        return Err(DatabaseError::UnsupportedStmt(
-                            "column is not allowed to exist in `default`".to_string(),
-                        ));
+                    if has_subquery(expr) {
+                        return Err(DatabaseError::InvalidDefault(format!(
+                            "subquery is not allowed in the default of column `{}`",
+                            column_name
+                        )));
+                    }
+                    let mut expr = self.bind_expr(expr).map_err(|err| match err {
+                        DatabaseError::NotFound("column", name) => {
+                            DatabaseError::InvalidDefault(format!(
+                                "the default of column `{}` cannot reference column `{}`",
+                                column_name, name
+                            ))
+                        }
+                        err => err,
+                    })?;
+
+                    if self.context.sub_queries_at_now().is_some() {
+                        return Err(DatabaseError::InvalidDefault(format!(
+                            "subquery is not allowed in the default of column `{}`",
+                            column_name
+                        )));
+                    }
+                    if let Some(column) = expr.referenced_columns(true).first() {
+                        return Err(DatabaseError::InvalidDefault(format!(
+                            "the default of column `{}` cannot reference column `{}`",
+                            column_name,
+                            column.name()
+                        )));
+                    }
+                    if expr.has_agg_call() {
+                        return Err(DatabaseError::InvalidDefault(format!(
+                            "aggregate function is not allowed in the default of column `{}`",
+                            column_name
+                        )));
                     }
                     if expr.return_type() != column_desc.column_datatype {
                         expr = ScalarExpression::TypeCast {
                             expr: Box::new(expr),
-                            ty: column_desc.column_datatype,
+                            ty: column_desc.column_datatype.clone(),
+                            is_try: false,
                         }
                     }
+                    expr.bind_evaluator()?;
                     column_desc.default = Some(expr);
+                    column_desc.default_text = Some(default_text);
                 }
+                ColumnOption::DialectSpecific(tokens)
+                    if column_desc.is_unique && tokens == &nulls_distinct_tokens(true) =>
+                {
+                    column_desc.nulls_not_distinct = true;
+                }
+                ColumnOption::DialectSpecific(tokens)
+                    if column_desc.is_unique && tokens == &nulls_distinct_tokens(false) =>
+                {
+                    column_desc.nulls_not_distinct = false;
+                }
+                ColumnOption::DialectSpecific(tokens) if tokens == &auto_increment_tokens() => {
+                    column_desc.is_auto_increment = true;
+                }
+                // Tips: kept by the foreign key it follows, see `column_foreign_key_defs`
+                ColumnOption::DialectSpecific(tokens) if tokens == &deferred_tokens() => (),
+                ColumnOption::DialectSpecific(tokens) => {
+                    if let Some(method) = parse_compression(tokens) {
+                        column_desc.compression = Some(ColumnCompression::from_name(&method)?);
+                        continue;
+                    }
+                    let Some(generated) = parse_generated(tokens) else {
+                        return Err(DatabaseError::UnsupportedStmt(format!(
+                            "column option: {:?}",
+                            tokens
+                        )));
+                    };
+                    let (expr, is_stored) = generated?;
+
+                    if !is_stored {
+                        return Err(DatabaseError::UnsupportedStmt(
+                            "virtual generated column".to_string(),
+                        ));
+                    }
+                    // Tips: bound by the callers against the columns of the table
+                    column_desc.generated = Some(expr.to_string());
+                }
+                // Tips: the checks and the foreign keys are bound by the callers against the
+                // columns of the table
+                ColumnOption::Check(_) | ColumnOption::ForeignKey { .. } => (),
                 _ => todo!(),
             }
         }
+        // e.g. `id UUID PRIMARY KEY DEFAULT gen_random_uuid()`, whose other options are kept
+        if column_desc.is_primary() {
+            nullable = false;
+        }
+        if column_desc.generated.is_some()
+            && (column_desc.default.is_some() || column_desc.is_auto_increment)
+        {
+            return Err(DatabaseError::InvalidGenerated(format!(
+                "generated column `{}` cannot have a default",
+                column_name
+            )));
+        }
+        if column_desc.is_auto_increment {
+            let ty = &column_desc.column_datatype;
+
+            if !ty.is_signed_numeric() && !ty.is_unsigned_numeric() {
+                return Err(DatabaseError::InvalidColumn(format!(
+                    "auto-increment column `{}` must be an integer, not {}",
+                    column_name, ty
+                )));
+            }
+            if column_desc.default.is_some() {
+                return Err(DatabaseError::InvalidColumn(format!(
+                    "auto-increment column `{}` cannot have a default",
+                    column_name
+                )));
+            }
+        }
+
+        if column_desc.compression.is_some() && column_desc.column_datatype.raw_len().is_some() {
+            return Err(DatabaseError::InvalidColumn(format!(
+                "compressed column `{}` must be of a variable length type, not {}",
+                column_name, column_desc.column_datatype
+            )));
+        }
 
         Ok(ColumnCatalog::new(column_name, nullable, column_desc))
     }
+
+    /// `CREATE TABLE ... AS query`, whose columns are inferred from the output of the query, or
+    /// named by `columns` if given.
+    pub(crate) fn bind_create_table_as_query(
+        &mut self,
+        table_name: TableName,
+        columns: &[ColumnDef],
+        query: &Query,
+        if_not_exists: bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        if !table_name.split('.').all(is_valid_identifier) {
+            return Err(DatabaseError::InvalidTable(
+                "illegal table naming".to_string(),
+            ));
+        }
+        let column_names = columns
+            .iter()
+            .map(|col| lower_ident(&col.name))
+            .collect_vec();
+        let plan = self.bind_query(query)?;
+
+        self.bind_create_table_as(plan, table_name, &column_names, None, if_not_exists)
+    }
+
+    /// Binds the `CHECK` constraints declared on `table`, which are named after the table, or
+    /// after the column, if they are not given a name.
+    pub(crate) fn bind_check_defs(
+        &self,
+        table: &TableCatalog,
+        check_defs: Vec<(Option<&Ident>, String, &Expr)>,
+    ) -> Result<Vec<(CheckConstraint, ScalarExpression)>, DatabaseError> {
+        let mut names = table
+            .checks()
+            .map(|check| check.name.clone())
+            .collect::<HashSet<_>>();
+        let mut checks = Vec::with_capacity(check_defs.len());
+
+        for (name, default_name, expr) in check_defs {
+            let name = constraint_name(name, default_name, &names)
+                .map_err(|name| DatabaseError::InvalidCheck(name_exists(&name)))?;
+            let bound = self.bind_check(table, &name, expr)?;
+            let columns = bound
+                .referenced_columns(true)
+                .iter()
+                .map(|column| column.name().to_string())
+                .unique()
+                .collect_vec();
+
+            names.insert(name.clone());
+            checks.push((
+                CheckConstraint {
+                    name,
+                    expr: expr.to_string(),
+                    columns,
+                },
+                bound,
+            ));
+        }
+        Ok(checks)
+    }
+
+    /// Binds the `FOREIGN KEY`s declared on `table`, which are named after the column if they are
+    /// not given a name.
+    ///
+    /// Tips: only the foreign keys of one column with `RESTRICT` or `NO ACTION` are supported
+    pub(crate) fn bind_foreign_key_defs(
+        &self,
+        table: &TableCatalog,
+        foreign_key_defs: Vec<ForeignKeyDef>,
+    ) -> Result<Vec<ForeignKey>, DatabaseError> {
+        let mut names = table
+            .foreign_keys()
+            .map(|foreign_key| foreign_key.name.clone())
+            .collect::<HashSet<_>>();
+        let mut foreign_keys = Vec::with_capacity(foreign_key_defs.len());
+
+        for def in foreign_key_defs {
+            if let Some(action) = def.actions.iter().flatten().find(|action| {
+                !matches!(
+                    action,
+                    ReferentialAction::Restrict | ReferentialAction::NoAction
+                )
+            }) {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "foreign key action: {}",
+                    action
+                )));
+            }
+            let (column, ref_column) = match (def.columns, def.referred_columns) {
+                ([column], []) => (column, None),
+                ([column], [ref_column]) => (column, Some(ref_column)),
+                _ => {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "foreign key on multiple columns".to_string(),
+                    ))
+                }
+            };
+            let column_name = column.value.to_lowercase();
+            let column = table
+                .get_column_by_name(&column_name)
+                .ok_or(DatabaseError::NotFound("column", column_name))?;
+            let mut ref_table_name = self.bind_table_name(def.foreign_table)?;
+            // e.g. the parent of a row in the same temporary table
+            if matches!(def.foreign_table.0.as_slice(), [_])
+                && self.temp_schema.as_deref().is_some_and(|temp_schema| {
+                    temp_table_name(temp_schema, &ref_table_name) == table.name
+                })
+            {
+                ref_table_name = table.name.clone();
+            }
+            if is_temp_table(&ref_table_name) && !is_temp_table(&table.name) {
+                return Err(DatabaseError::InvalidForeignKey(format!(
+                    "permanent table {} cannot reference temporary table {}",
+                    table.name, ref_table_name
+                )));
+            }
+            // e.g. the parent of a row in the same table
+            let ref_table = if ref_table_name == table.name {
+                table
+            } else {
+                self.context
+                    .table(ref_table_name.clone())
+                    .ok_or(DatabaseError::TableNotFound)?
+            };
+            let ref_column = match ref_column {
+                Some(ref_column) => {
+                    let ref_column_name = ref_column.value.to_lowercase();
+
+                    ref_table
+                        .get_column_by_name(&ref_column_name)
+                        .ok_or(DatabaseError::NotFound("column", ref_column_name))?
+                }
+                None => match ref_table.primary_keys()?[..] {
+                    [(_, column)] => column,
+                    _ => {
+                        return Err(DatabaseError::UnsupportedStmt(
+                            "foreign key on multiple columns".to_string(),
+                        ))
+                    }
+                },
+            };
+            // Tips: the referenced keys are looked up by the primary key or the unique index, and a
+            // column of a composite primary key is not unique by itself
+            let is_primary_key =
+                ref_column.desc.is_primary() && ref_table.primary_keys()?.len() == 1;
+            if !is_primary_key
+                && !ref_column.desc.is_unique
+                && ref_table
+                    .get_unique_index(&ref_column.id().unwrap())
+                    .is_none()
+            {
+                return Err(DatabaseError::InvalidForeignKey(format!(
+                    "there is no unique constraint on column {} of table {}",
+                    ref_column.name(),
+                    ref_table_name
+                )));
+            }
+            if column.datatype() != ref_column.datatype() {
+                return Err(DatabaseError::InvalidForeignKey(format!(
+                    "column {} of type {} cannot reference column {} of type {}",
+                    column.name(),
+                    column.datatype(),
+                    ref_column.name(),
+                    ref_column.datatype()
+                )));
+            }
+            let name = constraint_name(
+                def.name,
+                format!("{}_{}_fkey", table.name, column.name()),
+                &names,
+            )
+            .map_err(|name| DatabaseError::InvalidForeignKey(name_exists(&name)))?;
+
+            names.insert(name.clone());
+            foreign_keys.push(ForeignKey {
+                name,
+                column: column.name().to_string(),
+                ref_table: ref_table_name,
+                ref_column: ref_column.name().to_string(),
+                deferred: def.deferred,
+            });
+        }
+        Ok(foreign_keys)
+    }
+
+    /// Binds the `CHECK` constraints kept by `table` to be evaluated on the rows written to it.
+    pub(crate) fn bind_checks(
+        &self,
+        table: &TableCatalog,
+    ) -> Result<Vec<(CheckConstraint, ScalarExpression)>, DatabaseError> {
+        table
+            .checks()
+            .map(|check| -> Result<_, DatabaseError> {
+                let expr = parse_expr(&check.expr)?;
+
+                Ok((check.clone(), self.bind_check(table, &check.name, &expr)?))
+            })
+            .try_collect()
+    }
+
+    /// Binds the expressions of the generated columns of `table` by the positions of the columns,
+    /// to be evaluated on the rows written to it.
+    pub(crate) fn bind_generated_columns(
+        &self,
+        table: &TableCatalog,
+    ) -> Result<Vec<(usize, ScalarExpression)>, DatabaseError> {
+        table
+            .columns()
+            .enumerate()
+            .filter_map(|(i, column)| Some((i, column, column.desc.generated.as_ref()?)))
+            .map(|(i, column, generated)| -> Result<_, DatabaseError> {
+                let expr = parse_expr(generated)?;
+
+                Ok((i, self.bind_generated_column(table, column, &expr)?))
+            })
+            .try_collect()
+    }
+
+    /// Binds the expression of a generated column against the other columns of `table`, which
+    /// cannot be generated columns themselves.
+    fn bind_generated_column(
+        &self,
+        table: &TableCatalog,
+        column: &ColumnCatalog,
+        expr: &Expr,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let not_allowed = |what: &str| {
+            DatabaseError::InvalidGenerated(format!(
+                "{} is not allowed in the expression of generated column `{}`",
+                what,
+                column.name()
+            ))
+        };
+        let mut bound = self
+            .bind_table_expr(table, expr)?
+            .ok_or_else(|| not_allowed("subquery"))?;
+
+        if bound.has_agg_call() {
+            return Err(not_allowed("aggregate function"));
+        }
+        if bound.has_volatile_call() {
+            return Err(not_allowed("volatile function"));
+        }
+        if let Some(generated) = bound.referenced_columns(true).iter().find(|referenced| {
+            table
+                .get_column_by_name(referenced.name())
+                .is_some_and(|referenced| referenced.desc.generated.is_some())
+        }) {
+            return Err(DatabaseError::InvalidGenerated(format!(
+                "generated column `{}` cannot reference generated column `{}`",
+                column.name(),
+                generated.name()
+            )));
+        }
+        if bound.return_type() != *column.datatype() {
+            bound = ScalarExpression::TypeCast {
+                expr: Box::new(bound),
+                ty: column.datatype().clone(),
+                is_try: false,
+            }
+        }
+        bound.bind_evaluator()?;
+
+        Ok(bound)
+    }
+
+    /// Binds `expr` against the columns of `table` alone, `None` if it has a subquery.
+    fn bind_table_expr(
+        &self,
+        table: &TableCatalog,
+        expr: &Expr,
+    ) -> Result<Option<ScalarExpression>, DatabaseError> {
+        if has_subquery(expr) {
+            return Ok(None);
+        }
+        let mut binder = Binder::new(
+            BinderContext::new(
+                self.context.table_cache,
+                self.context.transaction,
+                self.context.functions,
+                Arc::new(AtomicUsize::new(0)),
+            ),
+            None,
+        );
+        binder
+            .context
+            .bind_table
+            .insert((table.name.clone(), None, None), table);
+        let bound = binder.bind_expr(expr)?;
+
+        Ok(binder
+            .context
+            .sub_queries_at_now()
+            .is_none()
+            .then_some(bound))
+    }
+
+    /// Binds the predicate of a `CHECK` constraint against the columns of `table` alone.
+    fn bind_check(
+        &self,
+        table: &TableCatalog,
+        name: &str,
+        expr: &Expr,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let mut bound = self.bind_table_expr(table, expr)?.ok_or_else(|| {
+            DatabaseError::InvalidCheck(format!(
+                "subquery is not allowed in check constraint \"{}\"",
+                name
+            ))
+        })?;
+
+        if bound.has_agg_call() {
+            return Err(DatabaseError::InvalidCheck(format!(
+                "aggregate function is not allowed in check constraint \"{}\"",
+                name
+            )));
+        }
+        match bound.return_type() {
+            LogicalType::Boolean | LogicalType::SqlNull => (),
+            ty => {
+                return Err(DatabaseError::InvalidCheck(format!(
+                    "check constraint \"{}\" must be a boolean expression, not {}",
+                    name, ty
+                )))
+            }
+        }
+        bound.bind_evaluator()?;
+
+        Ok(bound)
+    }
+}
+
+/// A `FOREIGN KEY` declared on the columns of a table or on a column by `REFERENCES`.
+pub(crate) struct ForeignKeyDef<'c> {
+    name: Option<&'c Ident>,
+    columns: &'c [Ident],
+    foreign_table: &'c ObjectName,
+    referred_columns: &'c [Ident],
+    /// `ON DELETE` and `ON UPDATE`
+    actions: [Option<ReferentialAction>; 2],
+    deferred: bool,
+}
+
+/// The name given to the constraint, or else `default_name` followed by a number if it is taken,
+/// e.g. `t_c1_check`, `t_c1_check1`. The given name is returned as the error if it is taken.
+fn constraint_name(
+    name: Option<&Ident>,
+    default_name: String,
+    names: &HashSet<String>,
+) -> Result<String, String> {
+    match name {
+        Some(name) => {
+            let name = name.value.to_lowercase();
+
+            if names.contains(&name) {
+                Err(name)
+            } else {
+                Ok(name)
+            }
+        }
+        None => Ok((0..)
+            .map(|i| match i {
+                0 => default_name.clone(),
+                i => format!("{}{}", default_name, i),
+            })
+            .find(|name| !names.contains(name))
+            .unwrap()),
+    }
+}
+
+fn name_exists(name: &str) -> String {
+    format!("constraint \"{}\" already exists", name)
+}
+
+/// The `FOREIGN KEY` declared by the constraint of a table.
+pub(crate) fn table_foreign_key_def(constraint: &TableConstraint) -> Option<ForeignKeyDef> {
+    match constraint {
+        TableConstraint::ForeignKey {
+            name,
+            columns,
+            foreign_table,
+            referred_columns,
+            on_delete,
+            on_update,
+        } => Some(ForeignKeyDef {
+            name: name.as_ref(),
+            columns,
+            foreign_table,
+            referred_columns,
+            actions: [*on_delete, *on_update],
+            deferred: false,
+        }),
+        _ => None,
+    }
+}
+
+/// The `FOREIGN KEY`s declared on the column by `REFERENCES`.
+pub(crate) fn column_foreign_key_defs(column_def: &ColumnDef) -> Vec<ForeignKeyDef> {
+    column_def
+        .options
+        .iter()
+        .enumerate()
+        .filter_map(|(i, option_def)| match &option_def.option {
+            ColumnOption::ForeignKey {
+                foreign_table,
+                referred_columns,
+                on_delete,
+                on_update,
+            } => Some(ForeignKeyDef {
+                name: option_def.name.as_ref(),
+                columns: slice::from_ref(&column_def.name),
+                foreign_table,
+                referred_columns,
+                actions: [*on_delete, *on_update],
+                // e.g. `REFERENCES t1 (c1) DEFERRABLE INITIALLY DEFERRED`
+                deferred: column_def.options.get(i + 1).is_some_and(|option_def| {
+                    matches!(
+                        &option_def.option,
+                        ColumnOption::DialectSpecific(tokens) if tokens == &deferred_tokens()
+                    )
+                }),
+            }),
+            _ => None,
+        })
+        .collect_vec()
+}
+
+/// The `CHECK` constraints declared on the column, with the names generated for the unnamed ones.
+pub(crate) fn column_check_defs<'c>(
+    table_name: &str,
+    column_def: &'c ColumnDef,
+) -> Vec<(Option<&'c Ident>, String, &'c Expr)> {
+    let column_name = column_def.name.value.to_lowercase();
+
+    column_def
+        .options
+        .iter()
+        .filter_map(|option_def| match &option_def.option {
+            ColumnOption::Check(expr) => Some((
+                option_def.name.as_ref(),
+                format!("{}_{}_check", table_name, column_name),
+                expr,
+            )),
+            _ => None,
+        })
+        .collect_vec()
+}
+
+fn has_subquery(expr: &Expr) -> bool {
+    match expr {
+        Expr::Subquery(_) | Expr::Exists { .. } | Expr::InSubquery { .. } => true,
+        Expr::Nested(expr)
+        | Expr::UnaryOp { expr, .. }
+        | Expr::Cast { expr, .. }
+        | Expr::TryCast { expr, .. }
+        | Expr::IsNull(expr)
+        | Expr::IsNotNull(expr) => has_subquery(expr),
+        Expr::BinaryOp { left, right, .. } => has_subquery(left) || has_subquery(right),
+        Expr::Between {
+            expr, low, high, ..
+        } => has_subquery(expr) || has_subquery(low) || has_subquery(high),
+        Expr::InList { expr, list, .. } => has_subquery(expr) || list.iter().any(has_subquery),
+        Expr::Function(function) => function.args.iter().any(|arg| match arg {
+            FunctionArg::Named {
+                arg: FunctionArgExpr::Expr(expr),
+                ..
+            }
+            | FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => has_subquery(expr),
+            _ => false,
+        }),
+        Expr::Case {
+            operand,
+            conditions,
+            results,
+            else_result,
+        } => {
+            operand.as_deref().is_some_and(has_subquery)
+                || conditions.iter().any(has_subquery)
+                || results.iter().any(has_subquery)
+                || else_result.as_deref().is_some_and(has_subquery)
+        }
+        _ => false,
+    }
 }
 
 #[cfg(test)]
@@ -158,16 +889,14 @@ mod tests {
         let storage = RocksStorage::new(temp_dir.path())?;
         let transaction = storage.transaction()?;
         let table_cache = Arc::new(ShardingLruCache::new(4, 1, RandomState::new())?);
-        let scala_functions = Default::default();
-        let table_functions = Default::default();
+        let functions = Default::default();
 
         let sql = "create table t1 (id int primary key, name varchar(10) null)";
         let mut binder = Binder::new(
             BinderContext::new(
                 &table_cache,
                 &transaction,
-                &scala_functions,
-                &table_functions,
+                &functions,
                 Arc::new(AtomicUsize::new(0)),
             ),
             None,
@@ -0,0 +1,60 @@
+use crate::binder::{lower_case_name, lower_ident, Binder};
+use crate::catalog::View;
+use crate::errors::DatabaseError;
+use crate::planner::operator::create_view::CreateViewOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use itertools::Itertools;
+use sqlparser::ast::{Ident, ObjectName, Query};
+use std::sync::Arc;
+
+impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
+    pub(crate) fn bind_create_view(
+        &mut self,
+        name: &ObjectName,
+        columns: &[Ident],
+        query: &Query,
+        materialized: bool,
+        or_replace: bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let view_name = Arc::new(lower_case_name(name)?);
+        let column_names = columns.iter().map(lower_ident).collect_vec();
+        let mut plan = self.bind_query(query)?;
+
+        if materialized {
+            if or_replace {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "`CREATE OR REPLACE MATERIALIZED VIEW`".to_string(),
+                ));
+            }
+            return self.bind_create_table_as(
+                plan,
+                view_name,
+                &column_names,
+                Some(query.to_string()),
+                false,
+            );
+        }
+        // Tips: the query is only bound to be checked, the view is bound again wherever it is used
+        let columns_len = plan.output_schema().len();
+        if !column_names.is_empty() && column_names.len() != columns_len {
+            return Err(DatabaseError::MisMatch(
+                "the number of column names",
+                "the number of columns returned by the query",
+            ));
+        }
+
+        Ok(LogicalPlan::new(
+            Operator::CreateView(CreateViewOperator {
+                view: View {
+                    name: view_name,
+                    columns: column_names,
+                    query: query.to_string(),
+                },
+                or_replace,
+            }),
+            vec![],
+        ))
+    }
+}
@@ -1,51 +1,58 @@
-use crate::binder::{lower_case_name, Binder};
+use crate::binder::Binder;
 use crate::errors::DatabaseError;
 use crate::planner::operator::delete::DeleteOperator;
-use crate::planner::operator::table_scan::TableScanOperator;
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
 use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
-use sqlparser::ast::{Expr, TableAlias, TableFactor, TableWithJoins};
-use std::sync::Arc;
+use itertools::Itertools;
+use sqlparser::ast::{Expr, TableFactor, TableWithJoins};
 
 impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
     pub(crate) fn bind_delete(
         &mut self,
-        from: &TableWithJoins,
+        from: &[TableWithJoins],
+        using: &Option<Vec<TableWithJoins>>,
         selection: &Option<Expr>,
     ) -> Result<LogicalPlan, DatabaseError> {
-        if let TableFactor::Table { name, alias, .. } = &from.relation {
-            let table_name = Arc::new(lower_case_name(name)?);
-            let mut table_alias = None;
-            let mut alias_idents = None;
+        if let TableFactor::Table { name, .. } = &from[0].relation {
+            let table_name = self.bind_table_name(name)?;
 
-            if let Some(TableAlias { name, columns }) = alias {
-                table_alias = Some(Arc::new(name.value.to_lowercase()));
-                alias_idents = Some(columns);
-            }
-            let table_catalog =
-                self.context
-                    .table_and_bind(table_name.clone(), table_alias.clone(), None)?;
-            let primary_key_column = table_catalog
-                .columns()
-                .find(|column| column.desc.is_primary)
-                .cloned()
-                .unwrap();
-            let mut plan = TableScanOperator::build(table_name.clone(), table_catalog);
+            let mut plan = self.bind_table_ref(&from[0])?;
+            let primary_keys = self
+                .context
+                .table(table_name.clone())
+                .ok_or(DatabaseError::TableNotFound)?
+                .primary_keys()?
+                .into_iter()
+                .map(|(_, column)| column.clone())
+                .collect_vec();
 
-            if let Some(alias_idents) = alias_idents {
-                plan =
-                    self.bind_alias(plan, alias_idents, table_alias.unwrap(), table_name.clone())?;
-            }
+            // e.g. `DELETE FROM t1 USING t2 WHERE t1.c1 = t2.c3`
+            let joined_tables = from[1..].iter().chain(using.iter().flatten());
+            let is_joined = !from[0].joins.is_empty() || joined_tables.clone().next().is_some();
 
+            for table in joined_tables {
+                plan = JoinOperator::build(
+                    plan,
+                    self.bind_table_ref(table)?,
+                    JoinCondition::None,
+                    JoinType::Cross,
+                );
+            }
             if let Some(predicate) = selection {
                 plan = self.bind_where(plan, predicate)?;
             }
+            if is_joined {
+                // only the rows of the table deleted from
+                let exprs = self.bind_table_columns(&mut plan, table_name.clone())?;
+                plan = self.bind_project(plan, exprs)?;
+            }
 
             Ok(LogicalPlan::new(
                 Operator::Delete(DeleteOperator {
                     table_name,
-                    primary_key_column,
+                    primary_keys,
                 }),
                 vec![plan],
             ))
@@ -1,18 +1,17 @@
-use crate::binder::{lower_case_name, Binder};
+use crate::binder::Binder;
 use crate::errors::DatabaseError;
 use crate::planner::operator::describe::DescribeOperator;
 use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
 use sqlparser::ast::ObjectName;
-use std::sync::Arc;
 
 impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
     pub(crate) fn bind_describe(
         &mut self,
         name: &ObjectName,
     ) -> Result<LogicalPlan, DatabaseError> {
-        let table_name = Arc::new(lower_case_name(name)?);
+        let table_name = self.bind_table_name(name)?;
 
         Ok(LogicalPlan::new(
             Operator::Describe(DescribeOperator { table_name }),
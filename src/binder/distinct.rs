@@ -1,8 +1,12 @@
 use crate::binder::{Binder, QueryBindStep};
+use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
 use crate::planner::operator::aggregate::AggregateOperator;
+use crate::planner::operator::distinct_on::DistinctOnOperator;
+use crate::planner::operator::sort::SortField;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
+use itertools::Itertools;
 
 impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
     pub fn bind_distinct(
@@ -14,4 +18,47 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
 
         AggregateOperator::build(children, vec![], select_list, true)
     }
+
+    /// `DISTINCT ON (on_exprs)` keeps the first row of each group in the order of `orderby`,
+    /// which must start with `on_exprs` in any order, the rest of `on_exprs` is sorted ascending.
+    pub fn bind_distinct_on(
+        &mut self,
+        children: LogicalPlan,
+        on_exprs: Vec<ScalarExpression>,
+        orderby: Option<Vec<SortField>>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        self.context.step(QueryBindStep::Distinct);
+
+        let is_same = |a: &ScalarExpression, b: &ScalarExpression| {
+            a.unpack_alias_ref() == b.unpack_alias_ref()
+        };
+        let mut sort_fields = orderby.unwrap_or_default();
+        let mut unsorted = on_exprs.iter().collect_vec();
+        let mut leading_len = 0;
+
+        for field in sort_fields.iter() {
+            if unsorted.is_empty() {
+                break;
+            }
+            if !on_exprs.iter().any(|on_expr| is_same(on_expr, &field.expr)) {
+                return Err(DatabaseError::MisMatch(
+                    "the expressions of DISTINCT ON",
+                    "the leading expressions of ORDER BY",
+                ));
+            }
+            unsorted.retain(|on_expr| !is_same(on_expr, &field.expr));
+            leading_len += 1;
+        }
+        // e.g. without `ORDER BY`
+        for on_expr in unsorted {
+            sort_fields.insert(
+                leading_len,
+                SortField::new(on_expr.clone(), true, self.nulls_first),
+            );
+            leading_len += 1;
+        }
+        let plan = self.bind_sort(children, sort_fields);
+
+        Ok(DistinctOnOperator::build(plan, on_exprs))
+    }
 }
@@ -0,0 +1,29 @@
+use crate::binder::{lower_case_name, Binder};
+use crate::errors::DatabaseError;
+use crate::planner::operator::drop_view::DropViewOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use sqlparser::ast::ObjectName;
+use std::sync::Arc;
+
+impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
+    pub(crate) fn bind_drop_view(
+        &mut self,
+        names: &[ObjectName],
+        if_exists: &bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let view_names = names
+            .iter()
+            .map(|name| Ok(Arc::new(lower_case_name(name)?)))
+            .collect::<Result<Vec<_>, DatabaseError>>()?;
+
+        Ok(LogicalPlan::new(
+            Operator::DropView(DropViewOperator {
+                view_names,
+                if_exists: *if_exists,
+            }),
+            vec![],
+        ))
+    }
+}
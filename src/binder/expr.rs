@@ -4,21 +4,34 @@ use crate::expression;
 use crate::expression::agg::AggKind;
 use itertools::Itertools;
 use sqlparser::ast::{
-    BinaryOperator, CharLengthUnits, DataType, Expr, Function, FunctionArg, FunctionArgExpr, Ident,
-    Query, UnaryOperator,
+    BinaryOperator, CharLengthUnits, DataType, DateTimeField, Expr, Function, FunctionArg,
+    FunctionArgExpr, Ident, Interval as SqlInterval, JsonOperator, Query, UnaryOperator, Value,
+    WindowType,
 };
-use std::slice;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::{mem, slice};
 
-use super::{lower_ident, Binder, BinderContext, QueryBindStep, SubQueryType};
-use crate::expression::function::scala::ScalarFunction;
+use super::{lower_ident, qualified_name, Binder, BinderContext, QueryBindStep, SubQueryType};
+use crate::expression::function::aggregate::AggregateFunction;
+use crate::expression::function::scala::{ScalarFunction, ScalarFunctionImpl};
 use crate::expression::function::table::TableFunction;
 use crate::expression::function::FunctionSummary;
-use crate::expression::{AliasType, ScalarExpression};
+use crate::expression::{AliasType, Collation, ScalarExpression};
+use crate::function::array::{ArrayCompare, ArraySubscript, Unnest};
+use crate::function::extract::{Extract, ExtractField};
+use crate::function::json::{JsonExtract, JsonExtractKind, JSON_EXTRACT};
+use crate::function::math::{MathFunction, MathFunctionKind};
+use crate::function::regexp::{REGEXP_MATCHES, REGEXP_REPLACE};
+use crate::parser::{AGGREGATE_FILTER, MARKER_QUOTE};
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
+use crate::types::bytea;
+use crate::types::evaluator::utf8::{check_like_pattern, check_regex};
+use crate::types::evaluator::EvaluatorFactory;
+use crate::types::interval::Interval;
 use crate::types::value::{DataValue, Utf8Type};
-use crate::types::LogicalType;
+use crate::types::{ArrayType, LogicalType, StructType};
 
 macro_rules! try_alias {
     ($context:expr, $full_name:expr) => {
@@ -45,8 +58,50 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             Expr::Identifier(ident) => {
                 self.bind_column_ref_from_identifiers(slice::from_ref(ident), None)
             }
-            Expr::CompoundIdentifier(idents) => self.bind_column_ref_from_identifiers(idents, None),
+            Expr::CompoundIdentifier(idents) => self.bind_compound_identifier(idents),
+            Expr::Collate { expr, collation } => {
+                let expr = self.bind_expr(expr)?;
+                let name = collation
+                    .0
+                    .iter()
+                    .map(|ident| ident.value.as_str())
+                    .join(".");
+                let collation = Collation::from_name(&name)
+                    .ok_or_else(|| DatabaseError::NotFound("collation", name))?;
+
+                if !matches!(
+                    expr.return_type(),
+                    LogicalType::Varchar(..) | LogicalType::Char(..) | LogicalType::SqlNull
+                ) {
+                    return Err(DatabaseError::InvalidType);
+                }
+                Ok(ScalarExpression::Collate {
+                    expr: Box::new(expr),
+                    collation,
+                })
+            }
+            Expr::CompositeAccess { expr, key } => {
+                let expr = self.bind_expr(expr)?;
+
+                Self::bind_field_access(expr, key)?
+                    .ok_or_else(|| DatabaseError::InvalidColumn(key.value.clone()))
+            }
             Expr::BinaryOp { left, right, op } => self.bind_binary_op_internal(left, right, op),
+            Expr::Value(Value::HexStringLiteral(digits)) => {
+                let bytes = bytea::parse_hex(digits)
+                    .ok_or_else(|| DatabaseError::InvalidBytea(format!("X'{}'", digits)))?;
+
+                Ok(ScalarExpression::Constant(Arc::new(DataValue::Bytea(
+                    Some(bytes),
+                ))))
+            }
+            // e.g. `$1`, bound to the first of the parameters
+            Expr::Value(Value::Placeholder(name)) => name
+                .strip_prefix('$')
+                .and_then(|position| position.parse::<usize>().ok())
+                .and_then(|position| self.parameters.get(position.checked_sub(1)?))
+                .map(|value| ScalarExpression::Constant(Arc::new(value.clone())))
+                .ok_or_else(|| DatabaseError::NotFound("parameter", name.clone())),
             Expr::Value(v) => Ok(ScalarExpression::Constant(Arc::new(v.into()))),
             Expr::Function(func) => self.bind_function(func),
             Expr::Nested(expr) => self.bind_expr(expr),
@@ -56,7 +111,13 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 expr,
                 pattern,
                 escape_char,
-            } => self.bind_like(*negated, expr, pattern, escape_char),
+            } => self.bind_like(*negated, false, expr, pattern, escape_char),
+            Expr::ILike {
+                negated,
+                expr,
+                pattern,
+                escape_char,
+            } => self.bind_like(*negated, true, expr, pattern, escape_char),
             Expr::IsNull(expr) => self.bind_is_null(expr, false),
             Expr::IsNotNull(expr) => self.bind_is_null(expr, true),
             Expr::InList {
@@ -66,7 +127,28 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             } => self.bind_is_in(expr, list, *negated),
             Expr::Cast {
                 expr, data_type, ..
-            } => self.bind_cast(expr, data_type),
+            } => self.bind_cast(expr, data_type, false),
+            Expr::TryCast {
+                expr, data_type, ..
+            } => self.bind_cast(expr, data_type, true),
+            Expr::Extract { field, expr } => self.bind_extract(field, expr),
+            // `CEIL(x TO field)` of the temporal types is not supported
+            Expr::Ceil { expr: arg, field } | Expr::Floor { expr: arg, field }
+                if matches!(field, DateTimeField::NoDateTime) =>
+            {
+                let kind = if let Expr::Ceil { .. } = expr {
+                    MathFunctionKind::Ceil
+                } else {
+                    MathFunctionKind::Floor
+                };
+                MathFunction::bind(kind, vec![self.bind_expr(arg)?])
+            }
+            Expr::Interval(_) => self.bind_interval(expr),
+            Expr::JsonAccess {
+                left,
+                operator,
+                right,
+            } => self.bind_json_access(left, operator, right),
             Expr::TypedString { data_type, value } => {
                 let logical_type = LogicalType::try_from(data_type.clone())?;
                 let value = DataValue::Utf8 {
@@ -83,12 +165,46 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 negated,
                 low,
                 high,
-            } => Ok(ScalarExpression::Between {
-                negated: *negated,
-                expr: Box::new(self.bind_expr(expr)?),
-                left_expr: Box::new(self.bind_expr(low)?),
-                right_expr: Box::new(self.bind_expr(high)?),
-            }),
+            } => {
+                let mut exprs = [
+                    self.bind_expr(expr)?,
+                    self.bind_expr(low)?,
+                    self.bind_expr(high)?,
+                ];
+                // Tips: the operands are compared with each other like `>=` and `<=`, so that
+                // e.g. `uuid_column BETWEEN '...' AND '...'` parses the strings
+                let mut ty = LogicalType::SqlNull;
+                for expr in exprs.iter() {
+                    ty = binary_operand_type(
+                        &expression::BinaryOperator::GtEq,
+                        &ty,
+                        &expr.return_type(),
+                    )?;
+                }
+                for expr in exprs.iter_mut() {
+                    if expr.return_type() == ty {
+                        continue;
+                    }
+                    *expr = match expr.unpack_val() {
+                        Some(value) => ScalarExpression::Constant(Arc::new(
+                            DataValue::clone(&value).cast(&ty)?,
+                        )),
+                        None => ScalarExpression::TypeCast {
+                            expr: Box::new(mem::replace(expr, ScalarExpression::Empty)),
+                            ty: ty.clone(),
+                            is_try: false,
+                        },
+                    };
+                }
+                let [expr, left_expr, right_expr] = exprs;
+
+                Ok(ScalarExpression::Between {
+                    negated: *negated,
+                    expr: Box::new(expr),
+                    left_expr: Box::new(left_expr),
+                    right_expr: Box::new(right_expr),
+                })
+            }
             Expr::Substring {
                 expr,
                 substring_for,
@@ -131,13 +247,14 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 })
             }
             Expr::Subquery(subquery) => {
-                let (sub_query, column) = self.bind_subquery(subquery)?;
+                let (sub_query, column, correlated_columns) = self.bind_subquery(subquery)?;
                 let (expr, sub_query) = if !self.context.is_step(&QueryBindStep::Where) {
                     self.bind_temp_table(column, sub_query)?
                 } else {
                     (ScalarExpression::ColumnRef(column), sub_query)
                 };
-                self.context.sub_query(SubQueryType::SubQuery(sub_query));
+                self.context
+                    .sub_query(SubQueryType::SubQuery(sub_query, correlated_columns));
                 Ok(expr)
             }
             Expr::InSubquery {
@@ -146,7 +263,7 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 negated,
             } => {
                 let left_expr = Box::new(self.bind_expr(expr)?);
-                let (sub_query, column) = self.bind_subquery(subquery)?;
+                let (sub_query, column, correlated_columns) = self.bind_subquery(subquery)?;
 
                 if !self.context.is_step(&QueryBindStep::Where) {
                     return Err(DatabaseError::UnsupportedStmt(
@@ -155,8 +272,11 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 }
 
                 let (alias_expr, sub_query) = self.bind_temp_table(column, sub_query)?;
-                self.context
-                    .sub_query(SubQueryType::InSubQuery(*negated, sub_query));
+                self.context.sub_query(SubQueryType::InSubQuery(
+                    *negated,
+                    sub_query,
+                    correlated_columns,
+                ));
 
                 Ok(ScalarExpression::Binary {
                     op: expression::BinaryOperator::Eq,
@@ -166,6 +286,32 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                     ty: LogicalType::Boolean,
                 })
             }
+            Expr::Exists { subquery, negated } => {
+                if !self.context.is_step(&QueryBindStep::Where) {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "'EXISTS (SUBQUERY)' can only appear in `WHERE`".to_string(),
+                    ));
+                }
+                let (sub_query, correlated_columns) = self.bind_correlated_subquery(subquery)?;
+                self.context.sub_query(SubQueryType::ExistsSubQuery(
+                    *negated,
+                    sub_query,
+                    correlated_columns,
+                ));
+
+                Ok(ScalarExpression::Constant(Arc::new(DataValue::Boolean(
+                    Some(true),
+                ))))
+            }
+            Expr::Array(array) => self.bind_array(&array.elem),
+            Expr::ArrayIndex { obj, indexes } => {
+                let mut expr = self.bind_expr(obj)?;
+
+                for index in indexes {
+                    expr = ArraySubscript::bind(expr, self.bind_expr(index)?)?;
+                }
+                Ok(expr)
+            }
             Expr::Tuple(exprs) => {
                 let mut bond_exprs = Vec::with_capacity(exprs.len());
 
@@ -185,7 +331,7 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                         if ty == &LogicalType::SqlNull {
                             *ty = result_ty;
                         } else if ty != &result_ty {
-                            return Err(DatabaseError::Incomparable(*ty, result_ty));
+                            return Err(DatabaseError::Incomparable(ty.clone(), result_ty));
                         }
                     }
 
@@ -225,6 +371,42 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
         }
     }
 
+    /// `ARRAY[...]`, whose elements are cast to the type they are compared as with each other.
+    fn bind_array(&mut self, elems: &[Expr]) -> Result<ScalarExpression, DatabaseError> {
+        let mut exprs = Vec::with_capacity(elems.len());
+        let mut ty = LogicalType::SqlNull;
+
+        for elem in elems {
+            let expr = self.bind_expr(elem)?;
+
+            ty = binary_operand_type(&expression::BinaryOperator::Eq, &ty, &expr.return_type())?;
+            exprs.push(expr);
+        }
+        if matches!(ty, LogicalType::Array(_) | LogicalType::Tuple) {
+            return Err(DatabaseError::UnsupportedStmt(
+                "multidimensional arrays".to_string(),
+            ));
+        }
+        let ty = ArrayType::new(ty);
+        // Tips: the empty tuple is the NULL tuple
+        if exprs.is_empty() {
+            return Ok(ScalarExpression::Constant(Arc::new(DataValue::Array {
+                values: Some(vec![]),
+                ty,
+            })));
+        }
+        let expr = ScalarExpression::TypeCast {
+            expr: Box::new(ScalarExpression::Tuple(exprs)),
+            ty: LogicalType::Array(ty),
+            is_try: false,
+        };
+
+        Ok(match expr.unpack_val() {
+            Some(value) => ScalarExpression::Constant(value),
+            None => expr,
+        })
+    }
+
     fn bind_temp_table(
         &mut self,
         column: ColumnRef,
@@ -246,26 +428,8 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
     fn bind_subquery(
         &mut self,
         subquery: &Query,
-    ) -> Result<(LogicalPlan, Arc<ColumnCatalog>), DatabaseError> {
-        let BinderContext {
-            table_cache,
-            transaction,
-            scala_functions,
-            table_functions,
-            temp_table_id,
-            ..
-        } = &self.context;
-        let mut binder = Binder::new(
-            BinderContext::new(
-                table_cache,
-                *transaction,
-                scala_functions,
-                table_functions,
-                temp_table_id.clone(),
-            ),
-            Some(self),
-        );
-        let mut sub_query = binder.bind_query(subquery)?;
+    ) -> Result<(LogicalPlan, Arc<ColumnCatalog>, Vec<ColumnRef>), DatabaseError> {
+        let (mut sub_query, correlated_columns) = self.bind_correlated_subquery(subquery)?;
         let sub_query_schema = sub_query.output_schema();
 
         if sub_query_schema.len() != 1 {
@@ -275,22 +439,59 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             ));
         }
         let column = sub_query_schema[0].clone();
-        Ok((sub_query, column))
+        Ok((sub_query, column, correlated_columns))
     }
 
+    /// Binds the subquery and returns it with the columns of the outer queries it references.
+    fn bind_correlated_subquery(
+        &mut self,
+        subquery: &Query,
+    ) -> Result<(LogicalPlan, Vec<ColumnRef>), DatabaseError> {
+        let mut binder = Binder::new(self.context.sub_context(), Some(self));
+        let sub_query = binder.bind_query(subquery)?;
+        let correlated_columns = binder.context.correlated_columns;
+        self.context
+            .resolved_tables
+            .extend(binder.context.resolved_tables);
+
+        // columns that belong to neither this query nor the subquery come from a query further out
+        if self.parent.is_some() {
+            for column in correlated_columns.iter() {
+                if let Some(table_name) = column.table_name() {
+                    if self.context.bind_table(table_name, None).is_err() {
+                        self.context.add_correlated_column(column.clone());
+                    }
+                }
+            }
+        }
+        Ok((sub_query, correlated_columns))
+    }
+
+    /// `[NOT] LIKE`, or `[NOT] ILIKE` which matches case-insensitively if `case_insensitive`.
     pub fn bind_like(
         &mut self,
         negated: bool,
+        case_insensitive: bool,
         expr: &Expr,
         pattern: &Expr,
         escape_char: &Option<char>,
     ) -> Result<ScalarExpression, DatabaseError> {
         let left_expr = Box::new(self.bind_expr(expr)?);
         let right_expr = Box::new(self.bind_expr(pattern)?);
-        let op = if negated {
-            expression::BinaryOperator::NotLike(*escape_char)
-        } else {
-            expression::BinaryOperator::Like(*escape_char)
+        if let ScalarExpression::Constant(value) = right_expr.as_ref() {
+            if let DataValue::Utf8 {
+                value: Some(pattern),
+                ..
+            } = value.as_ref()
+            {
+                check_like_pattern(pattern, *escape_char)?;
+            }
+        }
+        let op = match (negated, case_insensitive) {
+            (false, false) => expression::BinaryOperator::Like(*escape_char),
+            (true, false) => expression::BinaryOperator::NotLike(*escape_char),
+            (false, true) => expression::BinaryOperator::ILike(*escape_char),
+            (true, true) => expression::BinaryOperator::NotILike(*escape_char),
         };
         Ok(ScalarExpression::Binary {
             op,
@@ -309,6 +510,7 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
         let full_name = match idents {
             [column] => (None, lower_ident(column)),
             [table, column] => (Some(lower_ident(table)), lower_ident(column)),
+            [schema, table, column] => (Some(qualified_name(schema, table)), lower_ident(column)),
             _ => {
                 return Err(DatabaseError::InvalidColumn(
                     idents
@@ -328,8 +530,12 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
 
             let column_catalog = table_catalog
                 .get_column_by_name(&full_name.1)
-                .ok_or_else(|| DatabaseError::NotFound("column", full_name.1))?;
-            Ok(ScalarExpression::ColumnRef(column_catalog.clone()))
+                .ok_or_else(|| DatabaseError::NotFound("column", full_name.1))?
+                .clone();
+            if self.parent.is_some() && self.context.bind_table(&table, None).is_err() {
+                self.context.add_correlated_column(column_catalog.clone());
+            }
+            Ok(ScalarExpression::ColumnRef(column_catalog))
         } else {
             let op = |got_column: &mut Option<ScalarExpression>, context: &BinderContext<'a, T>| {
                 for ((_, alias, _), table_catalog) in context.bind_table.iter() {
@@ -360,36 +566,141 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             let mut got_column = None;
 
             op(&mut got_column, &self.context);
-            if let Some(parent) = self.parent {
-                op(&mut got_column, &parent.context);
+            if got_column.is_none() {
+                // e.g. a subquery in `FROM` of a subquery, which sees the queries further out
+                let mut parent = self.parent;
+
+                while let Some(binder) = parent.filter(|_| got_column.is_none()) {
+                    op(&mut got_column, &binder.context);
+                    parent = binder.parent;
+                }
+                if let Some(ScalarExpression::ColumnRef(column)) = &got_column {
+                    self.context.add_correlated_column(column.clone());
+                }
+            }
+            // handle the column alias list of a derived table, e.g. `(SELECT ...) AS sub(x, y)`
+            if got_column.is_none() {
+                let mut exprs = self.context.expr_aliases.iter().filter_map(
+                    |((alias_table, alias_column), expr)| {
+                        alias_table
+                            .as_ref()
+                            .filter(|table_name| {
+                                alias_column == &full_name.1
+                                    && self.context.table_aliases.contains_key(*table_name)
+                            })
+                            .map(|_| expr)
+                    },
+                );
+                if let Some(expr) = exprs.next() {
+                    // e.g. `x` of `(SELECT ...) AS a(x) JOIN (SELECT ...) AS b(x)`, unless
+                    // the join is `USING (x)`, which makes both the same column
+                    if exprs.next().is_some() && !self.context.using.contains(&full_name.1) {
+                        return Err(DatabaseError::AmbiguousColumn(full_name.1));
+                    }
+                    got_column = Some(ScalarExpression::Alias {
+                        expr: Box::new(expr.clone()),
+                        alias: AliasType::Name(full_name.1.clone()),
+                    });
+                }
             }
             Ok(got_column.ok_or_else(|| DatabaseError::NotFound("column", full_name.1))?)
         }
     }
 
+    /// `table.column`, `schema.table.column`, or the fields of a struct column: `column.field`,
+    /// `table.column.field`
+    fn bind_compound_identifier(
+        &mut self,
+        idents: &[Ident],
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let err = match self.bind_column_ref_from_identifiers(idents, None) {
+            Ok(expr) => return Ok(expr),
+            Err(err) => err,
+        };
+        for column_len in (1..idents.len().min(3)).rev() {
+            let Ok(mut expr) = self.bind_column_ref_from_identifiers(&idents[..column_len], None)
+            else {
+                continue;
+            };
+            for field in &idents[column_len..] {
+                match Self::bind_field_access(expr, field)? {
+                    Some(field_expr) => expr = field_expr,
+                    None => return Err(err),
+                }
+            }
+            return Ok(expr);
+        }
+        Err(err)
+    }
+
+    /// Returns `None` if `expr` is not a struct.
+    fn bind_field_access(
+        expr: ScalarExpression,
+        field: &Ident,
+    ) -> Result<Option<ScalarExpression>, DatabaseError> {
+        let LogicalType::Struct(struct_type) = expr.return_type() else {
+            return Ok(None);
+        };
+        let field_name = lower_ident(field);
+        let (index, ty) = struct_type
+            .field(&field_name)
+            .ok_or(DatabaseError::NotFound("field", field_name))?;
+
+        Ok(Some(ScalarExpression::FieldAccess {
+            expr: Box::new(expr),
+            index,
+            ty: ty.clone(),
+        }))
+    }
+
     fn bind_binary_op_internal(
         &mut self,
         left: &Expr,
         right: &Expr,
         op: &BinaryOperator,
     ) -> Result<ScalarExpression, DatabaseError> {
-        let left_expr = Box::new(self.bind_expr(left)?);
-        let right_expr = Box::new(self.bind_expr(right)?);
+        // e.g. `'a' = ANY(tags)`
+        if let Expr::AnyOp(array) | Expr::AllOp(array) = right {
+            return ArrayCompare::bind(
+                op.clone().into(),
+                matches!(right, Expr::AllOp(_)),
+                self.bind_expr(left)?,
+                self.bind_expr(array)?,
+            );
+        }
+        let mut left_expr = Box::new(self.bind_expr(left)?);
+        let mut right_expr = Box::new(self.bind_expr(right)?);
 
         let ty = match op {
             BinaryOperator::Plus
             | BinaryOperator::Minus
             | BinaryOperator::Multiply
             | BinaryOperator::Modulo => {
-                LogicalType::max_logical_type(&left_expr.return_type(), &right_expr.return_type())?
+                let (left_ty, right_ty) = (left_expr.return_type(), right_expr.return_type());
+
+                if let Some((ty, _)) = EvaluatorFactory::interval_binary_create(
+                    &op.clone().into(),
+                    &left_ty,
+                    &right_ty,
+                ) {
+                    ty
+                } else if let Some(ty) =
+                    LogicalType::decimal_arithmetic_type(&op.clone().into(), &left_ty, &right_ty)
+                {
+                    ty
+                } else {
+                    LogicalType::max_logical_type(&left_ty, &right_ty)?
+                }
             }
             BinaryOperator::Divide => {
-                if let LogicalType::Decimal(precision, scale) = LogicalType::max_logical_type(
-                    &left_expr.return_type(),
-                    &right_expr.return_type(),
-                )? {
-                    LogicalType::Decimal(precision, scale)
+                let (left_ty, right_ty) = (left_expr.return_type(), right_expr.return_type());
+
+                if let Some(ty) =
+                    LogicalType::decimal_arithmetic_type(&op.clone().into(), &left_ty, &right_ty)
+                {
+                    ty
                 } else {
+                    let _ = LogicalType::max_logical_type(&left_ty, &right_ty)?;
                     LogicalType::Double
                 }
             }
@@ -398,10 +709,37 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             | BinaryOperator::GtEq
             | BinaryOperator::LtEq
             | BinaryOperator::Eq
-            | BinaryOperator::NotEq
-            | BinaryOperator::And
-            | BinaryOperator::Or
-            | BinaryOperator::Xor => LogicalType::Boolean,
+            | BinaryOperator::NotEq => {
+                let _ = binary_operand_type(
+                    &op.clone().into(),
+                    &left_expr.return_type(),
+                    &right_expr.return_type(),
+                )?;
+                Self::unify_collation(&mut left_expr, &mut right_expr)?;
+                LogicalType::Boolean
+            }
+            BinaryOperator::And | BinaryOperator::Or | BinaryOperator::Xor => LogicalType::Boolean,
+            BinaryOperator::PGRegexMatch
+            | BinaryOperator::PGRegexNotMatch
+            | BinaryOperator::PGRegexIMatch
+            | BinaryOperator::PGRegexNotIMatch => {
+                if let ScalarExpression::Constant(value) = right_expr.as_ref() {
+                    if let DataValue::Utf8 {
+                        value: Some(pattern),
+                        ..
+                    } = value.as_ref()
+                    {
+                        check_regex(
+                            pattern,
+                            matches!(
+                                op,
+                                BinaryOperator::PGRegexIMatch | BinaryOperator::PGRegexNotIMatch
+                            ),
+                        )?;
+                    }
+                }
+                LogicalType::Boolean
+            }
             BinaryOperator::StringConcat => LogicalType::Varchar(None, CharLengthUnits::Characters),
             op => return Err(DatabaseError::UnsupportedStmt(format!("{}", op))),
         };
@@ -415,6 +753,42 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
         })
     }
 
+    /// An explicit collation on either side of a comparison applies to both sides.
+    fn unify_collation(
+        left_expr: &mut ScalarExpression,
+        right_expr: &mut ScalarExpression,
+    ) -> Result<(), DatabaseError> {
+        let fn_collate = |expr: &mut ScalarExpression, collation: Collation| {
+            *expr = ScalarExpression::Collate {
+                expr: Box::new(mem::replace(expr, ScalarExpression::Empty)),
+                collation,
+            }
+        };
+        match (&*left_expr, &*right_expr) {
+            (
+                ScalarExpression::Collate {
+                    collation: left_collation,
+                    ..
+                },
+                ScalarExpression::Collate {
+                    collation: right_collation,
+                    ..
+                },
+            ) => {
+                if left_collation != right_collation {
+                    return Err(DatabaseError::MisMatch(
+                        "collation of the left operand",
+                        "collation of the right operand",
+                    ));
+                }
+            }
+            (ScalarExpression::Collate { collation, .. }, _) => fn_collate(right_expr, *collation),
+            (_, ScalarExpression::Collate { collation, .. }) => fn_collate(left_expr, *collation),
+            _ => (),
+        }
+        Ok(())
+    }
+
     fn bind_unary_op_internal(
         &mut self,
         expr: &Expr,
@@ -435,15 +809,61 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
         })
     }
 
-    fn bind_function(&mut self, func: &Function) -> Result<ScalarExpression, DatabaseError> {
-        if !matches!(self.context.step_now(), QueryBindStep::From) {
-            return Err(DatabaseError::UnsupportedStmt(
-                "`TableFunction` cannot bind in non-From step".to_string(),
-            ));
+    pub(crate) fn bind_function(
+        &mut self,
+        func: &Function,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        match &func.over {
+            Some(WindowType::WindowSpec(window_spec)) => {
+                return self.bind_window_function(func, window_spec);
+            }
+            Some(WindowType::NamedWindow(name)) => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "named window: {}",
+                    name
+                )));
+            }
+            None => (),
+        }
+        let window_calls_len = self.context.window_calls.len();
+        let mut expr = self._bind_function(func)?;
+
+        if let Some(predicate) = func.args.iter().find_map(aggregate_filter) {
+            let ScalarExpression::AggCall { filter, .. } = &mut expr else {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "FILTER on the non-aggregate function {}",
+                    func.name
+                )));
+            };
+            let predicate = self.bind_expr(predicate)?;
+
+            if predicate.has_agg_call() {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "aggregate function in FILTER: {}",
+                    predicate
+                )));
+            }
+            *filter = Some(Box::new(predicate));
+        }
+        // Tips: the aggregation is computed before the window functions
+        if matches!(expr, ScalarExpression::AggCall { .. })
+            && self.context.window_calls.len() > window_calls_len
+        {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "window function in aggregate function {}",
+                func
+            )));
         }
+        Ok(expr)
+    }
+
+    fn _bind_function(&mut self, func: &Function) -> Result<ScalarExpression, DatabaseError> {
         let mut args = Vec::with_capacity(func.args.len());
 
         for arg in func.args.iter() {
+            if aggregate_filter(arg).is_some() {
+                continue;
+            }
             let arg_expr = match arg {
                 FunctionArg::Named { arg, .. } => arg,
                 FunctionArg::Unnamed(arg) => arg,
@@ -451,7 +871,12 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             match arg_expr {
                 FunctionArgExpr::Expr(expr) => args.push(self.bind_expr(expr)?),
                 FunctionArgExpr::Wildcard => args.push(Self::wildcard_expr()),
-                _ => todo!(),
+                FunctionArgExpr::QualifiedWildcard(table_name) => {
+                    return Err(DatabaseError::UnsupportedStmt(format!(
+                        "qualified wildcard `{}.*` in the arguments of {}",
+                        table_name, func.name
+                    )))
+                }
             }
         }
         let function_name = func.name.to_string().to_lowercase();
@@ -465,6 +890,7 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                     distinct: func.distinct,
                     kind: AggKind::Count,
                     args,
+                    filter: None,
                     ty: LogicalType::Integer,
                 });
             }
@@ -472,12 +898,13 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 if args.len() != 1 {
                     return Err(DatabaseError::MisMatch("number of sum() parameters", "1"));
                 }
-                let ty = args[0].return_type();
+                let ty = AggKind::Sum.return_type(args[0].return_type());
 
                 return Ok(ScalarExpression::AggCall {
                     distinct: func.distinct,
                     kind: AggKind::Sum,
                     args,
+                    filter: None,
                     ty,
                 });
             }
@@ -491,6 +918,7 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                     distinct: func.distinct,
                     kind: AggKind::Min,
                     args,
+                    filter: None,
                     ty,
                 });
             }
@@ -504,6 +932,7 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                     distinct: func.distinct,
                     kind: AggKind::Max,
                     args,
+                    filter: None,
                     ty,
                 });
             }
@@ -511,15 +940,31 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 if args.len() != 1 {
                     return Err(DatabaseError::MisMatch("number of avg() parameters", "1"));
                 }
-                let ty = args[0].return_type();
+                let ty = AggKind::Avg.return_type(args[0].return_type());
 
                 return Ok(ScalarExpression::AggCall {
                     distinct: func.distinct,
                     kind: AggKind::Avg,
                     args,
+                    filter: None,
                     ty,
                 });
             }
+            "grouping" => {
+                if args.is_empty() || args.len() > 31 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of grouping() parameters",
+                        "1 to 31",
+                    ));
+                }
+                return Ok(ScalarExpression::AggCall {
+                    distinct: false,
+                    kind: AggKind::Grouping,
+                    args,
+                    filter: None,
+                    ty: LogicalType::Integer,
+                });
+            }
             "if" => {
                 if args.len() != 3 {
                     return Err(DatabaseError::MisMatch("number of if() parameters", "3"));
@@ -570,6 +1015,52 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                     ty,
                 });
             }
+            "to_timestamp" => {
+                if args.len() != 1 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of to_timestamp() parameters",
+                        "1",
+                    ));
+                }
+                return Ok(ScalarExpression::TypeCast {
+                    expr: Box::new(args.pop().unwrap()),
+                    ty: LogicalType::DateTime,
+                    is_try: false,
+                });
+            }
+            // resolved from the inferred type of the argument, so it is never evaluated
+            "typeof" | "pg_typeof" => {
+                if args.len() != 1 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of typeof() parameters",
+                        "1",
+                    ));
+                }
+                let type_name = match args[0].return_type() {
+                    LogicalType::SqlNull => "NULL".to_string(),
+                    ty => ty.to_string(),
+                };
+
+                return Ok(ScalarExpression::Constant(Arc::new(DataValue::Utf8 {
+                    value: Some(type_name),
+                    ty: Utf8Type::Variable(None),
+                    unit: CharLengthUnits::Characters,
+                })));
+            }
+            // e.g. `ROW(1, 'a')` -> `STRUCT(f1 INTEGER, f2 VARCHAR)`
+            "row" => {
+                let fields = args
+                    .iter()
+                    .enumerate()
+                    .map(|(i, arg)| (format!("f{}", i + 1), arg.return_type()))
+                    .collect_vec();
+
+                return Ok(ScalarExpression::TypeCast {
+                    expr: Box::new(ScalarExpression::Tuple(args)),
+                    ty: LogicalType::Struct(StructType::new(fields)),
+                    is_try: false,
+                });
+            }
             "coalesce" => {
                 let mut ty = LogicalType::SqlNull;
 
@@ -591,29 +1082,162 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 }
                 return Ok(ScalarExpression::Coalesce { exprs: args, ty });
             }
+            "greatest" | "least" => {
+                if args.len() < 2 {
+                    return Err(DatabaseError::MisMatch(
+                        "number of greatest() and least() parameters",
+                        "at least 2",
+                    ));
+                }
+                // Tips: the arguments are compared with each other like `=`
+                let mut ty = LogicalType::SqlNull;
+                for arg in args.iter() {
+                    ty = binary_operand_type(
+                        &expression::BinaryOperator::Eq,
+                        &ty,
+                        &arg.return_type(),
+                    )?;
+                }
+                // e.g. `GREATEST(int_column, decimal_column)` compares the decimals
+                let exprs = args
+                    .into_iter()
+                    .map(|arg| {
+                        if arg.return_type() != ty {
+                            ScalarExpression::TypeCast {
+                                expr: Box::new(arg),
+                                ty: ty.clone(),
+                                is_try: false,
+                            }
+                        } else {
+                            arg
+                        }
+                    })
+                    .collect_vec();
+
+                return Ok(if function_name == "greatest" {
+                    ScalarExpression::Greatest { exprs, ty }
+                } else {
+                    ScalarExpression::Least { exprs, ty }
+                });
+            }
+            JSON_EXTRACT => return JsonExtract::bind(JsonExtractKind::Path, args),
+            // the pattern is checked here if it is a literal, or else when it is evaluated
+            REGEXP_REPLACE | REGEXP_MATCHES => {
+                if let Some(ScalarExpression::Constant(value)) = args.get(1) {
+                    if let DataValue::Utf8 {
+                        value: Some(pattern),
+                        ..
+                    } = value.as_ref()
+                    {
+                        check_regex(pattern, false)?;
+                    }
+                }
+            }
             _ => (),
         }
+        if let Some(kind) = MathFunctionKind::from_name(&function_name) {
+            return MathFunction::bind(kind, args);
+        }
         let arg_types = args.iter().map(ScalarExpression::return_type).collect_vec();
         let summary = FunctionSummary {
             name: function_name,
             arg_types,
         };
-        if let Some(function) = self.context.scala_functions.get(&summary) {
+        if let Some(function) = self.context.functions.scala.get(&summary) {
             return Ok(ScalarExpression::ScalaFunction(ScalarFunction {
                 args,
                 inner: function.clone(),
             }));
         }
-        if let Some(function) = self.context.table_functions.get(&summary) {
+        if let Some(function) = self.context.functions.aggregate.get(&summary) {
+            return Ok(ScalarExpression::AggCall {
+                distinct: func.distinct,
+                kind: AggKind::Custom(AggregateFunction {
+                    inner: function.clone(),
+                }),
+                args,
+                filter: None,
+                ty: function.return_type().clone(),
+            });
+        }
+        let table_function = match self.context.functions.table.get(&summary) {
+            Some(function) => Some(function.clone()),
+            None => Unnest::resolve(&summary)?,
+        };
+        if let Some(function) = table_function {
+            if !matches!(self.context.step_now(), QueryBindStep::From) {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "`TableFunction` cannot bind in non-From step".to_string(),
+                ));
+            }
             return Ok(ScalarExpression::TableFunction(TableFunction {
                 args,
-                inner: function.clone(),
+                inner: function,
+            }));
+        }
+        if let Some(function) = self.implicit_scala_function(&summary) {
+            let args = args
+                .into_iter()
+                .zip(function.summary().arg_types.iter())
+                .map(|(arg, ty)| {
+                    if arg.return_type() != *ty {
+                        ScalarExpression::TypeCast {
+                            expr: Box::new(arg),
+                            ty: ty.clone(),
+                            is_try: false,
+                        }
+                    } else {
+                        arg
+                    }
+                })
+                .collect_vec();
+
+            return Ok(ScalarExpression::ScalaFunction(ScalarFunction {
+                args,
+                inner: function,
             }));
         }
 
         Err(DatabaseError::NotFound("function", summary.name))
     }
 
+    /// The scalar function named `summary.name` whose parameters the arguments of `summary` are
+    /// cast to, with the fewest casts, where numbers are also cast to strings, e.g.
+    /// `lpad(int_column, 5, '0')`.
+    fn implicit_scala_function(
+        &self,
+        summary: &FunctionSummary,
+    ) -> Option<Arc<dyn ScalarFunctionImpl>> {
+        let can_pass = |from: &LogicalType, to: &LogicalType| {
+            LogicalType::can_assign(from, to)
+                || ((from.is_numeric() || matches!(from, LogicalType::Decimal(..)))
+                    && matches!(to, LogicalType::Char(..) | LogicalType::Varchar(..)))
+        };
+        let casts = |candidate: &FunctionSummary| {
+            let mut casts = 0;
+            for (from, to) in summary.arg_types.iter().zip(candidate.arg_types.iter()) {
+                if !can_pass(from, to) {
+                    return None;
+                }
+                casts += usize::from(from != to);
+            }
+            Some(casts)
+        };
+
+        self.context
+            .functions
+            .scala
+            .iter()
+            .filter(|(candidate, _)| {
+                candidate.name == summary.name
+                    && candidate.arg_types.len() == summary.arg_types.len()
+            })
+            .filter_map(|(candidate, function)| Some((casts(candidate)?, candidate, function)))
+            // Tips: the order of the functions in the map is not stable
+            .min_by_key(|(casts, candidate, _)| (*casts, candidate.arg_types.clone()))
+            .map(|(_, _, function)| function.clone())
+    }
+
     fn return_type(
         expr_1: &ScalarExpression,
         expr_2: &ScalarExpression,
@@ -654,13 +1278,134 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
         })
     }
 
-    fn bind_cast(&mut self, expr: &Expr, ty: &DataType) -> Result<ScalarExpression, DatabaseError> {
+    fn bind_cast(
+        &mut self,
+        expr: &Expr,
+        ty: &DataType,
+        is_try: bool,
+    ) -> Result<ScalarExpression, DatabaseError> {
         Ok(ScalarExpression::TypeCast {
             expr: Box::new(self.bind_expr(expr)?),
             ty: LogicalType::try_from(ty.clone())?,
+            is_try,
         })
     }
 
+    /// Binds `EXTRACT(field FROM expr)`, where `expr` other than a `DATE` is taken as a
+    /// `DATETIME`.
+    fn bind_extract(
+        &mut self,
+        field: &DateTimeField,
+        expr: &Expr,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let field = ExtractField::try_from(field)?;
+        let mut expr = self.bind_expr(expr)?;
+        let mut ty = expr.return_type();
+
+        if !matches!(
+            ty,
+            LogicalType::Date | LogicalType::DateTime | LogicalType::TimestampTz
+        ) {
+            ty = LogicalType::DateTime;
+            expr = ScalarExpression::TypeCast {
+                expr: Box::new(expr),
+                ty: ty.clone(),
+                is_try: false,
+            };
+        }
+        Ok(ScalarExpression::ScalaFunction(ScalarFunction {
+            args: vec![Extract::field_expr(field), expr],
+            inner: Extract::new(field, ty),
+        }))
+    }
+
+    /// Binds `json -> key` and `json ->> key`, where `key` is the key of an object or the index
+    /// of an array.
+    fn bind_json_access(
+        &mut self,
+        left: &Expr,
+        operator: &JsonOperator,
+        right: &Expr,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        // Tips: the parser nests a chain of the operators to the right, e.g. `a -> 'b' ->> 'c'`
+        // is `a -> ('b' ->> 'c')`, which is bound as `(a -> 'b') ->> 'c'`
+        if let Expr::JsonAccess {
+            left: key,
+            operator: next_operator,
+            right: next_key,
+        } = right
+        {
+            let left = Expr::JsonAccess {
+                left: Box::new(left.clone()),
+                operator: *operator,
+                right: key.clone(),
+            };
+            return self.bind_json_access(&left, next_operator, next_key);
+        }
+        let kind = match operator {
+            JsonOperator::Arrow => JsonExtractKind::Arrow,
+            JsonOperator::LongArrow => JsonExtractKind::LongArrow,
+            operator => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "JSON operator {}",
+                    operator
+                )))
+            }
+        };
+        JsonExtract::bind(kind, vec![self.bind_expr(left)?, self.bind_expr(right)?])
+    }
+
+    /// Binds `INTERVAL '1 year 2 months'` or `INTERVAL '3' DAY`, see [`Interval::from_str`].
+    fn bind_interval(&mut self, expr: &Expr) -> Result<ScalarExpression, DatabaseError> {
+        let Expr::Interval(SqlInterval {
+            value,
+            leading_field,
+            leading_precision: None,
+            last_field: None,
+            fractional_seconds_precision: None,
+        }) = expr
+        else {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "interval qualifier: {}",
+                expr
+            )));
+        };
+        let text = match value.as_ref() {
+            Expr::Value(Value::SingleQuotedString(text) | Value::Number(text, _)) => text,
+            _ => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "interval value: {}",
+                    value
+                )))
+            }
+        };
+        let interval = match leading_field {
+            None => Interval::from_str(text.as_str())?,
+            Some(field) => {
+                let unit = match field {
+                    DateTimeField::Year => "year",
+                    DateTimeField::Month => "month",
+                    DateTimeField::Week => "week",
+                    DateTimeField::Day => "day",
+                    DateTimeField::Hour => "hour",
+                    DateTimeField::Minute => "minute",
+                    DateTimeField::Second => "second",
+                    field => {
+                        return Err(DatabaseError::UnsupportedStmt(format!(
+                            "interval field: {}",
+                            field
+                        )))
+                    }
+                };
+                Interval::from_str(&format!("{} {}", text, unit))?
+            }
+        };
+
+        Ok(ScalarExpression::Constant(Arc::new(DataValue::Interval(
+            Some(interval),
+        ))))
+    }
+
     fn wildcard_expr() -> ScalarExpression {
         ScalarExpression::Constant(Arc::new(DataValue::Utf8 {
             value: Some("*".to_string()),
@@ -669,3 +1414,296 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
         }))
     }
 }
+
+/// The predicate of `FILTER (WHERE ...)`, which the parser moves into the arguments of the
+/// aggregate function as the argument named [`AGGREGATE_FILTER`].
+fn aggregate_filter(arg: &FunctionArg) -> Option<&Expr> {
+    match arg {
+        FunctionArg::Named {
+            name,
+            arg: FunctionArgExpr::Expr(expr),
+        } if name.quote_style == Some(MARKER_QUOTE) && name.value == AGGREGATE_FILTER => Some(expr),
+        _ => None,
+    }
+}
+
+/// The type both operands of `op` are cast to before it is evaluated.
+///
+/// Comparisons are resolved by the table below, a pair of types it does not list cannot be
+/// compared. The other operators cast to the wider of the two types, but the arithmetic with a
+/// decimal casts to a decimal of the scale of the result, see
+/// [`LogicalType::decimal_arithmetic_type`].
+pub(crate) fn binary_operand_type(
+    op: &expression::BinaryOperator,
+    left: &LogicalType,
+    right: &LogicalType,
+) -> Result<LogicalType, DatabaseError> {
+    if !matches!(
+        op,
+        expression::BinaryOperator::Gt
+            | expression::BinaryOperator::Lt
+            | expression::BinaryOperator::GtEq
+            | expression::BinaryOperator::LtEq
+            | expression::BinaryOperator::Spaceship
+            | expression::BinaryOperator::Eq
+            | expression::BinaryOperator::NotEq
+    ) {
+        // e.g. `decimal_column * 2`, computed at the scale of the result
+        if let Some(ty) = LogicalType::decimal_arithmetic_type(op, left, right) {
+            return Ok(match ty {
+                LogicalType::Decimal(_, scale) => LogicalType::Decimal(None, scale),
+                ty => ty,
+            });
+        }
+        return LogicalType::max_logical_type(left, right);
+    }
+    let ty = match (left, right) {
+        (left, right) if left == right => left.clone(),
+        (LogicalType::SqlNull, ty) | (ty, LogicalType::SqlNull) => ty.clone(),
+        (left, right) if left.is_numeric() && right.is_numeric() => {
+            LogicalType::max_logical_type(left, right)?
+        }
+        // e.g. `decimal_column > 1.5::float`, compared approximately like the float
+        (LogicalType::Decimal(..), ty) | (ty, LogicalType::Decimal(..))
+            if ty.is_floating_point_numeric() =>
+        {
+            LogicalType::Double
+        }
+        // e.g. `decimal_column > 1`, the scale is kept from being rounded away
+        (LogicalType::Decimal(..), ty) | (ty, LogicalType::Decimal(..))
+            if ty.is_numeric() || matches!(ty, LogicalType::Decimal(..)) =>
+        {
+            LogicalType::Decimal(None, None)
+        }
+        (
+            LogicalType::Char(..) | LogicalType::Varchar(..),
+            LogicalType::Char(..) | LogicalType::Varchar(..),
+        ) => LogicalType::Varchar(None, CharLengthUnits::Characters),
+        (LogicalType::Date, LogicalType::DateTime) | (LogicalType::DateTime, LogicalType::Date) => {
+            LogicalType::DateTime
+        }
+        // e.g. `timestamptz_column > date_column`, the date is taken at the session time zone
+        (LogicalType::TimestampTz, LogicalType::Date | LogicalType::DateTime)
+        | (LogicalType::Date | LogicalType::DateTime, LogicalType::TimestampTz) => {
+            LogicalType::TimestampTz
+        }
+        // e.g. `date_column = '2024-01-01'`, the string is parsed as the type of the other side
+        (
+            ty @ (LogicalType::Date
+            | LogicalType::DateTime
+            | LogicalType::TimestampTz
+            | LogicalType::Time
+            | LogicalType::Interval
+            | LogicalType::Json
+            | LogicalType::Uuid
+            | LogicalType::Bytea
+            | LogicalType::Array(_)),
+            LogicalType::Char(..) | LogicalType::Varchar(..),
+        )
+        | (
+            LogicalType::Char(..) | LogicalType::Varchar(..),
+            ty @ (LogicalType::Date
+            | LogicalType::DateTime
+            | LogicalType::TimestampTz
+            | LogicalType::Time
+            | LogicalType::Interval
+            | LogicalType::Json
+            | LogicalType::Uuid
+            | LogicalType::Bytea
+            | LogicalType::Array(_)),
+        ) => ty.clone(),
+        // e.g. `struct_column = (1, 'a')`
+        (ty @ LogicalType::Struct(_), LogicalType::Tuple)
+        | (LogicalType::Tuple, ty @ LogicalType::Struct(_)) => ty.clone(),
+        // e.g. `int_array_column = ARRAY[1.5]`, compared element by element
+        (LogicalType::Array(left_array), LogicalType::Array(right_array)) => {
+            LogicalType::Array(ArrayType::new(binary_operand_type(
+                &expression::BinaryOperator::Eq,
+                left_array.element_type(),
+                right_array.element_type(),
+            )?))
+        }
+        _ => return Err(DatabaseError::Incomparable(left.clone(), right.clone())),
+    };
+    if ty == LogicalType::Boolean
+        && !matches!(
+            op,
+            expression::BinaryOperator::Eq | expression::BinaryOperator::NotEq
+        )
+    {
+        return Err(DatabaseError::UnsupportedBinaryOperator(ty, *op));
+    }
+
+    Ok(ty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::binary_operand_type;
+    use crate::errors::DatabaseError;
+    use crate::expression::BinaryOperator;
+    use crate::types::LogicalType;
+    use sqlparser::ast::CharLengthUnits;
+
+    #[test]
+    fn test_comparison_operand_type() -> Result<(), DatabaseError> {
+        let varchar = LogicalType::Varchar(None, CharLengthUnits::Characters);
+        let char = LogicalType::Char(2, CharLengthUnits::Characters);
+        let decimal = LogicalType::Decimal(Some(10), Some(2));
+
+        let coercions = [
+            (
+                LogicalType::Integer,
+                LogicalType::Bigint,
+                LogicalType::Bigint,
+            ),
+            (
+                LogicalType::UBigint,
+                LogicalType::Integer,
+                LogicalType::Double,
+            ),
+            (
+                LogicalType::UInteger,
+                LogicalType::Integer,
+                LogicalType::Bigint,
+            ),
+            (
+                LogicalType::Tinyint,
+                LogicalType::Double,
+                LogicalType::Double,
+            ),
+            (
+                decimal.clone(),
+                LogicalType::Integer,
+                LogicalType::Decimal(None, None),
+            ),
+            (
+                LogicalType::Bigint,
+                decimal.clone(),
+                LogicalType::Decimal(None, None),
+            ),
+            (
+                decimal.clone(),
+                LogicalType::Decimal(Some(5), Some(4)),
+                LogicalType::Decimal(None, None),
+            ),
+            (decimal.clone(), LogicalType::Float, LogicalType::Double),
+            (
+                LogicalType::DateTime,
+                LogicalType::Date,
+                LogicalType::DateTime,
+            ),
+            (LogicalType::Date, varchar.clone(), LogicalType::Date),
+            (
+                varchar.clone(),
+                LogicalType::DateTime,
+                LogicalType::DateTime,
+            ),
+            (LogicalType::Time, varchar.clone(), LogicalType::Time),
+            (char, varchar.clone(), varchar.clone()),
+            (LogicalType::SqlNull, LogicalType::Time, LogicalType::Time),
+            (
+                LogicalType::Boolean,
+                LogicalType::Boolean,
+                LogicalType::Boolean,
+            ),
+        ];
+        for (left, right, ty) in coercions {
+            for op in [BinaryOperator::Eq, BinaryOperator::NotEq] {
+                assert_eq!(binary_operand_type(&op, &left, &right)?, ty);
+                assert_eq!(binary_operand_type(&op, &right, &left)?, ty);
+            }
+        }
+
+        let rejections = [
+            (LogicalType::Time, LogicalType::DateTime),
+            (LogicalType::Time, LogicalType::Date),
+            (LogicalType::Boolean, LogicalType::Integer),
+            (LogicalType::Boolean, varchar.clone()),
+            (varchar.clone(), LogicalType::Integer),
+            (decimal.clone(), varchar),
+            (LogicalType::Date, LogicalType::Double),
+        ];
+        for (left, right) in rejections {
+            assert!(matches!(
+                binary_operand_type(&BinaryOperator::Lt, &left, &right),
+                Err(DatabaseError::Incomparable(..))
+            ));
+            assert!(matches!(
+                binary_operand_type(&BinaryOperator::Lt, &right, &left),
+                Err(DatabaseError::Incomparable(..))
+            ));
+        }
+        assert!(matches!(
+            binary_operand_type(
+                &BinaryOperator::Gt,
+                &LogicalType::Boolean,
+                &LogicalType::Boolean
+            ),
+            Err(DatabaseError::UnsupportedBinaryOperator(
+                LogicalType::Boolean,
+                BinaryOperator::Gt
+            ))
+        ));
+        // arithmetic is not a comparison
+        assert_eq!(
+            binary_operand_type(
+                &BinaryOperator::Plus,
+                &LogicalType::Integer,
+                &LogicalType::Bigint
+            )?,
+            LogicalType::Bigint
+        );
+        // the arithmetic with a decimal is computed at the scale of the result
+        let decimal_arithmetics = [
+            (
+                BinaryOperator::Plus,
+                decimal.clone(),
+                LogicalType::Integer,
+                Some(2),
+            ),
+            (
+                BinaryOperator::Minus,
+                decimal.clone(),
+                decimal.clone(),
+                Some(2),
+            ),
+            (
+                BinaryOperator::Multiply,
+                decimal.clone(),
+                decimal.clone(),
+                Some(4),
+            ),
+            (
+                BinaryOperator::Divide,
+                decimal.clone(),
+                LogicalType::Integer,
+                Some(6),
+            ),
+            (
+                BinaryOperator::Modulo,
+                decimal.clone(),
+                LogicalType::Decimal(Some(5), Some(4)),
+                Some(4),
+            ),
+            (
+                BinaryOperator::Plus,
+                decimal.clone(),
+                LogicalType::Decimal(None, None),
+                None,
+            ),
+        ];
+        for (op, left, right, scale) in decimal_arithmetics {
+            assert_eq!(
+                binary_operand_type(&op, &left, &right)?,
+                LogicalType::Decimal(None, scale)
+            );
+        }
+        assert_eq!(
+            binary_operand_type(&BinaryOperator::Multiply, &decimal, &LogicalType::Float)?,
+            LogicalType::Double
+        );
+
+        Ok(())
+    }
+}
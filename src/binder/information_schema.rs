@@ -0,0 +1,214 @@
+use crate::binder::Binder;
+use crate::catalog::{is_temp_table, ColumnCatalog, ColumnDesc, TableMeta};
+use crate::errors::DatabaseError;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::index::IndexType;
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::{ColumnId, LogicalType};
+use itertools::Itertools;
+use sqlparser::ast::{CharLengthUnits, TableAlias};
+use std::sync::Arc;
+
+/// The schema of the read-only tables describing the tables of the database, which are read
+/// from the catalog whenever they are bound:
+///
+/// - `tables`: `table_schema`, `table_name`, `table_type`, `comment`
+/// - `columns`: `table_schema`, `table_name`, `column_name`, `ordinal_position`, `data_type`,
+///   `is_nullable`, `column_default`, `comment`
+/// - `indexes`: `table_schema`, `table_name`, `index_name`, `index_type`, `column_name`,
+///   `ordinal_position`
+///
+/// `ordinal_position` is an `INTEGER` counted from 1, and the others are `VARCHAR`.
+pub const INFORMATION_SCHEMA: &str = "information_schema";
+
+const VARCHAR: LogicalType = LogicalType::Varchar(None, CharLengthUnits::Characters);
+
+impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
+    /// Binds the table `name` of [`INFORMATION_SCHEMA`] as the rows of the catalog, `None` if
+    /// there is no such table.
+    pub(crate) fn bind_information_schema(
+        &mut self,
+        name: &str,
+        alias: Option<&TableAlias>,
+    ) -> Result<Option<LogicalPlan>, DatabaseError> {
+        let (columns, rows) = match name {
+            "tables" => (
+                vec![
+                    ("table_schema", VARCHAR),
+                    ("table_name", VARCHAR),
+                    ("table_type", VARCHAR),
+                    ("comment", VARCHAR),
+                ],
+                self.information_schema_tables()?,
+            ),
+            "columns" => (
+                vec![
+                    ("table_schema", VARCHAR),
+                    ("table_name", VARCHAR),
+                    ("column_name", VARCHAR),
+                    ("ordinal_position", LogicalType::Integer),
+                    ("data_type", VARCHAR),
+                    ("is_nullable", VARCHAR),
+                    ("column_default", VARCHAR),
+                    ("comment", VARCHAR),
+                ],
+                self.information_schema_columns()?,
+            ),
+            "indexes" => (
+                vec![
+                    ("table_schema", VARCHAR),
+                    ("table_name", VARCHAR),
+                    ("index_name", VARCHAR),
+                    ("index_type", VARCHAR),
+                    ("column_name", VARCHAR),
+                    ("ordinal_position", LogicalType::Integer),
+                ],
+                self.information_schema_indexes()?,
+            ),
+            _ => return Ok(None),
+        };
+        let table_name = self.context.temp_table();
+        let schema = columns
+            .into_iter()
+            .enumerate()
+            .map(|(i, (column_name, ty))| {
+                let mut column = ColumnCatalog::new(
+                    column_name.to_string(),
+                    true,
+                    ColumnDesc::new(ty, false, false, None)?,
+                );
+                column.set_ref_table(table_name.clone(), i as ColumnId);
+
+                Ok(Arc::new(column))
+            })
+            .try_collect::<_, _, DatabaseError>()?;
+        let plan = self.bind_values(rows, Arc::new(schema));
+
+        self.bind_inline_plan(plan, Arc::new(name.to_string()), vec![], alias)
+            .map(Some)
+    }
+
+    fn information_schema_tables(&self) -> Result<Vec<Vec<ValueRef>>, DatabaseError> {
+        let mut rows = Vec::new();
+
+        for TableMeta {
+            table_name,
+            view_query,
+            comment,
+            ..
+        } in self.context.transaction.table_metas()?
+        {
+            if !self.is_visible(&table_name) {
+                continue;
+            }
+            let table_type = if view_query.is_some() {
+                "MATERIALIZED VIEW"
+            } else if is_temp_table(&table_name) {
+                "LOCAL TEMPORARY"
+            } else {
+                "BASE TABLE"
+            };
+            let (schema, name) = split_table_name(&table_name);
+
+            rows.push(vec![
+                schema,
+                name,
+                utf8(table_type.to_string()),
+                Arc::new(DataValue::from(comment)),
+            ]);
+        }
+        for view in self.context.transaction.views()? {
+            let (schema, name) = split_table_name(&view.name);
+
+            rows.push(vec![
+                schema,
+                name,
+                utf8("VIEW".to_string()),
+                Arc::new(DataValue::from(None::<String>)),
+            ]);
+        }
+        Ok(rows)
+    }
+
+    fn information_schema_columns(&self) -> Result<Vec<Vec<ValueRef>>, DatabaseError> {
+        let mut rows = Vec::new();
+
+        for meta in self.context.transaction.table_metas()? {
+            if !self.is_visible(&meta.table_name) {
+                continue;
+            }
+            let table = self
+                .context
+                .table(meta.table_name.clone())
+                .ok_or(DatabaseError::TableNotFound)?;
+            let (schema, name) = split_table_name(&table.name);
+
+            for (i, column) in table.columns().enumerate() {
+                rows.push(vec![
+                    schema.clone(),
+                    name.clone(),
+                    utf8(column.name().to_string()),
+                    Arc::new(DataValue::Int32(Some(i as i32 + 1))),
+                    utf8(column.datatype().to_string()),
+                    utf8(if column.nullable { "YES" } else { "NO" }.to_string()),
+                    Arc::new(DataValue::from(column.desc.default_display())),
+                    Arc::new(DataValue::from(column.desc.comment.clone())),
+                ]);
+            }
+        }
+        Ok(rows)
+    }
+
+    fn information_schema_indexes(&self) -> Result<Vec<Vec<ValueRef>>, DatabaseError> {
+        let mut rows = Vec::new();
+
+        for meta in self.context.transaction.table_metas()? {
+            if !self.is_visible(&meta.table_name) {
+                continue;
+            }
+            let table = self
+                .context
+                .table(meta.table_name.clone())
+                .ok_or(DatabaseError::TableNotFound)?;
+            let (schema, name) = split_table_name(&table.name);
+
+            for index_meta in table.indexes() {
+                let index_type = match index_meta.ty {
+                    IndexType::PrimaryKey => "PRIMARY KEY",
+                    IndexType::Unique { .. } => "UNIQUE",
+                    IndexType::Normal => "NORMAL",
+                    IndexType::Composite => "COMPOSITE",
+                };
+
+                for (i, column_id) in index_meta.column_ids.iter().enumerate() {
+                    let column = table
+                        .get_column_by_id(column_id)
+                        .ok_or_else(|| DatabaseError::NotFound("column", column_id.to_string()))?;
+
+                    rows.push(vec![
+                        schema.clone(),
+                        name.clone(),
+                        utf8(index_meta.name.clone()),
+                        utf8(index_type.to_string()),
+                        utf8(column.name().to_string()),
+                        Arc::new(DataValue::Int32(Some(i as i32 + 1))),
+                    ]);
+                }
+            }
+        }
+        Ok(rows)
+    }
+}
+
+fn utf8(value: String) -> ValueRef {
+    Arc::new(DataValue::from(value))
+}
+
+/// The schema and the name of the table, e.g. `pg_temp_3` and `t1` of `pg_temp_3.t1`, or `public`
+/// and `t1` of `t1`.
+fn split_table_name(table_name: &str) -> (ValueRef, ValueRef) {
+    let (schema, name) = table_name.split_once('.').unwrap_or(("public", table_name));
+
+    (utf8(schema.to_string()), utf8(name.to_string()))
+}
@@ -1,4 +1,5 @@
-use crate::binder::{lower_case_name, Binder};
+use crate::binder::Binder;
+use crate::catalog::{CheckConstraint, TableName};
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
 use crate::planner::operator::insert::InsertOperator;
@@ -8,7 +9,7 @@ use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
 use crate::types::tuple::SchemaRef;
 use crate::types::value::{DataValue, ValueRef};
-use sqlparser::ast::{Expr, Ident, ObjectName};
+use sqlparser::ast::{Expr, Ident, ObjectName, Query, SetExpr};
 use std::slice;
 use std::sync::Arc;
 
@@ -17,45 +18,53 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
         &mut self,
         name: &ObjectName,
         idents: &[Ident],
+        source: &Query,
+        is_overwrite: bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let table_name = self.bind_table_name(name)?;
+
+        if let SetExpr::Values(values) = source.body.as_ref() {
+            return self.bind_insert_values(table_name, idents, &values.rows, is_overwrite);
+        }
+        // Tips: the query is bound before the table inserted into, otherwise the columns of the
+        // table would be ambiguous with the columns of the query
+        let mut plan = self.bind_query(source)?;
+        let values_len = plan.output_schema().len();
+        let schema_ref = self.bind_insert_columns(table_name.clone(), idents, values_len)?;
+        if let Some(column) = schema_ref[..values_len]
+            .iter()
+            .find(|column| column.desc.generated.is_some())
+        {
+            return Err(DatabaseError::GeneratedColumnWrite(
+                column.name().to_string(),
+            ));
+        }
+        let checks = self.bind_table_checks(table_name.clone())?;
+        let generated = self.bind_table_generated_columns(table_name.clone())?;
+
+        self.bind_insert_by_position(
+            plan,
+            table_name,
+            schema_ref.to_vec(),
+            checks,
+            generated,
+            is_overwrite,
+        )
+    }
+
+    fn bind_insert_values(
+        &mut self,
+        table_name: TableName,
+        idents: &[Ident],
         expr_rows: &Vec<Vec<Expr>>,
         is_overwrite: bool,
     ) -> Result<LogicalPlan, DatabaseError> {
         // FIXME: Make it better to detect the current BindStep
         self.context.allow_default = true;
-        let table_name = Arc::new(lower_case_name(name)?);
-
-        let table = self
-            .context
-            .table_and_bind(table_name.clone(), None, None)?;
-        let mut _schema_ref = None;
         let values_len = expr_rows[0].len();
-
-        if idents.is_empty() {
-            let temp_schema_ref = table.schema_ref().clone();
-            if values_len > temp_schema_ref.len() {
-                return Err(DatabaseError::ValuesLenMismatch(
-                    temp_schema_ref.len(),
-                    values_len,
-                ));
-            }
-            _schema_ref = Some(temp_schema_ref);
-        } else {
-            let mut columns = Vec::with_capacity(idents.len());
-            for ident in idents {
-                match self.bind_column_ref_from_identifiers(
-                    slice::from_ref(ident),
-                    Some(table_name.to_string()),
-                )? {
-                    ScalarExpression::ColumnRef(catalog) => columns.push(catalog),
-                    _ => return Err(DatabaseError::UnsupportedStmt(ident.to_string())),
-                }
-            }
-            if values_len != columns.len() {
-                return Err(DatabaseError::ValuesLenMismatch(columns.len(), values_len));
-            }
-            _schema_ref = Some(Arc::new(columns));
-        }
-        let schema_ref = _schema_ref.ok_or(DatabaseError::ColumnsEmpty)?;
+        let schema_ref = self.bind_insert_columns(table_name.clone(), idents, values_len)?;
+        let checks = self.bind_table_checks(table_name.clone())?;
+        let generated = self.bind_table_generated_columns(table_name.clone())?;
         let mut rows = Vec::with_capacity(expr_rows.len());
 
         for expr_row in expr_rows {
@@ -65,10 +74,12 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             let mut row = Vec::with_capacity(expr_row.len());
 
             for (i, expr) in expr_row.iter().enumerate() {
-                let mut expression = self.bind_expr(expr)?;
-
-                expression.constant_calculation()?;
-                match expression {
+                match self.bind_constant_expr(expr)? {
+                    ScalarExpression::Constant(_) if schema_ref[i].desc.generated.is_some() => {
+                        return Err(DatabaseError::GeneratedColumnWrite(
+                            schema_ref[i].name().to_string(),
+                        ))
+                    }
                     ScalarExpression::Constant(mut value) => {
                         let ty = schema_ref[i].datatype();
                         // Check if the value length is too long
@@ -80,9 +91,17 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                         row.push(value);
                     }
                     ScalarExpression::Empty => {
-                        let default_value = schema_ref[i]
-                            .default_value()?
-                            .ok_or(DatabaseError::DefaultNotExist)?;
+                        let column = &schema_ref[i];
+                        // the value is taken from the sequence of the table, or generated from the
+                        // other columns, by `Insert`
+                        let default_value =
+                            if column.desc.is_auto_increment || column.desc.generated.is_some() {
+                                Arc::new(DataValue::none(column.datatype()))
+                            } else {
+                                column
+                                    .default_value()?
+                                    .ok_or(DatabaseError::DefaultNotExist)?
+                            };
                         row.push(default_value);
                     }
                     _ => return Err(DatabaseError::UnsupportedStmt(expr.to_string())),
@@ -97,11 +116,94 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
             Operator::Insert(InsertOperator {
                 table_name,
                 is_overwrite,
+                checks,
+                generated,
             }),
             vec![values_plan],
         ))
     }
 
+    /// Binds the `CHECK` constraints of the table written to.
+    pub(crate) fn bind_table_checks(
+        &self,
+        table_name: TableName,
+    ) -> Result<Vec<(CheckConstraint, ScalarExpression)>, DatabaseError> {
+        let table = self
+            .context
+            .table(table_name)
+            .ok_or(DatabaseError::TableNotFound)?;
+
+        self.bind_checks(table)
+    }
+
+    /// Binds the expressions of the generated columns of the table written to.
+    pub(crate) fn bind_table_generated_columns(
+        &self,
+        table_name: TableName,
+    ) -> Result<Vec<(usize, ScalarExpression)>, DatabaseError> {
+        let table = self
+            .context
+            .table(table_name)
+            .ok_or(DatabaseError::TableNotFound)?;
+
+        self.bind_generated_columns(table)
+    }
+
+    /// Binds the expression of a value in `VALUES`, folded into a constant if it can be.
+    pub(crate) fn bind_constant_expr(
+        &mut self,
+        expr: &Expr,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let mut expression = self.bind_expr(expr)?;
+
+        expression.constant_calculation()?;
+        // e.g. the struct value `(1, 'a')`
+        if let ScalarExpression::Tuple(_) | ScalarExpression::TypeCast { .. } = expression {
+            if let Some(value) = expression.unpack_val() {
+                expression = ScalarExpression::Constant(value);
+            }
+        }
+        Ok(expression)
+    }
+
+    /// The columns inserted into, the ones named by `idents` or else the columns of the table,
+    /// of which the first `values_len` are given.
+    fn bind_insert_columns(
+        &mut self,
+        table_name: TableName,
+        idents: &[Ident],
+        values_len: usize,
+    ) -> Result<SchemaRef, DatabaseError> {
+        let table = self
+            .context
+            .table_and_bind(table_name.clone(), None, None)?;
+
+        if idents.is_empty() {
+            let schema_ref = table.schema_ref().clone();
+            if values_len > schema_ref.len() {
+                return Err(DatabaseError::ValuesLenMismatch(
+                    schema_ref.len(),
+                    values_len,
+                ));
+            }
+            return Ok(schema_ref);
+        }
+        let mut columns = Vec::with_capacity(idents.len());
+        for ident in idents {
+            match self.bind_column_ref_from_identifiers(
+                slice::from_ref(ident),
+                Some(table_name.to_string()),
+            )? {
+                ScalarExpression::ColumnRef(catalog) => columns.push(catalog),
+                _ => return Err(DatabaseError::UnsupportedStmt(ident.to_string())),
+            }
+        }
+        if values_len != columns.len() {
+            return Err(DatabaseError::ValuesLenMismatch(columns.len(), values_len));
+        }
+        Ok(Arc::new(columns))
+    }
+
     pub(crate) fn bind_values(
         &mut self,
         rows: Vec<Vec<ValueRef>>,
@@ -1,33 +1,45 @@
 pub mod aggregate;
 mod alter_table;
 mod analyze;
+mod comment;
 pub mod copy;
 mod create_index;
 mod create_table;
+mod create_view;
 mod delete;
 mod describe;
 mod distinct;
 mod drop_table;
+mod drop_view;
 mod explain;
 pub mod expr;
+mod information_schema;
 mod insert;
+mod refresh;
 mod select;
 mod show;
 mod truncate;
 mod update;
+mod window;
 
-use sqlparser::ast::{Ident, ObjectName, ObjectType, SetExpr, Statement};
-use std::collections::{BTreeMap, HashMap, HashSet};
+use sqlparser::ast::{Cte, Ident, ObjectName, ObjectType, SetExpr, Statement};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
-use crate::catalog::{TableCatalog, TableName};
-use crate::db::{ScalaFunctions, TableFunctions};
+use crate::catalog::{
+    is_temp_table, temp_schema_of, temp_table_name, ColumnRef, TableCatalog, TableName, TEMP_SCHEMA,
+};
+use crate::db::Functions;
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
+use crate::parser::PARSER_RECURSION_LIMIT;
 use crate::planner::operator::join::JoinType;
 use crate::planner::LogicalPlan;
 use crate::storage::{TableCache, Transaction};
+use crate::types::tuple::SchemaRef;
+use crate::types::value::DataValue;
+use information_schema::INFORMATION_SCHEMA;
 
 pub enum InputRefType {
     AggCall,
@@ -38,14 +50,21 @@ pub enum CommandType {
     DQL,
     DML,
     DDL,
+    TCL,
 }
 
 pub fn command_type(stmt: &Statement) -> Result<CommandType, DatabaseError> {
     match stmt {
         Statement::CreateTable { .. }
+        | Statement::CreateView { .. }
         | Statement::CreateIndex { .. }
         | Statement::AlterTable { .. }
+        | Statement::Comment { .. }
         | Statement::Drop { .. } => Ok(CommandType::DDL),
+        // `SELECT ... INTO` creates the table
+        Statement::Query(query) if matches!(query.body.as_ref(), SetExpr::Select(select) if select.into.is_some()) => {
+            Ok(CommandType::DDL)
+        }
         Statement::Query(_)
         | Statement::Explain { .. }
         | Statement::ExplainTable { .. }
@@ -56,6 +75,10 @@ pub fn command_type(stmt: &Statement) -> Result<CommandType, DatabaseError> {
         | Statement::Delete { .. }
         | Statement::Insert { .. }
         | Statement::Copy { .. } => Ok(CommandType::DML),
+        Statement::StartTransaction { modes } if modes.is_empty() => Ok(CommandType::TCL),
+        Statement::Commit { chain: false } | Statement::Rollback { chain: false } => {
+            Ok(CommandType::TCL)
+        }
         stmt => Err(DatabaseError::UnsupportedStmt(stmt.to_string())),
     }
 }
@@ -76,30 +99,50 @@ pub enum QueryBindStep {
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub enum SubQueryType {
-    SubQuery(LogicalPlan),
-    InSubQuery(bool, LogicalPlan),
+    SubQuery(LogicalPlan, Vec<ColumnRef>),
+    InSubQuery(bool, LogicalPlan, Vec<ColumnRef>),
+    ExistsSubQuery(bool, LogicalPlan, Vec<ColumnRef>),
 }
 
 #[derive(Clone)]
 pub struct BinderContext<'a, T: Transaction> {
-    pub(crate) scala_functions: &'a ScalaFunctions,
-    pub(crate) table_functions: &'a TableFunctions,
+    pub(crate) functions: &'a Functions,
     pub(crate) table_cache: &'a TableCache,
     pub(crate) transaction: &'a T,
     // Tips: When there are multiple tables and Wildcard, use BTreeMap to ensure that the order of the output tables is certain.
     pub(crate) bind_table:
         BTreeMap<(TableName, Option<TableName>, Option<JoinType>), &'a TableCatalog>,
+    // the views by their aliases, which are expanded by wildcards along with `bind_table`
+    pub(crate) bind_views: BTreeSet<TableName>,
+    // the common table expressions of `WITH` visible to this query, the latest first to be found,
+    // and whether each one is declared by `WITH RECURSIVE`
+    ctes: Vec<(Cte, bool)>,
+    // the recursive common table expression being bound, which can only be read as the work table
+    recursive_cte: Option<TableName>,
+    // the column names and the schema of the work table, until it is read by the recursive term
+    work_table: Option<(Vec<Ident>, SchemaRef)>,
     // alias
     expr_aliases: BTreeMap<(Option<String>, String), ScalarExpression>,
+    // the column names of each table alias in `expr_aliases`, in the order of its columns
+    alias_columns: HashMap<TableName, Vec<String>>,
     table_aliases: HashMap<TableName, TableName>,
+    // the tables resolved in this bind, so that each table is only looked up once
+    resolved_tables: HashMap<TableName, &'a TableCatalog>,
     // agg
     group_by_exprs: Vec<ScalarExpression>,
+    // the grouping sets of `ROLLUP`, `CUBE` and `GROUPING SETS`, as positions in `group_by_exprs`
+    grouping_sets: Option<Vec<Vec<usize>>>,
     pub(crate) agg_calls: Vec<ScalarExpression>,
+    // window
+    pub(crate) window_calls: Vec<ScalarExpression>,
+    allow_window: bool,
     // join
     using: HashSet<String>,
 
     bind_step: QueryBindStep,
     sub_queries: HashMap<QueryBindStep, Vec<SubQueryType>>,
+    // columns of the outer query referenced by this (sub)query
+    correlated_columns: Vec<ColumnRef>,
 
     temp_table_id: Arc<AtomicUsize>,
     pub(crate) allow_default: bool,
@@ -109,28 +152,53 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
     pub fn new(
         table_cache: &'a TableCache,
         transaction: &'a T,
-        scala_functions: &'a ScalaFunctions,
-        table_functions: &'a TableFunctions,
+        functions: &'a Functions,
         temp_table_id: Arc<AtomicUsize>,
     ) -> Self {
         BinderContext {
-            scala_functions,
-            table_functions,
+            functions,
             table_cache,
             transaction,
             bind_table: Default::default(),
+            bind_views: Default::default(),
+            ctes: vec![],
+            recursive_cte: None,
+            work_table: None,
             expr_aliases: Default::default(),
+            alias_columns: Default::default(),
             table_aliases: Default::default(),
+            resolved_tables: Default::default(),
             group_by_exprs: vec![],
+            grouping_sets: None,
             agg_calls: Default::default(),
+            window_calls: vec![],
+            allow_window: false,
             using: Default::default(),
             bind_step: QueryBindStep::From,
             sub_queries: Default::default(),
+            correlated_columns: vec![],
             temp_table_id,
             allow_default: false,
         }
     }
 
+    /// The context of a join or subquery, which reuses the tables resolved so far
+    /// and sees the common table expressions of the query.
+    fn sub_context(&self) -> Self {
+        let mut context = BinderContext::new(
+            self.table_cache,
+            self.transaction,
+            self.functions,
+            self.temp_table_id.clone(),
+        );
+        context.resolved_tables.clone_from(&self.resolved_tables);
+        context.ctes.clone_from(&self.ctes);
+        context.recursive_cte.clone_from(&self.recursive_cte);
+        context
+    }
+
+    /// The name of a table derived by the query, e.g. a subquery without an alias, which has no
+    /// schema unlike the temporary tables in [`TEMP_SCHEMA`].
     pub fn temp_table(&mut self) -> TableName {
         Arc::new(format!(
             "_temp_table_{}_",
@@ -161,27 +229,46 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
         self.sub_queries.remove(&self.bind_step)
     }
 
+    pub fn add_correlated_column(&mut self, column: ColumnRef) {
+        if !self.correlated_columns.contains(&column) {
+            self.correlated_columns.push(column);
+        }
+    }
+
     pub fn table(&self, table_name: TableName) -> Option<&TableCatalog> {
-        if let Some(real_name) = self.table_aliases.get(table_name.as_ref()) {
-            self.transaction.table(self.table_cache, real_name.clone())
-        } else {
-            self.transaction.table(self.table_cache, table_name)
+        let table_name = self.real_table_name(table_name);
+
+        match self.resolved_tables.get(&table_name) {
+            Some(table) => Some(*table),
+            None => self.transaction.table(self.table_cache, table_name),
         }
     }
 
+    fn real_table_name(&self, table_name: TableName) -> TableName {
+        self.table_aliases
+            .get(table_name.as_ref())
+            .cloned()
+            .unwrap_or(table_name)
+    }
+
     pub fn table_and_bind(
         &mut self,
         table_name: TableName,
         alias: Option<TableName>,
         join_type: Option<JoinType>,
     ) -> Result<&TableCatalog, DatabaseError> {
-        let table = if let Some(real_name) = self.table_aliases.get(table_name.as_ref()) {
-            self.transaction.table(self.table_cache, real_name.clone())
-        } else {
-            self.transaction.table(self.table_cache, table_name.clone())
-        }
-        .ok_or(DatabaseError::TableNotFound)?;
-
+        let real_name = self.real_table_name(table_name.clone());
+        let table = match self.resolved_tables.get(&real_name) {
+            Some(table) => *table,
+            None => {
+                let table = self
+                    .transaction
+                    .table(self.table_cache, real_name.clone())
+                    .ok_or(DatabaseError::TableNotFound)?;
+                self.resolved_tables.insert(real_name, table);
+                table
+            }
+        };
         self.bind_table
             .insert((table_name.clone(), alias, join_type), table);
 
@@ -195,8 +282,15 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
         parent: Option<&'b Binder<'a, 'b, T>>,
     ) -> Result<&TableCatalog, DatabaseError> {
         if let Some(table_catalog) = self.bind_table.iter().find(|((t, alias, _), _)| {
-            t.as_str() == table_name
-                || matches!(alias.as_ref().map(|a| a.as_str() == table_name), Some(true))
+            match alias {
+                Some(alias) => alias.as_str() == table_name || t.as_str() == table_name,
+                // e.g. `scratch.c1` of the temporary table `pg_temp_3.scratch`
+                None => {
+                    t.as_str() == table_name
+                        || t.split_once('.')
+                            .is_some_and(|(_, name)| is_temp_table(t) && name == table_name)
+                }
+            }
         }) {
             Ok(table_catalog.1)
         } else if let Some(binder) = parent {
@@ -236,14 +330,133 @@ impl<'a, T: Transaction> BinderContext<'a, T> {
     }
 }
 
+/// The default limit of how deep queries and set operations may nest, the same as the limit of
+/// the parser, which also counts the nested expressions: a query nested as `a IN (subquery)`
+/// takes two levels of it, so such subqueries reach the parser's limit first.
+pub const DEFAULT_MAX_RECURSION_DEPTH: usize = PARSER_RECURSION_LIMIT;
+/// The default limit of how many times the recursive term of `WITH RECURSIVE` may be run.
+pub const DEFAULT_MAX_RECURSIVE_ITERATIONS: usize = 10000;
+
 pub struct Binder<'a, 'b, T: Transaction> {
     context: BinderContext<'a, T>,
     pub(crate) parent: Option<&'b Binder<'a, 'b, T>>,
+    // nesting depth of queries and set operations, counted from the outermost query
+    depth: usize,
+    max_depth: usize,
+    max_iterations: usize,
+    // whether `ORDER BY` puts NULLs first when `NULLS FIRST | LAST` is omitted
+    nulls_first: bool,
+    // the schema of the temporary tables of the session, see `temp_schema`
+    temp_schema: Option<String>,
+    // the values of the placeholders `$1`, `$2`..., see `set_parameters`
+    parameters: Vec<DataValue>,
 }
 
 impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
     pub fn new(context: BinderContext<'a, T>, parent: Option<&'b Binder<'a, 'b, T>>) -> Self {
-        Binder { context, parent }
+        let (depth, max_depth, max_iterations, nulls_first) = parent
+            .map(|binder| {
+                (
+                    binder.depth,
+                    binder.max_depth,
+                    binder.max_iterations,
+                    binder.nulls_first,
+                )
+            })
+            .unwrap_or((
+                0,
+                DEFAULT_MAX_RECURSION_DEPTH,
+                DEFAULT_MAX_RECURSIVE_ITERATIONS,
+                false,
+            ));
+
+        Binder {
+            context,
+            parent,
+            depth,
+            max_depth,
+            max_iterations,
+            nulls_first,
+            temp_schema: parent.and_then(|binder| binder.temp_schema.clone()),
+            parameters: parent
+                .map(|binder| binder.parameters.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Fail with [`DatabaseError::RecursionLimit`] instead of overflowing the stack
+    /// when queries and set operations nest deeper than `max_depth`.
+    pub fn max_recursion_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Fail with [`DatabaseError::RecursiveIterationLimit`] when the recursive term of
+    /// `WITH RECURSIVE` still returns rows after `max_iterations` iterations.
+    pub fn max_recursive_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// The default of `ORDER BY` clauses without `NULLS FIRST | LAST`.
+    pub fn nulls_first(mut self, nulls_first: bool) -> Self {
+        self.nulls_first = nulls_first;
+        self
+    }
+
+    /// The schema of the temporary tables of the session, which [`TEMP_SCHEMA`] stands for,
+    /// `None` if the session has none.
+    pub(crate) fn temp_schema(mut self, temp_schema: Option<String>) -> Self {
+        self.temp_schema = temp_schema;
+        self
+    }
+
+    /// The values bound to the placeholders `$1`, `$2`... of the statement, in their order.
+    pub(crate) fn set_parameters(&mut self, parameters: Vec<DataValue>) {
+        self.parameters = parameters;
+    }
+
+    /// Whether the table `table_name` is visible to the session, which the temporary tables of
+    /// the other sessions are not.
+    pub(crate) fn is_visible(&self, table_name: &str) -> bool {
+        check_temp_schema(table_name, self.temp_schema.as_deref()).is_ok()
+    }
+
+    /// The table `name` stands for, the temporary table of the name shadows the table of the
+    /// same name unless the name has a schema.
+    pub(crate) fn bind_table_name(&self, name: &ObjectName) -> Result<TableName, DatabaseError> {
+        let table_name = lower_case_name(name)?;
+        check_temp_schema(&table_name, self.temp_schema.as_deref())?;
+
+        match (name.0.as_slice(), &self.temp_schema) {
+            ([_], Some(temp_schema)) => {
+                let temp_name = temp_table_name(temp_schema, &table_name);
+
+                if self.context.table(temp_name.clone()).is_some() {
+                    return Ok(temp_name);
+                }
+            }
+            // e.g. `pg_temp.scratch`, which is the temporary table `scratch` of the session
+            ([schema, table], Some(temp_schema)) if lower_ident(schema) == TEMP_SCHEMA => {
+                return Ok(temp_table_name(temp_schema, &lower_ident(table)));
+            }
+            _ => (),
+        }
+        Ok(Arc::new(table_name))
+    }
+
+    fn nested<R>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<R, DatabaseError>,
+    ) -> Result<R, DatabaseError> {
+        if self.depth >= self.max_depth {
+            return Err(DatabaseError::RecursionLimit(self.max_depth));
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+
+        result
     }
 
     pub fn bind(&mut self, stmt: &Statement) -> Result<LogicalPlan, DatabaseError> {
@@ -255,15 +468,37 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 columns,
                 constraints,
                 if_not_exists,
+                query,
+                temporary,
+                ..
+            } => {
+                let table_name = new_table_name(name, *temporary, self.temp_schema.as_deref())?;
+
+                match query {
+                    Some(query) => {
+                        self.bind_create_table_as_query(table_name, columns, query, *if_not_exists)?
+                    }
+                    None => {
+                        self.bind_create_table(table_name, columns, constraints, *if_not_exists)?
+                    }
+                }
+            }
+            Statement::CreateView {
+                or_replace,
+                materialized,
+                name,
+                columns,
+                query,
                 ..
-            } => self.bind_create_table(name, columns, constraints, *if_not_exists)?,
+            } => self.bind_create_view(name, columns, query, *materialized, *or_replace)?,
             Statement::Drop {
                 object_type,
                 names,
                 if_exists,
                 ..
             } => match object_type {
-                ObjectType::Table => self.bind_drop_table(&names[0], if_exists)?,
+                ObjectType::Table => self.bind_drop_table(names, if_exists)?,
+                ObjectType::View => self.bind_drop_view(names, if_exists)?,
                 _ => todo!(),
             },
             Statement::Insert {
@@ -272,38 +507,28 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 source,
                 overwrite,
                 ..
-            } => {
-                if let SetExpr::Values(values) = source.body.as_ref() {
-                    self.bind_insert(table_name, columns, &values.rows, *overwrite)?
-                } else {
-                    todo!()
-                }
-            }
+            } => self.bind_insert(table_name, columns, source, *overwrite)?,
             Statement::Update {
                 table,
                 selection,
                 assignments,
+                from,
                 ..
-            } => {
-                if !table.joins.is_empty() {
-                    unimplemented!()
-                } else {
-                    self.bind_update(table, selection, assignments)?
-                }
-            }
+            } => self.bind_update(table, from, selection, assignments)?,
             Statement::Delete {
-                from, selection, ..
-            } => {
-                let table = &from[0];
-
-                if !table.joins.is_empty() {
-                    unimplemented!()
-                } else {
-                    self.bind_delete(table, selection)?
-                }
-            }
+                from,
+                using,
+                selection,
+                ..
+            } => self.bind_delete(from, using, selection)?,
             Statement::Analyze { table_name, .. } => self.bind_analyze(table_name)?,
-            Statement::Truncate { table_name, .. } => self.bind_truncate(table_name)?,
+            Statement::Comment {
+                object_type,
+                object_name,
+                comment,
+                ..
+            } => self.bind_comment(object_type, object_name, comment)?,
+            Statement::Truncate { table_name, .. } => self.bind_truncate(table_name, false)?,
             Statement::ShowTables { .. } => self.bind_show_tables()?,
             Statement::Copy {
                 source,
@@ -344,6 +569,7 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
                 left,
                 right,
             } => self.bind_set_operation(op, set_quantifier, left, right),
+            SetExpr::Values(values) => self.bind_values_query(values),
             _ => todo!(),
         }
     }
@@ -352,12 +578,17 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
         for (key, table) in context.bind_table {
             self.context.bind_table.insert(key, table);
         }
+        self.context.bind_views.extend(context.bind_views);
         for (key, expr) in context.expr_aliases {
             self.context.expr_aliases.insert(key, expr);
         }
+        self.context.alias_columns.extend(context.alias_columns);
         for (key, table_name) in context.table_aliases {
             self.context.table_aliases.insert(key, table_name);
         }
+        self.context.resolved_tables.extend(context.resolved_tables);
+        // Tips: the work table may have been read by the join
+        self.context.work_table = context.work_table;
     }
 }
 
@@ -365,12 +596,87 @@ fn lower_ident(ident: &Ident) -> String {
     ident.value.to_lowercase()
 }
 
+/// The schemas which name the tables without a schema, e.g. `public.t1` is `t1`.
+pub const DEFAULT_SCHEMAS: [&str; 2] = ["fnck_sql", "public"];
+
 /// Convert an object name into lower case
 fn lower_case_name(name: &ObjectName) -> Result<String, DatabaseError> {
-    if name.0.len() == 1 {
-        return Ok(lower_ident(&name.0[0]));
+    match name.0.as_slice() {
+        [name] => Ok(lower_ident(name)),
+        [schema, name] => Ok(qualified_name(schema, name)),
+        _ => Err(DatabaseError::InvalidTable(name.to_string())),
+    }
+}
+
+/// The name of the table created as `name`, which is in `temp_schema`, the schema of the
+/// temporary tables of the session, if it is temporary or in [`TEMP_SCHEMA`].
+fn new_table_name(
+    name: &ObjectName,
+    temporary: bool,
+    temp_schema: Option<&str>,
+) -> Result<TableName, DatabaseError> {
+    let table_name = lower_case_name(name)?;
+
+    if table_name
+        .strip_prefix(INFORMATION_SCHEMA)
+        .is_some_and(|name| name.starts_with('.'))
+    {
+        return Err(DatabaseError::InvalidTable(format!(
+            "`{}` is read-only",
+            INFORMATION_SCHEMA
+        )));
+    }
+    check_temp_schema(&table_name, temp_schema)?;
+
+    let temp_name = match name.0.as_slice() {
+        [table] if temporary => table,
+        // e.g. `pg_temp.scratch`, which is the temporary table `scratch` of the session
+        [schema, table]
+            if lower_ident(schema) == TEMP_SCHEMA
+                || Some(lower_ident(schema).as_str()) == temp_schema =>
+        {
+            table
+        }
+        [_, _] if temporary => {
+            return Err(DatabaseError::InvalidTable(format!(
+                "temporary table `{}` cannot be created in a schema",
+                name
+            )))
+        }
+        _ => return Ok(Arc::new(table_name)),
+    };
+    temp_schema
+        .map(|temp_schema| temp_table_name(temp_schema, &lower_ident(temp_name)))
+        .ok_or_else(|| {
+            DatabaseError::UnsupportedStmt(format!(
+                "temporary table `{}` only lives in a `Session`, see `Database::session`",
+                name
+            ))
+        })
+}
+
+/// Checks that `table_name` is not a temporary table of another session than the one of
+/// `temp_schema`, e.g. `pg_temp_3.scratch` outside the session 3.
+fn check_temp_schema(table_name: &str, temp_schema: Option<&str>) -> Result<(), DatabaseError> {
+    match temp_schema_of(table_name) {
+        Some(schema) if Some(schema) != temp_schema => Err(DatabaseError::InvalidTable(format!(
+            "`{}` is a temporary table of another session",
+            table_name
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// The name of `schema.name`, which keeps the schema unless it is one of [`DEFAULT_SCHEMAS`],
+/// so that the same name in different schemas names different tables.
+fn qualified_name(schema: &Ident, name: &Ident) -> String {
+    let (schema, name) = (lower_ident(schema), lower_ident(name));
+
+    if DEFAULT_SCHEMAS.contains(&schema.as_str()) {
+        name
+    } else {
+        format!("{}.{}", schema, name)
     }
-    Err(DatabaseError::InvalidTable(name.to_string()))
 }
 
 pub(crate) fn is_valid_identifier(s: &str) -> bool {
@@ -417,6 +723,9 @@ pub mod test {
                     ColumnDesc::new(Integer, false, true, None)?,
                 ),
             ],
+            vec![],
+            vec![],
+            vec![],
             false,
         )?;
 
@@ -435,6 +744,9 @@ pub mod test {
                     ColumnDesc::new(Integer, false, false, None)?,
                 ),
             ],
+            vec![],
+            vec![],
+            vec![],
             false,
         )?;
 
@@ -448,14 +760,12 @@ pub mod test {
         let table_cache = Arc::new(ShardingLruCache::new(4, 1, RandomState::new())?);
         let storage = build_test_catalog(&table_cache, temp_dir.path())?;
         let transaction = storage.transaction()?;
-        let scala_functions = Default::default();
-        let table_functions = Default::default();
+        let functions = Default::default();
         let mut binder = Binder::new(
             BinderContext::new(
                 &table_cache,
                 &transaction,
-                &scala_functions,
-                &table_functions,
+                &functions,
                 Arc::new(AtomicUsize::new(0)),
             ),
             None,
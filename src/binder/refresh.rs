@@ -0,0 +1,47 @@
+use crate::binder::{lower_case_name, Binder};
+use crate::errors::DatabaseError;
+use crate::parser::parse_stored_sql;
+use crate::planner::operator::truncate::TruncateOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use sqlparser::ast::{ObjectName, Statement};
+use std::sync::Arc;
+
+impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
+    /// `REFRESH MATERIALIZED VIEW` truncates the view and fills it with its query again.
+    pub(crate) fn bind_refresh(&mut self, name: &ObjectName) -> Result<LogicalPlan, DatabaseError> {
+        let view_name = Arc::new(lower_case_name(name)?);
+        let view_query = self
+            .context
+            .transaction
+            .table_meta(&view_name)?
+            .ok_or(DatabaseError::TableNotFound)?
+            .view_query
+            .ok_or_else(|| {
+                DatabaseError::InvalidTable(format!("`{}` is not a materialized view", view_name))
+            })?;
+        let columns = self
+            .context
+            .table(view_name.clone())
+            .ok_or(DatabaseError::TableNotFound)?
+            .columns()
+            .cloned()
+            .collect();
+        let stmts = parse_stored_sql(view_query)?;
+        let Some(Statement::Query(query)) = stmts.first() else {
+            unreachable!("the query of a materialized view must be a `SELECT`")
+        };
+        let plan = self.bind_query(query)?;
+        let insert =
+            self.bind_insert_by_position(plan, view_name.clone(), columns, vec![], vec![], false)?;
+
+        Ok(LogicalPlan::new(
+            Operator::Truncate(TruncateOperator {
+                table_name: view_name,
+                restart_identity: false,
+            }),
+            vec![insert],
+        ))
+    }
+}
@@ -1,6 +1,7 @@
 use std::borrow::Borrow;
 use std::collections::HashSet;
 use std::sync::Arc;
+use std::{mem, slice};
 
 use crate::{
     expression::ScalarExpression,
@@ -14,34 +15,53 @@ use crate::{
     types::value::DataValue,
 };
 
-use super::{lower_case_name, lower_ident, Binder, BinderContext, QueryBindStep, SubQueryType};
+use super::expr::binary_operand_type;
+use super::information_schema::INFORMATION_SCHEMA;
+use super::{
+    lower_case_name, lower_ident, new_table_name, Binder, BinderContext, QueryBindStep,
+    SubQueryType,
+};
 
-use crate::catalog::{ColumnCatalog, ColumnSummary, TableName};
+use crate::catalog::{
+    CheckConstraint, ColumnCatalog, ColumnDesc, ColumnRef, ColumnSummary, TableName, View,
+};
 use crate::errors::DatabaseError;
 use crate::execution::dql::join::joins_nullable;
+use crate::expression::agg::AggKind;
 use crate::expression::{AliasType, BinaryOperator};
+use crate::parser::{parse_stored_sql, wildcard_options, MARKER_QUOTE, ORDINALITY};
+use crate::planner::operator::create_table::CreateTableOperator;
 use crate::planner::operator::function_scan::FunctionScanOperator;
 use crate::planner::operator::insert::InsertOperator;
 use crate::planner::operator::join::JoinCondition;
+use crate::planner::operator::recursive_union::RecursiveUnionOperator;
 use crate::planner::operator::sort::{SortField, SortOperator};
 use crate::planner::operator::union::UnionOperator;
+use crate::planner::operator::work_table::WorkTableOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
 use crate::types::tuple::{Schema, SchemaRef};
-use crate::types::LogicalType;
+use crate::types::{ColumnId, LogicalType};
 use itertools::Itertools;
 use sqlparser::ast::{
-    Distinct, Expr, Ident, Join, JoinConstraint, JoinOperator, Offset, OrderByExpr, Query, Select,
-    SelectInto, SelectItem, SetExpr, SetOperator, SetQuantifier, TableAlias, TableFactor,
-    TableWithJoins,
+    CharLengthUnits, Cte, Distinct, ExcludeSelectItem, Expr, Function, Ident, Join, JoinConstraint,
+    JoinOperator, Offset, OrderByExpr, Query, Select, SelectInto, SelectItem, SetExpr, SetOperator,
+    SetQuantifier, Statement, TableAlias, TableFactor, TableWithJoins, Values,
+    WildcardAdditionalOptions, With,
 };
 
 impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
     pub(crate) fn bind_query(&mut self, query: &Query) -> Result<LogicalPlan, DatabaseError> {
+        self.nested(|binder| binder._bind_query(query))
+    }
+
+    fn _bind_query(&mut self, query: &Query) -> Result<LogicalPlan, DatabaseError> {
         let origin_step = self.context.step_now();
 
-        if let Some(_with) = &query.with {
-            // TODO support with clause.
+        let ctes_len = self.context.ctes.len();
+
+        if let Some(with) = &query.with {
+            self.bind_with(with)?;
         }
 
         let mut plan = match query.body.borrow() {
@@ -53,7 +73,8 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
                 left,
                 right,
             } => self.bind_set_operation(op, set_quantifier, left, right),
-            _ => unimplemented!(),
+            SetExpr::Values(values) => self.bind_values_query(values),
+            expr => return Err(DatabaseError::UnsupportedStmt(expr.to_string())),
         }?;
 
         let limit = &query.limit;
@@ -63,10 +84,23 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
             plan = self.bind_limit(plan, limit, offset)?;
         }
 
+        self.context.ctes.truncate(ctes_len);
         self.context.step(origin_step);
         Ok(plan)
     }
 
+    /// Registers the common table expressions, which are bound wherever they are referenced.
+    fn bind_with(&mut self, with: &With) -> Result<(), DatabaseError> {
+        for cte in with.cte_tables.iter() {
+            if cte.from.is_some() {
+                return Err(DatabaseError::UnsupportedStmt(cte.to_string()));
+            }
+            self.context.ctes.push((cte.clone(), with.recursive));
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn bind_select(
         &mut self,
         select: &Select,
@@ -79,6 +113,20 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
 
             if select.from.len() > 1 {
                 for from in select.from[1..].iter() {
+                    // Tips: bound like `CROSS JOIN`, so that only `t1, LATERAL (SELECT ...)`
+                    // can reference `t1`
+                    if is_lateral(&from.relation) || from.joins.is_empty() {
+                        let join = Join {
+                            relation: from.relation.clone(),
+                            join_operator: JoinOperator::CrossJoin,
+                        };
+                        plan = self.bind_join(plan, &join)?;
+
+                        for join in from.joins.iter() {
+                            plan = self.bind_join(plan, join)?;
+                        }
+                        continue;
+                    }
                     plan = LJoinOperator::build(
                         plan,
                         self.bind_table_ref(from)?,
@@ -92,7 +140,11 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
         // Resolve scalar function call.
         // TODO support SRF(Set-Returning Function).
 
+        self.context.step(QueryBindStep::Project);
+        self.context.allow_window = true;
         let mut select_list = self.normalize_select_item(&select.projection, &plan)?;
+        self.context.allow_window = false;
+        let project_sub_queries = self.context.sub_queries_at_now();
 
         if let Some(predicate) = &select.selection {
             plan = self.bind_where(plan, predicate)?;
@@ -107,10 +159,38 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
         let mut having_orderby = (None, None);
 
         if select.having.is_some() || !orderby.is_empty() {
-            having_orderby = self.extract_having_orderby_aggregate(&select.having, orderby)?;
+            having_orderby =
+                self.extract_having_orderby_aggregate(&select_list, &select.having, orderby)?;
         }
+        let distinct_on = match &select.distinct {
+            Some(Distinct::On(on_exprs)) => {
+                Some(self.extract_distinct_on_aggregate(&select_list, on_exprs)?)
+            }
+            _ => None,
+        };
 
-        if !self.context.agg_calls.is_empty() || !self.context.group_by_exprs.is_empty() {
+        let has_grouping_call = self.context.agg_calls.iter().any(|expr| {
+            matches!(
+                expr,
+                ScalarExpression::AggCall {
+                    kind: AggKind::Grouping,
+                    ..
+                }
+            )
+        });
+        if self.context.grouping_sets.is_some() || has_grouping_call {
+            let grouping_sets = self
+                .context
+                .grouping_sets
+                .take()
+                .unwrap_or_else(|| vec![(0..self.context.group_by_exprs.len()).collect()]);
+            plan = self.bind_grouping_sets(
+                plan,
+                self.context.agg_calls.clone(),
+                self.context.group_by_exprs.clone(),
+                grouping_sets,
+            )?;
+        } else if !self.context.agg_calls.is_empty() || !self.context.group_by_exprs.is_empty() {
             plan = self.bind_aggregate(
                 plan,
                 self.context.agg_calls.clone(),
@@ -122,11 +202,22 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
             plan = self.bind_having(plan, having)?;
         }
 
+        for sub_query in project_sub_queries.into_iter().flatten() {
+            plan = Self::bind_scalar_sub_query(plan, sub_query);
+        }
+
+        if !self.context.window_calls.is_empty() {
+            let window_calls = mem::take(&mut self.context.window_calls);
+            plan = self.bind_window(plan, window_calls);
+        }
+
         if let Some(Distinct::Distinct) = select.distinct {
             plan = self.bind_distinct(plan, select_list.clone());
         }
 
-        if let Some(orderby) = having_orderby.1 {
+        if let Some(on_exprs) = distinct_on {
+            plan = self.bind_distinct_on(plan, on_exprs, having_orderby.1)?;
+        } else if let Some(orderby) = having_orderby.1 {
             plan = self.bind_sort(plan, orderby);
         }
 
@@ -141,58 +232,199 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
             ..
         }) = &select.into
         {
-            if *unlogged || *temporary {
+            if *unlogged {
                 todo!()
             }
-            plan = LogicalPlan::new(
-                Operator::Insert(InsertOperator {
-                    table_name: Arc::new(lower_case_name(name)?),
-                    is_overwrite: false,
-                }),
-                vec![plan],
-            )
+            let table_name = new_table_name(name, *temporary, self.temp_schema.as_deref())?;
+
+            plan = self.bind_select_into(plan, table_name)?;
         }
 
         Ok(plan)
     }
 
+    /// `SELECT ... INTO table` creates the table from the projection and inserts the result into it.
+    fn bind_select_into(
+        &mut self,
+        plan: LogicalPlan,
+        table_name: TableName,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        self.bind_create_table_as(plan, table_name, &[], None, false)
+    }
+
+    /// Creates the table with the columns inferred from the output of `plan`, or named by
+    /// `column_names` if given, and fills it with the result of `plan`.
+    pub(crate) fn bind_create_table_as(
+        &mut self,
+        mut plan: LogicalPlan,
+        table_name: TableName,
+        column_names: &[String],
+        view_query: Option<String>,
+        if_not_exists: bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let schema = plan.output_schema().clone();
+        if !column_names.is_empty() && column_names.len() != schema.len() {
+            return Err(DatabaseError::MisMatch(
+                "the number of column names",
+                "the number of columns returned by the query",
+            ));
+        }
+        let mut columns = Vec::with_capacity(schema.len());
+        let mut has_primary = false;
+        let source_primaries = match &plan.operator {
+            Operator::Project(op) => op
+                .exprs
+                .iter()
+                .map(|expr| {
+                    matches!(expr.unpack_alias_ref(), ScalarExpression::ColumnRef(column) if column.desc.is_primary())
+                })
+                .collect_vec(),
+            _ => vec![],
+        };
+
+        let mut names = HashSet::with_capacity(schema.len());
+
+        for (i, column) in schema.iter().enumerate() {
+            let column_name = column_names
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| column.name().to_string());
+            if !names.insert(column_name.clone()) {
+                return Err(DatabaseError::InvalidTable(format!(
+                    "column `{}` of `{}` is returned more than once by the query, give the columns distinct aliases",
+                    column_name, table_name
+                )));
+            }
+            // e.g. `SELECT NULL`, whose column has no type of its own
+            let datatype = match column.datatype() {
+                LogicalType::SqlNull => LogicalType::Varchar(None, CharLengthUnits::Characters),
+                datatype => datatype.clone(),
+            };
+            // the first primary key of the source tables becomes the primary key of the new table
+            let is_primary = !has_primary
+                && (column.desc.is_primary() || source_primaries.get(i).copied().unwrap_or(false));
+            has_primary |= is_primary;
+
+            let mut new_column = ColumnCatalog::new(
+                column_name,
+                column.nullable && !is_primary,
+                ColumnDesc::new(datatype, is_primary, false, None)?,
+            );
+            new_column.set_ref_table(table_name.clone(), i as ColumnId);
+            columns.push(new_column);
+        }
+        if !has_primary {
+            return Err(DatabaseError::InvalidTable(format!(
+                "the query creating `{}` must return a primary key",
+                table_name
+            )));
+        }
+        let insert = self.bind_insert_by_position(
+            plan,
+            table_name.clone(),
+            columns.iter().cloned().map(Arc::new).collect_vec(),
+            vec![],
+            vec![],
+            false,
+        )?;
+
+        Ok(LogicalPlan::new(
+            Operator::CreateTable(CreateTableOperator {
+                table_name,
+                columns,
+                checks: vec![],
+                unique_keys: vec![],
+                foreign_keys: vec![],
+                if_not_exists,
+                view_query,
+            }),
+            vec![insert],
+        ))
+    }
+
+    /// Inserts the output of `plan` into `columns` of the table one by one, the outputs of other
+    /// types are cast to the types of the columns.
+    pub(crate) fn bind_insert_by_position(
+        &mut self,
+        mut plan: LogicalPlan,
+        table_name: TableName,
+        columns: Vec<ColumnRef>,
+        checks: Vec<(CheckConstraint, ScalarExpression)>,
+        generated: Vec<(usize, ScalarExpression)>,
+        is_overwrite: bool,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let alias_exprs = plan
+            .output_schema()
+            .iter()
+            .zip(columns)
+            .map(|(column, alias_column)| {
+                let mut expr = ScalarExpression::ColumnRef(column.clone());
+                let (from, to) = (column.datatype(), alias_column.datatype());
+                if from != to {
+                    if !LogicalType::can_assign(from, to) {
+                        return Err(DatabaseError::IncompatibleAssignment(
+                            alias_column.name().to_string(),
+                            to.clone(),
+                            from.clone(),
+                        ));
+                    }
+                    expr = ScalarExpression::TypeCast {
+                        expr: Box::new(expr),
+                        ty: to.clone(),
+                        is_try: false,
+                    };
+                }
+                Ok(ScalarExpression::Alias {
+                    expr: Box::new(expr),
+                    alias: AliasType::Expr(Box::new(ScalarExpression::ColumnRef(alias_column))),
+                })
+            })
+            .try_collect()?;
+
+        Ok(LogicalPlan::new(
+            Operator::Insert(InsertOperator {
+                table_name,
+                is_overwrite,
+                checks,
+                generated,
+            }),
+            vec![self.bind_project(plan, alias_exprs)?],
+        ))
+    }
+
     pub(crate) fn bind_set_operation(
         &mut self,
         op: &SetOperator,
         set_quantifier: &SetQuantifier,
         left: &SetExpr,
         right: &SetExpr,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        self.nested(|binder| binder._bind_set_operation(op, set_quantifier, left, right))
+    }
+
+    fn _bind_set_operation(
+        &mut self,
+        op: &SetOperator,
+        set_quantifier: &SetQuantifier,
+        left: &SetExpr,
+        right: &SetExpr,
     ) -> Result<LogicalPlan, DatabaseError> {
         let is_all = match set_quantifier {
             SetQuantifier::All => true,
             SetQuantifier::Distinct | SetQuantifier::None => false,
         };
-        let mut left_plan = self.bind_set_expr(left)?;
-        let mut right_plan = self.bind_set_expr(right)?;
-        let fn_eq = |left_schema: &Schema, right_schema: &Schema| {
-            let left_len = left_schema.len();
+        let left_plan = self.bind_set_expr(left)?;
+        // Tips: bound apart, so that the tables on the left are not visible on the right
+        let mut binder = Binder::new(self.context.sub_context(), Some(self));
+        let right_plan = binder.bind_set_expr(right)?;
+        let resolved_tables = binder.context.resolved_tables;
+        self.context.resolved_tables.extend(resolved_tables);
+        let (mut left_plan, mut right_plan) = self.coerce_set_operation(left_plan, right_plan)?;
 
-            if left_len != right_schema.len() {
-                return false;
-            }
-            for i in 0..left_len {
-                if left_schema[i].datatype() != right_schema[i].datatype() {
-                    return false;
-                }
-            }
-            true
-        };
         match (op, is_all) {
             (SetOperator::Union, true) => {
                 let left_schema = left_plan.output_schema();
                 let right_schema = right_plan.output_schema();
-
-                if !fn_eq(left_schema, right_schema) {
-                    return Err(DatabaseError::MisMatch(
-                        "the output types on the left",
-                        "the output types on the right",
-                    ));
-                }
                 Ok(UnionOperator::build(
                     left_schema.clone(),
                     right_schema.clone(),
@@ -203,13 +435,6 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
             (SetOperator::Union, false) => {
                 let left_schema = left_plan.output_schema();
                 let right_schema = right_plan.output_schema();
-
-                if !fn_eq(left_schema, right_schema) {
-                    return Err(DatabaseError::MisMatch(
-                        "the output types on the left",
-                        "the output types on the right",
-                    ));
-                }
                 let union_op = Operator::Union(UnionOperator {
                     left_schema_ref: left_schema.clone(),
                     _right_schema_ref: right_schema.clone(),
@@ -225,19 +450,182 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
                     distinct_exprs,
                 ))
             }
-            (SetOperator::Intersect, true) => {
-                todo!()
+            (SetOperator::Intersect | SetOperator::Except, _) => {
+                let left_schema = left_plan.output_schema().clone();
+                let right_schema = right_plan.output_schema();
+                let fn_columns = |schema: &Schema| {
+                    schema
+                        .iter()
+                        .cloned()
+                        .map(ScalarExpression::ColumnRef)
+                        .collect_vec()
+                };
+                let join_type = if matches!(op, SetOperator::Intersect) {
+                    JoinType::LeftSemi
+                } else {
+                    JoinType::LeftAnti
+                };
+                // a tuple is never `NULL`, so that `NULL`s are equal as for `UNION`
+                let on = vec![(
+                    ScalarExpression::Tuple(fn_columns(&left_schema)),
+                    ScalarExpression::Tuple(fn_columns(right_schema)),
+                )];
+                if !is_all {
+                    left_plan = self.bind_distinct(left_plan, fn_columns(&left_schema));
+                }
+
+                Ok(LogicalPlan::new(
+                    Operator::Join(LJoinOperator {
+                        on: JoinCondition::On { on, filter: None },
+                        join_type,
+                        correlated_columns: vec![],
+                        match_once: is_all,
+                    }),
+                    vec![left_plan, right_plan],
+                ))
             }
-            (SetOperator::Intersect, false) => {
-                todo!()
+        }
+    }
+
+    /// Casts the columns of both sides of a set operation to their common supertypes,
+    /// while the result columns keep the names of the left side.
+    fn coerce_set_operation(
+        &mut self,
+        mut left_plan: LogicalPlan,
+        mut right_plan: LogicalPlan,
+    ) -> Result<(LogicalPlan, LogicalPlan), DatabaseError> {
+        let left_schema = left_plan.output_schema().clone();
+        let right_schema = right_plan.output_schema().clone();
+
+        if left_schema.len() != right_schema.len() {
+            return Err(DatabaseError::MisMatch(
+                "the number of columns on the left",
+                "the number of columns on the right",
+            ));
+        }
+        let types: Vec<LogicalType> = left_schema
+            .iter()
+            .zip(right_schema.iter())
+            .enumerate()
+            .map(|(i, (left, right))| {
+                let (left_ty, right_ty) = (left.datatype(), right.datatype());
+
+                common_type(left_ty, right_ty).map_err(|_| {
+                    DatabaseError::SetOperationTypeMismatch(
+                        i + 1,
+                        left_ty.clone(),
+                        right_ty.clone(),
+                    )
+                })
+            })
+            .try_collect()?;
+
+        Ok((
+            self.cast_columns(left_plan, &left_schema, &types),
+            self.cast_columns(right_plan, &right_schema, &types),
+        ))
+    }
+
+    /// `VALUES (...), (...)` as a table, whose columns are named `column1`, `column2`, ... and
+    /// typed by the common supertype of the values in them.
+    pub(crate) fn bind_values_query(
+        &mut self,
+        values: &Values,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let values_len = values.rows[0].len();
+        let mut types = vec![LogicalType::SqlNull; values_len];
+        let mut rows = Vec::with_capacity(values.rows.len());
+
+        for expr_row in values.rows.iter() {
+            if expr_row.len() != values_len {
+                return Err(DatabaseError::ValuesLenMismatch(values_len, expr_row.len()));
             }
-            (SetOperator::Except, true) => {
-                todo!()
+            let mut row = Vec::with_capacity(values_len);
+
+            for (i, expr) in expr_row.iter().enumerate() {
+                let ScalarExpression::Constant(value) = self.bind_constant_expr(expr)? else {
+                    return Err(DatabaseError::UnsupportedStmt(expr.to_string()));
+                };
+                let value_ty = value.logical_type();
+
+                types[i] = common_type(&types[i], &value_ty).map_err(|_| {
+                    DatabaseError::ValuesTypeMismatch(i + 1, types[i].clone(), value_ty)
+                })?;
+                row.push(value);
             }
-            (SetOperator::Except, false) => {
-                todo!()
+            rows.push(row);
+        }
+        for row in rows.iter_mut() {
+            for (value, ty) in row.iter_mut().zip(types.iter()) {
+                if value.logical_type() != *ty {
+                    *value = Arc::new(DataValue::clone(value).cast(ty)?);
+                }
             }
         }
+        let table_name = self.context.temp_table();
+        let mut columns = Vec::with_capacity(values_len);
+
+        for (i, ty) in types.into_iter().enumerate() {
+            let mut column = ColumnCatalog::new(
+                format!("column{}", i + 1),
+                true,
+                ColumnDesc::new(ty, false, false, None)?,
+            );
+            column.set_ref_table(table_name.clone(), i as ColumnId);
+
+            columns.push(Arc::new(column));
+        }
+
+        Ok(self.bind_values(rows, Arc::new(columns)))
+    }
+
+    fn cast_columns(
+        &mut self,
+        plan: LogicalPlan,
+        schema: &Schema,
+        types: &[LogicalType],
+    ) -> LogicalPlan {
+        if schema
+            .iter()
+            .zip(types)
+            .all(|(column, ty)| column.datatype() == ty)
+        {
+            return plan;
+        }
+        let table_name = self.context.temp_table();
+        let exprs = schema
+            .iter()
+            .zip(types)
+            .enumerate()
+            .map(|(i, (column, ty))| {
+                let expr = ScalarExpression::ColumnRef(column.clone());
+
+                if column.datatype() == ty {
+                    return expr;
+                }
+                // Tips: a new column, which is not mistaken for the one being cast
+                let mut cast_column = ColumnCatalog::new(
+                    column.name().to_string(),
+                    column.nullable,
+                    // SAFETY: without a default expr
+                    ColumnDesc::new(ty.clone(), false, false, None).unwrap(),
+                );
+                cast_column.set_ref_table(table_name.clone(), i as ColumnId);
+
+                ScalarExpression::Alias {
+                    expr: Box::new(ScalarExpression::TypeCast {
+                        expr: Box::new(expr),
+                        ty: ty.clone(),
+                        is_try: false,
+                    }),
+                    alias: AliasType::Expr(Box::new(ScalarExpression::ColumnRef(Arc::new(
+                        cast_column,
+                    )))),
+                }
+            })
+            .collect_vec();
+
+        LogicalPlan::new(Operator::Project(ProjectOperator { exprs }), vec![plan])
     }
 
     pub(crate) fn bind_table_ref(
@@ -261,64 +649,176 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
         joint_type: Option<JoinType>,
     ) -> Result<LogicalPlan, DatabaseError> {
         let plan = match table {
+            // e.g. `numbers(10)`
+            TableFactor::Table {
+                name,
+                alias,
+                args: Some(args),
+                with_hints,
+            } => {
+                let function = Expr::Function(Function {
+                    name: name.clone(),
+                    args: args.clone(),
+                    over: None,
+                    distinct: false,
+                    special: false,
+                    order_by: vec![],
+                });
+
+                self.bind_table_function(&function, alias.as_ref(), with_hints, joint_type)?
+            }
             TableFactor::Table { name, alias, .. } => {
                 let table_name = lower_case_name(name)?;
 
-                self._bind_single_table_ref(joint_type, &table_name, alias.as_ref())?
+                // a common table expression shadows the table of the same name
+                if let [_] = name.0.as_slice() {
+                    if let Some(cte_name) = self
+                        .context
+                        .recursive_cte
+                        .clone()
+                        .filter(|cte_name| cte_name.as_str() == table_name)
+                    {
+                        return self.bind_work_table(cte_name, alias.as_ref());
+                    }
+                    if let Some(i) = self
+                        .context
+                        .ctes
+                        .iter()
+                        .rposition(|(cte, _)| lower_ident(&cte.alias.name) == table_name)
+                    {
+                        return self.bind_cte(i, alias.as_ref());
+                    }
+                }
+                if let Some(name) = table_name
+                    .strip_prefix(INFORMATION_SCHEMA)
+                    .and_then(|name| name.strip_prefix('.'))
+                {
+                    if let Some(plan) = self.bind_information_schema(name, alias.as_ref())? {
+                        return Ok(plan);
+                    }
+                }
+                let real_name = self.bind_table_name(name)?;
+
+                match self._bind_single_table_ref(joint_type, &real_name, alias.as_ref()) {
+                    Err(DatabaseError::TableNotFound) => {
+                        let view = self
+                            .context
+                            .transaction
+                            .view(&table_name)?
+                            .ok_or(DatabaseError::TableNotFound)?;
+
+                        self.bind_view(view, alias.as_ref())?
+                    }
+                    plan => plan?,
+                }
             }
             TableFactor::Derived {
                 subquery, alias, ..
             } => {
-                let mut plan = self.bind_query(subquery)?;
-                let mut tables = plan.referenced_table();
-
-                if let Some(TableAlias {
-                    name,
-                    columns: alias_column,
-                }) = alias
-                {
-                    if tables.len() > 1 {
-                        todo!("Implement virtual tables for multiple table aliases");
+                // Tips: bound apart, so that only the columns of the subquery are visible outside
+                let mut context = self.context.sub_context();
+                context.work_table = self.context.work_table.take();
+                let mut binder = Binder::new(context, Some(self));
+                let mut plan = binder.bind_query(subquery)?;
+                let BinderContext {
+                    resolved_tables,
+                    correlated_columns,
+                    work_table,
+                    ..
+                } = binder.context;
+                self.context.resolved_tables.extend(resolved_tables);
+                self.context.work_table = work_table;
+                if self.parent.is_some() {
+                    for column in correlated_columns {
+                        self.context.add_correlated_column(column);
                     }
-                    let table_alias = Arc::new(name.value.to_lowercase());
-
-                    plan =
-                        self.bind_alias(plan, alias_column, table_alias, tables.pop().unwrap())?;
                 }
-                plan
+                // e.g. `(VALUES (1, 'a')) AS v(id, name)`, or a subquery without an alias, which
+                // keeps the table of its columns if they all come from one
+                let name = match alias {
+                    Some(alias) => Arc::new(lower_ident(&alias.name)),
+                    None => {
+                        let table_names = plan
+                            .output_schema()
+                            .iter()
+                            .map(|column| column.table_name().cloned())
+                            .unique()
+                            .collect_vec();
+                        match table_names.as_slice() {
+                            [Some(table_name)] => table_name.clone(),
+                            _ => self.context.temp_table(),
+                        }
+                    }
+                };
+
+                self.bind_inline_plan(plan, name, vec![], alias.as_ref())?
             }
             TableFactor::TableFunction { expr, alias } => {
-                if let ScalarExpression::TableFunction(function) = self.bind_expr(expr)? {
-                    let mut table_alias = None;
-                    let table_name = Arc::new(function.summary().name.clone());
-                    let table = function.table();
-                    let mut plan = FunctionScanOperator::build(function);
-
-                    if let Some(TableAlias {
-                        name,
-                        columns: alias_column,
-                    }) = alias
-                    {
-                        table_alias = Some(Arc::new(name.value.to_lowercase()));
+                self.bind_table_function(expr, alias.as_ref(), &[], joint_type)?
+            }
+            _ => unimplemented!(),
+        };
 
-                        plan = self.bind_alias(
-                            plan,
-                            alias_column,
-                            table_alias.clone().unwrap(),
-                            table_name.clone(),
-                        )?;
-                    }
+        Ok(plan)
+    }
 
-                    self.context
-                        .bind_table
-                        .insert((table_name, table_alias, joint_type), table);
-                    plan
-                } else {
-                    unreachable!()
-                }
+    /// `with_hints` is `[ORDINALITY]` for `WITH ORDINALITY`, see [`ORDINALITY`].
+    fn bind_table_function(
+        &mut self,
+        expr: &Expr,
+        alias: Option<&TableAlias>,
+        with_hints: &[Expr],
+        joint_type: Option<JoinType>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let with_ordinality = match with_hints {
+            [] => false,
+            [Expr::Identifier(ident)]
+                if ident.quote_style == Some(MARKER_QUOTE) && ident.value == ORDINALITY =>
+            {
+                true
             }
-            _ => unimplemented!(),
+            _ => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "table hints: {}",
+                    with_hints.iter().join(", ")
+                )))
+            }
+        };
+        let ScalarExpression::TableFunction(function) = self.bind_expr(expr)? else {
+            return Err(DatabaseError::NotFound("table function", expr.to_string()));
+        };
+        let table_name = Arc::new(function.summary().name.clone());
+        let table = function.table();
+        let ordinality = if with_ordinality {
+            let mut column = ColumnCatalog::new(
+                ORDINALITY.to_lowercase(),
+                false,
+                ColumnDesc::new(LogicalType::Bigint, false, false, None)?,
+            );
+            column.set_ref_table(table_name.clone(), table.columns_len() as ColumnId);
+
+            Some(Arc::new(column))
+        } else {
+            None
         };
+        let mut plan = FunctionScanOperator::build(function, ordinality);
+        // Tips: the ordinality column is not in the table of the function, so it is bound as an
+        // alias of the function like the columns of `AS t(...)`
+        let alias = match alias {
+            Some(TableAlias { name, columns }) => {
+                Some((Arc::new(name.value.to_lowercase()), columns.as_slice()))
+            }
+            None => with_ordinality.then(|| (table_name.clone(), [].as_slice())),
+        };
+        let mut table_alias = None;
+
+        if let Some((alias, alias_column)) = alias {
+            plan = self.bind_alias(plan, alias_column, alias.clone(), table_name.clone())?;
+            table_alias = Some(alias);
+        }
+        self.context
+            .bind_table
+            .insert((table_name, table_alias, joint_type), table);
 
         Ok(plan)
     }
@@ -348,6 +848,7 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
                 .collect_vec()
         };
         let mut alias_exprs = Vec::with_capacity(aliases_with_columns.len());
+        let mut alias_names = Vec::with_capacity(aliases_with_columns.len());
 
         for (alias, column) in aliases_with_columns {
             let mut alias_column = ColumnCatalog::clone(&column);
@@ -362,15 +863,231 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
             };
             self.context.add_alias(
                 Some(table_alias.to_string()),
-                alias,
+                alias.clone(),
                 alias_column_expr.clone(),
             );
+            alias_names.push(alias);
             alias_exprs.push(alias_column_expr);
         }
+        self.context
+            .alias_columns
+            .insert(table_alias.clone(), alias_names);
         self.context.add_table_alias(table_alias, table_name);
         self.bind_project(plan, alias_exprs)
     }
 
+    /// Inlines the query of the view as a subquery aliased by the name of the view.
+    fn bind_view(
+        &mut self,
+        view: View,
+        alias: Option<&TableAlias>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let stmts = parse_stored_sql(&view.query)?;
+        let Some(Statement::Query(query)) = stmts.first() else {
+            unreachable!("the query of a view must be a `SELECT`")
+        };
+        let columns = view.columns.into_iter().map(Ident::new).collect_vec();
+
+        // the query of a view does not see the common table expressions where it is used
+        self.bind_inline_query(query, view.name, columns, alias, vec![])
+    }
+
+    /// Inlines the `i`-th common table expression as a subquery aliased by its name,
+    /// whose query only sees the common table expressions before it.
+    fn bind_cte(
+        &mut self,
+        i: usize,
+        alias: Option<&TableAlias>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let (
+            Cte {
+                alias: TableAlias { name, columns },
+                query,
+                ..
+            },
+            recursive,
+        ) = self.context.ctes[i].clone();
+        let ctes = self.context.ctes[..i].to_vec();
+        let name = Arc::new(lower_ident(&name));
+
+        if recursive {
+            if let Some(plan) = self.bind_recursive_query(&query, name.clone(), &columns, &ctes)? {
+                return self.bind_inline_plan(plan, name, columns, alias);
+            }
+        }
+        self.bind_inline_query(&query, name, columns, alias, ctes)
+    }
+
+    /// Binds `anchor UNION [ALL] recursive_term` of `WITH RECURSIVE`, where the recursive term
+    /// reads the rows returned by the last iteration as the common table expression `name`.
+    ///
+    /// Returns `None` if the query does not reference itself, which is bound as a plain query.
+    fn bind_recursive_query(
+        &mut self,
+        query: &Query,
+        name: TableName,
+        columns: &[Ident],
+        ctes: &[(Cte, bool)],
+    ) -> Result<Option<LogicalPlan>, DatabaseError> {
+        let SetExpr::SetOperation {
+            op: SetOperator::Union,
+            set_quantifier,
+            left,
+            right,
+        } = query.body.as_ref()
+        else {
+            return Ok(None);
+        };
+        if !query.order_by.is_empty() || query.limit.is_some() || query.offset.is_some() {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "`ORDER BY`, `LIMIT` or `OFFSET` in the recursive query `{}`",
+                name
+            )));
+        }
+        let is_all = matches!(set_quantifier, SetQuantifier::All);
+        let recursive_binder = |binder: &Binder<'a, '_, T>| {
+            let mut context = binder.context.sub_context();
+            context.ctes = ctes.to_vec();
+            context.recursive_cte = Some(name.clone());
+            context
+        };
+
+        // Tips: the anchor can not read the work table, which is filled with its rows
+        let mut binder = Binder::new(recursive_binder(self), Some(self));
+        if let Some(with) = &query.with {
+            binder.bind_with(with)?;
+        }
+        let mut anchor = binder.bind_set_expr(left)?;
+        self.context
+            .resolved_tables
+            .extend(binder.context.resolved_tables);
+
+        let table_name = self.context.temp_table();
+        let schema_ref = Arc::new(
+            anchor
+                .output_schema()
+                .iter()
+                .enumerate()
+                .map(|(i, column)| {
+                    let mut column = ColumnCatalog::clone(column);
+                    column.set_ref_table(table_name.clone(), i as ColumnId);
+
+                    Arc::new(column)
+                })
+                .collect_vec(),
+        );
+        let mut context = recursive_binder(self);
+        context.work_table = Some((columns.to_vec(), schema_ref));
+        let mut binder = Binder::new(context, Some(self));
+        if let Some(with) = &query.with {
+            binder.bind_with(with)?;
+        }
+        let mut recursive_term = binder.bind_set_expr(right)?;
+        if binder.context.work_table.is_some() {
+            return Ok(None);
+        }
+        self.context
+            .resolved_tables
+            .extend(binder.context.resolved_tables);
+
+        let anchor_schema = anchor.output_schema().clone();
+        let recursive_schema = recursive_term.output_schema();
+        if anchor_schema.len() != recursive_schema.len()
+            || anchor_schema
+                .iter()
+                .zip(recursive_schema.iter())
+                .any(|(left, right)| left.datatype() != right.datatype())
+        {
+            return Err(DatabaseError::MisMatch(
+                "the output types of the anchor",
+                "the output types of the recursive term",
+            ));
+        }
+
+        Ok(Some(LogicalPlan::new(
+            Operator::RecursiveUnion(RecursiveUnionOperator {
+                table_name: name,
+                schema_ref: anchor_schema,
+                is_all,
+                max_iterations: self.max_iterations,
+            }),
+            vec![anchor, recursive_term],
+        )))
+    }
+
+    /// Reads the rows of the last iteration of the recursive common table expression `name`,
+    /// which is only allowed once in the `FROM` of its recursive term.
+    fn bind_work_table(
+        &mut self,
+        name: TableName,
+        alias: Option<&TableAlias>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let Some((columns, schema_ref)) = self.context.work_table.take() else {
+            return Err(DatabaseError::InvalidRecursion(format!(
+                "`{}` can only be referenced once in the `FROM` of its recursive term",
+                name
+            )));
+        };
+        let plan = LogicalPlan::new(
+            Operator::WorkTable(WorkTableOperator {
+                table_name: name.clone(),
+                schema_ref,
+            }),
+            vec![],
+        );
+
+        self.bind_inline_plan(plan, name, columns, alias)
+    }
+
+    fn bind_inline_query(
+        &mut self,
+        query: &Query,
+        name: TableName,
+        columns: Vec<Ident>,
+        alias: Option<&TableAlias>,
+        ctes: Vec<(Cte, bool)>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        // Tips: bound apart, so that only the columns of the query are visible outside
+        let mut context = self.context.sub_context();
+        context.ctes = ctes;
+        context.recursive_cte = None;
+        let mut binder = Binder::new(context, Some(self));
+        let plan = binder.bind_query(query)?;
+        let resolved_tables = binder.context.resolved_tables;
+        self.context.resolved_tables.extend(resolved_tables);
+
+        self.bind_inline_plan(plan, name, columns, alias)
+    }
+
+    /// Aliases the plan of a view, common table expression or subquery in `FROM` by its name,
+    /// or by the alias where it is referenced.
+    pub(crate) fn bind_inline_plan(
+        &mut self,
+        plan: LogicalPlan,
+        name: TableName,
+        columns: Vec<Ident>,
+        alias: Option<&TableAlias>,
+    ) -> Result<LogicalPlan, DatabaseError> {
+        let (view_alias, alias_columns) = match alias {
+            Some(TableAlias { name, columns }) if !columns.is_empty() => {
+                (Arc::new(name.value.to_lowercase()), columns.clone())
+            }
+            alias => (
+                alias.map_or(name, |alias| Arc::new(alias.name.value.to_lowercase())),
+                columns,
+            ),
+        };
+        let mut tables = plan.referenced_table();
+        let table_name = if tables.len() == 1 {
+            tables.pop().unwrap()
+        } else {
+            view_alias.clone()
+        };
+
+        self.context.bind_views.insert(view_alias.clone());
+        self.bind_alias(plan, &alias_columns, view_alias, table_name)
+    }
+
     pub(crate) fn _bind_single_table_ref(
         &mut self,
         join_type: Option<JoinType>,
@@ -412,8 +1129,17 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
         let mut select_items = vec![];
 
         for item in items.iter() {
-            match item {
-                SelectItem::UnnamedExpr(expr) => select_items.push(self.bind_expr(expr)?),
+            let unquoted = wildcard_options(item)?;
+
+            match unquoted.as_ref().unwrap_or(item) {
+                SelectItem::UnnamedExpr(expr) => {
+                    // a collation only changes how the value is compared, not the value
+                    let expr = match self.bind_expr(expr)? {
+                        ScalarExpression::Collate { expr, .. } => *expr,
+                        expr => expr,
+                    };
+                    select_items.push(expr)
+                }
                 SelectItem::ExprWithAlias { expr, alias } => {
                     let expr = self.bind_expr(expr)?;
                     let alias_name = alias.to_string();
@@ -426,29 +1152,49 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
                         alias: AliasType::Name(alias_name),
                     });
                 }
-                SelectItem::Wildcard(_) => {
+                SelectItem::Wildcard(options) => {
+                    let mut exprs = vec![];
+
                     if let Operator::Project(op) = &plan.operator {
-                        for expr in op.exprs.iter() {
-                            select_items.push(expr.clone());
+                        exprs.extend(op.exprs.iter().cloned());
+                    } else {
+                        let mut join_used = HashSet::with_capacity(self.context.using.len());
+
+                        let tables = self
+                            .context
+                            .bind_table
+                            .keys()
+                            .map(|(table_name, alias, _)| {
+                                (table_name, alias.as_ref().unwrap_or(table_name))
+                            })
+                            .merge_by(
+                                self.context.bind_views.iter().map(|view| (view, view)),
+                                |(a, _), (b, _)| a <= b,
+                            )
+                            .map(|(_, table_name)| table_name.clone())
+                            .collect_vec();
+
+                        for table_name in tables {
+                            self.bind_table_column_refs(
+                                &mut exprs,
+                                table_name,
+                                Some(&mut join_used),
+                            )?;
                         }
-                        continue;
-                    }
-                    let mut join_used = HashSet::with_capacity(self.context.using.len());
-
-                    for (table_name, alias, _) in self.context.bind_table.keys() {
-                        self.bind_table_column_refs(
-                            &mut select_items,
-                            alias.as_ref().unwrap_or(table_name).clone(),
-                            Some(&mut join_used),
-                        )?;
                     }
+                    self.bind_wildcard_options(&mut exprs, options)?;
+                    select_items.append(&mut exprs);
                 }
-                SelectItem::QualifiedWildcard(table_name, _) => {
+                SelectItem::QualifiedWildcard(table_name, options) => {
+                    let mut exprs = vec![];
+
                     self.bind_table_column_refs(
-                        &mut select_items,
+                        &mut exprs,
                         Arc::new(lower_case_name(table_name)?),
                         None,
                     )?;
+                    self.bind_wildcard_options(&mut exprs, options)?;
+                    select_items.append(&mut exprs);
                 }
             };
         }
@@ -456,45 +1202,82 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
         Ok(select_items)
     }
 
+    /// Apply `EXCLUDE (...)` and `REPLACE (... AS ...)` to the columns expanded from a wildcard,
+    /// a replaced column keeps its position.
+    fn bind_wildcard_options(
+        &mut self,
+        exprs: &mut Vec<ScalarExpression>,
+        options: &WildcardAdditionalOptions,
+    ) -> Result<(), DatabaseError> {
+        let is_column = |expr: &ScalarExpression, name: &str| expr.output_column().name() == name;
+
+        if let Some(exclude) = &options.opt_exclude {
+            let columns = match exclude {
+                ExcludeSelectItem::Single(column) => slice::from_ref(column),
+                ExcludeSelectItem::Multiple(columns) => columns.as_slice(),
+            };
+            for column in columns {
+                let name = lower_ident(column);
+                let len = exprs.len();
+
+                exprs.retain(|expr| !is_column(expr, &name));
+                if exprs.len() == len {
+                    return Err(DatabaseError::NotFound("column", name));
+                }
+            }
+        }
+        if let Some(replace) = &options.opt_replace {
+            for element in replace.items.iter() {
+                let name = lower_ident(&element.column_name);
+                let position = exprs
+                    .iter()
+                    .position(|expr| is_column(expr, &name))
+                    .ok_or_else(|| DatabaseError::NotFound("column", name.clone()))?;
+
+                exprs[position] = ScalarExpression::Alias {
+                    expr: Box::new(self.bind_expr(&element.expr)?),
+                    alias: AliasType::Name(name),
+                };
+            }
+        }
+        Ok(())
+    }
+
+    /// Expands the columns of `table_name`, the real name or the alias of a table in `FROM`,
+    /// in the order of its columns.
     fn bind_table_column_refs(
         &self,
         exprs: &mut Vec<ScalarExpression>,
         table_name: TableName,
         mut join_used: Option<&mut HashSet<String>>,
     ) -> Result<(), DatabaseError> {
-        let mut is_bound_alias = false;
-
         let fn_used =
             |column_name: &str, context: &BinderContext<T>, join_used: Option<&HashSet<_>>| {
                 context.using.contains(column_name)
                     && matches!(join_used.map(|used| used.contains(column_name)), Some(true))
             };
-        for (_, alias_expr) in self.context.expr_aliases.iter().filter(|(_, expr)| {
-            if let ScalarExpression::ColumnRef(col) = expr.unpack_alias_ref() {
-                let column_name = col.name();
-
-                if Some(&table_name) == col.table_name()
-                    && !fn_used(column_name, &self.context, join_used.as_deref())
-                {
-                    if let Some(used) = join_used.as_mut() {
-                        used.insert(column_name.to_string());
-                    }
-                    return true;
+        // e.g. a view, a common table expression or an aliased subquery
+        if let Some(column_names) = self.context.alias_columns.get(&table_name) {
+            for column_name in column_names {
+                if fn_used(column_name, &self.context, join_used.as_deref()) {
+                    continue;
+                }
+                let Some(alias_expr) = self
+                    .context
+                    .expr_aliases
+                    .get(&(Some(table_name.to_string()), column_name.clone()))
+                else {
+                    continue;
+                };
+                if let Some(used) = join_used.as_mut() {
+                    used.insert(column_name.clone());
                 }
+                exprs.push(alias_expr.clone());
             }
-            false
-        }) {
-            is_bound_alias = true;
-            exprs.push(alias_expr.clone());
-        }
-        if is_bound_alias {
             return Ok(());
         }
+        let table = self.context.bind_table(&table_name, None)?;
 
-        let table = self
-            .context
-            .table(table_name.clone())
-            .ok_or(DatabaseError::TableNotFound)?;
         for column in table.columns() {
             let column_name = column.name();
 
@@ -529,27 +1312,46 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
             JoinOperator::CrossJoin => (JoinType::Cross, None),
             _ => unimplemented!(),
         };
-        let BinderContext {
-            table_cache,
-            transaction,
-            scala_functions,
-            table_functions,
-            temp_table_id,
-            ..
-        } = &self.context;
-        let mut binder = Binder::new(
-            BinderContext::new(
-                table_cache,
-                *transaction,
-                scala_functions,
-                table_functions,
-                temp_table_id.clone(),
-            ),
-            Some(self),
-        );
+        let mut context = self.context.sub_context();
+        context.work_table = self.context.work_table.take();
+        let mut binder = Binder::new(context, Some(self));
         let mut right = binder.bind_single_table_ref(relation, Some(join_type))?;
+        let correlated_columns = mem::take(&mut binder.context.correlated_columns);
         self.extend(binder.context);
 
+        // the columns on the left referenced by a `LATERAL` subquery or a table function,
+        // the others belong to the queries further out
+        let left_schema = left.output_schema().clone();
+        let (lateral_columns, outer_columns): (Vec<_>, Vec<_>) =
+            correlated_columns.into_iter().partition(|column| {
+                left_schema
+                    .iter()
+                    .any(|left_column| left_column.summary() == column.summary())
+            });
+        if self.parent.is_some() {
+            for column in outer_columns {
+                self.context.add_correlated_column(column);
+            }
+        }
+        if !lateral_columns.is_empty() {
+            if !is_lateral(relation) {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "`{}` references the tables before it in FROM without LATERAL",
+                    relation
+                )));
+            }
+            // Tips: the right side is run again for each row on the left
+            if !matches!(
+                join_type,
+                JoinType::Inner | JoinType::LeftOuter | JoinType::Cross
+            ) {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "LATERAL `{}` on the right of a {:?} join",
+                    relation, join_type
+                )));
+            }
+        }
+
         let on = match joint_condition {
             Some(constraint) => {
                 self.bind_join_constraint(left.output_schema(), right.output_schema(), constraint)?
@@ -557,7 +1359,13 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
             None => JoinCondition::None,
         };
 
-        Ok(LJoinOperator::build(left, right, on, join_type))
+        Ok(LJoinOperator::build_correlated(
+            left,
+            right,
+            on,
+            join_type,
+            lateral_columns,
+        ))
     }
 
     pub(crate) fn bind_where(
@@ -570,29 +1378,98 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
         let predicate = self.bind_expr(predicate)?;
 
         if let Some(sub_queries) = self.context.sub_queries_at_now() {
+            // `EXISTS (SUBQUERY)` is bound as `true`, which the joins have no use for
+            let mut predicates = vec![];
+            Self::split_conjunctions(predicate, &mut predicates);
+            predicates.retain(|expr| {
+                !matches!(expr, ScalarExpression::Constant(value) if value.as_ref() == &DataValue::Boolean(Some(true)))
+            });
+            // the predicates without subqueries filter the outer query before the joins,
+            // as they would otherwise decide whether a row matches a semi or anti join
+            let mut sub_queries = sub_queries;
+            let sub_query_columns = sub_queries
+                .iter_mut()
+                .flat_map(|sub_query| {
+                    let (SubQueryType::SubQuery(plan, _)
+                    | SubQueryType::InSubQuery(_, plan, _)
+                    | SubQueryType::ExistsSubQuery(_, plan, _)) = sub_query;
+
+                    plan.output_schema().iter().cloned().collect_vec()
+                })
+                .collect_vec();
+            let (predicates, outer_predicates): (Vec<_>, Vec<_>) =
+                predicates.into_iter().partition(|expr| {
+                    expr.referenced_columns(false).iter().any(|column| {
+                        sub_query_columns
+                            .iter()
+                            .any(|sub_query_column| sub_query_column.summary() == column.summary())
+                    })
+                });
+            if let Some(predicate) =
+                outer_predicates
+                    .into_iter()
+                    .reduce(|acc, expr| ScalarExpression::Binary {
+                        op: BinaryOperator::And,
+                        left_expr: Box::new(acc),
+                        right_expr: Box::new(expr),
+                        evaluator: None,
+                        ty: LogicalType::Boolean,
+                    })
+            {
+                children = FilterOperator::build(predicate, children, false);
+            }
+
             for sub_query in sub_queries {
+                if let SubQueryType::ExistsSubQuery(is_not, plan, _) = &sub_query {
+                    // the result of `EXISTS` is known without joining the subquery
+                    if let Some(is_empty) = Self::is_empty_sub_query(plan) {
+                        if is_empty != *is_not {
+                            let predicate = ScalarExpression::Constant(Arc::new(
+                                DataValue::Boolean(Some(false)),
+                            ));
+                            children = FilterOperator::build(predicate, children, false);
+                        }
+                        continue;
+                    }
+                }
                 let mut on_keys: Vec<(ScalarExpression, ScalarExpression)> = vec![];
                 let mut filter = vec![];
 
-                let (mut plan, join_ty) = match sub_query {
-                    SubQueryType::SubQuery(plan) => (plan, JoinType::Inner),
-                    SubQueryType::InSubQuery(is_not, plan) => {
+                let (mut plan, join_ty, correlated_columns, correlated_predicates) = match sub_query
+                {
+                    SubQueryType::SubQuery(plan, correlated_columns) => {
+                        (plan, JoinType::Inner, correlated_columns, vec![])
+                    }
+                    SubQueryType::InSubQuery(is_not, plan, correlated_columns) => {
+                        let join_ty = if is_not {
+                            JoinType::LeftAnti
+                        } else {
+                            JoinType::LeftSemi
+                        };
+                        (plan, join_ty, correlated_columns, vec![])
+                    }
+                    SubQueryType::ExistsSubQuery(is_not, plan, correlated_columns) => {
                         let join_ty = if is_not {
                             JoinType::LeftAnti
                         } else {
                             JoinType::LeftSemi
                         };
-                        (plan, join_ty)
+                        let (plan, correlated_predicates) =
+                            Self::pull_up_correlated_predicates(plan, &correlated_columns);
+
+                        (plan, join_ty, correlated_columns, correlated_predicates)
                     }
                 };
 
-                Self::extract_join_keys(
-                    predicate.clone(),
-                    &mut on_keys,
-                    &mut filter,
-                    children.output_schema(),
-                    plan.output_schema(),
-                )?;
+                for expr in predicates.iter().cloned().chain(correlated_predicates) {
+                    Self::extract_join_keys(
+                        expr,
+                        &mut on_keys,
+                        &mut filter,
+                        children.output_schema(),
+                        plan.output_schema(),
+                    )?;
+                }
 
                 // combine multiple filter exprs into one BinaryExpr
                 let join_filter = filter
@@ -605,7 +1482,7 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
                         ty: LogicalType::Boolean,
                     });
 
-                children = LJoinOperator::build(
+                children = LJoinOperator::build_correlated(
                     children,
                     plan,
                     JoinCondition::On {
@@ -613,6 +1490,7 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
                         filter: join_filter,
                     },
                     join_ty,
+                    correlated_columns,
                 );
             }
             return Ok(children);
@@ -644,7 +1522,43 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
         ))
     }
 
-    fn bind_sort(&mut self, children: LogicalPlan, sort_fields: Vec<SortField>) -> LogicalPlan {
+    /// The columns of the table written to by `UPDATE` or `DELETE`, which are the leading columns
+    /// of `plan`, as the columns of the table even when it is aliased.
+    pub(crate) fn bind_table_columns(
+        &mut self,
+        plan: &mut LogicalPlan,
+        table_name: TableName,
+    ) -> Result<Vec<ScalarExpression>, DatabaseError> {
+        let table = self
+            .context
+            .table(table_name)
+            .ok_or(DatabaseError::TableNotFound)?;
+
+        Ok(table
+            .columns()
+            .zip(plan.output_schema().iter())
+            .map(|(column, output_column)| {
+                let expr = ScalarExpression::ColumnRef(output_column.clone());
+
+                if column.summary() == output_column.summary() {
+                    expr
+                } else {
+                    ScalarExpression::Alias {
+                        expr: Box::new(expr),
+                        alias: AliasType::Expr(Box::new(ScalarExpression::ColumnRef(
+                            column.clone(),
+                        ))),
+                    }
+                }
+            })
+            .collect())
+    }
+
+    pub(crate) fn bind_sort(
+        &mut self,
+        children: LogicalPlan,
+        sort_fields: Vec<SortField>,
+    ) -> LogicalPlan {
         self.context.step(QueryBindStep::Sort);
 
         LogicalPlan::new(
@@ -742,10 +1656,10 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
         }
     }
 
-    fn bind_join_constraint<'c>(
+    fn bind_join_constraint(
         &mut self,
-        left_schema: &'c SchemaRef,
-        right_schema: &'c SchemaRef,
+        left_schema: &SchemaRef,
+        right_schema: &SchemaRef,
         constraint: &JoinConstraint,
     ) -> Result<JoinCondition, DatabaseError> {
         match constraint {
@@ -754,15 +1668,25 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
                 let mut on_keys: Vec<(ScalarExpression, ScalarExpression)> = vec![];
                 // expression that didn't match equi-join pattern
                 let mut filter = vec![];
-                let expr = self.bind_expr(expr)?;
-
-                Self::extract_join_keys(
-                    expr,
-                    &mut on_keys,
-                    &mut filter,
-                    left_schema,
-                    right_schema,
-                )?;
+                let mut predicates = vec![];
+                Self::split_conjunctions(self.bind_expr(expr)?, &mut predicates);
+
+                for predicate in predicates {
+                    let (keys_len, filter_len) = (on_keys.len(), filter.len());
+
+                    Self::extract_join_keys(
+                        predicate.clone(),
+                        &mut on_keys,
+                        &mut filter,
+                        left_schema,
+                        right_schema,
+                    )?;
+                    // e.g. `s.total > 10` of a derived table `s`, whose column is not in the
+                    // schema of either side by the column it is an alias of
+                    if on_keys.len() == keys_len && filter.len() == filter_len {
+                        filter.push(predicate);
+                    }
+                }
 
                 // combine multiple filter exprs into one BinaryExpr
                 let join_filter = filter
@@ -807,22 +1731,34 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
             }
             JoinConstraint::None => Ok(JoinCondition::None),
             JoinConstraint::Natural => {
-                let fn_names = |schema: &'c Schema| -> HashSet<&'c str> {
-                    schema.iter().map(|column| column.name()).collect()
-                };
                 let mut on_keys: Vec<(ScalarExpression, ScalarExpression)> = Vec::new();
 
-                for name in fn_names(left_schema).intersection(&fn_names(right_schema)) {
+                // Tips: in the order of the columns on the left, whose names may repeat after a join
+                for left_column in left_schema.iter().unique_by(|column| column.name()) {
+                    let name = left_column.name();
+                    let Some(right_column) =
+                        right_schema.iter().find(|column| column.name() == name)
+                    else {
+                        continue;
+                    };
+                    let (left_ty, right_ty) = (left_column.datatype(), right_column.datatype());
+                    LogicalType::max_logical_type(left_ty, right_ty).map_err(|_| {
+                        DatabaseError::JoinColumnTypeMismatch(
+                            name.to_string(),
+                            left_ty.clone(),
+                            right_ty.clone(),
+                        )
+                    })?;
                     self.context.add_using(name.to_string());
-                    if let (Some(left_column), Some(right_column)) = (
-                        left_schema.iter().find(|column| column.name() == *name),
-                        right_schema.iter().find(|column| column.name() == *name),
-                    ) {
-                        on_keys.push((
-                            ScalarExpression::ColumnRef(left_column.clone()),
-                            ScalarExpression::ColumnRef(right_column.clone()),
-                        ));
-                    }
+                    on_keys.push((
+                        ScalarExpression::ColumnRef(left_column.clone()),
+                        ScalarExpression::ColumnRef(right_column.clone()),
+                    ));
+                }
+                if on_keys.is_empty() {
+                    return Err(DatabaseError::InvalidColumn(
+                        "the tables of NATURAL JOIN have no column names in common".to_string(),
+                    ));
                 }
                 Ok(JoinCondition::On {
                     on: on_keys,
@@ -832,6 +1768,39 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
         }
     }
 
+    /// Joins a scalar subquery of the projection, which returns `NULL` without rows
+    /// and fails with more than one row, by running it for each row of the outer query.
+    fn bind_scalar_sub_query(plan: LogicalPlan, sub_query: SubQueryType) -> LogicalPlan {
+        let SubQueryType::SubQuery(sub_plan, correlated_columns) = sub_query else {
+            unreachable!("only scalar subqueries can be bound in the projection")
+        };
+
+        LJoinOperator::build_correlated(
+            plan,
+            sub_plan,
+            JoinCondition::None,
+            JoinType::LeftSingle,
+            correlated_columns,
+        )
+    }
+
+    /// Whether the subquery provably returns no rows, or provably returns some rows,
+    /// e.g. an aggregate without `GROUP BY` always returns a single row.
+    fn is_empty_sub_query(plan: &LogicalPlan) -> Option<bool> {
+        match &plan.operator {
+            Operator::Project(_) | Operator::Sort(_) => {
+                Self::is_empty_sub_query(&plan.childrens[0])
+            }
+            Operator::Limit(LimitOperator { limit: Some(0), .. }) => Some(true),
+            Operator::Limit(LimitOperator { offset: None, .. }) => {
+                Self::is_empty_sub_query(&plan.childrens[0])
+            }
+            Operator::Aggregate(op) if op.groupby_exprs.is_empty() => Some(false),
+            Operator::Dummy => Some(false),
+            _ => None,
+        }
+    }
+
     /// for sqlrs
     /// original idea from datafusion planner.rs
     /// Extracts equijoin ON condition be a single Eq or multiple conjunctive Eqs
@@ -843,6 +1812,78 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
     /// foo = bar AND bar = baz => accum=[(foo, bar), (bar, baz)] accum_filter=[]
     /// foo = bar AND baz > 1 => accum=[(foo, bar)] accum_filter=[baz > 1]
     /// ```
+    /// `EXISTS` only cares about whether the subquery returns rows, so its projections, sorts,
+    /// limits and groupings are dropped and the conjuncts of its `WHERE` referencing the outer query are returned to be
+    /// evaluated by the join instead.
+    fn pull_up_correlated_predicates(
+        mut plan: LogicalPlan,
+        correlated_columns: &[ColumnRef],
+    ) -> (LogicalPlan, Vec<ScalarExpression>) {
+        if correlated_columns.is_empty() {
+            return (plan, vec![]);
+        }
+        while match &plan.operator {
+            Operator::Project(_) | Operator::Sort(_) => true,
+            Operator::Limit(LimitOperator {
+                offset: None,
+                limit,
+            }) => limit != &Some(0),
+            Operator::Aggregate(op) => !op.groupby_exprs.is_empty(),
+            _ => false,
+        } {
+            plan = plan.childrens.remove(0);
+        }
+        let Operator::Filter(FilterOperator {
+            predicate,
+            having: false,
+        }) = &plan.operator
+        else {
+            return (plan, vec![]);
+        };
+        let mut conjunctions = vec![];
+        Self::split_conjunctions(predicate.clone(), &mut conjunctions);
+        let (correlated_predicates, predicates): (Vec<_>, Vec<_>) =
+            conjunctions.into_iter().partition(|expr| {
+                expr.referenced_columns(true)
+                    .iter()
+                    .any(|column| correlated_columns.contains(column))
+            });
+
+        match predicates
+            .into_iter()
+            .reduce(|acc, expr| ScalarExpression::Binary {
+                op: BinaryOperator::And,
+                left_expr: Box::new(acc),
+                right_expr: Box::new(expr),
+                evaluator: None,
+                ty: LogicalType::Boolean,
+            }) {
+            Some(predicate) => {
+                plan.operator = Operator::Filter(FilterOperator {
+                    predicate,
+                    having: false,
+                })
+            }
+            None => plan = plan.childrens.remove(0),
+        }
+        (plan, correlated_predicates)
+    }
+
+    fn split_conjunctions(expr: ScalarExpression, accum: &mut Vec<ScalarExpression>) {
+        match expr {
+            ScalarExpression::Binary {
+                op: BinaryOperator::And,
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                Self::split_conjunctions(*left_expr, accum);
+                Self::split_conjunctions(*right_expr, accum);
+            }
+            expr => accum.push(expr),
+        }
+    }
+
     fn extract_join_keys(
         expr: ScalarExpression,
         accum: &mut Vec<(ScalarExpression, ScalarExpression)>,
@@ -981,6 +2022,31 @@ impl<'a: 'b, 'b, T: Transaction> Binder<'a, 'b, T> {
     }
 }
 
+/// The type the values of two columns are both cast to when they are combined into one column,
+/// e.g. by a set operation or by `VALUES`.
+fn common_type(left: &LogicalType, right: &LogicalType) -> Result<LogicalType, DatabaseError> {
+    match (left, right) {
+        (
+            LogicalType::Varchar(Some(left_len), left_unit),
+            LogicalType::Varchar(Some(right_len), right_unit),
+        ) if left_unit == right_unit => Ok(LogicalType::Varchar(
+            Some(*left_len.max(right_len)),
+            *left_unit,
+        )),
+        (left, right) => binary_operand_type(&BinaryOperator::Eq, left, right),
+    }
+}
+
+/// Whether `relation` can reference the tables before it in `FROM`, as table functions always can.
+fn is_lateral(relation: &TableFactor) -> bool {
+    matches!(
+        relation,
+        TableFactor::Derived { lateral: true, .. }
+            | TableFactor::Table { args: Some(_), .. }
+            | TableFactor::TableFunction { .. }
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use crate::binder::test::select_sql_run;
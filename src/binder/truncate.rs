@@ -1,21 +1,24 @@
-use crate::binder::{lower_case_name, Binder};
+use crate::binder::Binder;
 use crate::errors::DatabaseError;
 use crate::planner::operator::truncate::TruncateOperator;
 use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
 use sqlparser::ast::ObjectName;
-use std::sync::Arc;
 
 impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
     pub(crate) fn bind_truncate(
         &mut self,
         name: &ObjectName,
+        restart_identity: bool,
     ) -> Result<LogicalPlan, DatabaseError> {
-        let table_name = Arc::new(lower_case_name(name)?);
+        let table_name = self.bind_table_name(name)?;
 
         Ok(LogicalPlan::new(
-            Operator::Truncate(TruncateOperator { table_name }),
+            Operator::Truncate(TruncateOperator {
+                table_name,
+                restart_identity,
+            }),
             vec![],
         ))
     }
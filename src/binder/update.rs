@@ -1,11 +1,13 @@
-use crate::binder::{lower_case_name, Binder};
+use crate::binder::Binder;
 use crate::errors::DatabaseError;
-use crate::expression::ScalarExpression;
+use crate::expression::{AliasType, ScalarExpression};
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
 use crate::planner::operator::update::UpdateOperator;
 use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::Transaction;
 use crate::types::value::DataValue;
+use crate::types::LogicalType;
 use sqlparser::ast::{Assignment, Expr, TableFactor, TableWithJoins};
 use std::slice;
 use std::sync::Arc;
@@ -14,63 +16,123 @@ impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
     pub(crate) fn bind_update(
         &mut self,
         to: &TableWithJoins,
+        from: &Option<TableWithJoins>,
         selection: &Option<Expr>,
         assignments: &[Assignment],
     ) -> Result<LogicalPlan, DatabaseError> {
         // FIXME: Make it better to detect the current BindStep
         self.context.allow_default = true;
         if let TableFactor::Table { name, .. } = &to.relation {
-            let table_name = Arc::new(lower_case_name(name)?);
+            let table_name = self.bind_table_name(name)?;
 
             let mut plan = self.bind_table_ref(to)?;
+            let is_joined = !to.joins.is_empty() || from.is_some();
 
+            // e.g. `UPDATE t1 SET c2 = t2.c4 FROM t2 WHERE t1.c1 = t2.c3`
+            if let Some(from) = from {
+                plan = JoinOperator::build(
+                    plan,
+                    self.bind_table_ref(from)?,
+                    JoinCondition::None,
+                    JoinType::Cross,
+                );
+            }
             if let Some(predicate) = selection {
                 plan = self.bind_where(plan, predicate)?;
             }
 
             let mut schema = Vec::with_capacity(assignments.len());
             let mut row = Vec::with_capacity(assignments.len());
+            // the values that differ by row, which are computed on top of `plan`
+            let mut row_exprs = Vec::new();
 
             for Assignment { id, value } in assignments {
                 let mut expression = self.bind_expr(value)?;
                 expression.constant_calculation()?;
+                // e.g. the struct value `(1, 'a')`
+                if let ScalarExpression::Tuple(_) | ScalarExpression::TypeCast { .. } = expression {
+                    if let Some(value) = expression.unpack_val() {
+                        expression = ScalarExpression::Constant(value);
+                    }
+                }
 
                 for ident in id {
                     match self.bind_column_ref_from_identifiers(
                         slice::from_ref(ident),
                         Some(table_name.to_string()),
                     )? {
-                        ScalarExpression::ColumnRef(column) => {
-                            match &expression {
-                                ScalarExpression::Constant(value) => {
-                                    let ty = column.datatype();
-                                    // Check if the value length is too long
-                                    value.check_len(ty)?;
+                        // Tips: the generated column is computed again from the new row anyway
+                        ScalarExpression::ColumnRef(column) if column.desc.generated.is_some() => {
+                            if !matches!(expression, ScalarExpression::Empty) {
+                                return Err(DatabaseError::GeneratedColumnWrite(
+                                    column.name().to_string(),
+                                ));
+                            }
+                        }
+                        ScalarExpression::ColumnRef(column) => match &expression {
+                            ScalarExpression::Constant(value) => {
+                                let ty = column.datatype();
+                                // Check if the value length is too long
+                                value.check_len(ty)?;
 
-                                    if value.logical_type() != *ty {
-                                        row.push(Arc::new(DataValue::clone(value).cast(ty)?));
-                                    }
+                                if value.logical_type() != *ty {
+                                    row.push(Arc::new(DataValue::clone(value).cast(ty)?));
+                                } else {
                                     row.push(value.clone());
                                 }
-                                ScalarExpression::Empty => {
-                                    let default_value = column
-                                        .default_value()?
-                                        .ok_or(DatabaseError::DefaultNotExist)?;
-                                    row.push(default_value);
+                                schema.push(column);
+                            }
+                            ScalarExpression::Empty => {
+                                let default_value = column
+                                    .default_value()?
+                                    .ok_or(DatabaseError::DefaultNotExist)?;
+                                row.push(default_value);
+                                schema.push(column);
+                            }
+                            expr => {
+                                let (from, to) = (expr.return_type(), column.datatype().clone());
+                                if !LogicalType::can_assign(&from, &to) {
+                                    return Err(DatabaseError::IncompatibleAssignment(
+                                        column.name().to_string(),
+                                        to,
+                                        from,
+                                    ));
+                                }
+                                let mut expr = expr.clone();
+                                if from != to {
+                                    expr = ScalarExpression::TypeCast {
+                                        expr: Box::new(expr),
+                                        ty: to,
+                                        is_try: false,
+                                    };
                                 }
-                                _ => return Err(DatabaseError::UnsupportedStmt(value.to_string())),
+                                row_exprs.push(ScalarExpression::Alias {
+                                    expr: Box::new(expr),
+                                    alias: AliasType::Name(column.name().to_string()),
+                                });
                             }
-                            schema.push(column);
-                        }
+                        },
                         _ => return Err(DatabaseError::InvalidColumn(ident.to_string())),
                     }
                 }
             }
             self.context.allow_default = false;
+            if is_joined || !row_exprs.is_empty() {
+                // the columns of the table followed by the new values of the row
+                let mut exprs = self.bind_table_columns(&mut plan, table_name.clone())?;
+                exprs.append(&mut row_exprs);
+                plan = self.bind_project(plan, exprs)?;
+            }
             let values_plan = self.bind_values(vec![row], Arc::new(schema));
+            let checks = self.bind_table_checks(table_name.clone())?;
+            let generated = self.bind_table_generated_columns(table_name.clone())?;
 
             Ok(LogicalPlan::new(
-                Operator::Update(UpdateOperator { table_name }),
+                Operator::Update(UpdateOperator {
+                    table_name,
+                    checks,
+                    generated,
+                }),
                 vec![plan, values_plan],
             ))
         } else {
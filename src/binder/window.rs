@@ -0,0 +1,132 @@
+use crate::binder::Binder;
+use crate::errors::DatabaseError;
+use crate::expression::agg::AggKind;
+use crate::expression::window::WindowKind;
+use crate::expression::ScalarExpression;
+use crate::planner::operator::sort::SortField;
+use crate::planner::operator::window::WindowOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::Transaction;
+use crate::types::LogicalType;
+use sqlparser::ast::{Function, OrderByExpr, WindowSpec};
+
+impl<'a, 'b, T: Transaction> Binder<'a, 'b, T> {
+    pub fn bind_window(
+        &mut self,
+        children: LogicalPlan,
+        window_calls: Vec<ScalarExpression>,
+    ) -> LogicalPlan {
+        WindowOperator::build(children, window_calls)
+    }
+
+    /// Binds `func OVER (window_spec)`, which is only allowed in the select list and `ORDER BY`,
+    /// over the default frame.
+    pub(crate) fn bind_window_function(
+        &mut self,
+        func: &Function,
+        window_spec: &WindowSpec,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        if !self.context.allow_window {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "window function `{}` is only allowed in the select list and ORDER BY",
+                func
+            )));
+        }
+        if window_spec.window_frame.is_some() {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "window frame in {}",
+                func
+            )));
+        }
+        // Tips: window functions can not be nested
+        self.context.allow_window = false;
+        let result = self.bind_window_call(func, window_spec);
+        self.context.allow_window = true;
+        let window_call = result?;
+
+        self.context.window_calls.push(window_call.clone());
+        Ok(window_call)
+    }
+
+    fn bind_window_call(
+        &mut self,
+        func: &Function,
+        window_spec: &WindowSpec,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let function_name = func.name.to_string().to_lowercase();
+
+        let (kind, args, ty) = if let Some(kind) = WindowKind::from_name(&function_name) {
+            if !func.args.is_empty() {
+                return Err(DatabaseError::MisMatch(
+                    "number of ranking window function parameters",
+                    "0",
+                ));
+            }
+            (kind, vec![], LogicalType::Bigint)
+        } else {
+            let agg_call = self.bind_function(&Function {
+                over: None,
+                ..func.clone()
+            })?;
+
+            match agg_call {
+                ScalarExpression::AggCall {
+                    kind: AggKind::Grouping,
+                    ..
+                } => {
+                    return Err(DatabaseError::UnsupportedStmt(format!(
+                        "GROUPING in window function: {}",
+                        func
+                    )))
+                }
+                ScalarExpression::AggCall {
+                    distinct: false,
+                    kind,
+                    args,
+                    filter: None,
+                    ty,
+                } => (WindowKind::Aggregate(kind), args, ty),
+                ScalarExpression::AggCall {
+                    filter: Some(_), ..
+                } => {
+                    return Err(DatabaseError::UnsupportedStmt(format!(
+                        "FILTER in window function: {}",
+                        func
+                    )))
+                }
+                ScalarExpression::AggCall { distinct: true, .. } => {
+                    return Err(DatabaseError::UnsupportedStmt(format!(
+                        "DISTINCT in window function: {}",
+                        func
+                    )))
+                }
+                _ => return Err(DatabaseError::NotFound("window function", function_name)),
+            }
+        };
+        let mut partition_by = Vec::with_capacity(window_spec.partition_by.len());
+        for expr in window_spec.partition_by.iter() {
+            partition_by.push(self.bind_expr(expr)?);
+        }
+        let mut order_by = Vec::with_capacity(window_spec.order_by.len());
+        for OrderByExpr {
+            expr,
+            asc,
+            nulls_first,
+        } in window_spec.order_by.iter()
+        {
+            order_by.push(SortField::new(
+                self.bind_expr(expr)?,
+                asc.map_or(true, |asc| asc),
+                nulls_first.unwrap_or(self.nulls_first),
+            ));
+        }
+
+        Ok(ScalarExpression::WindowCall {
+            kind,
+            args,
+            partition_by,
+            order_by,
+            ty,
+        })
+    }
+}
@@ -125,9 +125,66 @@ impl ColumnCatalog {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ColumnDesc {
     pub(crate) column_datatype: LogicalType,
-    pub(crate) is_primary: bool,
+    /// The position of the column in the primary key, e.g. `1` for `b` of `PRIMARY KEY (a, b)`
+    pub(crate) primary: Option<usize>,
     pub(crate) is_unique: bool,
+    /// `UNIQUE NULLS NOT DISTINCT`
+    pub(crate) nulls_not_distinct: bool,
+    /// `AUTO_INCREMENT` or `SERIAL`, the omitted values are taken from the sequence of the table
+    pub(crate) is_auto_increment: bool,
     pub(crate) default: Option<ScalarExpression>,
+    /// The default as written, e.g. `c2 * 2`, which is shown by `DESCRIBE` instead of the bound one
+    pub(crate) default_text: Option<String>,
+    /// The default evaluated by `ALTER TABLE ADD COLUMN`, which the rows written before the column
+    /// was added are read with instead of being rewritten, `None` for NULL
+    pub(crate) added_default: Option<ValueRef>,
+    /// The expression of `GENERATED ALWAYS AS (expr) STORED` kept as SQL, e.g. `a + b`, which is
+    /// bound again against the columns of the table whenever its rows are written
+    pub(crate) generated: Option<String>,
+    /// e.g. `amount in cents` of `COMMENT ON COLUMN t1.c2 IS 'amount in cents'`
+    pub(crate) comment: Option<String>,
+    /// `COMPRESSION lz4`, the values of the column are stored compressed
+    pub(crate) compression: Option<ColumnCompression>,
+}
+
+/// The compression of the stored values of a column, see [`Tuple::serialize_to`].
+///
+/// [`Tuple::serialize_to`]: crate::types::tuple::Tuple::serialize_to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColumnCompression {
+    Lz4,
+}
+
+impl ColumnCompression {
+    pub(crate) fn from_name(name: &str) -> Result<Self, DatabaseError> {
+        if name.eq_ignore_ascii_case("lz4") {
+            return Ok(ColumnCompression::Lz4);
+        }
+        Err(DatabaseError::UnsupportedStmt(format!(
+            "compression method: {}",
+            name
+        )))
+    }
+
+    /// The compressed bytes, which start with the id of the compression, so that
+    /// [`ColumnCompression::decompress`] does not depend on the column they are read by.
+    pub(crate) fn compress(&self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            ColumnCompression::Lz4 => {
+                let mut compressed = vec![0];
+                compressed.extend(lz4_flex::compress_prepend_size(bytes));
+                compressed
+            }
+        }
+    }
+
+    /// The bytes of [`ColumnCompression::compress`] decompressed, `None` if they are corrupted.
+    pub(crate) fn decompress(bytes: &[u8]) -> Option<Vec<u8>> {
+        match bytes.split_first()? {
+            (0, bytes) => lz4_flex::decompress_size_prepended(bytes).ok(),
+            _ => None,
+        }
+    }
 }
 
 impl ColumnDesc {
@@ -145,9 +202,30 @@ impl ColumnDesc {
 
         Ok(ColumnDesc {
             column_datatype,
-            is_primary,
+            primary: is_primary.then_some(0),
             is_unique,
+            nulls_not_distinct: false,
+            is_auto_increment: false,
             default,
+            default_text: None,
+            added_default: None,
+            generated: None,
+            comment: None,
+            compression: None,
         })
     }
+
+    pub(crate) fn is_primary(&self) -> bool {
+        self.primary.is_some()
+    }
+
+    /// The default as shown by `DESCRIBE`, e.g. `GENERATED ALWAYS AS (a + b) STORED` of a
+    /// generated column.
+    pub(crate) fn default_display(&self) -> Option<String> {
+        self.generated
+            .as_ref()
+            .map(|expr| format!("GENERATED ALWAYS AS ({}) STORED", expr))
+            .or_else(|| self.default_text.clone())
+            .or_else(|| self.default.as_ref().map(|expr| expr.to_string()))
+    }
 }
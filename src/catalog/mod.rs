@@ -2,6 +2,8 @@
 
 pub(crate) use self::column::*;
 pub(crate) use self::table::*;
+pub(crate) use self::view::*;
 
 pub mod column;
 pub mod table;
+pub mod view;
@@ -6,12 +6,51 @@ use std::{slice, vec};
 
 use crate::catalog::{ColumnCatalog, ColumnRef, ColumnRelation};
 use crate::errors::DatabaseError;
+use crate::function::json::JsonPath;
 use crate::types::index::{IndexMeta, IndexMetaRef, IndexType};
 use crate::types::tuple::SchemaRef;
 use crate::types::{ColumnId, LogicalType};
 
 pub type TableName = Arc<String>;
 
+/// The schema of the temporary tables of the session running the statement, e.g. `pg_temp` of
+/// `pg_temp.scratch`, which stands for the schema [`temp_schema`] of the session.
+pub const TEMP_SCHEMA: &str = "pg_temp";
+
+/// The schema of the temporary tables of the session `session_id`, e.g. `pg_temp_3.scratch` of
+/// `CREATE TEMPORARY TABLE scratch`, which only live as long as the
+/// [`Session`](crate::db::Session) creating them.
+pub(crate) fn temp_schema(session_id: i64) -> String {
+    format!("{}_{}", TEMP_SCHEMA, session_id)
+}
+
+/// The name of the temporary table `table_name` in `temp_schema`.
+pub(crate) fn temp_table_name(temp_schema: &str, table_name: &str) -> TableName {
+    Arc::new(format!("{}.{}", temp_schema, table_name))
+}
+
+/// Whether `schema` is the schema of the temporary tables of a session, e.g. `pg_temp_3`.
+pub(crate) fn is_temp_schema(schema: &str) -> bool {
+    schema
+        .strip_prefix(TEMP_SCHEMA)
+        .and_then(|schema| schema.strip_prefix('_'))
+        .is_some_and(|session_id| {
+            !session_id.is_empty() && session_id.bytes().all(|b| b.is_ascii_digit())
+        })
+}
+
+/// The schema of the temporary table `table_name`, `None` if it is not temporary.
+pub(crate) fn temp_schema_of(table_name: &str) -> Option<&str> {
+    table_name
+        .split_once('.')
+        .map(|(schema, _)| schema)
+        .filter(|schema| is_temp_schema(schema))
+}
+
+pub(crate) fn is_temp_table(table_name: &str) -> bool {
+    temp_schema_of(table_name).is_some()
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TableCatalog {
     pub(crate) name: TableName,
@@ -19,6 +58,12 @@ pub struct TableCatalog {
     column_idxs: BTreeMap<String, (ColumnId, usize)>,
     columns: BTreeMap<ColumnId, usize>,
     pub(crate) indexes: Vec<IndexMetaRef>,
+    pub(crate) checks: Vec<CheckConstraint>,
+    pub(crate) foreign_keys: Vec<ForeignKey>,
+    /// The foreign keys referencing the table, with the tables they belong to
+    pub(crate) referenced_by: Vec<(TableName, ForeignKey)>,
+    /// e.g. `orders fact table` of `COMMENT ON TABLE t1 IS 'orders fact table'`
+    pub(crate) comment: Option<String>,
 
     schema_ref: SchemaRef,
 }
@@ -27,13 +72,52 @@ pub struct TableCatalog {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TableMeta {
     pub(crate) table_name: TableName,
+    /// The defining query when the table stores a materialized view
+    pub(crate) view_query: Option<String>,
+    pub(crate) checks: Vec<CheckConstraint>,
+    pub(crate) foreign_keys: Vec<ForeignKey>,
+    pub(crate) comment: Option<String>,
+}
+
+/// A `CHECK` constraint of the table, whose predicate is kept as SQL to be bound again
+/// against the columns of the table whenever its rows are written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CheckConstraint {
+    pub(crate) name: String,
+    /// e.g. `c1 < c2`
+    pub(crate) expr: String,
+    /// The names of the columns referenced by the predicate
+    pub(crate) columns: Vec<String>,
+}
+
+/// A `UNIQUE` constraint on multiple columns of the table, e.g. `UNIQUE (c1, c2)`, whose
+/// combination of the values is unique.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UniqueKey {
+    pub(crate) name: String,
+    pub(crate) columns: Vec<String>,
+}
+
+/// A `FOREIGN KEY` of the table, whose column references the primary key or a unique column of
+/// the referenced table, which may be the table itself.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ForeignKey {
+    pub(crate) name: String,
+    pub(crate) column: String,
+    pub(crate) ref_table: TableName,
+    pub(crate) ref_column: String,
+    /// `DEFERRABLE INITIALLY DEFERRED`, which is checked once the transaction commits rather
+    /// than by each statement
+    pub(crate) deferred: bool,
 }
 
 impl TableCatalog {
     pub(crate) fn get_unique_index(&self, col_id: &ColumnId) -> Option<&IndexMetaRef> {
-        self.indexes
-            .iter()
-            .find(|meta| matches!(meta.ty, IndexType::Unique) && &meta.column_ids[0] == col_id)
+        self.indexes.iter().find(|meta| {
+            matches!(meta.ty, IndexType::Unique { .. })
+                && meta.column_ids == slice::from_ref(col_id)
+                && meta.json_path.is_empty()
+        })
     }
 
     #[allow(dead_code)]
@@ -64,6 +148,23 @@ impl TableCatalog {
         self.indexes.iter()
     }
 
+    pub(crate) fn checks(&self) -> slice::Iter<'_, CheckConstraint> {
+        self.checks.iter()
+    }
+
+    pub(crate) fn foreign_keys(&self) -> slice::Iter<'_, ForeignKey> {
+        self.foreign_keys.iter()
+    }
+
+    pub(crate) fn referenced_by(&self) -> slice::Iter<'_, (TableName, ForeignKey)> {
+        self.referenced_by.iter()
+    }
+
+    /// The position of the column in the tuples of the table.
+    pub(crate) fn get_column_index_by_name(&self, name: &str) -> Option<usize> {
+        self.column_idxs.get(name).map(|(_, i)| *i)
+    }
+
     pub fn schema_ref(&self) -> &SchemaRef {
         &self.schema_ref
     }
@@ -72,17 +173,34 @@ impl TableCatalog {
         self.columns.len()
     }
 
-    pub(crate) fn primary_key(&self) -> Result<(usize, &ColumnRef), DatabaseError> {
-        self.schema_ref
+    /// The columns of the primary key in the order of the key, with their positions in the tuples.
+    pub(crate) fn primary_keys(&self) -> Result<Vec<(usize, &ColumnRef)>, DatabaseError> {
+        let primary_keys = self
+            .schema_ref
             .iter()
             .enumerate()
-            .find(|(_, column)| column.desc.is_primary)
-            .ok_or(DatabaseError::PrimaryKeyNotFound)
+            .filter(|(_, column)| column.desc.is_primary())
+            .sorted_by_key(|(_, column)| column.desc.primary)
+            .collect_vec();
+
+        if primary_keys.is_empty() {
+            return Err(DatabaseError::PrimaryKeyNotFound);
+        }
+        Ok(primary_keys)
+    }
+
+    /// The positions of the columns of the primary key in the tuples, in the order of the key.
+    pub(crate) fn primary_key_indices(&self) -> Result<Vec<usize>, DatabaseError> {
+        Ok(self
+            .primary_keys()?
+            .into_iter()
+            .map(|(i, _)| i)
+            .collect_vec())
     }
 
     pub(crate) fn types(&self) -> Vec<LogicalType> {
         self.columns()
-            .map(|column| *column.datatype())
+            .map(|column| column.datatype().clone())
             .collect_vec()
     }
 
@@ -120,6 +238,7 @@ impl TableCatalog {
         name: String,
         column_ids: Vec<ColumnId>,
         ty: IndexType,
+        json_path: JsonPath,
     ) -> Result<&IndexMeta, DatabaseError> {
         for index in self.indexes.iter() {
             if index.name == name {
@@ -128,14 +247,19 @@ impl TableCatalog {
         }
 
         let index_id = self.indexes.last().map(|index| index.id + 1).unwrap_or(0);
-        let pk_ty = *self.primary_key()?.1.datatype();
+        let pk_types = self
+            .primary_keys()?
+            .into_iter()
+            .map(|(_, column)| column.datatype().clone())
+            .collect_vec();
         let index = IndexMeta {
             id: index_id,
             column_ids,
             table_name: self.name.clone(),
-            pk_ty,
+            pk_types,
             name,
             ty,
+            json_path,
         };
         self.indexes.push(Arc::new(index));
         Ok(self.indexes.last().unwrap())
@@ -153,6 +277,10 @@ impl TableCatalog {
             column_idxs: BTreeMap::new(),
             columns: BTreeMap::new(),
             indexes: vec![],
+            checks: vec![],
+            foreign_keys: vec![],
+            referenced_by: vec![],
+            comment: None,
             schema_ref: Arc::new(vec![]),
         };
         for col_catalog in columns.into_iter() {
@@ -166,6 +294,8 @@ impl TableCatalog {
         name: TableName,
         column_refs: Vec<ColumnRef>,
         indexes: Vec<IndexMetaRef>,
+        checks: Vec<CheckConstraint>,
+        foreign_keys: Vec<ForeignKey>,
     ) -> Result<TableCatalog, DatabaseError> {
         let mut column_idxs = BTreeMap::new();
         let mut columns = BTreeMap::new();
@@ -185,6 +315,10 @@ impl TableCatalog {
             column_idxs,
             columns,
             indexes,
+            checks,
+            foreign_keys,
+            referenced_by: vec![],
+            comment: None,
             schema_ref,
         })
     }
@@ -192,7 +326,13 @@ impl TableCatalog {
 
 impl TableMeta {
     pub(crate) fn empty(table_name: TableName) -> Self {
-        TableMeta { table_name }
+        TableMeta {
+            table_name,
+            view_query: None,
+            checks: vec![],
+            foreign_keys: vec![],
+            comment: None,
+        }
     }
 }
 
@@ -0,0 +1,11 @@
+use crate::catalog::TableName;
+use serde::{Deserialize, Serialize};
+
+/// A view, which is bound again from its defining query wherever it is referenced.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct View {
+    pub(crate) name: TableName,
+    /// The names given to the columns of the query, e.g. `CREATE VIEW v (a, b) AS ...`
+    pub(crate) columns: Vec<String>,
+    pub(crate) query: String,
+}
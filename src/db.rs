@@ -1,33 +1,71 @@
-use crate::binder::{command_type, Binder, BinderContext, CommandType};
-use crate::catalog::TableCatalog;
+use crate::binder::{
+    command_type, Binder, BinderContext, CommandType, DEFAULT_MAX_RECURSION_DEPTH,
+    DEFAULT_MAX_RECURSIVE_ITERATIONS,
+};
+use crate::catalog::{temp_schema, ForeignKey, TableCatalog, TableName};
 use crate::errors::DatabaseError;
-use crate::execution::{build_write, try_collect};
+use crate::execution::dml::foreign_key::check_deferred;
+use crate::execution::{
+    build_write, try_collect, try_collect_bounded, Executor, StatementDeadline,
+    StatementDeferredForeignKeys, StatementLastInsertId, StatementTime, StatementTimeZone,
+    StatementWorkMem,
+};
+use crate::expression::function::aggregate::AggregateFunctionImpl;
 use crate::expression::function::scala::ScalarFunctionImpl;
 use crate::expression::function::table::TableFunctionImpl;
 use crate::expression::function::FunctionSummary;
 use crate::function::current_date::CurrentDate;
+use crate::function::date_trunc::DateTrunc;
+use crate::function::gen_random_uuid::GenRandomUuid;
+use crate::function::last_insert_id::LastInsertId;
+use crate::function::now::Now;
 use crate::function::numbers::Numbers;
+use crate::function::regexp::Regexp;
+use crate::function::string::StringFunction;
 use crate::optimizer::heuristic::batch::HepBatchStrategy;
 use crate::optimizer::heuristic::optimizer::HepOptimizer;
 use crate::optimizer::rule::implementation::ImplementationRuleImpl;
 use crate::optimizer::rule::normalization::NormalizationRuleImpl;
-use crate::parser::parse_sql;
+use crate::parser::{parse_run_sql, ExtendedStatement, ParsedSql};
+use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::rocksdb::RocksStorage;
 use crate::storage::{StatisticsMetaCache, Storage, TableCache, Transaction};
+use crate::types::timestamp_tz::parse_time_zone;
 use crate::types::tuple::{SchemaRef, Tuple};
-use crate::utils::lru::ShardingLruCache;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use crate::utils::lru::{LruCache, ShardingLruCache};
 use ahash::HashMap;
+use chrono::FixedOffset;
 use parking_lot::lock_api::{ArcRwLockReadGuard, ArcRwLockWriteGuard};
-use parking_lot::{RawRwLock, RwLock};
-use sqlparser::ast::Statement;
+use parking_lot::{Mutex, RawRwLock, RwLock};
+use sqlparser::ast::{Expr, Ident, ObjectName, Statement, Value};
 use std::hash::RandomState;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicUsize;
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{iter, mem};
 
 pub(crate) type ScalaFunctions = HashMap<FunctionSummary, Arc<dyn ScalarFunctionImpl>>;
 pub(crate) type TableFunctions = HashMap<FunctionSummary, Arc<dyn TableFunctionImpl>>;
+pub(crate) type AggregateFunctions = HashMap<FunctionSummary, Arc<dyn AggregateFunctionImpl>>;
+
+// the sequence of the ids of the sessions, which no table shares as its name would be empty
+const SESSION_ID_SEQUENCE: &str = "pg_temp.";
+
+/// How long DDL waits for the metadata lock by default, see [`DataBaseBuilder::lock_timeout`].
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The user-defined and built-in functions available to the binder.
+#[derive(Default)]
+pub struct Functions {
+    pub(crate) scala: ScalaFunctions,
+    pub(crate) table: TableFunctions,
+    pub(crate) aggregate: AggregateFunctions,
+}
 
 #[allow(dead_code)]
 pub(crate) enum MetaDataLock {
@@ -35,20 +73,227 @@ pub(crate) enum MetaDataLock {
     Write(ArcRwLockWriteGuard<RawRwLock, ()>),
 }
 
+/// A message sent by `NOTIFY channel, 'payload'`, delivered to the subscribers of the channel
+/// once the transaction that sent it commits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub channel: String,
+    pub payload: String,
+}
+
+#[derive(Default)]
+pub(crate) struct Notifier {
+    subscribers: Mutex<HashMap<String, Vec<Sender<Notification>>>>,
+}
+
+impl Notifier {
+    fn subscribe(&self, channel: &str) -> Receiver<Notification> {
+        let (tx, rx) = mpsc::channel();
+
+        self.subscribers
+            .lock()
+            .entry(channel.to_string())
+            .or_default()
+            .push(tx);
+        rx
+    }
+
+    fn publish(&self, notifications: Vec<Notification>) {
+        if notifications.is_empty() {
+            return;
+        }
+        let mut subscribers = self.subscribers.lock();
+
+        for notification in notifications {
+            if let Some(senders) = subscribers.get_mut(&notification.channel) {
+                // Tips: the subscribers whose `Receiver` has been dropped are removed here
+                senders.retain(|sender| sender.send(notification.clone()).is_ok());
+            }
+        }
+    }
+}
+
+/// The results of queries kept by [`DataBaseBuilder::result_cache`], keyed by the normalized
+/// SQL and the schema version, and dropped once a referenced table is written or `ttl` expires.
+pub(crate) struct ResultCache {
+    ttl: Duration,
+    state: Mutex<ResultCacheState>,
+}
+
+struct ResultCacheState {
+    entries: LruCache<ResultCacheKey, CachedResult>,
+    // bumped by every committed write
+    generation: u64,
+    // bumped by every DDL, so results bound against an old schema are never returned
+    schema_version: u64,
+    table_generations: HashMap<String, u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ResultCacheKey {
+    schema_version: u64,
+    nulls_first: bool,
+    time_zone: FixedOffset,
+    // the temporary tables of the session shadow the tables of the same name
+    session_id: Option<i64>,
+    sql: String,
+}
+
+struct CachedResult {
+    tables: Vec<String>,
+    generation: u64,
+    created: Instant,
+    schema: SchemaRef,
+    tuples: Vec<Tuple>,
+}
+
+impl ResultCache {
+    fn new(capacity: usize, ttl: Duration) -> Result<Self, DatabaseError> {
+        Ok(ResultCache {
+            ttl,
+            state: Mutex::new(ResultCacheState {
+                entries: LruCache::new(capacity)?,
+                generation: 0,
+                schema_version: 0,
+                table_generations: Default::default(),
+            }),
+        })
+    }
+
+    fn key(&self, sql: String, settings: BindSettings) -> ResultCacheKey {
+        ResultCacheKey {
+            schema_version: self.state.lock().schema_version,
+            nulls_first: settings.nulls_first,
+            time_zone: settings.time_zone,
+            session_id: settings.session_id,
+            sql,
+        }
+    }
+
+    /// The generation to pass to [`ResultCache::put`], taken before the query runs.
+    fn generation(&self) -> u64 {
+        self.state.lock().generation
+    }
+
+    fn get(&self, key: &ResultCacheKey) -> Option<(SchemaRef, Vec<Tuple>)> {
+        let mut state = self.state.lock();
+        let ResultCacheState {
+            entries,
+            table_generations,
+            ..
+        } = &mut *state;
+
+        let result = entries.get(key)?;
+        let is_stale = result.created.elapsed() > self.ttl
+            || result.tables.iter().any(|table| {
+                table_generations
+                    .get(table)
+                    .is_some_and(|generation| *generation > result.generation)
+            });
+        if is_stale {
+            entries.remove(key);
+            return None;
+        }
+        Some((result.schema.clone(), result.tuples.clone()))
+    }
+
+    fn put(
+        &self,
+        key: ResultCacheKey,
+        generation: u64,
+        tables: Vec<String>,
+        schema: SchemaRef,
+        tuples: Vec<Tuple>,
+    ) {
+        // Tips: a table written while the query ran makes the result stale on the next `get`
+        let _ = self.state.lock().entries.put(
+            key,
+            CachedResult {
+                tables,
+                generation,
+                created: Instant::now(),
+                schema,
+                tuples,
+            },
+        );
+    }
+
+    fn invalidate(&self, tables: impl IntoIterator<Item = String>) {
+        let mut state = self.state.lock();
+
+        state.generation += 1;
+        let generation = state.generation;
+        for table in tables {
+            state.table_generations.insert(table, generation);
+        }
+    }
+
+    fn invalidate_schema(&self) {
+        self.state.lock().schema_version += 1;
+    }
+}
+
+/// Collects the tables read or written by `plan`.
+fn plan_tables(plan: &LogicalPlan, tables: &mut Vec<String>) {
+    let table_name = match &plan.operator {
+        Operator::TableScan(op) => Some(op.table_name.to_string()),
+        Operator::Insert(op) => Some(op.table_name.to_string()),
+        Operator::Update(op) => Some(op.table_name.to_string()),
+        Operator::Delete(op) => Some(op.table_name.to_string()),
+        Operator::Truncate(op) => Some(op.table_name.to_string()),
+        Operator::CopyFromFile(op) => Some(op.table.clone()),
+        _ => None,
+    };
+    if let Some(table_name) = table_name {
+        if !tables.contains(&table_name) {
+            tables.push(table_name);
+        }
+    }
+    for child in &plan.childrens {
+        plan_tables(child, tables);
+    }
+}
+
 pub struct DataBaseBuilder {
     path: PathBuf,
-    scala_functions: ScalaFunctions,
-    table_functions: TableFunctions,
+    functions: Functions,
+    max_result_rows: Option<usize>,
+    max_recursion_depth: usize,
+    max_recursive_iterations: usize,
+    statement_timeout: Option<Duration>,
+    work_mem: Option<usize>,
+    lock_timeout: Duration,
+    time_zone: FixedOffset,
+    result_cache: Option<(usize, Duration)>,
 }
 
 impl DataBaseBuilder {
     pub fn path(path: impl Into<PathBuf> + Send) -> Self {
         let mut builder = DataBaseBuilder {
             path: path.into(),
-            scala_functions: Default::default(),
-            table_functions: Default::default(),
+            functions: Default::default(),
+            max_result_rows: None,
+            max_recursion_depth: DEFAULT_MAX_RECURSION_DEPTH,
+            max_recursive_iterations: DEFAULT_MAX_RECURSIVE_ITERATIONS,
+            statement_timeout: None,
+            work_mem: None,
+            lock_timeout: DEFAULT_LOCK_TIMEOUT,
+            time_zone: FixedOffset::east_opt(0).unwrap(),
+            result_cache: None,
         };
         builder = builder.register_scala_function(CurrentDate::new());
+        builder = builder.register_scala_function(Now::new("now"));
+        builder = builder.register_scala_function(Now::new("current_timestamp"));
+        builder = builder.register_scala_function(DateTrunc::new(LogicalType::DateTime));
+        builder = builder.register_scala_function(DateTrunc::new(LogicalType::Date));
+        builder = builder.register_scala_function(GenRandomUuid::new());
+        builder = builder.register_scala_function(LastInsertId::new());
+        for function in StringFunction::all() {
+            builder = builder.register_scala_function(function);
+        }
+        for function in Regexp::all() {
+            builder = builder.register_scala_function(function);
+        }
         builder = builder.register_table_function(Numbers::new());
         builder
     }
@@ -56,14 +301,80 @@ impl DataBaseBuilder {
     pub fn register_scala_function(mut self, function: Arc<dyn ScalarFunctionImpl>) -> Self {
         let summary = function.summary().clone();
 
-        self.scala_functions.insert(summary, function);
+        self.functions.scala.insert(summary, function);
         self
     }
 
     pub fn register_table_function(mut self, function: Arc<dyn TableFunctionImpl>) -> Self {
         let summary = function.summary().clone();
 
-        self.table_functions.insert(summary, function);
+        self.functions.table.insert(summary, function);
+        self
+    }
+
+    /// Register an aggregate function, e.g. defined with
+    /// [`aggregate_function`](crate::aggregate_function), which can be called like the built-in
+    /// aggregates, with or without `GROUP BY`.
+    pub fn register_aggregate_function(mut self, function: Arc<dyn AggregateFunctionImpl>) -> Self {
+        let summary = function.summary().clone();
+
+        self.functions.aggregate.insert(summary, function);
+        self
+    }
+
+    /// Abort any query that would return more than `rows` rows with
+    /// [`DatabaseError::ResultTooLarge`], queries with a `LIMIT` within the cap are unaffected.
+    pub fn max_result_rows(mut self, rows: usize) -> Self {
+        self.max_result_rows = Some(rows);
+        self
+    }
+
+    /// Fail with [`DatabaseError::RecursionLimit`] when subqueries and set operations
+    /// nest deeper than `depth`, instead of overflowing the stack while binding.
+    pub fn max_recursion_depth(mut self, depth: usize) -> Self {
+        self.max_recursion_depth = depth;
+        self
+    }
+
+    /// Fail with [`DatabaseError::RecursiveIterationLimit`] when the recursive term of
+    /// `WITH RECURSIVE` still returns rows after `iterations` iterations.
+    pub fn max_recursive_iterations(mut self, iterations: usize) -> Self {
+        self.max_recursive_iterations = iterations;
+        self
+    }
+
+    /// Abort any query still running after `timeout` with [`DatabaseError::Timeout`],
+    /// can be changed per session with `SET statement_timeout`.
+    pub fn statement_timeout(mut self, timeout: Duration) -> Self {
+        self.statement_timeout = Some(timeout);
+        self
+    }
+
+    /// Abort any query whose sort, hash join or hash aggregation buffers more than `bytes` with
+    /// [`DatabaseError::WorkMemExceeded`], can be changed per session with `SET work_mem`.
+    pub fn work_mem(mut self, bytes: usize) -> Self {
+        self.work_mem = Some(bytes);
+        self
+    }
+
+    /// Fail DDL with [`DatabaseError::Deadlock`] once it has waited `timeout` for the open
+    /// transactions, e.g. one of another session on the same thread, which would never end.
+    pub fn lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = timeout;
+        self
+    }
+
+    /// The time zone the timestamps with time zone are displayed at and the timestamps without
+    /// time zone are taken at, UTC by default, can be changed per session with `SET TIME ZONE`.
+    pub fn time_zone(mut self, time_zone: FixedOffset) -> Self {
+        self.time_zone = time_zone;
+        self
+    }
+
+    /// Keep the results of up to `capacity` queries for `ttl`, a cached result is dropped as soon
+    /// as a table it reads is changed by a committed DML or any DDL runs.
+    pub fn result_cache(mut self, capacity: usize, ttl: Duration) -> Self {
+        self.result_cache = Some((capacity, ttl));
         self
     }
 
@@ -71,96 +382,214 @@ impl DataBaseBuilder {
         let storage = RocksStorage::new(self.path)?;
         let meta_cache = Arc::new(ShardingLruCache::new(256, 8, RandomState::new())?);
         let table_cache = Arc::new(ShardingLruCache::new(48, 4, RandomState::new())?);
+        let result_cache = self
+            .result_cache
+            .map(|(capacity, ttl)| ResultCache::new(capacity, ttl).map(Arc::new))
+            .transpose()?;
+        let mut transaction = storage.transaction()?;
+        transaction.upgrade_format()?;
+        transaction.seed_row_count_delta_id()?;
+        // e.g. the temporary tables of a process which exited without dropping its sessions
+        transaction.drop_temp_tables(&table_cache, None)?;
+        transaction.commit()?;
+        let settings = SessionSettings {
+            statement_timeout: self.statement_timeout,
+            work_mem: self.work_mem,
+            nulls_first: false,
+            time_zone: self.time_zone,
+            batch_checkpoint: None,
+        };
 
         Ok(Database {
             storage,
-            scala_functions: Arc::new(self.scala_functions),
-            table_functions: Arc::new(self.table_functions),
+            functions: Arc::new(self.functions),
             mdl: Arc::new(RwLock::new(())),
+            dropped_sessions: Default::default(),
+            notifier: Default::default(),
             meta_cache,
             table_cache,
+            max_result_rows: self.max_result_rows,
+            max_recursion_depth: self.max_recursion_depth,
+            max_recursive_iterations: self.max_recursive_iterations,
+            lock_timeout: self.lock_timeout,
+            settings,
+            result_cache,
+            run_session: Mutex::new(RunSession {
+                settings,
+                last_insert_id: 0,
+                transaction: None,
+            }),
         })
     }
 }
 
 pub struct Database<S: Storage> {
     pub(crate) storage: S,
-    scala_functions: Arc<ScalaFunctions>,
-    table_functions: Arc<TableFunctions>,
+    functions: Arc<Functions>,
     mdl: Arc<RwLock<()>>,
+    // the ids of the sessions dropped while the metadata lock was held for reading, whose
+    // temporary tables are dropped by the next DDL
+    dropped_sessions: Mutex<Vec<i64>>,
+    notifier: Arc<Notifier>,
     pub(crate) meta_cache: Arc<StatisticsMetaCache>,
     pub(crate) table_cache: Arc<ShardingLruCache<String, TableCatalog>>,
+    max_result_rows: Option<usize>,
+    max_recursion_depth: usize,
+    max_recursive_iterations: usize,
+    // how long DDL waits for the metadata lock
+    lock_timeout: Duration,
+    // the settings every session starts with
+    settings: SessionSettings,
+    result_cache: Option<Arc<ResultCache>>,
+    // the session of `Database::run`, shared by its callers
+    run_session: Mutex<RunSession<S>>,
+}
+
+/// The state of the session of [`Database::run`] kept between its calls.
+struct RunSession<S: Storage> {
+    settings: SessionSettings,
+    last_insert_id: i64,
+    // opened by `BEGIN` and kept until `COMMIT` or `ROLLBACK`
+    transaction: Option<SessionTransaction<S>>,
 }
 
 impl<S: Storage> Database<S> {
-    /// Run SQL queries.
+    /// Run SQL queries in the session of the database, whose `BEGIN` opens a transaction
+    /// spanning the following calls until `COMMIT` or `ROLLBACK`, and whose `SET` applies to them.
+    ///
+    /// The session is shared by every caller of the database, whose statements join the open
+    /// transaction, see [`Database::session`] for sessions of their own.
     pub fn run<T: AsRef<str>>(&self, sql: T) -> Result<(SchemaRef, Vec<Tuple>), DatabaseError> {
-        // parse
-        let stmts = parse_sql(sql)?;
-        if stmts.is_empty() {
-            return Err(DatabaseError::EmptyStatement);
-        }
-        let stmt = &stmts[0];
-        let _guard = if matches!(command_type(stmt)?, CommandType::DDL) {
-            MetaDataLock::Write(self.mdl.write_arc())
-        } else {
-            MetaDataLock::Read(self.mdl.read_arc())
+        let mut run_session = self.run_session.lock();
+        let (settings, last_insert_id) = (run_session.settings, run_session.last_insert_id);
+        let transaction = run_session.transaction.take();
+        // Tips: the statements out of a transaction run at the same time as the ones of the
+        // other callers, the ones in the transaction one after another
+        let run_session = transaction.is_some().then_some(run_session);
+
+        let mut session = Session {
+            database: self,
+            id: None,
+            settings,
+            last_insert_id,
+            transaction,
         };
-        let mut transaction = self.storage.transaction()?;
-        let mut plan = Self::build_plan(
-            stmt,
-            &self.table_cache,
-            &self.meta_cache,
-            &transaction,
-            &self.scala_functions,
-            &self.table_functions,
-        )?;
+        let result = session.run_with(sql, false);
+        let transaction = session.transaction.take();
+        let mut run_session = run_session.unwrap_or_else(|| self.run_session.lock());
 
-        let schema = plan.output_schema().clone();
-        let iterator = build_write(
-            plan,
-            (&self.table_cache, &self.meta_cache),
-            &mut transaction,
-        );
-        let tuples = try_collect(iterator)?;
+        if session.settings != settings {
+            run_session.settings = session.settings;
+        }
+        if session.last_insert_id != last_insert_id {
+            run_session.last_insert_id = session.last_insert_id;
+        }
+        if transaction.is_some() {
+            if run_session.transaction.is_some() {
+                // e.g. another caller ran `BEGIN` at the same time, this one is rolled back
+                return Err(DatabaseError::TransactionAlreadyExists);
+            }
+            run_session.transaction = transaction;
+        }
+        result
+    }
 
-        transaction.commit()?;
+    /// Open a session of its own, e.g. for a connection, whose `BEGIN` opens a transaction
+    /// spanning the following [`Session::run`] calls until `COMMIT` or `ROLLBACK`.
+    pub fn session(&self) -> Session<'_, S> {
+        Session {
+            database: self,
+            id: None,
+            settings: self.settings,
+            last_insert_id: 0,
+            transaction: None,
+        }
+    }
 
-        Ok((schema, tuples))
+    /// Receive the notifications sent to `channel` by committed transactions.
+    pub fn subscribe(&self, channel: &str) -> Receiver<Notification> {
+        self.notifier.subscribe(channel)
     }
 
     pub fn new_transaction(&self) -> Result<DBTransaction<S>, DatabaseError> {
-        let guard = self.mdl.read_arc();
-        let transaction = self.storage.transaction()?;
+        let mut session = self.session();
+        session.begin()?;
 
-        Ok(DBTransaction {
-            inner: transaction,
-            scala_functions: self.scala_functions.clone(),
-            table_functions: self.table_functions.clone(),
-            _guard: guard,
-            meta_cache: self.meta_cache.clone(),
-            table_cache: self.table_cache.clone(),
-        })
+        Ok(DBTransaction { session })
     }
 
-    pub(crate) fn build_plan(
-        stmt: &Statement,
+    /// Takes the metadata lock for writing, failing with [`DatabaseError::Deadlock`] once it
+    /// has waited for `lock_timeout`.
+    fn write_mdl(&self) -> Result<ArcRwLockWriteGuard<RawRwLock, ()>, DatabaseError> {
+        self.mdl
+            .try_write_arc_for(self.lock_timeout)
+            .ok_or_else(|| {
+                DatabaseError::Deadlock(format!(
+                    "`DDL` waited {:?} for the open transactions",
+                    self.lock_timeout
+                ))
+            })
+    }
+
+    /// Drops the temporary tables of the sessions, while the metadata lock is held for writing.
+    fn drop_temp_tables(&self, ids: impl IntoIterator<Item = i64>) {
+        for id in ids {
+            if let Ok(mut transaction) = self.storage.transaction() {
+                if transaction
+                    .drop_temp_tables(&self.table_cache, Some(&temp_schema(id)))
+                    .is_ok()
+                {
+                    let _ = transaction.commit();
+                }
+            }
+        }
+        if let Some(cache) = &self.result_cache {
+            cache.invalidate_schema();
+        }
+    }
+
+    /// Collects the results, whose timestamps with time zone are rendered at `time_zone`, the
+    /// time zone of the session, see [`DataValue::at_time_zone`].
+    fn collect(
+        executor: Executor,
+        max_result_rows: Option<usize>,
+        time_zone: FixedOffset,
+    ) -> Result<Vec<Tuple>, DatabaseError> {
+        let mut tuples = match max_result_rows {
+            Some(max_rows) => try_collect_bounded(executor, max_rows),
+            None => try_collect(executor),
+        }?;
+        for tuple in tuples.iter_mut() {
+            for value in tuple.values.iter_mut() {
+                *value = DataValue::at_time_zone(value, time_zone);
+            }
+        }
+        Ok(tuples)
+    }
+
+    pub(crate) fn build_plan<'t>(
+        bind: impl FnOnce(
+            &mut Binder<'_, '_, <S as Storage>::TransactionType<'t>>,
+        ) -> Result<LogicalPlan, DatabaseError>,
         table_cache: &TableCache,
         meta_cache: &StatisticsMetaCache,
-        transaction: &<S as Storage>::TransactionType<'_>,
-        scala_functions: &ScalaFunctions,
-        table_functions: &TableFunctions,
+        transaction: &<S as Storage>::TransactionType<'t>,
+        functions: &Functions,
+        settings: BindSettings,
     ) -> Result<LogicalPlan, DatabaseError> {
         let mut binder = Binder::new(
             BinderContext::new(
                 table_cache,
                 transaction,
-                scala_functions,
-                table_functions,
+                functions,
                 Arc::new(AtomicUsize::new(0)),
             ),
             None,
-        );
+        )
+        .max_recursion_depth(settings.max_recursion_depth)
+        .max_recursive_iterations(settings.max_recursive_iterations)
+        .nulls_first(settings.nulls_first)
+        .temp_schema(settings.session_id.map(temp_schema));
         /// Build a logical plan.
         ///
         /// SELECT a,b FROM t1 ORDER BY a LIMIT 1;
@@ -168,7 +597,7 @@ impl<S: Storage> Database<S> {
         ///   Sort(a)
         ///     Limit(1)
         ///       Project(a,b)
-        let source_plan = binder.bind(stmt)?;
+        let source_plan = bind(&mut binder)?;
         // println!("source_plan plan: {:#?}", source_plan);
 
         let best_plan = Self::default_optimizer(source_plan)
@@ -180,6 +609,11 @@ impl<S: Storage> Database<S> {
 
     pub(crate) fn default_optimizer(source_plan: LogicalPlan) -> HepOptimizer {
         HepOptimizer::new(source_plan)
+            .batch(
+                "Decorrelate Subquery".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![NormalizationRuleImpl::DecorrelateSubquery],
+            )
             .batch(
                 "Column Pruning".to_string(),
                 HepBatchStrategy::once_topdown(),
@@ -198,9 +632,15 @@ impl<S: Storage> Database<S> {
                 HepBatchStrategy::fix_point_topdown(10),
                 vec![
                     NormalizationRuleImpl::PushPredicateThroughJoin,
+                    NormalizationRuleImpl::PushOrPredicateIntoUnion,
                     NormalizationRuleImpl::PushPredicateIntoScan,
                 ],
             )
+            .batch(
+                "Sort Pushdown".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![NormalizationRuleImpl::PushSortIntoIndexScan],
+            )
             .batch(
                 "Limit Pushdown".to_string(),
                 HepBatchStrategy::fix_point_topdown(10),
@@ -219,6 +659,14 @@ impl<S: Storage> Database<S> {
                     NormalizationRuleImpl::CombineFilter,
                 ],
             )
+            .batch(
+                "Aggregate Pushdown".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![
+                    NormalizationRuleImpl::PushAggregateThroughJoin,
+                    NormalizationRuleImpl::PushMinMaxIntoIndexScan,
+                ],
+            )
             .batch(
                 "Expression Remapper".to_string(),
                 HepBatchStrategy::once_topdown(),
@@ -251,6 +699,7 @@ impl<S: Storage> Database<S> {
                 ImplementationRuleImpl::Update,
                 // DLL
                 ImplementationRuleImpl::AddColumn,
+                ImplementationRuleImpl::AlterColumn,
                 ImplementationRuleImpl::CreateTable,
                 ImplementationRuleImpl::DropColumn,
                 ImplementationRuleImpl::DropTable,
@@ -259,118 +708,710 @@ impl<S: Storage> Database<S> {
     }
 }
 
-pub struct DBTransaction<'a, S: Storage + 'a> {
-    inner: S::TransactionType<'a>,
-    scala_functions: Arc<ScalaFunctions>,
-    table_functions: Arc<TableFunctions>,
+/// The settings of a session changed by `SET`, which start from the ones of the
+/// [`DataBaseBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SessionSettings {
+    // `None` when the statements are never aborted, set by `SET statement_timeout`
+    statement_timeout: Option<Duration>,
+    // `None` when the buffers of the executors are unbounded, set by `SET work_mem`
+    work_mem: Option<usize>,
+    nulls_first: bool,
+    time_zone: FixedOffset,
+    // every how many rows `Session::execute_batch` commits, set by `SET batch_checkpoint`
+    batch_checkpoint: Option<usize>,
+}
+
+/// The settings of a session that apply while binding a statement.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BindSettings {
+    max_recursion_depth: usize,
+    max_recursive_iterations: usize,
+    // the default of `ORDER BY` without `NULLS FIRST | LAST`, set by `SET null_ordering`
+    nulls_first: bool,
+    // the time zone of the timestamps with time zone, set by `SET TIME ZONE`
+    time_zone: FixedOffset,
+    // the id of the session naming the schema of its temporary tables
+    session_id: Option<i64>,
+}
+
+/// Parses the value of `statement_timeout`: a number of milliseconds, or a string
+/// with an optional unit such as `'5s'`, `'100ms'` or `'1min'`.
+fn parse_timeout(value: &[Expr]) -> Option<Duration> {
+    let text = match value {
+        [Expr::Value(Value::Number(number, _))] => number.as_str(),
+        [Expr::Value(Value::SingleQuotedString(text))] => text.trim(),
+        _ => return None,
+    };
+    let split = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (amount, unit) = text.split_at(split);
+    let amount = amount.parse::<f64>().ok()?;
+    let millis = match unit.trim() {
+        "us" => amount / 1000.0,
+        "" | "ms" => amount,
+        "s" => amount * 1000.0,
+        "min" => amount * 60_000.0,
+        "h" => amount * 3_600_000.0,
+        "d" => amount * 86_400_000.0,
+        _ => return None,
+    };
+
+    Duration::try_from_secs_f64(millis / 1000.0).ok()
+}
+
+/// Parses the value of `work_mem`: a number of kilobytes, or a string with an optional unit such
+/// as `'64MB'` or `'512kB'`, into bytes.
+fn parse_work_mem(value: &[Expr]) -> Option<usize> {
+    let text = match value {
+        [Expr::Value(Value::Number(number, _))] => number.as_str(),
+        [Expr::Value(Value::SingleQuotedString(text))] => text.trim(),
+        _ => return None,
+    };
+    let split = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (amount, unit) = text.split_at(split);
+    let amount = amount.parse::<usize>().ok()?;
+    let unit = match unit.trim() {
+        "B" => 1,
+        "" | "kB" => 1 << 10,
+        "MB" => 1 << 20,
+        "GB" => 1 << 30,
+        _ => return None,
+    };
+
+    amount.checked_mul(unit)
+}
+
+fn display_values(value: &[Expr]) -> String {
+    value
+        .iter()
+        .map(|expr| expr.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A connection to a [`Database`], whose `BEGIN` opens a transaction spanning the following
+/// [`Session::run`] calls until `COMMIT` or `ROLLBACK`, the transaction still open when the
+/// session is dropped is rolled back.
+pub struct Session<'a, S: Storage + 'a> {
+    database: &'a Database<S>,
+    // the id naming the schema of the temporary tables, assigned by the first DDL of the session
+    id: Option<i64>,
+    settings: SessionSettings,
+    // the value of `last_insert_id()`, set by the inserts into auto-increment columns
+    last_insert_id: i64,
+    transaction: Option<SessionTransaction<S>>,
+}
+
+/// The transaction of a session, which owns a clone of the storage it borrows, so that it may be
+/// kept by the [`Database`] between the calls of [`Database::run`].
+struct SessionTransaction<S: Storage> {
+    // Tips: declared before `_storage`, so that it is dropped before the storage it borrows
+    inner: S::TransactionType<'static>,
+    // the metadata lock is held until the transaction ends, so that no DDL of the other sessions
+    // changes the tables between its statements
     _guard: ArcRwLockReadGuard<RawRwLock, ()>,
-    pub(crate) meta_cache: Arc<StatisticsMetaCache>,
-    pub(crate) table_cache: Arc<ShardingLruCache<String, TableCatalog>>,
+    notifications: Vec<Notification>,
+    // the tables to invalidate in the result cache once committed
+    written_tables: Vec<String>,
+    // the foreign keys initially deferred of the rows written, which are checked once committed
+    deferred_foreign_keys: Vec<(TableName, ForeignKey)>,
+    _storage: Arc<S>,
 }
 
-impl<S: Storage> DBTransaction<'_, S> {
+impl<S: Storage> SessionTransaction<S> {
+    fn begin(database: &Database<S>) -> Result<Self, DatabaseError> {
+        let guard = database.mdl.read_arc();
+        let storage = Arc::new(database.storage.clone());
+        // SAFETY: the transaction only borrows the storage of the `Arc` kept with it, which is
+        // neither moved nor dropped before the transaction
+        let inner = unsafe { &*Arc::as_ptr(&storage) }.transaction()?;
+
+        Ok(SessionTransaction {
+            inner,
+            _guard: guard,
+            notifications: vec![],
+            written_tables: vec![],
+            deferred_foreign_keys: vec![],
+            _storage: storage,
+        })
+    }
+}
+
+impl<'a, S: Storage> Session<'a, S> {
+    /// Run SQL queries, in the transaction of the session if one is open.
     pub fn run<T: AsRef<str>>(&mut self, sql: T) -> Result<(SchemaRef, Vec<Tuple>), DatabaseError> {
-        let stmts = parse_sql(sql)?;
+        self.run_with(sql, true)
+    }
+
+    /// Runs the DML `sql` once for each of `params`, whose values are bound to its placeholders
+    /// `$1`, `$2`... in their order, e.g. `INSERT INTO t VALUES ($1, $2)`.
+    ///
+    /// `sql` is parsed once and the rows are run in a transaction of their own, so that a failing
+    /// row rolls back the whole batch and is reported by its index, unless `SET batch_checkpoint =
+    /// n` commits every `n` rows, which only rolls back the rows since the last commit.
+    pub fn execute_batch<T: AsRef<str>>(
+        &mut self,
+        sql: T,
+        params: &[Vec<DataValue>],
+    ) -> Result<(), DatabaseError> {
+        let stmts = match parse_run_sql(sql)? {
+            ParsedSql::Extended(stmt) => {
+                return Err(DatabaseError::UnsupportedStmt(format!("{:?}", stmt)))
+            }
+            ParsedSql::Statements(stmts) => stmts,
+        };
+        let stmt = match stmts.as_slice() {
+            [] => return Err(DatabaseError::EmptyStatement),
+            [stmt] => stmt,
+            [_, stmt, ..] => return Err(DatabaseError::UnsupportedStmt(stmt.to_string())),
+        };
+        if !matches!(command_type(stmt)?, CommandType::DML) {
+            return Err(DatabaseError::UnsupportedStmt(stmt.to_string()));
+        }
+        self.begin()?;
+
+        for (i, row) in params.iter().enumerate() {
+            if let Err(err) = self.execute_with(|binder| {
+                binder.set_parameters(row.clone());
+                binder.bind(stmt)
+            }) {
+                self.transaction = None;
+
+                return Err(DatabaseError::BatchFailed(i, Box::new(err)));
+            }
+            if self
+                .settings
+                .batch_checkpoint
+                .is_some_and(|rows| (i + 1) % rows == 0)
+            {
+                self.commit()
+                    .and_then(|_| self.begin())
+                    .map_err(|err| DatabaseError::BatchFailed(i, Box::new(err)))?;
+            }
+        }
+        self.commit()
+    }
+
+    fn run_with<T: AsRef<str>>(
+        &mut self,
+        sql: T,
+        temp_tables: bool,
+    ) -> Result<(SchemaRef, Vec<Tuple>), DatabaseError> {
+        // parse
+        let stmts = match parse_run_sql(sql)? {
+            ParsedSql::Extended(stmt) => return self.run_extended(stmt),
+            ParsedSql::Statements(stmts) => stmts,
+        };
         if stmts.is_empty() {
             return Err(DatabaseError::EmptyStatement);
         }
         let stmt = &stmts[0];
-        if matches!(command_type(stmt)?, CommandType::DDL) {
-            return Err(DatabaseError::UnsupportedStmt(
-                "`DDL` is not allowed to execute within a transaction".to_string(),
-            ));
+        if let Statement::SetVariable {
+            variable, value, ..
+        } = stmt
+        {
+            self.set_variable(variable, value)?;
+
+            return Ok((Arc::new(vec![]), vec![]));
         }
-        let mut plan = Database::<S>::build_plan(
-            stmt,
-            &self.table_cache,
-            &self.meta_cache,
-            &self.inner,
-            &self.scala_functions,
-            &self.table_functions,
-        )?;
+        if let Statement::SetTimeZone { value, .. } = stmt {
+            self.set_variable(
+                &ObjectName(vec![Ident::new("timezone")]),
+                std::slice::from_ref(value),
+            )?;
 
-        let schema = plan.output_schema().clone();
-        let executor = build_write(plan, (&self.table_cache, &self.meta_cache), &mut self.inner);
+            return Ok((Arc::new(vec![]), vec![]));
+        }
+        let command_type = command_type(stmt)?;
+        if matches!(command_type, CommandType::TCL) {
+            self.run_tcl(stmt)?;
 
-        Ok((schema, try_collect(executor)?))
+            return Ok((Arc::new(vec![]), vec![]));
+        }
+        if self.transaction.is_some() {
+            if matches!(command_type, CommandType::DDL) {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "`DDL` is not allowed to execute within a transaction".to_string(),
+                ));
+            }
+            return self.execute_with(|binder| binder.bind(stmt));
+        }
+        self.execute(stmt, command_type, temp_tables)
     }
 
-    pub fn commit(self) -> Result<(), DatabaseError> {
-        self.inner.commit()?;
+    /// Runs a statement `sqlparser` does not support yet, in the transaction of the session if
+    /// one is open.
+    fn run_extended(
+        &mut self,
+        stmt: ExtendedStatement,
+    ) -> Result<(SchemaRef, Vec<Tuple>), DatabaseError> {
+        match stmt {
+            ExtendedStatement::Notify { channel, payload } => {
+                self.notify(Notification { channel, payload });
 
-        Ok(())
+                Ok((Arc::new(vec![]), vec![]))
+            }
+            ExtendedStatement::Refresh { name } => {
+                self.execute_in_transaction(|binder| binder.bind_refresh(&name))
+            }
+            ExtendedStatement::Truncate {
+                name,
+                restart_identity,
+            } => {
+                self.execute_in_transaction(|binder| binder.bind_truncate(&name, restart_identity))
+            }
+        }
     }
-}
-
-#[cfg(test)]
-pub(crate) mod test {
-    use crate::catalog::{ColumnCatalog, ColumnDesc};
-    use crate::db::{DataBaseBuilder, DatabaseError};
-    use crate::storage::{Storage, TableCache, Transaction};
-    use crate::types::tuple::{create_table, Tuple};
-    use crate::types::value::DataValue;
-    use crate::types::LogicalType;
-    use chrono::{Datelike, Local};
-    use std::sync::Arc;
-    use tempfile::TempDir;
-
-    pub(crate) fn build_table<T: Transaction>(
-        table_cache: &TableCache,
-        transaction: &mut T,
-    ) -> Result<(), DatabaseError> {
-        let columns = vec![
-            ColumnCatalog::new(
-                "c1".to_string(),
-                false,
-                ColumnDesc::new(LogicalType::Integer, true, false, None).unwrap(),
-            ),
-            ColumnCatalog::new(
-                "c2".to_string(),
-                false,
-                ColumnDesc::new(LogicalType::Boolean, false, false, None).unwrap(),
-            ),
-            ColumnCatalog::new(
-                "c3".to_string(),
-                false,
-                ColumnDesc::new(LogicalType::Integer, false, false, None).unwrap(),
-            ),
-        ];
-        let _ =
-            transaction.create_table(table_cache, Arc::new("t1".to_string()), columns, false)?;
 
-        Ok(())
-    }
+    /// Runs `stmt` in a transaction of its own, whose result may be taken from the result cache.
+    fn execute(
+        &mut self,
+        stmt: &Statement,
+        command_type: CommandType,
+        temp_tables: bool,
+    ) -> Result<(SchemaRef, Vec<Tuple>), DatabaseError> {
+        let database = self.database;
+        let _guard = if matches!(command_type, CommandType::DDL) {
+            let guard = database.write_mdl()?;
+            let dropped_sessions = mem::take(&mut *database.dropped_sessions.lock());
 
-    #[test]
-    fn test_run_sql() -> Result<(), DatabaseError> {
-        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
-        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+            if !dropped_sessions.is_empty() {
+                database.drop_temp_tables(dropped_sessions);
+            }
+            MetaDataLock::Write(guard)
+        } else {
+            MetaDataLock::Read(database.mdl.read_arc())
+        };
+        // e.g. `CREATE TEMPORARY TABLE`, whose table is in the schema named by the id
+        if matches!(command_type, CommandType::DDL) && temp_tables && self.id.is_none() {
+            self.id = Some(Self::next_id(database)?);
+        }
+        let settings = self.bind_settings();
+        let cached = database
+            .result_cache
+            .as_ref()
+            .filter(|_| matches!(stmt, Statement::Query(_)))
+            .map(|cache| {
+                (
+                    cache,
+                    cache.key(stmt.to_string(), settings),
+                    cache.generation(),
+                )
+            });
+        if let Some((cache, key, _)) = &cached {
+            if let Some(result) = cache.get(key) {
+                return Ok(result);
+            }
+        }
+        let _deadline = StatementDeadline::start(self.settings.statement_timeout);
+        let _work_mem = StatementWorkMem::start(self.settings.work_mem);
+        let statement_time = StatementTime::start();
+        let _time_zone = StatementTimeZone::start(settings.time_zone);
+        let _last_insert_id = StatementLastInsertId::start(&mut self.last_insert_id);
         let mut transaction = database.storage.transaction()?;
+        let mut plan = Database::<S>::build_plan(
+            |binder| binder.bind(stmt),
+            &database.table_cache,
+            &database.meta_cache,
+            &transaction,
+            &database.functions,
+            settings,
+        )?;
+        let mut tables = Vec::new();
+        if database.result_cache.is_some() {
+            plan_tables(&plan, &mut tables);
+        }
+
+        let schema = plan.output_schema().clone();
+        let iterator = build_write(
+            plan,
+            (&database.table_cache, &database.meta_cache),
+            &mut transaction,
+        );
+        let tuples =
+            Database::<S>::collect(iterator, database.max_result_rows, settings.time_zone)?;
 
-        build_table(&database.table_cache, &mut transaction)?;
         transaction.commit()?;
 
-        let batch = database.run("select * from t1")?;
+        if let Some((cache, key, generation)) = cached {
+            // Tips: e.g. the result of `select now()` is never returned again
+            if !statement_time.is_read() {
+                cache.put(key, generation, tables, schema.clone(), tuples.clone());
+            }
+        } else if let Some(cache) = &database.result_cache {
+            match command_type {
+                CommandType::DDL => cache.invalidate_schema(),
+                CommandType::DML => cache.invalidate(tables),
+                CommandType::DQL | CommandType::TCL => (),
+            }
+        }
 
-        println!("{:#?}", batch);
-        Ok(())
+        Ok((schema, tuples))
     }
 
-    /// use [CurrentDate](crate::function::current_date::CurrentDate) on this case
-    #[test]
-    fn test_udf() -> Result<(), DatabaseError> {
-        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
-        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
-        let (schema, tuples) = fnck_sql.run("select current_date()")?;
-        println!("{}", create_table(&schema, &tuples));
+    /// Runs `bind` in the transaction of the session, or in a transaction of its own.
+    fn execute_in_transaction(
+        &mut self,
+        bind: impl FnOnce(
+            &mut Binder<'_, '_, S::TransactionType<'_>>,
+        ) -> Result<LogicalPlan, DatabaseError>,
+    ) -> Result<(SchemaRef, Vec<Tuple>), DatabaseError> {
+        if self.transaction.is_some() {
+            return self.execute_with(bind);
+        }
+        self.begin()?;
+        let result = self.execute_with(bind);
+        if result.is_ok() {
+            self.commit()?;
+        } else {
+            self.transaction = None;
+        }
+        result
+    }
 
-        debug_assert_eq!(
-            schema,
-            Arc::new(vec![Arc::new(ColumnCatalog::new(
-                "current_date()".to_string(),
-                true,
-                ColumnDesc::new(LogicalType::Date, false, false, None).unwrap()
-            ))])
+    fn execute_with(
+        &mut self,
+        bind: impl FnOnce(
+            &mut Binder<'_, '_, S::TransactionType<'_>>,
+        ) -> Result<LogicalPlan, DatabaseError>,
+    ) -> Result<(SchemaRef, Vec<Tuple>), DatabaseError> {
+        let database = self.database;
+        let settings = self.bind_settings();
+        let transaction = self
+            .transaction
+            .as_mut()
+            .ok_or(DatabaseError::NoTransactionBegin)?;
+        let _deadline = StatementDeadline::start(self.settings.statement_timeout);
+        let _work_mem = StatementWorkMem::start(self.settings.work_mem);
+        let _statement_time = StatementTime::start();
+        let _time_zone = StatementTimeZone::start(settings.time_zone);
+        let _last_insert_id = StatementLastInsertId::start(&mut self.last_insert_id);
+        let _deferred_foreign_keys =
+            StatementDeferredForeignKeys::start(&mut transaction.deferred_foreign_keys);
+        let mut plan = Database::<S>::build_plan(
+            bind,
+            &database.table_cache,
+            &database.meta_cache,
+            &transaction.inner,
+            &database.functions,
+            settings,
+        )?;
+        if database.result_cache.is_some()
+            && matches!(
+                plan.operator,
+                Operator::Insert(_)
+                    | Operator::Update(_)
+                    | Operator::Delete(_)
+                    | Operator::Truncate(_)
+                    | Operator::CopyFromFile(_)
+            )
+        {
+            plan_tables(&plan, &mut transaction.written_tables);
+        }
+
+        let schema = plan.output_schema().clone();
+        let executor = build_write(
+            plan,
+            (&database.table_cache, &database.meta_cache),
+            &mut transaction.inner,
+        );
+
+        Ok((
+            schema,
+            Database::<S>::collect(executor, database.max_result_rows, settings.time_zone)?,
+        ))
+    }
+
+    fn run_tcl(&mut self, stmt: &Statement) -> Result<(), DatabaseError> {
+        match stmt {
+            Statement::StartTransaction { .. } => self.begin(),
+            Statement::Commit { .. } => self.commit(),
+            Statement::Rollback { .. } => self.rollback(),
+            stmt => Err(DatabaseError::UnsupportedStmt(stmt.to_string())),
+        }
+    }
+
+    fn begin(&mut self) -> Result<(), DatabaseError> {
+        if self.transaction.is_some() {
+            return Err(DatabaseError::TransactionAlreadyExists);
+        }
+        self.transaction = Some(SessionTransaction::begin(self.database)?);
+
+        Ok(())
+    }
+
+    fn commit(&mut self) -> Result<(), DatabaseError> {
+        let transaction = self
+            .transaction
+            .take()
+            .ok_or(DatabaseError::NoTransactionBegin)?;
+
+        // Tips: the transaction is rolled back once dropped if they are violated
+        check_deferred(
+            &transaction.inner,
+            &self.database.table_cache,
+            &transaction.deferred_foreign_keys,
+        )?;
+        transaction.inner.commit()?;
+        self.database.notifier.publish(transaction.notifications);
+        if let Some(cache) = &self.database.result_cache {
+            cache.invalidate(transaction.written_tables);
+        }
+        Ok(())
+    }
+
+    fn rollback(&mut self) -> Result<(), DatabaseError> {
+        let _ = self
+            .transaction
+            .take()
+            .ok_or(DatabaseError::NoTransactionBegin)?;
+
+        Ok(())
+    }
+
+    fn set_variable(&mut self, variable: &ObjectName, value: &[Expr]) -> Result<(), DatabaseError> {
+        let name = variable.to_string().to_lowercase();
+
+        match name.as_str() {
+            "statement_timeout" => {
+                let timeout = parse_timeout(value)
+                    .ok_or_else(|| DatabaseError::InvalidParameter(name, display_values(value)))?;
+
+                self.settings.statement_timeout = (!timeout.is_zero()).then_some(timeout);
+            }
+            "null_ordering" => {
+                let nulls_first = match value {
+                    [Expr::Value(Value::SingleQuotedString(text))]
+                    | [Expr::Identifier(Ident { value: text, .. })] => {
+                        match text.to_lowercase().as_str() {
+                            "nulls_first" => Some(true),
+                            "nulls_last" => Some(false),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+                .ok_or_else(|| DatabaseError::InvalidParameter(name, display_values(value)))?;
+
+                self.settings.nulls_first = nulls_first;
+            }
+            "work_mem" => {
+                let work_mem = parse_work_mem(value)
+                    .ok_or_else(|| DatabaseError::InvalidParameter(name, display_values(value)))?;
+
+                self.settings.work_mem = (work_mem > 0).then_some(work_mem);
+            }
+            "batch_checkpoint" => {
+                let rows = match value {
+                    [Expr::Value(Value::Number(number, _))] => number.parse::<usize>().ok(),
+                    _ => None,
+                }
+                .ok_or_else(|| DatabaseError::InvalidParameter(name, display_values(value)))?;
+
+                self.settings.batch_checkpoint = (rows > 0).then_some(rows);
+            }
+            // `SET TIME ZONE '+08'` is `SET timezone = '+08'`
+            "timezone" | "time_zone" => {
+                let time_zone = match value {
+                    [Expr::Value(Value::SingleQuotedString(text))]
+                    | [Expr::Identifier(Ident { value: text, .. })] => parse_time_zone(text)?,
+                    _ => return Err(DatabaseError::InvalidParameter(name, display_values(value))),
+                };
+
+                self.settings.time_zone = time_zone;
+            }
+            _ => return Err(DatabaseError::NotFound("parameter", name)),
+        }
+        Ok(())
+    }
+
+    /// Takes the id of a session from a sequence of the storage, so that the schema of its
+    /// temporary tables is never shared with another session, even of another process.
+    fn next_id(database: &Database<S>) -> Result<i64, DatabaseError> {
+        let mut transaction = database.storage.transaction()?;
+        let id = transaction.sequence_value(SESSION_ID_SEQUENCE)? + 1;
+
+        transaction.set_sequence_value(SESSION_ID_SEQUENCE, id)?;
+        transaction.commit()?;
+
+        Ok(id)
+    }
+
+    fn bind_settings(&self) -> BindSettings {
+        BindSettings {
+            max_recursion_depth: self.database.max_recursion_depth,
+            max_recursive_iterations: self.database.max_recursive_iterations,
+            nulls_first: self.settings.nulls_first,
+            time_zone: self.settings.time_zone,
+            session_id: self.id,
+        }
+    }
+
+    /// Sends `notification` once the transaction of the session commits, or at once outside
+    /// of a transaction.
+    fn notify(&mut self, notification: Notification) {
+        match self.transaction.as_mut() {
+            Some(transaction) => transaction.notifications.push(notification),
+            None => self.database.notifier.publish(vec![notification]),
+        }
+    }
+}
+
+/// The temporary tables only live as long as the session, so they are dropped with it.
+impl<S: Storage> Drop for Session<'_, S> {
+    fn drop(&mut self) {
+        // Tips: the open transaction is rolled back first, as its writes would conflict
+        self.transaction = None;
+
+        let Some(id) = self.id else {
+            return;
+        };
+        let database = self.database;
+
+        // Tips: never waits, e.g. for another session of the same thread in a transaction
+        match database.mdl.try_write_arc() {
+            Some(_guard) => database.drop_temp_tables(iter::once(id)),
+            None => database.dropped_sessions.lock().push(id),
+        }
+    }
+}
+
+/// A transaction of its own opened by [`Database::new_transaction`], which neither runs DDL
+/// nor the transaction control statements.
+pub struct DBTransaction<'a, S: Storage + 'a> {
+    session: Session<'a, S>,
+}
+
+impl<S: Storage> DBTransaction<'_, S> {
+    pub fn run<T: AsRef<str>>(&mut self, sql: T) -> Result<(SchemaRef, Vec<Tuple>), DatabaseError> {
+        let stmts = match parse_run_sql(sql)? {
+            // Tips: in the transaction of the session, which is always open
+            ParsedSql::Extended(stmt) => return self.session.run_extended(stmt),
+            ParsedSql::Statements(stmts) => stmts,
+        };
+        if stmts.is_empty() {
+            return Err(DatabaseError::EmptyStatement);
+        }
+        let stmt = &stmts[0];
+        match command_type(stmt)? {
+            CommandType::DDL => {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "`DDL` is not allowed to execute within a transaction".to_string(),
+                ))
+            }
+            CommandType::TCL => {
+                return Err(DatabaseError::UnsupportedStmt(
+                    "transaction control statements are not allowed within a `DBTransaction`"
+                        .to_string(),
+                ))
+            }
+            CommandType::DQL | CommandType::DML => (),
+        }
+        self.session.execute_with(|binder| binder.bind(stmt))
+    }
+
+    pub fn commit(mut self) -> Result<(), DatabaseError> {
+        self.session.commit()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use crate::binder::{Binder, BinderContext, DEFAULT_MAX_RECURSION_DEPTH};
+    use crate::catalog::{
+        is_temp_table, ColumnCatalog, ColumnCompression, ColumnDesc, TableCatalog, TableName,
+    };
+    use crate::db::{DataBaseBuilder, Database, DatabaseError, Notification};
+    use crate::execution::{build_write, try_collect};
+    use crate::parser::{parse_sql, PARSER_RECURSION_LIMIT};
+    use crate::storage::rocksdb::{RocksIter, RocksStorage, RocksTransaction};
+    use crate::storage::table_codec::{TableCodec, FORMAT_VERSION};
+    use crate::storage::{InnerIter, Storage, TableCache, Transaction};
+    use crate::types::tuple::{create_table, Tuple};
+    use crate::types::value::{DataValue, Utf8Type, ValueRef};
+    use crate::types::LogicalType;
+    use bytes::Bytes;
+    use chrono::{Datelike, FixedOffset, Local};
+    use integer_encoding::FixedInt;
+    use itertools::Itertools;
+    use sqlparser::ast::CharLengthUnits;
+    use std::cell::Cell;
+    use std::collections::Bound;
+    use std::mem;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+    use tempfile::TempDir;
+
+    pub(crate) fn build_table<T: Transaction>(
+        table_cache: &TableCache,
+        transaction: &mut T,
+    ) -> Result<(), DatabaseError> {
+        let columns = vec![
+            ColumnCatalog::new(
+                "c1".to_string(),
+                false,
+                ColumnDesc::new(LogicalType::Integer, true, false, None).unwrap(),
+            ),
+            ColumnCatalog::new(
+                "c2".to_string(),
+                false,
+                ColumnDesc::new(LogicalType::Boolean, false, false, None).unwrap(),
+            ),
+            ColumnCatalog::new(
+                "c3".to_string(),
+                false,
+                ColumnDesc::new(LogicalType::Integer, false, false, None).unwrap(),
+            ),
+        ];
+        let _ = transaction.create_table(
+            table_cache,
+            Arc::new("t1".to_string()),
+            columns,
+            vec![],
+            vec![],
+            vec![],
+            false,
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_sql() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let database = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let mut transaction = database.storage.transaction()?;
+
+        build_table(&database.table_cache, &mut transaction)?;
+        transaction.commit()?;
+
+        let batch = database.run("select * from t1")?;
+
+        println!("{:#?}", batch);
+        Ok(())
+    }
+
+    /// use [CurrentDate](crate::function::current_date::CurrentDate) on this case
+    #[test]
+    fn test_udf() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let (schema, tuples) = fnck_sql.run("select current_date()")?;
+        println!("{}", create_table(&schema, &tuples));
+
+        debug_assert_eq!(
+            schema,
+            Arc::new(vec![Arc::new(ColumnCatalog::new(
+                "current_date()".to_string(),
+                true,
+                ColumnDesc::new(LogicalType::Date, false, false, None).unwrap()
+            ))])
         );
         debug_assert_eq!(
             tuples,
@@ -480,4 +1521,1512 @@ pub(crate) mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_explicit_transaction_sql() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let mut session = fnck_sql.session();
+
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int)")?;
+
+        let _ = session.run("begin")?;
+        let _ = session.run("insert into t1 values(0, 0)")?;
+        let _ = session.run("insert into t1 values(1, 1)")?;
+        let (_, tuples) = session.run("select * from t1")?;
+        debug_assert_eq!(tuples.len(), 2);
+        debug_assert!(session.run("begin").is_err());
+        debug_assert!(session.run("create table t2 (a int primary key)").is_err());
+        // the uncommitted rows are only seen by the session
+        let (_, tuples) = fnck_sql.run("select * from t1")?;
+        debug_assert_eq!(tuples.len(), 0);
+        let (_, tuples) = fnck_sql.session().run("select * from t1")?;
+        debug_assert_eq!(tuples.len(), 0);
+        // the DDL of the others waits for the open transaction, which holds the metadata lock
+        thread::scope(|scope| -> Result<(), DatabaseError> {
+            let ddl = scope.spawn(|| fnck_sql.run("create table t3 (a int primary key)"));
+            thread::sleep(Duration::from_millis(100));
+            debug_assert!(!ddl.is_finished());
+
+            let _ = session.run("rollback")?;
+            let _ = ddl.join().unwrap()?;
+
+            Ok(())
+        })?;
+
+        let (_, tuples) = session.run("select * from t1")?;
+        debug_assert_eq!(tuples.len(), 0);
+
+        let _ = session.run("start transaction")?;
+        let _ = session.run("insert into t1 values(2, 2)")?;
+        let _ = session.run("commit")?;
+
+        let (_, tuples) = fnck_sql.run("select * from t1")?;
+        debug_assert_eq!(tuples.len(), 1);
+        debug_assert_eq!(
+            tuples[0].values,
+            vec![
+                Arc::new(DataValue::Int32(Some(2))),
+                Arc::new(DataValue::Int32(Some(2)))
+            ]
+        );
+
+        debug_assert!(matches!(
+            session.run("commit"),
+            Err(DatabaseError::NoTransactionBegin)
+        ));
+        debug_assert!(matches!(
+            session.run("rollback"),
+            Err(DatabaseError::NoTransactionBegin)
+        ));
+
+        // the transaction still open is rolled back with the session
+        let _ = session.run("begin")?;
+        let _ = session.run("insert into t1 values(3, 3)")?;
+        drop(session);
+        let (_, tuples) = fnck_sql.run("select * from t1")?;
+        debug_assert_eq!(tuples.len(), 1);
+
+        let mut tx = fnck_sql.new_transaction()?;
+        debug_assert!(tx.run("begin").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_transaction_sql() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int)")?;
+
+        let _ = fnck_sql.run("begin")?;
+        let _ = fnck_sql.run("insert into t1 values(0, 0)")?;
+        let _ = fnck_sql.run("insert into t1 values(1, 1)")?;
+        let (_, tuples) = fnck_sql.run("select * from t1")?;
+        debug_assert_eq!(tuples.len(), 2);
+        debug_assert!(matches!(
+            fnck_sql.run("begin"),
+            Err(DatabaseError::TransactionAlreadyExists)
+        ));
+        // fails at once, rather than waiting for the metadata lock held by the transaction
+        debug_assert!(matches!(
+            fnck_sql.run("create table t2 (a int primary key)"),
+            Err(DatabaseError::UnsupportedStmt(_))
+        ));
+        // the uncommitted rows are only seen by the transaction
+        let (_, tuples) = fnck_sql.session().run("select * from t1")?;
+        debug_assert_eq!(tuples.len(), 0);
+        let _ = fnck_sql.run("rollback")?;
+
+        let (_, tuples) = fnck_sql.run("select * from t1")?;
+        debug_assert_eq!(tuples.len(), 0);
+
+        let _ = fnck_sql.run("start transaction")?;
+        let _ = fnck_sql.run("insert into t1 values(2, 2)")?;
+        let _ = fnck_sql.run("commit")?;
+
+        let (_, tuples) = fnck_sql.session().run("select * from t1")?;
+        debug_assert_eq!(tuples.len(), 1);
+        debug_assert!(matches!(
+            fnck_sql.run("commit"),
+            Err(DatabaseError::NoTransactionBegin)
+        ));
+        debug_assert!(matches!(
+            fnck_sql.run("rollback"),
+            Err(DatabaseError::NoTransactionBegin)
+        ));
+
+        // `SET` applies to the following calls
+        let _ = fnck_sql.run("insert into t1 values(3, null)")?;
+        let _ = fnck_sql.run("set null_ordering = 'nulls_first'")?;
+        let (_, tuples) = fnck_sql.run("select b from t1 order by b")?;
+        debug_assert!(tuples[0].values[0].is_null());
+
+        // the transaction still open is rolled back with the database
+        let _ = fnck_sql.run("begin")?;
+        let _ = fnck_sql.run("insert into t1 values(4, 4)")?;
+        drop(fnck_sql);
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let (_, tuples) = fnck_sql.run("select * from t1")?;
+        debug_assert_eq!(tuples.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ddl_waits_for_other_sessions() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path())
+            .lock_timeout(Duration::from_millis(100))
+            .build()?;
+        let temp_tables = || -> Result<usize, DatabaseError> {
+            Ok(fnck_sql
+                .storage
+                .transaction()?
+                .table_metas()?
+                .iter()
+                .filter(|meta| is_temp_table(&meta.table_name))
+                .count())
+        };
+
+        let _ = fnck_sql.run("create table t1 (a int primary key)")?;
+        let mut temp_session = fnck_sql.session();
+        let _ = temp_session.run("create temporary table t2 (a int primary key)")?;
+
+        // the DDL of another session gives up once it has waited `lock_timeout` for the
+        // transaction, which is never ended by the same thread
+        let mut session = fnck_sql.session();
+        let _ = session.run("begin")?;
+        debug_assert!(matches!(
+            fnck_sql.run("create table t3 (a int primary key)"),
+            Err(DatabaseError::Deadlock(_))
+        ));
+        let _ = fnck_sql.run("insert into t1 values (0)")?;
+        // the temporary tables of a session dropped meanwhile are dropped by the next DDL
+        drop(temp_session);
+        debug_assert_eq!(temp_tables()?, 1);
+        let _ = session.run("commit")?;
+
+        let _ = session.run("create table t3 (a int primary key)")?;
+        debug_assert_eq!(temp_tables()?, 0);
+        let (_, tuples) = fnck_sql.run("select * from t1")?;
+        debug_assert_eq!(tuples.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_notify() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let receiver = fnck_sql.subscribe("events");
+        let other = fnck_sql.subscribe("others");
+        let mut session = fnck_sql.session();
+
+        let _ = session.run("begin")?;
+        let _ = session.run("notify events, 'committed'")?;
+        debug_assert!(receiver.try_recv().is_err());
+        let _ = session.run("commit")?;
+        debug_assert_eq!(receiver.try_recv().unwrap().payload, "committed");
+
+        let _ = session.run("begin")?;
+        let _ = session.run("notify events, 'rolled back'")?;
+        let _ = session.run("rollback")?;
+
+        let mut tx = fnck_sql.new_transaction()?;
+        let _ = tx.run("NOTIFY Events, 'transaction'")?;
+        debug_assert!(receiver.try_recv().is_err());
+        tx.commit()?;
+
+        let mut tx = fnck_sql.new_transaction()?;
+        let _ = tx.run("notify events, 'dropped'")?;
+        drop(tx);
+
+        let _ = fnck_sql.run("notify events")?;
+
+        debug_assert_eq!(
+            receiver.try_iter().collect_vec(),
+            ["transaction", ""]
+                .into_iter()
+                .map(|payload| Notification {
+                    channel: "events".to_string(),
+                    payload: payload.to_string(),
+                })
+                .collect_vec()
+        );
+        debug_assert!(other.try_recv().is_err());
+        debug_assert!(fnck_sql.run("notify events 'missing comma'").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_column_without_rewrite() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let row_version = |id: i32| -> Result<u16, DatabaseError> {
+            let transaction = fnck_sql.storage.transaction()?;
+            let key = TableCodec::encode_tuple_key("t1", &Arc::new(DataValue::Int32(Some(id))))?;
+            let bytes = transaction.get(&key)?.unwrap();
+
+            Ok(u16::decode_fixed(&bytes[0..2]))
+        };
+
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int)")?;
+        let _ = fnck_sql.run("insert into t1 values (0, 0), (1, null)")?;
+        let _ = fnck_sql.run("alter table t1 add column c int default 7")?;
+        let _ = fnck_sql.run("alter table t1 add column d varchar")?;
+        let _ = fnck_sql.run("insert into t1 values (2, 2, 3, 'x')")?;
+
+        debug_assert_eq!(row_version(0)?, 2);
+        debug_assert_eq!(row_version(1)?, 2);
+        debug_assert_eq!(row_version(2)?, 4);
+
+        let (_, tuples) = fnck_sql.run("select a, b, c, d from t1")?;
+        debug_assert_eq!(
+            tuples
+                .iter()
+                .map(|tuple| tuple.values.iter().join(" "))
+                .collect_vec(),
+            vec!["0 0 7 null", "1 null 7 null", "2 2 3 x"]
+        );
+        let (_, tuples) = fnck_sql.run("select d, c from t1 where c = 7")?;
+        debug_assert_eq!(
+            tuples
+                .iter()
+                .map(|tuple| tuple.values.iter().join(" "))
+                .collect_vec(),
+            vec!["null 7", "null 7"]
+        );
+
+        let _ = fnck_sql.run("update t1 set d = 'y' where a = 1")?;
+        debug_assert_eq!(row_version(1)?, 4);
+        let (_, tuples) = fnck_sql.run("select * from t1 where a = 1")?;
+        debug_assert_eq!(tuples[0].values.iter().join(" "), "1 null 7 y");
+
+        // the default the rows written before are filled with is kept in the catalog
+        let table = fnck_sql
+            .storage
+            .transaction()?
+            .table(&fnck_sql.table_cache, Arc::new("t1".to_string()))
+            .cloned()
+            .unwrap();
+        debug_assert_eq!(
+            table.get_column_by_name("c").unwrap().desc.added_default,
+            Some(Arc::new(DataValue::Int32(Some(7))))
+        );
+        debug_assert_eq!(
+            table.get_column_by_name("d").unwrap().desc.added_default,
+            None
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_compression() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let row_len = |table_name: &str| -> Result<usize, DatabaseError> {
+            let transaction = fnck_sql.storage.transaction()?;
+            let key =
+                TableCodec::encode_tuple_key(table_name, &Arc::new(DataValue::Int32(Some(0))))?;
+
+            Ok(transaction.get(&key)?.unwrap().len())
+        };
+
+        let _ = fnck_sql.run("create table plain (id int primary key, b bytea)")?;
+        let _ = fnck_sql.run(
+            "create table compressed (id int primary key, b bytea compression lz4, compression varchar compression lz4)",
+        )?;
+        let _ = fnck_sql.run("insert into plain values (0, cast(repeat('ab', 4096) as bytea))")?;
+        let _ = fnck_sql.run(
+            "insert into compressed values (0, cast(repeat('ab', 4096) as bytea), 'x'), (1, X'01', repeat('c', 1024))",
+        )?;
+
+        debug_assert!(row_len("plain")? > 8192);
+        debug_assert!(row_len("compressed")? < 1024);
+
+        let (_, tuples) = fnck_sql.run(
+            "select p.b = c.b, length(c.b), c.compression from plain p join compressed c on p.id = c.id",
+        )?;
+        debug_assert_eq!(tuples[0].values.iter().join(" "), "true 8192 x");
+
+        let (_, tuples) =
+            fnck_sql.run("select b, length(compression) from compressed where id = 1")?;
+        debug_assert_eq!(tuples[0].values.iter().join(" "), "\\x01 1024");
+
+        let table = fnck_sql
+            .storage
+            .transaction()?
+            .table(&fnck_sql.table_cache, Arc::new("compressed".to_string()))
+            .cloned()
+            .unwrap();
+        debug_assert_eq!(
+            table.get_column_by_name("b").unwrap().desc.compression,
+            Some(ColumnCompression::Lz4)
+        );
+        debug_assert!(fnck_sql
+            .run("create table t1 (a int primary key, b int compression lz4)")
+            .is_err());
+        debug_assert!(fnck_sql
+            .run("create table t1 (a int primary key, b varchar compression zstd)")
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_upgrade_format() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        {
+            let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+            let _ = fnck_sql.run("create table t1 (a int primary key, b varchar)")?;
+            let _ = fnck_sql.run("insert into t1 values (0, 'x'), (1, null)")?;
+
+            // e.g. the rows written before the format is versioned, without their number of columns
+            let mut transaction = fnck_sql.storage.transaction()?;
+            let mut rows = Vec::new();
+            {
+                let (min, max) = TableCodec::tuple_bound("t1");
+                let mut iter = transaction.range(Bound::Included(&min), Bound::Included(&max))?;
+
+                while let Some(row) = iter.try_next()? {
+                    rows.push(row);
+                }
+            }
+            for (key, value) in rows {
+                transaction.set(key, value.slice(2..))?;
+            }
+            transaction.remove(&TableCodec::encode_format_version_key())?;
+            transaction.commit()?;
+        }
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        let (_, tuples) = fnck_sql.run("select a, b from t1")?;
+        debug_assert_eq!(
+            tuples
+                .iter()
+                .map(|tuple| tuple.values.iter().join(" "))
+                .collect_vec(),
+            vec!["0 x", "1 null"]
+        );
+
+        // e.g. the storage is written by a newer version
+        let mut transaction = fnck_sql.storage.transaction()?;
+        let (key, value) = TableCodec::encode_format_version(FORMAT_VERSION + 1);
+        transaction.set(key, value)?;
+        transaction.commit()?;
+        drop(fnck_sql);
+
+        debug_assert!(matches!(
+            DataBaseBuilder::path(temp_dir.path()).build(),
+            Err(DatabaseError::UnsupportedFormatVersion(2, 1))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_result_rows() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path())
+            .max_result_rows(3)
+            .build()?;
+
+        let _ = fnck_sql.run("create table t1 (a int primary key)")?;
+        let _ = fnck_sql.run("insert into t1 values (0), (1), (2), (3), (4)")?;
+
+        debug_assert!(matches!(
+            fnck_sql.run("select * from t1"),
+            Err(DatabaseError::ResultTooLarge(3))
+        ));
+        let (_, tuples) = fnck_sql.run("select * from t1 limit 3")?;
+        debug_assert_eq!(tuples.len(), 3);
+        // the error of the row past the cap is not hidden by the cap
+        debug_assert!(matches!(
+            fnck_sql.run("select 1 / (a - 3) from t1"),
+            Err(DatabaseError::DivisionByZero)
+        ));
+
+        let mut transaction = fnck_sql.new_transaction()?;
+        debug_assert!(matches!(
+            transaction.run("select * from t1 limit 4"),
+            Err(DatabaseError::ResultTooLarge(3))
+        ));
+        let (_, tuples) = transaction.run("select * from t1 where a < 2")?;
+        debug_assert_eq!(tuples.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_execute_batch() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let rows = 10000;
+        let params = (0..rows)
+            .map(|i| {
+                vec![
+                    DataValue::Int32(Some(i)),
+                    DataValue::Utf8 {
+                        value: Some(format!("name_{i}")),
+                        ty: Utf8Type::Variable(None),
+                        unit: CharLengthUnits::Characters,
+                    },
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let _ = fnck_sql.run("create table t1 (a int primary key, b varchar)")?;
+        let _ = fnck_sql.run("create table t2 (a int primary key, b varchar)")?;
+        let mut session = fnck_sql.session();
+
+        let start = Instant::now();
+        for i in 0..rows {
+            let _ = session.run(format!("insert into t1 values ({i}, 'name_{i}')"))?;
+        }
+        let single = start.elapsed();
+        let start = Instant::now();
+        session.execute_batch("insert into t2 values ($1, $2)", &params)?;
+        let batch = start.elapsed();
+        // Tips: the batch is parsed once and committed once
+        debug_assert!(batch < single, "batch: {batch:?}, single: {single:?}");
+
+        let (_, tuples) = session.run("select * from t1 order by a")?;
+        let (_, batch_tuples) = session.run("select * from t2 order by a")?;
+        debug_assert_eq!(batch_tuples.len(), rows as usize);
+        debug_assert_eq!(
+            tuples.iter().map(|tuple| &tuple.values).collect::<Vec<_>>(),
+            batch_tuples
+                .iter()
+                .map(|tuple| &tuple.values)
+                .collect::<Vec<_>>()
+        );
+
+        // the failing row is reported, and the whole batch is rolled back
+        let params = [10000, 10001, 0, 10002]
+            .map(|i| vec![DataValue::Int32(Some(i))])
+            .to_vec();
+        debug_assert!(matches!(
+            session.execute_batch("insert into t2 (a) values ($1)", &params),
+            Err(DatabaseError::BatchFailed(2, _))
+        ));
+        let (_, tuples) = session.run("select count(*) from t2")?;
+        debug_assert_eq!(
+            tuples[0].values,
+            vec![Arc::new(DataValue::Int32(Some(rows)))]
+        );
+
+        // unless the rows before the last checkpoint are committed
+        let _ = session.run("set batch_checkpoint = 2")?;
+        debug_assert!(matches!(
+            session.execute_batch("insert into t2 (a) values ($1)", &params),
+            Err(DatabaseError::BatchFailed(2, _))
+        ));
+        let (_, tuples) = session.run("select count(*) from t2")?;
+        debug_assert_eq!(
+            tuples[0].values,
+            vec![Arc::new(DataValue::Int32(Some(rows + 2)))]
+        );
+
+        session.execute_batch(
+            "update t2 set b = $2 where a = $1",
+            &[vec![
+                DataValue::Int32(Some(10000)),
+                DataValue::Utf8 {
+                    value: Some("updated".to_string()),
+                    ty: Utf8Type::Variable(None),
+                    unit: CharLengthUnits::Characters,
+                },
+            ]],
+        )?;
+        let (_, tuples) = session.run("select b from t2 where a = 10000")?;
+        debug_assert_eq!(
+            tuples[0].values,
+            vec![Arc::new(DataValue::Utf8 {
+                value: Some("updated".to_string()),
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            })]
+        );
+
+        debug_assert!(matches!(
+            session.execute_batch("insert into t2 (a) values ($2)", &params),
+            Err(DatabaseError::BatchFailed(0, err)) if matches!(*err, DatabaseError::NotFound("parameter", _))
+        ));
+        debug_assert!(matches!(
+            session.execute_batch("create table t3 (a int primary key)", &params),
+            Err(DatabaseError::UnsupportedStmt(_))
+        ));
+        let _ = session.run("begin")?;
+        debug_assert!(matches!(
+            session.execute_batch("insert into t2 (a) values ($1)", &params),
+            Err(DatabaseError::TransactionAlreadyExists)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_statement_timeout() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let values = (0..300).map(|i| format!("({i})")).collect::<Vec<_>>();
+        let sql = "select a.a, b.a from t1 a, t1 b";
+
+        let _ = fnck_sql.run("create table t1 (a int primary key)")?;
+        let _ = fnck_sql.run(format!("insert into t1 values {}", values.join(", ")))?;
+
+        let mut session = fnck_sql.session();
+        let _ = session.run("set statement_timeout = '1ms'")?;
+        debug_assert!(matches!(session.run(sql), Err(DatabaseError::Timeout)));
+
+        let _ = session.run("begin")?;
+        debug_assert!(matches!(session.run(sql), Err(DatabaseError::Timeout)));
+        let _ = session.run("rollback")?;
+
+        // the other sessions keep their own timeout
+        let (_, tuples) = fnck_sql.session().run(sql)?;
+        debug_assert_eq!(tuples.len(), 90000);
+        let mut transaction = fnck_sql.new_transaction()?;
+        let (_, tuples) = transaction.run(sql)?;
+        debug_assert_eq!(tuples.len(), 90000);
+        drop(transaction);
+        // and so does the session of `Database::run`
+        let _ = fnck_sql.run("set statement_timeout = '1ms'")?;
+        debug_assert!(matches!(fnck_sql.run(sql), Err(DatabaseError::Timeout)));
+
+        let _ = session.run("set statement_timeout = 0")?;
+        let (_, tuples) = session.run(sql)?;
+        debug_assert_eq!(tuples.len(), 90000);
+
+        debug_assert!(matches!(
+            session.run("set statement_timeout = '5 weeks'"),
+            Err(DatabaseError::InvalidParameter(..))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_work_mem() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let values = (0..1000)
+            .map(|i| format!("({i}, {})", i % 100))
+            .collect::<Vec<_>>();
+        let agg = "select b, count(a) from t1 group by b order by b";
+
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int)")?;
+        let _ = fnck_sql.run(format!("insert into t1 values {}", values.join(", ")))?;
+        let (_, unbounded) = fnck_sql.run(agg)?;
+        debug_assert_eq!(unbounded.len(), 100);
+
+        let mut session = fnck_sql.session();
+        let _ = session.run("set work_mem = '64MB'")?;
+        let (_, tuples) = session.run(agg)?;
+        debug_assert_eq!(tuples, unbounded);
+
+        let _ = session.run("set work_mem = '1B'")?;
+        debug_assert!(matches!(
+            session.run("select b, count(a) from t1 group by b"),
+            Err(DatabaseError::WorkMemExceeded("hash aggregate", 1))
+        ));
+        debug_assert!(matches!(
+            session.run("select * from t1 order by b"),
+            Err(DatabaseError::WorkMemExceeded("sort", 1))
+        ));
+        debug_assert!(matches!(
+            session.run("select * from t1 x join t1 y on x.a = y.b"),
+            Err(DatabaseError::WorkMemExceeded("hash join", 1))
+        ));
+        // the rows streamed without a buffer are not bounded
+        let (_, tuples) = session.run("select * from t1 where b = 0")?;
+        debug_assert_eq!(tuples.len(), 10);
+        // and neither are the other sessions
+        let (_, tuples) = fnck_sql.session().run(agg)?;
+        debug_assert_eq!(tuples, unbounded);
+
+        let _ = session.run("begin")?;
+        debug_assert!(matches!(
+            session.run(agg),
+            Err(DatabaseError::WorkMemExceeded(..))
+        ));
+        let _ = session.run("rollback")?;
+
+        let _ = session.run("set work_mem = 0")?;
+        let (_, tuples) = session.run(agg)?;
+        debug_assert_eq!(tuples, unbounded);
+        debug_assert!(matches!(
+            session.run("set work_mem = '64 parsecs'"),
+            Err(DatabaseError::InvalidParameter(..))
+        ));
+
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path().join("bounded"))
+            .work_mem(1)
+            .build()?;
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int)")?;
+        let _ = fnck_sql.run("insert into t1 values (1, 2), (2, 1)")?;
+        debug_assert!(matches!(
+            fnck_sql.run("select * from t1 order by b"),
+            Err(DatabaseError::WorkMemExceeded("sort", 1))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_time_zone() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path())
+            .time_zone(FixedOffset::east_opt(8 * 3600).unwrap())
+            .result_cache(8, Duration::from_secs(60))
+            .build()?;
+        let sql = "select cast('2024-05-01 04:00:00Z' as timestamptz)";
+
+        let mut session = fnck_sql.session();
+
+        let (_, tuples) = session.run(sql)?;
+        debug_assert_eq!(tuples[0].values[0].to_string(), "2024-05-01 12:00:00+08");
+
+        // the results cached at another time zone are not returned
+        let _ = session.run("set time zone '-03'")?;
+        let (_, tuples) = session.run(sql)?;
+        debug_assert_eq!(tuples[0].values[0].to_string(), "2024-05-01 01:00:00-03");
+
+        let _ = session.run("begin")?;
+        let (_, tuples) = session.run(sql)?;
+        debug_assert_eq!(tuples[0].values[0].to_string(), "2024-05-01 01:00:00-03");
+        let _ = session.run("rollback")?;
+
+        // the other sessions keep the time zone of the database
+        let (_, tuples) = fnck_sql.session().run(sql)?;
+        debug_assert_eq!(tuples[0].values[0].to_string(), "2024-05-01 12:00:00+08");
+        let mut transaction = fnck_sql.new_transaction()?;
+        let (_, tuples) = transaction.run(sql)?;
+        debug_assert_eq!(tuples[0].values[0].to_string(), "2024-05-01 12:00:00+08");
+        drop(transaction);
+
+        // the stored values are rendered at the time zone of each session, and cached with it
+        let _ = fnck_sql.run("create table tz_t (id int primary key, t timestamptz)")?;
+        let _ = fnck_sql.run("insert into tz_t values (0, '2024-05-01 04:00:00Z')")?;
+        let sql = "select t from tz_t";
+        for _ in 0..2 {
+            let (_, tuples) = session.run(sql)?;
+            debug_assert_eq!(tuples[0].values[0].to_string(), "2024-05-01 01:00:00-03");
+            let (_, tuples) = fnck_sql.run(sql)?;
+            debug_assert_eq!(tuples[0].values[0].to_string(), "2024-05-01 12:00:00+08");
+        }
+
+        debug_assert!(matches!(
+            session.run("set time zone 'Mars/Olympus'"),
+            Err(DatabaseError::InvalidTimeZone(..))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_null_ordering() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path())
+            .result_cache(8, Duration::from_secs(60))
+            .build()?;
+        let sql = "select b from t1 order by b";
+        let mut session_1 = fnck_sql.session();
+        let mut session_2 = fnck_sql.session();
+
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int)")?;
+        let _ = fnck_sql.run("insert into t1 values (0, 1), (1, null)")?;
+
+        let _ = session_1.run("set null_ordering = 'nulls_first'")?;
+        let (_, tuples) = session_1.run(sql)?;
+        debug_assert_eq!(tuples[0].values[0].to_string(), "null");
+
+        // neither the setting nor the result cached with it is seen by the other sessions
+        let (_, tuples) = session_2.run(sql)?;
+        debug_assert_eq!(tuples[0].values[0].to_string(), "1");
+        let (_, tuples) = fnck_sql.run(sql)?;
+        debug_assert_eq!(tuples[0].values[0].to_string(), "1");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_auto_increment() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        {
+            let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+            let _ = fnck_sql.run("create table t1 (id int primary key auto_increment, v int)")?;
+            let _ = fnck_sql.run("insert into t1 (v) values (0), (1)")?;
+
+            // the transactions assigning the same value conflict on commit
+            let mut transaction_1 = fnck_sql.new_transaction()?;
+            let mut transaction_2 = fnck_sql.new_transaction()?;
+            let _ = transaction_1.run("insert into t1 (v) values (2)")?;
+            let _ = transaction_2.run("insert into t1 (v) values (3)")?;
+            transaction_1.commit()?;
+            debug_assert!(transaction_2.commit().is_err());
+        }
+        // the values are never assigned again once the database is reopened
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let mut session = fnck_sql.session();
+        let _ = session.run("delete from t1 where id = 3")?;
+        let _ = session.run("insert into t1 (v) values (4)")?;
+
+        let (_, tuples) = session.run("select id from t1 where v = 4")?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(4))));
+        let (_, tuples) = session.run("select last_insert_id()")?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int64(Some(4))));
+
+        // the value is only seen by the session which inserted it
+        let (_, tuples) = fnck_sql.session().run("select last_insert_id()")?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int64(Some(0))));
+        let _ = fnck_sql.run("insert into t1 (v) values (5)")?;
+        let (_, tuples) = session.run("select last_insert_id()")?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int64(Some(4))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_result_cache() -> Result<(), DatabaseError> {
+        // writes to the storage directly, so the result cache is not told about it
+        fn run_uncached(database: &Database<RocksStorage>, sql: &str) -> Result<(), DatabaseError> {
+            let stmts = parse_sql(sql)?;
+            let mut transaction = database.storage.transaction()?;
+            let plan = Database::<RocksStorage>::build_plan(
+                |binder| binder.bind(&stmts[0]),
+                &database.table_cache,
+                &database.meta_cache,
+                &transaction,
+                &database.functions,
+                database.session().bind_settings(),
+            )?;
+            let _ = try_collect(build_write(
+                plan,
+                (&database.table_cache, &database.meta_cache),
+                &mut transaction,
+            ))?;
+
+            transaction.commit()
+        }
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path())
+            .result_cache(16, Duration::from_secs(3600))
+            .build()?;
+        let sql = "select count(a) from t1";
+        let count = |tuples: Vec<Tuple>| tuples[0].values[0].clone();
+
+        let _ = fnck_sql.run("create table t1 (a int primary key)")?;
+        let _ = fnck_sql.run("insert into t1 values (0), (1)")?;
+        debug_assert_eq!(
+            count(fnck_sql.run(sql)?.1),
+            Arc::new(DataValue::Int32(Some(2)))
+        );
+
+        // hit: the row written behind the cache's back is not seen
+        run_uncached(&fnck_sql, "insert into t1 values (2)")?;
+        debug_assert_eq!(
+            count(fnck_sql.run(sql)?.1),
+            Arc::new(DataValue::Int32(Some(2)))
+        );
+        // the normalized SQL is the key
+        debug_assert_eq!(
+            count(fnck_sql.run("SELECT  count(a)\nFROM t1")?.1),
+            Arc::new(DataValue::Int32(Some(2)))
+        );
+
+        // a DML on the referenced table invalidates the result
+        let _ = fnck_sql.run("insert into t1 values (3)")?;
+        debug_assert_eq!(
+            count(fnck_sql.run(sql)?.1),
+            Arc::new(DataValue::Int32(Some(4)))
+        );
+
+        let mut transaction = fnck_sql.new_transaction()?;
+        let _ = transaction.run("delete from t1 where a = 0")?;
+        debug_assert_eq!(
+            count(fnck_sql.run(sql)?.1),
+            Arc::new(DataValue::Int32(Some(4)))
+        );
+        transaction.commit()?;
+        debug_assert_eq!(
+            count(fnck_sql.run(sql)?.1),
+            Arc::new(DataValue::Int32(Some(3)))
+        );
+
+        // the DML on another table keeps it, a DDL drops it
+        let _ = fnck_sql.run("create table t2 (a int primary key)")?;
+        debug_assert_eq!(
+            count(fnck_sql.run(sql)?.1),
+            Arc::new(DataValue::Int32(Some(3)))
+        );
+        run_uncached(&fnck_sql, "insert into t1 values (4)")?;
+        let _ = fnck_sql.run("insert into t2 values (0)")?;
+        debug_assert_eq!(
+            count(fnck_sql.run(sql)?.1),
+            Arc::new(DataValue::Int32(Some(3)))
+        );
+        let _ = fnck_sql.run("drop table t2")?;
+        debug_assert_eq!(
+            count(fnck_sql.run(sql)?.1),
+            Arc::new(DataValue::Int32(Some(4)))
+        );
+
+        // the expired result runs again
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path())
+            .result_cache(16, Duration::from_millis(1))
+            .build()?;
+
+        let _ = fnck_sql.run("create table t1 (a int primary key)")?;
+        let _ = fnck_sql.run("insert into t1 values (0), (1)")?;
+        debug_assert_eq!(
+            count(fnck_sql.run(sql)?.1),
+            Arc::new(DataValue::Int32(Some(2)))
+        );
+        run_uncached(&fnck_sql, "insert into t1 values (2)")?;
+        thread::sleep(Duration::from_millis(10));
+        debug_assert_eq!(
+            count(fnck_sql.run(sql)?.1),
+            Arc::new(DataValue::Int32(Some(3)))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_recursion_depth() -> Result<(), DatabaseError> {
+        let nested_sql = |depth: usize| {
+            let mut sql = "select a from t1".to_string();
+            for _ in 0..depth {
+                sql = format!("select a from t1 where a in ({})", sql);
+            }
+            sql
+        };
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path())
+            .max_recursion_depth(4)
+            .build()?;
+
+        let _ = fnck_sql.run("create table t1 (a int primary key)")?;
+        let _ = fnck_sql.run("insert into t1 values (0), (1)")?;
+
+        let (_, tuples) = fnck_sql.run(nested_sql(3))?;
+        debug_assert_eq!(tuples.len(), 2);
+        debug_assert!(matches!(
+            fnck_sql.run(nested_sql(4)),
+            Err(DatabaseError::RecursionLimit(4))
+        ));
+        let union_sql = ["select a from t1"; 6].join(" union ");
+        debug_assert!(matches!(
+            fnck_sql.run(union_sql),
+            Err(DatabaseError::RecursionLimit(4))
+        ));
+
+        let mut transaction = fnck_sql.new_transaction()?;
+        debug_assert!(matches!(
+            transaction.run(nested_sql(4)),
+            Err(DatabaseError::RecursionLimit(4))
+        ));
+        drop(transaction);
+
+        // far beyond any limit, it still fails gracefully rather than overflowing the stack
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let _ = fnck_sql.run("create table t1 (a int primary key)")?;
+        debug_assert!(matches!(
+            fnck_sql.run(nested_sql(10_000)),
+            Err(DatabaseError::RecursionLimit(PARSER_RECURSION_LIMIT))
+        ));
+        // set operations do not nest in the parser, the binder reaches its default limit
+        let union_sql = ["select a from t1"; DEFAULT_MAX_RECURSION_DEPTH + 2].join(" union ");
+        debug_assert!(matches!(
+            fnck_sql.run(union_sql),
+            Err(DatabaseError::RecursionLimit(DEFAULT_MAX_RECURSION_DEPTH))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_recursive_iterations() -> Result<(), DatabaseError> {
+        let sequence_sql = |n: usize| {
+            format!(
+                "with recursive seq(n) as (select 1 union all select n + 1 from seq where n < {}) select n from seq",
+                n
+            )
+        };
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path())
+            .max_recursive_iterations(5)
+            .build()?;
+
+        let (_, tuples) = fnck_sql.run(sequence_sql(6))?;
+        debug_assert_eq!(tuples.len(), 6);
+        debug_assert!(matches!(
+            fnck_sql.run(sequence_sql(7)),
+            Err(DatabaseError::RecursiveIterationLimit(5))
+        ));
+
+        let mut transaction = fnck_sql.new_transaction()?;
+        debug_assert!(matches!(
+            transaction.run(sequence_sql(7)),
+            Err(DatabaseError::RecursiveIterationLimit(5))
+        ));
+
+        Ok(())
+    }
+
+    /// Counts every key-value pair read from the storage and every table looked up.
+    struct CountingTransaction<'txn> {
+        inner: RocksTransaction<'txn>,
+        reads: Cell<usize>,
+        table_lookups: Cell<usize>,
+    }
+
+    struct CountingIter<'txn, 'iter> {
+        inner: RocksIter<'txn, 'iter>,
+        reads: &'iter Cell<usize>,
+    }
+
+    impl InnerIter for CountingIter<'_, '_> {
+        fn try_next(&mut self) -> Result<Option<(Bytes, Bytes)>, DatabaseError> {
+            let next = self.inner.try_next()?;
+            if next.is_some() {
+                self.reads.set(self.reads.get() + 1);
+            }
+            Ok(next)
+        }
+    }
+
+    impl<'txn> Transaction for CountingTransaction<'txn> {
+        type IterType<'iter> = CountingIter<'txn, 'iter> where Self: 'iter;
+
+        fn get(&self, key: &[u8]) -> Result<Option<Bytes>, DatabaseError> {
+            self.reads.set(self.reads.get() + 1);
+            self.inner.get(key)
+        }
+
+        fn set(&mut self, key: Bytes, value: Bytes) -> Result<(), DatabaseError> {
+            self.inner.set(key, value)
+        }
+
+        fn remove(&mut self, key: &[u8]) -> Result<(), DatabaseError> {
+            self.inner.remove(key)
+        }
+
+        fn fold_row_count_on_commit(&mut self, table_name: &str) {
+            self.inner.fold_row_count_on_commit(table_name)
+        }
+
+        fn table<'a>(
+            &'a self,
+            table_cache: &'a TableCache,
+            table_name: TableName,
+        ) -> Option<&TableCatalog> {
+            self.table_lookups.set(self.table_lookups.get() + 1);
+            self.inner.table(table_cache, table_name)
+        }
+
+        fn range<'a>(
+            &'a self,
+            min: Bound<&[u8]>,
+            max: Bound<&[u8]>,
+        ) -> Result<Self::IterType<'a>, DatabaseError> {
+            Ok(CountingIter {
+                inner: self.inner.range(min, max)?,
+                reads: &self.reads,
+            })
+        }
+
+        fn range_rev<'a>(
+            &'a self,
+            min: Bound<&[u8]>,
+            max: Bound<&[u8]>,
+        ) -> Result<Self::IterType<'a>, DatabaseError> {
+            Ok(CountingIter {
+                inner: self.inner.range_rev(min, max)?,
+                reads: &self.reads,
+            })
+        }
+
+        fn commit(self) -> Result<(), DatabaseError> {
+            self.inner.commit()
+        }
+    }
+
+    fn run_with_reads(
+        database: &Database<RocksStorage>,
+        sql: &str,
+    ) -> Result<(Vec<Tuple>, usize), DatabaseError> {
+        let stmts = parse_sql(sql)?;
+        let mut transaction = CountingTransaction {
+            inner: database.storage.transaction()?,
+            reads: Cell::new(0),
+            table_lookups: Cell::new(0),
+        };
+        let plan = Database::<RocksStorage>::build_plan(
+            |binder| binder.bind(&stmts[0]),
+            &database.table_cache,
+            &database.meta_cache,
+            &transaction.inner,
+            &database.functions,
+            database.session().bind_settings(),
+        )?;
+        transaction.reads.set(0);
+
+        let tuples = try_collect(build_write(
+            plan,
+            (&database.table_cache, &database.meta_cache),
+            &mut transaction,
+        ))?;
+
+        Ok((tuples, transaction.reads.get()))
+    }
+
+    #[test]
+    fn test_bind_resolves_table_once() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int)")?;
+
+        let transaction = CountingTransaction {
+            inner: fnck_sql.storage.transaction()?,
+            reads: Cell::new(0),
+            table_lookups: Cell::new(0),
+        };
+        let stmts = parse_sql(
+            "select * from t1 x join t1 y on x.a = y.a join t1 z on y.b = z.b \
+             where x.a in (select a from t1) and z.b > 0",
+        )?;
+        let mut binder = Binder::new(
+            BinderContext::new(
+                &fnck_sql.table_cache,
+                &transaction,
+                &fnck_sql.functions,
+                Arc::new(AtomicUsize::new(0)),
+            ),
+            None,
+        );
+        let _ = binder.bind(&stmts[0])?;
+        debug_assert_eq!(transaction.table_lookups.get(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_star_by_row_count() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int)")?;
+        let values = (0..1000).map(|i| format!("({}, {})", i, i)).join(", ");
+        let _ = fnck_sql.run(format!("insert into t1 values {}", values))?;
+        let _ = fnck_sql.run("delete from t1 where a < 100")?;
+
+        let (tuples, count_star_reads) = run_with_reads(&fnck_sql, "select count(*) from t1")?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(900))));
+
+        let (tuples, scan_reads) = run_with_reads(&fnck_sql, "select count(b) from t1")?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(900))));
+
+        debug_assert!(scan_reads >= 900);
+        debug_assert!(count_star_reads * 100 < scan_reads);
+
+        // the concurrent writers record their changes of the count under keys of their own
+        let mut transaction_1 = fnck_sql.new_transaction()?;
+        let mut transaction_2 = fnck_sql.new_transaction()?;
+        let _ = transaction_1.run("insert into t1 values (0, 0)")?;
+        let _ = transaction_2.run("insert into t1 values (1, 1)")?;
+        transaction_1.commit()?;
+        transaction_2.commit()?;
+
+        let (_, tuples) = fnck_sql.run("select count(*) from t1")?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(902))));
+
+        let _ = fnck_sql.run("analyze table t1")?;
+        let (tuples, compacted_reads) = run_with_reads(&fnck_sql, "select count(*) from t1")?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(902))));
+        debug_assert_eq!(compacted_reads, 1);
+
+        // the count is unknown after an overwrite, so the tuples are counted again
+        let _ = fnck_sql.run("insert overwrite t1 values (0, 0), (2000, 2000)")?;
+        let (tuples, overwrite_reads) = run_with_reads(&fnck_sql, "select count(*) from t1")?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(903))));
+        debug_assert!(overwrite_reads >= 903);
+
+        let _ = fnck_sql.run("truncate t1")?;
+        let _ = fnck_sql.run("insert into t1 values (0, 0)")?;
+        let (tuples, truncated_reads) = run_with_reads(&fnck_sql, "select count(*) from t1")?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(1))));
+        debug_assert_eq!(truncated_reads, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_unreferenced_table() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        for i in 0..50 {
+            let _ = fnck_sql.run(format!("create table t{} (a int primary key)", i))?;
+        }
+        let _ = fnck_sql.run("insert into t0 values (0), (1)")?;
+
+        // the catalogs of the other tables are not read for the foreign keys referencing `t0`
+        let (_, reads) = run_with_reads(&fnck_sql, "delete from t0 where a = 0")?;
+        debug_assert!(reads < 10);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_without_where() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let count = |sql: &str| -> Result<ValueRef, DatabaseError> {
+            Ok(fnck_sql.run(sql)?.1[0].values[0].clone())
+        };
+        let deleted = |tuples: &[Tuple]| tuples[0].values[0].to_string();
+
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int unique)")?;
+        let values = (0..1000).map(|i| format!("({}, {})", i, i)).join(", ");
+        let _ = fnck_sql.run(format!("insert into t1 values {}", values))?;
+
+        // Tips: `run_with_reads` does not commit, so both deletions are rolled back
+        let (tuples, clear_reads) = run_with_reads(&fnck_sql, "delete from t1")?;
+        debug_assert_eq!(deleted(&tuples), "1000");
+        let (tuples, row_by_row_reads) = run_with_reads(&fnck_sql, "delete from t1 where b >= 0")?;
+        debug_assert_eq!(deleted(&tuples), "1000");
+
+        // every tuple and index entry is read once as a raw key, without decoding the tuples
+        // and projecting their index values one by one
+        debug_assert!(clear_reads <= 2000 + 10);
+        debug_assert!(row_by_row_reads >= 1000);
+        debug_assert_eq!(
+            count("select count(*) from t1")?,
+            Arc::new(DataValue::Int32(Some(1000)))
+        );
+
+        let mut session = fnck_sql.session();
+        let _ = session.run("begin")?;
+        let _ = session.run("delete from t1")?;
+        debug_assert_eq!(
+            session.run("select count(b) from t1")?.1[0].values[0],
+            Arc::new(DataValue::Int32(Some(0)))
+        );
+        let _ = session.run("rollback")?;
+        debug_assert_eq!(
+            count("select count(b) from t1")?,
+            Arc::new(DataValue::Int32(Some(1000)))
+        );
+
+        let (_, tuples) = fnck_sql.run("delete from t1")?;
+        debug_assert_eq!(deleted(&tuples), "1000");
+        debug_assert_eq!(
+            count("select count(*) from t1")?,
+            Arc::new(DataValue::Int32(Some(0)))
+        );
+        // the entries of the unique index are cleared too
+        let _ = fnck_sql.run("insert into t1 values (0, 1), (1, 0)")?;
+        debug_assert_eq!(
+            count("select a from t1 where b = 1")?,
+            Arc::new(DataValue::Int32(Some(0)))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_max_by_index() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int unique, c int)")?;
+        let values = (0..1000)
+            .map(|i| format!("({}, {}, {})", i, i, i))
+            .join(", ");
+        let _ = fnck_sql.run(format!("insert into t1 values {}", values))?;
+
+        let (tuples, min_reads) = run_with_reads(&fnck_sql, "select min(a) from t1")?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(0))));
+        debug_assert_eq!(min_reads, 1);
+
+        let (tuples, max_reads) = run_with_reads(&fnck_sql, "select max(a) from t1")?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(999))));
+        debug_assert_eq!(max_reads, 1);
+
+        // one entry of the unique index, and the tuple it points to
+        let (tuples, range_reads) =
+            run_with_reads(&fnck_sql, "select max(b) from t1 where b < 500")?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(499))));
+        debug_assert_eq!(range_reads, 2);
+
+        let (tuples, scan_reads) = run_with_reads(&fnck_sql, "select min(c) from t1")?;
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(0))));
+        debug_assert!(scan_reads >= 1000);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_correlated_subquery() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        let _ = fnck_sql.run("create table t1 (id int primary key, a int)")?;
+        let _ = fnck_sql.run("create table t2 (id int primary key, b int)")?;
+
+        let (_, tuples) = fnck_sql
+            .run("explain select * from t1 where exists (select 1 from t2 where t2.id = t1.id)")?;
+        let explain = tuples[0].values[0].to_string();
+        debug_assert!(
+            explain.contains("LeftSemi Join On t1.id = t2.id [HashJoin]"),
+            "{}",
+            explain
+        );
+
+        // the predicate can't be pulled up through the `LIMIT`
+        let (_, tuples) = fnck_sql.run(
+            "explain select * from t1 where a in (select b from t2 where t2.id = t1.id limit 1)",
+        )?;
+        let explain = tuples[0].values[0].to_string();
+        debug_assert!(explain.contains("Correlated: [t1.id]"), "{}", explain);
+        debug_assert!(explain.contains("[NestLoopJoin]"), "{}", explain);
+
+        let (_, tuples) =
+            fnck_sql.run("explain select * from t1 where exists (select 1 from t2)")?;
+        debug_assert!(!tuples[0].values[0].to_string().contains("Correlated"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_order_by_index_desc() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        let _ = fnck_sql.run("create table t1 (id int primary key, a int, b int)")?;
+        let _ = fnck_sql.run("create index i_a on t1 (a)")?;
+
+        let (_, tuples) = fnck_sql.run("explain select * from t1 order by a desc limit 10")?;
+        let explain = tuples[0].values[0].to_string();
+        debug_assert!(
+            explain.contains("Limit: 10, Order: i_a DESC [IndexScan By i_a"),
+            "{}",
+            explain
+        );
+        debug_assert!(!explain.contains("Sort"), "{}", explain);
+
+        // `DESC NULLS FIRST` is not the backward order of the index
+        let (_, tuples) =
+            fnck_sql.run("explain select * from t1 order by a desc nulls first limit 10")?;
+        debug_assert!(tuples[0].values[0].to_string().contains("Sort"));
+
+        // neither are the mixed directions
+        let _ = fnck_sql.run("create index i_a_b on t1 (a, b)")?;
+        let (_, tuples) = fnck_sql.run("explain select * from t1 order by a desc, b asc")?;
+        debug_assert!(tuples[0].values[0].to_string().contains("Sort"));
+        let (_, tuples) = fnck_sql.run("explain select * from t1 order by a desc, b desc")?;
+        let explain = tuples[0].values[0].to_string();
+        debug_assert!(explain.contains("Order: i_a_b DESC"), "{}", explain);
+        debug_assert!(!explain.contains("Sort"), "{}", explain);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_date_trunc_range() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        let _ = fnck_sql.run("create table t1 (id int primary key, ts datetime)")?;
+        let _ = fnck_sql.run("create index i_ts on t1 (ts)")?;
+        for i in 0..200 {
+            let _ = fnck_sql.run(format!(
+                "insert into t1 values ({}, '2024-01-{:02} {:02}:00:00')",
+                i,
+                i / 24 + 1,
+                i % 24
+            ))?;
+        }
+        let _ = fnck_sql.run("analyze table t1")?;
+
+        let (_, tuples) = fnck_sql
+            .run("explain select id from t1 where date_trunc('day', ts) = DATE '2024-01-02'")?;
+        let explain = tuples[0].values[0].to_string();
+        debug_assert!(explain.contains("IndexScan By i_ts"), "{}", explain);
+        debug_assert!(!explain.contains("date_trunc"), "{}", explain);
+
+        let (_, tuples) =
+            fnck_sql.run("select id from t1 where date_trunc('day', ts) = DATE '2024-01-02'")?;
+        debug_assert_eq!(
+            tuples
+                .iter()
+                .map(|tuple| tuple.values[0].to_string())
+                .collect_vec(),
+            (24..48).map(|i| i.to_string()).collect_vec()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_date_functions_folded() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        let _ = fnck_sql.run("create table t1 (id int primary key, ts datetime)")?;
+        let _ = fnck_sql.run("create index i_ts on t1 (ts)")?;
+        for i in 0..200 {
+            let _ = fnck_sql.run(format!(
+                "insert into t1 values ({}, '2024-01-{:02} {:02}:00:00')",
+                i,
+                i / 24 + 1,
+                i % 24
+            ))?;
+        }
+        let _ = fnck_sql.run("analyze table t1")?;
+
+        let (_, tuples) = fnck_sql.run(
+            "explain select id from t1 where ts >= date_trunc('day', TIMESTAMP '2024-01-08 12:30:00')",
+        )?;
+        let explain = tuples[0].values[0].to_string();
+        debug_assert!(explain.contains("IndexScan By i_ts"), "{}", explain);
+        debug_assert!(!explain.contains("date_trunc"), "{}", explain);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_math_functions_folded() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        let _ = fnck_sql.run("create table t1 (id int primary key, price decimal(10, 2))")?;
+
+        let (_, tuples) =
+            fnck_sql.run("explain select round(price, 2) from t1 where id > sqrt(16) + abs(-2)")?;
+        let explain = tuples[0].values[0].to_string();
+        debug_assert!(explain.contains("round"), "{}", explain);
+        debug_assert!(!explain.contains("sqrt"), "{}", explain);
+        debug_assert!(!explain.contains("abs"), "{}", explain);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_statement_time() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path())
+            .result_cache(16, Duration::from_secs(3600))
+            .build()?;
+
+        let (_, tuples) = fnck_sql.run("explain select now() = current_timestamp")?;
+        let explain = tuples[0].values[0].to_string();
+        debug_assert!(explain.contains("now()"), "{}", explain);
+
+        let (_, tuples) = fnck_sql.run("select now(), current_timestamp")?;
+        debug_assert_eq!(tuples[0].values[0], tuples[0].values[1]);
+
+        // the result of `now()` is not cached
+        thread::sleep(Duration::from_millis(1100));
+        let (_, later) = fnck_sql.run("select now(), current_timestamp")?;
+        debug_assert!(later[0].values[0] > tuples[0].values[0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_temporary_table() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let temp_tables = || -> Result<usize, DatabaseError> {
+            Ok(fnck_sql
+                .storage
+                .transaction()?
+                .table_metas()?
+                .iter()
+                .filter(|meta| is_temp_table(&meta.table_name))
+                .count())
+        };
+
+        let _ = fnck_sql.run("create table t1 (a int primary key, b int)")?;
+        let _ = fnck_sql.run("insert into t1 values (0, 0)")?;
+        debug_assert!(fnck_sql
+            .run("create temporary table t2 (a int primary key)")
+            .is_err());
+
+        let mut session = fnck_sql.session();
+        let _ = session.run("create temporary table t1 (a int primary key, b int)")?;
+        let _ = session.run("insert into t1 values (1, 1), (2, 2)")?;
+
+        // the temporary table shadows the table of the same name
+        let (_, tuples) = session.run("select t1.a from t1 order by a")?;
+        debug_assert_eq!(tuples.len(), 2);
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(1))));
+        let (_, tuples) = session.run("select a from public.t1")?;
+        debug_assert_eq!(tuples.len(), 1);
+
+        // ... but only in the session which created it
+        let mut other = fnck_sql.session();
+        let (_, tuples) = other.run("select a from t1")?;
+        debug_assert_eq!(tuples.len(), 1);
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(0))));
+        debug_assert!(matches!(
+            other.run("select a from pg_temp.t1"),
+            Err(DatabaseError::TableNotFound)
+        ));
+        let _ = other.run("create temporary table t1 (a int primary key, b int)")?;
+        let (_, tuples) = other.run("select a from t1")?;
+        debug_assert_eq!(tuples.len(), 0);
+        let (_, tuples) = session.run("select a from pg_temp.t1")?;
+        debug_assert_eq!(tuples.len(), 2);
+
+        // the temporary tables of a session are private to it, even by the name of its schema
+        let (_, tuples) = session.run("select a from pg_temp_1.t1")?;
+        debug_assert_eq!(tuples.len(), 2);
+        debug_assert!(other.run("select a from pg_temp_1.t1").is_err());
+        debug_assert!(other.run("drop table pg_temp_1.t1").is_err());
+        debug_assert!(fnck_sql.run("select a from pg_temp_1.t1").is_err());
+        debug_assert!(fnck_sql
+            .run("create table pg_temp_99.t3 (a int primary key)")
+            .is_err());
+        let (_, tuples) = other
+            .run("select table_schema from information_schema.tables where table_name = 't1'")?;
+        debug_assert_eq!(
+            tuples
+                .iter()
+                .map(|tuple| tuple.values[0].to_string())
+                .sorted()
+                .collect_vec(),
+            vec!["pg_temp_2", "public"]
+        );
+
+        drop(other);
+        debug_assert_eq!(temp_tables()?, 1);
+        drop(session);
+        debug_assert_eq!(temp_tables()?, 0);
+
+        let (_, tuples) = fnck_sql.run("select a from t1")?;
+        debug_assert_eq!(tuples.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_temporary_table_reopen() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        {
+            let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+            let mut session = fnck_sql.session();
+
+            let _ = session.run("create table t1 (a int primary key, b int)")?;
+            let _ = session.run("insert into t1 values (0, 0)")?;
+            let _ = session.run("create temporary table t1 (a int primary key, b int)")?;
+            let _ = session.run("insert into t1 values (1, 1), (2, 2)")?;
+
+            // e.g. the process is killed before the session is dropped
+            mem::forget(session);
+        }
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path()).build()?;
+
+        let tables = fnck_sql.storage.transaction()?.table_metas()?;
+        debug_assert!(!tables.iter().any(|meta| is_temp_table(&meta.table_name)));
+        let mut session = fnck_sql.session();
+        let (_, tuples) = session.run("select a from t1")?;
+        debug_assert_eq!(tuples.len(), 1);
+        debug_assert_eq!(tuples[0].values[0], Arc::new(DataValue::Int32(Some(0))));
+        debug_assert!(matches!(
+            session.run("select a from pg_temp.t1"),
+            Err(DatabaseError::TableNotFound)
+        ));
+
+        Ok(())
+    }
 }
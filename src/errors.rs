@@ -1,4 +1,5 @@
 use crate::expression::{BinaryOperator, UnaryOperator};
+use crate::parser::PARSER_RECURSION_LIMIT;
 use crate::types::LogicalType;
 use chrono::ParseError;
 use sqlparser::parser::ParserError;
@@ -10,6 +11,12 @@ use std::string::FromUtf8Error;
 pub enum DatabaseError {
     #[error("agg miss: {0}")]
     AggMiss(String),
+    #[error("column reference \"{0}\" is ambiguous")]
+    AmbiguousColumn(String),
+    #[error("arithmetic overflow: {0}")]
+    ArithmeticOverflow(String),
+    #[error("row {0} of the batch failed: {1}")]
+    BatchFailed(usize, Box<DatabaseError>),
     #[error("bindcode: {0}")]
     Bincode(
         #[source]
@@ -20,6 +27,8 @@ pub enum DatabaseError {
     CacheSizeOverFlow,
     #[error("cast fail")]
     CastFail,
+    #[error("new row violates check constraint \"{0}\"")]
+    CheckViolation(String),
     #[error("channel close")]
     ChannelClose,
     #[error("columns empty")]
@@ -30,38 +39,84 @@ pub enum DatabaseError {
         #[source]
         csv::Error,
     ),
+    #[error("deadlock detected: {0}")]
+    Deadlock(String),
     #[error("default cannot be a column related to the table")]
     DefaultNotColumnRef,
     #[error("default does not exist")]
     DefaultNotExist,
+    #[error("the transaction is rolled back, as its rows violate the deferred foreign key constraints {0}")]
+    DeferredForeignKeyViolation(String),
+    #[error("division by zero")]
+    DivisionByZero,
     #[error("column: {0} already exists")]
     DuplicateColumn(String),
     #[error("index: {0} already exists")]
     DuplicateIndex(String),
     #[error("duplicate primary key")]
     DuplicatePrimaryKey,
-    #[error("the column has been declared unique and the value already exists")]
-    DuplicateUniqueValue,
+    #[error("duplicate key value violates unique constraint \"{0}\": ({1})")]
+    DuplicateUniqueValue(String, String),
     #[error("empty plan")]
     EmptyPlan,
     #[error("sql statement is empty")]
     EmptyStatement,
     #[error("evaluator not found")]
     EvaluatorNotFound,
+    #[error("{0} is used by foreign key constraint \"{1}\" of table {2}")]
+    ForeignKeyDependency(String, String, String),
+    #[error("update or delete on table {0} violates foreign key constraint \"{1}\" of table {2}")]
+    ForeignKeyReferenced(String, String, String),
+    #[error("insert or update on table {0} violates foreign key constraint \"{1}\"")]
+    ForeignKeyViolation(String, String),
     #[error("from utf8: {0}")]
     FromUtf8Error(
         #[source]
         #[from]
         FromUtf8Error,
     ),
+    #[error("column: {0} is a generated column and can only be written with DEFAULT")]
+    GeneratedColumnWrite(String),
     #[error("can not compare two types: {0} and {1}")]
     Incomparable(LogicalType, LogicalType),
+    #[error("column: {0} of type {1} cannot be assigned a value of type {2}")]
+    IncompatibleAssignment(String, LogicalType, LogicalType),
+    #[error("malformed array literal: \"{0}\"")]
+    InvalidArray(String),
+    #[error("invalid input syntax for type bytea: \"{0}\"")]
+    InvalidBytea(String),
+    #[error("invalid check constraint: {0}")]
+    InvalidCheck(String),
     #[error("invalid column: {0}")]
     InvalidColumn(String),
+    #[error("invalid default: {0}")]
+    InvalidDefault(String),
+    #[error("invalid foreign key: {0}")]
+    InvalidForeignKey(String),
+    #[error("invalid generated column: {0}")]
+    InvalidGenerated(String),
     #[error("invalid index")]
     InvalidIndex,
+    #[error("invalid interval: {0}")]
+    InvalidInterval(String),
+    #[error("invalid input syntax for type json: {0}")]
+    InvalidJson(String),
+    #[error("invalid LIKE pattern: {0}")]
+    InvalidLikePattern(String),
+    #[error("invalid regular expression: {0}")]
+    InvalidRegex(String),
+    #[error("{0} position {1} is not in the select list, expected an integer from 1 to {2}")]
+    InvalidOrdinal(&'static str, String, usize),
+    #[error("invalid input syntax for type uuid: \"{0}\"")]
+    InvalidUuid(String),
+    #[error("invalid value for parameter \"{0}\": {1}")]
+    InvalidParameter(String, String),
+    #[error("invalid recursive query: {0}")]
+    InvalidRecursion(String),
     #[error("invalid table: {0}")]
     InvalidTable(String),
+    #[error("time zone \"{0}\" not recognized")]
+    InvalidTimeZone(String),
     #[error("invalid type")]
     InvalidType,
     #[error("io: {0}")]
@@ -70,6 +125,16 @@ pub enum DatabaseError {
         #[from]
         std::io::Error,
     ),
+    #[error("the types of the join column {0} cannot be matched: {1} and {2}")]
+    JoinColumnTypeMismatch(String, LogicalType, LogicalType),
+    #[error("json: {0}")]
+    Json(
+        #[from]
+        #[source]
+        serde_json::Error,
+    ),
+    #[error("json value: {0} cannot be imported into column `{1}` of type {2}")]
+    JsonMismatch(String, String, LogicalType),
     #[error("{0} and {1} do not match")]
     MisMatch(&'static str, &'static str),
     #[error("add column must be nullable or specify a default value")]
@@ -80,6 +145,10 @@ pub enum DatabaseError {
     NotNull,
     #[error("{0} not found: {1}")]
     NotFound(&'static str, String),
+    /// No longer returned, the overflows are reported as [`DatabaseError::ArithmeticOverflow`]
+    /// which names the overflowing operation.
+    #[error("numeric overflow")]
+    NumericOverFlow,
     #[error("parser bool: {0}")]
     ParseBool(
         #[source]
@@ -105,25 +174,33 @@ pub enum DatabaseError {
         ParseIntError,
     ),
     #[error("parser sql: {0}")]
-    ParserSql(
-        #[source]
-        #[from]
-        ParserError,
-    ),
+    ParserSql(#[source] ParserError),
     #[error("must contain primary key!")]
     PrimaryKeyNotFound,
+    #[error("the query exceeds the maximum nesting depth of {0}")]
+    RecursionLimit(usize),
+    #[error("the recursive query exceeds the maximum of {0} iterations")]
+    RecursiveIterationLimit(usize),
+    #[error("the result exceeds the maximum of {0} rows")]
+    ResultTooLarge(usize),
     #[error("rocksdb: {0}")]
     RocksDB(
         #[source]
         #[from]
         rocksdb::Error,
     ),
+    #[error("the types of column {0} in the set operation cannot be matched: {1} and {2}")]
+    SetOperationTypeMismatch(usize, LogicalType, LogicalType),
     #[error("the number of caches cannot be divisible by the number of shards")]
     ShardingNotAlign,
+    #[error("more than one row returned by a subquery used as an expression")]
+    SubqueryTooManyRows,
     #[error("the table already exists")]
     TableExists,
     #[error("the table not found")]
     TableNotFound,
+    #[error("canceling statement due to statement timeout")]
+    Timeout,
     #[error("transaction already exists")]
     TransactionAlreadyExists,
     #[error("try from decimal: {0}")]
@@ -142,12 +219,35 @@ pub enum DatabaseError {
     TooLong,
     #[error("there are more buckets: {0} than elements: {1}")]
     TooManyBuckets(usize, usize),
+    #[error("the values of type {0} are not ordered, so they cannot be sorted or indexed")]
+    Unorderable(LogicalType),
     #[error("unsupported unary operator: {0} cannot support {1} for calculations")]
     UnsupportedUnaryOperator(LogicalType, UnaryOperator),
     #[error("unsupported binary operator: {0} cannot support {1} for calculations")]
     UnsupportedBinaryOperator(LogicalType, BinaryOperator),
+    #[error("the storage is of format version {0}, but only up to {1} is supported")]
+    UnsupportedFormatVersion(u16, u16),
     #[error("unsupported statement: {0}")]
     UnsupportedStmt(String),
     #[error("values length not match, expect {0}, got {1}")]
     ValuesLenMismatch(usize, usize),
+    #[error("the types of column {0} in values cannot be matched: {1} and {2}")]
+    ValuesTypeMismatch(usize, LogicalType, LogicalType),
+    #[error("the view already exists")]
+    ViewExists,
+    #[error("the view not found")]
+    ViewNotFound,
+    #[error("the memory of {0} exceeds the work_mem of {1} bytes")]
+    WorkMemExceeded(&'static str, usize),
+}
+
+impl From<ParserError> for DatabaseError {
+    fn from(err: ParserError) -> Self {
+        match err {
+            ParserError::RecursionLimitExceeded => {
+                DatabaseError::RecursionLimit(PARSER_RECURSION_LIMIT)
+            }
+            err => DatabaseError::ParserSql(err),
+        }
+    }
 }
@@ -0,0 +1,51 @@
+use crate::execution::dml::check_constraints;
+use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::planner::operator::alter_table::add_check::AddCheckOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::tuple::Tuple;
+use crate::types::tuple_builder::TupleBuilder;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
+use std::slice;
+
+pub struct AddCheck {
+    op: AddCheckOperator,
+    input: LogicalPlan,
+}
+
+impl From<(AddCheckOperator, LogicalPlan)> for AddCheck {
+    fn from((op, input): (AddCheckOperator, LogicalPlan)) -> Self {
+        Self { op, input }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for AddCheck {
+    fn execute_mut(
+        mut self,
+        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        transaction: &'a mut T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let AddCheckOperator { table_name, check } = self.op;
+                let schema = self.input.output_schema().clone();
+                let mut coroutine = build_read(self.input, cache, transaction);
+
+                // the existing rows are checked before the constraint is kept
+                while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                    let tuple: Tuple = throw!(tuple);
+
+                    throw!(check_constraints(slice::from_ref(&check), &tuple, &schema));
+                }
+                drop(coroutine);
+                throw!(transaction.add_check(cache.0, &table_name, check.0));
+
+                yield Ok(TupleBuilder::build_result("1".to_string()));
+            },
+        )
+    }
+}
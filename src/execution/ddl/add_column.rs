@@ -1,7 +1,10 @@
+use crate::errors::DatabaseError;
+use crate::execution::dml::check_constraints;
 use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::expression::ScalarExpression;
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache};
-use crate::types::index::{Index, IndexType};
+use crate::types::index::Index;
 use crate::types::tuple::Tuple;
 use crate::types::tuple_builder::TupleBuilder;
 use crate::types::value::DataValue;
@@ -38,17 +41,55 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for AddColumn {
                     table_name,
                     column,
                     if_not_exists,
+                    checks,
                 } = &self.op;
+                let constraints = checks
+                    .iter()
+                    .map(|(check, _)| check.clone())
+                    .collect::<Vec<_>>();
 
+                // Tips: the rows written before are filled with a constant default lazily on read,
+                // so only the volatile defaults, the unique index and the checks need the rows to
+                // be rewritten
+                if !column.desc().is_unique
+                    && column.desc().default.as_ref().map_or(true, is_constant)
+                    && checks.is_empty()
+                {
+                    let mut column = column.clone();
+                    column.desc.added_default = throw!(column.default_value());
+
+                    throw!(transaction.add_column(
+                        cache.0,
+                        table_name,
+                        &column,
+                        &constraints,
+                        *if_not_exists
+                    ));
+
+                    yield Ok(TupleBuilder::build_result("1".to_string()));
+                    return;
+                }
                 let mut unique_values = column.desc().is_unique.then(Vec::new);
                 let mut tuples = Vec::new();
                 let schema = self.input.output_schema();
-                let mut types = Vec::with_capacity(schema.len() + 1);
+                let mut table_columns = Vec::with_capacity(schema.len() + 1);
 
                 for column_ref in schema.iter() {
-                    types.push(*column_ref.datatype());
+                    table_columns.push(column_ref.clone());
                 }
-                types.push(*column.datatype());
+                table_columns.push(Arc::new(column.clone()));
+                // the columns of the table with the new one, which the checks are bound against
+                let check_schema = if checks.is_empty() {
+                    None
+                } else {
+                    let mut table = throw!(transaction
+                        .table(cache.0, table_name.clone())
+                        .cloned()
+                        .ok_or(DatabaseError::TableNotFound));
+                    throw!(table.add_column(column.clone()));
+
+                    Some(table.schema_ref().clone())
+                };
 
                 let mut coroutine = build_read(self.input, cache, transaction);
 
@@ -63,15 +104,23 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for AddColumn {
                     } else {
                         tuple.values.push(Arc::new(DataValue::Null));
                     }
+                    if let Some(check_schema) = &check_schema {
+                        throw!(check_constraints(checks, &tuple, check_schema));
+                    }
                     tuples.push(tuple);
                 }
                 drop(coroutine);
 
                 for tuple in tuples {
-                    throw!(transaction.append_tuple(table_name, tuple, &types, true));
+                    throw!(transaction.append_tuple(table_name, tuple, &table_columns, true));
                 }
-                let col_id =
-                    throw!(transaction.add_column(cache.0, table_name, column, *if_not_exists));
+                let col_id = throw!(transaction.add_column(
+                    cache.0,
+                    table_name,
+                    column,
+                    &constraints,
+                    *if_not_exists
+                ));
 
                 // Unique Index
                 if let (Some(unique_values), Some(unique_meta)) = (
@@ -83,8 +132,13 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for AddColumn {
                 ) {
                     for (tuple_id, value) in unique_values {
                         let index =
-                            Index::new(unique_meta.id, slice::from_ref(&value), IndexType::Unique);
-                        throw!(transaction.add_index(table_name, index, &tuple_id));
+                            Index::new(unique_meta.id, slice::from_ref(&value), unique_meta.ty);
+                        throw!(transaction.add_index(
+                            table_name,
+                            &unique_meta.name,
+                            index,
+                            &tuple_id
+                        ));
                     }
                 }
 
@@ -93,3 +147,13 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for AddColumn {
         )
     }
 }
+
+fn is_constant(expr: &ScalarExpression) -> bool {
+    match expr {
+        ScalarExpression::Constant(_) => true,
+        ScalarExpression::Alias { expr, .. } | ScalarExpression::TypeCast { expr, .. } => {
+            is_constant(expr)
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,76 @@
+use crate::errors::DatabaseError;
+use crate::execution::dml::foreign_key::contains_key;
+use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::planner::operator::alter_table::add_foreign_key::AddForeignKeyOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::tuple::Tuple;
+use crate::types::tuple_builder::TupleBuilder;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
+
+pub struct AddForeignKey {
+    op: AddForeignKeyOperator,
+    input: LogicalPlan,
+}
+
+impl From<(AddForeignKeyOperator, LogicalPlan)> for AddForeignKey {
+    fn from((op, input): (AddForeignKeyOperator, LogicalPlan)) -> Self {
+        Self { op, input }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for AddForeignKey {
+    fn execute_mut(
+        mut self,
+        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        transaction: &'a mut T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let AddForeignKeyOperator {
+                    table_name,
+                    foreign_key,
+                } = self.op;
+                let i = throw!(self
+                    .input
+                    .output_schema()
+                    .iter()
+                    .position(|column| column.name() == foreign_key.column)
+                    .ok_or_else(|| DatabaseError::NotFound("column", foreign_key.column.clone())));
+                let mut keys = Vec::new();
+                let mut coroutine = build_read(self.input, cache, transaction);
+
+                while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                    let tuple: Tuple = throw!(tuple);
+
+                    if !tuple.values[i].is_null() {
+                        keys.push(tuple.values[i].clone());
+                    }
+                }
+                drop(coroutine);
+                // the existing rows are checked before the constraint is kept
+                for key in keys {
+                    if !throw!(contains_key(
+                        transaction,
+                        cache.0,
+                        &foreign_key.ref_table,
+                        &foreign_key.ref_column,
+                        &key,
+                    )) {
+                        throw!(Err(DatabaseError::ForeignKeyViolation(
+                            table_name.to_string(),
+                            foreign_key.name.clone(),
+                        )));
+                    }
+                }
+                throw!(transaction.add_foreign_key(cache.0, &table_name, foreign_key));
+
+                yield Ok(TupleBuilder::build_result("1".to_string()));
+            },
+        )
+    }
+}
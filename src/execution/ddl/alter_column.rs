@@ -0,0 +1,126 @@
+use crate::errors::DatabaseError;
+use crate::execution::dql::projection::Projection;
+use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::planner::operator::alter_table::alter_column::AlterColumnOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::index::Index;
+use crate::types::tuple::{tuple_id, Tuple};
+use crate::types::tuple_builder::TupleBuilder;
+use crate::types::value::DataValue;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
+use std::sync::Arc;
+
+pub struct AlterColumn {
+    op: AlterColumnOperator,
+    input: LogicalPlan,
+}
+
+impl From<(AlterColumnOperator, LogicalPlan)> for AlterColumn {
+    fn from((op, input): (AlterColumnOperator, LogicalPlan)) -> Self {
+        Self { op, input }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for AlterColumn {
+    fn execute_mut(
+        mut self,
+        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        transaction: &'a mut T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let AlterColumnOperator {
+                    table_name,
+                    column_name,
+                    ty,
+                } = self.op;
+
+                let schema = self.input.output_schema().clone();
+                let Some((column_index, column)) = schema
+                    .iter()
+                    .enumerate()
+                    .find(|(_, column)| column.name() == column_name)
+                else {
+                    yield Err(DatabaseError::NotFound("column", column_name));
+                    return;
+                };
+                let (column_id, is_primary) = (column.id(), column.desc.is_primary());
+                let mut tuples = Vec::new();
+                let mut coroutine = build_read(self.input, cache, transaction);
+
+                // Tips: a value failing to be cast aborts the statement before anything is written
+                while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                    let mut tuple: Tuple = throw!(tuple);
+                    let value = throw!(DataValue::clone(&tuple.values[column_index]).cast(&ty));
+                    throw!(value.check_len(&ty));
+
+                    tuple.values[column_index] = Arc::new(value);
+                    tuples.push(tuple);
+                }
+                drop(coroutine);
+                throw!(transaction.change_column_type(
+                    cache.0,
+                    cache.1,
+                    &table_name,
+                    &column_name,
+                    ty
+                ));
+                let table_catalog = throw!(transaction
+                    .table(cache.0, table_name.clone())
+                    .cloned()
+                    .ok_or(DatabaseError::TableNotFound));
+                let table_columns = table_catalog.schema_ref();
+                let mut index_metas = Vec::new();
+
+                // the entries of these indexes are dropped by `change_column_type`
+                for index_meta in table_catalog.indexes() {
+                    if is_primary || column_id.is_some_and(|id| index_meta.column_ids.contains(&id))
+                    {
+                        let exprs = throw!(index_meta.column_exprs(&table_catalog));
+                        index_metas.push((index_meta, exprs));
+                    }
+                }
+                if is_primary {
+                    let pk_indices = throw!(table_catalog.primary_key_indices());
+
+                    // the key of a tuple is encoded by the type of the primary key
+                    for tuple in tuples.iter_mut() {
+                        let new_id = tuple_id(
+                            pk_indices
+                                .iter()
+                                .map(|i| tuple.values[*i].clone())
+                                .collect(),
+                        );
+                        let old_id = tuple.id.replace(new_id);
+                        throw!(transaction.remove_tuple(&table_name, &old_id.unwrap()));
+                    }
+                }
+                for tuple in tuples {
+                    for (index_meta, exprs) in index_metas.iter() {
+                        let values = throw!(Projection::projection(&tuple, exprs, &schema));
+                        let index = Index::new(index_meta.id, &values, index_meta.ty);
+                        throw!(transaction.add_index(
+                            &table_name,
+                            &index_meta.name,
+                            index,
+                            tuple.id.as_ref().unwrap()
+                        ));
+                    }
+                    throw!(transaction.append_tuple(
+                        &table_name,
+                        tuple,
+                        table_columns,
+                        !is_primary
+                    ));
+                }
+
+                yield Ok(TupleBuilder::build_result("1".to_string()));
+            },
+        )
+    }
+}
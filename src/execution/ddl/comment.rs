@@ -0,0 +1,43 @@
+use crate::execution::{Executor, WriteExecutor};
+use crate::planner::operator::comment::CommentOperator;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::tuple_builder::TupleBuilder;
+
+pub struct Comment {
+    op: CommentOperator,
+}
+
+impl From<CommentOperator> for Comment {
+    fn from(op: CommentOperator) -> Self {
+        Comment { op }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Comment {
+    fn execute_mut(
+        self,
+        (table_cache, _): (&'a TableCache, &'a StatisticsMetaCache),
+        transaction: &'a mut T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let CommentOperator {
+                    table_name,
+                    column_name,
+                    comment,
+                } = self.op;
+
+                throw!(transaction.set_comment(
+                    table_cache,
+                    &table_name,
+                    column_name.as_deref(),
+                    comment
+                ));
+
+                yield Ok(TupleBuilder::build_result(format!("{}", table_name)));
+            },
+        )
+    }
+}
@@ -2,14 +2,17 @@ use crate::execution::dql::projection::Projection;
 use crate::execution::DatabaseError;
 use crate::execution::{build_read, Executor, WriteExecutor};
 use crate::expression::ScalarExpression;
+use crate::function::json::json_path_expr;
 use crate::planner::operator::create_index::CreateIndexOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
-use crate::types::index::Index;
+use crate::types::index::{Index, IndexType};
 use crate::types::tuple::Tuple;
 use crate::types::tuple_builder::TupleBuilder;
 use crate::types::ColumnId;
+use itertools::Itertools;
+use std::collections::HashSet;
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
 use std::pin::Pin;
@@ -40,34 +43,18 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateIndex {
                     columns,
                     if_not_exists,
                     ty,
+                    json_path,
                 } = self.op;
 
-                let (column_ids, column_exprs): (Vec<ColumnId>, Vec<ScalarExpression>) = columns
-                    .into_iter()
-                    .filter_map(|column| {
-                        column
-                            .id()
-                            .map(|id| (id, ScalarExpression::ColumnRef(column)))
-                    })
-                    .unzip();
+                let (column_ids, column_exprs): (Vec<ColumnId>, Vec<ScalarExpression>) =
+                    throw!(columns
+                        .into_iter()
+                        .filter_map(|column| {
+                            let id = column.id()?;
+                            Some(json_path_expr(column, &json_path).map(|expr| (id, expr)))
+                        })
+                        .process_results(|iter| iter.unzip()));
                 let schema = self.input.output_schema().clone();
-                let index_id = match transaction.add_index_meta(
-                    cache.0,
-                    &table_name,
-                    index_name,
-                    column_ids,
-                    ty,
-                ) {
-                    Ok(index_id) => index_id,
-                    Err(DatabaseError::DuplicateIndex(index_name)) => {
-                        if if_not_exists {
-                            return;
-                        } else {
-                            throw!(Err(DatabaseError::DuplicateIndex(index_name)))
-                        }
-                    }
-                    err => throw!(err),
-                };
                 let mut index_values = Vec::new();
                 let mut coroutine = build_read(self.input, cache, transaction);
 
@@ -85,9 +72,51 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateIndex {
                     ));
                 }
                 drop(coroutine);
+                // the duplicates are found before the index is kept, e.g. by
+                // `ALTER TABLE t ADD CONSTRAINT ... UNIQUE (c1, c2)` on the rows violating it
+                if let IndexType::Unique { nulls_not_distinct } = ty {
+                    let mut keys = HashSet::with_capacity(index_values.len());
+
+                    for (_, values) in index_values.iter() {
+                        if !nulls_not_distinct && values.iter().any(|value| value.is_null()) {
+                            continue;
+                        }
+                        if !keys.insert(values) {
+                            throw!(Err(DatabaseError::DuplicateUniqueValue(
+                                index_name.clone(),
+                                values.iter().join(", ")
+                            )));
+                        }
+                    }
+                }
+                // Tips: the table is reloaded into the cache while scanning, so the meta is added
+                // afterward to keep a failed build from leaving a stale index in the cache
+                let index_id = match transaction.add_index_meta(
+                    cache.0,
+                    &table_name,
+                    index_name.clone(),
+                    column_ids,
+                    ty,
+                    json_path,
+                ) {
+                    Ok(index_id) => index_id,
+                    Err(DatabaseError::DuplicateIndex(index_name)) => {
+                        if if_not_exists {
+                            return;
+                        } else {
+                            throw!(Err(DatabaseError::DuplicateIndex(index_name)))
+                        }
+                    }
+                    err => throw!(err),
+                };
                 for (tuple_id, values) in index_values {
                     let index = Index::new(index_id, &values, ty);
-                    throw!(transaction.add_index(table_name.as_str(), index, &tuple_id));
+                    throw!(transaction.add_index(
+                        table_name.as_str(),
+                        &index_name,
+                        index,
+                        &tuple_id
+                    ));
                 }
                 yield Ok(TupleBuilder::build_result("1".to_string()));
             },
@@ -1,23 +1,38 @@
-use crate::execution::{Executor, WriteExecutor};
+use crate::execution::{build_write, Executor, WriteExecutor};
 use crate::planner::operator::create_table::CreateTableOperator;
+use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
 use crate::types::tuple_builder::TupleBuilder;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
 
 pub struct CreateTable {
     op: CreateTableOperator,
+    /// Fills the table once created, e.g. the insertion of `SELECT ... INTO`
+    input: Option<LogicalPlan>,
 }
 
 impl From<CreateTableOperator> for CreateTable {
     fn from(op: CreateTableOperator) -> Self {
-        CreateTable { op }
+        CreateTable { op, input: None }
+    }
+}
+
+impl From<(CreateTableOperator, LogicalPlan)> for CreateTable {
+    fn from((op, input): (CreateTableOperator, LogicalPlan)) -> Self {
+        CreateTable {
+            op,
+            input: Some(input),
+        }
     }
 }
 
 impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateTable {
     fn execute_mut(
         self,
-        (table_cache, _): (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache),
         transaction: &'a mut T,
     ) -> Executor<'a> {
         Box::new(
@@ -26,17 +41,50 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateTable {
                 let CreateTableOperator {
                     table_name,
                     columns,
+                    checks,
+                    unique_keys,
+                    foreign_keys,
                     if_not_exists,
+                    view_query,
                 } = self.op;
 
-                let _ = throw!(transaction.create_table(
-                    table_cache,
-                    table_name.clone(),
-                    columns,
-                    if_not_exists
-                ));
+                // `CREATE TABLE IF NOT EXISTS ... AS` leaves the existing table as it is
+                if if_not_exists
+                    && self.input.is_some()
+                    && (transaction.table(cache.0, table_name.clone()).is_some()
+                        || throw!(transaction.view(&table_name)).is_some())
+                {
+                    yield Ok(TupleBuilder::build_result(format!("{}", table_name)));
+                    return;
+                }
+                if let Some(view_query) = view_query {
+                    let _ = throw!(transaction.create_materialized_view(
+                        cache.0,
+                        table_name.clone(),
+                        columns,
+                        view_query
+                    ));
+                } else {
+                    let _ = throw!(transaction.create_table(
+                        cache.0,
+                        table_name.clone(),
+                        columns,
+                        checks,
+                        unique_keys,
+                        foreign_keys,
+                        if_not_exists
+                    ));
+                }
+
+                if let Some(input) = self.input {
+                    let mut coroutine = build_write(input, cache, transaction);
 
-                yield Ok(TupleBuilder::build_result(format!("{}", table_name)));
+                    while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                        yield tuple;
+                    }
+                } else {
+                    yield Ok(TupleBuilder::build_result(format!("{}", table_name)));
+                }
             },
         )
     }
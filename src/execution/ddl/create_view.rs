@@ -0,0 +1,35 @@
+use crate::execution::{Executor, WriteExecutor};
+use crate::planner::operator::create_view::CreateViewOperator;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::tuple_builder::TupleBuilder;
+
+pub struct CreateView {
+    op: CreateViewOperator,
+}
+
+impl From<CreateViewOperator> for CreateView {
+    fn from(op: CreateViewOperator) -> Self {
+        CreateView { op }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CreateView {
+    fn execute_mut(
+        self,
+        _: (&'a TableCache, &'a StatisticsMetaCache),
+        transaction: &'a mut T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let CreateViewOperator { view, or_replace } = self.op;
+                let view_name = view.name.clone();
+
+                throw!(transaction.create_view(view, or_replace));
+
+                yield Ok(TupleBuilder::build_result(format!("{}", view_name)));
+            },
+        )
+    }
+}
@@ -41,7 +41,7 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for DropColumn {
                     .iter()
                     .enumerate()
                     .find(|(_, column)| column.name() == column_name)
-                    .map(|(i, column)| (i, column.desc.is_primary))
+                    .map(|(i, column)| (i, column.desc.is_primary()))
                 {
                     if is_primary {
                         throw!(Err(DatabaseError::InvalidColumn(
@@ -49,13 +49,13 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for DropColumn {
                         )));
                     }
                     let mut tuples = Vec::new();
-                    let mut types = Vec::with_capacity(tuple_columns.len() - 1);
+                    let mut table_columns = Vec::with_capacity(tuple_columns.len() - 1);
 
                     for (i, column_ref) in tuple_columns.iter().enumerate() {
                         if i == column_index {
                             continue;
                         }
-                        types.push(*column_ref.datatype());
+                        table_columns.push(column_ref.clone());
                     }
                     let mut coroutine = build_read(self.input, cache, transaction);
 
@@ -67,7 +67,7 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for DropColumn {
                     }
                     drop(coroutine);
                     for tuple in tuples {
-                        throw!(transaction.append_tuple(&table_name, tuple, &types, true));
+                        throw!(transaction.append_tuple(&table_name, tuple, &table_columns, true));
                     }
                     throw!(transaction.drop_column(cache.0, cache.1, &table_name, &column_name));
 
@@ -0,0 +1,44 @@
+use crate::execution::{Executor, WriteExecutor};
+use crate::planner::operator::alter_table::drop_constraint::DropConstraintOperator;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::tuple_builder::TupleBuilder;
+
+pub struct DropConstraint {
+    op: DropConstraintOperator,
+}
+
+impl From<DropConstraintOperator> for DropConstraint {
+    fn from(op: DropConstraintOperator) -> Self {
+        DropConstraint { op }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for DropConstraint {
+    fn execute_mut(
+        self,
+        (table_cache, meta_cache): (&'a TableCache, &'a StatisticsMetaCache),
+        transaction: &'a mut T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let DropConstraintOperator {
+                    table_name,
+                    constraint_name,
+                    if_exists,
+                } = self.op;
+
+                throw!(transaction.drop_constraint(
+                    table_cache,
+                    meta_cache,
+                    &table_name,
+                    &constraint_name,
+                    if_exists
+                ));
+
+                yield Ok(TupleBuilder::build_result(constraint_name));
+            },
+        )
+    }
+}
@@ -24,13 +24,20 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for DropTable {
             #[coroutine]
             move || {
                 let DropTableOperator {
-                    table_name,
+                    table_names,
                     if_exists,
                 } = self.op;
+                let mut count = 0;
 
-                throw!(transaction.drop_table(table_cache, table_name.clone(), if_exists));
+                for table_name in table_names {
+                    // Tips: `IF EXISTS` skips the missing tables, which are not counted
+                    if transaction.table(table_cache, table_name.clone()).is_some() {
+                        count += 1;
+                    }
+                    throw!(transaction.drop_table(table_cache, table_name, if_exists));
+                }
 
-                yield Ok(TupleBuilder::build_result(format!("{}", table_name)));
+                yield Ok(TupleBuilder::build_result(count.to_string()));
             },
         )
     }
@@ -0,0 +1,43 @@
+use crate::execution::{Executor, WriteExecutor};
+use crate::planner::operator::drop_view::DropViewOperator;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::tuple_builder::TupleBuilder;
+
+pub struct DropView {
+    op: DropViewOperator,
+}
+
+impl From<DropViewOperator> for DropView {
+    fn from(op: DropViewOperator) -> Self {
+        DropView { op }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for DropView {
+    fn execute_mut(
+        self,
+        _: (&'a TableCache, &'a StatisticsMetaCache),
+        transaction: &'a mut T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let DropViewOperator {
+                    view_names,
+                    if_exists,
+                } = self.op;
+                let mut count = 0;
+
+                for view_name in view_names {
+                    if throw!(transaction.view(&view_name)).is_some() {
+                        count += 1;
+                    }
+                    throw!(transaction.drop_view(view_name, if_exists));
+                }
+
+                yield Ok(TupleBuilder::build_result(count.to_string()));
+            },
+        )
+    }
+}
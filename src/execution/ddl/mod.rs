@@ -1,6 +1,14 @@
+pub(crate) mod add_check;
 pub mod add_column;
+pub(crate) mod add_foreign_key;
+pub(crate) mod alter_column;
+pub(crate) mod comment;
 pub(crate) mod create_index;
 pub(crate) mod create_table;
+pub(crate) mod create_view;
 pub mod drop_column;
+pub(crate) mod drop_constraint;
 pub(crate) mod drop_table;
+pub(crate) mod drop_view;
+pub(crate) mod rename_table;
 pub(crate) mod truncate;
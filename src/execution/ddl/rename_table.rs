@@ -0,0 +1,42 @@
+use crate::execution::{Executor, WriteExecutor};
+use crate::planner::operator::alter_table::rename_table::RenameTableOperator;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::tuple_builder::TupleBuilder;
+
+pub struct RenameTable {
+    op: RenameTableOperator,
+}
+
+impl From<RenameTableOperator> for RenameTable {
+    fn from(op: RenameTableOperator) -> Self {
+        RenameTable { op }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for RenameTable {
+    fn execute_mut(
+        self,
+        (table_cache, meta_cache): (&'a TableCache, &'a StatisticsMetaCache),
+        transaction: &'a mut T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let RenameTableOperator {
+                    table_name,
+                    new_table_name,
+                } = self.op;
+
+                throw!(transaction.rename_table(
+                    table_cache,
+                    meta_cache,
+                    &table_name,
+                    new_table_name.clone()
+                ));
+
+                yield Ok(TupleBuilder::build_result(new_table_name.to_string()));
+            },
+        )
+    }
+}
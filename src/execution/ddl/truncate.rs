@@ -1,33 +1,63 @@
-use crate::execution::{Executor, WriteExecutor};
+use crate::execution::{build_write, Executor, WriteExecutor};
 use crate::planner::operator::truncate::TruncateOperator;
+use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
 use crate::types::tuple_builder::TupleBuilder;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
 
 pub struct Truncate {
     op: TruncateOperator,
+    /// Refills the table once truncated, e.g. the insertion of `REFRESH MATERIALIZED VIEW`
+    input: Option<LogicalPlan>,
 }
 
 impl From<TruncateOperator> for Truncate {
     fn from(op: TruncateOperator) -> Self {
-        Truncate { op }
+        Truncate { op, input: None }
+    }
+}
+
+impl From<(TruncateOperator, LogicalPlan)> for Truncate {
+    fn from((op, input): (TruncateOperator, LogicalPlan)) -> Self {
+        Truncate {
+            op,
+            input: Some(input),
+        }
     }
 }
 
 impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Truncate {
     fn execute_mut(
         self,
-        _: (&'a TableCache, &'a StatisticsMetaCache),
+        cache: (&'a TableCache, &'a StatisticsMetaCache),
         transaction: &'a mut T,
     ) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
-                let TruncateOperator { table_name } = self.op;
+                let TruncateOperator {
+                    table_name,
+                    restart_identity,
+                } = self.op;
 
+                throw!(transaction.check_unreferenced(&table_name));
                 throw!(transaction.drop_data(&table_name));
+                if restart_identity {
+                    throw!(transaction.reset_sequence(&table_name));
+                }
+
+                if let Some(input) = self.input {
+                    let mut coroutine = build_write(input, cache, transaction);
 
-                yield Ok(TupleBuilder::build_result(format!("{}", table_name)));
+                    while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                        yield tuple;
+                    }
+                } else {
+                    yield Ok(TupleBuilder::build_result(format!("{}", table_name)));
+                }
             },
         )
     }
@@ -96,6 +96,7 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Analyze {
                     }
                 }
                 drop(coroutine);
+                throw!(transaction.compact_row_count(&table_name));
                 let mut values = Vec::with_capacity(builders.len());
                 let dir_path = dirs::config_dir()
                     .expect("Your system does not have a Config directory!")
@@ -1,15 +1,21 @@
 use crate::binder::copy::FileFormat;
+use crate::catalog::ColumnCatalog;
 use crate::errors::DatabaseError;
 use crate::execution::{Executor, WriteExecutor};
 use crate::planner::operator::copy_from_file::CopyFromFileOperator;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
-use crate::types::tuple::{types, Tuple};
+use crate::types::tuple::{tuple_id_of, Tuple};
 use crate::types::tuple_builder::TupleBuilder;
+use crate::types::value::{DataValue, Utf8Type};
+use crate::types::LogicalType;
+use serde_json::Value;
+use sqlparser::ast::CharLengthUnits;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufRead, BufReader};
 use std::sync::mpsc;
 use std::sync::mpsc::Sender;
+use std::sync::Arc;
 use std::thread;
 
 pub struct CopyFromFile {
@@ -32,20 +38,21 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CopyFromFile {
         Box::new(
             #[coroutine]
             move || {
-                let types = types(&self.op.schema_ref);
                 let (tx, rx) = mpsc::channel();
                 let (tx1, rx1) = mpsc::channel();
                 // # Cancellation
                 // When this stream is dropped, the `rx` is dropped, the spawned task will fail to send to
                 // `tx`, then the task will finish.
                 let table_name = self.op.table.clone();
+                let schema_ref = self.op.schema_ref.clone();
                 let handle = thread::spawn(|| self.read_file_blocking(tx));
                 let mut size = 0_usize;
                 while let Ok(chunk) = rx.recv() {
-                    throw!(transaction.append_tuple(&table_name, chunk, &types, false));
+                    throw!(transaction.append_tuple(&table_name, chunk, &schema_ref, false));
                     size += 1;
                 }
                 throw!(handle.join().unwrap());
+                throw!(transaction.add_row_count(&table_name, Some(size as i64)));
 
                 let handle = thread::spawn(move || return_result(size, tx1));
                 while let Ok(chunk) = rx1.recv() {
@@ -62,7 +69,7 @@ impl CopyFromFile {
     ///
     /// The read data chunks will be sent through `tx`.
     fn read_file_blocking(mut self, tx: Sender<Tuple>) -> Result<(), DatabaseError> {
-        let file = File::open(self.op.source.path)?;
+        let file = File::open(&self.op.source.path)?;
         let mut buf_reader = BufReader::new(file);
         let mut reader = match self.op.source.format {
             FileFormat::Csv {
@@ -76,6 +83,7 @@ impl CopyFromFile {
                 .escape(escape.map(|c| c as u8))
                 .has_headers(header)
                 .from_reader(&mut buf_reader),
+            FileFormat::Ndjson => return self.read_ndjson_blocking(buf_reader, tx),
         };
 
         let column_count = self.op.schema_ref.len();
@@ -97,6 +105,74 @@ impl CopyFromFile {
         }
         Ok(())
     }
+
+    /// Read the lines of a NDJSON file, see [`FileFormat::Ndjson`] for how the JSON values are
+    /// imported.
+    fn read_ndjson_blocking(
+        mut self,
+        reader: impl BufRead,
+        tx: Sender<Tuple>,
+    ) -> Result<(), DatabaseError> {
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Value::Object(mut object) = serde_json::from_str(&line)? else {
+                return Err(DatabaseError::MisMatch("a JSON object", "the line"));
+            };
+            let mut values = Vec::with_capacity(self.op.schema_ref.len());
+
+            for column in self.op.schema_ref.iter() {
+                // Tips: the default is evaluated for each line, e.g. `DEFAULT gen_random_uuid()`
+                let value = match object.remove(column.name()) {
+                    Some(json) => Arc::new(json_to_value(json, column)?),
+                    None => column
+                        .default_value()?
+                        .unwrap_or_else(|| Arc::new(DataValue::none(column.datatype()))),
+                };
+                if value.is_null() && !column.nullable {
+                    return Err(DatabaseError::NotNull);
+                }
+                values.push(value);
+            }
+
+            self.size += 1;
+            tx.send(Tuple {
+                id: tuple_id_of(&self.op.schema_ref, &values),
+                values,
+            })
+            .map_err(|_| DatabaseError::ChannelClose)?;
+        }
+        Ok(())
+    }
+}
+
+fn json_to_value(json: Value, column: &ColumnCatalog) -> Result<DataValue, DatabaseError> {
+    let ty = column.datatype();
+    let value = match &json {
+        Value::Null => return Ok(DataValue::none(ty)),
+        json if *ty == LogicalType::Json => return Ok(DataValue::Json(Some(json.to_string()))),
+        Value::Bool(v) if *ty == LogicalType::Boolean => return Ok(DataValue::Boolean(Some(*v))),
+        Value::Number(v) => v.to_string(),
+        Value::String(v) => v.clone(),
+        _ => return Err(json_mismatch(&json, column)),
+    };
+    DataValue::Utf8 {
+        value: Some(value),
+        ty: Utf8Type::Variable(None),
+        unit: CharLengthUnits::Characters,
+    }
+    .cast(ty)
+    .map_err(|_| json_mismatch(&json, column))
+}
+
+fn json_mismatch(json: &Value, column: &ColumnCatalog) -> DatabaseError {
+    DatabaseError::JsonMismatch(
+        json.to_string(),
+        column.name().to_string(),
+        column.datatype().clone(),
+    )
 }
 
 fn return_result(size: usize, tx: Sender<Tuple>) -> Result<(), DatabaseError> {
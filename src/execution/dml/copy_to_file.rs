@@ -1,6 +1,405 @@
+use crate::binder::copy::FileFormat;
+use crate::errors::DatabaseError;
+use crate::execution::{build_read, Executor, WriteExecutor};
 use crate::planner::operator::copy_to_file::CopyToFileOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::tuple::{Schema, Tuple};
+use crate::types::tuple_builder::TupleBuilder;
+use crate::types::value::DataValue;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::{Coroutine, CoroutineState};
+use std::pin::Pin;
 
-#[allow(dead_code)]
 pub struct CopyToFile {
     op: CopyToFileOperator,
+    input: LogicalPlan,
+}
+
+impl From<(CopyToFileOperator, LogicalPlan)> for CopyToFile {
+    fn from((op, input): (CopyToFileOperator, LogicalPlan)) -> Self {
+        CopyToFile { op, input }
+    }
+}
+
+impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for CopyToFile {
+    fn execute_mut(
+        self,
+        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        transaction: &'a mut T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let CopyToFile { op, mut input } = self;
+
+                let schema = input.output_schema().clone();
+                let mut writer = throw!(op.create_writer());
+                if op.header() {
+                    throw!(writer.write_header(&schema));
+                }
+
+                let mut coroutine = build_read(input, cache, transaction);
+                let mut size = 0_usize;
+
+                while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                    let tuple = throw!(tuple);
+
+                    throw!(writer.write_tuple(&schema, &tuple));
+                    size += 1;
+                }
+                drop(coroutine);
+                throw!(writer.flush());
+
+                yield Ok(TupleBuilder::build_result(format!("export {} rows", size)));
+            },
+        )
+    }
+}
+
+impl CopyToFileOperator {
+    fn header(&self) -> bool {
+        match self.target.format {
+            FileFormat::Csv { header, .. } => header,
+            FileFormat::Ndjson => false,
+        }
+    }
+
+    fn create_writer(&self) -> Result<FileWriter, DatabaseError> {
+        let file = File::create(&self.target.path)?;
+
+        Ok(match self.target.format {
+            FileFormat::Csv {
+                delimiter,
+                quote,
+                escape,
+                ..
+            } => {
+                let mut builder = csv::WriterBuilder::new();
+                builder.delimiter(delimiter as u8).quote(quote as u8);
+                if let Some(escape) = escape {
+                    builder.escape(escape as u8).double_quote(false);
+                }
+                FileWriter::Csv(Box::new(builder.from_writer(file)))
+            }
+            FileFormat::Ndjson => FileWriter::Ndjson(BufWriter::new(file)),
+        })
+    }
+}
+
+enum FileWriter {
+    Csv(Box<csv::Writer<File>>),
+    Ndjson(BufWriter<File>),
+}
+
+impl FileWriter {
+    fn write_header(&mut self, schema: &Schema) -> Result<(), DatabaseError> {
+        if let FileWriter::Csv(writer) = self {
+            writer.write_record(schema.iter().map(|column| column.name()))?;
+        }
+        Ok(())
+    }
+
+    fn write_tuple(&mut self, schema: &Schema, tuple: &Tuple) -> Result<(), DatabaseError> {
+        match self {
+            FileWriter::Csv(writer) => writer.write_record(tuple.values.iter().map(|value| {
+                // Tips: `NULL` is exported as an unquoted empty field
+                if value.is_null() {
+                    String::new()
+                } else {
+                    value.to_string()
+                }
+            }))?,
+            FileWriter::Ndjson(writer) => {
+                writer.write_all(b"{")?;
+                for (i, (column, value)) in schema.iter().zip(tuple.values.iter()).enumerate() {
+                    if i > 0 {
+                        writer.write_all(b",")?;
+                    }
+                    serde_json::to_writer(&mut *writer, column.name())?;
+                    writer.write_all(b":")?;
+                    Self::write_json_value(writer, value)?;
+                }
+                writer.write_all(b"}\n")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Numbers, booleans and JSON values are written as they are, the other values as strings,
+    /// so decimals are exported exactly.
+    fn write_json_value(writer: &mut impl Write, value: &DataValue) -> Result<(), DatabaseError> {
+        match value {
+            value if value.is_null() => serde_json::to_writer(writer, &()),
+            DataValue::Boolean(Some(v)) => serde_json::to_writer(writer, v),
+            DataValue::Float32(Some(v)) => serde_json::to_writer(writer, v),
+            DataValue::Float64(Some(v)) => serde_json::to_writer(writer, v),
+            DataValue::Int8(Some(v)) => serde_json::to_writer(writer, v),
+            DataValue::Int16(Some(v)) => serde_json::to_writer(writer, v),
+            DataValue::Int32(Some(v)) => serde_json::to_writer(writer, v),
+            DataValue::Int64(Some(v)) => serde_json::to_writer(writer, v),
+            DataValue::UInt8(Some(v)) => serde_json::to_writer(writer, v),
+            DataValue::UInt16(Some(v)) => serde_json::to_writer(writer, v),
+            DataValue::UInt32(Some(v)) => serde_json::to_writer(writer, v),
+            DataValue::UInt64(Some(v)) => serde_json::to_writer(writer, v),
+            // Tips: the JSON text is normalized, so it is a single line
+            DataValue::Json(Some(v)) => return Ok(writer.write_all(v.as_bytes())?),
+            value => serde_json::to_writer(writer, &value.to_string()),
+        }?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), DatabaseError> {
+        match self {
+            FileWriter::Csv(writer) => writer.flush()?,
+            FileWriter::Ndjson(writer) => writer.flush()?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::DataBaseBuilder;
+    use crate::errors::DatabaseError;
+    use itertools::Itertools;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn write_csv() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let _ = db.run("create table t1 (a int primary key, b varchar)")?;
+        let _ = db.run("create table t2 (c int primary key, d int not null)")?;
+        let _ = db
+            .run("insert into t1 values (0, 'zero'), (1, null), (2, 'two, \"2\"'), (3, 'three')")?;
+        let _ = db.run("insert into t2 values (10, 0), (11, 1), (12, 2), (13, 3)")?;
+
+        let query = "select a, b, c from t1 inner join t2 on a = d where c > 10 order by a";
+        let path = temp_dir.path().join("export.csv");
+        let (_, tuples) = db.run(format!(
+            "copy ({}) to '{}' with (header true)",
+            query,
+            path.display()
+        ))?;
+        assert_eq!(tuples[0].values[0].to_string(), "export 3 rows");
+
+        let (schema, tuples) = db.run(query)?;
+        let expected = std::iter::once(schema.iter().map(|column| column.name()).join(","))
+            .chain(tuples.iter().map(|tuple| {
+                tuple
+                    .values
+                    .iter()
+                    .map(|value| match value.to_string().as_str() {
+                        "null" => String::new(),
+                        value if value.contains(',') => {
+                            format!("\"{}\"", value.replace('"', "\"\""))
+                        }
+                        value => value.to_string(),
+                    })
+                    .join(",")
+            }))
+            .join("\n");
+        assert_eq!(fs::read_to_string(&path)?.trim_end(), expected);
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            "a,b,c\n1,,11\n2,\"two, \"\"2\"\"\",12\n3,three,13\n"
+        );
+
+        let _ = db.run(format!("copy t2 to '{}'", path.display()))?;
+        assert_eq!(fs::read_to_string(&path)?, "10,0\n11,1\n12,2\n13,3\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn ndjson_round_trip() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = DataBaseBuilder::path(temp_dir.path()).build()?;
+        let columns = "a int primary key, b varchar, c decimal(5, 2), d date, e boolean, f double, g int default 7";
+        let _ = db.run(format!("create table t1 ({})", columns))?;
+        let _ = db.run(format!("create table t2 ({})", columns))?;
+        let _ = db.run(
+            "insert into t1 values (0, 'zero, \"0\"', 1.25, '2024-01-01', true, 0.1, 1), (1, null, null, null, null, null, null)",
+        )?;
+
+        let path = temp_dir.path().join("export.ndjson");
+        let (_, tuples) = db.run(format!(
+            "copy t1 to '{}' with (format ndjson)",
+            path.display()
+        ))?;
+        assert_eq!(tuples[0].values[0].to_string(), "export 2 rows");
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            "{\"a\":0,\"b\":\"zero, \\\"0\\\"\",\"c\":\"1.25\",\"d\":\"2024-01-01\",\"e\":true,\"f\":0.1,\"g\":1}\n\
+             {\"a\":1,\"b\":null,\"c\":null,\"d\":null,\"e\":null,\"f\":null,\"g\":null}\n"
+        );
+
+        // the keys are unordered, and the missing ones are the defaults of the columns or `NULL`
+        fs::write(
+            &path,
+            format!(
+                "{}\n{{\"d\": \"2024-02-29\", \"a\": 2, \"c\": 3, \"unknown\": []}}\n",
+                fs::read_to_string(&path)?
+            ),
+        )?;
+        let (_, tuples) = db.run(format!("copy t2 from '{}' (format ndjson)", path.display()))?;
+        assert_eq!(tuples[0].values[0].to_string(), "import 3 rows");
+
+        let _ = db.run("insert into t1 values (2, null, 3, '2024-02-29', null, null, 7)")?;
+        let (_, expected) = db.run("select * from t1")?;
+        let (_, tuples) = db.run("select * from t2")?;
+        assert_eq!(tuples, expected);
+
+        let import = |json: &str| {
+            fs::write(&path, json)?;
+            db.run(format!("copy t2 from '{}' (format ndjson)", path.display()))
+        };
+        assert!(matches!(
+            import("{\"a\": 3, \"g\": 1.5}"),
+            Err(DatabaseError::JsonMismatch(value, column, _)) if value == "1.5" && column == "g"
+        ));
+        assert!(matches!(
+            import("{\"a\": 3, \"b\": true}"),
+            Err(DatabaseError::JsonMismatch(..))
+        ));
+        assert!(matches!(
+            import("{\"b\": \"b\"}"),
+            Err(DatabaseError::NotNull)
+        ));
+        assert!(matches!(import("[3]"), Err(DatabaseError::MisMatch(..))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn json_round_trip() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = DataBaseBuilder::path(temp_dir.path()).build()?;
+        for table in ["t1", "t2", "t3"] {
+            let _ = db.run(format!(
+                "create table {} (a int primary key, b json)",
+                table
+            ))?;
+        }
+        let _ = db.run(
+            "insert into t1 values (0, '{\"k\": \"v, w\", \"n\": [1, {\"m\": null}]}'), (1, '\"text\"'), (2, null)",
+        )?;
+        let (_, expected) = db.run("select * from t1")?;
+
+        let path = temp_dir.path().join("export.csv");
+        let _ = db.run(format!("copy t1 to '{}'", path.display()))?;
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            "0,\"{\"\"k\"\":\"\"v, w\"\",\"\"n\"\":[1,{\"\"m\"\":null}]}\"\n1,\"\"\"text\"\"\"\n2,\n"
+        );
+        let _ = db.run(format!("copy t2 from '{}'", path.display()))?;
+        let (_, tuples) = db.run("select * from t2")?;
+        assert_eq!(tuples, expected);
+
+        // the JSON values are written as they are rather than as strings
+        let path = temp_dir.path().join("export.ndjson");
+        let _ = db.run(format!(
+            "copy t1 to '{}' with (format ndjson)",
+            path.display()
+        ))?;
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            "{\"a\":0,\"b\":{\"k\":\"v, w\",\"n\":[1,{\"m\":null}]}}\n\
+             {\"a\":1,\"b\":\"text\"}\n\
+             {\"a\":2,\"b\":null}\n"
+        );
+        let _ = db.run(format!("copy t3 from '{}' (format ndjson)", path.display()))?;
+        let (_, tuples) = db.run("select * from t3")?;
+        assert_eq!(tuples, expected);
+
+        let (_, tuples) = db.run("select a from t3 where (b ->> 'k') = 'v, w'")?;
+        assert_eq!(tuples.len(), 1);
+        assert_eq!(tuples[0].values[0].to_string(), "0");
+
+        Ok(())
+    }
+
+    #[test]
+    fn uuid_round_trip() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = DataBaseBuilder::path(temp_dir.path()).build()?;
+        for table in ["t1", "t2"] {
+            let _ = db.run(format!(
+                "create table {} (a int primary key, b uuid default gen_random_uuid())",
+                table
+            ))?;
+        }
+        let _ =
+            db.run("insert into t1 values (0, 'A0EEBC99-9C0B-4EF8-BB6D-6BB9BD380A11'), (1, null)")?;
+        let (_, expected) = db.run("select * from t1")?;
+
+        // the UUIDs are written in the canonical form of lowercase digits
+        let path = temp_dir.path().join("export.csv");
+        let _ = db.run(format!("copy t1 to '{}'", path.display()))?;
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            "0,a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11\n1,\n"
+        );
+        let _ = db.run(format!("copy t2 from '{}'", path.display()))?;
+        let (_, tuples) = db.run("select * from t2")?;
+        assert_eq!(tuples, expected);
+
+        // the default is evaluated for each line which lacks the column
+        let path = temp_dir.path().join("import.ndjson");
+        fs::write(&path, "{\"a\": 2}\n{\"a\": 3}\n")?;
+        let _ = db.run(format!("copy t2 from '{}' (format ndjson)", path.display()))?;
+        let (_, tuples) = db.run("select count(distinct b) from t2 where a > 1")?;
+        assert_eq!(tuples[0].values[0].to_string(), "2");
+
+        Ok(())
+    }
+
+    #[test]
+    fn bytea_round_trip() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().unwrap();
+        let db = DataBaseBuilder::path(temp_dir.path()).build()?;
+        for table in ["t1", "t2", "t3"] {
+            let _ = db.run(format!(
+                "create table {} (a int primary key, b bytea)",
+                table
+            ))?;
+        }
+        // a value of 16 KiB with zero bytes all over it
+        let long = (0..16384).map(|i| format!("{:02x}", i % 7)).join("");
+        let _ = db.run(format!(
+            "insert into t1 values (0, X'{}'), (1, X'00'), (2, X''), (3, null)",
+            long
+        ))?;
+        let (_, expected) = db.run("select * from t1")?;
+
+        // the bytes are written in the hex form
+        let path = temp_dir.path().join("export.csv");
+        let _ = db.run(format!("copy t1 to '{}'", path.display()))?;
+        assert_eq!(
+            fs::read_to_string(&path)?,
+            format!("0,\\x{}\n1,\\x00\n2,\\x\n3,\n", long)
+        );
+        let _ = db.run(format!("copy t2 from '{}'", path.display()))?;
+        let (_, tuples) = db.run("select * from t2")?;
+        assert_eq!(tuples, expected);
+
+        let path = temp_dir.path().join("export.ndjson");
+        let _ = db.run(format!(
+            "copy t1 to '{}' with (format ndjson)",
+            path.display()
+        ))?;
+        let _ = db.run(format!("copy t3 from '{}' (format ndjson)", path.display()))?;
+        let (_, tuples) = db.run("select * from t3")?;
+        assert_eq!(tuples, expected);
+
+        let (_, tuples) = db.run(format!("select a from t3 where b = X'{}'", long))?;
+        assert_eq!(tuples.len(), 1);
+        assert_eq!(tuples[0].values[0].to_string(), "0");
+
+        Ok(())
+    }
 }
@@ -1,17 +1,19 @@
 use crate::catalog::TableName;
 use crate::errors::DatabaseError;
+use crate::execution::dml::foreign_key::RemovedKeys;
 use crate::execution::dql::projection::Projection;
 use crate::execution::{build_read, Executor, WriteExecutor};
 use crate::expression::ScalarExpression;
 use crate::planner::operator::delete::DeleteOperator;
+use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
 use crate::types::index::{Index, IndexId, IndexType};
-use crate::types::tuple::Tuple;
+use crate::types::tuple::{tuple_id, Tuple};
 use crate::types::tuple_builder::TupleBuilder;
 use crate::types::value::ValueRef;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
 use std::pin::Pin;
@@ -41,19 +43,48 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Delete {
                     mut input,
                 } = self;
 
-                let schema = input.output_schema().clone();
                 let table = throw!(transaction
                     .table(cache.0, table_name.clone())
                     .cloned()
                     .ok_or(DatabaseError::TableNotFound));
+                if Self::is_unconditioned(&input) {
+                    let removed_keys = RemovedKeys::new(&table).map(RemovedKeys::all);
+                    let count = throw!(transaction.clear_data(&table_name));
+                    if let Some(removed_keys) = removed_keys {
+                        throw!(removed_keys.check(transaction, cache.0, &table_name));
+                    }
+
+                    yield Ok(TupleBuilder::build_result(count.to_string()));
+                    return;
+                }
+                let schema = input.output_schema().clone();
+                let pk_indices = throw!(table.primary_key_indices());
                 let mut tuple_ids = Vec::new();
+                let mut deleted_ids = HashSet::new();
                 let mut indexes: HashMap<IndexId, Value> = HashMap::new();
+                let mut removed_keys = RemovedKeys::new(&table);
 
                 let mut coroutine = build_read(input, cache, transaction);
 
                 while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
-                    let tuple: Tuple = throw!(tuple);
+                    let mut tuple: Tuple = throw!(tuple);
 
+                    // the tuples of joins have no id
+                    let tuple_id = tuple.id.get_or_insert_with(|| {
+                        tuple_id(
+                            pk_indices
+                                .iter()
+                                .map(|i| tuple.values[*i].clone())
+                                .collect(),
+                        )
+                    });
+                    // a row joined with several rows is only deleted once
+                    if !deleted_ids.insert(tuple_id.clone()) {
+                        continue;
+                    }
+                    if let Some(removed_keys) = removed_keys.as_mut() {
+                        removed_keys.collect(&table, &tuple, &schema);
+                    }
                     for index_meta in table.indexes() {
                         if let Some(Value {
                             exprs, value_rows, ..
@@ -96,15 +127,35 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Delete {
                         ));
                     }
                 }
+                let count = tuple_ids.len();
                 for tuple_id in tuple_ids {
                     throw!(transaction.remove_tuple(&table_name, &tuple_id));
                 }
-                yield Ok(TupleBuilder::build_result("1".to_string()));
+                throw!(transaction.add_row_count(&table_name, Some(-(count as i64))));
+                if let Some(removed_keys) = removed_keys {
+                    throw!(removed_keys.check(transaction, cache.0, &table_name));
+                }
+                yield Ok(TupleBuilder::build_result(count.to_string()));
             },
         )
     }
 }
 
+impl Delete {
+    /// `DELETE FROM t` without `WHERE` clears the whole keyspace of the table instead of removing
+    /// the tuples one by one.
+    fn is_unconditioned(input: &LogicalPlan) -> bool {
+        match &input.operator {
+            Operator::TableScan(op) => {
+                input.childrens.is_empty()
+                    && op.limit == (None, None)
+                    && op.index_infos.iter().all(|info| info.range.is_none())
+            }
+            _ => false,
+        }
+    }
+}
+
 struct Value {
     exprs: Vec<ScalarExpression>,
     value_rows: Vec<Vec<ValueRef>>,
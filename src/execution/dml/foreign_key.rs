@@ -0,0 +1,255 @@
+use crate::catalog::{ColumnRef, ForeignKey, TableCatalog, TableName};
+use crate::errors::DatabaseError;
+use crate::execution::defer_foreign_key;
+use crate::storage::table_codec::TableCodec;
+use crate::storage::{Iter, TableCache, Transaction};
+use crate::types::index::Index;
+use crate::types::tuple::Tuple;
+use crate::types::value::ValueRef;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use std::slice;
+
+/// The keys referenced by the foreign keys of the rows written to a table, which are looked up
+/// once the whole statement is written, so that a row can reference a row written along with it.
+#[derive(Default)]
+pub(crate) struct ReferencedKeys {
+    /// the position of the foreign key in the table and the key it references
+    keys: Vec<(usize, ValueRef)>,
+}
+
+impl ReferencedKeys {
+    /// Collects the keys referenced by the row of `table`, a NULL references nothing.
+    pub(crate) fn collect(&mut self, table: &TableCatalog, tuple: &Tuple) {
+        for (i, foreign_key) in table.foreign_keys().enumerate() {
+            if let Some(value) = table
+                .get_column_index_by_name(&foreign_key.column)
+                .map(|j| &tuple.values[j])
+            {
+                if !value.is_null() {
+                    self.keys.push((i, value.clone()));
+                }
+            }
+        }
+    }
+
+    /// Fails with [`DatabaseError::ForeignKeyViolation`] if a key is missing from the table
+    /// it references.
+    pub(crate) fn check<T: Transaction>(
+        self,
+        transaction: &T,
+        table_cache: &TableCache,
+        table: &TableCatalog,
+    ) -> Result<(), DatabaseError> {
+        for (i, key) in self.keys {
+            let foreign_key = &table.foreign_keys[i];
+
+            if foreign_key.deferred && defer_foreign_key(&table.name, foreign_key) {
+                continue;
+            }
+
+            if !contains_key(
+                transaction,
+                table_cache,
+                &foreign_key.ref_table,
+                &foreign_key.ref_column,
+                &key,
+            )? {
+                return Err(DatabaseError::ForeignKeyViolation(
+                    table.name.to_string(),
+                    foreign_key.name.clone(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The keys removed from a table by the rows deleted or updated, which must not be referenced by
+/// the foreign keys of any row once the whole statement is written.
+pub(crate) struct RemovedKeys {
+    /// the foreign keys referencing the table, with the tables they belong to
+    references: Vec<(TableName, ForeignKey)>,
+    /// the removed keys of each referenced column, all of its keys if `None`
+    keys: HashMap<String, Option<HashSet<ValueRef>>>,
+}
+
+impl RemovedKeys {
+    /// `None` if no foreign key references the table, so that nothing has to be checked.
+    pub(crate) fn new(table: &TableCatalog) -> Option<Self> {
+        let references = table.referenced_by().cloned().collect_vec();
+        if references.is_empty() {
+            return None;
+        }
+        let keys = references
+            .iter()
+            .map(|(_, foreign_key)| (foreign_key.ref_column.clone(), Some(HashSet::new())))
+            .collect();
+
+        Some(RemovedKeys { references, keys })
+    }
+
+    /// Every key of the table is removed, e.g. by `DELETE` without `WHERE`.
+    pub(crate) fn all(mut self) -> Self {
+        for keys in self.keys.values_mut() {
+            *keys = None;
+        }
+        self
+    }
+
+    /// Collects the keys of the row of `table` to be removed, whose columns are `schema`.
+    pub(crate) fn collect(&mut self, table: &TableCatalog, tuple: &Tuple, schema: &[ColumnRef]) {
+        for (column_name, keys) in self.keys.iter_mut() {
+            let (Some(keys), Some(column)) = (keys, table.get_column_by_name(column_name)) else {
+                continue;
+            };
+            if let Some(value) = schema
+                .iter()
+                .position(|schema_column| schema_column.summary() == column.summary())
+                .map(|i| &tuple.values[i])
+            {
+                if !value.is_null() {
+                    keys.insert(value.clone());
+                }
+            }
+        }
+    }
+
+    /// Fails with [`DatabaseError::ForeignKeyReferenced`] if a row references a removed key,
+    /// which is not written to the table again.
+    pub(crate) fn check<T: Transaction>(
+        self,
+        transaction: &T,
+        table_cache: &TableCache,
+        table_name: &TableName,
+    ) -> Result<(), DatabaseError> {
+        for (child_name, foreign_key) in self.references.iter() {
+            let keys = &self.keys[&foreign_key.ref_column];
+
+            if foreign_key.deferred && defer_foreign_key(child_name, foreign_key) {
+                continue;
+            }
+
+            if keys.as_ref().is_some_and(|keys| keys.is_empty()) {
+                continue;
+            }
+            let child = transaction
+                .table(table_cache, child_name.clone())
+                .ok_or(DatabaseError::TableNotFound)?;
+            let i = child
+                .get_column_index_by_name(&foreign_key.column)
+                .ok_or_else(|| DatabaseError::NotFound("column", foreign_key.column.clone()))?;
+            let mut iter = transaction.read(
+                table_cache,
+                child_name.clone(),
+                (None, None),
+                vec![(i, child.schema_ref()[i].clone())],
+            )?;
+
+            while let Some(tuple) = iter.next_tuple()? {
+                let key = &tuple.values[0];
+
+                if key.is_null() || keys.as_ref().is_some_and(|keys| !keys.contains(key)) {
+                    continue;
+                }
+                if !contains_key(
+                    transaction,
+                    table_cache,
+                    table_name,
+                    &foreign_key.ref_column,
+                    key,
+                )? {
+                    return Err(DatabaseError::ForeignKeyReferenced(
+                        table_name.to_string(),
+                        foreign_key.name.clone(),
+                        child_name.to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks the foreign keys initially deferred by a transaction once it commits, which fails with
+/// [`DatabaseError::DeferredForeignKeyViolation`] naming every foreign key a row violates.
+pub(crate) fn check_deferred<T: Transaction>(
+    transaction: &T,
+    table_cache: &TableCache,
+    foreign_keys: &[(TableName, ForeignKey)],
+) -> Result<(), DatabaseError> {
+    let mut violated = Vec::new();
+
+    for (table_name, foreign_key) in foreign_keys {
+        // the table may be dropped by the transaction since
+        let Some(table) = transaction.table(table_cache, table_name.clone()) else {
+            continue;
+        };
+        let i = table
+            .get_column_index_by_name(&foreign_key.column)
+            .ok_or_else(|| DatabaseError::NotFound("column", foreign_key.column.clone()))?;
+        let mut iter = transaction.read(
+            table_cache,
+            table_name.clone(),
+            (None, None),
+            vec![(i, table.schema_ref()[i].clone())],
+        )?;
+
+        while let Some(tuple) = iter.next_tuple()? {
+            let key = &tuple.values[0];
+
+            if !key.is_null()
+                && !contains_key(
+                    transaction,
+                    table_cache,
+                    &foreign_key.ref_table,
+                    &foreign_key.ref_column,
+                    key,
+                )?
+            {
+                violated.push(format!("\"{}\" of table {}", foreign_key.name, table_name));
+                break;
+            }
+        }
+    }
+    if !violated.is_empty() {
+        return Err(DatabaseError::DeferredForeignKeyViolation(
+            violated.join(", "),
+        ));
+    }
+    Ok(())
+}
+
+/// Whether a row of the table has the key in the column, which is looked up by the primary key
+/// or the unique index on the column.
+pub(crate) fn contains_key<T: Transaction>(
+    transaction: &T,
+    table_cache: &TableCache,
+    table_name: &TableName,
+    column_name: &str,
+    key: &ValueRef,
+) -> Result<bool, DatabaseError> {
+    let table = transaction
+        .table(table_cache, table_name.clone())
+        .ok_or(DatabaseError::TableNotFound)?;
+    let column = table
+        .get_column_by_name(column_name)
+        .ok_or_else(|| DatabaseError::NotFound("column", column_name.to_string()))?;
+
+    let key = if column.desc.is_primary() && table.primary_keys()?.len() == 1 {
+        TableCodec::encode_tuple_key(table_name, key)?
+    } else {
+        let index_meta = table
+            .get_unique_index(&column.id().unwrap())
+            .ok_or_else(|| {
+                DatabaseError::InvalidForeignKey(format!(
+                    "there is no unique constraint on column {} of table {}",
+                    column_name, table_name
+                ))
+            })?;
+        let index = Index::new(index_meta.id, slice::from_ref(key), index_meta.ty);
+
+        TableCodec::encode_index_key(table_name, &index, None)?
+    };
+    Ok(transaction.get(&key)?.is_some())
+}
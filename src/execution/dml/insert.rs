@@ -1,15 +1,20 @@
-use crate::catalog::TableName;
+use crate::catalog::{CheckConstraint, TableName};
 use crate::errors::DatabaseError;
+use crate::execution::dml::foreign_key::ReferencedKeys;
+use crate::execution::dml::{check_constraints, generate_columns};
 use crate::execution::dql::projection::Projection;
-use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::execution::{build_read, set_last_insert_id, Executor, WriteExecutor};
+use crate::expression::ScalarExpression;
 use crate::planner::operator::insert::InsertOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
 use crate::types::index::Index;
-use crate::types::tuple::Tuple;
+use crate::types::tuple::{tuple_id, Tuple};
 use crate::types::tuple_builder::TupleBuilder;
-use crate::types::value::DataValue;
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+use itertools::Itertools;
 use std::collections::HashMap;
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
@@ -20,6 +25,8 @@ pub struct Insert {
     table_name: TableName,
     input: LogicalPlan,
     is_overwrite: bool,
+    checks: Vec<(CheckConstraint, ScalarExpression)>,
+    generated: Vec<(usize, ScalarExpression)>,
 }
 
 impl From<(InsertOperator, LogicalPlan)> for Insert {
@@ -28,6 +35,8 @@ impl From<(InsertOperator, LogicalPlan)> for Insert {
             InsertOperator {
                 table_name,
                 is_overwrite,
+                checks,
+                generated,
             },
             input,
         ): (InsertOperator, LogicalPlan),
@@ -36,6 +45,8 @@ impl From<(InsertOperator, LogicalPlan)> for Insert {
             table_name,
             input,
             is_overwrite,
+            checks,
+            generated,
         }
     }
 }
@@ -53,20 +64,27 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Insert {
                     table_name,
                     mut input,
                     is_overwrite,
+                    checks,
+                    generated,
                 } = self;
 
                 let mut tuples = Vec::new();
                 let schema = input.output_schema().clone();
 
-                let pk_index = throw!(schema
-                    .iter()
-                    .find(|col| col.desc.is_primary)
-                    .map(|col| col.id())
-                    .ok_or_else(|| DatabaseError::NotNull));
-
                 if let Some(table_catalog) = transaction.table(cache.0, table_name.clone()).cloned()
                 {
-                    let types = table_catalog.types();
+                    let table_columns = table_catalog.schema_ref();
+                    let pk_indices = throw!(table_catalog.primary_key_indices());
+                    let has_auto_increment = table_catalog
+                        .columns()
+                        .any(|column| column.desc.is_auto_increment);
+                    let mut sequence = if has_auto_increment {
+                        Some(throw!(transaction.sequence_value(&table_name)))
+                    } else {
+                        None
+                    };
+                    let mut first_assigned = None;
+                    let mut referenced_keys = ReferencedKeys::default();
                     let mut coroutine = build_read(input, cache, transaction);
 
                     while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
@@ -76,14 +94,10 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Insert {
                         for (i, value) in values.into_iter().enumerate() {
                             tuple_map.insert(schema[i].id(), value);
                         }
-                        let tuple_id = throw!(tuple_map
-                            .get(&pk_index)
-                            .cloned()
-                            .ok_or(DatabaseError::NotNull));
                         let mut values = Vec::with_capacity(table_catalog.columns_len());
 
                         for col in table_catalog.columns() {
-                            let value = {
+                            let mut value = {
                                 let mut value = tuple_map.remove(&col.id());
 
                                 if value.is_none() {
@@ -91,34 +105,79 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Insert {
                                 }
                                 value.unwrap_or_else(|| Arc::new(DataValue::none(col.datatype())))
                             };
-                            if value.is_null() && !col.nullable {
+                            if let Some(sequence) =
+                                sequence.as_mut().filter(|_| col.desc.is_auto_increment)
+                            {
+                                let is_assigned = value.is_null();
+
+                                value = throw!(auto_increment(sequence, value, col.datatype()));
+                                if is_assigned && first_assigned.is_none() {
+                                    first_assigned = Some(*sequence);
+                                }
+                            }
+                            if value.is_null() && !col.nullable && col.desc.generated.is_none() {
                                 yield Err(DatabaseError::NotNull);
                                 return;
                             }
                             values.push(value)
                         }
-                        tuples.push(Tuple {
-                            id: Some(tuple_id),
-                            values,
-                        });
+                        let mut tuple = Tuple { id: None, values };
+                        throw!(generate_columns(
+                            &generated,
+                            &mut tuple,
+                            table_catalog.schema_ref()
+                        ));
+                        tuple.id = Some(tuple_id(
+                            pk_indices
+                                .iter()
+                                .map(|i| tuple.values[*i].clone())
+                                .collect_vec(),
+                        ));
+                        throw!(check_constraints(
+                            &checks,
+                            &tuple,
+                            table_catalog.schema_ref()
+                        ));
+                        referenced_keys.collect(&table_catalog, &tuple);
+                        tuples.push(tuple);
                     }
                     drop(coroutine);
                     for index_meta in table_catalog.indexes() {
                         let exprs = throw!(index_meta.column_exprs(&table_catalog));
 
                         for tuple in tuples.iter() {
-                            let values = throw!(Projection::projection(tuple, &exprs, &schema));
+                            let values = throw!(Projection::projection(
+                                tuple,
+                                &exprs,
+                                table_catalog.schema_ref()
+                            ));
                             let index = Index::new(index_meta.id, &values, index_meta.ty);
 
                             throw!(transaction.add_index(
                                 &table_name,
+                                &index_meta.name,
                                 index,
                                 tuple.id.as_ref().unwrap()
                             ));
                         }
                     }
+                    // the tuples overwritten by `INSERT OVERWRITE` are not known without reading them
+                    let row_count = (!is_overwrite).then_some(tuples.len() as i64);
                     for tuple in tuples {
-                        throw!(transaction.append_tuple(&table_name, tuple, &types, is_overwrite));
+                        throw!(transaction.append_tuple(
+                            &table_name,
+                            tuple,
+                            table_columns,
+                            is_overwrite
+                        ));
+                    }
+                    throw!(transaction.add_row_count(&table_name, row_count));
+                    throw!(referenced_keys.check(transaction, cache.0, &table_catalog));
+                    if let Some(sequence) = sequence {
+                        throw!(transaction.set_sequence_value(&table_name, sequence));
+                    }
+                    if let Some(first_assigned) = first_assigned {
+                        set_last_insert_id(first_assigned);
                     }
                 }
                 yield Ok(TupleBuilder::build_result("1".to_string()));
@@ -126,3 +185,23 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Insert {
         )
     }
 }
+
+/// The value of the auto-increment column of a row, which is the next value of `sequence` if it
+/// is omitted or NULL, otherwise `sequence` is moved past it to keep from assigning it again.
+fn auto_increment(
+    sequence: &mut i64,
+    value: ValueRef,
+    ty: &LogicalType,
+) -> Result<ValueRef, DatabaseError> {
+    if value.is_null() {
+        *sequence = sequence.checked_add(1).ok_or_else(|| {
+            DatabaseError::ArithmeticOverflow("auto-increment sequence".to_string())
+        })?;
+
+        return Ok(Arc::new(DataValue::Int64(Some(*sequence)).cast(ty)?));
+    }
+    if let DataValue::Int64(Some(explicit)) = DataValue::clone(&value).cast(&LogicalType::Bigint)? {
+        *sequence = (*sequence).max(explicit);
+    }
+    Ok(value)
+}
@@ -2,5 +2,46 @@ pub(crate) mod analyze;
 pub(crate) mod copy_from_file;
 pub(crate) mod copy_to_file;
 pub(crate) mod delete;
+pub(crate) mod foreign_key;
 pub(crate) mod insert;
 pub(crate) mod update;
+
+use crate::catalog::{CheckConstraint, ColumnRef};
+use crate::errors::DatabaseError;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+
+/// Fails with [`DatabaseError::CheckViolation`] if the row makes the predicate of a `CHECK`
+/// constraint false, the row making it NULL passes.
+pub(crate) fn check_constraints(
+    checks: &[(CheckConstraint, ScalarExpression)],
+    tuple: &Tuple,
+    schema: &[ColumnRef],
+) -> Result<(), DatabaseError> {
+    for (check, expr) in checks {
+        if let DataValue::Boolean(Some(false)) = expr.eval(tuple, schema)?.as_ref() {
+            return Err(DatabaseError::CheckViolation(check.name.clone()));
+        }
+    }
+    Ok(())
+}
+
+/// Computes the values of the generated columns of the row from its other columns, whose
+/// positions in `schema` are given with their expressions.
+pub(crate) fn generate_columns(
+    generated: &[(usize, ScalarExpression)],
+    tuple: &mut Tuple,
+    schema: &[ColumnRef],
+) -> Result<(), DatabaseError> {
+    for (i, expr) in generated {
+        let value = expr.eval(tuple, schema)?;
+
+        if value.is_null() && !schema[*i].nullable {
+            return Err(DatabaseError::NotNull);
+        }
+        value.check_len(schema[*i].datatype())?;
+        tuple.values[*i] = value;
+    }
+    Ok(())
+}
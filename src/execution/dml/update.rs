@@ -1,15 +1,19 @@
-use crate::catalog::TableName;
+use crate::catalog::{CheckConstraint, TableName};
+use crate::errors::DatabaseError;
+use crate::execution::dml::foreign_key::{ReferencedKeys, RemovedKeys};
+use crate::execution::dml::{check_constraints, generate_columns};
 use crate::execution::dql::projection::Projection;
 use crate::execution::{build_read, Executor, WriteExecutor};
+use crate::expression::ScalarExpression;
 use crate::planner::operator::update::UpdateOperator;
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
 use crate::types::index::Index;
-use crate::types::tuple::types;
-use crate::types::tuple::Tuple;
+use crate::types::tuple::{tuple_id, Tuple};
 use crate::types::tuple_builder::TupleBuilder;
-use std::collections::HashMap;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
 use std::pin::Pin;
@@ -18,16 +22,28 @@ pub struct Update {
     table_name: TableName,
     input: LogicalPlan,
     values: LogicalPlan,
+    checks: Vec<(CheckConstraint, ScalarExpression)>,
+    generated: Vec<(usize, ScalarExpression)>,
 }
 
 impl From<(UpdateOperator, LogicalPlan, LogicalPlan)> for Update {
     fn from(
-        (UpdateOperator { table_name }, input, values): (UpdateOperator, LogicalPlan, LogicalPlan),
+        (
+            UpdateOperator {
+                table_name,
+                checks,
+                generated,
+            },
+            input,
+            values,
+        ): (UpdateOperator, LogicalPlan, LogicalPlan),
     ) -> Self {
         Update {
             table_name,
             input,
             values,
+            checks,
+            generated,
         }
     }
 }
@@ -45,16 +61,36 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Update {
                     table_name,
                     mut input,
                     mut values,
+                    checks,
+                    generated,
                 } = self;
 
                 let values_schema = values.output_schema().clone();
                 let input_schema = input.output_schema().clone();
-                let types = types(&input_schema);
 
                 if let Some(table_catalog) = transaction.table(cache.0, table_name.clone()).cloned()
                 {
+                    // Tips: the columns after the ones of the table are the new values of each row,
+                    // named after the columns they are assigned to,
+                    // e.g. `UPDATE t1 SET c2 = t2.c4 FROM t2 WHERE t1.c1 = t2.c3`
+                    let (table_schema, row_value_schema) =
+                        input_schema.split_at(table_catalog.columns_len());
+                    let table_columns = table_catalog.schema_ref();
+                    let pk_indices = throw!(table_catalog.primary_key_indices());
+                    let row_value_indexes: Vec<usize> = throw!(row_value_schema
+                        .iter()
+                        .map(|column| {
+                            table_schema
+                                .iter()
+                                .position(|table_column| table_column.name() == column.name())
+                                .ok_or_else(|| {
+                                    DatabaseError::NotFound("column", column.name().to_string())
+                                })
+                        })
+                        .try_collect());
                     let mut value_map = HashMap::new();
                     let mut tuples = Vec::new();
+                    let mut tuple_ids = HashSet::new();
 
                     // only once
                     let mut coroutine = build_read(values, cache, transaction);
@@ -69,18 +105,32 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Update {
                     let mut coroutine = build_read(input, cache, transaction);
 
                     while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
-                        let tuple: Tuple = throw!(tuple);
+                        let mut tuple: Tuple = throw!(tuple);
+                        let row_values = tuple.values.split_off(table_schema.len());
 
-                        tuples.push(tuple);
+                        // the tuples of joins have no id
+                        let tuple_id = tuple.id.get_or_insert_with(|| {
+                            tuple_id(
+                                pk_indices
+                                    .iter()
+                                    .map(|i| tuple.values[*i].clone())
+                                    .collect(),
+                            )
+                        });
+                        // a row joined with several rows is only updated once
+                        if !tuple_ids.insert(tuple_id.clone()) {
+                            continue;
+                        }
+                        tuples.push((tuple, row_values));
                     }
                     drop(coroutine);
                     let mut index_metas = Vec::new();
                     for index_meta in table_catalog.indexes() {
                         let exprs = throw!(index_meta.column_exprs(&table_catalog));
 
-                        for tuple in tuples.iter() {
+                        for (tuple, _) in tuples.iter() {
                             let values =
-                                throw!(Projection::projection(tuple, &exprs, &input_schema));
+                                throw!(Projection::projection(tuple, &exprs, table_schema));
                             let index = Index::new(index_meta.id, &values, index_meta.ty);
                             throw!(transaction.del_index(
                                 &table_name,
@@ -90,32 +140,73 @@ impl<'a, T: Transaction + 'a> WriteExecutor<'a, T> for Update {
                         }
                         index_metas.push((index_meta, exprs));
                     }
-                    for mut tuple in tuples {
+                    let mut referenced_keys = ReferencedKeys::default();
+                    let mut removed_keys = RemovedKeys::new(&table_catalog);
+
+                    for (mut tuple, row_values) in tuples {
                         let mut is_overwrite = true;
+                        if let Some(removed_keys) = removed_keys.as_mut() {
+                            removed_keys.collect(&table_catalog, &tuple, table_schema);
+                        }
+                        let new_values = table_schema
+                            .iter()
+                            .enumerate()
+                            .filter_map(|(i, column)| {
+                                Some((i, value_map.get(&column.id())?.clone()))
+                            })
+                            .chain(row_value_indexes.iter().copied().zip(row_values));
 
-                        for (i, column) in input_schema.iter().enumerate() {
-                            if let Some(value) = value_map.get(&column.id()) {
-                                if column.desc.is_primary {
-                                    let old_key = tuple.id.replace(value.clone()).unwrap();
+                        let mut is_key_updated = false;
 
-                                    throw!(transaction.remove_tuple(&table_name, &old_key));
-                                    is_overwrite = false;
-                                }
-                                tuple.values[i] = value.clone();
-                            }
+                        for (i, value) in new_values {
+                            is_key_updated |= table_schema[i].desc.is_primary();
+                            tuple.values[i] = value;
                         }
+                        if !generated.is_empty() {
+                            let old_values = tuple.values.clone();
+
+                            throw!(generate_columns(&generated, &mut tuple, table_schema));
+                            is_key_updated |= pk_indices
+                                .iter()
+                                .any(|i| tuple.values[*i] != old_values[*i]);
+                        }
+                        // the row is moved to its new key, e.g. `SET c1 = 2` of `PRIMARY KEY (c1, c2)`
+                        if is_key_updated {
+                            let new_key = tuple_id(
+                                pk_indices
+                                    .iter()
+                                    .map(|i| tuple.values[*i].clone())
+                                    .collect(),
+                            );
+                            let old_key = tuple.id.replace(new_key).unwrap();
+
+                            throw!(transaction.remove_tuple(&table_name, &old_key));
+                            is_overwrite = false;
+                        }
+                        throw!(check_constraints(&checks, &tuple, table_schema));
+                        referenced_keys.collect(&table_catalog, &tuple);
                         for (index_meta, exprs) in index_metas.iter() {
                             let values =
-                                throw!(Projection::projection(&tuple, exprs, &input_schema));
+                                throw!(Projection::projection(&tuple, exprs, table_schema));
                             let index = Index::new(index_meta.id, &values, index_meta.ty);
                             throw!(transaction.add_index(
                                 &table_name,
+                                &index_meta.name,
                                 index,
                                 tuple.id.as_ref().unwrap()
                             ));
                         }
 
-                        throw!(transaction.append_tuple(&table_name, tuple, &types, is_overwrite));
+                        throw!(transaction.append_tuple(
+                            &table_name,
+                            tuple,
+                            table_columns,
+                            is_overwrite
+                        ));
+                    }
+                    throw!(referenced_keys.check(transaction, cache.0, &table_catalog));
+                    if let Some(removed_keys) = removed_keys {
+                        throw!(removed_keys.check(transaction, cache.0, &table_name));
                     }
                 }
                 yield Ok(TupleBuilder::build_result("1".to_string()));
@@ -1,21 +1,26 @@
 use crate::errors::DatabaseError;
 use crate::execution::dql::aggregate::sum::SumAccumulator;
 use crate::execution::dql::aggregate::Accumulator;
+use crate::expression::agg::AggKind;
 use crate::expression::BinaryOperator;
 use crate::types::evaluator::EvaluatorFactory;
 use crate::types::value::{DataValue, ValueRef};
 use crate::types::LogicalType;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use std::sync::Arc;
 
 pub struct AvgAccumulator {
     inner: SumAccumulator,
+    ty: LogicalType,
     count: usize,
 }
 
 impl AvgAccumulator {
     pub fn new(ty: &LogicalType) -> Result<Self, DatabaseError> {
         Ok(Self {
-            inner: SumAccumulator::new(ty)?,
+            inner: SumAccumulator::new(&AggKind::Sum.return_type(ty.clone()))?,
+            ty: ty.clone(),
             count: 0,
         })
     }
@@ -38,10 +43,28 @@ impl Accumulator for AvgAccumulator {
         if self.count == 0 {
             return Ok(Arc::new(DataValue::init(&value_ty)));
         }
+        if let DataValue::Decimal(Some(sum)) = value.as_ref() {
+            let mut avg = sum
+                .checked_div(Decimal::from(self.count))
+                .ok_or_else(|| DatabaseError::ArithmeticOverflow(format!("avg of {}", sum)))?;
+            match self.ty {
+                LogicalType::Decimal(_, Some(scale)) => {
+                    avg = avg.round_dp_with_strategy(
+                        scale as u32,
+                        rust_decimal::RoundingStrategy::MidpointAwayFromZero,
+                    );
+                    avg.rescale(scale as u32);
+                }
+                LogicalType::Decimal(..) => (),
+                // Tips: `BIGINT`s are summed as decimals, but averaged as doubles like the others
+                _ => return Ok(Arc::new(DataValue::Float64(avg.to_f64()))),
+            }
+            return Ok(Arc::new(DataValue::Decimal(Some(avg))));
+        }
         let quantity = if value_ty.is_signed_numeric() {
             DataValue::Int64(Some(self.count as i64))
         } else {
-            DataValue::UInt32(Some(self.count as u32))
+            DataValue::UInt64(Some(self.count as u64))
         };
         let quantity_ty = quantity.logical_type();
 
@@ -49,6 +72,8 @@ impl Accumulator for AvgAccumulator {
             value = Arc::new(DataValue::clone(&value).cast(&quantity_ty)?)
         }
         let evaluator = EvaluatorFactory::binary_create(quantity_ty, BinaryOperator::Divide)?;
-        Ok(Arc::new(evaluator.0.binary_eval(&value, &quantity)))
+        Ok(Arc::new(
+            evaluator.0.checked_binary_eval(&value, &quantity)?,
+        ))
     }
 }
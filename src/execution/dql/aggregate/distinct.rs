@@ -0,0 +1,36 @@
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::Accumulator;
+use crate::types::value::ValueRef;
+use ahash::RandomState;
+use std::collections::HashSet;
+
+/// Feeds each distinct non-null value to the inner accumulator once, e.g. for `COUNT(DISTINCT c)`.
+///
+/// Only the distinct values of a group are kept, not its rows.
+pub struct DistinctAccumulator {
+    distinct_values: HashSet<ValueRef, RandomState>,
+    inner: Box<dyn Accumulator>,
+}
+
+impl DistinctAccumulator {
+    pub fn new(inner: Box<dyn Accumulator>) -> Self {
+        Self {
+            distinct_values: HashSet::default(),
+            inner,
+        }
+    }
+}
+
+impl Accumulator for DistinctAccumulator {
+    fn update_value(&mut self, value: &ValueRef) -> Result<(), DatabaseError> {
+        if !value.is_null() && self.distinct_values.insert(value.clone()) {
+            self.inner.update_value(value)?;
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<ValueRef, DatabaseError> {
+        self.inner.evaluate()
+    }
+}
@@ -1,7 +1,7 @@
 use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
-use crate::execution::dql::aggregate::{create_accumulators, Accumulator};
-use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::execution::dql::aggregate::{create_accumulators, eval_agg_arg, Accumulator};
+use crate::execution::{build_read, Executor, ReadExecutor, WorkMem};
 use crate::expression::ScalarExpression;
 use crate::planner::operator::aggregate::AggregateOperator;
 use crate::planner::LogicalPlan;
@@ -9,8 +9,9 @@ use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
 use crate::types::tuple::{SchemaRef, Tuple};
 use crate::types::value::ValueRef;
-use ahash::HashMap;
+use crate::utils::hash::FixedHashMap;
 use itertools::Itertools;
+use std::collections::hash_map::Entry;
 use std::ops::{Coroutine, CoroutineState};
 use std::pin::Pin;
 
@@ -78,7 +79,8 @@ pub(crate) struct HashAggStatus {
     groupby_exprs: Vec<ScalarExpression>,
 
     group_columns: Vec<ColumnRef>,
-    group_hash_accs: HashMap<Vec<ValueRef>, Vec<Box<dyn Accumulator>>>,
+    group_hash_accs: FixedHashMap<Vec<ValueRef>, Vec<Box<dyn Accumulator>>>,
+    work_mem: WorkMem,
 }
 
 impl HashAggStatus {
@@ -93,6 +95,7 @@ impl HashAggStatus {
             groupby_exprs,
             group_columns: vec![],
             group_hash_accs: Default::default(),
+            work_mem: WorkMem::new("hash aggregate"),
         }
     }
 
@@ -109,16 +112,10 @@ impl HashAggStatus {
         }
 
         // 2.1 evaluate agg exprs and collect the result values for later accumulators.
-        let values: Vec<ValueRef> = self
+        let values: Vec<Option<ValueRef>> = self
             .agg_calls
             .iter()
-            .map(|expr| {
-                if let ScalarExpression::AggCall { args, .. } = expr {
-                    args[0].eval(&tuple, &self.schema_ref)
-                } else {
-                    unreachable!()
-                }
-            })
+            .map(|expr| eval_agg_arg(expr, &tuple, &self.schema_ref))
             .try_collect()?;
 
         let group_keys: Vec<ValueRef> = self
@@ -127,14 +124,18 @@ impl HashAggStatus {
             .map(|expr| expr.eval(&tuple, &self.schema_ref))
             .try_collect()?;
 
-        for (acc, value) in self
-            .group_hash_accs
-            .entry(group_keys)
-            .or_insert_with(|| create_accumulators(&self.agg_calls).unwrap())
-            .iter_mut()
-            .zip_eq(values.iter())
-        {
-            acc.update_value(value)?;
+        let accs = match self.group_hash_accs.entry(group_keys) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            // Tips: only the keys of each group are counted, not the state of its accumulators
+            Entry::Vacant(entry) => {
+                self.work_mem.reserve(entry.key())?;
+                entry.insert(create_accumulators(&self.agg_calls)?)
+            }
+        };
+        for (acc, value) in accs.iter_mut().zip_eq(values.iter()) {
+            if let Some(value) = value {
+                acc.update_value(value)?;
+            }
         }
 
         Ok(())
@@ -162,11 +163,13 @@ mod test {
     use crate::catalog::{ColumnCatalog, ColumnDesc};
     use crate::errors::DatabaseError;
     use crate::execution::dql::aggregate::hash_agg::HashAggExecutor;
+    use crate::execution::dql::join::hash_join::HashJoin;
     use crate::execution::dql::test::build_integers;
     use crate::execution::{try_collect, ReadExecutor};
     use crate::expression::agg::AggKind;
     use crate::expression::ScalarExpression;
     use crate::planner::operator::aggregate::AggregateOperator;
+    use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
     use crate::planner::operator::values::ValuesOperator;
     use crate::planner::operator::Operator;
     use crate::planner::LogicalPlan;
@@ -203,6 +206,7 @@ mod test {
                 distinct: false,
                 kind: AggKind::Sum,
                 args: vec![ScalarExpression::ColumnRef(t1_schema[1].clone())],
+                filter: None,
                 ty: LogicalType::Integer,
             }],
             is_distinct: false,
@@ -261,4 +265,126 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_hash_agg_deterministic_order() -> Result<(), DatabaseError> {
+        let meta_cache = Arc::new(ShardingLruCache::new(4, 1, RandomState::new())?);
+        let table_cache = Arc::new(ShardingLruCache::new(4, 1, RandomState::new())?);
+
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = RocksStorage::new(temp_dir.path()).unwrap();
+        let transaction = storage.transaction()?;
+        let desc = ColumnDesc::new(LogicalType::Integer, false, false, None)?;
+
+        let t1_schema = Arc::new(vec![
+            Arc::new(ColumnCatalog::new("c1".to_string(), true, desc.clone())),
+            Arc::new(ColumnCatalog::new("c2".to_string(), true, desc.clone())),
+        ]);
+        let run = || {
+            let operator = AggregateOperator {
+                groupby_exprs: vec![ScalarExpression::ColumnRef(t1_schema[0].clone())],
+                agg_calls: vec![ScalarExpression::AggCall {
+                    distinct: false,
+                    kind: AggKind::Sum,
+                    args: vec![ScalarExpression::ColumnRef(t1_schema[1].clone())],
+                    filter: None,
+                    ty: LogicalType::Integer,
+                }],
+                is_distinct: false,
+            };
+            let input = LogicalPlan {
+                operator: Operator::Values(ValuesOperator {
+                    rows: (0..1000)
+                        .map(|i| build_integers(vec![Some(i % 100), Some(i)]))
+                        .collect_vec(),
+                    schema_ref: t1_schema.clone(),
+                }),
+                childrens: vec![],
+                physical_option: None,
+                _output_schema_ref: None,
+            };
+
+            try_collect(
+                HashAggExecutor::from((operator, input))
+                    .execute((&table_cache, &meta_cache), &transaction),
+            )
+        };
+
+        // Tips: the groups come out in the order of the hash table, which must not be seeded randomly
+        let first = run()?;
+        let second = run()?;
+
+        debug_assert_eq!(first.len(), 100);
+        debug_assert_eq!(first, second);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hash_join_deterministic_order() -> Result<(), DatabaseError> {
+        let meta_cache = Arc::new(ShardingLruCache::new(4, 1, RandomState::new())?);
+        let table_cache = Arc::new(ShardingLruCache::new(4, 1, RandomState::new())?);
+
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = RocksStorage::new(temp_dir.path()).unwrap();
+        let transaction = storage.transaction()?;
+        let desc = ColumnDesc::new(LogicalType::Integer, false, false, None)?;
+
+        let t1_schema = Arc::new(vec![
+            Arc::new(ColumnCatalog::new("c1".to_string(), true, desc.clone())),
+            Arc::new(ColumnCatalog::new("c2".to_string(), true, desc.clone())),
+        ]);
+        let t2_schema = Arc::new(vec![
+            Arc::new(ColumnCatalog::new("c3".to_string(), true, desc.clone())),
+            Arc::new(ColumnCatalog::new("c4".to_string(), true, desc.clone())),
+        ]);
+        let values = |rows: Vec<_>, schema_ref| LogicalPlan {
+            operator: Operator::Values(ValuesOperator { rows, schema_ref }),
+            childrens: vec![],
+            physical_option: None,
+            _output_schema_ref: None,
+        };
+        let run = || {
+            let operator = JoinOperator {
+                on: JoinCondition::On {
+                    on: vec![(
+                        ScalarExpression::ColumnRef(t1_schema[0].clone()),
+                        ScalarExpression::ColumnRef(t2_schema[0].clone()),
+                    )],
+                    filter: None,
+                },
+                join_type: JoinType::Full,
+                correlated_columns: vec![],
+                match_once: false,
+            };
+            let left = values(
+                (0..1000)
+                    .map(|i| build_integers(vec![Some(i % 500), Some(i)]))
+                    .collect_vec(),
+                t1_schema.clone(),
+            );
+            let right = values(
+                (0..1000)
+                    .step_by(10)
+                    .map(|i| build_integers(vec![Some(i), Some(i)]))
+                    .collect_vec(),
+                t2_schema.clone(),
+            );
+
+            try_collect(
+                HashJoin::from((operator, left, right))
+                    .execute((&table_cache, &meta_cache), &transaction),
+            )
+        };
+
+        // Tips: the unmatched rows of the build side come out in the order of the buckets of the
+        // hash table, which must not be seeded randomly
+        let first = run()?;
+        let second = run()?;
+
+        debug_assert_eq!(first.len(), 1050);
+        debug_assert_eq!(first, second);
+
+        Ok(())
+    }
 }
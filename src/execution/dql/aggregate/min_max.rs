@@ -23,7 +23,7 @@ impl MinMaxAccumulator {
         Self {
             inner: None,
             op,
-            ty: *ty,
+            ty: ty.clone(),
         }
     }
 }
@@ -34,7 +34,7 @@ impl Accumulator for MinMaxAccumulator {
             if let Some(inner_value) = &self.inner {
                 let evaluator = EvaluatorFactory::binary_create(value.logical_type(), self.op)?;
                 if let DataValue::Boolean(Some(result)) =
-                    evaluator.0.binary_eval(inner_value, value)
+                    evaluator.0.checked_binary_eval(inner_value, value)?
                 {
                     result
                 } else {
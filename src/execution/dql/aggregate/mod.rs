@@ -1,5 +1,6 @@
 mod avg;
 mod count;
+mod distinct;
 pub mod hash_agg;
 mod min_max;
 pub mod simple_agg;
@@ -7,13 +8,16 @@ mod sum;
 
 use crate::errors::DatabaseError;
 use crate::execution::dql::aggregate::avg::AvgAccumulator;
-use crate::execution::dql::aggregate::count::{CountAccumulator, DistinctCountAccumulator};
+use crate::execution::dql::aggregate::count::CountAccumulator;
+use crate::execution::dql::aggregate::distinct::DistinctAccumulator;
 use crate::execution::dql::aggregate::min_max::MinMaxAccumulator;
-use crate::execution::dql::aggregate::sum::{DistinctSumAccumulator, SumAccumulator};
+use crate::execution::dql::aggregate::sum::SumAccumulator;
 use crate::expression::agg::AggKind;
 use crate::expression::ScalarExpression;
+use crate::types::tuple::{Schema, Tuple};
 use crate::types::value::ValueRef;
 use itertools::Itertools;
+use std::any::Any;
 
 /// Tips: Idea for sqlrs
 /// An accumulator represents a stateful object that lives throughout the evaluation of multiple
@@ -24,21 +28,54 @@ pub trait Accumulator: Send + Sync {
 
     /// returns its value based on its current state.
     fn evaluate(&self) -> Result<ValueRef, DatabaseError>;
+
+    /// merges the state of another accumulator of the same aggregate into this one, e.g. the
+    /// partial states of an aggregate computed in parallel.
+    fn merge(&mut self, _other: &dyn Accumulator) -> Result<(), DatabaseError> {
+        Err(DatabaseError::UnsupportedStmt(
+            "merging the accumulators of a built-in aggregate".to_string(),
+        ))
+    }
+
+    /// used to downcast `other` in [`Accumulator::merge`].
+    fn as_any(&self) -> Option<&dyn Any> {
+        None
+    }
 }
 
-fn create_accumulator(expr: &ScalarExpression) -> Result<Box<dyn Accumulator>, DatabaseError> {
+/// Tips: the optimizer may wrap an aggregate call in an alias to rename its output column,
+/// e.g. `PushAggregateThroughJoin`.
+pub(crate) fn unpack_agg_call(expr: &ScalarExpression) -> &ScalarExpression {
+    if let ScalarExpression::Alias { expr, .. } = expr {
+        unpack_agg_call(expr)
+    } else {
+        expr
+    }
+}
+
+pub(crate) fn create_accumulator(
+    expr: &ScalarExpression,
+) -> Result<Box<dyn Accumulator>, DatabaseError> {
     if let ScalarExpression::AggCall {
         kind, ty, distinct, ..
-    } = expr
+    } = unpack_agg_call(expr)
     {
-        Ok(match (kind, distinct) {
-            (AggKind::Count, false) => Box::new(CountAccumulator::new()),
-            (AggKind::Count, true) => Box::new(DistinctCountAccumulator::new()),
-            (AggKind::Sum, false) => Box::new(SumAccumulator::new(ty)?),
-            (AggKind::Sum, true) => Box::new(DistinctSumAccumulator::new(ty)?),
-            (AggKind::Min, _) => Box::new(MinMaxAccumulator::new(ty, false)),
-            (AggKind::Max, _) => Box::new(MinMaxAccumulator::new(ty, true)),
-            (AggKind::Avg, _) => Box::new(AvgAccumulator::new(ty)?),
+        let accumulator: Box<dyn Accumulator> = match kind {
+            AggKind::Count => Box::new(CountAccumulator::new()),
+            AggKind::Sum => Box::new(SumAccumulator::new(ty)?),
+            AggKind::Min => Box::new(MinMaxAccumulator::new(ty, false)),
+            AggKind::Max => Box::new(MinMaxAccumulator::new(ty, true)),
+            AggKind::Avg => Box::new(AvgAccumulator::new(ty)?),
+            AggKind::Custom(function) => function.inner.accumulator(),
+            AggKind::Grouping => {
+                unreachable!("GROUPING is computed per grouping set by the binder")
+            }
+        };
+        // Tips: the duplicates are skipped per group, before they are accumulated
+        Ok(if *distinct && kind.allow_distinct() {
+            Box::new(DistinctAccumulator::new(accumulator))
+        } else {
+            accumulator
         })
     } else {
         unreachable!(
@@ -53,3 +90,21 @@ pub(crate) fn create_accumulators(
 ) -> Result<Vec<Box<dyn Accumulator>>, DatabaseError> {
     exprs.iter().map(create_accumulator).try_collect()
 }
+
+/// Evaluates the argument of the aggregate call `expr` for `tuple`, `None` if the row is not
+/// aggregated by the `FILTER (WHERE ...)` of the call.
+pub(crate) fn eval_agg_arg(
+    expr: &ScalarExpression,
+    tuple: &Tuple,
+    schema: &Schema,
+) -> Result<Option<ValueRef>, DatabaseError> {
+    let ScalarExpression::AggCall { args, filter, .. } = unpack_agg_call(expr) else {
+        unreachable!()
+    };
+    if let Some(filter) = filter {
+        if !filter.eval(tuple, schema)?.is_true()? {
+            return Ok(None);
+        }
+    }
+    Ok(Some(args[0].eval(tuple, schema)?))
+}
@@ -1,16 +1,21 @@
-use crate::execution::dql::aggregate::create_accumulators;
+use crate::catalog::TableName;
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::{create_accumulators, eval_agg_arg, unpack_agg_call};
 use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::expression::agg::AggKind;
 use crate::expression::ScalarExpression;
 use crate::planner::operator::aggregate::AggregateOperator;
+use crate::planner::operator::{Operator, PhysicalOption};
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
 use crate::types::tuple::Tuple;
-use crate::types::value::ValueRef;
+use crate::types::value::{DataValue, ValueRef};
 use itertools::Itertools;
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
 use std::pin::Pin;
+use std::sync::Arc;
 
 pub struct SimpleAggExecutor {
     agg_calls: Vec<ScalarExpression>,
@@ -25,6 +30,32 @@ impl From<(AggregateOperator, LogicalPlan)> for SimpleAggExecutor {
     }
 }
 
+impl SimpleAggExecutor {
+    /// `COUNT(*)` (or `COUNT` of any non-null constant) directly over a full table scan can be
+    /// answered by the row count of the table.
+    fn count_star_table(agg_calls: &[ScalarExpression], input: &LogicalPlan) -> Option<TableName> {
+        let Operator::TableScan(scan_op) = &input.operator else {
+            return None;
+        };
+        if scan_op.limit != (None, None) || input.physical_option != Some(PhysicalOption::SeqScan) {
+            return None;
+        }
+        agg_calls
+            .iter()
+            .all(|expr| match unpack_agg_call(expr) {
+                ScalarExpression::AggCall {
+                    distinct: false,
+                    kind: AggKind::Count,
+                    args,
+                    filter: None,
+                    ..
+                } => matches!(args.as_slice(), [ScalarExpression::Constant(value)] if !value.is_null()),
+                _ => false,
+            })
+            .then(|| scan_op.table_name.clone())
+    }
+}
+
 impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for SimpleAggExecutor {
     fn execute(
         self,
@@ -39,6 +70,19 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for SimpleAggExecutor {
                     mut input,
                 } = self;
 
+                if let Some(table_name) = Self::count_star_table(&agg_calls, &input) {
+                    if let Some(row_count) = throw!(transaction.row_count(&table_name)) {
+                        let row_count =
+                            throw!(i32::try_from(row_count).map_err(DatabaseError::from));
+                        let value = Arc::new(DataValue::Int32(Some(row_count)));
+
+                        yield Ok(Tuple {
+                            id: None,
+                            values: vec![value; agg_calls.len()],
+                        });
+                        return;
+                    }
+                }
                 let mut accs = throw!(create_accumulators(&agg_calls));
                 let schema = input.output_schema().clone();
 
@@ -47,16 +91,15 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for SimpleAggExecutor {
                 while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
                     let tuple = throw!(tuple);
 
-                    let values: Vec<ValueRef> = throw!(agg_calls
+                    let values: Vec<Option<ValueRef>> = throw!(agg_calls
                         .iter()
-                        .map(|expr| match expr {
-                            ScalarExpression::AggCall { args, .. } => args[0].eval(&tuple, &schema),
-                            _ => unreachable!(),
-                        })
+                        .map(|expr| eval_agg_arg(expr, &tuple, &schema))
                         .try_collect());
 
                     for (acc, value) in accs.iter_mut().zip_eq(values.iter()) {
-                        throw!(acc.update_value(value));
+                        if let Some(value) = value {
+                            throw!(acc.update_value(value));
+                        }
                     }
                 }
                 let values: Vec<ValueRef> =
@@ -4,22 +4,26 @@ use crate::expression::BinaryOperator;
 use crate::types::evaluator::{BinaryEvaluatorBox, EvaluatorFactory};
 use crate::types::value::{DataValue, ValueRef};
 use crate::types::LogicalType;
-use ahash::RandomState;
-use std::collections::HashSet;
 use std::sync::Arc;
 
 pub struct SumAccumulator {
     result: DataValue,
+    ty: LogicalType,
+    /// the type of the values of `ty`, e.g. `DECIMAL` for `DECIMAL(10, 2)`
+    value_ty: LogicalType,
     evaluator: BinaryEvaluatorBox,
 }
 
 impl SumAccumulator {
     pub fn new(ty: &LogicalType) -> Result<Self, DatabaseError> {
-        debug_assert!(ty.is_numeric());
+        debug_assert!(ty.is_numeric() || matches!(ty, LogicalType::Decimal(..)));
+        let result = DataValue::none(ty);
 
         Ok(Self {
-            result: DataValue::none(ty),
-            evaluator: EvaluatorFactory::binary_create(*ty, BinaryOperator::Plus)?,
+            value_ty: result.logical_type(),
+            result,
+            ty: ty.clone(),
+            evaluator: EvaluatorFactory::binary_create(ty.clone(), BinaryOperator::Plus)?,
         })
     }
 }
@@ -27,10 +31,17 @@ impl SumAccumulator {
 impl Accumulator for SumAccumulator {
     fn update_value(&mut self, value: &ValueRef) -> Result<(), DatabaseError> {
         if !value.is_null() {
+            // Tips: the values are summed in the wider type of the result, e.g. `BIGINT` for
+            // `INTEGER`
+            let value = if value.logical_type() != self.value_ty {
+                Arc::new(DataValue::clone(value).cast(&self.ty)?)
+            } else {
+                value.clone()
+            };
             if self.result.is_null() {
-                self.result = DataValue::clone(value);
+                self.result = DataValue::clone(&value);
             } else {
-                self.result = self.evaluator.0.binary_eval(&self.result, value);
+                self.result = self.evaluator.0.checked_binary_eval(&self.result, &value)?;
             }
         }
 
@@ -38,35 +49,15 @@ impl Accumulator for SumAccumulator {
     }
 
     fn evaluate(&self) -> Result<ValueRef, DatabaseError> {
-        Ok(Arc::new(self.result.clone()))
-    }
-}
-
-pub struct DistinctSumAccumulator {
-    distinct_values: HashSet<ValueRef, RandomState>,
-    inner: SumAccumulator,
-}
-
-impl DistinctSumAccumulator {
-    pub fn new(ty: &LogicalType) -> Result<Self, DatabaseError> {
-        Ok(Self {
-            distinct_values: HashSet::default(),
-            inner: SumAccumulator::new(ty)?,
-        })
-    }
-}
-
-impl Accumulator for DistinctSumAccumulator {
-    fn update_value(&mut self, value: &ValueRef) -> Result<(), DatabaseError> {
-        if !self.distinct_values.contains(value) {
-            self.distinct_values.insert(value.clone());
-            self.inner.update_value(value)?;
+        // Tips: values are not padded to the scale of the column when stored
+        if let (LogicalType::Decimal(_, Some(scale)), DataValue::Decimal(Some(v))) =
+            (&self.ty, &self.result)
+        {
+            let mut decimal = *v;
+            decimal.rescale(*scale as u32);
+
+            return Ok(Arc::new(DataValue::Decimal(Some(decimal))));
         }
-
-        Ok(())
-    }
-
-    fn evaluate(&self) -> Result<ValueRef, DatabaseError> {
-        self.inner.evaluate()
+        Ok(Arc::new(self.result.clone()))
     }
 }
@@ -6,6 +6,7 @@ use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
 use crate::types::tuple::Tuple;
 use crate::types::value::{DataValue, Utf8Type, ValueRef};
+use itertools::Itertools;
 use lazy_static::lazy_static;
 use sqlparser::ast::CharLengthUnits;
 use std::sync::Arc;
@@ -52,8 +53,16 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Describe {
                 let table = throw!(transaction
                     .table(cache.0, self.table_name.clone())
                     .ok_or(DatabaseError::TableNotFound));
-                let key_fn = |column: &ColumnCatalog| {
-                    if column.desc.is_primary {
+                let is_composite_key = throw!(table.primary_keys()).len() > 1;
+                let key_fn = move |column: &ColumnCatalog| {
+                    if let Some(i) = column.desc.primary.filter(|_| is_composite_key) {
+                        // e.g. `PRIMARY(2)` of `c2` in `PRIMARY KEY (c1, c2)`
+                        Arc::new(DataValue::Utf8 {
+                            value: Some(format!("PRIMARY({})", i + 1)),
+                            ty: Utf8Type::Variable(None),
+                            unit: CharLengthUnits::Characters,
+                        })
+                    } else if column.desc.is_primary() {
                         PRIMARY_KEY_TYPE.clone()
                     } else if column.desc.is_unique {
                         UNIQUE_KEY_TYPE.clone()
@@ -66,10 +75,14 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Describe {
                     let datatype = column.datatype();
                     let default = column
                         .desc
-                        .default
-                        .as_ref()
-                        .map(|expr| format!("{}", expr))
+                        .default_display()
                         .unwrap_or_else(|| "null".to_string());
+                    // e.g. `t_c2_check CHECK (c2 > 0)`
+                    let checks = table
+                        .checks()
+                        .filter(|check| check.columns.iter().any(|name| name == column.name()))
+                        .map(|check| format!("{} CHECK ({})", check.name, check.expr))
+                        .join(", ");
                     let values = vec![
                         Arc::new(DataValue::Utf8 {
                             value: Some(column.name().to_string()),
@@ -97,6 +110,16 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Describe {
                             ty: Utf8Type::Variable(None),
                             unit: CharLengthUnits::Characters,
                         }),
+                        Arc::new(DataValue::Utf8 {
+                            value: (!checks.is_empty()).then_some(checks),
+                            ty: Utf8Type::Variable(None),
+                            unit: CharLengthUnits::Characters,
+                        }),
+                        Arc::new(DataValue::Utf8 {
+                            value: column.desc.comment.clone(),
+                            ty: Utf8Type::Variable(None),
+                            unit: CharLengthUnits::Characters,
+                        }),
                     ];
                     yield Ok(Tuple { id: None, values });
                 }
@@ -0,0 +1,62 @@
+use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::expression::ScalarExpression;
+use crate::planner::operator::distinct_on::DistinctOnOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::value::ValueRef;
+use itertools::Itertools;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
+
+pub struct DistinctOn {
+    on_exprs: Vec<ScalarExpression>,
+    input: LogicalPlan,
+}
+
+impl From<(DistinctOnOperator, LogicalPlan)> for DistinctOn {
+    fn from((DistinctOnOperator { on_exprs }, input): (DistinctOnOperator, LogicalPlan)) -> Self {
+        DistinctOn { on_exprs, input }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for DistinctOn {
+    fn execute(
+        self,
+        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let DistinctOn {
+                    on_exprs,
+                    mut input,
+                } = self;
+
+                let schema = input.output_schema().clone();
+                let mut last_key: Option<Vec<ValueRef>> = None;
+
+                let mut coroutine = build_read(input, cache, transaction);
+
+                // Tips: the input is sorted by the keys, so the rows of a group are adjacent and
+                // only the key of the last group is kept, NULLs are equal to each other
+                while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                    let tuple = throw!(tuple);
+                    let key: Vec<ValueRef> = throw!(on_exprs
+                        .iter()
+                        .map(|expr| expr.eval(&tuple, &schema))
+                        .try_collect());
+
+                    if last_key.as_ref() == Some(&key) {
+                        continue;
+                    }
+                    last_key = Some(key);
+
+                    yield Ok(tuple);
+                }
+            },
+        )
+    }
+}
@@ -1,17 +1,22 @@
+use crate::catalog::ColumnRef;
 use crate::execution::{Executor, ReadExecutor};
 use crate::expression::function::table::TableFunction;
 use crate::planner::operator::function_scan::FunctionScanOperator;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
+use crate::types::value::DataValue;
+use std::sync::Arc;
 
 pub struct FunctionScan {
     table_function: TableFunction,
+    ordinality: Option<ColumnRef>,
 }
 
 impl From<FunctionScanOperator> for FunctionScan {
     fn from(op: FunctionScanOperator) -> Self {
         FunctionScan {
             table_function: op.table_function,
+            ordinality: op.ordinality,
         }
     }
 }
@@ -22,8 +27,15 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for FunctionScan {
             #[coroutine]
             move || {
                 let TableFunction { args, inner } = self.table_function;
-                for tuple in throw!(inner.eval(&args)) {
-                    yield tuple;
+                for (i, tuple) in throw!(inner.eval(&args)).enumerate() {
+                    let mut tuple = throw!(tuple);
+
+                    if self.ordinality.is_some() {
+                        tuple
+                            .values
+                            .push(Arc::new(DataValue::Int64(Some(i as i64 + 1))));
+                    }
+                    yield Ok(tuple);
                 }
             },
         )
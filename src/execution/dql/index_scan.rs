@@ -39,6 +39,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for IndexScan {
                     table_name,
                     columns,
                     limit,
+                    order,
                     ..
                 } = self.op;
 
@@ -52,6 +53,9 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for IndexScan {
                         self.ranges,
                     )
                     .unwrap();
+                if order.is_some_and(|order| order.reverse) {
+                    iter = iter.rev();
+                }
 
                 while let Some(tuple) = throw!(iter.next_tuple()) {
                     yield Ok(tuple);
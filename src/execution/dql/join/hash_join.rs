@@ -1,7 +1,7 @@
 use crate::catalog::{ColumnCatalog, ColumnRef};
 use crate::errors::DatabaseError;
 use crate::execution::dql::join::joins_nullable;
-use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::execution::{build_read, Executor, ReadExecutor, WorkMem};
 use crate::expression::ScalarExpression;
 use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
 use crate::planner::LogicalPlan;
@@ -10,7 +10,7 @@ use crate::throw;
 use crate::types::tuple::{Schema, SchemaRef, Tuple};
 use crate::types::value::{DataValue, ValueRef, NULL_VALUE};
 use crate::utils::bit_vector::BitVector;
-use ahash::HashMap;
+use crate::utils::hash::FixedHashMap;
 use itertools::Itertools;
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
@@ -20,21 +20,28 @@ use std::sync::Arc;
 pub struct HashJoin {
     on: JoinCondition,
     ty: JoinType,
+    match_once: bool,
     left_input: LogicalPlan,
     right_input: LogicalPlan,
 }
 
 impl From<(JoinOperator, LogicalPlan, LogicalPlan)> for HashJoin {
     fn from(
-        (JoinOperator { on, join_type, .. }, left_input, right_input): (
-            JoinOperator,
-            LogicalPlan,
-            LogicalPlan,
-        ),
+        (
+            JoinOperator {
+                on,
+                join_type,
+                match_once,
+                ..
+            },
+            left_input,
+            right_input,
+        ): (JoinOperator, LogicalPlan, LogicalPlan),
     ) -> Self {
         HashJoin {
             on,
             ty: join_type,
+            match_once,
             left_input,
             right_input,
         }
@@ -53,12 +60,14 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashJoin {
                 let HashJoin {
                     on,
                     ty,
+                    match_once,
                     mut left_input,
                     mut right_input,
                 } = self;
                 let mut join_status = HashJoinStatus::new(
                     on,
                     ty,
+                    match_once,
                     left_input.output_schema(),
                     right_input.output_schema(),
                 );
@@ -106,10 +115,16 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for HashJoin {
     }
 }
 
+/// The rows on the left by their keys, whether a row on the right has the same keys,
+/// and which of the rows matched a semi or anti join.
+type BuildMap = FixedHashMap<Vec<ValueRef>, (Vec<Tuple>, bool, Option<BitVector>)>;
+
 pub(crate) struct HashJoinStatus {
     ty: JoinType,
+    match_once: bool,
     filter: Option<ScalarExpression>,
-    build_map: HashMap<Vec<ValueRef>, (Vec<Tuple>, bool, bool)>,
+    build_map: BuildMap,
+    work_mem: WorkMem,
 
     full_schema_ref: SchemaRef,
     left_schema_len: usize,
@@ -121,6 +136,7 @@ impl HashJoinStatus {
     pub(crate) fn new(
         on: JoinCondition,
         ty: JoinType,
+        match_once: bool,
         left_schema: &SchemaRef,
         right_schema: &SchemaRef,
     ) -> Self {
@@ -160,8 +176,10 @@ impl HashJoinStatus {
 
         HashJoinStatus {
             ty,
+            match_once,
             filter,
             build_map: Default::default(),
+            work_mem: WorkMem::new("hash join"),
 
             full_schema_ref: Arc::new(join_schema),
             left_schema_len,
@@ -174,15 +192,17 @@ impl HashJoinStatus {
         let HashJoinStatus {
             on_left_keys,
             build_map,
+            work_mem,
             full_schema_ref,
             left_schema_len,
             ..
         } = self;
         let values = Self::eval_keys(on_left_keys, &tuple, &full_schema_ref[0..*left_schema_len])?;
+        work_mem.reserve(&tuple.values)?;
 
         build_map
             .entry(values)
-            .or_insert_with(|| (Vec::new(), false, false))
+            .or_insert_with(|| (Vec::new(), false, None))
             .0
             .push(tuple);
 
@@ -198,12 +218,12 @@ impl HashJoinStatus {
                     full_schema_ref,
                     build_map,
                     ty,
+                    match_once,
                     filter,
                     left_schema_len,
                     ..
                 } = self;
 
-                let right_cols_len = tuple.values.len();
                 let values = throw!(Self::eval_keys(
                     on_right_keys,
                     &tuple,
@@ -211,24 +231,81 @@ impl HashJoinStatus {
                 ));
                 let has_null = values.iter().any(|value| value.is_null());
 
-                if let (false, Some((tuples, is_used, is_filtered))) =
+                if let (false, Some((tuples, is_used, matched))) =
                     (has_null, build_map.get_mut(&values))
                 {
-                    let mut bits_option = None;
                     *is_used = true;
 
-                    match ty {
-                        JoinType::LeftSemi => {
-                            if *is_filtered {
-                                return;
-                            } else {
-                                bits_option = Some(BitVector::new(tuples.len()));
+                    if matches!(ty, JoinType::LeftSemi | JoinType::LeftAnti) {
+                        // the rows on the left are only marked here, and returned once all
+                        // rows on the right have been probed
+                        let bits = matched.get_or_insert_with(|| BitVector::new(tuples.len()));
+
+                        for (i, Tuple { values, .. }) in tuples.iter().enumerate() {
+                            if bits.get_bit(i) {
+                                continue;
+                            }
+                            let full_values = values
+                                .iter()
+                                .cloned()
+                                .chain(tuple.values.iter().cloned())
+                                .collect_vec();
+                            let tuple = Tuple {
+                                id: None,
+                                values: full_values,
+                            };
+                            if throw!(Self::filter(
+                                tuple,
+                                full_schema_ref,
+                                filter,
+                                ty,
+                                *left_schema_len
+                            ))
+                            .is_some()
+                            {
+                                bits.set_bit(i, true);
+
+                                if *match_once {
+                                    break;
+                                }
                             }
                         }
-                        JoinType::LeftAnti => return,
-                        _ => (),
+                        return;
                     }
-                    for (i, Tuple { values, .. }) in tuples.iter().enumerate() {
+                    if matches!(ty, JoinType::Full) {
+                        // Tips: a row only matches if it satisfies the filter as well, the rows
+                        // on the left that never match are returned once all rows on the right
+                        // have been probed
+                        let bits = matched.get_or_insert_with(|| BitVector::new(tuples.len()));
+                        let mut is_matched = false;
+
+                        for (i, Tuple { values, .. }) in tuples.iter().enumerate() {
+                            let full_values = values
+                                .iter()
+                                .cloned()
+                                .chain(tuple.values.iter().cloned())
+                                .collect_vec();
+                            let full_tuple = Tuple {
+                                id: None,
+                                values: full_values,
+                            };
+                            if let Some(expr) = filter {
+                                if !throw!(throw!(expr.eval(&full_tuple, full_schema_ref)).is_true())
+                                {
+                                    continue;
+                                }
+                            }
+                            bits.set_bit(i, true);
+                            is_matched = true;
+
+                            yield Ok(full_tuple);
+                        }
+                        if !is_matched {
+                            yield Ok(Self::left_null_tuple(tuple, full_schema_ref));
+                        }
+                        return;
+                    }
+                    for Tuple { values, .. } in tuples.iter() {
                         let full_values = values
                             .iter()
                             .cloned()
@@ -245,29 +322,11 @@ impl HashJoinStatus {
                             ty,
                             *left_schema_len
                         )) {
-                            if let Some(bits) = bits_option.as_mut() {
-                                bits.set_bit(i, true);
-                            } else {
-                                yield Ok(tuple);
-                            }
+                            yield Ok(tuple);
                         }
                     }
-                    if let Some(bits) = bits_option {
-                        let mut cnt = 0;
-                        tuples.retain(|_| {
-                            let res = bits.get_bit(cnt);
-                            cnt += 1;
-                            res
-                        });
-                        *is_filtered = true
-                    }
                 } else if matches!(ty, JoinType::RightOuter | JoinType::Full) {
-                    let empty_len = full_schema_ref.len() - right_cols_len;
-                    let values = (0..empty_len)
-                        .map(|_| NULL_VALUE.clone())
-                        .chain(tuple.values)
-                        .collect_vec();
-                    let tuple = Tuple { id: None, values };
+                    let tuple = Self::left_null_tuple(tuple, full_schema_ref);
                     if let Some(tuple) = throw!(Self::filter(
                         tuple,
                         full_schema_ref,
@@ -321,8 +380,6 @@ impl HashJoinStatus {
             full_schema_ref,
             build_map,
             ty,
-            filter,
-            left_schema_len,
             ..
         } = self;
 
@@ -330,29 +387,35 @@ impl HashJoinStatus {
             JoinType::LeftOuter | JoinType::Full => {
                 Some(Self::right_null_tuple(build_map, full_schema_ref))
             }
-            JoinType::LeftSemi | JoinType::LeftAnti => Some(Self::one_side_tuple(
-                build_map,
-                full_schema_ref,
-                filter,
-                ty,
-                *left_schema_len,
-            )),
+            JoinType::LeftSemi | JoinType::LeftAnti => Some(Self::one_side_tuple(build_map, ty)),
             _ => None,
         }
     }
 
-    fn right_null_tuple<'a>(
-        build_map: &'a mut HashMap<Vec<ValueRef>, (Vec<Tuple>, bool, bool)>,
-        schema: &'a Schema,
-    ) -> Executor<'a> {
+    /// Pads the row on the right with NULLs for the columns on the left.
+    fn left_null_tuple(tuple: Tuple, schema: &Schema) -> Tuple {
+        let values = (0..schema.len() - tuple.values.len())
+            .map(|_| NULL_VALUE.clone())
+            .chain(tuple.values)
+            .collect_vec();
+
+        Tuple { id: None, values }
+    }
+
+    fn right_null_tuple<'a>(build_map: &'a mut BuildMap, schema: &'a Schema) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
-                for (_, (left_tuples, is_used, _)) in build_map.drain() {
-                    if is_used {
-                        continue;
-                    }
-                    for mut tuple in left_tuples {
+                for (_, (left_tuples, is_used, matched)) in build_map.drain() {
+                    for (i, mut tuple) in left_tuples.into_iter().enumerate() {
+                        // e.g. the rows of a full join are matched one by one by the filter
+                        let is_matched = match &matched {
+                            Some(bits) => bits.get_bit(i),
+                            None => is_used,
+                        };
+                        if is_matched {
+                            continue;
+                        }
                         while tuple.values.len() != schema.len() {
                             tuple.values.push(NULL_VALUE.clone());
                         }
@@ -363,39 +426,17 @@ impl HashJoinStatus {
         )
     }
 
-    fn one_side_tuple<'a>(
-        build_map: &'a mut HashMap<Vec<ValueRef>, (Vec<Tuple>, bool, bool)>,
-        schema: &'a Schema,
-        filter: &'a Option<ScalarExpression>,
-        join_ty: &'a JoinType,
-        left_schema_len: usize,
-    ) -> Executor<'a> {
+    fn one_side_tuple<'a>(build_map: &'a mut BuildMap, join_ty: &'a JoinType) -> Executor<'a> {
         Box::new(
             #[coroutine]
             move || {
                 let is_left_semi = matches!(join_ty, JoinType::LeftSemi);
 
-                for (_, (left_tuples, mut is_used, is_filtered)) in build_map.drain() {
-                    if is_left_semi {
-                        is_used = !is_used;
-                    }
-                    if is_used {
-                        continue;
-                    }
-                    if is_filtered {
-                        for tuple in left_tuples {
-                            yield Ok(tuple);
-                        }
-                        continue;
-                    }
-                    for tuple in left_tuples {
-                        if let Some(tuple) = throw!(Self::filter(
-                            tuple,
-                            schema,
-                            filter,
-                            join_ty,
-                            left_schema_len
-                        )) {
+                for (_, (left_tuples, _, matched)) in build_map.drain() {
+                    for (i, tuple) in left_tuples.into_iter().enumerate() {
+                        let is_matched = matched.as_ref().is_some_and(|bits| bits.get_bit(i));
+
+                        if is_matched == is_left_semi {
                             yield Ok(tuple);
                         }
                     }
@@ -538,6 +579,8 @@ mod test {
                 filter: None,
             },
             join_type: JoinType::Inner,
+            correlated_columns: vec![],
+            match_once: false,
         };
         let executor =
             HashJoin::from((op, left, right)).execute((&table_cache, &meta_cache), &transaction);
@@ -576,6 +619,8 @@ mod test {
                 filter: None,
             },
             join_type: JoinType::LeftOuter,
+            correlated_columns: vec![],
+            match_once: false,
         };
         //Outer
         {
@@ -655,6 +700,8 @@ mod test {
                 filter: None,
             },
             join_type: JoinType::RightOuter,
+            correlated_columns: vec![],
+            match_once: false,
         };
         let executor =
             HashJoin::from((op, left, right)).execute((&table_cache, &meta_cache), &transaction);
@@ -697,6 +744,8 @@ mod test {
                 filter: None,
             },
             join_type: JoinType::Full,
+            correlated_columns: vec![],
+            match_once: false,
         };
         let executor =
             HashJoin::from((op, left, right)).execute((&table_cache, &meta_cache), &transaction);
@@ -1,5 +1,9 @@
 //! Defines the nested loop join executor, it supports [`JoinType::Inner`], [`JoinType::LeftOuter`],
-//! [`JoinType::LeftSemi`], [`JoinType::LeftAnti`], [`JoinType::RightOuter`], [`JoinType::Cross`], [`JoinType::Full`].
+//! [`JoinType::LeftSemi`], [`JoinType::LeftAnti`], [`JoinType::LeftSingle`], [`JoinType::RightOuter`],
+//! [`JoinType::Cross`], [`JoinType::Full`].
+//!
+//! The right input of a correlated join is run again for each row on the left,
+//! with the columns of the left it references replaced by their values.
 
 use super::joins_nullable;
 use crate::catalog::{ColumnCatalog, ColumnRef};
@@ -12,9 +16,10 @@ use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
 use crate::types::tuple::{Schema, SchemaRef, Tuple};
-use crate::types::value::{DataValue, NULL_VALUE};
+use crate::types::value::{DataValue, ValueRef, NULL_VALUE};
 use crate::utils::bit_vector::BitVector;
 use itertools::Itertools;
+use std::collections::HashSet;
 use std::ops::Coroutine;
 use std::ops::CoroutineState;
 use std::pin::Pin;
@@ -61,6 +66,10 @@ impl EqualCondition {
         let right_values =
             Projection::projection(right_tuple, &self.on_right_keys, &self.right_schema)?;
 
+        // `NULL` never equals anything, just as for the hash join
+        if left_values.iter().any(|value| value.is_null()) {
+            return Ok(false);
+        }
         Ok(left_values == right_values)
     }
 }
@@ -81,15 +90,22 @@ pub struct NestedLoopJoin {
     ty: JoinType,
     filter: Option<ScalarExpression>,
     eq_cond: EqualCondition,
+    correlated_columns: Vec<ColumnRef>,
+    match_once: bool,
 }
 
 impl From<(JoinOperator, LogicalPlan, LogicalPlan)> for NestedLoopJoin {
     fn from(
-        (JoinOperator { on, join_type, .. }, left_input, right_input): (
-            JoinOperator,
-            LogicalPlan,
-            LogicalPlan,
-        ),
+        (
+            JoinOperator {
+                on,
+                join_type,
+                correlated_columns,
+                match_once,
+            },
+            left_input,
+            right_input,
+        ): (JoinOperator, LogicalPlan, LogicalPlan),
     ) -> Self {
         let ((mut on_left_keys, mut on_right_keys), filter) = match on {
             JoinCondition::On { on, filter } => (on.into_iter().unzip(), filter),
@@ -121,6 +137,8 @@ impl From<(JoinOperator, LogicalPlan, LogicalPlan)> for NestedLoopJoin {
             output_schema_ref,
             filter,
             eq_cond,
+            correlated_columns,
+            match_once,
         }
     }
 }
@@ -141,21 +159,34 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for NestedLoopJoin {
                     output_schema_ref,
                     filter,
                     eq_cond,
-                    ..
+                    correlated_columns,
+                    match_once,
                 } = self;
 
                 let right_schema_len = eq_cond.right_schema.len();
                 let mut left_coroutine = build_read(left_input, cache, transaction);
                 let mut bitmap: Option<BitVector> = None;
                 let mut first_matches = Vec::new();
+                // the rows on the right already matched by a row on the left with `match_once`
+                let mut matched_rights = HashSet::new();
 
                 while let CoroutineState::Yielded(left_tuple) =
                     Pin::new(&mut left_coroutine).resume(())
                 {
                     let left_tuple: Tuple = throw!(left_tuple);
                     let mut has_matched = false;
-
-                    let mut right_coroutine = build_read(right_input.clone(), cache, transaction);
+                    let mut has_emitted = false;
+
+                    let mut right_plan = right_input.clone();
+                    if !correlated_columns.is_empty() {
+                        let values = Self::correlated_values(
+                            &left_tuple,
+                            &eq_cond.left_schema,
+                            &correlated_columns,
+                        );
+                        right_plan.bind_correlated_values(&values);
+                    }
+                    let mut right_coroutine = build_read(right_plan, cache, transaction);
                     let mut right_idx = 0;
 
                     while let CoroutineState::Yielded(right_tuple) =
@@ -163,14 +194,22 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for NestedLoopJoin {
                     {
                         let right_tuple: Tuple = throw!(right_tuple);
 
+                        if match_once && matched_rights.contains(&right_idx) {
+                            right_idx += 1;
+                            continue;
+                        }
                         let tuple = match (
                             filter.as_ref(),
                             throw!(eq_cond.equals(&left_tuple, &right_tuple)),
                         ) {
                             (None, true) if matches!(ty, JoinType::RightOuter) => {
+                                has_matched = true;
                                 Self::emit_tuple(&right_tuple, &left_tuple, ty, true)
                             }
-                            (None, true) => Self::emit_tuple(&left_tuple, &right_tuple, ty, true),
+                            (None, true) => {
+                                has_matched = true;
+                                Self::emit_tuple(&left_tuple, &right_tuple, ty, true)
+                            }
                             (Some(filter), true) => {
                                 let new_tuple = Self::merge_tuple(&left_tuple, &right_tuple, &ty);
                                 let value = throw!(filter.eval(&new_tuple, &output_schema_ref));
@@ -204,8 +243,16 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for NestedLoopJoin {
                             }
                             _ => None,
                         };
+                        if match_once && has_matched {
+                            matched_rights.insert(right_idx);
+                        }
 
                         if let Some(tuple) = tuple {
+                            if matches!(ty, JoinType::LeftSingle) && has_emitted {
+                                yield Err(DatabaseError::SubqueryTooManyRows);
+                                return;
+                            }
+                            has_emitted = true;
                             yield Ok(tuple);
                             if matches!(ty, JoinType::LeftSemi) {
                                 break;
@@ -230,6 +277,7 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for NestedLoopJoin {
                     let tuple = match ty {
                         JoinType::LeftAnti if !has_matched => Some(left_tuple.clone()),
                         JoinType::LeftOuter
+                        | JoinType::LeftSingle
                         | JoinType::LeftSemi
                         | JoinType::RightOuter
                         | JoinType::Full
@@ -299,7 +347,7 @@ impl NestedLoopJoin {
             .collect_vec();
         match ty {
             JoinType::Inner | JoinType::Cross | JoinType::LeftSemi if !is_matched => values.clear(),
-            JoinType::LeftOuter | JoinType::Full if !is_matched => {
+            JoinType::LeftOuter | JoinType::LeftSingle | JoinType::Full if !is_matched => {
                 values
                     .iter_mut()
                     .skip(left_len)
@@ -354,6 +402,23 @@ impl NestedLoopJoin {
         }
     }
 
+    /// The values in the row on the left of the columns referenced by the right input.
+    fn correlated_values(
+        left_tuple: &Tuple,
+        left_schema: &Schema,
+        correlated_columns: &[ColumnRef],
+    ) -> Vec<(ColumnRef, ValueRef)> {
+        correlated_columns
+            .iter()
+            .filter_map(|column| {
+                left_schema
+                    .iter()
+                    .position(|left_column| left_column.summary() == column.summary())
+                    .map(|i| (column.clone(), left_tuple.values[i].clone()))
+            })
+            .collect_vec()
+    }
+
     fn merge_schema(
         left_schema: &[ColumnRef],
         right_schema: &[ColumnRef],
@@ -545,6 +610,8 @@ mod test {
                 filter: Some(filter),
             },
             join_type: JoinType::Inner,
+            correlated_columns: vec![],
+            match_once: false,
         };
         let executor = NestedLoopJoin::from((op, left, right))
             .execute((&table_cache, &meta_cache), &transaction);
@@ -573,6 +640,8 @@ mod test {
                 filter: Some(filter),
             },
             join_type: JoinType::LeftOuter,
+            correlated_columns: vec![],
+            match_once: false,
         };
         let executor = NestedLoopJoin::from((op, left, right))
             .execute((&table_cache, &meta_cache), &transaction);
@@ -613,6 +682,8 @@ mod test {
                 filter: Some(filter),
             },
             join_type: JoinType::Cross,
+            correlated_columns: vec![],
+            match_once: false,
         };
         let executor = NestedLoopJoin::from((op, left, right))
             .execute((&table_cache, &meta_cache), &transaction);
@@ -642,6 +713,8 @@ mod test {
                 filter: None,
             },
             join_type: JoinType::Cross,
+            correlated_columns: vec![],
+            match_once: false,
         };
         let executor = NestedLoopJoin::from((op, left, right))
             .execute((&table_cache, &meta_cache), &transaction);
@@ -674,6 +747,8 @@ mod test {
                 filter: None,
             },
             join_type: JoinType::Cross,
+            correlated_columns: vec![],
+            match_once: false,
         };
         let executor = NestedLoopJoin::from((op, left, right))
             .execute((&table_cache, &meta_cache), &transaction);
@@ -698,6 +773,8 @@ mod test {
                 filter: Some(filter),
             },
             join_type: JoinType::LeftSemi,
+            correlated_columns: vec![],
+            match_once: false,
         };
         let executor = NestedLoopJoin::from((op, left, right))
             .execute((&table_cache, &meta_cache), &transaction);
@@ -711,6 +788,37 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_nested_left_semi_join_match_once() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = RocksStorage::new(temp_dir.path())?;
+        let transaction = storage.transaction()?;
+        let meta_cache = Arc::new(ShardingLruCache::new(4, 1, RandomState::new())?);
+        let table_cache = Arc::new(ShardingLruCache::new(4, 1, RandomState::new())?);
+        let (keys, left, right, _) = build_join_values(true);
+        let op = JoinOperator {
+            on: JoinCondition::On {
+                on: keys,
+                filter: None,
+            },
+            join_type: JoinType::LeftSemi,
+            correlated_columns: vec![],
+            match_once: true,
+        };
+        let executor = NestedLoopJoin::from((op, left, right))
+            .execute((&table_cache, &meta_cache), &transaction);
+        let tuples = try_collect(executor)?;
+
+        // only one of the two rows with `c2 = 2` matches the single row with `c5 = 2`
+        let mut expected_set = HashSet::with_capacity(2);
+        expected_set.insert(build_integers(vec![Some(0), Some(2), Some(4)]));
+        expected_set.insert(build_integers(vec![Some(1), Some(3), Some(5)]));
+
+        valid_result(&mut expected_set, &tuples);
+
+        Ok(())
+    }
+
     #[test]
     fn test_nested_left_anti_join() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
@@ -725,6 +833,8 @@ mod test {
                 filter: Some(filter),
             },
             join_type: JoinType::LeftAnti,
+            correlated_columns: vec![],
+            match_once: false,
         };
         let executor = NestedLoopJoin::from((op, left, right))
             .execute((&table_cache, &meta_cache), &transaction);
@@ -740,6 +850,85 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_nested_left_anti_join_without_filter() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = RocksStorage::new(temp_dir.path())?;
+        let transaction = storage.transaction()?;
+        let meta_cache = Arc::new(ShardingLruCache::new(4, 1, RandomState::new())?);
+        let table_cache = Arc::new(ShardingLruCache::new(4, 1, RandomState::new())?);
+        let (keys, left, right, _) = build_join_values(true);
+        let op = JoinOperator {
+            on: JoinCondition::On {
+                on: keys,
+                filter: None,
+            },
+            join_type: JoinType::LeftAnti,
+            correlated_columns: vec![],
+            match_once: false,
+        };
+        let executor = NestedLoopJoin::from((op, left, right))
+            .execute((&table_cache, &meta_cache), &transaction);
+        let tuples = try_collect(executor)?;
+
+        let mut expected_set = HashSet::with_capacity(1);
+        expected_set.insert(build_integers(vec![Some(3), Some(5), Some(7)]));
+
+        valid_result(&mut expected_set, &tuples);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_left_single_join() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = RocksStorage::new(temp_dir.path())?;
+        let transaction = storage.transaction()?;
+        let meta_cache = Arc::new(ShardingLruCache::new(4, 1, RandomState::new())?);
+        let table_cache = Arc::new(ShardingLruCache::new(4, 1, RandomState::new())?);
+        let (keys, left, right, _) = build_join_values(true);
+        let op = JoinOperator {
+            on: JoinCondition::On {
+                on: keys,
+                filter: None,
+            },
+            join_type: JoinType::LeftSingle,
+            correlated_columns: vec![],
+            match_once: false,
+        };
+        let executor = NestedLoopJoin::from((op, left.clone(), right.clone()))
+            .execute((&table_cache, &meta_cache), &transaction);
+        let tuples = try_collect(executor)?;
+
+        let mut expected_set = HashSet::with_capacity(4);
+        let tuple = build_integers(vec![Some(0), Some(2), Some(4), Some(0), Some(2), Some(4)]);
+        expected_set.insert(tuple);
+        let tuple = build_integers(vec![Some(1), Some(2), Some(5), Some(0), Some(2), Some(4)]);
+        expected_set.insert(tuple);
+        let tuple = build_integers(vec![Some(1), Some(3), Some(5), Some(1), Some(3), Some(5)]);
+        expected_set.insert(tuple);
+        let tuple = build_integers(vec![Some(3), Some(5), Some(7), None, None, None]);
+        expected_set.insert(tuple);
+
+        valid_result(&mut expected_set, &tuples);
+
+        // every row on the left matches all the rows on the right
+        let op = JoinOperator {
+            on: JoinCondition::None,
+            join_type: JoinType::LeftSingle,
+            correlated_columns: vec![],
+            match_once: false,
+        };
+        let executor = NestedLoopJoin::from((op, left, right))
+            .execute((&table_cache, &meta_cache), &transaction);
+        debug_assert!(matches!(
+            try_collect(executor),
+            Err(DatabaseError::SubqueryTooManyRows)
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn test_nested_right_out_join() -> Result<(), DatabaseError> {
         let temp_dir = TempDir::new().expect("unable to create temporary working directory");
@@ -754,6 +943,8 @@ mod test {
                 filter: Some(filter),
             },
             join_type: JoinType::RightOuter,
+            correlated_columns: vec![],
+            match_once: false,
         };
         let executor = NestedLoopJoin::from((op, left, right))
             .execute((&table_cache, &meta_cache), &transaction);
@@ -788,6 +979,8 @@ mod test {
                 filter: Some(filter),
             },
             join_type: JoinType::Full,
+            correlated_columns: vec![],
+            match_once: false,
         };
         let executor = NestedLoopJoin::from((op, left, right))
             .execute((&table_cache, &meta_cache), &transaction);
@@ -51,11 +51,13 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Limit {
                     i += 1;
                     if i - 1 < offset_val {
                         continue;
-                    } else if i - 1 > offset_limit {
-                        break;
                     }
 
                     yield tuple;
+                    // Tips: stop before pulling another tuple from the input
+                    if i > offset_limit {
+                        break;
+                    }
                 }
             },
         )
@@ -1,5 +1,6 @@
 pub(crate) mod aggregate;
 pub(crate) mod describe;
+pub(crate) mod distinct_on;
 pub(crate) mod dummy;
 pub(crate) mod explain;
 pub(crate) mod filter;
@@ -8,11 +9,13 @@ pub(crate) mod index_scan;
 pub(crate) mod join;
 pub(crate) mod limit;
 pub(crate) mod projection;
+pub(crate) mod recursive_union;
 pub(crate) mod seq_scan;
 pub(crate) mod show_table;
 pub(crate) mod sort;
 pub(crate) mod union;
 pub(crate) mod values;
+pub(crate) mod window;
 
 #[cfg(test)]
 pub(crate) mod test {
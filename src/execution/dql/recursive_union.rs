@@ -0,0 +1,101 @@
+use crate::errors::DatabaseError;
+use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::planner::operator::recursive_union::RecursiveUnionOperator;
+use crate::planner::operator::values::ValuesOperator;
+use crate::planner::operator::work_table::WorkTableOperator;
+use crate::planner::operator::Operator;
+use crate::planner::LogicalPlan;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::value::ValueRef;
+use std::collections::HashSet;
+use std::mem;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
+
+pub struct RecursiveUnion {
+    op: RecursiveUnionOperator,
+    anchor: LogicalPlan,
+    recursive_term: LogicalPlan,
+}
+
+impl From<(RecursiveUnionOperator, LogicalPlan, LogicalPlan)> for RecursiveUnion {
+    fn from(
+        (op, anchor, recursive_term): (RecursiveUnionOperator, LogicalPlan, LogicalPlan),
+    ) -> Self {
+        RecursiveUnion {
+            op,
+            anchor,
+            recursive_term,
+        }
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for RecursiveUnion {
+    fn execute(
+        self,
+        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let RecursiveUnion {
+                    op:
+                        RecursiveUnionOperator {
+                            is_all,
+                            max_iterations,
+                            ..
+                        },
+                    anchor,
+                    recursive_term,
+                } = self;
+                // Tips: with `UNION` the rows seen so far, which are not returned nor fed back again
+                let mut seen: HashSet<Vec<ValueRef>> = HashSet::new();
+                let mut input = anchor;
+
+                for iteration in 0.. {
+                    let mut coroutine = build_read(input, cache, transaction);
+                    let mut next_rows = Vec::new();
+
+                    while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                        let mut tuple = throw!(tuple);
+
+                        if !is_all && !seen.insert(tuple.values.clone()) {
+                            continue;
+                        }
+                        // Tips: the anchor is not counted as an iteration
+                        if iteration > max_iterations {
+                            yield Err(DatabaseError::RecursiveIterationLimit(max_iterations));
+                            return;
+                        }
+                        tuple.id = None;
+                        next_rows.push(tuple.values.clone());
+
+                        yield Ok(tuple);
+                    }
+                    if next_rows.is_empty() {
+                        break;
+                    }
+                    input = recursive_term.clone();
+                    fill_work_table(&mut input, &mut next_rows);
+                }
+            },
+        )
+    }
+}
+
+/// Replaces the work table read by the recursive term with the rows of the last iteration.
+fn fill_work_table(plan: &mut LogicalPlan, rows: &mut Vec<Vec<ValueRef>>) {
+    if let Operator::WorkTable(WorkTableOperator { schema_ref, .. }) = &plan.operator {
+        plan.operator = Operator::Values(ValuesOperator {
+            rows: mem::take(rows),
+            schema_ref: schema_ref.clone(),
+        });
+        return;
+    }
+    for child in plan.childrens.iter_mut() {
+        fill_work_table(child, rows);
+    }
+}
@@ -19,13 +19,45 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for ShowTables {
             #[coroutine]
             move || {
                 let metas = throw!(transaction.table_metas());
+                let views = throw!(transaction.views());
 
-                for TableMeta { table_name } in metas {
-                    let values = vec![Arc::new(DataValue::Utf8 {
-                        value: Some(table_name.to_string()),
-                        ty: Utf8Type::Variable(None),
-                        unit: CharLengthUnits::Characters,
-                    })];
+                let tables = metas
+                    .into_iter()
+                    .map(
+                        |TableMeta {
+                             table_name,
+                             view_query,
+                             comment,
+                             ..
+                         }| {
+                            let ty = if view_query.is_some() {
+                                "MATERIALIZED VIEW"
+                            } else {
+                                "TABLE"
+                            };
+                            (table_name, ty, comment)
+                        },
+                    )
+                    .chain(views.into_iter().map(|view| (view.name, "VIEW", None)));
+
+                for (table_name, ty, comment) in tables {
+                    let values = vec![
+                        Arc::new(DataValue::Utf8 {
+                            value: Some(table_name.to_string()),
+                            ty: Utf8Type::Variable(None),
+                            unit: CharLengthUnits::Characters,
+                        }),
+                        Arc::new(DataValue::Utf8 {
+                            value: Some(ty.to_string()),
+                            ty: Utf8Type::Variable(None),
+                            unit: CharLengthUnits::Characters,
+                        }),
+                        Arc::new(DataValue::Utf8 {
+                            value: comment,
+                            ty: Utf8Type::Variable(None),
+                            unit: CharLengthUnits::Characters,
+                        }),
+                    ];
 
                     yield Ok(Tuple { id: None, values });
                 }
@@ -1,10 +1,11 @@
 use crate::errors::DatabaseError;
-use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::execution::{build_read, Executor, ReadExecutor, WorkMem};
 use crate::planner::operator::sort::{SortField, SortOperator};
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::throw;
 use crate::types::tuple::{Schema, Tuple};
+use crate::types::value::ValueRef;
 use itertools::Itertools;
 use std::cmp::Ordering;
 use std::mem;
@@ -65,6 +66,11 @@ impl Iterator for RemappingIterator {
 
 const BUCKET_SIZE: usize = u8::MAX as usize + 1;
 
+// the leading byte of the radix sort key of a field
+const NULL_FIRST: u8 = 0;
+const NOT_NULL: u8 = 1;
+const NULL_LAST: u8 = 2;
+
 // LSD Radix Sort
 pub(crate) fn radix_sort<T>(mut tuples: Vec<(T, Vec<u8>)>) -> Vec<T> {
     if let Some(max_len) = tuples.iter().map(|(_, bytes)| bytes.len()).max() {
@@ -88,6 +94,47 @@ pub(crate) fn radix_sort<T>(mut tuples: Vec<(T, Vec<u8>)>) -> Vec<T> {
     Vec::new()
 }
 
+/// Compares the values of two rows calculated from `sort_fields`, one value for each field.
+pub(crate) fn compare_sort_values(
+    sort_fields: &[SortField],
+    values_1: &[ValueRef],
+    values_2: &[ValueRef],
+) -> Ordering {
+    let fn_nulls_first = |nulls_first: bool| {
+        if nulls_first {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        }
+    };
+    let mut ordering = Ordering::Equal;
+
+    for (
+        SortField {
+            asc, nulls_first, ..
+        },
+        (value_1, value_2),
+    ) in sort_fields.iter().zip(values_1.iter().zip(values_2.iter()))
+    {
+        ordering = match (value_1.is_null(), value_2.is_null()) {
+            (false, true) => fn_nulls_first(*nulls_first),
+            (true, false) => fn_nulls_first(*nulls_first).reverse(),
+            _ => {
+                let mut ordering = value_1.partial_cmp(value_2).unwrap_or(Ordering::Equal);
+                if !*asc {
+                    ordering = ordering.reverse();
+                }
+                ordering
+            }
+        };
+        if ordering != Ordering::Equal {
+            break;
+        }
+    }
+
+    ordering
+}
+
 pub enum SortBy {
     Radix,
     Fast,
@@ -115,16 +162,24 @@ impl SortBy {
                         asc,
                     } in sort_fields
                     {
-                        let mut key = Vec::new();
                         let tuple = tuple.as_ref().map(|(_, tuple)| tuple).unwrap();
+                        let value = expr.eval(tuple, schema)?;
+
+                        // Tips: the key of each field starts with whether it is NULL, which places
+                        // NULLs regardless of the direction and before comparing any value byte
+                        if value.is_null() {
+                            full_key.push(if *nulls_first { NULL_FIRST } else { NULL_LAST });
+                            continue;
+                        }
+                        let mut key = Vec::new();
 
-                        expr.eval(tuple, schema)?.memcomparable_encode(&mut key)?;
+                        value.memcomparable_encode(&mut key)?;
                         if !asc {
                             for byte in key.iter_mut() {
                                 *byte ^= 0xFF;
                             }
                         }
-                        key.push(if *nulls_first { u8::MIN } else { u8::MAX });
+                        full_key.push(NOT_NULL);
                         full_key.extend(key);
                     }
                     sort_keys.push((i, full_key))
@@ -138,24 +193,19 @@ impl SortBy {
                 }))
             }
             SortBy::Fast => {
-                let fn_nulls_first = |nulls_first: bool| {
-                    if nulls_first {
-                        Ordering::Greater
-                    } else {
-                        Ordering::Less
-                    }
-                };
                 // Extract the results of calculating SortFields to avoid double calculation
                 // of data during comparison
-                let mut eval_values = vec![Vec::with_capacity(sort_fields.len()); tuples.len()];
+                let mut eval_values = Vec::with_capacity(tuples.len());
 
-                for (x, SortField { expr, .. }) in sort_fields.iter().enumerate() {
-                    for tuple in tuples.0.iter() {
-                        debug_assert!(tuple.is_some());
+                for tuple in tuples.0.iter() {
+                    debug_assert!(tuple.is_some());
 
-                        let (_, tuple) = tuple.as_ref().unwrap();
-                        eval_values[x].push(expr.eval(tuple, schema)?);
-                    }
+                    let (_, tuple) = tuple.as_ref().unwrap();
+                    let values: Vec<ValueRef> = sort_fields
+                        .iter()
+                        .map(|SortField { expr, .. }| expr.eval(tuple, schema))
+                        .try_collect()?;
+                    eval_values.push(values);
                 }
 
                 tuples.0.sort_by(|tuple_1, tuple_2| {
@@ -164,36 +214,8 @@ impl SortBy {
 
                     let (i_1, _) = tuple_1.as_ref().unwrap();
                     let (i_2, _) = tuple_2.as_ref().unwrap();
-                    let mut ordering = Ordering::Equal;
 
-                    for (
-                        x,
-                        SortField {
-                            asc, nulls_first, ..
-                        },
-                    ) in sort_fields.iter().enumerate()
-                    {
-                        let value_1 = &eval_values[x][*i_1];
-                        let value_2 = &eval_values[x][*i_2];
-
-                        ordering = match (value_1.is_null(), value_2.is_null()) {
-                            (false, true) => fn_nulls_first(*nulls_first),
-                            (true, false) => fn_nulls_first(*nulls_first).reverse(),
-                            _ => {
-                                let mut ordering =
-                                    value_1.partial_cmp(value_2).unwrap_or(Ordering::Equal);
-                                if !*asc {
-                                    ordering = ordering.reverse();
-                                }
-                                ordering
-                            }
-                        };
-                        if ordering != Ordering::Equal {
-                            break;
-                        }
-                    }
-
-                    ordering
+                    compare_sort_values(sort_fields, &eval_values[*i_1], &eval_values[*i_2])
                 });
                 drop(eval_values);
 
@@ -242,11 +264,15 @@ impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Sort {
                 let schema = input.output_schema().clone();
                 let mut tuples = NullableVec::default();
                 let mut offset = 0;
+                let mut work_mem = WorkMem::new("sort");
 
                 let mut coroutine = build_read(input, cache, transaction);
 
                 while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
-                    tuples.put((offset, throw!(tuple)));
+                    let tuple = throw!(tuple);
+
+                    throw!(work_mem.reserve(&tuple.values));
+                    tuples.put((offset, tuple));
                     offset += 1;
                 }
 
@@ -278,6 +304,7 @@ mod test {
     use crate::types::tuple::Tuple;
     use crate::types::value::DataValue;
     use crate::types::LogicalType;
+    use itertools::Itertools;
     use std::sync::Arc;
 
     #[test]
@@ -869,4 +896,73 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_radix_and_fast_sort_eq_with_nulls() -> Result<(), DatabaseError> {
+        let schema = Arc::new(vec![
+            Arc::new(ColumnCatalog::new(
+                "c1".to_string(),
+                true,
+                ColumnDesc::new(LogicalType::Integer, false, false, None).unwrap(),
+            )),
+            Arc::new(ColumnCatalog::new(
+                "c2".to_string(),
+                true,
+                ColumnDesc::new(LogicalType::Integer, false, false, None).unwrap(),
+            )),
+        ]);
+        let values = [None, Some(i32::MIN), Some(-1), Some(0), Some(i32::MAX)];
+        let mut tuples = NullableVec::default();
+
+        for (i, (c1, c2)) in values
+            .iter()
+            .flat_map(|c1| values.iter().map(move |c2| (c1, c2)))
+            .enumerate()
+        {
+            tuples.put((
+                i,
+                Tuple {
+                    id: None,
+                    values: vec![
+                        Arc::new(DataValue::Int32(*c1)),
+                        Arc::new(DataValue::Int32(*c2)),
+                    ],
+                },
+            ));
+        }
+        let sort_field = |pos: usize, asc: bool, nulls_first: bool| SortField {
+            expr: ScalarExpression::Reference {
+                expr: Box::new(ScalarExpression::Empty),
+                pos,
+            },
+            asc,
+            nulls_first,
+        };
+
+        // Tips: every combination of the directions and the placements of NULLs of both fields
+        for flags in 0..16 {
+            let sort_fields = vec![
+                sort_field(0, flags & 1 != 0, flags & 2 != 0),
+                sort_field(1, flags & 4 != 0, flags & 8 != 0),
+            ];
+            let radix = SortBy::Radix
+                .sorted_tuples(&schema, &sort_fields, tuples.clone())?
+                .map(|tuple| tuple.values)
+                .collect_vec();
+            let fast = SortBy::Fast
+                .sorted_tuples(&schema, &sort_fields, tuples.clone())?
+                .map(|tuple| tuple.values)
+                .collect_vec();
+
+            assert_eq!(radix, fast);
+            assert_eq!(
+                radix[0][0].is_null(),
+                sort_fields[0].nulls_first,
+                "{:?}",
+                sort_fields
+            );
+        }
+
+        Ok(())
+    }
 }
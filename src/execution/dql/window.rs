@@ -0,0 +1,180 @@
+use crate::errors::DatabaseError;
+use crate::execution::dql::aggregate::create_accumulator;
+use crate::execution::dql::sort::compare_sort_values;
+use crate::execution::{build_read, Executor, ReadExecutor};
+use crate::expression::window::WindowKind;
+use crate::expression::ScalarExpression;
+use crate::planner::operator::sort::SortField;
+use crate::planner::operator::window::WindowOperator;
+use crate::planner::LogicalPlan;
+use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
+use crate::throw;
+use crate::types::tuple::{Schema, Tuple};
+use crate::types::value::{DataValue, ValueRef};
+use itertools::Itertools;
+use std::ops::Coroutine;
+use std::ops::CoroutineState;
+use std::pin::Pin;
+use std::sync::Arc;
+
+pub struct Window {
+    window_calls: Vec<ScalarExpression>,
+    input: LogicalPlan,
+}
+
+impl From<(WindowOperator, LogicalPlan)> for Window {
+    fn from((WindowOperator { window_calls }, input): (WindowOperator, LogicalPlan)) -> Self {
+        Window {
+            window_calls,
+            input,
+        }
+    }
+}
+
+impl Window {
+    /// Calculates the values of `window_call` for all `tuples`, in the order of `tuples`.
+    ///
+    /// Returns the values and the positions of `tuples` sorted by the partitions and the order
+    /// of the window.
+    fn eval_window_call(
+        window_call: &ScalarExpression,
+        schema: &Schema,
+        tuples: &[Tuple],
+    ) -> Result<(Vec<ValueRef>, Vec<usize>), DatabaseError> {
+        let ScalarExpression::WindowCall {
+            kind,
+            args,
+            partition_by,
+            order_by,
+            ty,
+        } = window_call.unpack_alias_ref()
+        else {
+            unreachable!()
+        };
+        // Tips: the rows are sorted by the partition keys first, the direction does not matter
+        let sort_fields = partition_by
+            .iter()
+            .map(|expr| SortField::new(expr.clone(), true, true))
+            .chain(order_by.iter().cloned())
+            .collect_vec();
+        let mut keys = Vec::with_capacity(tuples.len());
+        for tuple in tuples {
+            let key: Vec<ValueRef> = sort_fields
+                .iter()
+                .map(|SortField { expr, .. }| expr.eval(tuple, schema))
+                .try_collect()?;
+            keys.push(key);
+        }
+        let mut indices = (0..tuples.len()).collect_vec();
+        indices.sort_by(|i_1, i_2| compare_sort_values(&sort_fields, &keys[*i_1], &keys[*i_2]));
+
+        let partition_len = partition_by.len();
+        let null = Arc::new(DataValue::Null);
+        let mut values = vec![null; tuples.len()];
+        let mut partition_start = 0;
+
+        while partition_start < indices.len() {
+            let partition_key = &keys[indices[partition_start]][..partition_len];
+            let partition_end = (partition_start..indices.len())
+                .find(|pos| &keys[indices[*pos]][..partition_len] != partition_key)
+                .unwrap_or(indices.len());
+            let mut accumulator = match kind {
+                WindowKind::Aggregate(agg_kind) => {
+                    Some(create_accumulator(&ScalarExpression::AggCall {
+                        distinct: false,
+                        kind: agg_kind.clone(),
+                        args: args.clone(),
+                        filter: None,
+                        ty: ty.clone(),
+                    })?)
+                }
+                _ => None,
+            };
+            let mut dense_rank = 0;
+            let mut peer_start = partition_start;
+
+            // Tips: the rows with equal keys are peers, which share the same rank and the same
+            // frame, the whole partition without `ORDER BY`
+            while peer_start < partition_end {
+                let peer_key = &keys[indices[peer_start]];
+                let peer_end = (peer_start..partition_end)
+                    .find(|pos| &keys[indices[*pos]] != peer_key)
+                    .unwrap_or(partition_end);
+                dense_rank += 1;
+
+                let aggregate_value = if let Some(accumulator) = accumulator.as_mut() {
+                    for pos in peer_start..peer_end {
+                        accumulator.update_value(&args[0].eval(&tuples[indices[pos]], schema)?)?;
+                    }
+                    Some(accumulator.evaluate()?)
+                } else {
+                    None
+                };
+                for pos in peer_start..peer_end {
+                    values[indices[pos]] = match kind {
+                        WindowKind::RowNumber => {
+                            Arc::new(DataValue::Int64(Some((pos - partition_start + 1) as i64)))
+                        }
+                        WindowKind::Rank => Arc::new(DataValue::Int64(Some(
+                            (peer_start - partition_start + 1) as i64,
+                        ))),
+                        WindowKind::DenseRank => Arc::new(DataValue::Int64(Some(dense_rank))),
+                        WindowKind::Aggregate(_) => aggregate_value.clone().unwrap(),
+                    };
+                }
+                peer_start = peer_end;
+            }
+            partition_start = partition_end;
+        }
+
+        Ok((values, indices))
+    }
+}
+
+impl<'a, T: Transaction + 'a> ReadExecutor<'a, T> for Window {
+    fn execute(
+        self,
+        cache: (&'a TableCache, &'a StatisticsMetaCache),
+        transaction: &'a T,
+    ) -> Executor<'a> {
+        Box::new(
+            #[coroutine]
+            move || {
+                let Window {
+                    window_calls,
+                    mut input,
+                } = self;
+
+                let schema = input.output_schema().clone();
+                let mut tuples = Vec::new();
+
+                let mut coroutine = build_read(input, cache, transaction);
+
+                while let CoroutineState::Yielded(tuple) = Pin::new(&mut coroutine).resume(()) {
+                    tuples.push(throw!(tuple));
+                }
+                let mut window_values = Vec::with_capacity(window_calls.len());
+                let mut indices = (0..tuples.len()).collect_vec();
+
+                for window_call in window_calls.iter() {
+                    let (values, sorted_indices) =
+                        throw!(Self::eval_window_call(window_call, &schema, &tuples));
+
+                    window_values.push(values);
+                    indices = sorted_indices;
+                }
+                let mut tuples = tuples.into_iter().map(Some).collect_vec();
+
+                // Tips: the rows are returned in the order of the last window
+                for i in indices {
+                    let mut tuple = tuples[i].take().unwrap();
+
+                    for values in window_values.iter() {
+                        tuple.values.push(values[i].clone());
+                    }
+                    yield Ok(tuple);
+                }
+            },
+        )
+    }
+}
@@ -3,22 +3,33 @@ pub(crate) mod dml;
 pub(crate) mod dql;
 pub(crate) mod marco;
 
+use self::ddl::add_check::AddCheck;
 use self::ddl::add_column::AddColumn;
+use self::ddl::add_foreign_key::AddForeignKey;
+use self::ddl::alter_column::AlterColumn;
 use self::dql::join::nested_loop_join::NestedLoopJoin;
+use crate::catalog::{ForeignKey, TableName};
 use crate::errors::DatabaseError;
+use crate::execution::ddl::comment::Comment;
 use crate::execution::ddl::create_index::CreateIndex;
 use crate::execution::ddl::create_table::CreateTable;
+use crate::execution::ddl::create_view::CreateView;
 use crate::execution::ddl::drop_column::DropColumn;
+use crate::execution::ddl::drop_constraint::DropConstraint;
 use crate::execution::ddl::drop_table::DropTable;
+use crate::execution::ddl::drop_view::DropView;
+use crate::execution::ddl::rename_table::RenameTable;
 use crate::execution::ddl::truncate::Truncate;
 use crate::execution::dml::analyze::Analyze;
 use crate::execution::dml::copy_from_file::CopyFromFile;
+use crate::execution::dml::copy_to_file::CopyToFile;
 use crate::execution::dml::delete::Delete;
 use crate::execution::dml::insert::Insert;
 use crate::execution::dml::update::Update;
 use crate::execution::dql::aggregate::hash_agg::HashAggExecutor;
 use crate::execution::dql::aggregate::simple_agg::SimpleAggExecutor;
 use crate::execution::dql::describe::Describe;
+use crate::execution::dql::distinct_on::DistinctOn;
 use crate::execution::dql::dummy::Dummy;
 use crate::execution::dql::explain::Explain;
 use crate::execution::dql::filter::Filter;
@@ -27,23 +38,291 @@ use crate::execution::dql::index_scan::IndexScan;
 use crate::execution::dql::join::hash_join::HashJoin;
 use crate::execution::dql::limit::Limit;
 use crate::execution::dql::projection::Projection;
+use crate::execution::dql::recursive_union::RecursiveUnion;
 use crate::execution::dql::seq_scan::SeqScan;
 use crate::execution::dql::show_table::ShowTables;
 use crate::execution::dql::sort::Sort;
 use crate::execution::dql::union::Union;
 use crate::execution::dql::values::Values;
+use crate::execution::dql::window::Window;
 use crate::planner::operator::join::JoinCondition;
 use crate::planner::operator::{Operator, PhysicalOption};
 use crate::planner::LogicalPlan;
 use crate::storage::{StatisticsMetaCache, TableCache, Transaction};
 use crate::types::index::IndexInfo;
 use crate::types::tuple::Tuple;
+use crate::types::value::ValueRef;
+use chrono::{FixedOffset, Local, NaiveDateTime};
+use std::cell::{Cell, RefCell};
+use std::mem;
 use std::ops::{Coroutine, CoroutineState};
 use std::pin::Pin;
+use std::time::{Duration, Instant};
 
 pub type Executor<'a> =
     Box<dyn Coroutine<Yield = Result<Tuple, DatabaseError>, Return = ()> + 'a + Unpin>;
 
+thread_local! {
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+    // the time at which the statement on this thread started, and whether it has been read
+    static STATEMENT_TIME: Cell<Option<(NaiveDateTime, bool)>> = const { Cell::new(None) };
+    // the time zone of the session of the statement on this thread, see `SET TIME ZONE`
+    static STATEMENT_TIME_ZONE: Cell<Option<FixedOffset>> = const { Cell::new(None) };
+    // the last insert id of the session of the statement on this thread, see `last_insert_id()`
+    static LAST_INSERT_ID: Cell<Option<i64>> = const { Cell::new(None) };
+    // the foreign keys initially deferred written by the transaction of the statement on this
+    // thread, see `StatementDeferredForeignKeys`
+    static DEFERRED_FOREIGN_KEYS: RefCell<Option<Vec<(TableName, ForeignKey)>>> =
+        const { RefCell::new(None) };
+    // the bytes the buffer of each executor of the statement on this thread may take, see
+    // `SET work_mem`
+    static WORK_MEM: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Makes the executors built on this thread abort with [`DatabaseError::Timeout`]
+/// once `timeout` has elapsed, until the guard is dropped.
+pub(crate) struct StatementDeadline {
+    previous: Option<Instant>,
+}
+
+impl StatementDeadline {
+    pub(crate) fn start(timeout: Option<Duration>) -> Self {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+        StatementDeadline {
+            previous: DEADLINE.replace(deadline),
+        }
+    }
+}
+
+impl Drop for StatementDeadline {
+    fn drop(&mut self) {
+        DEADLINE.set(self.previous);
+    }
+}
+
+/// Makes `now()` and `current_date` on this thread return the time at which the statement
+/// started, until the guard is dropped, so that all of their calls in a statement agree.
+pub(crate) struct StatementTime {
+    previous: Option<(NaiveDateTime, bool)>,
+}
+
+impl StatementTime {
+    pub(crate) fn start() -> Self {
+        StatementTime {
+            previous: STATEMENT_TIME.replace(Some((Local::now().naive_local(), false))),
+        }
+    }
+
+    /// Whether the statement has read its time or called a volatile function, so that its
+    /// result is only valid once.
+    pub(crate) fn is_read(&self) -> bool {
+        STATEMENT_TIME.get().is_some_and(|(_, is_read)| is_read)
+    }
+}
+
+impl Drop for StatementTime {
+    fn drop(&mut self) {
+        STATEMENT_TIME.set(self.previous);
+    }
+}
+
+/// The time at which the statement on this thread started, or the current time out of a
+/// statement.
+pub(crate) fn statement_time() -> NaiveDateTime {
+    match STATEMENT_TIME.get() {
+        Some((time, _)) => {
+            STATEMENT_TIME.set(Some((time, true)));
+            time
+        }
+        None => Local::now().naive_local(),
+    }
+}
+
+/// Marks the result of the statement on this thread as valid only once, like a read of
+/// [`statement_time`] does, e.g. for `gen_random_uuid()`.
+pub(crate) fn mark_volatile() {
+    if let Some((time, _)) = STATEMENT_TIME.get() {
+        STATEMENT_TIME.set(Some((time, true)));
+    }
+}
+
+/// Makes the timestamps with time zone on this thread be converted from and to the timestamps
+/// without time zone, and displayed, at `time_zone`, until the guard is dropped.
+pub(crate) struct StatementTimeZone {
+    previous: Option<FixedOffset>,
+}
+
+impl StatementTimeZone {
+    pub(crate) fn start(time_zone: FixedOffset) -> Self {
+        StatementTimeZone {
+            previous: STATEMENT_TIME_ZONE.replace(Some(time_zone)),
+        }
+    }
+}
+
+impl Drop for StatementTimeZone {
+    fn drop(&mut self) {
+        STATEMENT_TIME_ZONE.set(self.previous);
+    }
+}
+
+/// The time zone of the statement on this thread, or UTC out of a statement.
+pub(crate) fn statement_time_zone() -> FixedOffset {
+    STATEMENT_TIME_ZONE
+        .get()
+        .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap())
+}
+
+/// Makes `last_insert_id()` on this thread return the last insert id of the session, which is
+/// replaced by the value assigned by the statement once the guard is dropped.
+pub(crate) struct StatementLastInsertId<'a> {
+    session: &'a mut i64,
+    previous: Option<i64>,
+}
+
+impl<'a> StatementLastInsertId<'a> {
+    pub(crate) fn start(session: &'a mut i64) -> Self {
+        let previous = LAST_INSERT_ID.replace(Some(*session));
+
+        StatementLastInsertId { session, previous }
+    }
+}
+
+impl Drop for StatementLastInsertId<'_> {
+    fn drop(&mut self) {
+        if let Some(last_insert_id) = LAST_INSERT_ID.replace(self.previous) {
+            *self.session = last_insert_id;
+        }
+    }
+}
+
+/// The first value assigned to an auto-increment column by the last `INSERT` of the session of
+/// the statement on this thread, or `0` if none.
+pub(crate) fn last_insert_id() -> i64 {
+    LAST_INSERT_ID.get().unwrap_or(0)
+}
+
+pub(crate) fn set_last_insert_id(last_insert_id: i64) {
+    if LAST_INSERT_ID.get().is_some() {
+        LAST_INSERT_ID.set(Some(last_insert_id));
+    }
+}
+
+/// Makes the statements on this thread leave the checks of the foreign keys initially deferred
+/// to the transaction, which checks them once it commits, until the guard is dropped.
+pub(crate) struct StatementDeferredForeignKeys<'a> {
+    transaction: &'a mut Vec<(TableName, ForeignKey)>,
+    previous: Option<Vec<(TableName, ForeignKey)>>,
+}
+
+impl<'a> StatementDeferredForeignKeys<'a> {
+    pub(crate) fn start(transaction: &'a mut Vec<(TableName, ForeignKey)>) -> Self {
+        let previous = DEFERRED_FOREIGN_KEYS.replace(Some(mem::take(transaction)));
+
+        StatementDeferredForeignKeys {
+            transaction,
+            previous,
+        }
+    }
+}
+
+impl Drop for StatementDeferredForeignKeys<'_> {
+    fn drop(&mut self) {
+        if let Some(foreign_keys) = DEFERRED_FOREIGN_KEYS.replace(self.previous.take()) {
+            *self.transaction = foreign_keys;
+        }
+    }
+}
+
+/// Leaves the check of the foreign key of the table to the transaction of the statement on this
+/// thread, returns `false` out of a transaction, whose statements check it at once.
+pub(crate) fn defer_foreign_key(table_name: &TableName, foreign_key: &ForeignKey) -> bool {
+    DEFERRED_FOREIGN_KEYS.with_borrow_mut(|foreign_keys| {
+        let Some(foreign_keys) = foreign_keys else {
+            return false;
+        };
+        if !foreign_keys
+            .iter()
+            .any(|(name, deferred)| name == table_name && deferred.name == foreign_key.name)
+        {
+            foreign_keys.push((table_name.clone(), foreign_key.clone()));
+        }
+        true
+    })
+}
+
+/// Makes the sorts, hash joins and hash aggregations built on this thread abort with
+/// [`DatabaseError::WorkMemExceeded`] once their buffer takes more than `work_mem` bytes, until
+/// the guard is dropped.
+pub(crate) struct StatementWorkMem {
+    previous: Option<usize>,
+}
+
+impl StatementWorkMem {
+    pub(crate) fn start(work_mem: Option<usize>) -> Self {
+        StatementWorkMem {
+            previous: WORK_MEM.replace(work_mem),
+        }
+    }
+}
+
+impl Drop for StatementWorkMem {
+    fn drop(&mut self) {
+        WORK_MEM.set(self.previous);
+    }
+}
+
+/// The memory taken by the buffer of an executor, bounded by the `work_mem` of the statement on
+/// this thread like the rows of a result are by `max_result_rows`.
+pub(crate) struct WorkMem {
+    operator: &'static str,
+    limit: Option<usize>,
+    used: usize,
+}
+
+impl WorkMem {
+    pub(crate) fn new(operator: &'static str) -> Self {
+        WorkMem {
+            operator,
+            limit: WORK_MEM.get(),
+            used: 0,
+        }
+    }
+
+    /// Counts the memory of `values` kept in the buffer, fails once the buffer takes more than
+    /// `work_mem`.
+    pub(crate) fn reserve(&mut self, values: &[ValueRef]) -> Result<(), DatabaseError> {
+        let Some(limit) = self.limit else {
+            return Ok(());
+        };
+        self.used += values
+            .iter()
+            .map(|value| mem::size_of::<ValueRef>() + value.memory_size())
+            .sum::<usize>();
+
+        if self.used > limit {
+            return Err(DatabaseError::WorkMemExceeded(self.operator, limit));
+        }
+        Ok(())
+    }
+}
+
+fn with_deadline(mut executor: Executor, deadline: Instant) -> Executor {
+    Box::new(
+        #[coroutine]
+        move || {
+            while let CoroutineState::Yielded(tuple) = Pin::new(&mut executor).resume(()) {
+                if Instant::now() >= deadline {
+                    yield Err(DatabaseError::Timeout);
+                    return;
+                }
+                yield tuple;
+            }
+        },
+    )
+}
+
 pub trait ReadExecutor<'a, T: Transaction + 'a> {
     fn execute(
         self,
@@ -64,6 +343,19 @@ pub fn build_read<'a, T: Transaction + 'a>(
     plan: LogicalPlan,
     cache: (&'a TableCache, &'a StatisticsMetaCache),
     transaction: &'a T,
+) -> Executor<'a> {
+    let executor = build_read_operator(plan, cache, transaction);
+
+    match DEADLINE.get() {
+        Some(deadline) => with_deadline(executor, deadline),
+        None => executor,
+    }
+}
+
+fn build_read_operator<'a, T: Transaction + 'a>(
+    plan: LogicalPlan,
+    cache: (&'a TableCache, &'a StatisticsMetaCache),
+    transaction: &'a T,
 ) -> Executor<'a> {
     let LogicalPlan {
         operator,
@@ -129,6 +421,16 @@ pub fn build_read<'a, T: Transaction + 'a>(
 
             Limit::from((op, input)).execute(cache, transaction)
         }
+        Operator::Window(op) => {
+            let input = childrens.pop().unwrap();
+
+            Window::from((op, input)).execute(cache, transaction)
+        }
+        Operator::DistinctOn(op) => {
+            let input = childrens.pop().unwrap();
+
+            DistinctOn::from((op, input)).execute(cache, transaction)
+        }
         Operator::Values(op) => Values::from(op).execute(cache, transaction),
         Operator::Show => ShowTables.execute(cache, transaction),
         Operator::Explain => {
@@ -143,6 +445,12 @@ pub fn build_read<'a, T: Transaction + 'a>(
 
             Union::from((left_input, right_input)).execute(cache, transaction)
         }
+        Operator::RecursiveUnion(op) => {
+            let recursive_term = childrens.pop().unwrap();
+            let anchor = childrens.pop().unwrap();
+
+            RecursiveUnion::from((op, anchor, recursive_term)).execute(cache, transaction)
+        }
         _ => unreachable!(),
     }
 }
@@ -180,22 +488,46 @@ pub fn build_write<'a, T: Transaction + 'a>(
             let input = childrens.pop().unwrap();
             AddColumn::from((op, input)).execute_mut(cache, transaction)
         }
+        Operator::AlterColumn(op) => {
+            let input = childrens.pop().unwrap();
+            AlterColumn::from((op, input)).execute_mut(cache, transaction)
+        }
         Operator::DropColumn(op) => {
             let input = childrens.pop().unwrap();
             DropColumn::from((op, input)).execute_mut(cache, transaction)
         }
-        Operator::CreateTable(op) => CreateTable::from(op).execute_mut(cache, transaction),
+        Operator::AddCheck(op) => {
+            let input = childrens.pop().unwrap();
+            AddCheck::from((op, input)).execute_mut(cache, transaction)
+        }
+        Operator::AddForeignKey(op) => {
+            let input = childrens.pop().unwrap();
+            AddForeignKey::from((op, input)).execute_mut(cache, transaction)
+        }
+        Operator::DropConstraint(op) => DropConstraint::from(op).execute_mut(cache, transaction),
+        Operator::CreateTable(op) => match childrens.pop() {
+            Some(input) => CreateTable::from((op, input)).execute_mut(cache, transaction),
+            None => CreateTable::from(op).execute_mut(cache, transaction),
+        },
         Operator::CreateIndex(op) => {
             let input = childrens.pop().unwrap();
 
             CreateIndex::from((op, input)).execute_mut(cache, transaction)
         }
+        Operator::RenameTable(op) => RenameTable::from(op).execute_mut(cache, transaction),
         Operator::DropTable(op) => DropTable::from(op).execute_mut(cache, transaction),
-        Operator::Truncate(op) => Truncate::from(op).execute_mut(cache, transaction),
+        Operator::CreateView(op) => CreateView::from(op).execute_mut(cache, transaction),
+        Operator::DropView(op) => DropView::from(op).execute_mut(cache, transaction),
+        Operator::Comment(op) => Comment::from(op).execute_mut(cache, transaction),
+        Operator::Truncate(op) => match childrens.pop() {
+            Some(input) => Truncate::from((op, input)).execute_mut(cache, transaction),
+            None => Truncate::from(op).execute_mut(cache, transaction),
+        },
         Operator::CopyFromFile(op) => CopyFromFile::from(op).execute_mut(cache, transaction),
-        #[warn(unused_assignments)]
-        Operator::CopyToFile(_op) => {
-            todo!()
+        Operator::CopyToFile(op) => {
+            let input = childrens.pop().unwrap();
+
+            CopyToFile::from((op, input)).execute_mut(cache, transaction)
         }
         Operator::Analyze(op) => {
             let input = childrens.pop().unwrap();
@@ -223,3 +555,21 @@ pub fn try_collect(mut executor: Executor) -> Result<Vec<Tuple>, DatabaseError>
     }
     Ok(output)
 }
+
+/// Like [`try_collect`], but fails as soon as more than `max_rows` tuples are produced.
+pub fn try_collect_bounded(
+    mut executor: Executor,
+    max_rows: usize,
+) -> Result<Vec<Tuple>, DatabaseError> {
+    let mut output = Vec::new();
+
+    while let CoroutineState::Yielded(tuple) = Pin::new(&mut executor).resume(()) {
+        let tuple = tuple?;
+
+        if output.len() == max_rows {
+            return Err(DatabaseError::ResultTooLarge(max_rows));
+        }
+        output.push(tuple);
+    }
+    Ok(output)
+}
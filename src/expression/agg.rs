@@ -1,25 +1,65 @@
+use crate::expression::function::aggregate::AggregateFunction;
 use crate::serdes::Serialization;
+use crate::types::{LogicalType, MAX_DECIMAL_SCALE};
 use serde::{Deserialize, Serialize};
 use std::io;
 use std::io::{Read, Write};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// The scale added to a `DECIMAL` argument for the result of `AVG`.
+pub const AVG_DECIMAL_SCALE_INCREMENT: u8 = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AggKind {
     Avg,
     Max,
     Min,
     Sum,
     Count,
+    /// `GROUPING(c1, ...)`, the bitmask of the arguments not in the grouping set of the row,
+    /// which is replaced with constants per grouping set by the binder
+    Grouping,
+    /// registered with [`register_aggregate_function`](crate::db::DataBaseBuilder::register_aggregate_function)
+    Custom(AggregateFunction),
 }
 
 impl AggKind {
+    /// Whether `DISTINCT` changes the result, `MIN(DISTINCT c)` is the same as `MIN(c)`.
     pub fn allow_distinct(&self) -> bool {
         match self {
-            AggKind::Avg => false,
+            AggKind::Avg => true,
             AggKind::Max => false,
             AggKind::Min => false,
             AggKind::Sum => true,
             AggKind::Count => true,
+            AggKind::Grouping => false,
+            AggKind::Custom(_) => true,
+        }
+    }
+
+    /// The return type of `SUM` and `AVG` for the argument type.
+    ///
+    /// - `SUM(TINYINT | SMALLINT | INTEGER)` => `BIGINT`, and the same for the unsigned ones
+    /// - `SUM(BIGINT | UBIGINT)` => `DECIMAL(0)`
+    /// - `SUM(DECIMAL(p, s))` => `DECIMAL(s)`, the precision is only limited by `Decimal`
+    /// - `AVG(DECIMAL(p, s))` => `DECIMAL(s + AVG_DECIMAL_SCALE_INCREMENT)`
+    pub fn return_type(&self, arg_ty: LogicalType) -> LogicalType {
+        match (self, arg_ty.clone()) {
+            (AggKind::Sum, LogicalType::Tinyint | LogicalType::Smallint | LogicalType::Integer) => {
+                LogicalType::Bigint
+            }
+            (
+                AggKind::Sum,
+                LogicalType::UTinyint | LogicalType::USmallint | LogicalType::UInteger,
+            ) => LogicalType::UBigint,
+            (AggKind::Sum, LogicalType::Bigint | LogicalType::UBigint) => {
+                LogicalType::Decimal(None, Some(0))
+            }
+            (AggKind::Sum, LogicalType::Decimal(_, scale)) => LogicalType::Decimal(None, scale),
+            (AggKind::Avg, LogicalType::Decimal(_, scale)) => LogicalType::Decimal(
+                None,
+                Some((scale.unwrap_or(0) + AVG_DECIMAL_SCALE_INCREMENT).min(MAX_DECIMAL_SCALE)),
+            ),
+            _ => arg_ty,
         }
     }
 }
@@ -34,8 +74,17 @@ impl Serialization for AggKind {
             AggKind::Min => 2u8,
             AggKind::Sum => 3u8,
             AggKind::Count => 4u8,
+            AggKind::Custom(_) => 5u8,
+            AggKind::Grouping => 6u8,
         };
-        writer.write_all(&[type_id])
+        writer.write_all(&[type_id])?;
+
+        if let AggKind::Custom(function) = self {
+            let bytes = bincode::serialize(function).map_err(io::Error::other)?;
+            (bytes.len() as u32).encode(writer)?;
+            writer.write_all(&bytes)?;
+        }
+        Ok(())
     }
 
     fn decode<R: Read>(reader: &mut R) -> Result<Self, Self::Error> {
@@ -48,6 +97,13 @@ impl Serialization for AggKind {
             2 => AggKind::Min,
             3 => AggKind::Sum,
             4 => AggKind::Count,
+            5 => {
+                let mut buf = vec![0u8; u32::decode(reader)? as usize];
+                reader.read_exact(&mut buf)?;
+
+                AggKind::Custom(bincode::deserialize(&buf).map_err(io::Error::other)?)
+            }
+            6 => AggKind::Grouping,
             _ => unreachable!(),
         })
     }
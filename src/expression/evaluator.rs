@@ -2,13 +2,14 @@ use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
 use crate::expression::function::scala::ScalarFunction;
 use crate::expression::{AliasType, BinaryOperator, ScalarExpression};
+use crate::function::string::strpos;
+use crate::types::evaluator::utf8::{check_like_pattern, check_regex};
 use crate::types::evaluator::EvaluatorFactory;
 use crate::types::tuple::Tuple;
 use crate::types::value::{DataValue, Utf8Type, ValueRef};
 use crate::types::LogicalType;
 use itertools::Itertools;
 use lazy_static::lazy_static;
-use regex::Regex;
 use sqlparser::ast::{CharLengthUnits, TrimWhereField};
 use std::cmp;
 use std::cmp::Ordering;
@@ -74,26 +75,83 @@ impl ScalarExpression {
 
                 expr.eval(tuple, schema)
             }
-            ScalarExpression::TypeCast { expr, ty, .. } => {
-                let value = expr.eval(tuple, schema)?;
+            ScalarExpression::TypeCast { expr, ty, is_try } => {
+                let value = DataValue::clone(expr.eval(tuple, schema)?.as_ref());
 
-                Ok(Arc::new(DataValue::clone(&value).cast(ty)?))
+                Ok(Arc::new(if *is_try {
+                    value.cast_or_null(ty)
+                } else {
+                    value.cast(ty)?
+                }))
+            }
+            ScalarExpression::Collate { expr, collation } => {
+                Ok(collation.key(expr.eval(tuple, schema)?))
+            }
+            ScalarExpression::FieldAccess { expr, index, ty } => {
+                match expr.eval(tuple, schema)?.as_ref() {
+                    DataValue::Struct {
+                        values: Some(values),
+                        ..
+                    } => Ok(values[*index].clone()),
+                    _ => Ok(Arc::new(DataValue::none(ty))),
+                }
             }
             ScalarExpression::Binary {
+                op,
                 left_expr,
                 right_expr,
                 evaluator,
                 ..
             } => {
                 let left = left_expr.eval(tuple, schema)?;
+                // Tips: the right side is not evaluated if the left side decides the result, as
+                // it may be guarded by the left side, e.g. `c1 <> 0 AND 10 / c1 > 2`
+                if let (BinaryOperator::And, DataValue::Boolean(Some(false)))
+                | (BinaryOperator::Or, DataValue::Boolean(Some(true))) = (op, left.as_ref())
+                {
+                    return Ok(left);
+                }
                 let right = right_expr.eval(tuple, schema)?;
 
+                if let (
+                    BinaryOperator::Like(escape_char)
+                    | BinaryOperator::NotLike(escape_char)
+                    | BinaryOperator::ILike(escape_char)
+                    | BinaryOperator::NotILike(escape_char),
+                    DataValue::Utf8 {
+                        value: Some(pattern),
+                        ..
+                    },
+                ) = (op, right.as_ref())
+                {
+                    check_like_pattern(pattern, *escape_char)?;
+                }
+                if let (
+                    BinaryOperator::RegexMatch
+                    | BinaryOperator::NotRegexMatch
+                    | BinaryOperator::RegexIMatch
+                    | BinaryOperator::NotRegexIMatch,
+                    DataValue::Utf8 {
+                        value: Some(pattern),
+                        ..
+                    },
+                ) = (op, right.as_ref())
+                {
+                    check_regex(
+                        pattern,
+                        matches!(
+                            op,
+                            BinaryOperator::RegexIMatch | BinaryOperator::NotRegexIMatch
+                        ),
+                    )?;
+                }
+
                 Ok(Arc::new(
                     evaluator
                         .as_ref()
                         .ok_or(DatabaseError::EvaluatorNotFound)?
                         .0
-                        .binary_eval(&left, &right),
+                        .checked_binary_eval(&left, &right)?,
                 ))
             }
             ScalarExpression::IsNull { expr, negated } => {
@@ -139,10 +197,10 @@ impl ScalarExpression {
                         .as_ref()
                         .ok_or(DatabaseError::EvaluatorNotFound)?
                         .0
-                        .unary_eval(&value),
+                        .checked_unary_eval(&value)?,
                 ))
             }
-            ScalarExpression::AggCall { .. } => {
+            ScalarExpression::AggCall { .. } | ScalarExpression::WindowCall { .. } => {
                 unreachable!("must use `NormalizationRuleImpl::ExpressionRemapper`")
             }
             ScalarExpression::Between {
@@ -173,7 +231,36 @@ impl ScalarExpression {
                 for_expr,
                 from_expr,
             } => {
-                if let Some(mut string) = DataValue::clone(expr.eval(tuple, schema)?.as_ref())
+                let value = expr.eval(tuple, schema)?;
+
+                // Tips: the bytes of a BYTEA are counted rather than the characters of a string
+                if let DataValue::Bytea(bytes) = value.as_ref() {
+                    let bound = |expr: &Option<Box<ScalarExpression>>, default: i32| match expr {
+                        Some(expr) => Ok::<_, DatabaseError>(
+                            DataValue::clone(expr.eval(tuple, schema)?.as_ref())
+                                .cast(&LogicalType::Integer)?
+                                .i32(),
+                        ),
+                        None => Ok(Some(default)),
+                    };
+                    let value = match (bytes, bound(from_expr, 1)?, bound(for_expr, i32::MAX)?) {
+                        (Some(bytes), Some(from), Some(len)) => {
+                            let len_i = bytes.len() as i32;
+                            let mut from = from.saturating_sub(1);
+
+                            while from < 0 {
+                                from += len_i + 1;
+                            }
+                            (from <= len_i).then(|| {
+                                let rest = &bytes[from as usize..];
+                                rest[..cmp::min(len as usize, rest.len())].to_vec()
+                            })
+                        }
+                        _ => None,
+                    };
+                    return Ok(Arc::new(DataValue::Bytea(value)));
+                }
+                if let Some(mut string) = DataValue::clone(value.as_ref())
                     .cast(&LogicalType::Varchar(None, CharLengthUnits::Characters))?
                     .utf8()
                 {
@@ -213,54 +300,53 @@ impl ScalarExpression {
                 }
             }
             ScalarExpression::Position { expr, in_expr } => {
-                let unpack = |expr: &ScalarExpression| -> Result<String, DatabaseError> {
+                let unpack = |expr: &ScalarExpression| -> Result<Option<String>, DatabaseError> {
                     Ok(DataValue::clone(expr.eval(tuple, schema)?.as_ref())
                         .cast(&LogicalType::Varchar(None, CharLengthUnits::Characters))?
-                        .utf8()
-                        .unwrap_or("".to_owned()))
+                        .utf8())
+                };
+                let value = match (unpack(expr)?, unpack(in_expr)?) {
+                    (Some(pattern), Some(str)) => Some(strpos(&str, &pattern)),
+                    _ => None,
                 };
-                let pattern = unpack(expr)?;
-                let str = unpack(in_expr)?;
-                Ok(Arc::new(DataValue::Int32(Some(
-                    str.find(&pattern).map(|pos| pos as i32 + 1).unwrap_or(0),
-                ))))
+                Ok(Arc::new(DataValue::Int32(value)))
             }
             ScalarExpression::Trim {
                 expr,
                 trim_what_expr,
                 trim_where,
             } => {
-                let mut value = None;
-                if let Some(string) = DataValue::clone(expr.eval(tuple, schema)?.as_ref())
-                    .cast(&LogicalType::Varchar(None, CharLengthUnits::Characters))?
-                    .utf8()
-                {
-                    let mut trim_what = String::from(" ");
-                    if let Some(trim_what_expr) = trim_what_expr {
-                        trim_what = DataValue::clone(trim_what_expr.eval(tuple, schema)?.as_ref())
-                            .cast(&LogicalType::Varchar(None, CharLengthUnits::Characters))?
-                            .utf8()
-                            .unwrap_or_default();
-                    }
-                    let trim_regex = match trim_where {
-                        Some(TrimWhereField::Both) | None => Regex::new(&format!(
-                            r"^(?:{0})*([\w\W]*?)(?:{0})*$",
-                            regex::escape(&trim_what)
-                        ))
-                        .unwrap(),
-                        Some(TrimWhereField::Leading) => {
-                            Regex::new(&format!(r"^(?:{0})*([\w\W]*?)", regex::escape(&trim_what)))
-                                .unwrap()
-                        }
-                        Some(TrimWhereField::Trailing) => {
-                            Regex::new(&format!(r"([\w\W]*?)(?:{0})*$", regex::escape(&trim_what)))
-                                .unwrap()
-                        }
-                    };
-                    let string_trimmed = trim_regex.replace_all(&string, "$1").to_string();
+                let unpack = |expr: &ScalarExpression| -> Result<Option<String>, DatabaseError> {
+                    Ok(DataValue::clone(expr.eval(tuple, schema)?.as_ref())
+                        .cast(&LogicalType::Varchar(None, CharLengthUnits::Characters))?
+                        .utf8())
+                };
+                let trim_what = match trim_what_expr {
+                    Some(trim_what_expr) => unpack(trim_what_expr)?,
+                    None => Some(" ".to_string()),
+                };
+                // Tips: any of the characters of `trim_what` is trimmed, in any order
+                let value = match (unpack(expr)?, trim_what) {
+                    (Some(string), Some(trim_what)) => {
+                        let is_trimmed = |c: char| trim_what.contains(c);
 
-                    value = Some(string_trimmed)
-                }
+                        Some(
+                            match trim_where {
+                                Some(TrimWhereField::Both) | None => {
+                                    string.trim_matches(is_trimmed)
+                                }
+                                Some(TrimWhereField::Leading) => {
+                                    string.trim_start_matches(is_trimmed)
+                                }
+                                Some(TrimWhereField::Trailing) => {
+                                    string.trim_end_matches(is_trimmed)
+                                }
+                            }
+                            .to_string(),
+                        )
+                    }
+                    _ => None,
+                };
                 Ok(Arc::new(DataValue::Utf8 {
                     value,
                     ty: Utf8Type::Variable(None),
@@ -337,6 +423,30 @@ impl ScalarExpression {
                 }
                 check_cast(value.unwrap_or_else(|| NULL_VALUE.clone()), ty)
             }
+            ScalarExpression::Greatest { exprs, ty } | ScalarExpression::Least { exprs, ty } => {
+                let ordering = if matches!(self, ScalarExpression::Greatest { .. }) {
+                    Ordering::Greater
+                } else {
+                    Ordering::Less
+                };
+                let mut value: Option<ValueRef> = None;
+
+                for expr in exprs {
+                    let temp = check_cast(expr.eval(tuple, schema)?, ty)?;
+
+                    // Tips: NULLs are skipped, the result is only NULL if all the arguments are
+                    if temp.is_null() {
+                        continue;
+                    }
+                    if value
+                        .as_ref()
+                        .map_or(true, |value| temp.partial_cmp(value) == Some(ordering))
+                    {
+                        value = Some(temp);
+                    }
+                }
+                Ok(value.unwrap_or_else(|| Arc::new(DataValue::none(ty))))
+            }
             ScalarExpression::CaseWhen {
                 operand_expr,
                 expr_pairs,
@@ -353,14 +463,15 @@ impl ScalarExpression {
                     let mut when_value = when_expr.eval(tuple, schema)?;
                     let is_true = if let Some(operand_value) = &operand_value {
                         let ty = operand_value.logical_type();
-                        let evaluator = EvaluatorFactory::binary_create(ty, BinaryOperator::Eq)?;
+                        let evaluator =
+                            EvaluatorFactory::binary_create(ty.clone(), BinaryOperator::Eq)?;
 
                         if when_value.logical_type() != ty {
                             when_value = Arc::new(DataValue::clone(&when_value).cast(&ty)?);
                         }
                         evaluator
                             .0
-                            .binary_eval(operand_value, &when_value)
+                            .checked_binary_eval(operand_value, &when_value)?
                             .is_true()?
                     } else {
                         when_value.is_true()?
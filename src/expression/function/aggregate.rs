@@ -0,0 +1,101 @@
+use crate::errors::DatabaseError;
+pub use crate::execution::dql::aggregate::Accumulator;
+use crate::expression::function::FunctionSummary;
+use crate::types::value::{DataValue, ValueRef};
+use crate::types::LogicalType;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregateFunction {
+    pub(crate) inner: Arc<dyn AggregateFunctionImpl>,
+}
+
+impl PartialEq for AggregateFunction {
+    fn eq(&self, other: &Self) -> bool {
+        self.summary() == other.summary()
+    }
+}
+
+impl Eq for AggregateFunction {}
+
+impl Hash for AggregateFunction {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.summary().hash(state);
+    }
+}
+
+#[typetag::serde(tag = "aggregate")]
+pub trait AggregateFunctionImpl: Debug + Send + Sync {
+    /// A new accumulator for one group.
+    fn accumulator(&self) -> Box<dyn Accumulator>;
+
+    fn return_type(&self) -> &LogicalType;
+
+    fn summary(&self) -> &FunctionSummary;
+}
+
+impl AggregateFunction {
+    pub fn summary(&self) -> &FunctionSummary {
+        self.inner.summary()
+    }
+}
+
+/// The accumulator of an aggregate function defined with
+/// [`aggregate_function`](crate::aggregate_function), the `NULL` values are skipped like the
+/// built-in aggregates.
+pub struct AggregateFunctionAccumulator<S> {
+    state: S,
+    accumulate: fn(&mut S, &ValueRef) -> Result<(), DatabaseError>,
+    merge: fn(&mut S, &S) -> Result<(), DatabaseError>,
+    finalize: fn(&S) -> Result<DataValue, DatabaseError>,
+}
+
+impl<S> AggregateFunctionAccumulator<S> {
+    pub fn new(
+        state: S,
+        accumulate: fn(&mut S, &ValueRef) -> Result<(), DatabaseError>,
+        merge: fn(&mut S, &S) -> Result<(), DatabaseError>,
+        finalize: fn(&S) -> Result<DataValue, DatabaseError>,
+    ) -> Self {
+        AggregateFunctionAccumulator {
+            state,
+            accumulate,
+            merge,
+            finalize,
+        }
+    }
+}
+
+impl<S: Send + Sync + 'static> Accumulator for AggregateFunctionAccumulator<S> {
+    fn update_value(&mut self, value: &ValueRef) -> Result<(), DatabaseError> {
+        if value.is_null() {
+            return Ok(());
+        }
+        (self.accumulate)(&mut self.state, value)
+    }
+
+    fn evaluate(&self) -> Result<ValueRef, DatabaseError> {
+        Ok(Arc::new((self.finalize)(&self.state)?))
+    }
+
+    fn merge(&mut self, other: &dyn Accumulator) -> Result<(), DatabaseError> {
+        let Some(other) = other
+            .as_any()
+            .and_then(|other| other.downcast_ref::<Self>())
+        else {
+            return Err(DatabaseError::MisMatch(
+                "accumulator",
+                "the accumulator of the same aggregate function",
+            ));
+        };
+        (self.merge)(&mut self.state, &other.state)
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+}
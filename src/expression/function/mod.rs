@@ -1,6 +1,7 @@
 use crate::types::LogicalType;
 use serde::{Deserialize, Serialize};
 
+pub mod aggregate;
 pub mod scala;
 pub mod table;
 
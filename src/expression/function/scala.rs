@@ -23,7 +23,7 @@ pub struct ScalarFunction {
 
 impl PartialEq for ScalarFunction {
     fn eq(&self, other: &Self) -> bool {
-        self.summary() == other.summary()
+        self.summary() == other.summary() && self.args == other.args
     }
 }
 
@@ -32,6 +32,7 @@ impl Eq for ScalarFunction {}
 impl Hash for ScalarFunction {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.summary().hash(state);
+        self.args.hash(state);
     }
 }
 
@@ -50,6 +51,12 @@ pub trait ScalarFunctionImpl: Debug + Send + Sync {
     fn return_type(&self) -> &LogicalType;
 
     fn summary(&self) -> &FunctionSummary;
+
+    /// Whether the same arguments always give the same result, so that it is calculated once
+    /// when all of its arguments are constants.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
 }
 
 impl ScalarFunction {
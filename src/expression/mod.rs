@@ -9,12 +9,15 @@ use std::sync::Arc;
 use std::{fmt, mem};
 
 use self::agg::AggKind;
+use self::window::WindowKind;
+use crate::binder::expr::binary_operand_type;
 use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef};
 use crate::errors::DatabaseError;
 use crate::expression::function::scala::ScalarFunction;
 use crate::expression::function::table::TableFunction;
+use crate::planner::operator::sort::SortField;
 use crate::types::evaluator::{BinaryEvaluatorBox, EvaluatorFactory, UnaryEvaluatorBox};
-use crate::types::value::ValueRef;
+use crate::types::value::{DataValue, ValueRef};
 use crate::types::LogicalType;
 
 pub mod agg;
@@ -22,6 +25,7 @@ mod evaluator;
 pub mod function;
 pub mod range_detacher;
 pub mod simplify;
+pub mod window;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum AliasType {
@@ -44,6 +48,20 @@ pub enum ScalarExpression {
     TypeCast {
         expr: Box<ScalarExpression>,
         ty: LogicalType,
+        /// `TRY_CAST`, which evaluates to NULL rather than failing if the value can not be cast
+        is_try: bool,
+    },
+    /// The `index`-th field of a struct
+    FieldAccess {
+        expr: Box<ScalarExpression>,
+        index: usize,
+        ty: LogicalType,
+    },
+    /// `expr COLLATE "collation"`, evaluates to the collation key of `expr`
+    /// so that comparisons and sorting on it follow the collation.
+    Collate {
+        expr: Box<ScalarExpression>,
+        collation: Collation,
     },
     IsNull {
         negated: bool,
@@ -66,6 +84,17 @@ pub enum ScalarExpression {
         distinct: bool,
         kind: AggKind,
         args: Vec<ScalarExpression>,
+        /// `FILTER (WHERE filter)`, only the rows it is true for are aggregated
+        filter: Option<Box<ScalarExpression>>,
+        ty: LogicalType,
+    },
+    /// `kind(args) OVER (PARTITION BY partition_by ORDER BY order_by)`, computed by the window
+    /// operator for each row of its input
+    WindowCall {
+        kind: WindowKind,
+        args: Vec<ScalarExpression>,
+        partition_by: Vec<ScalarExpression>,
+        order_by: Vec<SortField>,
         ty: LogicalType,
     },
     In {
@@ -122,6 +151,16 @@ pub enum ScalarExpression {
         exprs: Vec<ScalarExpression>,
         ty: LogicalType,
     },
+    /// `GREATEST(expr, ...)`, the largest of the arguments which are not NULL
+    Greatest {
+        exprs: Vec<ScalarExpression>,
+        ty: LogicalType,
+    },
+    /// `LEAST(expr, ...)`, the smallest of the arguments which are not NULL
+    Least {
+        exprs: Vec<ScalarExpression>,
+        ty: LogicalType,
+    },
     CaseWhen {
         operand_expr: Option<Box<ScalarExpression>>,
         expr_pairs: Vec<(ScalarExpression, ScalarExpression)>,
@@ -175,6 +214,9 @@ impl ScalarExpression {
             ScalarExpression::Alias { expr, .. } => {
                 expr.try_reference(output_exprs);
             }
+            ScalarExpression::FieldAccess { expr, .. } | ScalarExpression::Collate { expr, .. } => {
+                expr.try_reference(output_exprs);
+            }
             ScalarExpression::TypeCast { expr, .. } => {
                 expr.try_reference(output_exprs);
             }
@@ -192,13 +234,33 @@ impl ScalarExpression {
                 left_expr.try_reference(output_exprs);
                 right_expr.try_reference(output_exprs);
             }
-            ScalarExpression::AggCall { args, .. }
-            | ScalarExpression::Coalesce { exprs: args, .. }
+            ScalarExpression::AggCall { args, filter, .. } => {
+                for arg in args.iter_mut().chain(filter.as_deref_mut()) {
+                    arg.try_reference(output_exprs);
+                }
+            }
+            ScalarExpression::Coalesce { exprs: args, .. }
+            | ScalarExpression::Greatest { exprs: args, .. }
+            | ScalarExpression::Least { exprs: args, .. }
             | ScalarExpression::Tuple(args) => {
                 for arg in args {
                     arg.try_reference(output_exprs);
                 }
             }
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                for expr in args
+                    .iter_mut()
+                    .chain(partition_by.iter_mut())
+                    .chain(order_by.iter_mut().map(|field| &mut field.expr))
+                {
+                    expr.try_reference(output_exprs);
+                }
+            }
             ScalarExpression::In { expr, args, .. } => {
                 expr.try_reference(output_exprs);
                 for arg in args {
@@ -299,6 +361,146 @@ impl ScalarExpression {
         }
     }
 
+    /// Replaces the columns of an outer query with their values in the current outer row,
+    /// so that a correlated subquery can be run for each row of the outer query.
+    pub fn bind_correlated_values(&mut self, values: &[(ColumnRef, ValueRef)]) {
+        match self {
+            ScalarExpression::ColumnRef(column) => {
+                if let Some((_, value)) = values
+                    .iter()
+                    .find(|(outer_column, _)| outer_column.summary() == column.summary())
+                {
+                    *self = ScalarExpression::Constant(value.clone());
+                }
+            }
+            ScalarExpression::Alias { expr, .. }
+            | ScalarExpression::TypeCast { expr, .. }
+            | ScalarExpression::FieldAccess { expr, .. }
+            | ScalarExpression::Collate { expr, .. }
+            | ScalarExpression::IsNull { expr, .. }
+            | ScalarExpression::Unary { expr, .. } => {
+                expr.bind_correlated_values(values);
+            }
+            ScalarExpression::Binary {
+                left_expr,
+                right_expr,
+                ..
+            }
+            | ScalarExpression::IfNull {
+                left_expr,
+                right_expr,
+                ..
+            }
+            | ScalarExpression::NullIf {
+                left_expr,
+                right_expr,
+                ..
+            }
+            | ScalarExpression::Position {
+                expr: left_expr,
+                in_expr: right_expr,
+            } => {
+                left_expr.bind_correlated_values(values);
+                right_expr.bind_correlated_values(values);
+            }
+            ScalarExpression::AggCall { args, filter, .. } => {
+                for arg in args.iter_mut().chain(filter.as_deref_mut()) {
+                    arg.bind_correlated_values(values);
+                }
+            }
+            ScalarExpression::Coalesce { exprs: args, .. }
+            | ScalarExpression::Greatest { exprs: args, .. }
+            | ScalarExpression::Least { exprs: args, .. }
+            | ScalarExpression::Tuple(args) => {
+                for arg in args {
+                    arg.bind_correlated_values(values);
+                }
+            }
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                for expr in args
+                    .iter_mut()
+                    .chain(partition_by.iter_mut())
+                    .chain(order_by.iter_mut().map(|field| &mut field.expr))
+                {
+                    expr.bind_correlated_values(values);
+                }
+            }
+            ScalarExpression::In { expr, args, .. } => {
+                expr.bind_correlated_values(values);
+                for arg in args {
+                    arg.bind_correlated_values(values);
+                }
+            }
+            ScalarExpression::Between {
+                expr,
+                left_expr,
+                right_expr,
+                ..
+            }
+            | ScalarExpression::If {
+                condition: expr,
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                expr.bind_correlated_values(values);
+                left_expr.bind_correlated_values(values);
+                right_expr.bind_correlated_values(values);
+            }
+            ScalarExpression::SubString {
+                expr,
+                for_expr,
+                from_expr,
+            } => {
+                expr.bind_correlated_values(values);
+                for expr in for_expr.iter_mut().chain(from_expr.iter_mut()) {
+                    expr.bind_correlated_values(values);
+                }
+            }
+            ScalarExpression::Trim {
+                expr,
+                trim_what_expr,
+                ..
+            } => {
+                expr.bind_correlated_values(values);
+                if let Some(trim_what_expr) = trim_what_expr {
+                    trim_what_expr.bind_correlated_values(values);
+                }
+            }
+            ScalarExpression::ScalaFunction(ScalarFunction { args, .. })
+            | ScalarExpression::TableFunction(TableFunction { args, .. }) => {
+                for arg in args {
+                    arg.bind_correlated_values(values);
+                }
+            }
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ..
+            } => {
+                if let Some(expr) = operand_expr {
+                    expr.bind_correlated_values(values);
+                }
+                for (expr_1, expr_2) in expr_pairs {
+                    expr_1.bind_correlated_values(values);
+                    expr_2.bind_correlated_values(values);
+                }
+                if let Some(expr) = else_expr {
+                    expr.bind_correlated_values(values);
+                }
+            }
+            ScalarExpression::Constant(_)
+            | ScalarExpression::Reference { .. }
+            | ScalarExpression::Empty => (),
+        }
+    }
+
     pub fn bind_evaluator(&mut self) -> Result<(), DatabaseError> {
         match self {
             ScalarExpression::Binary {
@@ -311,20 +513,25 @@ impl ScalarExpression {
                 left_expr.bind_evaluator()?;
                 right_expr.bind_evaluator()?;
 
-                let ty = LogicalType::max_logical_type(
-                    &left_expr.return_type(),
-                    &right_expr.return_type(),
-                )?;
+                let (left_ty, right_ty) = (left_expr.return_type(), right_expr.return_type());
+                if let Some((_, interval_evaluator)) =
+                    EvaluatorFactory::interval_binary_create(op, &left_ty, &right_ty)
+                {
+                    *evaluator = Some(interval_evaluator);
+                    return Ok(());
+                }
+                let ty = binary_operand_type(op, &left_ty, &right_ty)?;
                 let fn_cast = |expr: &mut ScalarExpression, ty: LogicalType| {
                     if expr.return_type() != ty {
                         *expr = ScalarExpression::TypeCast {
                             expr: Box::new(mem::replace(expr, ScalarExpression::Empty)),
                             ty,
+                            is_try: false,
                         }
                     }
                 };
-                fn_cast(left_expr, ty);
-                fn_cast(right_expr, ty);
+                fn_cast(left_expr, ty.clone());
+                fn_cast(right_expr, ty.clone());
 
                 *evaluator = Some(EvaluatorFactory::binary_create(ty, *op)?);
             }
@@ -347,6 +554,7 @@ impl ScalarExpression {
                             LogicalType::UBigint => LogicalType::Bigint,
                             _ => unreachable!(),
                         },
+                        is_try: false,
                     }
                 }
                 *evaluator = Some(EvaluatorFactory::unary_create(ty, *op)?);
@@ -354,19 +562,41 @@ impl ScalarExpression {
             ScalarExpression::Alias { expr, .. } => {
                 expr.bind_evaluator()?;
             }
-            ScalarExpression::TypeCast { expr, .. } => {
+            ScalarExpression::TypeCast { expr, .. }
+            | ScalarExpression::FieldAccess { expr, .. }
+            | ScalarExpression::Collate { expr, .. } => {
                 expr.bind_evaluator()?;
             }
             ScalarExpression::IsNull { expr, .. } => {
                 expr.bind_evaluator()?;
             }
-            ScalarExpression::AggCall { args, .. }
-            | ScalarExpression::Coalesce { exprs: args, .. }
+            ScalarExpression::AggCall { args, filter, .. } => {
+                for arg in args.iter_mut().chain(filter.as_deref_mut()) {
+                    arg.bind_evaluator()?;
+                }
+            }
+            ScalarExpression::Coalesce { exprs: args, .. }
+            | ScalarExpression::Greatest { exprs: args, .. }
+            | ScalarExpression::Least { exprs: args, .. }
             | ScalarExpression::Tuple(args) => {
                 for arg in args {
                     arg.bind_evaluator()?;
                 }
             }
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                for expr in args
+                    .iter_mut()
+                    .chain(partition_by.iter_mut())
+                    .chain(order_by.iter_mut().map(|field| &mut field.expr))
+                {
+                    expr.bind_evaluator()?;
+                }
+            }
             ScalarExpression::In { expr, args, .. } => {
                 expr.bind_evaluator()?;
                 for arg in args {
@@ -473,6 +703,8 @@ impl ScalarExpression {
         match self {
             ScalarExpression::Alias { expr, .. } => expr.has_count_star(),
             ScalarExpression::TypeCast { expr, .. } => expr.has_count_star(),
+            ScalarExpression::FieldAccess { expr, .. } => expr.has_count_star(),
+            ScalarExpression::Collate { expr, .. } => expr.has_count_star(),
             ScalarExpression::IsNull { expr, .. } => expr.has_count_star(),
             ScalarExpression::Unary { expr, .. } => expr.has_count_star(),
             ScalarExpression::Binary {
@@ -481,10 +713,11 @@ impl ScalarExpression {
                 ..
             } => left_expr.has_count_star() || right_expr.has_count_star(),
             ScalarExpression::AggCall { args, .. }
+            | ScalarExpression::WindowCall { args, .. }
             | ScalarExpression::ScalaFunction(ScalarFunction { args, .. })
-            | ScalarExpression::Coalesce { exprs: args, .. } => {
-                args.iter().any(Self::has_count_star)
-            }
+            | ScalarExpression::Coalesce { exprs: args, .. }
+            | ScalarExpression::Greatest { exprs: args, .. }
+            | ScalarExpression::Least { exprs: args, .. } => args.iter().any(Self::has_count_star),
             ScalarExpression::TableFunction(_) => unreachable!(),
             ScalarExpression::Constant(_) | ScalarExpression::ColumnRef(_) => false,
             ScalarExpression::In { expr, args, .. } => {
@@ -568,7 +801,8 @@ impl ScalarExpression {
     pub fn return_type(&self) -> LogicalType {
         match self {
             ScalarExpression::Constant(v) => v.logical_type(),
-            ScalarExpression::ColumnRef(col) => *col.datatype(),
+            ScalarExpression::Collate { expr, .. } => expr.return_type(),
+            ScalarExpression::ColumnRef(col) => col.datatype().clone(),
             ScalarExpression::Binary {
                 ty: return_type, ..
             }
@@ -578,9 +812,15 @@ impl ScalarExpression {
             | ScalarExpression::TypeCast {
                 ty: return_type, ..
             }
+            | ScalarExpression::FieldAccess {
+                ty: return_type, ..
+            }
             | ScalarExpression::AggCall {
                 ty: return_type, ..
             }
+            | ScalarExpression::WindowCall {
+                ty: return_type, ..
+            }
             | ScalarExpression::If {
                 ty: return_type, ..
             }
@@ -593,15 +833,22 @@ impl ScalarExpression {
             | ScalarExpression::Coalesce {
                 ty: return_type, ..
             }
+            | ScalarExpression::Greatest {
+                ty: return_type, ..
+            }
+            | ScalarExpression::Least {
+                ty: return_type, ..
+            }
             | ScalarExpression::CaseWhen {
                 ty: return_type, ..
-            } => *return_type,
+            } => return_type.clone(),
             ScalarExpression::IsNull { .. }
             | ScalarExpression::In { .. }
             | ScalarExpression::Between { .. } => LogicalType::Boolean,
-            ScalarExpression::SubString { .. } => {
-                LogicalType::Varchar(None, CharLengthUnits::Characters)
-            }
+            ScalarExpression::SubString { expr, .. } => match expr.return_type() {
+                LogicalType::Bytea => LogicalType::Bytea,
+                _ => LogicalType::Varchar(None, CharLengthUnits::Characters),
+            },
             ScalarExpression::Position { .. } => LogicalType::Integer,
             ScalarExpression::Trim { .. } => {
                 LogicalType::Varchar(None, CharLengthUnits::Characters)
@@ -611,7 +858,9 @@ impl ScalarExpression {
             }
             ScalarExpression::Empty | ScalarExpression::TableFunction(_) => unreachable!(),
             ScalarExpression::Tuple(_) => LogicalType::Tuple,
-            ScalarExpression::ScalaFunction(ScalarFunction { inner, .. }) => *inner.return_type(),
+            ScalarExpression::ScalaFunction(ScalarFunction { inner, .. }) => {
+                inner.return_type().clone()
+            }
         }
     }
 
@@ -635,6 +884,10 @@ impl ScalarExpression {
                 ScalarExpression::TypeCast { expr, .. } => {
                     columns_collect(expr, vec, only_column_ref)
                 }
+                ScalarExpression::FieldAccess { expr, .. }
+                | ScalarExpression::Collate { expr, .. } => {
+                    columns_collect(expr, vec, only_column_ref)
+                }
                 ScalarExpression::IsNull { expr, .. } => {
                     columns_collect(expr, vec, only_column_ref)
                 }
@@ -647,15 +900,35 @@ impl ScalarExpression {
                     columns_collect(left_expr, vec, only_column_ref);
                     columns_collect(right_expr, vec, only_column_ref);
                 }
-                ScalarExpression::AggCall { args, .. }
-                | ScalarExpression::ScalaFunction(ScalarFunction { args, .. })
+                ScalarExpression::AggCall { args, filter, .. } => {
+                    for expr in args.iter().chain(filter.as_deref()) {
+                        columns_collect(expr, vec, only_column_ref)
+                    }
+                }
+                ScalarExpression::ScalaFunction(ScalarFunction { args, .. })
                 | ScalarExpression::TableFunction(TableFunction { args, .. })
                 | ScalarExpression::Tuple(args)
-                | ScalarExpression::Coalesce { exprs: args, .. } => {
+                | ScalarExpression::Coalesce { exprs: args, .. }
+                | ScalarExpression::Greatest { exprs: args, .. }
+                | ScalarExpression::Least { exprs: args, .. } => {
                     for expr in args {
                         columns_collect(expr, vec, only_column_ref)
                     }
                 }
+                ScalarExpression::WindowCall {
+                    args,
+                    partition_by,
+                    order_by,
+                    ..
+                } => {
+                    for expr in args
+                        .iter()
+                        .chain(partition_by.iter())
+                        .chain(order_by.iter().map(|field| &field.expr))
+                    {
+                        columns_collect(expr, vec, only_column_ref)
+                    }
+                }
                 ScalarExpression::In { expr, args, .. } => {
                     columns_collect(expr, vec, only_column_ref);
                     for arg in args {
@@ -757,18 +1030,30 @@ impl ScalarExpression {
                 column.table_name().is_some() && column.id().is_some()
             }
             ScalarExpression::Alias { expr, .. } => expr.has_table_ref_column(),
-            ScalarExpression::TypeCast { expr, .. } | ScalarExpression::IsNull { expr, .. } => {
-                expr.has_table_ref_column()
-            }
+            ScalarExpression::TypeCast { expr, .. }
+            | ScalarExpression::FieldAccess { expr, .. }
+            | ScalarExpression::Collate { expr, .. }
+            | ScalarExpression::IsNull { expr, .. } => expr.has_table_ref_column(),
             ScalarExpression::Unary { expr, .. } => expr.has_table_ref_column(),
             ScalarExpression::Binary {
                 left_expr,
                 right_expr,
                 ..
             } => left_expr.has_table_ref_column() || right_expr.has_table_ref_column(),
-            ScalarExpression::AggCall { args, .. } => {
-                args.iter().any(ScalarExpression::has_table_ref_column)
-            }
+            ScalarExpression::AggCall { args, filter, .. } => args
+                .iter()
+                .chain(filter.as_deref())
+                .any(ScalarExpression::has_table_ref_column),
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => args
+                .iter()
+                .chain(partition_by.iter())
+                .chain(order_by.iter().map(|field| &field.expr))
+                .any(ScalarExpression::has_table_ref_column),
             ScalarExpression::In { expr, args, .. } => {
                 expr.has_table_ref_column()
                     || args.iter().any(ScalarExpression::has_table_ref_column)
@@ -845,7 +1130,9 @@ impl ScalarExpression {
                 right_expr,
                 ..
             } => left_expr.has_table_ref_column() || right_expr.has_table_ref_column(),
-            ScalarExpression::Coalesce { exprs, .. } => {
+            ScalarExpression::Coalesce { exprs, .. }
+            | ScalarExpression::Greatest { exprs, .. }
+            | ScalarExpression::Least { exprs, .. } => {
                 exprs.iter().any(ScalarExpression::has_table_ref_column)
             }
             ScalarExpression::CaseWhen {
@@ -872,10 +1159,22 @@ impl ScalarExpression {
     pub fn has_agg_call(&self) -> bool {
         match self {
             ScalarExpression::AggCall { .. } => true,
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => args
+                .iter()
+                .chain(partition_by.iter())
+                .chain(order_by.iter().map(|field| &field.expr))
+                .any(ScalarExpression::has_agg_call),
             ScalarExpression::Constant(_) => false,
             ScalarExpression::ColumnRef(_) => false,
             ScalarExpression::Alias { expr, .. } => expr.has_agg_call(),
             ScalarExpression::TypeCast { expr, .. } => expr.has_agg_call(),
+            ScalarExpression::FieldAccess { expr, .. } => expr.has_agg_call(),
+            ScalarExpression::Collate { expr, .. } => expr.has_agg_call(),
             ScalarExpression::IsNull { expr, .. } => expr.has_agg_call(),
             ScalarExpression::Unary { expr, .. } => expr.has_agg_call(),
             ScalarExpression::Binary {
@@ -923,7 +1222,9 @@ impl ScalarExpression {
             | ScalarExpression::TableFunction(_) => unreachable!(),
             ScalarExpression::Tuple(args)
             | ScalarExpression::ScalaFunction(ScalarFunction { args, .. })
-            | ScalarExpression::Coalesce { exprs: args, .. } => args.iter().any(Self::has_agg_call),
+            | ScalarExpression::Coalesce { exprs: args, .. }
+            | ScalarExpression::Greatest { exprs: args, .. }
+            | ScalarExpression::Least { exprs: args, .. } => args.iter().any(Self::has_agg_call),
             ScalarExpression::If {
                 condition,
                 left_expr,
@@ -960,6 +1261,174 @@ impl ScalarExpression {
         }
     }
 
+    /// Whether the expression calls a function whose result may differ for the same arguments,
+    /// e.g. `now()` or `gen_random_uuid()`.
+    pub fn has_volatile_call(&self) -> bool {
+        match self {
+            ScalarExpression::AggCall { args, filter, .. } => args
+                .iter()
+                .chain(filter.as_deref())
+                .any(ScalarExpression::has_volatile_call),
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => args
+                .iter()
+                .chain(partition_by.iter())
+                .chain(order_by.iter().map(|field| &field.expr))
+                .any(ScalarExpression::has_volatile_call),
+            ScalarExpression::Constant(_) => false,
+            ScalarExpression::ColumnRef(_) => false,
+            ScalarExpression::Alias { expr, .. } => expr.has_volatile_call(),
+            ScalarExpression::TypeCast { expr, .. } => expr.has_volatile_call(),
+            ScalarExpression::FieldAccess { expr, .. } => expr.has_volatile_call(),
+            ScalarExpression::Collate { expr, .. } => expr.has_volatile_call(),
+            ScalarExpression::IsNull { expr, .. } => expr.has_volatile_call(),
+            ScalarExpression::Unary { expr, .. } => expr.has_volatile_call(),
+            ScalarExpression::Binary {
+                left_expr,
+                right_expr,
+                ..
+            } => left_expr.has_volatile_call() || right_expr.has_volatile_call(),
+            ScalarExpression::In { expr, args, .. } => {
+                expr.has_volatile_call() || args.iter().any(|arg| arg.has_volatile_call())
+            }
+            ScalarExpression::Between {
+                expr,
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                expr.has_volatile_call()
+                    || left_expr.has_volatile_call()
+                    || right_expr.has_volatile_call()
+            }
+            ScalarExpression::SubString {
+                expr,
+                for_expr,
+                from_expr,
+            } => {
+                expr.has_volatile_call()
+                    || matches!(
+                        for_expr.as_ref().map(|expr| expr.has_volatile_call()),
+                        Some(true)
+                    )
+                    || matches!(
+                        from_expr.as_ref().map(|expr| expr.has_volatile_call()),
+                        Some(true)
+                    )
+            }
+            ScalarExpression::Position { expr, in_expr } => {
+                expr.has_volatile_call() || in_expr.has_volatile_call()
+            }
+            ScalarExpression::Trim {
+                expr,
+                trim_what_expr,
+                ..
+            } => {
+                expr.has_volatile_call()
+                    || trim_what_expr.as_ref().map(|expr| expr.has_volatile_call()) == Some(true)
+            }
+            ScalarExpression::Reference { .. }
+            | ScalarExpression::Empty
+            | ScalarExpression::TableFunction(_) => unreachable!(),
+            ScalarExpression::ScalaFunction(ScalarFunction { args, inner }) => {
+                !inner.is_deterministic() || args.iter().any(Self::has_volatile_call)
+            }
+            ScalarExpression::Tuple(args)
+            | ScalarExpression::Coalesce { exprs: args, .. }
+            | ScalarExpression::Greatest { exprs: args, .. }
+            | ScalarExpression::Least { exprs: args, .. } => {
+                args.iter().any(Self::has_volatile_call)
+            }
+            ScalarExpression::If {
+                condition,
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                condition.has_volatile_call()
+                    || left_expr.has_volatile_call()
+                    || right_expr.has_volatile_call()
+            }
+            ScalarExpression::IfNull {
+                left_expr,
+                right_expr,
+                ..
+            }
+            | ScalarExpression::NullIf {
+                left_expr,
+                right_expr,
+                ..
+            } => left_expr.has_volatile_call() || right_expr.has_volatile_call(),
+            ScalarExpression::CaseWhen {
+                operand_expr,
+                expr_pairs,
+                else_expr,
+                ..
+            } => {
+                matches!(
+                    operand_expr.as_ref().map(|expr| expr.has_volatile_call()),
+                    Some(true)
+                ) || expr_pairs.iter().any(|(expr_1, expr_2)| {
+                    expr_1.has_volatile_call() || expr_2.has_volatile_call()
+                }) || matches!(
+                    else_expr.as_ref().map(|expr| expr.has_volatile_call()),
+                    Some(true)
+                )
+            }
+        }
+    }
+
+    /// Whether evaluating the expression may fail for some rows, e.g. `10 / c1` for `c1 = 0`, so
+    /// that it should not be moved ahead of a predicate guarding it.
+    pub fn may_fail(&self) -> bool {
+        match self {
+            ScalarExpression::Constant(_)
+            | ScalarExpression::ColumnRef(_)
+            | ScalarExpression::Empty => false,
+            ScalarExpression::Alias { expr, .. }
+            | ScalarExpression::Reference { expr, .. }
+            | ScalarExpression::IsNull { expr, .. }
+            | ScalarExpression::TypeCast {
+                expr, is_try: true, ..
+            } => expr.may_fail(),
+            ScalarExpression::Unary {
+                op: UnaryOperator::Not | UnaryOperator::Plus,
+                expr,
+                ..
+            } => expr.may_fail(),
+            ScalarExpression::Binary {
+                op,
+                left_expr,
+                right_expr,
+                ..
+            } => {
+                matches!(
+                    op,
+                    BinaryOperator::Plus
+                        | BinaryOperator::Minus
+                        | BinaryOperator::Multiply
+                        | BinaryOperator::Divide
+                        | BinaryOperator::Modulo
+                ) || left_expr.may_fail()
+                    || right_expr.may_fail()
+            }
+            ScalarExpression::In { expr, args, .. } => {
+                expr.may_fail() || args.iter().any(ScalarExpression::may_fail)
+            }
+            ScalarExpression::Between {
+                expr,
+                left_expr,
+                right_expr,
+                ..
+            } => expr.may_fail() || left_expr.may_fail() || right_expr.may_fail(),
+            _ => true,
+        }
+    }
+
     pub fn output_name(&self) -> String {
         match self {
             ScalarExpression::Constant(value) => format!("{}", value),
@@ -970,8 +1439,19 @@ impl ScalarExpression {
                     format!("({}) as ({})", expr, alias_expr.output_name())
                 }
             },
-            ScalarExpression::TypeCast { expr, ty } => {
-                format!("cast ({} as {})", expr.output_name(), ty)
+            ScalarExpression::TypeCast { expr, ty, is_try } => {
+                let cast = if *is_try { "try_cast" } else { "cast" };
+
+                format!("{} ({} as {})", cast, expr.output_name(), ty)
+            }
+            ScalarExpression::FieldAccess { expr, index, .. } => match expr.return_type() {
+                LogicalType::Struct(struct_type) => {
+                    format!("{}.{}", expr.output_name(), struct_type.fields()[*index].0)
+                }
+                _ => unreachable!(),
+            },
+            ScalarExpression::Collate { expr, collation } => {
+                format!("{} collate \"{}\"", expr.output_name(), collation)
             }
             ScalarExpression::IsNull { expr, negated } => {
                 let suffix = if *negated { "is not null" } else { "is null" };
@@ -994,9 +1474,14 @@ impl ScalarExpression {
                 args,
                 kind,
                 distinct,
+                filter,
                 ..
             } => {
                 let args_str = args.iter().map(|expr| expr.output_name()).join(", ");
+                let filter_str = filter
+                    .as_ref()
+                    .map(|expr| format!(" filter (where {})", expr.output_name()))
+                    .unwrap_or_default();
                 let op = |allow_distinct, distinct| {
                     if allow_distinct && distinct {
                         "distinct "
@@ -1004,13 +1489,59 @@ impl ScalarExpression {
                         ""
                     }
                 };
+                let kind_str = match kind {
+                    AggKind::Custom(function) => function.summary().name.clone(),
+                    kind => format!("{:?}", kind),
+                };
                 format!(
-                    "{:?}({}{})",
-                    kind,
+                    "{}({}{}){}",
+                    kind_str,
                     op(kind.allow_distinct(), *distinct),
-                    args_str
+                    args_str,
+                    filter_str
                 )
             }
+            ScalarExpression::WindowCall {
+                kind,
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                let args_str = args.iter().map(|expr| expr.output_name()).join(", ");
+                let mut window_str = Vec::with_capacity(2);
+
+                if !partition_by.is_empty() {
+                    window_str.push(format!(
+                        "partition by {}",
+                        partition_by
+                            .iter()
+                            .map(|expr| expr.output_name())
+                            .join(", ")
+                    ));
+                }
+                if !order_by.is_empty() {
+                    window_str.push(format!(
+                        "order by {}",
+                        order_by
+                            .iter()
+                            .map(|field| {
+                                format!(
+                                    "{}{}{}",
+                                    field.expr.output_name(),
+                                    if field.asc { "" } else { " desc" },
+                                    if field.nulls_first {
+                                        " nulls first"
+                                    } else {
+                                        ""
+                                    },
+                                )
+                            })
+                            .join(", ")
+                    ));
+                }
+                format!("{}({}) over ({})", kind, args_str, window_str.join(" "))
+            }
             ScalarExpression::In {
                 args,
                 negated,
@@ -1126,6 +1657,14 @@ impl ScalarExpression {
                 let exprs_str = exprs.iter().map(|expr| expr.output_name()).join(", ");
                 format!("coalesce({})", exprs_str)
             }
+            ScalarExpression::Greatest { exprs, .. } => {
+                let exprs_str = exprs.iter().map(|expr| expr.output_name()).join(", ");
+                format!("greatest({})", exprs_str)
+            }
+            ScalarExpression::Least { exprs, .. } => {
+                let exprs_str = exprs.iter().map(|expr| expr.output_name()).join(", ");
+                format!("least({})", exprs_str)
+            }
             ScalarExpression::CaseWhen {
                 operand_expr,
                 expr_pairs,
@@ -1170,6 +1709,52 @@ impl ScalarExpression {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Collation {
+    /// compares the bytes of strings
+    Binary,
+    /// compares strings ignoring the case of ASCII letters
+    NoCase,
+}
+
+impl Collation {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "binary" | "c" | "default" => Some(Collation::Binary),
+            "nocase" => Some(Collation::NoCase),
+            _ => None,
+        }
+    }
+
+    /// The value that stands for `value` in comparisons and sorting.
+    pub fn key(&self, value: ValueRef) -> ValueRef {
+        match (self, value.as_ref()) {
+            (
+                Collation::NoCase,
+                DataValue::Utf8 {
+                    value: Some(string),
+                    ty,
+                    unit,
+                },
+            ) => Arc::new(DataValue::Utf8 {
+                value: Some(string.to_ascii_lowercase()),
+                ty: ty.clone(),
+                unit: *unit,
+            }),
+            _ => value,
+        }
+    }
+}
+
+impl fmt::Display for Collation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Collation::Binary => write!(f, "binary"),
+            Collation::NoCase => write!(f, "nocase"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum UnaryOperator {
     Plus,
@@ -1207,6 +1792,15 @@ pub enum BinaryOperator {
     NotEq,
     Like(Option<char>),
     NotLike(Option<char>),
+    /// `LIKE` which ignores the case of the letters
+    ILike(Option<char>),
+    NotILike(Option<char>),
+    /// `~`, whether the string matches the regular expression somewhere
+    RegexMatch,
+    NotRegexMatch,
+    /// `~*`, `~` which ignores the case of the letters
+    RegexIMatch,
+    NotRegexIMatch,
 
     And,
     Or,
@@ -1253,6 +1847,18 @@ impl fmt::Display for BinaryOperator {
                 write!(f, "not like")?;
                 like_op(f, escape_char)
             }
+            BinaryOperator::ILike(escape_char) => {
+                write!(f, "ilike")?;
+                like_op(f, escape_char)
+            }
+            BinaryOperator::NotILike(escape_char) => {
+                write!(f, "not ilike")?;
+                like_op(f, escape_char)
+            }
+            BinaryOperator::RegexMatch => write!(f, "~"),
+            BinaryOperator::NotRegexMatch => write!(f, "!~"),
+            BinaryOperator::RegexIMatch => write!(f, "~*"),
+            BinaryOperator::NotRegexIMatch => write!(f, "!~*"),
         }
     }
 }
@@ -1286,6 +1892,10 @@ impl From<SqlBinaryOperator> for BinaryOperator {
             SqlBinaryOperator::And => BinaryOperator::And,
             SqlBinaryOperator::Or => BinaryOperator::Or,
             SqlBinaryOperator::Xor => BinaryOperator::Xor,
+            SqlBinaryOperator::PGRegexMatch => BinaryOperator::RegexMatch,
+            SqlBinaryOperator::PGRegexNotMatch => BinaryOperator::NotRegexMatch,
+            SqlBinaryOperator::PGRegexIMatch => BinaryOperator::RegexIMatch,
+            SqlBinaryOperator::PGRegexNotIMatch => BinaryOperator::NotRegexIMatch,
             _ => unimplemented!("not support!"),
         }
     }
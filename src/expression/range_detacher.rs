@@ -1,7 +1,8 @@
 use crate::catalog::ColumnRef;
 use crate::expression::{BinaryOperator, ScalarExpression};
-use crate::types::value::{DataValue, ValueRef, NULL_VALUE};
-use crate::types::ColumnId;
+use crate::function::json::{unpack_json_path, JsonExtractKind};
+use crate::types::value::{DataValue, Utf8Type, ValueRef, NULL_VALUE};
+use crate::types::{ColumnId, LogicalType};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
@@ -177,6 +178,7 @@ impl Range {
 pub struct RangeDetacher<'a> {
     table_name: &'a str,
     column_id: &'a ColumnId,
+    json_path: &'a [(JsonExtractKind, ValueRef)],
 }
 
 impl<'a> RangeDetacher<'a> {
@@ -184,9 +186,31 @@ impl<'a> RangeDetacher<'a> {
         Self {
             table_name,
             column_id,
+            json_path: &[],
         }
     }
 
+    /// Detaches the ranges of the JSON path on the column instead of the ones of the column, see
+    /// [`IndexMeta::json_path`](crate::types::index::IndexMeta::json_path).
+    pub(crate) fn with_json_path(mut self, json_path: &'a [(JsonExtractKind, ValueRef)]) -> Self {
+        self.json_path = json_path;
+        self
+    }
+
+    /// The column the ranges are detached on if the expression is the column, or its JSON path.
+    fn unpack_col(&self, expr: &ScalarExpression) -> Option<ColumnRef> {
+        if self.json_path.is_empty() {
+            return expr.unpack_col(false);
+        }
+        let expr = match expr {
+            ScalarExpression::Alias { expr, .. } => expr,
+            expr => expr,
+        };
+        unpack_json_path(expr)
+            .filter(|(_, json_path)| json_path == self.json_path)
+            .map(|(column, _)| column)
+    }
+
     pub(crate) fn detach(&mut self, expr: &ScalarExpression) -> Option<Range> {
         match expr {
             ScalarExpression::Binary {
@@ -200,11 +224,11 @@ impl<'a> RangeDetacher<'a> {
                 }
                 (None, None) => {
                     if let (Some(col), Some(val)) =
-                        (left_expr.unpack_col(false), right_expr.unpack_val())
+                        (self.unpack_col(left_expr), right_expr.unpack_val())
                     {
                         return self.new_range(*op, col, val, false);
                     } else if let (Some(val), Some(col)) =
-                        (left_expr.unpack_val(), right_expr.unpack_col(false))
+                        (left_expr.unpack_val(), self.unpack_col(right_expr))
                     {
                         return self.new_range(*op, col, val, true);
                     }
@@ -221,7 +245,20 @@ impl<'a> RangeDetacher<'a> {
             | ScalarExpression::SubString { expr, .. } => self.detach(expr),
             ScalarExpression::Position { expr, .. } => self.detach(expr),
             ScalarExpression::Trim { expr, .. } => self.detach(expr),
+            // the range of a field is not the range of the struct column
+            ScalarExpression::FieldAccess { .. } => None,
+            // the index is ordered by the bytes of the column
+            ScalarExpression::Collate { .. } => None,
             ScalarExpression::IsNull { expr, negated, .. } => match expr.as_ref() {
+                // e.g. `data ->> 'email' IS NULL` of the JSON path index on `data`
+                expr if !self.json_path.is_empty() => self
+                    .unpack_col(expr)
+                    .filter(|column| {
+                        !*negated
+                            && Self::_is_belong(self.table_name, column)
+                            && column.id() == Some(*self.column_id)
+                    })
+                    .map(|_| Range::Eq(NULL_VALUE.clone())),
                 ScalarExpression::ColumnRef(column) => {
                     if let (Some(col_id), Some(col_table)) = (column.id(), column.table_name()) {
                         if &col_id == self.column_id && col_table.as_str() == self.table_name {
@@ -239,10 +276,13 @@ impl<'a> RangeDetacher<'a> {
                 ScalarExpression::Constant(_)
                 | ScalarExpression::Alias { .. }
                 | ScalarExpression::TypeCast { .. }
+                | ScalarExpression::FieldAccess { .. }
+                | ScalarExpression::Collate { .. }
                 | ScalarExpression::IsNull { .. }
                 | ScalarExpression::Unary { .. }
                 | ScalarExpression::Binary { .. }
                 | ScalarExpression::AggCall { .. }
+                | ScalarExpression::WindowCall { .. }
                 | ScalarExpression::In { .. }
                 | ScalarExpression::Between { .. }
                 | ScalarExpression::SubString { .. }
@@ -253,6 +293,8 @@ impl<'a> RangeDetacher<'a> {
                 | ScalarExpression::IfNull { .. }
                 | ScalarExpression::NullIf { .. }
                 | ScalarExpression::Coalesce { .. }
+                | ScalarExpression::Greatest { .. }
+                | ScalarExpression::Least { .. }
                 | ScalarExpression::CaseWhen { .. } => self.detach(expr),
                 ScalarExpression::Tuple(_)
                 | ScalarExpression::TableFunction(_)
@@ -263,11 +305,14 @@ impl<'a> RangeDetacher<'a> {
             // FIXME: support [RangeDetacher::_detach]
             ScalarExpression::Tuple(_)
             | ScalarExpression::AggCall { .. }
+            | ScalarExpression::WindowCall { .. }
             | ScalarExpression::ScalaFunction(_)
             | ScalarExpression::If { .. }
             | ScalarExpression::IfNull { .. }
             | ScalarExpression::NullIf { .. }
             | ScalarExpression::Coalesce { .. }
+            | ScalarExpression::Greatest { .. }
+            | ScalarExpression::Least { .. }
             | ScalarExpression::CaseWhen { .. } => None,
             ScalarExpression::TableFunction(_)
             | ScalarExpression::Reference { .. }
@@ -710,6 +755,32 @@ impl<'a> RangeDetacher<'a> {
         if !Self::_is_belong(self.table_name, &col) || col.id() != Some(*self.column_id) {
             return None;
         }
+        // e.g. `decimal_column > 1`, the bound is encoded like the decimals in the index, without
+        // being rounded to the scale of the column
+        let val = if matches!(col.datatype(), LogicalType::Decimal(..))
+            && !matches!(val.as_ref(), DataValue::Decimal(_))
+        {
+            Arc::new(
+                DataValue::clone(&val)
+                    .cast(&LogicalType::Decimal(None, None))
+                    .ok()?,
+            )
+        } else if let (
+            LogicalType::Char(len, unit),
+            DataValue::Utf8 {
+                value: Some(value), ..
+            },
+        ) = (col.datatype(), val.as_ref())
+        {
+            // e.g. `char_column = 'ab  '`, the bound is encoded like the `CHAR`s in the index
+            Arc::new(DataValue::Utf8 {
+                value: Some(value.trim_end_matches(' ').to_string()),
+                ty: Utf8Type::Fixed(*len),
+                unit: *unit,
+            })
+        } else {
+            val
+        };
         if is_flip {
             op = match op {
                 BinaryOperator::Gt => BinaryOperator::Lt,
@@ -1426,6 +1497,33 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_detach_regex_cases() -> Result<(), DatabaseError> {
+        {
+            let plan = select_sql_run("select * from t1 where c1 ~ '^1'")?;
+            let op = plan_filter(plan)?.unwrap();
+            let range = RangeDetacher::new("t1", &0).detach(&op.predicate);
+            println!("c1 ~ '^1' => {:#?}", range);
+            debug_assert_eq!(range, None)
+        }
+        {
+            let plan = select_sql_run("select * from t1 where c1 ~ '^1' or c1 = 1")?;
+            let op = plan_filter(plan)?.unwrap();
+            let range = RangeDetacher::new("t1", &0).detach(&op.predicate);
+            println!("c1 ~ '^1' or c1 = 1 => {:#?}", range);
+            debug_assert_eq!(range, None)
+        }
+        {
+            let plan = select_sql_run("select * from t1 where c1 !~* '^1' and c1 = 1")?;
+            let op = plan_filter(plan)?.unwrap();
+            let range = RangeDetacher::new("t1", &0).detach(&op.predicate).unwrap();
+            println!("c1 !~* '^1' and c1 = 1 => c1: {}", range);
+            debug_assert_eq!(range, Range::Eq(Arc::new(DataValue::Int32(Some(1)))))
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_to_tuple_range_some() {
         let eqs_ranges = vec![
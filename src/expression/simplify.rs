@@ -1,11 +1,15 @@
+use crate::binder::expr::binary_operand_type;
 use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
 use crate::expression::function::scala::ScalarFunction;
 use crate::expression::function::table::TableFunction;
 use crate::expression::{BinaryOperator, ScalarExpression, UnaryOperator};
+use crate::function::date_trunc::{DateTruncUnit, DATE_TRUNC};
 use crate::types::evaluator::EvaluatorFactory;
+use crate::types::tuple::Tuple;
 use crate::types::value::{DataValue, ValueRef};
 use crate::types::{ColumnId, LogicalType};
+use chrono::NaiveDateTime;
 use std::mem;
 use std::sync::Arc;
 
@@ -39,6 +43,8 @@ impl ScalarExpression {
             }
             ScalarExpression::Alias { expr, .. } => expr.exist_column(table_name, col_id),
             ScalarExpression::TypeCast { expr, .. } => expr.exist_column(table_name, col_id),
+            ScalarExpression::FieldAccess { expr, .. } => expr.exist_column(table_name, col_id),
+            ScalarExpression::Collate { expr, .. } => expr.exist_column(table_name, col_id),
             ScalarExpression::IsNull { expr, .. } => expr.exist_column(table_name, col_id),
             ScalarExpression::Unary { expr, .. } => expr.exist_column(table_name, col_id),
             ScalarExpression::Binary {
@@ -49,12 +55,27 @@ impl ScalarExpression {
                 left_expr.exist_column(table_name, col_id)
                     || right_expr.exist_column(table_name, col_id)
             }
-            ScalarExpression::AggCall { args, .. }
-            | ScalarExpression::Tuple(args)
+            ScalarExpression::AggCall { args, filter, .. } => args
+                .iter()
+                .chain(filter.as_deref())
+                .any(|expr| expr.exist_column(table_name, col_id)),
+            ScalarExpression::Tuple(args)
             | ScalarExpression::ScalaFunction(ScalarFunction { args, .. })
             | ScalarExpression::TableFunction(TableFunction { args, .. })
-            | ScalarExpression::Coalesce { exprs: args, .. } => args
+            | ScalarExpression::Coalesce { exprs: args, .. }
+            | ScalarExpression::Greatest { exprs: args, .. }
+            | ScalarExpression::Least { exprs: args, .. } => args
+                .iter()
+                .any(|expr| expr.exist_column(table_name, col_id)),
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => args
                 .iter()
+                .chain(partition_by.iter())
+                .chain(order_by.iter().map(|field| &field.expr))
                 .any(|expr| expr.exist_column(table_name, col_id)),
             ScalarExpression::In { expr, args, .. } => {
                 expr.exist_column(table_name, col_id)
@@ -154,9 +175,37 @@ impl ScalarExpression {
         match self {
             ScalarExpression::Constant(val) => Some(val.clone()),
             ScalarExpression::Alias { expr, .. } => expr.unpack_val(),
-            ScalarExpression::TypeCast { expr, ty, .. } => expr
-                .unpack_val()
-                .and_then(|val| DataValue::clone(&val).cast(ty).ok().map(Arc::new)),
+            ScalarExpression::TypeCast { expr, ty, is_try } => expr.unpack_val().and_then(|val| {
+                let val = DataValue::clone(&val);
+
+                if *is_try {
+                    Some(Arc::new(val.cast_or_null(ty)))
+                } else {
+                    val.cast(ty).ok().map(Arc::new)
+                }
+            }),
+            ScalarExpression::Tuple(exprs) => {
+                let values = exprs
+                    .iter()
+                    .map(ScalarExpression::unpack_val)
+                    .collect::<Option<Vec<_>>>()?;
+
+                Some(Arc::new(DataValue::Tuple(
+                    (!values.is_empty()).then_some(values),
+                )))
+            }
+            ScalarExpression::Collate { expr, collation } => {
+                expr.unpack_val().map(|value| collation.key(value))
+            }
+            ScalarExpression::FieldAccess { expr, index, ty } => {
+                match expr.unpack_val()?.as_ref() {
+                    DataValue::Struct {
+                        values: Some(values),
+                        ..
+                    } => Some(values[*index].clone()),
+                    _ => Some(Arc::new(DataValue::none(ty))),
+                }
+            }
             ScalarExpression::IsNull { expr, .. } => {
                 let is_null = expr.unpack_val().map(|val| val.is_null());
 
@@ -171,12 +220,13 @@ impl ScalarExpression {
             } => {
                 let value = expr.unpack_val()?;
                 let unary_value = if let Some(evaluator) = evaluator {
-                    evaluator.0.unary_eval(&value)
+                    evaluator.0.checked_unary_eval(&value).ok()?
                 } else {
-                    EvaluatorFactory::unary_create(*ty, *op)
+                    EvaluatorFactory::unary_create(ty.clone(), *op)
                         .ok()?
                         .0
-                        .unary_eval(&value)
+                        .checked_unary_eval(&value)
+                        .ok()?
                 };
                 Some(Arc::new(unary_value))
             }
@@ -190,6 +240,13 @@ impl ScalarExpression {
             } => {
                 let mut left = left_expr.unpack_val()?;
                 let mut right = right_expr.unpack_val()?;
+                if let Some((_, evaluator)) = EvaluatorFactory::interval_binary_create(
+                    op,
+                    &left_expr.return_type(),
+                    &right_expr.return_type(),
+                ) {
+                    return Some(Arc::new(evaluator.checked_binary_eval(&left, &right).ok()?));
+                }
                 if left.logical_type() != *ty {
                     left = Arc::new(DataValue::clone(&left).cast(ty).ok()?);
                 }
@@ -197,12 +254,13 @@ impl ScalarExpression {
                     right = Arc::new(DataValue::clone(&right).cast(ty).ok()?);
                 }
                 let binary_value = if let Some(evaluator) = evaluator {
-                    evaluator.0.binary_eval(&left, &right)
+                    evaluator.0.checked_binary_eval(&left, &right).ok()?
                 } else {
-                    EvaluatorFactory::binary_create(*ty, *op)
+                    EvaluatorFactory::binary_create(ty.clone(), *op)
                         .ok()?
                         .0
-                        .binary_eval(&left, &right)
+                        .checked_binary_eval(&left, &right)
+                        .ok()?
                 };
                 Some(Arc::new(binary_value))
             }
@@ -249,11 +307,11 @@ impl ScalarExpression {
 
                 if let ScalarExpression::Constant(unary_val) = expr.as_ref() {
                     let value = if let Some(evaluator) = evaluator {
-                        evaluator.0.unary_eval(unary_val)
+                        evaluator.0.checked_unary_eval(unary_val)?
                     } else {
-                        EvaluatorFactory::unary_create(*ty, *op)?
+                        EvaluatorFactory::unary_create(ty.clone(), *op)?
                             .0
-                            .unary_eval(unary_val)
+                            .checked_unary_eval(unary_val)?
                     };
                     let _ = mem::replace(self, ScalarExpression::Constant(Arc::new(value)));
                 }
@@ -264,35 +322,78 @@ impl ScalarExpression {
                 op,
                 ..
             } => {
-                let ty = LogicalType::max_logical_type(
-                    &left_expr.return_type(),
-                    &right_expr.return_type(),
-                )?;
+                let (left_ty, right_ty) = (left_expr.return_type(), right_expr.return_type());
                 left_expr.constant_calculation()?;
-                right_expr.constant_calculation()?;
+
+                if matches!(op, BinaryOperator::And | BinaryOperator::Or) {
+                    if let ScalarExpression::Constant(left_val) = left_expr.as_ref() {
+                        if let (BinaryOperator::And, DataValue::Boolean(Some(false)))
+                        | (BinaryOperator::Or, DataValue::Boolean(Some(true))) =
+                            (*op, left_val.as_ref())
+                        {
+                            let value = left_val.clone();
+                            let _ = mem::replace(self, ScalarExpression::Constant(value));
+                            return Ok(());
+                        }
+                    }
+                    right_expr.guarded_constant_calculation();
+                } else {
+                    right_expr.constant_calculation()?;
+                }
 
                 if let (
                     ScalarExpression::Constant(left_val),
                     ScalarExpression::Constant(right_val),
                 ) = (left_expr.as_mut(), right_expr.as_mut())
                 {
-                    let evaluator = EvaluatorFactory::binary_create(ty, *op)?;
+                    let value = if let Some((_, evaluator)) =
+                        EvaluatorFactory::interval_binary_create(op, &left_ty, &right_ty)
+                    {
+                        evaluator.checked_binary_eval(left_val, right_val)?
+                    } else {
+                        let ty = binary_operand_type(op, &left_ty, &right_ty)?;
+                        let evaluator = EvaluatorFactory::binary_create(ty.clone(), *op)?;
 
-                    if left_val.logical_type() != ty {
-                        *left_val = Arc::new(DataValue::clone(left_val).cast(&ty)?);
-                    }
-                    if right_val.logical_type() != ty {
-                        *right_val = Arc::new(DataValue::clone(right_val).cast(&ty)?);
-                    }
-                    let value = evaluator.0.binary_eval(left_val, right_val);
+                        if left_val.logical_type() != ty {
+                            *left_val = Arc::new(DataValue::clone(left_val).cast(&ty)?);
+                        }
+                        if right_val.logical_type() != ty {
+                            *right_val = Arc::new(DataValue::clone(right_val).cast(&ty)?);
+                        }
+                        evaluator.checked_binary_eval(left_val, right_val)?
+                    };
                     let _ = mem::replace(self, ScalarExpression::Constant(Arc::new(value)));
                 }
             }
             ScalarExpression::Alias { expr, .. } => expr.constant_calculation()?,
-            ScalarExpression::TypeCast { expr, .. } => expr.constant_calculation()?,
+            ScalarExpression::TypeCast { expr, ty, is_try } => {
+                expr.constant_calculation()?;
+
+                // e.g. `TRY_CAST('abc' AS INT)` is folded to NULL, while `CAST` fails when run
+                if let (true, ScalarExpression::Constant(value)) = (*is_try, expr.as_ref()) {
+                    let value = DataValue::clone(value).cast_or_null(ty);
+                    let _ = mem::replace(self, ScalarExpression::Constant(Arc::new(value)));
+                }
+            }
+            ScalarExpression::FieldAccess { expr, .. } => expr.constant_calculation()?,
+            ScalarExpression::Collate { expr, .. } => expr.constant_calculation()?,
             ScalarExpression::IsNull { expr, .. } => expr.constant_calculation()?,
-            ScalarExpression::AggCall { args, .. } => {
-                for expr in args {
+            ScalarExpression::AggCall { args, filter, .. } => {
+                for expr in args.iter_mut().chain(filter.as_deref_mut()) {
+                    expr.constant_calculation()?;
+                }
+            }
+            ScalarExpression::WindowCall {
+                args,
+                partition_by,
+                order_by,
+                ..
+            } => {
+                for expr in args
+                    .iter_mut()
+                    .chain(partition_by.iter_mut())
+                    .chain(order_by.iter_mut().map(|field| &mut field.expr))
+                {
                     expr.constant_calculation()?;
                 }
             }
@@ -344,6 +445,22 @@ impl ScalarExpression {
                     expr.constant_calculation()?;
                 }
             }
+            ScalarExpression::Greatest { exprs, .. } | ScalarExpression::Least { exprs, .. } => {
+                for expr in exprs.iter_mut() {
+                    expr.constant_calculation()?;
+                }
+                if exprs
+                    .iter()
+                    .all(|expr| matches!(expr, ScalarExpression::Constant(_)))
+                {
+                    let tuple = Tuple {
+                        id: None,
+                        values: Vec::new(),
+                    };
+                    let value = self.eval(&tuple, &[])?;
+                    let _ = mem::replace(self, ScalarExpression::Constant(value));
+                }
+            }
             ScalarExpression::If {
                 condition,
                 left_expr,
@@ -351,8 +468,8 @@ impl ScalarExpression {
                 ..
             } => {
                 condition.constant_calculation()?;
-                left_expr.constant_calculation()?;
-                right_expr.constant_calculation()?;
+                left_expr.guarded_constant_calculation();
+                right_expr.guarded_constant_calculation();
             }
             ScalarExpression::IfNull {
                 left_expr,
@@ -376,16 +493,37 @@ impl ScalarExpression {
                 if let Some(operand_expr) = operand_expr {
                     operand_expr.constant_calculation()?;
                 }
-                for (left_expr, right_expr) in expr_pairs {
-                    left_expr.constant_calculation()?;
-                    right_expr.constant_calculation()?;
+                for (i, (left_expr, right_expr)) in expr_pairs.iter_mut().enumerate() {
+                    // Tips: only the first condition is always evaluated
+                    if i == 0 {
+                        left_expr.constant_calculation()?;
+                    } else {
+                        left_expr.guarded_constant_calculation();
+                    }
+                    right_expr.guarded_constant_calculation();
                 }
                 if let Some(else_expr) = else_expr {
-                    else_expr.constant_calculation()?;
+                    else_expr.guarded_constant_calculation();
                 }
             }
-            ScalarExpression::ScalaFunction(ScalarFunction { args, .. })
-            | ScalarExpression::TableFunction(TableFunction { args, .. }) => {
+            ScalarExpression::ScalaFunction(ScalarFunction { args, inner }) => {
+                for expr in args.iter_mut() {
+                    expr.constant_calculation()?;
+                }
+                if inner.is_deterministic()
+                    && args
+                        .iter()
+                        .all(|expr| matches!(expr, ScalarExpression::Constant(_)))
+                {
+                    let tuple = Tuple {
+                        id: None,
+                        values: Vec::new(),
+                    };
+                    let value = self.eval(&tuple, &[])?;
+                    let _ = mem::replace(self, ScalarExpression::Constant(value));
+                }
+            }
+            ScalarExpression::TableFunction(TableFunction { args, .. }) => {
                 for expr in args {
                     expr.constant_calculation()?;
                 }
@@ -399,6 +537,13 @@ impl ScalarExpression {
         Ok(())
     }
 
+    /// Like [`ScalarExpression::constant_calculation`] for an expression that is not always
+    /// evaluated, e.g. a branch of `CASE`, so a constant that fails to be calculated is left to
+    /// fail only if it is evaluated.
+    fn guarded_constant_calculation(&mut self) {
+        let _ = self.constant_calculation();
+    }
+
     // Tips: Indirect expressions like `ScalarExpression:：Alias` will be lost
     fn _simplify(&mut self, replaces: &mut Vec<Replace>) -> Result<(), DatabaseError> {
         match self {
@@ -409,11 +554,21 @@ impl ScalarExpression {
                 ty,
                 ..
             } => {
+                if let Some(range_expr) = Self::unpack_date_trunc(left_expr, right_expr, *op)? {
+                    let _ = mem::replace(self, range_expr);
+                    return Ok(());
+                }
                 Self::fix_expr(replaces, left_expr, right_expr, op)?;
 
                 // `(c1 - 1) and (c1 + 2)` cannot fix!
                 Self::fix_expr(replaces, right_expr, left_expr, op)?;
 
+                // Tips: `10 / c1 > 2` is not `c1 < 10 / 2` if `c1` is negative, and the
+                // quotient of two integers is a `DOUBLE` unlike either of them
+                if matches!(op, BinaryOperator::Multiply | BinaryOperator::Divide) {
+                    replaces.clear();
+                    return Ok(());
+                }
                 if Self::is_arithmetic(op) {
                     match (left_expr.unpack_col(false), right_expr.unpack_col(false)) {
                         (Some(col), None) => {
@@ -421,7 +576,7 @@ impl ScalarExpression {
                                 column_expr: ScalarExpression::ColumnRef(col),
                                 val_expr: mem::replace(right_expr, ScalarExpression::Empty),
                                 op: *op,
-                                ty: *ty,
+                                ty: ty.clone(),
                                 is_column_left: true,
                             }));
                         }
@@ -430,7 +585,7 @@ impl ScalarExpression {
                                 column_expr: ScalarExpression::ColumnRef(col),
                                 val_expr: mem::replace(left_expr, ScalarExpression::Empty),
                                 op: *op,
-                                ty: *ty,
+                                ty: ty.clone(),
                                 is_column_left: false,
                             }));
                         }
@@ -445,7 +600,7 @@ impl ScalarExpression {
                                         column_expr: ScalarExpression::ColumnRef(col),
                                         val_expr: mem::replace(right_expr, ScalarExpression::Empty),
                                         op: *op,
-                                        ty: *ty,
+                                        ty: ty.clone(),
                                         is_column_left: true,
                                     }));
                                 }
@@ -454,7 +609,7 @@ impl ScalarExpression {
                                         column_expr: ScalarExpression::ColumnRef(col),
                                         val_expr: mem::replace(left_expr, ScalarExpression::Empty),
                                         op: *op,
-                                        ty: *ty,
+                                        ty: ty.clone(),
                                         is_column_left: false,
                                     }));
                                 }
@@ -466,8 +621,8 @@ impl ScalarExpression {
                 }
             }
             ScalarExpression::Alias { expr, .. } => expr._simplify(replaces)?,
-            ScalarExpression::TypeCast { expr, .. } => {
-                if let Some(val) = expr.unpack_val() {
+            ScalarExpression::TypeCast { .. } => {
+                if let Some(val) = self.unpack_val() {
                     let _ = mem::replace(self, ScalarExpression::Constant(val));
                 }
             }
@@ -490,11 +645,11 @@ impl ScalarExpression {
             } => {
                 if let Some(value) = expr.unpack_val() {
                     let value = if let Some(evaluator) = evaluator {
-                        evaluator.0.unary_eval(&value)
+                        evaluator.0.checked_unary_eval(&value)?
                     } else {
-                        EvaluatorFactory::unary_create(*ty, *op)?
+                        EvaluatorFactory::unary_create(ty.clone(), *op)?
                             .0
-                            .unary_eval(&value)
+                            .checked_unary_eval(&value)?
                     };
                     let new_expr = ScalarExpression::Constant(Arc::new(value));
                     let _ = mem::replace(self, new_expr);
@@ -502,7 +657,7 @@ impl ScalarExpression {
                     replaces.push(Replace::Unary(ReplaceUnary {
                         child_expr: expr.as_ref().clone(),
                         op: *op,
-                        ty: *ty,
+                        ty: ty.clone(),
                     }));
                 }
             }
@@ -600,6 +755,87 @@ impl ScalarExpression {
         )
     }
 
+    /// Rewrite a comparison on `date_trunc(unit, c1)` into a range on `c1`, so that an index on
+    /// `c1` can be used, e.g.
+    /// `date_trunc('day', c1) = '2024-01-01'` -> `c1 >= '2024-01-01' and c1 < '2024-01-02'`
+    fn unpack_date_trunc(
+        left_expr: &ScalarExpression,
+        right_expr: &ScalarExpression,
+        op: BinaryOperator,
+    ) -> Result<Option<ScalarExpression>, DatabaseError> {
+        let (function, val, op) = match (left_expr, right_expr) {
+            (ScalarExpression::ScalaFunction(function), expr) => (function, expr.unpack_val(), op),
+            (expr, ScalarExpression::ScalaFunction(function)) => {
+                let op = match op {
+                    BinaryOperator::Gt => BinaryOperator::Lt,
+                    BinaryOperator::GtEq => BinaryOperator::LtEq,
+                    BinaryOperator::Lt => BinaryOperator::Gt,
+                    BinaryOperator::LtEq => BinaryOperator::GtEq,
+                    source_op => source_op,
+                };
+                (function, expr.unpack_val(), op)
+            }
+            _ => return Ok(None),
+        };
+        let Some(val) = val else {
+            return Ok(None);
+        };
+        // Tips: the range of a `DATE` column is not bounded by `DATETIME` values
+        if function.inner.summary().name != DATE_TRUNC
+            || function.inner.return_type() != &LogicalType::DateTime
+        {
+            return Ok(None);
+        }
+        let (Some(unit), Some(ScalarExpression::ColumnRef(column))) =
+            (function.args[0].unpack_val(), function.args.get(1))
+        else {
+            return Ok(None);
+        };
+        let Some(unit) = unit.utf8() else {
+            return Ok(None);
+        };
+        let unit = DateTruncUnit::try_from(unit.as_str())?;
+        let Some(datetime) = DataValue::clone(&val)
+            .cast(&LogicalType::DateTime)?
+            .datetime()
+        else {
+            return Ok(None);
+        };
+        let start = unit.trunc(datetime);
+        let Some(next) = unit.next(start) else {
+            return Ok(None);
+        };
+        // the first `unit` that starts at or after `datetime`
+        let ceil = if start == datetime { start } else { next };
+
+        let column_expr = Box::new(ScalarExpression::ColumnRef(column.clone()));
+        let comparison = |op: BinaryOperator, datetime: NaiveDateTime| ScalarExpression::Binary {
+            op,
+            left_expr: column_expr.clone(),
+            right_expr: Box::new(ScalarExpression::Constant(Arc::new(DataValue::Date64(
+                Some(datetime.and_utc().timestamp()),
+            )))),
+            evaluator: None,
+            ty: LogicalType::Boolean,
+        };
+
+        Ok(Some(match op {
+            // Tips: `date_trunc` never equals a value that is not the start of a `unit`
+            BinaryOperator::Eq if start == datetime => ScalarExpression::Binary {
+                op: BinaryOperator::And,
+                left_expr: Box::new(comparison(BinaryOperator::GtEq, start)),
+                right_expr: Box::new(comparison(BinaryOperator::Lt, next)),
+                evaluator: None,
+                ty: LogicalType::Boolean,
+            },
+            BinaryOperator::Lt => comparison(BinaryOperator::Lt, ceil),
+            BinaryOperator::LtEq => comparison(BinaryOperator::Lt, next),
+            BinaryOperator::Gt => comparison(BinaryOperator::GtEq, next),
+            BinaryOperator::GtEq => comparison(BinaryOperator::GtEq, ceil),
+            _ => return Ok(None),
+        }))
+    }
+
     fn fix_expr(
         replaces: &mut Vec<Replace>,
         left_expr: &mut Box<ScalarExpression>,
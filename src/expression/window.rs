@@ -0,0 +1,71 @@
+use crate::expression::agg::AggKind;
+use crate::serdes::Serialization;
+use std::fmt::Formatter;
+use std::io::{Read, Write};
+use std::{fmt, io};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WindowKind {
+    RowNumber,
+    Rank,
+    DenseRank,
+    /// an aggregate function computed over the window frame of each row, e.g. `SUM(c1) OVER (...)`
+    Aggregate(AggKind),
+}
+
+impl WindowKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "row_number" => Some(WindowKind::RowNumber),
+            "rank" => Some(WindowKind::Rank),
+            "dense_rank" => Some(WindowKind::DenseRank),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for WindowKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            WindowKind::RowNumber => write!(f, "row_number"),
+            WindowKind::Rank => write!(f, "rank"),
+            WindowKind::DenseRank => write!(f, "dense_rank"),
+            WindowKind::Aggregate(AggKind::Custom(function)) => {
+                write!(f, "{}", function.summary().name)
+            }
+            WindowKind::Aggregate(kind) => write!(f, "{:?}", kind),
+        }
+    }
+}
+
+impl Serialization for WindowKind {
+    type Error = io::Error;
+
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), Self::Error> {
+        let type_id = match self {
+            WindowKind::RowNumber => 0u8,
+            WindowKind::Rank => 1u8,
+            WindowKind::DenseRank => 2u8,
+            WindowKind::Aggregate(_) => 3u8,
+        };
+        writer.write_all(&[type_id])?;
+
+        if let WindowKind::Aggregate(kind) = self {
+            kind.encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, Self::Error> {
+        let mut type_bytes = [0u8; 1];
+        reader.read_exact(&mut type_bytes)?;
+
+        Ok(match type_bytes[0] {
+            0 => WindowKind::RowNumber,
+            1 => WindowKind::Rank,
+            2 => WindowKind::DenseRank,
+            3 => WindowKind::Aggregate(AggKind::decode(reader)?),
+            _ => unreachable!(),
+        })
+    }
+}
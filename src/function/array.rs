@@ -0,0 +1,346 @@
+use crate::binder::expr::binary_operand_type;
+use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef, TableCatalog};
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::{ScalarFunction, ScalarFunctionImpl};
+use crate::expression::function::table::TableFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::{BinaryOperator, ScalarExpression};
+use crate::types::evaluator::{BinaryEvaluatorBox, EvaluatorFactory};
+use crate::types::tuple::{SchemaRef, Tuple};
+use crate::types::value::DataValue;
+use crate::types::{ArrayType, LogicalType};
+use ahash::HashMap;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde::Serialize;
+use std::mem;
+use std::sync::Arc;
+
+pub(crate) const UNNEST: &str = "unnest";
+
+lazy_static! {
+    static ref UNNEST_TABLES: Mutex<HashMap<LogicalType, &'static TableCatalog>> =
+        Mutex::new(HashMap::default());
+}
+
+/// Casts each of `args` to the type of its parameter, a constant is cast here so that the call
+/// with constants is folded.
+fn cast_args(
+    args: &mut [ScalarExpression],
+    param_types: &[LogicalType],
+) -> Result<(), DatabaseError> {
+    for (arg, ty) in args.iter_mut().zip(param_types.iter()) {
+        if &arg.return_type() == ty {
+            continue;
+        }
+        *arg = match arg.unpack_val() {
+            Some(value) => ScalarExpression::Constant(Arc::new(DataValue::clone(&value).cast(ty)?)),
+            None => ScalarExpression::TypeCast {
+                expr: Box::new(mem::replace(arg, ScalarExpression::Empty)),
+                ty: ty.clone(),
+                is_try: false,
+            },
+        };
+    }
+    Ok(())
+}
+
+/// `array[index]`, the element at `index` counted from 1, or `NULL` if the array or `index` is
+/// `NULL` or `index` is out of the array.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ArraySubscript {
+    summary: FunctionSummary,
+    element_type: LogicalType,
+}
+
+impl ArraySubscript {
+    /// Casts `index` to a `BIGINT`.
+    pub(crate) fn bind(
+        array: ScalarExpression,
+        index: ScalarExpression,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let (array_type, index_type) = (array.return_type(), index.return_type());
+        let LogicalType::Array(ty) = &array_type else {
+            return Err(DatabaseError::NotFound(
+                "function",
+                format!("{}[{}]", array_type, index_type),
+            ));
+        };
+        let element_type = ty.element_type().clone();
+        if !(index_type.is_signed_numeric()
+            || index_type.is_unsigned_numeric()
+            || index_type == LogicalType::SqlNull)
+        {
+            return Err(DatabaseError::NotFound(
+                "function",
+                format!("{}[{}]", array_type, index_type),
+            ));
+        }
+        let param_types = vec![array_type, LogicalType::Bigint];
+        let mut args = vec![array, index];
+        cast_args(&mut args, &param_types)?;
+
+        Ok(ScalarExpression::ScalaFunction(ScalarFunction {
+            args,
+            inner: Arc::new(ArraySubscript {
+                summary: FunctionSummary {
+                    name: "array_subscript".to_string(),
+                    arg_types: param_types,
+                },
+                element_type,
+            }),
+        }))
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for ArraySubscript {
+    fn eval(
+        &self,
+        args: &[ScalarExpression],
+        tuple: &Tuple,
+        schema: &[ColumnRef],
+    ) -> Result<DataValue, DatabaseError> {
+        let array = args[0].eval(tuple, schema)?;
+        let index = args[1].eval(tuple, schema)?;
+
+        if let (
+            DataValue::Array {
+                values: Some(values),
+                ..
+            },
+            DataValue::Int64(Some(index)),
+        ) = (array.as_ref(), index.as_ref())
+        {
+            if let Some(value) = index
+                .checked_sub(1)
+                .and_then(|i| usize::try_from(i).ok())
+                .and_then(|i| values.get(i))
+            {
+                return Ok(DataValue::clone(value));
+            }
+        }
+        Ok(DataValue::none(&self.element_type))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &self.element_type
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+/// `value op ANY(array)` and `value op ALL(array)`, which compare `value` with each element of
+/// `array` like `value op element`.
+///
+/// `ANY` is true if any of the comparisons is true, and else `NULL` if any of them is `NULL`.
+/// `ALL` is false if any of them is false, and else `NULL` if any of them is `NULL`. Both are
+/// `NULL` if `array` is `NULL`.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ArrayCompare {
+    summary: FunctionSummary,
+    is_all: bool,
+    evaluator: BinaryEvaluatorBox,
+}
+
+impl ArrayCompare {
+    /// Casts `value` and the elements of `array` to the type they are compared as.
+    pub(crate) fn bind(
+        op: BinaryOperator,
+        is_all: bool,
+        value: ScalarExpression,
+        array: ScalarExpression,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        let quantifier = if is_all { "all" } else { "any" };
+        let (value_type, array_type) = (value.return_type(), array.return_type());
+
+        if !matches!(
+            op,
+            BinaryOperator::Eq
+                | BinaryOperator::NotEq
+                | BinaryOperator::Gt
+                | BinaryOperator::GtEq
+                | BinaryOperator::Lt
+                | BinaryOperator::LtEq
+        ) {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "{} {}(array)",
+                op, quantifier
+            )));
+        }
+        let element_type = match array_type {
+            LogicalType::Array(ty) => ty.element_type().clone(),
+            LogicalType::SqlNull => value_type.clone(),
+            _ => {
+                return Err(DatabaseError::NotFound(
+                    "function",
+                    format!("{} {} {}({})", value_type, op, quantifier, array_type),
+                ))
+            }
+        };
+        let ty = binary_operand_type(&op, &value_type, &element_type)?;
+        let param_types = vec![ty.clone(), LogicalType::Array(ArrayType::new(ty.clone()))];
+        let mut args = vec![value, array];
+        cast_args(&mut args, &param_types)?;
+
+        Ok(ScalarExpression::ScalaFunction(ScalarFunction {
+            args,
+            inner: Arc::new(ArrayCompare {
+                summary: FunctionSummary {
+                    name: format!("{} {}", op, quantifier),
+                    arg_types: param_types,
+                },
+                is_all,
+                evaluator: EvaluatorFactory::binary_create(ty, op)?,
+            }),
+        }))
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for ArrayCompare {
+    fn eval(
+        &self,
+        args: &[ScalarExpression],
+        tuple: &Tuple,
+        schema: &[ColumnRef],
+    ) -> Result<DataValue, DatabaseError> {
+        let value = args[0].eval(tuple, schema)?;
+        let array = args[1].eval(tuple, schema)?;
+        let DataValue::Array {
+            values: Some(values),
+            ..
+        } = array.as_ref()
+        else {
+            return Ok(DataValue::Boolean(None));
+        };
+        let mut has_null = false;
+
+        for element in values.iter() {
+            match self.evaluator.checked_binary_eval(&value, element)? {
+                DataValue::Boolean(Some(result)) if result != self.is_all => {
+                    return Ok(DataValue::Boolean(Some(result)))
+                }
+                DataValue::Boolean(Some(_)) => (),
+                _ => has_null = true,
+            }
+        }
+        Ok(DataValue::Boolean((!has_null).then_some(self.is_all)))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Boolean
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+/// `unnest(array)`, a row of the column `unnest` for each element of `array`, and no rows if
+/// `array` is `NULL`.
+///
+/// It is looked up by [`Unnest::resolve`] for any type of array, and its table is kept for each
+/// type of the elements.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Unnest {
+    summary: FunctionSummary,
+}
+
+impl Unnest {
+    pub(crate) fn resolve(
+        summary: &FunctionSummary,
+    ) -> Result<Option<Arc<dyn TableFunctionImpl>>, DatabaseError> {
+        let (UNNEST, [LogicalType::Array(ty)]) =
+            (summary.name.as_str(), summary.arg_types.as_slice())
+        else {
+            return Ok(None);
+        };
+        Self::table_of(ty.element_type())?;
+
+        Ok(Some(Arc::new(Unnest {
+            summary: summary.clone(),
+        })))
+    }
+
+    fn table_of(element_type: &LogicalType) -> Result<&'static TableCatalog, DatabaseError> {
+        let mut tables = UNNEST_TABLES.lock();
+
+        if let Some(table) = tables.get(element_type) {
+            return Ok(table);
+        }
+        let table: &'static TableCatalog = Box::leak(Box::new(TableCatalog::new(
+            Arc::new(UNNEST.to_string()),
+            vec![ColumnCatalog::new(
+                UNNEST.to_string(),
+                true,
+                ColumnDesc::new(element_type.clone(), false, false, None)?,
+            )],
+        )?));
+        tables.insert(element_type.clone(), table);
+
+        Ok(table)
+    }
+}
+
+#[typetag::serde]
+impl TableFunctionImpl for Unnest {
+    fn eval(
+        &self,
+        args: &[ScalarExpression],
+    ) -> Result<Box<dyn Iterator<Item = Result<Tuple, DatabaseError>>>, DatabaseError> {
+        let tuple = Tuple {
+            id: None,
+            values: Vec::new(),
+        };
+        let values = match args[0].eval(&tuple, &[])?.as_ref() {
+            DataValue::Array {
+                values: Some(values),
+                ..
+            } => values.clone(),
+            _ => Vec::new(),
+        };
+
+        Ok(Box::new(values.into_iter().map(|value| {
+            Ok(Tuple {
+                id: None,
+                values: vec![value],
+            })
+        })))
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn output_schema(&self) -> &SchemaRef {
+        self.table().schema_ref()
+    }
+
+    fn table(&self) -> &'static TableCatalog {
+        let LogicalType::Array(ty) = &self.summary.arg_types[0] else {
+            unreachable!()
+        };
+        Self::table_of(ty.element_type()).expect("the table of `unnest` is created when bound")
+    }
+}
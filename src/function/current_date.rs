@@ -1,5 +1,6 @@
 use crate::catalog::ColumnRef;
 use crate::errors::DatabaseError;
+use crate::execution::statement_time;
 use crate::expression::function::scala::FuncMonotonicity;
 use crate::expression::function::scala::ScalarFunctionImpl;
 use crate::expression::function::FunctionSummary;
@@ -7,7 +8,7 @@ use crate::expression::ScalarExpression;
 use crate::types::tuple::Tuple;
 use crate::types::value::DataValue;
 use crate::types::LogicalType;
-use chrono::{Datelike, Local};
+use chrono::Datelike;
 use serde::Deserialize;
 use serde::Serialize;
 use std::sync::Arc;
@@ -40,7 +41,7 @@ impl ScalarFunctionImpl for CurrentDate {
         _: &Tuple,
         _: &[ColumnRef],
     ) -> Result<DataValue, DatabaseError> {
-        Ok(DataValue::Date32(Some(Local::now().num_days_from_ce())))
+        Ok(DataValue::Date32(Some(statement_time().num_days_from_ce())))
     }
 
     fn monotonicity(&self) -> Option<FuncMonotonicity> {
@@ -0,0 +1,159 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use serde::Deserialize;
+use serde::Serialize;
+use sqlparser::ast::CharLengthUnits;
+use std::sync::Arc;
+
+pub(crate) const DATE_TRUNC: &str = "date_trunc";
+
+/// `date_trunc(unit, timestamp)`, the start of the `unit` that contains `timestamp`, in the type
+/// of `timestamp`, which is either a `DATE` or a `DATETIME`.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct DateTrunc {
+    summary: FunctionSummary,
+}
+
+impl DateTrunc {
+    pub(crate) fn new(ty: LogicalType) -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: DATE_TRUNC.to_string(),
+                arg_types: vec![LogicalType::Varchar(None, CharLengthUnits::Characters), ty],
+            },
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for DateTrunc {
+    fn eval(
+        &self,
+        args: &[ScalarExpression],
+        tuple: &Tuple,
+        schema: &[ColumnRef],
+    ) -> Result<DataValue, DatabaseError> {
+        let unit = args[0].eval(tuple, schema)?;
+        let value = args[1].eval(tuple, schema)?;
+        let datetime = value
+            .datetime()
+            .or_else(|| value.date().map(|date| date.and_time(NaiveTime::MIN)));
+        let (Some(unit), Some(datetime)) = (unit.utf8(), datetime) else {
+            return Ok(DataValue::none(self.return_type()));
+        };
+        let datetime = DateTruncUnit::try_from(unit.as_str())?.trunc(datetime);
+
+        Ok(DataValue::Date64(Some(datetime.and_utc().timestamp())))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        Some(vec![None, Some(true)])
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &self.summary.arg_types[1]
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DateTruncUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+    Decade,
+    Century,
+    Millennium,
+}
+
+impl TryFrom<&str> for DateTruncUnit {
+    type Error = DatabaseError;
+
+    fn try_from(unit: &str) -> Result<Self, Self::Error> {
+        Ok(match unit.to_lowercase().as_str() {
+            "second" => DateTruncUnit::Second,
+            "minute" => DateTruncUnit::Minute,
+            "hour" => DateTruncUnit::Hour,
+            "day" => DateTruncUnit::Day,
+            "week" => DateTruncUnit::Week,
+            "month" => DateTruncUnit::Month,
+            "quarter" => DateTruncUnit::Quarter,
+            "year" => DateTruncUnit::Year,
+            "decade" => DateTruncUnit::Decade,
+            "century" => DateTruncUnit::Century,
+            "millennium" => DateTruncUnit::Millennium,
+            _ => return Err(DatabaseError::NotFound("date_trunc unit", unit.to_string())),
+        })
+    }
+}
+
+impl DateTruncUnit {
+    pub(crate) fn trunc(&self, datetime: NaiveDateTime) -> NaiveDateTime {
+        let date = datetime.date();
+        let time = datetime.time();
+        let midnight = |date: NaiveDate| date.and_time(NaiveTime::MIN);
+        let new_year = |year: i32| midnight(NaiveDate::from_yo_opt(year, 1).unwrap_or(date));
+
+        match self {
+            DateTruncUnit::Second => datetime.with_nanosecond(0).unwrap_or(datetime),
+            DateTruncUnit::Minute => date.and_time(
+                NaiveTime::from_hms_opt(time.hour(), time.minute(), 0).unwrap_or(NaiveTime::MIN),
+            ),
+            DateTruncUnit::Hour => {
+                date.and_time(NaiveTime::from_hms_opt(time.hour(), 0, 0).unwrap_or(NaiveTime::MIN))
+            }
+            DateTruncUnit::Day => midnight(date),
+            // Tips: weeks start on Monday
+            DateTruncUnit::Week => {
+                midnight(date - Duration::days(date.weekday().num_days_from_monday() as i64))
+            }
+            DateTruncUnit::Month => midnight(date.with_day(1).unwrap_or(date)),
+            DateTruncUnit::Quarter => midnight(
+                NaiveDate::from_ymd_opt(date.year(), (date.month0() / 3) * 3 + 1, 1)
+                    .unwrap_or(date),
+            ),
+            DateTruncUnit::Year => midnight(date.with_ordinal(1).unwrap_or(date)),
+            DateTruncUnit::Decade => new_year(date.year() - date.year().rem_euclid(10)),
+            // Tips: centuries and millennia start at the year 1, e.g. `2001-01-01`
+            DateTruncUnit::Century => new_year((date.year() - 1).div_euclid(100) * 100 + 1),
+            DateTruncUnit::Millennium => new_year((date.year() - 1).div_euclid(1000) * 1000 + 1),
+        }
+    }
+
+    /// The start of the `unit` following the one that starts at `start`.
+    pub(crate) fn next(&self, start: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            DateTruncUnit::Second => start.checked_add_signed(Duration::seconds(1)),
+            DateTruncUnit::Minute => start.checked_add_signed(Duration::minutes(1)),
+            DateTruncUnit::Hour => start.checked_add_signed(Duration::hours(1)),
+            DateTruncUnit::Day => start.checked_add_signed(Duration::days(1)),
+            DateTruncUnit::Week => start.checked_add_signed(Duration::weeks(1)),
+            DateTruncUnit::Month => start.checked_add_months(Months::new(1)),
+            DateTruncUnit::Quarter => start.checked_add_months(Months::new(3)),
+            DateTruncUnit::Year => start.checked_add_months(Months::new(12)),
+            DateTruncUnit::Decade => start.checked_add_months(Months::new(12 * 10)),
+            DateTruncUnit::Century => start.checked_add_months(Months::new(12 * 100)),
+            DateTruncUnit::Millennium => start.checked_add_months(Months::new(12 * 1000)),
+        }
+    }
+}
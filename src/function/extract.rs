@@ -0,0 +1,162 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::execution::statement_time_zone;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::{DataValue, Utf8Type};
+use crate::types::LogicalType;
+use chrono::{Datelike, NaiveTime, Timelike};
+use serde::Deserialize;
+use serde::Serialize;
+use sqlparser::ast::{CharLengthUnits, DateTimeField};
+use std::sync::Arc;
+
+pub(crate) const EXTRACT: &str = "extract";
+
+/// `extract(field from timestamp)`, which is only bound from `EXTRACT`, the name of `field` is
+/// its first argument so that the extractions of different fields are told apart.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Extract {
+    field: ExtractField,
+    summary: FunctionSummary,
+}
+
+impl Extract {
+    pub(crate) fn new(field: ExtractField, ty: LogicalType) -> Arc<Self> {
+        Arc::new(Self {
+            field,
+            summary: FunctionSummary {
+                name: EXTRACT.to_string(),
+                arg_types: vec![LogicalType::Varchar(None, CharLengthUnits::Characters), ty],
+            },
+        })
+    }
+
+    pub(crate) fn field_expr(field: ExtractField) -> ScalarExpression {
+        ScalarExpression::Constant(Arc::new(DataValue::Utf8 {
+            value: Some(field.name().to_string()),
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        }))
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for Extract {
+    fn eval(
+        &self,
+        args: &[ScalarExpression],
+        tuple: &Tuple,
+        schema: &[ColumnRef],
+    ) -> Result<DataValue, DatabaseError> {
+        let value = args[1].eval(tuple, schema)?;
+        // Tips: the fields of a timestamp with time zone are those at the session time zone
+        let time_zone = statement_time_zone();
+        let Some(datetime) = value
+            .datetime()
+            .or_else(|| value.date().map(|date| date.and_time(NaiveTime::MIN)))
+            .or_else(|| {
+                value
+                    .timestamp_tz()
+                    .map(|datetime| datetime.with_timezone(&time_zone).naive_local())
+            })
+        else {
+            return Ok(DataValue::none(self.return_type()));
+        };
+
+        Ok(match self.field {
+            ExtractField::Year => DataValue::Int32(Some(datetime.year())),
+            ExtractField::Month => DataValue::Int32(Some(datetime.month() as i32)),
+            ExtractField::Day => DataValue::Int32(Some(datetime.day() as i32)),
+            ExtractField::Hour => DataValue::Int32(Some(datetime.hour() as i32)),
+            ExtractField::Minute => DataValue::Int32(Some(datetime.minute() as i32)),
+            ExtractField::Second => DataValue::Int32(Some(datetime.second() as i32)),
+            ExtractField::Dow => {
+                DataValue::Int32(Some(datetime.weekday().num_days_from_sunday() as i32))
+            }
+            ExtractField::Epoch => DataValue::Int64(Some(value.timestamp_tz().map_or_else(
+                || datetime.and_utc().timestamp(),
+                |instant| instant.timestamp(),
+            ))),
+        })
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        Some(vec![
+            None,
+            matches!(self.field, ExtractField::Year | ExtractField::Epoch).then_some(true),
+        ])
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        // Tips: the temporal types are in seconds, so neither `SECOND` nor `EPOCH` has fractions
+        match self.field {
+            ExtractField::Epoch => &LogicalType::Bigint,
+            _ => &LogicalType::Integer,
+        }
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum ExtractField {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    /// the day of the week, from 0 for Sunday to 6 for Saturday
+    Dow,
+    /// the seconds since `1970-01-01 00:00:00`, or since the UNIX epoch for a timestamp with
+    /// time zone
+    Epoch,
+}
+
+impl TryFrom<&DateTimeField> for ExtractField {
+    type Error = DatabaseError;
+
+    fn try_from(field: &DateTimeField) -> Result<Self, Self::Error> {
+        Ok(match field {
+            DateTimeField::Year => ExtractField::Year,
+            DateTimeField::Month => ExtractField::Month,
+            DateTimeField::Day => ExtractField::Day,
+            DateTimeField::Hour => ExtractField::Hour,
+            DateTimeField::Minute => ExtractField::Minute,
+            DateTimeField::Second => ExtractField::Second,
+            DateTimeField::Dow => ExtractField::Dow,
+            DateTimeField::Epoch => ExtractField::Epoch,
+            field => {
+                return Err(DatabaseError::UnsupportedStmt(format!(
+                    "extract field: {}",
+                    field
+                )))
+            }
+        })
+    }
+}
+
+impl ExtractField {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            ExtractField::Year => "year",
+            ExtractField::Month => "month",
+            ExtractField::Day => "day",
+            ExtractField::Hour => "hour",
+            ExtractField::Minute => "minute",
+            ExtractField::Second => "second",
+            ExtractField::Dow => "dow",
+            ExtractField::Epoch => "epoch",
+        }
+    }
+}
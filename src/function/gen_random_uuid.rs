@@ -0,0 +1,58 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::execution::mark_volatile;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::uuid::Uuid;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use serde::Deserialize;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// `gen_random_uuid()`, a random UUID of version 4 for each call, which is never folded into
+/// a constant nor kept in the result cache.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct GenRandomUuid {
+    summary: FunctionSummary,
+}
+
+impl GenRandomUuid {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "gen_random_uuid".to_string(),
+                arg_types: Vec::new(),
+            },
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for GenRandomUuid {
+    fn eval(
+        &self,
+        _: &[ScalarExpression],
+        _: &Tuple,
+        _: &[ColumnRef],
+    ) -> Result<DataValue, DatabaseError> {
+        mark_volatile();
+
+        Ok(DataValue::Uuid(Some(Uuid::new_v4())))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Uuid
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
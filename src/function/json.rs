@@ -0,0 +1,229 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::{ScalarFunction, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::json::{parse, parse_path, PathStep};
+use crate::types::tuple::Tuple;
+use crate::types::value::{DataValue, Utf8Type, ValueRef};
+use crate::types::LogicalType;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use sqlparser::ast::CharLengthUnits;
+use std::mem;
+use std::sync::Arc;
+
+pub(crate) const JSON_EXTRACT: &str = "json_extract";
+
+const VARCHAR: LogicalType = LogicalType::Varchar(None, CharLengthUnits::Characters);
+
+/// `json -> key`, `json ->> key` and `json_extract(json, path)`, which return `NULL` if any
+/// argument is `NULL` or nothing is found at `key` or `path`.
+///
+/// They are bound by [`JsonExtract::bind`], a string is parsed into the JSON to extract from.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct JsonExtract {
+    kind: JsonExtractKind,
+    summary: FunctionSummary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum JsonExtractKind {
+    /// `json -> key` or `json -> index`, the member of an object or the element of an array,
+    /// counted from the end if `index` is negative
+    Arrow,
+    /// `json ->> key` or `json ->> index`, like `->` but a JSON string is unquoted and the JSON
+    /// `null` is `NULL`
+    LongArrow,
+    /// `json_extract(json, path)`, see [`parse_path`]
+    Path,
+}
+
+impl JsonExtractKind {
+    fn name(&self) -> &'static str {
+        match self {
+            JsonExtractKind::Arrow => "json_get",
+            JsonExtractKind::LongArrow => "json_get_text",
+            JsonExtractKind::Path => JSON_EXTRACT,
+        }
+    }
+}
+
+/// The keys of `json -> key` and `json ->> key` chained on a column, e.g. `-> 'a' ->> 'b'` of
+/// `data -> 'a' ->> 'b'`, by which a JSON path index is built, see [`json_path_expr`].
+pub(crate) type JsonPath = Vec<(JsonExtractKind, ValueRef)>;
+
+/// The expression of the JSON path chained on the column.
+pub(crate) fn json_path_expr(
+    column: ColumnRef,
+    path: &[(JsonExtractKind, ValueRef)],
+) -> Result<ScalarExpression, DatabaseError> {
+    path.iter()
+        .try_fold(ScalarExpression::ColumnRef(column), |expr, (kind, key)| {
+            JsonExtract::bind(*kind, vec![expr, ScalarExpression::Constant(key.clone())])
+        })
+}
+
+/// The column and the JSON path the expression is chained by, `None` if it is not
+/// [`json_path_expr`] of a column, e.g. a key which is not a constant.
+pub(crate) fn unpack_json_path(expr: &ScalarExpression) -> Option<(ColumnRef, JsonPath)> {
+    let mut path = Vec::new();
+    let mut next = expr;
+
+    let column = loop {
+        match next {
+            ScalarExpression::ScalaFunction(ScalarFunction { args, inner }) if args.len() == 2 => {
+                let kind = [JsonExtractKind::Arrow, JsonExtractKind::LongArrow]
+                    .into_iter()
+                    .find(|kind| kind.name() == inner.summary().name)?;
+                path.push((kind, args[1].unpack_val()?));
+                next = &args[0];
+            }
+            ScalarExpression::TypeCast { expr, .. } => next = expr,
+            ScalarExpression::ColumnRef(column) => break column.clone(),
+            _ => return None,
+        }
+    };
+    path.reverse();
+
+    // e.g. a function of the users named like the operators
+    (!path.is_empty() && &json_path_expr(column.clone(), &path).ok()? == expr)
+        .then_some((column, path))
+}
+
+impl JsonExtract {
+    /// Casts the first of `args` to `JSON` and the second to a `VARCHAR`, or an `INTEGER` index
+    /// for `->` and `->>`. A literal path is checked here, or else when it is evaluated.
+    pub(crate) fn bind(
+        kind: JsonExtractKind,
+        mut args: Vec<ScalarExpression>,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        if args.len() != 2 {
+            return Err(DatabaseError::InvalidParameter(
+                format!("number of {}() parameters", kind.name()),
+                args.len().to_string(),
+            ));
+        }
+        let (json_type, key_type) = (args[0].return_type(), args[1].return_type());
+        let not_found = || {
+            DatabaseError::NotFound(
+                "function",
+                format!("{}({}, {})", kind.name(), json_type, key_type),
+            )
+        };
+
+        if !matches!(
+            json_type,
+            LogicalType::Json
+                | LogicalType::SqlNull
+                | LogicalType::Char(..)
+                | LogicalType::Varchar(..)
+        ) {
+            return Err(not_found());
+        }
+        let key_type = match key_type {
+            LogicalType::Char(..) | LogicalType::Varchar(..) | LogicalType::SqlNull => VARCHAR,
+            ty if kind != JsonExtractKind::Path
+                && (ty.is_signed_numeric() || ty.is_unsigned_numeric()) =>
+            {
+                LogicalType::Integer
+            }
+            _ => return Err(not_found()),
+        };
+        let param_types = vec![LogicalType::Json, key_type];
+
+        for (arg, ty) in args.iter_mut().zip(param_types.iter()) {
+            if &arg.return_type() == ty {
+                continue;
+            }
+            *arg = match arg.unpack_val() {
+                Some(value) => {
+                    ScalarExpression::Constant(Arc::new(DataValue::clone(&value).cast(ty)?))
+                }
+                None => ScalarExpression::TypeCast {
+                    expr: Box::new(mem::replace(arg, ScalarExpression::Empty)),
+                    ty: ty.clone(),
+                    is_try: false,
+                },
+            };
+        }
+        if kind == JsonExtractKind::Path {
+            if let Some(path) = args[1].unpack_val().and_then(|value| value.utf8()) {
+                parse_path(&path)?;
+            }
+        }
+        Ok(ScalarExpression::ScalaFunction(ScalarFunction {
+            args,
+            inner: Arc::new(JsonExtract {
+                kind,
+                summary: FunctionSummary {
+                    name: kind.name().to_string(),
+                    arg_types: param_types,
+                },
+            }),
+        }))
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for JsonExtract {
+    fn eval(
+        &self,
+        args: &[ScalarExpression],
+        tuple: &Tuple,
+        schema: &[ColumnRef],
+    ) -> Result<DataValue, DatabaseError> {
+        let json = args[0].eval(tuple, schema)?;
+        let DataValue::Json(Some(json)) = json.as_ref() else {
+            return Ok(DataValue::none(self.return_type()));
+        };
+        let steps = match args[1].eval(tuple, schema)?.as_ref() {
+            DataValue::Int32(Some(i)) => vec![PathStep::Index(*i as i64)],
+            DataValue::Utf8 {
+                value: Some(path), ..
+            } if self.kind == JsonExtractKind::Path => parse_path(path)?,
+            DataValue::Utf8 {
+                value: Some(key), ..
+            } => vec![PathStep::Key(key.clone())],
+            _ => return Ok(DataValue::none(self.return_type())),
+        };
+        let json = parse(json)?;
+        let Some(value) = steps.iter().try_fold(&json, |value, step| step.get(value)) else {
+            return Ok(DataValue::none(self.return_type()));
+        };
+
+        Ok(match (self.kind, value) {
+            (JsonExtractKind::LongArrow, value) => DataValue::Utf8 {
+                value: match value {
+                    Value::Null => None,
+                    Value::String(string) => Some(string.clone()),
+                    value => Some(value.to_string()),
+                },
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            },
+            (_, value) => DataValue::Json(Some(value.to_string())),
+        })
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        match self.kind {
+            JsonExtractKind::LongArrow => &VARCHAR,
+            _ => &LogicalType::Json,
+        }
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,57 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::execution::{last_insert_id, mark_volatile};
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use serde::Deserialize;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// `last_insert_id()`, the first value assigned to an auto-increment column by the last `INSERT`
+/// of the session, see [`last_insert_id`].
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct LastInsertId {
+    summary: FunctionSummary,
+}
+
+impl LastInsertId {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: "last_insert_id".to_string(),
+                arg_types: Vec::new(),
+            },
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for LastInsertId {
+    fn eval(
+        &self,
+        _: &[ScalarExpression],
+        _: &Tuple,
+        _: &[ColumnRef],
+    ) -> Result<DataValue, DatabaseError> {
+        mark_volatile();
+
+        Ok(DataValue::Int64(Some(last_insert_id())))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::Bigint
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
@@ -0,0 +1,462 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::{ScalarFunction, ScalarFunctionImpl};
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::Deserialize;
+use serde::Serialize;
+use std::mem;
+use std::sync::Arc;
+
+/// The built-in math functions, which return `NULL` if any argument is `NULL`.
+///
+/// They are bound by [`MathFunction::bind`] rather than looked up by their parameters, because
+/// most of them return the type of their argument.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct MathFunction {
+    kind: MathFunctionKind,
+    summary: FunctionSummary,
+    return_type: LogicalType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum MathFunctionKind {
+    /// `abs(x)`
+    Abs,
+    /// `ceil(x)` or `ceiling(x)`
+    Ceil,
+    /// `floor(x)`
+    Floor,
+    /// `round(x [, scale])`, half away from zero, to the tens, hundreds... if `scale` is negative
+    Round,
+    /// `mod(x, y)`, whose sign is the sign of `x`
+    Mod,
+    /// `power(x, y)` or `pow(x, y)`
+    Power,
+    /// `sqrt(x)`
+    Sqrt,
+    /// `ln(x)`
+    Ln,
+    /// `log(x)` in base 10, or `log(base, x)`
+    Log,
+}
+
+impl MathFunctionKind {
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "abs" => MathFunctionKind::Abs,
+            "ceil" | "ceiling" => MathFunctionKind::Ceil,
+            "floor" => MathFunctionKind::Floor,
+            "round" => MathFunctionKind::Round,
+            "mod" => MathFunctionKind::Mod,
+            "power" | "pow" => MathFunctionKind::Power,
+            "sqrt" => MathFunctionKind::Sqrt,
+            "ln" => MathFunctionKind::Ln,
+            "log" => MathFunctionKind::Log,
+            _ => return None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            MathFunctionKind::Abs => "abs",
+            MathFunctionKind::Ceil => "ceil",
+            MathFunctionKind::Floor => "floor",
+            MathFunctionKind::Round => "round",
+            MathFunctionKind::Mod => "mod",
+            MathFunctionKind::Power => "power",
+            MathFunctionKind::Sqrt => "sqrt",
+            MathFunctionKind::Ln => "ln",
+            MathFunctionKind::Log => "log",
+        }
+    }
+
+    fn parameters(&self) -> &'static [usize] {
+        match self {
+            MathFunctionKind::Round | MathFunctionKind::Log => &[1, 2],
+            MathFunctionKind::Mod | MathFunctionKind::Power => &[2],
+            _ => &[1],
+        }
+    }
+}
+
+impl MathFunction {
+    /// Casts `args` to the parameters of `kind`:
+    /// - `abs`, `ceil`, `floor` and `round` take and return any number, the scale of `round` is
+    ///   an `INTEGER`
+    /// - `mod` takes and returns the wider type of its two arguments
+    /// - `power`, `sqrt`, `ln` and `log` take and return `DOUBLE`
+    pub(crate) fn bind(
+        kind: MathFunctionKind,
+        mut args: Vec<ScalarExpression>,
+    ) -> Result<ScalarExpression, DatabaseError> {
+        if !kind.parameters().contains(&args.len()) {
+            return Err(DatabaseError::InvalidParameter(
+                format!("number of {}() parameters", kind.name()),
+                args.len().to_string(),
+            ));
+        }
+        let arg_types = args
+            .iter()
+            .map(ScalarExpression::return_type)
+            .collect::<Vec<_>>();
+        let number_type = |ty: &LogicalType| match ty {
+            LogicalType::SqlNull => Ok(LogicalType::Double),
+            ty if ty.is_numeric() || matches!(ty, LogicalType::Decimal(..)) => Ok(ty.clone()),
+            _ => Err(DatabaseError::NotFound(
+                "function",
+                format!("{}({})", kind.name(), ty),
+            )),
+        };
+
+        let param_types = match kind {
+            MathFunctionKind::Abs | MathFunctionKind::Ceil | MathFunctionKind::Floor => {
+                vec![number_type(&arg_types[0])?]
+            }
+            MathFunctionKind::Round => {
+                let mut param_types = vec![number_type(&arg_types[0])?];
+                if let Some(ty) = arg_types.get(1) {
+                    if !(ty.is_signed_numeric()
+                        || ty.is_unsigned_numeric()
+                        || ty == &LogicalType::SqlNull)
+                    {
+                        return Err(DatabaseError::NotFound(
+                            "function",
+                            format!("round({}, {})", arg_types[0], ty),
+                        ));
+                    }
+                    param_types.push(LogicalType::Integer);
+                }
+                param_types
+            }
+            MathFunctionKind::Mod => {
+                let (left, right) = (number_type(&arg_types[0])?, number_type(&arg_types[1])?);
+                let ty = match (left, right) {
+                    (left, right) if left == right => left,
+                    (LogicalType::Decimal(..), ty) | (ty, LogicalType::Decimal(..)) => {
+                        if ty.is_floating_point_numeric() {
+                            LogicalType::Double
+                        } else {
+                            LogicalType::Decimal(None, None)
+                        }
+                    }
+                    (left, right) => LogicalType::max_logical_type(&left, &right)?,
+                };
+                vec![ty.clone(), ty]
+            }
+            MathFunctionKind::Power
+            | MathFunctionKind::Sqrt
+            | MathFunctionKind::Ln
+            | MathFunctionKind::Log => {
+                for ty in arg_types.iter() {
+                    number_type(ty)?;
+                }
+                vec![LogicalType::Double; args.len()]
+            }
+        };
+        let return_type = match kind {
+            // Tips: the scale of a rounded decimal is not the scale of the argument
+            MathFunctionKind::Ceil | MathFunctionKind::Floor | MathFunctionKind::Round
+                if matches!(param_types[0], LogicalType::Decimal(..)) =>
+            {
+                LogicalType::Decimal(None, None)
+            }
+            _ => param_types[0].clone(),
+        };
+
+        for (arg, ty) in args.iter_mut().zip(param_types.iter()) {
+            if &arg.return_type() == ty {
+                continue;
+            }
+            // a constant is cast here so that the call with constants is folded
+            *arg = match arg
+                .unpack_val()
+                .and_then(|value| DataValue::clone(&value).cast(ty).ok())
+            {
+                Some(value) => ScalarExpression::Constant(Arc::new(value)),
+                None => ScalarExpression::TypeCast {
+                    expr: Box::new(mem::replace(arg, ScalarExpression::Empty)),
+                    ty: ty.clone(),
+                    is_try: false,
+                },
+            };
+        }
+        Ok(ScalarExpression::ScalaFunction(ScalarFunction {
+            args,
+            inner: Arc::new(MathFunction {
+                kind,
+                summary: FunctionSummary {
+                    name: kind.name().to_string(),
+                    arg_types: param_types,
+                },
+                return_type,
+            }),
+        }))
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for MathFunction {
+    fn eval(
+        &self,
+        args: &[ScalarExpression],
+        tuple: &Tuple,
+        schema: &[ColumnRef],
+    ) -> Result<DataValue, DatabaseError> {
+        let mut values = Vec::with_capacity(args.len());
+        for arg in args {
+            let value = arg.eval(tuple, schema)?;
+            if value.is_null() {
+                return Ok(DataValue::none(self.return_type()));
+            }
+            values.push(value);
+        }
+        let ty = &self.return_type;
+
+        match self.kind {
+            MathFunctionKind::Abs => Number::from(values[0].as_ref()).abs().into_value(ty),
+            MathFunctionKind::Ceil => Number::from(values[0].as_ref()).ceil().into_value(ty),
+            MathFunctionKind::Floor => Number::from(values[0].as_ref()).floor().into_value(ty),
+            MathFunctionKind::Round => {
+                let scale = values.get(1).and_then(|value| value.i32()).unwrap_or(0);
+                Number::from(values[0].as_ref())
+                    .round(scale)?
+                    .into_value(ty)
+            }
+            MathFunctionKind::Mod => Number::from(values[0].as_ref())
+                .rem(Number::from(values[1].as_ref()))?
+                .into_value(ty),
+            MathFunctionKind::Power => {
+                let (x, y) = (float(&values[0]), float(&values[1]));
+                if x == 0.0 && y < 0.0 {
+                    return Err(DatabaseError::DivisionByZero);
+                }
+                if x < 0.0 && y.fract() != 0.0 {
+                    return Err(DatabaseError::InvalidParameter(
+                        "power exponent of a negative number".to_string(),
+                        y.to_string(),
+                    ));
+                }
+                checked_float(x.powf(y))
+            }
+            MathFunctionKind::Sqrt => {
+                let x = float(&values[0]);
+                if x < 0.0 {
+                    return Err(DatabaseError::InvalidParameter(
+                        "sqrt".to_string(),
+                        x.to_string(),
+                    ));
+                }
+                checked_float(x.sqrt())
+            }
+            MathFunctionKind::Ln => checked_float(positive("ln", float(&values[0]))?.ln()),
+            MathFunctionKind::Log => {
+                let x = positive("log", float(values.last().unwrap()))?;
+                if values.len() == 1 {
+                    return checked_float(x.log10());
+                }
+                let base = positive("log base", float(&values[0]))?;
+                if base == 1.0 {
+                    return Err(DatabaseError::DivisionByZero);
+                }
+                checked_float(x.log(base))
+            }
+        }
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        match self.kind {
+            MathFunctionKind::Ceil
+            | MathFunctionKind::Floor
+            | MathFunctionKind::Round
+            | MathFunctionKind::Sqrt
+            | MathFunctionKind::Ln => Some(vec![Some(true), None]),
+            MathFunctionKind::Log if self.summary.arg_types.len() == 1 => Some(vec![Some(true)]),
+            _ => None,
+        }
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &self.return_type
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+/// A number of any type, computed as the widest of its kind.
+enum Number {
+    Integer(i128),
+    Float(f64),
+    Decimal(Decimal),
+}
+
+impl From<&DataValue> for Number {
+    fn from(value: &DataValue) -> Self {
+        match value {
+            DataValue::Int8(Some(v)) => Number::Integer(*v as i128),
+            DataValue::Int16(Some(v)) => Number::Integer(*v as i128),
+            DataValue::Int32(Some(v)) => Number::Integer(*v as i128),
+            DataValue::Int64(Some(v)) => Number::Integer(*v as i128),
+            DataValue::UInt8(Some(v)) => Number::Integer(*v as i128),
+            DataValue::UInt16(Some(v)) => Number::Integer(*v as i128),
+            DataValue::UInt32(Some(v)) => Number::Integer(*v as i128),
+            DataValue::UInt64(Some(v)) => Number::Integer(*v as i128),
+            DataValue::Float32(Some(v)) => Number::Float(*v as f64),
+            DataValue::Float64(Some(v)) => Number::Float(*v),
+            DataValue::Decimal(Some(v)) => Number::Decimal(*v),
+            _ => Number::Float(f64::NAN),
+        }
+    }
+}
+
+impl Number {
+    fn abs(self) -> Self {
+        match self {
+            Number::Integer(v) => Number::Integer(v.abs()),
+            Number::Float(v) => Number::Float(v.abs()),
+            Number::Decimal(v) => Number::Decimal(v.abs()),
+        }
+    }
+
+    fn ceil(self) -> Self {
+        match self {
+            Number::Integer(v) => Number::Integer(v),
+            Number::Float(v) => Number::Float(v.ceil()),
+            Number::Decimal(v) => Number::Decimal(v.ceil()),
+        }
+    }
+
+    fn floor(self) -> Self {
+        match self {
+            Number::Integer(v) => Number::Integer(v),
+            Number::Float(v) => Number::Float(v.floor()),
+            Number::Decimal(v) => Number::Decimal(v.floor()),
+        }
+    }
+
+    fn round(self, scale: i32) -> Result<Self, DatabaseError> {
+        Ok(match self {
+            Number::Integer(v) if scale >= 0 => Number::Integer(v),
+            Number::Integer(v) => {
+                // Tips: `i128` holds fewer than 39 digits
+                let Some(unit) = 10i128.checked_pow(scale.unsigned_abs()) else {
+                    return Ok(Number::Integer(0));
+                };
+                let (quotient, remainder) = (v / unit, v % unit);
+                let carry = if remainder.unsigned_abs() * 2 >= unit as u128 {
+                    v.signum()
+                } else {
+                    0
+                };
+                Number::Integer((quotient + carry).checked_mul(unit).ok_or_else(|| {
+                    DatabaseError::ArithmeticOverflow(format!("round({}, {})", v, scale))
+                })?)
+            }
+            Number::Float(v) => {
+                let unit = 10f64.powi(scale);
+                let rounded = (v * unit).round() / unit;
+                // the unit is out of the range of `f64` if the scale is too large or too small
+                Number::Float(match rounded.is_finite() {
+                    true => rounded,
+                    false if scale < 0 => 0.0,
+                    false => v,
+                })
+            }
+            Number::Decimal(v) if scale >= 0 => Number::Decimal(
+                v.round_dp_with_strategy(scale as u32, RoundingStrategy::MidpointAwayFromZero),
+            ),
+            Number::Decimal(v) => {
+                let Some(unit) = 10i128
+                    .checked_pow(scale.unsigned_abs())
+                    .and_then(Decimal::from_i128)
+                else {
+                    return Ok(Number::Decimal(Decimal::ZERO));
+                };
+                let quotient =
+                    (v / unit).round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero);
+                Number::Decimal(quotient.checked_mul(unit).ok_or_else(|| {
+                    DatabaseError::ArithmeticOverflow(format!("round({}, {})", v, scale))
+                })?)
+            }
+        })
+    }
+
+    fn rem(self, other: Number) -> Result<Self, DatabaseError> {
+        Ok(match (self, other) {
+            (Number::Integer(v1), Number::Integer(v2)) => {
+                Number::Integer(v1.checked_rem(v2).ok_or(DatabaseError::DivisionByZero)?)
+            }
+            (Number::Decimal(v1), Number::Decimal(v2)) => {
+                Number::Decimal(v1.checked_rem(v2).ok_or(DatabaseError::DivisionByZero)?)
+            }
+            (v1, v2) => {
+                let (v1, v2) = (v1.to_f64(), v2.to_f64());
+                if v2 == 0.0 {
+                    return Err(DatabaseError::DivisionByZero);
+                }
+                Number::Float(v1 % v2)
+            }
+        })
+    }
+
+    fn to_f64(&self) -> f64 {
+        match self {
+            Number::Integer(v) => *v as f64,
+            Number::Float(v) => *v,
+            Number::Decimal(v) => v.to_f64().unwrap_or(f64::NAN),
+        }
+    }
+
+    /// The value of type `ty`, which fails if it is out of the range of `ty`.
+    fn into_value(self, ty: &LogicalType) -> Result<DataValue, DatabaseError> {
+        let value = match self {
+            Number::Integer(v) => match i64::try_from(v) {
+                Ok(v) => DataValue::Int64(Some(v)),
+                Err(_) => DataValue::UInt64(Some(u64::try_from(v).map_err(|_| {
+                    DatabaseError::ArithmeticOverflow(format!("{} out of range", v))
+                })?)),
+            },
+            Number::Float(v) => DataValue::Float64(Some(v)),
+            Number::Decimal(v) => DataValue::Decimal(Some(v)),
+        };
+        value
+            .cast(ty)
+            .map_err(|_| DatabaseError::ArithmeticOverflow(format!("cast to {}", ty)))
+    }
+}
+
+fn float(value: &DataValue) -> f64 {
+    Number::from(value).to_f64()
+}
+
+fn checked_float(value: f64) -> Result<DataValue, DatabaseError> {
+    if value.is_infinite() {
+        return Err(DatabaseError::ArithmeticOverflow(format!(
+            "{} out of range",
+            value
+        )));
+    }
+    Ok(DataValue::Float64(Some(value)))
+}
+
+fn positive(name: &str, value: f64) -> Result<f64, DatabaseError> {
+    if value <= 0.0 {
+        return Err(DatabaseError::InvalidParameter(
+            name.to_string(),
+            value.to_string(),
+        ));
+    }
+    Ok(value)
+}
@@ -1,2 +1,12 @@
+pub(crate) mod array;
 pub(crate) mod current_date;
+pub(crate) mod date_trunc;
+pub(crate) mod extract;
+pub(crate) mod gen_random_uuid;
+pub(crate) mod json;
+pub(crate) mod last_insert_id;
+pub(crate) mod math;
+pub(crate) mod now;
 pub(crate) mod numbers;
+pub(crate) mod regexp;
+pub(crate) mod string;
@@ -0,0 +1,57 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::execution::statement_time;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use serde::Deserialize;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// `now()` and `current_timestamp`, the time at which the statement started, see
+/// [`statement_time`].
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Now {
+    summary: FunctionSummary,
+}
+
+impl Now {
+    pub(crate) fn new(name: &str) -> Arc<Self> {
+        Arc::new(Self {
+            summary: FunctionSummary {
+                name: name.to_string(),
+                arg_types: Vec::new(),
+            },
+        })
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for Now {
+    fn eval(
+        &self,
+        _: &[ScalarExpression],
+        _: &Tuple,
+        _: &[ColumnRef],
+    ) -> Result<DataValue, DatabaseError> {
+        Ok(DataValue::Date64(Some(
+            statement_time().and_utc().timestamp(),
+        )))
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &LogicalType::DateTime
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+}
@@ -0,0 +1,137 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::evaluator::utf8::regex;
+use crate::types::tuple::Tuple;
+use crate::types::value::{DataValue, Utf8Type};
+use crate::types::LogicalType;
+use serde::Deserialize;
+use serde::Serialize;
+use sqlparser::ast::CharLengthUnits;
+use std::sync::Arc;
+
+pub(crate) const REGEXP_REPLACE: &str = "regexp_replace";
+pub(crate) const REGEXP_MATCHES: &str = "regexp_matches";
+
+const VARCHAR: LogicalType = LogicalType::Varchar(None, CharLengthUnits::Characters);
+
+/// `regexp_replace(string, pattern, replacement)` and `regexp_matches(string, pattern)`, which
+/// return `NULL` if any argument is `NULL`.
+///
+/// A literal pattern is checked when the call is bound, and the compiled patterns are cached,
+/// see [`regex`].
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Regexp {
+    is_replace: bool,
+    summary: FunctionSummary,
+}
+
+impl Regexp {
+    pub(crate) fn all() -> Vec<Arc<Self>> {
+        vec![
+            Arc::new(Self {
+                is_replace: true,
+                summary: FunctionSummary {
+                    name: REGEXP_REPLACE.to_string(),
+                    arg_types: vec![VARCHAR, VARCHAR, VARCHAR],
+                },
+            }),
+            Arc::new(Self {
+                is_replace: false,
+                summary: FunctionSummary {
+                    name: REGEXP_MATCHES.to_string(),
+                    arg_types: vec![VARCHAR, VARCHAR],
+                },
+            }),
+        ]
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for Regexp {
+    fn eval(
+        &self,
+        args: &[ScalarExpression],
+        tuple: &Tuple,
+        schema: &[ColumnRef],
+    ) -> Result<DataValue, DatabaseError> {
+        let mut values = Vec::with_capacity(args.len());
+        for arg in args {
+            let Some(value) = arg.eval(tuple, schema)?.utf8() else {
+                return Ok(DataValue::none(self.return_type()));
+            };
+            values.push(value);
+        }
+        let regex = regex(&values[1], false)?;
+
+        let value = if self.is_replace {
+            // Tips: only the first match is replaced, as in PostgreSQL without the `g` flag
+            Some(
+                regex
+                    .replace(&values[0], replacement(&values[2]))
+                    .into_owned(),
+            )
+        } else {
+            // the whole match if the pattern has no capture group
+            regex.captures(&values[0]).and_then(|captures| {
+                captures
+                    .get(usize::from(captures.len() > 1))
+                    .map(|group| group.as_str().to_string())
+            })
+        };
+
+        Ok(DataValue::Utf8 {
+            value,
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        })
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        &VARCHAR
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+/// Translates the `\n` and `\&` of a replacement in PostgreSQL into `${n}` and `${0}` of the
+/// `regex` crate, whose `$` is escaped.
+fn replacement(replacement: &str) -> String {
+    let mut translated = String::with_capacity(replacement.len());
+    let mut chars = replacement.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '$' => translated.push_str("$$"),
+            '\\' => match chars.next() {
+                Some(n @ '0'..='9') => {
+                    translated.push_str("${");
+                    translated.push(n);
+                    translated.push('}');
+                }
+                Some('&') => translated.push_str("${0}"),
+                Some('\\') => translated.push('\\'),
+                Some(c) => {
+                    translated.push('\\');
+                    translated.push(c);
+                }
+                None => translated.push('\\'),
+            },
+            c => translated.push(c),
+        }
+    }
+    translated
+}
@@ -0,0 +1,278 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::function::scala::FuncMonotonicity;
+use crate::expression::function::scala::ScalarFunctionImpl;
+use crate::expression::function::FunctionSummary;
+use crate::expression::ScalarExpression;
+use crate::types::tuple::Tuple;
+use crate::types::value::{DataValue, Utf8Type};
+use crate::types::LogicalType;
+use serde::Deserialize;
+use serde::Serialize;
+use sqlparser::ast::CharLengthUnits;
+use std::iter;
+use std::sync::Arc;
+
+/// the longest string built by `lpad`, `rpad` and `repeat`, in characters
+const MAX_STRING_LENGTH: usize = 1 << 30;
+
+const VARCHAR: LogicalType = LogicalType::Varchar(None, CharLengthUnits::Characters);
+
+/// The built-in string functions, which return `NULL` if any argument is `NULL` and count the
+/// characters of a string rather than its bytes, but the bytes of a `BYTEA`.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct StringFunction {
+    kind: StringFunctionKind,
+    summary: FunctionSummary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum StringFunctionKind {
+    /// `lpad(string, length [, fill])`, `fill` is a space by default
+    Lpad,
+    /// `rpad(string, length [, fill])`, `fill` is a space by default
+    Rpad,
+    /// `replace(string, from, to)`
+    Replace,
+    /// `repeat(string, n)`
+    Repeat,
+    /// `split_part(string, delimiter, n)`, the fields are counted from the end if `n` is negative
+    SplitPart,
+    /// `left(string, n)`, all but the last `-n` characters if `n` is negative
+    Left,
+    /// `right(string, n)`, all but the first `-n` characters if `n` is negative
+    Right,
+    /// `strpos(string, substring)`, the same as `POSITION(substring IN string)`
+    Strpos,
+    /// `reverse(string)`
+    Reverse,
+    /// `length(string)` or `length(bytes)`
+    Length,
+}
+
+impl StringFunction {
+    /// All the string functions, one for each list of parameters.
+    pub(crate) fn all() -> Vec<Arc<Self>> {
+        let int = LogicalType::Integer;
+
+        vec![
+            Self::new(StringFunctionKind::Lpad, vec![VARCHAR, int.clone()]),
+            Self::new(
+                StringFunctionKind::Lpad,
+                vec![VARCHAR, int.clone(), VARCHAR],
+            ),
+            Self::new(StringFunctionKind::Rpad, vec![VARCHAR, int.clone()]),
+            Self::new(
+                StringFunctionKind::Rpad,
+                vec![VARCHAR, int.clone(), VARCHAR],
+            ),
+            Self::new(StringFunctionKind::Replace, vec![VARCHAR, VARCHAR, VARCHAR]),
+            Self::new(StringFunctionKind::Repeat, vec![VARCHAR, int.clone()]),
+            Self::new(
+                StringFunctionKind::SplitPart,
+                vec![VARCHAR, VARCHAR, int.clone()],
+            ),
+            Self::new(StringFunctionKind::Left, vec![VARCHAR, int.clone()]),
+            Self::new(StringFunctionKind::Right, vec![VARCHAR, int]),
+            Self::new(StringFunctionKind::Strpos, vec![VARCHAR, VARCHAR]),
+            Self::new(StringFunctionKind::Reverse, vec![VARCHAR]),
+            Self::new(StringFunctionKind::Length, vec![VARCHAR]),
+            Self::new(StringFunctionKind::Length, vec![LogicalType::Bytea]),
+        ]
+    }
+
+    fn new(kind: StringFunctionKind, arg_types: Vec<LogicalType>) -> Arc<Self> {
+        Arc::new(Self {
+            kind,
+            summary: FunctionSummary {
+                name: kind.name().to_string(),
+                arg_types,
+            },
+        })
+    }
+}
+
+impl StringFunctionKind {
+    fn name(&self) -> &'static str {
+        match self {
+            StringFunctionKind::Lpad => "lpad",
+            StringFunctionKind::Rpad => "rpad",
+            StringFunctionKind::Replace => "replace",
+            StringFunctionKind::Repeat => "repeat",
+            StringFunctionKind::SplitPart => "split_part",
+            StringFunctionKind::Left => "left",
+            StringFunctionKind::Right => "right",
+            StringFunctionKind::Strpos => "strpos",
+            StringFunctionKind::Reverse => "reverse",
+            StringFunctionKind::Length => "length",
+        }
+    }
+}
+
+#[typetag::serde]
+impl ScalarFunctionImpl for StringFunction {
+    fn eval(
+        &self,
+        args: &[ScalarExpression],
+        tuple: &Tuple,
+        schema: &[ColumnRef],
+    ) -> Result<DataValue, DatabaseError> {
+        let mut values = Vec::with_capacity(args.len());
+        for arg in args {
+            let value = arg.eval(tuple, schema)?;
+            if value.is_null() {
+                return Ok(DataValue::none(self.return_type()));
+            }
+            values.push(value);
+        }
+        let string = |i: usize| values[i].utf8().unwrap_or_default();
+        let int = |i: usize| values[i].i32().unwrap_or_default();
+
+        let value = match self.kind {
+            StringFunctionKind::Lpad | StringFunctionKind::Rpad => {
+                let fill = if values.len() > 2 {
+                    string(2)
+                } else {
+                    " ".to_string()
+                };
+                pad(
+                    &string(0),
+                    int(1),
+                    &fill,
+                    self.kind == StringFunctionKind::Lpad,
+                )?
+            }
+            StringFunctionKind::Replace => {
+                let (from, to) = (string(1), string(2));
+                if from.is_empty() {
+                    string(0)
+                } else {
+                    string(0).replace(&from, &to)
+                }
+            }
+            StringFunctionKind::Repeat => {
+                let (value, n) = (string(0), int(1).max(0) as usize);
+                if value.chars().count().saturating_mul(n) > MAX_STRING_LENGTH {
+                    return Err(DatabaseError::TooLong);
+                }
+                value.repeat(n)
+            }
+            StringFunctionKind::SplitPart => split_part(&string(0), &string(1), int(2))?,
+            StringFunctionKind::Left => {
+                let (chars, n) = (string(0).chars().collect::<Vec<_>>(), int(1));
+                let len = if n >= 0 {
+                    (n as usize).min(chars.len())
+                } else {
+                    chars.len().saturating_sub(n.unsigned_abs() as usize)
+                };
+                chars[..len].iter().collect()
+            }
+            StringFunctionKind::Right => {
+                let (chars, n) = (string(0).chars().collect::<Vec<_>>(), int(1));
+                let len = if n >= 0 {
+                    (n as usize).min(chars.len())
+                } else {
+                    chars.len().saturating_sub(n.unsigned_abs() as usize)
+                };
+                chars[chars.len() - len..].iter().collect()
+            }
+            StringFunctionKind::Strpos => {
+                return Ok(DataValue::Int32(Some(strpos(&string(0), &string(1)))))
+            }
+            StringFunctionKind::Reverse => string(0).chars().rev().collect(),
+            StringFunctionKind::Length => {
+                let len = match values[0].as_ref() {
+                    DataValue::Bytea(Some(bytes)) => bytes.len(),
+                    _ => string(0).chars().count(),
+                };
+                return Ok(DataValue::Int32(Some(len as i32)));
+            }
+        };
+
+        Ok(DataValue::Utf8 {
+            value: Some(value),
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        })
+    }
+
+    fn monotonicity(&self) -> Option<FuncMonotonicity> {
+        None
+    }
+
+    fn return_type(&self) -> &LogicalType {
+        match self.kind {
+            StringFunctionKind::Strpos | StringFunctionKind::Length => &LogicalType::Integer,
+            _ => &VARCHAR,
+        }
+    }
+
+    fn summary(&self) -> &FunctionSummary {
+        &self.summary
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+/// Fills `string` up to `length` characters with `fill` repeated, or truncates it to `length`
+/// characters on the right, it is left as is if `fill` is empty.
+fn pad(string: &str, length: i32, fill: &str, is_left: bool) -> Result<String, DatabaseError> {
+    let length = length.max(0) as usize;
+    if length > MAX_STRING_LENGTH {
+        return Err(DatabaseError::TooLong);
+    }
+    let len = string.chars().count();
+
+    if len >= length {
+        return Ok(string.chars().take(length).collect());
+    }
+    if fill.is_empty() {
+        return Ok(string.to_string());
+    }
+    let filling = iter::repeat(fill.chars())
+        .flatten()
+        .take(length - len)
+        .collect::<String>();
+
+    Ok(if is_left {
+        filling + string
+    } else {
+        string.to_string() + &filling
+    })
+}
+
+/// The `n`-th field of `string` split by `delimiter`, which is empty if `n` is out of range.
+fn split_part(string: &str, delimiter: &str, n: i32) -> Result<String, DatabaseError> {
+    if n == 0 {
+        return Err(DatabaseError::InvalidParameter(
+            "split_part field position".to_string(),
+            n.to_string(),
+        ));
+    }
+    // Tips: an empty delimiter does not split the string at all
+    let fields = if delimiter.is_empty() {
+        vec![string]
+    } else {
+        string.split(delimiter).collect::<Vec<_>>()
+    };
+    let i = if n > 0 {
+        Some(n as usize - 1)
+    } else {
+        fields.len().checked_sub(n.unsigned_abs() as usize)
+    };
+
+    Ok(i.and_then(|i| fields.get(i))
+        .map(|field| field.to_string())
+        .unwrap_or_default())
+}
+
+/// The position of the first `substring` in `string` in characters from 1, or 0 if there is
+/// none.
+pub(crate) fn strpos(string: &str, substring: &str) -> i32 {
+    string
+        .find(substring)
+        .map(|i| string[..i].chars().count() as i32 + 1)
+        .unwrap_or(0)
+}
@@ -29,7 +29,9 @@
 //! - Tuple
 //!
 //! support optimistic transaction with the
-//! [`Database::new_transaction`](db::Database::new_transaction) method.
+//! [`Database::new_transaction`](db::Database::new_transaction) method, or `BEGIN` with
+//! [`Database::run`](db::Database::run) and the [`Session`](db::Session) of
+//! [`Database::session`](db::Database::session).
 //!
 //! support UDF (User-Defined Function) so that users can customize internal calculation functions
 //! with the [`DataBaseBuilder::register_function`](db::DataBaseBuilder::register_scala_function)
@@ -219,3 +219,75 @@ macro_rules! table_function {
         }
     };
 }
+
+/// # Examples
+///
+/// ```
+/// aggregate_function!(MyAggregateFunction::my_sum(LogicalType::Integer) -> LogicalType::Integer => i32 {
+///     init: || 0,
+///     accumulate: |state: &mut i32, value: &ValueRef| {
+///         *state += value.i32().unwrap();
+///         Ok(())
+///     },
+///     merge: |state: &mut i32, other: &i32| {
+///         *state += other;
+///         Ok(())
+///     },
+///     finalize: |state: &i32| Ok(DataValue::Int32(Some(*state)))
+/// });
+///
+/// let fnck_sql = DataBaseBuilder::path("./example")
+///     .register_aggregate_function(MyAggregateFunction::new())
+///     .build()
+///     ?;
+/// ```
+#[macro_export]
+macro_rules! aggregate_function {
+    ($struct_name:ident::$function_name:ident($arg_ty:expr) -> $return_ty:expr => $state_ty:ty {
+        init: $init:expr,
+        accumulate: $accumulate:expr,
+        merge: $merge:expr,
+        finalize: $finalize:expr $(,)?
+    }) => {
+        #[derive(Debug, ::serde::Serialize, ::serde::Deserialize)]
+        pub(crate) struct $struct_name {
+            summary: ::fnck_sql::expression::function::FunctionSummary,
+        }
+
+        impl $struct_name {
+            pub(crate) fn new() -> Arc<Self> {
+                let function_name = stringify!($function_name).to_lowercase();
+
+                Arc::new(Self {
+                    summary: ::fnck_sql::expression::function::FunctionSummary {
+                        name: function_name,
+                        arg_types: vec![$arg_ty],
+                    },
+                })
+            }
+        }
+
+        #[typetag::serde]
+        impl ::fnck_sql::expression::function::aggregate::AggregateFunctionImpl for $struct_name {
+            fn accumulator(
+                &self,
+            ) -> Box<dyn ::fnck_sql::expression::function::aggregate::Accumulator> {
+                let init: fn() -> $state_ty = $init;
+
+                Box::new(
+                    ::fnck_sql::expression::function::aggregate::AggregateFunctionAccumulator::<
+                        $state_ty,
+                    >::new(init(), $accumulate, $merge, $finalize),
+                )
+            }
+
+            fn return_type(&self) -> &::fnck_sql::types::LogicalType {
+                &$return_ty
+            }
+
+            fn summary(&self) -> &::fnck_sql::expression::function::FunctionSummary {
+                &self.summary
+            }
+        }
+    };
+}
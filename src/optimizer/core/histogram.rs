@@ -176,7 +176,7 @@ fn is_under(
                 BinaryOperator::LtEq
             },
         )?;
-        let value = evaluator.0.binary_eval(value, target);
+        let value = evaluator.0.checked_binary_eval(value, target)?;
         Ok::<bool, DatabaseError>(matches!(value, DataValue::Boolean(Some(true))))
     };
 
@@ -201,7 +201,7 @@ fn is_above(
                 BinaryOperator::Gt
             },
         )?;
-        let value = evaluator.0.binary_eval(value, target);
+        let value = evaluator.0.checked_binary_eval(value, target)?;
         Ok::<bool, DatabaseError>(matches!(value, DataValue::Boolean(Some(true))))
     };
     Ok(match target {
@@ -302,6 +302,28 @@ impl Histogram {
                         .double(),
                     _ => unreachable!(),
                 },
+                LogicalType::TimestampTz => value
+                    .timestamp_tz()
+                    .map(|date_time| date_time.timestamp_micros() as f64),
+                LogicalType::Interval => value
+                    .interval()
+                    .map(|interval| interval.total_micros() as f64),
+                LogicalType::Uuid => value.uuid().map(|uuid| uuid.as_u128() as f64),
+                LogicalType::Bytea => match value {
+                    // Tips: the 8 bytes from `prefix_len` as a number, padded with zeros
+                    DataValue::Bytea(value) => value.as_ref().map(|bytes| {
+                        let mut buf = [0u8; 8];
+
+                        if let Some(rest) = bytes.get(prefix_len..) {
+                            let len = rest.len().min(8);
+                            buf[..len].copy_from_slice(&rest[..len]);
+                        }
+                        u64::from_be_bytes(buf) as f64
+                    }),
+                    _ => unreachable!(),
+                },
+                // Tips: JSON values and arrays are not indexed, see `DatabaseError::Unorderable`
+                LogicalType::Json | LogicalType::Array(_) => unreachable!(),
 
                 LogicalType::Invalid
                 | LogicalType::SqlNull
@@ -317,8 +339,12 @@ impl Histogram {
                 | LogicalType::Float
                 | LogicalType::Double
                 | LogicalType::Decimal(_, _) => value.clone().cast(&LogicalType::Double)?.double(),
-                LogicalType::Tuple => match value {
-                    DataValue::Tuple(Some(values)) => {
+                LogicalType::Tuple | LogicalType::Struct(_) => match value {
+                    DataValue::Tuple(Some(values))
+                    | DataValue::Struct {
+                        values: Some(values),
+                        ..
+                    } => {
                         let mut float = 0.0;
 
                         for (i, value) in values.iter().enumerate() {
@@ -473,9 +499,10 @@ mod tests {
             id: 0,
             column_ids: vec![0],
             table_name: Arc::new("t1".to_string()),
-            pk_ty: LogicalType::Integer,
+            pk_types: vec![LogicalType::Integer],
             name: "pk_c1".to_string(),
             ty: IndexType::PrimaryKey,
+            json_path: vec![],
         }
     }
 
@@ -116,14 +116,12 @@ mod tests {
         database.run("analyze table t1")?;
 
         let transaction = database.storage.transaction()?;
-        let scala_functions = Default::default();
-        let table_functions = Default::default();
+        let functions = Default::default();
         let mut binder = Binder::new(
             BinderContext::new(
                 &database.table_cache,
                 &transaction,
-                &scala_functions,
-                &table_functions,
+                &functions,
                 Arc::new(AtomicUsize::new(0)),
             ),
             None,
@@ -177,9 +175,10 @@ mod tests {
                     id: 0,
                     column_ids: vec![0],
                     table_name: Arc::new("t1".to_string()),
-                    pk_ty: LogicalType::Integer,
+                    pk_types: vec![LogicalType::Integer],
                     name: "pk_c1".to_string(),
                     ty: IndexType::PrimaryKey,
+                    json_path: vec![],
                 }),
                 range: Some(Range::SortedRanges(vec![
                     Range::Eq(Arc::new(DataValue::Int32(Some(2)))),
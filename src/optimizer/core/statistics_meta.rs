@@ -120,9 +120,10 @@ mod tests {
             id: 0,
             column_ids: vec![0],
             table_name: Arc::new("t1".to_string()),
-            pk_ty: LogicalType::Integer,
+            pk_types: vec![LogicalType::Integer],
             name: "pk_c1".to_string(),
             ty: IndexType::PrimaryKey,
+            json_path: vec![],
         };
 
         let mut builder = HistogramBuilder::new(&index, Some(15))?;
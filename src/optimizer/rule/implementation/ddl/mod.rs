@@ -1,4 +1,5 @@
 pub(crate) mod add_column;
+pub(crate) mod alter_column;
 pub(crate) mod create_table;
 pub(crate) mod drop_column;
 pub(crate) mod drop_table;
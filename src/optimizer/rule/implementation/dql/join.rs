@@ -3,7 +3,7 @@ use crate::optimizer::core::memo::{Expression, GroupExpression};
 use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
 use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
-use crate::planner::operator::join::{JoinCondition, JoinOperator};
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
 use crate::planner::operator::{Operator, PhysicalOption};
 use crate::storage::Transaction;
 use lazy_static::lazy_static;
@@ -37,10 +37,16 @@ impl<T: Transaction> ImplementationRule<T> for JoinImplementation {
 
         if let Operator::Join(JoinOperator {
             on: JoinCondition::On { on, .. },
+            join_type,
+            correlated_columns,
             ..
         }) = op
         {
-            if !on.is_empty() {
+            // a correlated join runs its right side again for each row on the left
+            if !on.is_empty()
+                && correlated_columns.is_empty()
+                && !matches!(join_type, JoinType::LeftSingle)
+            {
                 physical_option = PhysicalOption::HashJoin;
             }
         }
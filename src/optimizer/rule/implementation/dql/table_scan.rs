@@ -34,13 +34,15 @@ impl<T: Transaction> ImplementationRule<T> for SeqScanImplementation {
         group_expr: &mut GroupExpression,
     ) -> Result<(), DatabaseError> {
         if let Operator::TableScan(scan_op) = op {
+            if scan_op.order.is_some() {
+                return Ok(());
+            }
             let cost = scan_op
                 .index_infos
                 .iter()
                 .find(|index_info| {
-                    let column_ids = &index_info.meta.column_ids;
-
-                    column_ids.len() == 1 && column_ids[0] == scan_op.primary_key
+                    index_info.meta.column_ids == scan_op.primary_keys
+                        && index_info.meta.json_path.is_empty()
                 })
                 .map(|index_info| loader.load(&scan_op.table_name, index_info.meta.id))
                 .transpose()?
@@ -79,6 +81,11 @@ impl<T: Transaction> ImplementationRule<T> for IndexScanImplementation {
                 if index_info.range.is_none() {
                     continue;
                 }
+                if let Some(order) = &scan_op.order {
+                    if order.index != index_info.meta {
+                        continue;
+                    }
+                }
                 let mut cost = None;
 
                 if let Some(range) = &index_info.range {
@@ -9,6 +9,7 @@ use crate::optimizer::core::pattern::Pattern;
 use crate::optimizer::core::rule::{ImplementationRule, MatchPattern};
 use crate::optimizer::core::statistics_meta::StatisticMetaLoader;
 use crate::optimizer::rule::implementation::ddl::add_column::AddColumnImplementation;
+use crate::optimizer::rule::implementation::ddl::alter_column::AlterColumnImplementation;
 use crate::optimizer::rule::implementation::ddl::create_table::CreateTableImplementation;
 use crate::optimizer::rule::implementation::ddl::drop_column::DropColumnImplementation;
 use crate::optimizer::rule::implementation::ddl::drop_table::DropTableImplementation;
@@ -60,6 +61,7 @@ pub enum ImplementationRuleImpl {
     Update,
     // DDL
     AddColumn,
+    AlterColumn,
     CreateTable,
     DropColumn,
     DropTable,
@@ -87,6 +89,7 @@ impl MatchPattern for ImplementationRuleImpl {
             ImplementationRuleImpl::Insert => InsertImplementation.pattern(),
             ImplementationRuleImpl::Update => UpdateImplementation.pattern(),
             ImplementationRuleImpl::AddColumn => AddColumnImplementation.pattern(),
+            ImplementationRuleImpl::AlterColumn => AlterColumnImplementation.pattern(),
             ImplementationRuleImpl::CreateTable => CreateTableImplementation.pattern(),
             ImplementationRuleImpl::DropColumn => DropColumnImplementation.pattern(),
             ImplementationRuleImpl::DropTable => DropTableImplementation.pattern(),
@@ -158,6 +161,9 @@ impl<T: Transaction> ImplementationRule<T> for ImplementationRuleImpl {
             ImplementationRuleImpl::AddColumn => {
                 AddColumnImplementation.to_expression(operator, loader, group_expr)?
             }
+            ImplementationRuleImpl::AlterColumn => {
+                AlterColumnImplementation.to_expression(operator, loader, group_expr)?
+            }
             ImplementationRuleImpl::CreateTable => {
                 CreateTableImplementation.to_expression(operator, loader, group_expr)?
             }
@@ -73,6 +73,7 @@ impl ColumnPruning {
                             distinct: false,
                             kind: AggKind::Count,
                             args: vec![ScalarExpression::Constant(value)],
+                            filter: None,
                             ty: LogicalType::Integer,
                         })
                     }
@@ -108,6 +109,8 @@ impl ColumnPruning {
                 }
             }
             Operator::Sort(_)
+            | Operator::Window(_)
+            | Operator::DistinctOn(_)
             | Operator::Limit(_)
             | Operator::Join(_)
             | Operator::Filter(_)
@@ -124,9 +127,16 @@ impl ColumnPruning {
                     Self::_apply(copy_references, all_referenced, child_id, graph);
                 }
             }
+            // the rows of both children are fed back to the recursive term as a whole
+            Operator::RecursiveUnion(_) => {
+                Self::recollect_apply(column_references, true, node_id, graph);
+            }
             // Last Operator
-            Operator::Dummy | Operator::Values(_) | Operator::FunctionScan(_) => (),
-            Operator::Explain => {
+            Operator::Dummy
+            | Operator::Values(_)
+            | Operator::FunctionScan(_)
+            | Operator::WorkTable(_) => (),
+            Operator::Explain | Operator::CopyToFile(_) => {
                 if let Some(child_id) = graph.eldest_child_at(node_id) {
                     Self::_apply(column_references, true, child_id, graph);
                 } else {
@@ -147,16 +157,26 @@ impl ColumnPruning {
                     unreachable!();
                 }
             }
+            // `SELECT ... INTO` inserts into the created table,
+            // and `REFRESH MATERIALIZED VIEW` into the truncated one
+            Operator::CreateTable(_) | Operator::Truncate(_) => {
+                Self::recollect_apply(column_references, true, node_id, graph);
+            }
             // DDL Single Plan
-            Operator::CreateTable(_)
-            | Operator::CreateIndex(_)
+            Operator::CreateIndex(_)
             | Operator::DropTable(_)
-            | Operator::Truncate(_)
+            | Operator::CreateView(_)
+            | Operator::DropView(_)
+            | Operator::Comment(_)
             | Operator::Show
             | Operator::CopyFromFile(_)
-            | Operator::CopyToFile(_)
             | Operator::AddColumn(_)
+            | Operator::AlterColumn(_)
             | Operator::DropColumn(_)
+            | Operator::AddCheck(_)
+            | Operator::AddForeignKey(_)
+            | Operator::DropConstraint(_)
+            | Operator::RenameTable(_)
             | Operator::Describe(_) => (),
         }
     }
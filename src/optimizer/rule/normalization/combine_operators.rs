@@ -84,10 +84,11 @@ impl NormalizationRule for CombineFilter {
         if let Operator::Filter(op) = graph.operator(node_id).clone() {
             if let Some(child_id) = graph.eldest_child_at(node_id) {
                 if let Operator::Filter(child_op) = graph.operator_mut(child_id) {
+                    // Tips: the predicate of the child goes first, as it was evaluated first
                     child_op.predicate = ScalarExpression::Binary {
                         op: BinaryOperator::And,
-                        left_expr: Box::new(op.predicate),
-                        right_expr: Box::new(child_op.predicate.clone()),
+                        left_expr: Box::new(child_op.predicate.clone()),
+                        right_expr: Box::new(op.predicate),
                         evaluator: None,
                         ty: LogicalType::Boolean,
                     };
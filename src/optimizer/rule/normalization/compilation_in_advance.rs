@@ -39,13 +39,22 @@ impl ExpressionRemapper {
         }
         // for join
         let mut left_len = 0;
-        if let Operator::Join(_) = graph.operator(node_id) {
-            let mut second_output_exprs = Vec::new();
-            if let Some(child_id) = graph.youngest_child_at(node_id) {
-                Self::_apply(&mut second_output_exprs, child_id, graph)?;
+        match graph.operator(node_id) {
+            Operator::Join(_) => {
+                let mut second_output_exprs = Vec::new();
+                if let Some(child_id) = graph.youngest_child_at(node_id) {
+                    Self::_apply(&mut second_output_exprs, child_id, graph)?;
+                }
+                left_len = output_exprs.len();
+                output_exprs.append(&mut second_output_exprs);
             }
-            left_len = output_exprs.len();
-            output_exprs.append(&mut second_output_exprs);
+            // Tips: the output of union is the output of the left child
+            Operator::Union(_) | Operator::RecursiveUnion(_) => {
+                if let Some(child_id) = graph.youngest_child_at(node_id) {
+                    Self::_apply(&mut Vec::new(), child_id, graph)?;
+                }
+            }
+            _ => (),
         }
         let operator = graph.operator_mut(node_id);
 
@@ -84,6 +93,18 @@ impl ExpressionRemapper {
                     sort_field.expr.try_reference(output_exprs);
                 }
             }
+            // Tips: the output of window is the output of its child followed by the window calls
+            Operator::Window(op) => {
+                for expr in op.window_calls.iter_mut() {
+                    expr.try_reference(output_exprs);
+                }
+                output_exprs.extend(op.window_calls.iter().cloned());
+            }
+            Operator::DistinctOn(op) => {
+                for expr in op.on_exprs.iter_mut() {
+                    expr.try_reference(output_exprs);
+                }
+            }
             Operator::FunctionScan(op) => {
                 for expr in op.table_function.args.iter_mut() {
                     expr.try_reference(output_exprs);
@@ -101,14 +122,24 @@ impl ExpressionRemapper {
             | Operator::Delete(_)
             | Operator::Analyze(_)
             | Operator::AddColumn(_)
+            | Operator::AlterColumn(_)
             | Operator::DropColumn(_)
+            | Operator::AddCheck(_)
+            | Operator::AddForeignKey(_)
+            | Operator::DropConstraint(_)
+            | Operator::RenameTable(_)
             | Operator::CreateTable(_)
             | Operator::CreateIndex(_)
             | Operator::DropTable(_)
+            | Operator::CreateView(_)
+            | Operator::DropView(_)
+            | Operator::Comment(_)
             | Operator::Truncate(_)
             | Operator::CopyFromFile(_)
             | Operator::CopyToFile(_)
-            | Operator::Union(_) => (),
+            | Operator::Union(_)
+            | Operator::RecursiveUnion(_)
+            | Operator::WorkTable(_) => (),
         }
         if let Some(exprs) = operator.output_exprs() {
             *output_exprs = exprs;
@@ -142,8 +173,10 @@ impl EvaluatorBind {
         if let Some(child_id) = graph.eldest_child_at(node_id) {
             Self::_apply(child_id, graph)?;
         }
-        // for join
-        if let Operator::Join(_) = graph.operator(node_id) {
+        // for join and union
+        if let Operator::Join(_) | Operator::Union(_) | Operator::RecursiveUnion(_) =
+            graph.operator(node_id)
+        {
             if let Some(child_id) = graph.youngest_child_at(node_id) {
                 Self::_apply(child_id, graph)?;
             }
@@ -185,6 +218,16 @@ impl EvaluatorBind {
                     sort_field.expr.bind_evaluator()?;
                 }
             }
+            Operator::Window(op) => {
+                for expr in op.window_calls.iter_mut() {
+                    expr.bind_evaluator()?;
+                }
+            }
+            Operator::DistinctOn(op) => {
+                for expr in op.on_exprs.iter_mut() {
+                    expr.bind_evaluator()?;
+                }
+            }
             Operator::FunctionScan(op) => {
                 for expr in op.table_function.args.iter_mut() {
                     expr.bind_evaluator()?;
@@ -202,14 +245,24 @@ impl EvaluatorBind {
             | Operator::Delete(_)
             | Operator::Analyze(_)
             | Operator::AddColumn(_)
+            | Operator::AlterColumn(_)
             | Operator::DropColumn(_)
+            | Operator::AddCheck(_)
+            | Operator::AddForeignKey(_)
+            | Operator::DropConstraint(_)
+            | Operator::RenameTable(_)
             | Operator::CreateTable(_)
             | Operator::CreateIndex(_)
             | Operator::DropTable(_)
+            | Operator::CreateView(_)
+            | Operator::DropView(_)
+            | Operator::Comment(_)
             | Operator::Truncate(_)
             | Operator::CopyFromFile(_)
             | Operator::CopyToFile(_)
-            | Operator::Union(_) => (),
+            | Operator::Union(_)
+            | Operator::RecursiveUnion(_)
+            | Operator::WorkTable(_) => (),
         }
 
         Ok(())
@@ -0,0 +1,405 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::agg::AggKind;
+use crate::expression::{BinaryOperator, ScalarExpression};
+use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
+use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
+use crate::optimizer::heuristic::batch::HepMatchOrder;
+use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::optimizer::rule::normalization::pushdown_predicates::{
+    reduce_filters, split_conjunctive_predicates,
+};
+use crate::planner::operator::filter::FilterOperator;
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+use crate::planner::operator::Operator;
+use crate::types::LogicalType;
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use std::mem;
+
+lazy_static! {
+    static ref DECORRELATE_SUBQUERY_RULE: Pattern = {
+        Pattern {
+            predicate: |op| matches!(op, Operator::Join(JoinOperator { correlated_columns, .. }) if !correlated_columns.is_empty()),
+            children: PatternChildrenPredicate::None,
+        }
+    };
+}
+
+/// Rewrites a correlated subquery, which is run again for each row of the outer query,
+/// into a plain join by pulling the conjuncts of its `WHERE` referencing the outer query
+/// up into the join condition, e.g.
+/// ```text
+/// c1 IN (SELECT c3 FROM t2 WHERE t2.c4 = t1.c2) => LeftSemi Join On c1 = c3 AND c2 = c4
+/// ```
+/// A subquery aggregating without `GROUP BY` is grouped by the columns compared with the outer
+/// query instead, so that each row of the outer query still joins with its own aggregate.
+///
+/// The subquery is left to be run for each row when the outer query is referenced anywhere else,
+/// e.g. below a `LIMIT` or in the projection, or when grouping would change its results,
+/// e.g. `COUNT` returns `0` rather than no row for an empty input.
+pub struct DecorrelateSubquery;
+
+impl MatchPattern for DecorrelateSubquery {
+    fn pattern(&self) -> &Pattern {
+        &DECORRELATE_SUBQUERY_RULE
+    }
+}
+
+impl NormalizationRule for DecorrelateSubquery {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let Operator::Join(JoinOperator {
+            join_type,
+            correlated_columns,
+            ..
+        }) = graph.operator(node_id)
+        else {
+            return Ok(());
+        };
+        let join_type = *join_type;
+        let correlated_columns = correlated_columns.clone();
+        let Some(right_id) = graph.youngest_child_at(node_id) else {
+            return Ok(());
+        };
+
+        // e.g. the predicates of `EXISTS` are already pulled up by the binder
+        if !is_correlated(graph, right_id, &correlated_columns) {
+            decorrelate_join(graph, node_id, join_type, vec![], vec![]);
+            return Ok(());
+        }
+        if !matches!(
+            join_type,
+            JoinType::Inner | JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftSingle
+        ) {
+            return Ok(());
+        }
+        let mut path = Vec::new();
+        let mut aggregate_id = None;
+        let mut child_id = right_id;
+
+        let filter_id = loop {
+            match graph.operator(child_id) {
+                Operator::Filter(FilterOperator { having: false, .. }) => break child_id,
+                Operator::Project(_) | Operator::Sort(_) => (),
+                Operator::Aggregate(_) if aggregate_id.is_none() => aggregate_id = Some(child_id),
+                _ => return Ok(()),
+            }
+            path.push(child_id);
+
+            match graph.eldest_child_at(child_id) {
+                Some(id) => child_id = id,
+                None => return Ok(()),
+            }
+        };
+        let Operator::Filter(FilterOperator { predicate, .. }) = graph.operator(filter_id) else {
+            unreachable!()
+        };
+        let (correlated_predicates, predicates): (Vec<_>, Vec<_>) =
+            split_conjunctive_predicates(predicate)
+                .into_iter()
+                .partition(|expr| is_correlated_expr(expr, &correlated_columns));
+
+        if path
+            .iter()
+            .any(|id| is_correlated_op(graph.operator(*id), &correlated_columns))
+        {
+            return Ok(());
+        }
+        if let Some(filter_child_id) = graph.eldest_child_at(filter_id) {
+            if is_correlated(graph, filter_child_id, &correlated_columns) {
+                return Ok(());
+            }
+        }
+        let mut on_keys = Vec::new();
+        let mut filters = Vec::new();
+
+        for expr in correlated_predicates {
+            match outer_equality(&expr, &correlated_columns) {
+                Some(on_key) => on_keys.push(on_key),
+                None => filters.push(expr),
+            }
+        }
+        let mut join_type = join_type;
+
+        if let Some(aggregate_id) = aggregate_id {
+            let Operator::Aggregate(op) = graph.operator(aggregate_id) else {
+                unreachable!()
+            };
+            // only equalities on columns keep the groups of each row of the outer query apart
+            if !filters.is_empty()
+                || !on_keys
+                    .iter()
+                    .all(|(_, inner)| matches!(inner, ScalarExpression::ColumnRef(_)))
+            {
+                return Ok(());
+            }
+            if op.groupby_exprs.is_empty() {
+                // an aggregate without `GROUP BY` returns a row even without input,
+                // which the join can only stand in for with `NULL`
+                if join_type == JoinType::LeftAnti
+                    || !op.agg_calls.iter().all(|call| {
+                        matches!(
+                            call.unpack_alias_ref(),
+                            ScalarExpression::AggCall {
+                                kind: AggKind::Avg | AggKind::Max | AggKind::Min | AggKind::Sum,
+                                ..
+                            }
+                        )
+                    })
+                {
+                    return Ok(());
+                }
+                // a single group remains for each row of the outer query
+                if join_type == JoinType::LeftSingle {
+                    join_type = JoinType::LeftOuter;
+                }
+            }
+        }
+        let inner_columns = on_keys
+            .iter()
+            .map(|(_, inner)| inner)
+            .chain(filters.iter())
+            .flat_map(|expr| expr.referenced_columns(true))
+            .filter(|column| !correlated_columns.contains(column))
+            .unique()
+            .collect_vec();
+
+        for id in path {
+            match graph.operator_mut(id) {
+                Operator::Project(op) => {
+                    for column in inner_columns.iter() {
+                        let expr = ScalarExpression::ColumnRef(column.clone());
+
+                        if !op.exprs.contains(&expr) {
+                            op.exprs.push(expr);
+                        }
+                    }
+                }
+                Operator::Aggregate(op) => {
+                    for column in inner_columns.iter() {
+                        let expr = ScalarExpression::ColumnRef(column.clone());
+
+                        if !op.groupby_exprs.contains(&expr) {
+                            op.groupby_exprs.push(expr);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        match reduce_filters(predicates, false) {
+            Some(filter) => graph.replace_node(filter_id, Operator::Filter(filter)),
+            None => {
+                graph.remove_node(filter_id, false);
+            }
+        }
+        decorrelate_join(graph, node_id, join_type, on_keys, filters);
+
+        Ok(())
+    }
+}
+
+/// Adds the predicates pulled up from the subquery to the join, which no longer needs to run
+/// the subquery for each row.
+fn decorrelate_join(
+    graph: &mut HepGraph,
+    node_id: HepNodeId,
+    join_type: JoinType,
+    mut on_keys: Vec<(ScalarExpression, ScalarExpression)>,
+    filters: Vec<ScalarExpression>,
+) {
+    let Operator::Join(op) = graph.operator_mut(node_id) else {
+        unreachable!()
+    };
+    op.join_type = join_type;
+    op.correlated_columns.clear();
+
+    if on_keys.is_empty() && filters.is_empty() {
+        return;
+    }
+    let (mut on, filter) = match mem::replace(&mut op.on, JoinCondition::None) {
+        JoinCondition::On { on, filter } => (on, filter),
+        JoinCondition::None => (vec![], None),
+    };
+    on.append(&mut on_keys);
+    let filter = filter
+        .into_iter()
+        .chain(filters)
+        .reduce(|acc, expr| ScalarExpression::Binary {
+            op: BinaryOperator::And,
+            left_expr: Box::new(acc),
+            right_expr: Box::new(expr),
+            evaluator: None,
+            ty: LogicalType::Boolean,
+        });
+    op.on = JoinCondition::On { on, filter };
+}
+
+/// Splits `outer = inner` into a key of the join, with the expression on the outer query first.
+fn outer_equality(
+    expr: &ScalarExpression,
+    correlated_columns: &[ColumnRef],
+) -> Option<(ScalarExpression, ScalarExpression)> {
+    let ScalarExpression::Binary {
+        op: BinaryOperator::Eq,
+        left_expr,
+        right_expr,
+        ..
+    } = expr
+    else {
+        return None;
+    };
+    let is_outer = |expr: &ScalarExpression| {
+        let columns = expr.referenced_columns(true);
+
+        !columns.is_empty()
+            && columns
+                .iter()
+                .all(|column| correlated_columns.contains(column))
+    };
+    let is_inner = |expr: &ScalarExpression| !is_correlated_expr(expr, correlated_columns);
+
+    if is_outer(left_expr) && is_inner(right_expr) {
+        Some((*left_expr.clone(), *right_expr.clone()))
+    } else if is_outer(right_expr) && is_inner(left_expr) {
+        Some((*right_expr.clone(), *left_expr.clone()))
+    } else {
+        None
+    }
+}
+
+fn is_correlated_expr(expr: &ScalarExpression, correlated_columns: &[ColumnRef]) -> bool {
+    expr.referenced_columns(true)
+        .iter()
+        .any(|column| correlated_columns.contains(column))
+}
+
+fn is_correlated_op(op: &Operator, correlated_columns: &[ColumnRef]) -> bool {
+    op.referenced_columns(true)
+        .iter()
+        .any(|column| correlated_columns.contains(column))
+}
+
+/// Whether any operator of the plan rooted at `node_id` references the outer query.
+fn is_correlated(graph: &HepGraph, node_id: HepNodeId, correlated_columns: &[ColumnRef]) -> bool {
+    graph
+        .nodes_iter(HepMatchOrder::TopDown, Some(node_id))
+        .any(|id| is_correlated_op(graph.operator(id), correlated_columns))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binder::test::select_sql_run;
+    use crate::errors::DatabaseError;
+    use crate::optimizer::heuristic::batch::HepBatchStrategy;
+    use crate::optimizer::heuristic::optimizer::HepOptimizer;
+    use crate::optimizer::rule::normalization::NormalizationRuleImpl;
+    use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+    use crate::planner::operator::Operator;
+    use crate::planner::LogicalPlan;
+    use crate::storage::rocksdb::RocksTransaction;
+
+    fn decorrelate(sql: &str) -> Result<LogicalPlan, DatabaseError> {
+        HepOptimizer::new(select_sql_run(sql)?)
+            .batch(
+                "test_decorrelate_subquery".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![NormalizationRuleImpl::DecorrelateSubquery],
+            )
+            .find_best::<RocksTransaction>(None)
+    }
+
+    fn find_join(plan: &LogicalPlan) -> &LogicalPlan {
+        match &plan.operator {
+            Operator::Join(_) => plan,
+            _ => find_join(&plan.childrens[0]),
+        }
+    }
+
+    fn contains_filter(plan: &LogicalPlan) -> bool {
+        matches!(plan.operator, Operator::Filter(_)) || plan.childrens.iter().any(contains_filter)
+    }
+
+    #[test]
+    fn test_decorrelate_in_sub_query() -> Result<(), DatabaseError> {
+        let plan =
+            decorrelate("select * from t1 where c1 in (select c3 from t2 where c4 = t1.c2)")?;
+        let join_plan = find_join(&plan);
+
+        let Operator::Join(JoinOperator {
+            on: JoinCondition::On { on, filter: None },
+            join_type: JoinType::LeftSemi,
+            correlated_columns,
+            ..
+        }) = &join_plan.operator
+        else {
+            unreachable!("Should be a semi join on keys")
+        };
+        debug_assert_eq!(on.len(), 2);
+        debug_assert!(correlated_columns.is_empty());
+        debug_assert!(!contains_filter(&join_plan.childrens[1]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decorrelate_scalar_aggregate() -> Result<(), DatabaseError> {
+        let plan = decorrelate("select c1, (select max(c3) from t2 where c4 = t1.c2) from t1")?;
+        let join_plan = find_join(&plan);
+
+        let Operator::Join(JoinOperator {
+            on: JoinCondition::On { on, filter: None },
+            join_type: JoinType::LeftOuter,
+            correlated_columns,
+            ..
+        }) = &join_plan.operator
+        else {
+            unreachable!("Should be a left outer join on keys")
+        };
+        debug_assert_eq!(on.len(), 1);
+        debug_assert!(correlated_columns.is_empty());
+
+        let mut aggregate_plan = &join_plan.childrens[1];
+        while !matches!(aggregate_plan.operator, Operator::Aggregate(_)) {
+            aggregate_plan = &aggregate_plan.childrens[0];
+        }
+        let Operator::Aggregate(op) = &aggregate_plan.operator else {
+            unreachable!()
+        };
+        debug_assert_eq!(op.groupby_exprs.len(), 1);
+        debug_assert!(!contains_filter(aggregate_plan));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_keep_correlated_sub_query() -> Result<(), DatabaseError> {
+        for (sql, join_type) in [
+            (
+                "select * from t1 where c1 in (select c3 from t2 where c4 = t1.c2 limit 1)",
+                JoinType::LeftSemi,
+            ),
+            // `COUNT` returns `0` for a row of the outer query without matches
+            (
+                "select c1, (select count(*) from t2 where c4 = t1.c2) from t1",
+                JoinType::LeftSingle,
+            ),
+            (
+                "select c1, (select max(c3) from t2 where c4 > t1.c2) from t1",
+                JoinType::LeftSingle,
+            ),
+        ] {
+            let plan = decorrelate(sql)?;
+            let join_plan = find_join(&plan);
+
+            let Operator::Join(op) = &join_plan.operator else {
+                unreachable!()
+            };
+            debug_assert_eq!(op.join_type, join_type, "{}", sql);
+            debug_assert!(!op.correlated_columns.is_empty(), "{}", sql);
+            debug_assert!(contains_filter(&join_plan.childrens[1]), "{}", sql);
+        }
+
+        Ok(())
+    }
+}
@@ -10,19 +10,28 @@ use crate::optimizer::rule::normalization::combine_operators::{
 use crate::optimizer::rule::normalization::compilation_in_advance::{
     EvaluatorBind, ExpressionRemapper,
 };
+use crate::optimizer::rule::normalization::decorrelate_subquery::DecorrelateSubquery;
+use crate::optimizer::rule::normalization::pushdown_aggregate::{
+    PushAggregateThroughJoin, PushMinMaxIntoIndexScan,
+};
 use crate::optimizer::rule::normalization::pushdown_limit::{
     LimitProjectTranspose, PushLimitIntoScan, PushLimitThroughJoin,
 };
+use crate::optimizer::rule::normalization::pushdown_predicates::PushOrPredicateIntoUnion;
 use crate::optimizer::rule::normalization::pushdown_predicates::PushPredicateIntoScan;
 use crate::optimizer::rule::normalization::pushdown_predicates::PushPredicateThroughJoin;
+use crate::optimizer::rule::normalization::pushdown_sort::PushSortIntoIndexScan;
 use crate::optimizer::rule::normalization::simplification::ConstantCalculation;
 use crate::optimizer::rule::normalization::simplification::SimplifyFilter;
 
 mod column_pruning;
 mod combine_operators;
 mod compilation_in_advance;
+mod decorrelate_subquery;
+mod pushdown_aggregate;
 mod pushdown_limit;
 mod pushdown_predicates;
+mod pushdown_sort;
 mod simplification;
 
 #[derive(Debug, Copy, Clone)]
@@ -32,14 +41,22 @@ pub enum NormalizationRuleImpl {
     CollapseProject,
     CollapseGroupByAgg,
     CombineFilter,
+    // Subquery
+    DecorrelateSubquery,
     // PushDown limit
     LimitProjectTranspose,
     PushLimitThroughJoin,
     PushLimitIntoTableScan,
     // PushDown predicates
     PushPredicateThroughJoin,
+    PushOrPredicateIntoUnion,
     // Tips: need to be used with `SimplifyFilter`
     PushPredicateIntoScan,
+    // PushDown aggregate
+    PushAggregateThroughJoin,
+    PushMinMaxIntoIndexScan,
+    // PushDown sort
+    PushSortIntoIndexScan,
     // Simplification
     SimplifyFilter,
     ConstantCalculation,
@@ -55,11 +72,16 @@ impl MatchPattern for NormalizationRuleImpl {
             NormalizationRuleImpl::CollapseProject => CollapseProject.pattern(),
             NormalizationRuleImpl::CollapseGroupByAgg => CollapseGroupByAgg.pattern(),
             NormalizationRuleImpl::CombineFilter => CombineFilter.pattern(),
+            NormalizationRuleImpl::DecorrelateSubquery => DecorrelateSubquery.pattern(),
             NormalizationRuleImpl::LimitProjectTranspose => LimitProjectTranspose.pattern(),
             NormalizationRuleImpl::PushLimitThroughJoin => PushLimitThroughJoin.pattern(),
             NormalizationRuleImpl::PushLimitIntoTableScan => PushLimitIntoScan.pattern(),
             NormalizationRuleImpl::PushPredicateThroughJoin => PushPredicateThroughJoin.pattern(),
+            NormalizationRuleImpl::PushOrPredicateIntoUnion => PushOrPredicateIntoUnion.pattern(),
             NormalizationRuleImpl::PushPredicateIntoScan => PushPredicateIntoScan.pattern(),
+            NormalizationRuleImpl::PushAggregateThroughJoin => PushAggregateThroughJoin.pattern(),
+            NormalizationRuleImpl::PushMinMaxIntoIndexScan => PushMinMaxIntoIndexScan.pattern(),
+            NormalizationRuleImpl::PushSortIntoIndexScan => PushSortIntoIndexScan.pattern(),
             NormalizationRuleImpl::SimplifyFilter => SimplifyFilter.pattern(),
             NormalizationRuleImpl::ConstantCalculation => ConstantCalculation.pattern(),
             NormalizationRuleImpl::ExpressionRemapper => ExpressionRemapper.pattern(),
@@ -75,6 +97,7 @@ impl NormalizationRule for NormalizationRuleImpl {
             NormalizationRuleImpl::CollapseProject => CollapseProject.apply(node_id, graph),
             NormalizationRuleImpl::CollapseGroupByAgg => CollapseGroupByAgg.apply(node_id, graph),
             NormalizationRuleImpl::CombineFilter => CombineFilter.apply(node_id, graph),
+            NormalizationRuleImpl::DecorrelateSubquery => DecorrelateSubquery.apply(node_id, graph),
             NormalizationRuleImpl::LimitProjectTranspose => {
                 LimitProjectTranspose.apply(node_id, graph)
             }
@@ -87,7 +110,19 @@ impl NormalizationRule for NormalizationRuleImpl {
             NormalizationRuleImpl::PushPredicateThroughJoin => {
                 PushPredicateThroughJoin.apply(node_id, graph)
             }
+            NormalizationRuleImpl::PushAggregateThroughJoin => {
+                PushAggregateThroughJoin.apply(node_id, graph)
+            }
+            NormalizationRuleImpl::PushMinMaxIntoIndexScan => {
+                PushMinMaxIntoIndexScan.apply(node_id, graph)
+            }
+            NormalizationRuleImpl::PushSortIntoIndexScan => {
+                PushSortIntoIndexScan.apply(node_id, graph)
+            }
             NormalizationRuleImpl::SimplifyFilter => SimplifyFilter.apply(node_id, graph),
+            NormalizationRuleImpl::PushOrPredicateIntoUnion => {
+                PushOrPredicateIntoUnion.apply(node_id, graph)
+            }
             NormalizationRuleImpl::PushPredicateIntoScan => {
                 PushPredicateIntoScan.apply(node_id, graph)
             }
@@ -0,0 +1,383 @@
+use crate::catalog::ColumnRef;
+use crate::errors::DatabaseError;
+use crate::expression::agg::AggKind;
+use crate::expression::range_detacher::Range;
+use crate::expression::{AliasType, BinaryOperator, ScalarExpression};
+use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
+use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
+use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::planner::operator::aggregate::AggregateOperator;
+use crate::planner::operator::filter::FilterOperator;
+use crate::planner::operator::join::{JoinCondition, JoinOperator, JoinType};
+use crate::planner::operator::limit::LimitOperator;
+use crate::planner::operator::table_scan::ScanOrder;
+use crate::planner::operator::Operator;
+use crate::types::index::IndexInfo;
+use crate::types::LogicalType;
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use std::collections::Bound;
+
+lazy_static! {
+    static ref PUSH_AGGREGATE_THROUGH_JOIN_RULE: Pattern = {
+        Pattern {
+            predicate: |op| matches!(op, Operator::Aggregate(_)),
+            children: PatternChildrenPredicate::Predicate(vec![Pattern {
+                predicate: |op| matches!(op, Operator::Join(_)),
+                children: PatternChildrenPredicate::None,
+            }]),
+        }
+    };
+    static ref PUSH_MIN_MAX_INTO_INDEX_SCAN_RULE: Pattern = {
+        Pattern {
+            predicate: |op| matches!(op, Operator::Aggregate(_)),
+            children: PatternChildrenPredicate::None,
+        }
+    };
+}
+
+/// Eager aggregation: add a partial `Aggregate` grouped by the join keys below the right side of
+/// an INNER JOIN, so that the join only sees one row per key of the right side.
+///
+/// The rewrite is only applied when it cannot change the result:
+/// 1. the join is an equi-join without extra filters, and the right side is a (filtered) scan.
+/// 2. every aggregate call is a non-distinct `SUM`, `COUNT`, `MIN` or `MAX` over right columns.
+/// 3. the `GROUP BY` is not empty and only uses left columns or the right join keys, so each
+///    group combines whole partial groups.
+///
+/// The aggregate above the join then merges the partial results, `COUNT` becomes a `SUM` of the
+/// partial counts.
+pub struct PushAggregateThroughJoin;
+
+impl PushAggregateThroughJoin {
+    fn scan_columns(mut node_id: HepNodeId, graph: &HepGraph) -> Option<Vec<ColumnRef>> {
+        loop {
+            match graph.operator(node_id) {
+                Operator::Filter(_) => node_id = graph.eldest_child_at(node_id)?,
+                Operator::TableScan(op) => {
+                    return Some(
+                        op.columns
+                            .iter()
+                            .map(|(_, column)| column.clone())
+                            .collect(),
+                    )
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+impl MatchPattern for PushAggregateThroughJoin {
+    fn pattern(&self) -> &Pattern {
+        &PUSH_AGGREGATE_THROUGH_JOIN_RULE
+    }
+}
+
+impl NormalizationRule for PushAggregateThroughJoin {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let Operator::Aggregate(agg_op) = graph.operator(node_id) else {
+            return Ok(());
+        };
+        if agg_op.is_distinct || agg_op.groupby_exprs.is_empty() || agg_op.agg_calls.is_empty() {
+            return Ok(());
+        }
+        let Some(join_id) = graph.eldest_child_at(node_id) else {
+            return Ok(());
+        };
+        let Operator::Join(JoinOperator {
+            on: JoinCondition::On { on, filter: None },
+            join_type: JoinType::Inner,
+            ..
+        }) = graph.operator(join_id)
+        else {
+            return Ok(());
+        };
+        let Some(right_id) = graph.youngest_child_at(join_id) else {
+            return Ok(());
+        };
+        let Some(right_columns) = Self::scan_columns(right_id, graph) else {
+            return Ok(());
+        };
+
+        let mut partial_groupby_exprs = Vec::with_capacity(on.len());
+        for (_, right_expr) in on {
+            if !matches!(right_expr, ScalarExpression::ColumnRef(_)) {
+                return Ok(());
+            }
+            if !partial_groupby_exprs.contains(right_expr) {
+                partial_groupby_exprs.push(right_expr.clone());
+            }
+        }
+        let is_right_non_key = |column: ColumnRef| {
+            right_columns.contains(&column)
+                && !partial_groupby_exprs.contains(&ScalarExpression::ColumnRef(column))
+        };
+        if agg_op
+            .groupby_exprs
+            .iter()
+            .flat_map(|expr| expr.referenced_columns(true))
+            .any(is_right_non_key)
+        {
+            return Ok(());
+        }
+
+        let mut partial_agg_calls = Vec::with_capacity(agg_op.agg_calls.len());
+        let mut agg_calls = Vec::with_capacity(agg_op.agg_calls.len());
+        for agg_call in agg_op.agg_calls.iter() {
+            let ScalarExpression::AggCall {
+                distinct: false,
+                kind,
+                args,
+                filter: None,
+                ty,
+            } = agg_call
+            else {
+                return Ok(());
+            };
+            let kind = match kind {
+                AggKind::Count => AggKind::Sum,
+                AggKind::Sum | AggKind::Min | AggKind::Max => kind.clone(),
+                // Tips: the partial state of a custom aggregate is not a value to aggregate again
+                AggKind::Avg | AggKind::Custom(_) | AggKind::Grouping => return Ok(()),
+            };
+            if !args
+                .iter()
+                .flat_map(|arg| arg.referenced_columns(true))
+                .all(|column| right_columns.contains(&column))
+            {
+                return Ok(());
+            }
+            let partial_agg_call = ScalarExpression::Alias {
+                expr: Box::new(agg_call.clone()),
+                alias: AliasType::Name(format!("partial {}", agg_call.output_name())),
+            };
+            // Tips: keep the output column of the original call for the operators above
+            agg_calls.push(ScalarExpression::Alias {
+                expr: Box::new(ScalarExpression::AggCall {
+                    distinct: false,
+                    kind,
+                    args: vec![ScalarExpression::ColumnRef(
+                        partial_agg_call.output_column(),
+                    )],
+                    filter: None,
+                    ty: ty.clone(),
+                }),
+                alias: AliasType::Expr(Box::new(agg_call.clone())),
+            });
+            partial_agg_calls.push(partial_agg_call);
+        }
+        let partial_agg_calls = partial_agg_calls.into_iter().unique().collect_vec();
+
+        graph.add_node(
+            join_id,
+            Some(right_id),
+            Operator::Aggregate(AggregateOperator {
+                groupby_exprs: partial_groupby_exprs,
+                agg_calls: partial_agg_calls,
+                is_distinct: false,
+            }),
+        );
+        if let Operator::Aggregate(agg_op) = graph.operator_mut(node_id) {
+            agg_op.agg_calls = agg_calls;
+        }
+
+        Ok(())
+    }
+}
+
+/// Answer a lone `MIN(c)` / `MAX(c)` over a (filtered) scan by reading the first non-null entry
+/// of a single-column index on `c`, forward for `MIN` and backward for `MAX`:
+///
+/// `Aggregate(MIN(c)) -> [Filter ->] TableScan`
+/// =>
+/// `Aggregate(MIN(c)) -> Limit 1 -> Filter([..] AND c IS NOT NULL) -> TableScan(Order: index)`
+///
+/// The range already detached from the `WHERE` for that index is kept, otherwise the whole index
+/// is scanned. The filter stays below the limit, so the predicates the index cannot satisfy are
+/// still checked entry by entry.
+pub struct PushMinMaxIntoIndexScan;
+
+impl MatchPattern for PushMinMaxIntoIndexScan {
+    fn pattern(&self) -> &Pattern {
+        &PUSH_MIN_MAX_INTO_INDEX_SCAN_RULE
+    }
+}
+
+impl NormalizationRule for PushMinMaxIntoIndexScan {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let Operator::Aggregate(agg_op) = graph.operator(node_id) else {
+            return Ok(());
+        };
+        if agg_op.is_distinct || !agg_op.groupby_exprs.is_empty() {
+            return Ok(());
+        }
+        let [ScalarExpression::AggCall {
+            kind: kind @ (AggKind::Min | AggKind::Max),
+            args,
+            filter: None,
+            ..
+        }] = agg_op.agg_calls.as_slice()
+        else {
+            return Ok(());
+        };
+        let [ScalarExpression::ColumnRef(column)] = args.as_slice() else {
+            return Ok(());
+        };
+        let (column, reverse) = (column.clone(), matches!(kind, AggKind::Max));
+        let Some(child_id) = graph.eldest_child_at(node_id) else {
+            return Ok(());
+        };
+        let (filter_id, scan_id) = match graph.operator(child_id) {
+            Operator::Filter(_) => match graph.eldest_child_at(child_id) {
+                Some(scan_id) => (Some(child_id), scan_id),
+                None => return Ok(()),
+            },
+            _ => (None, child_id),
+        };
+        let Operator::TableScan(scan_op) = graph.operator_mut(scan_id) else {
+            return Ok(());
+        };
+        if scan_op.limit != (None, None)
+            || scan_op.order.is_some()
+            || !scan_op
+                .columns
+                .iter()
+                .any(|(_, scan_column)| scan_column == &column)
+        {
+            return Ok(());
+        }
+        let Some(IndexInfo { meta, range }) = scan_op
+            .index_infos
+            .iter()
+            .find(|index_info| {
+                index_info.meta.column_ids == [column.id().unwrap()]
+                    && index_info.meta.json_path.is_empty()
+            })
+            .cloned()
+        else {
+            return Ok(());
+        };
+        let range = range.unwrap_or(Range::Scope {
+            min: Bound::Unbounded,
+            max: Bound::Unbounded,
+        });
+        scan_op.index_infos = vec![IndexInfo {
+            meta: meta.clone(),
+            range: Some(range),
+        }];
+        scan_op.order = Some(ScanOrder {
+            index: meta,
+            reverse,
+        });
+
+        // Tips: null entries are at the start of the index
+        let mut limit_child_id = filter_id.unwrap_or(scan_id);
+        if column.nullable {
+            let not_null = ScalarExpression::IsNull {
+                negated: true,
+                expr: Box::new(ScalarExpression::ColumnRef(column)),
+            };
+            if let Some(Operator::Filter(filter_op)) =
+                filter_id.map(|filter_id| graph.operator_mut(filter_id))
+            {
+                filter_op.predicate = ScalarExpression::Binary {
+                    op: BinaryOperator::And,
+                    left_expr: Box::new(filter_op.predicate.clone()),
+                    right_expr: Box::new(not_null),
+                    evaluator: None,
+                    ty: LogicalType::Boolean,
+                };
+            } else {
+                graph.add_node(
+                    node_id,
+                    Some(scan_id),
+                    Operator::Filter(FilterOperator {
+                        predicate: not_null,
+                        having: false,
+                    }),
+                );
+                limit_child_id = graph.eldest_child_at(node_id).unwrap();
+            }
+        }
+        graph.add_node(
+            node_id,
+            Some(limit_child_id),
+            Operator::Limit(LimitOperator {
+                offset: None,
+                limit: Some(1),
+            }),
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binder::test::select_sql_run;
+    use crate::errors::DatabaseError;
+    use crate::optimizer::heuristic::batch::HepBatchStrategy;
+    use crate::optimizer::heuristic::optimizer::HepOptimizer;
+    use crate::optimizer::rule::normalization::NormalizationRuleImpl;
+    use crate::planner::operator::Operator;
+    use crate::planner::LogicalPlan;
+    use crate::storage::rocksdb::RocksTransaction;
+
+    fn optimize(sql: &str) -> Result<LogicalPlan, DatabaseError> {
+        HepOptimizer::new(select_sql_run(sql)?)
+            .batch(
+                "test_push_aggregate_through_join".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![NormalizationRuleImpl::PushAggregateThroughJoin],
+            )
+            .find_best::<RocksTransaction>(None)
+    }
+
+    #[test]
+    fn test_push_aggregate_through_join() -> Result<(), DatabaseError> {
+        let best_plan =
+            optimize("select c1, sum(c4), count(c4) from t1 join t2 on c1 = c3 group by c1")?;
+        let join_plan = &best_plan.childrens[0].childrens[0];
+
+        if let Operator::Join(_) = &join_plan.operator {
+        } else {
+            unreachable!("Should be a join operator")
+        }
+        if let Operator::Aggregate(op) = &join_plan.childrens[1].operator {
+            debug_assert_eq!(op.agg_calls.len(), 2);
+            debug_assert_eq!(op.groupby_exprs.len(), 1);
+        } else {
+            unreachable!("Should be a aggregate operator")
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_aggregate_through_join_ignored() -> Result<(), DatabaseError> {
+        for sql in [
+            // group by a non-key column of the right side
+            "select c4, sum(c4) from t1 join t2 on c1 = c3 group by c4",
+            // aggregate over the left side
+            "select c3, sum(c2) from t1 join t2 on c1 = c3 group by c3",
+            // not decomposable
+            "select c1, avg(c4) from t1 join t2 on c1 = c3 group by c1",
+            // not an inner join
+            "select c1, sum(c4) from t1 left join t2 on c1 = c3 group by c1",
+        ] {
+            let best_plan = optimize(sql)?;
+
+            debug_assert!(
+                matches!(
+                    best_plan.childrens[0].childrens[0].childrens[1].operator,
+                    Operator::TableScan(_)
+                ),
+                "{}",
+                sql
+            );
+        }
+
+        Ok(())
+    }
+}
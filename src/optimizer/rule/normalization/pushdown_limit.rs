@@ -93,9 +93,10 @@ impl NormalizationRule for PushLimitThroughJoin {
                     let children = graph.children_at(child_id).collect_vec();
 
                     if let Some(grandson_id) = match ty {
-                        JoinType::LeftOuter | JoinType::LeftSemi | JoinType::LeftAnti => {
-                            children.first()
-                        }
+                        JoinType::LeftOuter
+                        | JoinType::LeftSingle
+                        | JoinType::LeftSemi
+                        | JoinType::LeftAnti => children.first(),
                         JoinType::RightOuter => children.last(),
                         _ => None,
                     } {
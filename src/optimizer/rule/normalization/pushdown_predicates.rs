@@ -6,13 +6,17 @@ use crate::optimizer::core::pattern::Pattern;
 use crate::optimizer::core::pattern::PatternChildrenPredicate;
 use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
 use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::planner::operator::aggregate::AggregateOperator;
 use crate::planner::operator::filter::FilterOperator;
 use crate::planner::operator::join::JoinType;
+use crate::planner::operator::table_scan::TableScanOperator;
+use crate::planner::operator::union::UnionOperator;
 use crate::planner::operator::Operator;
 use crate::types::index::{IndexInfo, IndexType};
 use crate::types::LogicalType;
 use itertools::Itertools;
 use lazy_static::lazy_static;
+use std::sync::Arc;
 
 lazy_static! {
     static ref PUSH_PREDICATE_THROUGH_JOIN: Pattern = {
@@ -47,7 +51,7 @@ lazy_static! {
     };
 }
 
-fn split_conjunctive_predicates(expr: &ScalarExpression) -> Vec<ScalarExpression> {
+pub(crate) fn split_conjunctive_predicates(expr: &ScalarExpression) -> Vec<ScalarExpression> {
     match expr {
         ScalarExpression::Binary {
             op: BinaryOperator::And,
@@ -64,7 +68,10 @@ fn split_conjunctive_predicates(expr: &ScalarExpression) -> Vec<ScalarExpression
 
 /// reduce filters into a filter, and then build a new LogicalFilter node with input child.
 /// if filters is empty, return the input child.
-fn reduce_filters(filters: Vec<ScalarExpression>, having: bool) -> Option<FilterOperator> {
+pub(crate) fn reduce_filters(
+    filters: Vec<ScalarExpression>,
+    having: bool,
+) -> Option<FilterOperator> {
     filters
         .into_iter()
         .reduce(|a, b| ScalarExpression::Binary {
@@ -115,6 +122,7 @@ impl NormalizationRule for PushPredicateThroughJoin {
                 child_op.join_type,
                 JoinType::Inner
                     | JoinType::LeftOuter
+                    | JoinType::LeftSingle
                     | JoinType::LeftSemi
                     | JoinType::LeftAnti
                     | JoinType::RightOuter
@@ -131,54 +139,49 @@ impl NormalizationRule for PushPredicateThroughJoin {
             if let Operator::Filter(op) = graph.operator(node_id) {
                 let filter_exprs = split_conjunctive_predicates(&op.predicate);
 
-                let (left_filters, rest): (Vec<_>, Vec<_>) = filter_exprs
-                    .into_iter()
-                    .partition(|f| is_subset_cols(&f.referenced_columns(true), &left_columns));
-                let (right_filters, common_filters): (Vec<_>, Vec<_>) = rest
-                    .into_iter()
-                    .partition(|f| is_subset_cols(&f.referenced_columns(true), &right_columns));
-
-                let replace_filters = match child_op.join_type {
-                    JoinType::Inner => {
-                        if !left_filters.is_empty() {
-                            if let Some(left_filter_op) = reduce_filters(left_filters, op.having) {
-                                new_ops.0 = Some(Operator::Filter(left_filter_op));
-                            }
-                        }
-
-                        if !right_filters.is_empty() {
-                            if let Some(right_filter_op) = reduce_filters(right_filters, op.having)
-                            {
-                                new_ops.1 = Some(Operator::Filter(right_filter_op));
-                            }
-                        }
-
-                        common_filters
+                let (push_left, push_right) = match child_op.join_type {
+                    JoinType::Inner => (true, true),
+                    JoinType::LeftOuter
+                    | JoinType::LeftSingle
+                    | JoinType::LeftSemi
+                    | JoinType::LeftAnti => (true, false),
+                    JoinType::RightOuter => (false, true),
+                    _ => (false, false),
+                };
+                let (mut left_filters, mut right_filters, mut replace_filters) =
+                    (Vec::new(), Vec::new(), Vec::new());
+
+                // Tips: a filter that may fail is only pushed down if none of the filters before
+                // it stays behind, as they may guard it, e.g. `t2.c <> 0 AND t1.a / t2.c > 1`
+                for filter in filter_exprs {
+                    let columns = filter.referenced_columns(true);
+
+                    if push_left
+                        && is_subset_cols(&columns, &left_columns)
+                        && (!filter.may_fail()
+                            || (right_filters.is_empty() && replace_filters.is_empty()))
+                    {
+                        left_filters.push(filter);
+                    } else if push_right
+                        && is_subset_cols(&columns, &right_columns)
+                        && (!filter.may_fail()
+                            || (left_filters.is_empty() && replace_filters.is_empty()))
+                    {
+                        right_filters.push(filter);
+                    } else {
+                        replace_filters.push(filter);
                     }
-                    JoinType::LeftOuter | JoinType::LeftSemi | JoinType::LeftAnti => {
-                        if !left_filters.is_empty() {
-                            if let Some(left_filter_op) = reduce_filters(left_filters, op.having) {
-                                new_ops.0 = Some(Operator::Filter(left_filter_op));
-                            }
-                        }
-
-                        common_filters
-                            .into_iter()
-                            .chain(right_filters)
-                            .collect_vec()
+                }
+                if !left_filters.is_empty() {
+                    if let Some(left_filter_op) = reduce_filters(left_filters, op.having) {
+                        new_ops.0 = Some(Operator::Filter(left_filter_op));
                     }
-                    JoinType::RightOuter => {
-                        if !right_filters.is_empty() {
-                            if let Some(right_filter_op) = reduce_filters(right_filters, op.having)
-                            {
-                                new_ops.1 = Some(Operator::Filter(right_filter_op));
-                            }
-                        }
-
-                        common_filters.into_iter().chain(left_filters).collect_vec()
+                }
+                if !right_filters.is_empty() {
+                    if let Some(right_filter_op) = reduce_filters(right_filters, op.having) {
+                        new_ops.1 = Some(Operator::Filter(right_filter_op));
                     }
-                    _ => vec![],
-                };
+                }
 
                 if !replace_filters.is_empty() {
                     if let Some(replace_filter_op) = reduce_filters(replace_filters, op.having) {
@@ -225,11 +228,20 @@ impl NormalizationRule for PushPredicateIntoScan {
                             continue;
                         }
                         *range = match meta.ty {
-                            IndexType::PrimaryKey | IndexType::Unique | IndexType::Normal => {
+                            IndexType::PrimaryKey
+                            | IndexType::Unique { .. }
+                            | IndexType::Normal
+                                if meta.column_ids.len() == 1 =>
+                            {
                                 RangeDetacher::new(meta.table_name.as_str(), &meta.column_ids[0])
+                                    .with_json_path(&meta.json_path)
                                     .detach(&op.predicate)
                             }
-                            IndexType::Composite => {
+                            // e.g. the equalities on a prefix of a composite primary key
+                            IndexType::PrimaryKey
+                            | IndexType::Unique { .. }
+                            | IndexType::Normal
+                            | IndexType::Composite => {
                                 let mut res = None;
                                 let mut eq_ranges = Vec::with_capacity(meta.column_ids.len());
 
@@ -263,6 +275,112 @@ impl NormalizationRule for PushPredicateIntoScan {
     }
 }
 
+/// Rewrite `Filter(a OR b) -> TableScan` into `Distinct -> Union(Filter(a) -> TableScan, Filter(b) -> TableScan)`
+/// when no index can serve the whole predicate, but `a` and `b` can each be served by an index,
+/// so that both sides of the union can use an index scan.
+///
+/// Tips: the scan must output the primary key, then the distinct only removes the rows that
+/// satisfy both `a` and `b`
+pub struct PushOrPredicateIntoUnion;
+
+impl PushOrPredicateIntoUnion {
+    fn is_indexable(scan_op: &TableScanOperator, expr: &ScalarExpression) -> bool {
+        scan_op.index_infos.iter().any(|IndexInfo { meta, .. }| {
+            RangeDetacher::new(meta.table_name.as_str(), &meta.column_ids[0])
+                .with_json_path(&meta.json_path)
+                .detach(expr)
+                .is_some()
+        })
+    }
+}
+
+impl MatchPattern for PushOrPredicateIntoUnion {
+    fn pattern(&self) -> &Pattern {
+        &PUSH_PREDICATE_INTO_SCAN
+    }
+}
+
+impl NormalizationRule for PushOrPredicateIntoUnion {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let Operator::Filter(FilterOperator {
+            predicate:
+                predicate @ ScalarExpression::Binary {
+                    op: BinaryOperator::Or,
+                    left_expr,
+                    right_expr,
+                    ..
+                },
+            having: false,
+        }) = graph.operator(node_id)
+        else {
+            return Ok(());
+        };
+        let Some(scan_id) = graph.eldest_child_at(node_id) else {
+            return Ok(());
+        };
+        let Operator::TableScan(scan_op) = graph.operator(scan_id) else {
+            return Ok(());
+        };
+        if scan_op.limit != (None, None)
+            || !scan_op.primary_keys.iter().all(|column_id| {
+                scan_op
+                    .columns
+                    .iter()
+                    .any(|(_, column)| column.id() == Some(*column_id))
+            })
+            || Self::is_indexable(scan_op, predicate)
+            || !Self::is_indexable(scan_op, left_expr)
+            || !Self::is_indexable(scan_op, right_expr)
+        {
+            return Ok(());
+        }
+        let left_filter = Operator::Filter(FilterOperator {
+            predicate: left_expr.as_ref().clone(),
+            having: false,
+        });
+        let right_filter = Operator::Filter(FilterOperator {
+            predicate: right_expr.as_ref().clone(),
+            having: false,
+        });
+        let right_scan = Operator::TableScan(scan_op.clone());
+        let schema_ref = Arc::new(
+            scan_op
+                .columns
+                .iter()
+                .map(|(_, column)| column.clone())
+                .collect_vec(),
+        );
+        let distinct = Operator::Aggregate(AggregateOperator {
+            groupby_exprs: schema_ref
+                .iter()
+                .cloned()
+                .map(ScalarExpression::ColumnRef)
+                .collect_vec(),
+            agg_calls: vec![],
+            is_distinct: true,
+        });
+
+        graph.replace_node(
+            node_id,
+            Operator::Union(UnionOperator {
+                left_schema_ref: schema_ref.clone(),
+                _right_schema_ref: schema_ref,
+            }),
+        );
+        graph.add_node(node_id, Some(scan_id), left_filter);
+        graph.add_node(node_id, None, right_filter);
+        if let Some(right_filter_id) = graph.youngest_child_at(node_id) {
+            graph.add_node(right_filter_id, None, right_scan);
+        }
+        match graph.parent_id(node_id) {
+            Some(parent_id) => graph.add_node(parent_id, Some(node_id), distinct),
+            None => graph.add_root(distinct),
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::binder::test::select_sql_run;
@@ -271,6 +389,7 @@ mod tests {
     use crate::expression::{BinaryOperator, ScalarExpression};
     use crate::optimizer::heuristic::batch::HepBatchStrategy;
     use crate::optimizer::heuristic::optimizer::HepOptimizer;
+    use crate::optimizer::rule::normalization::pushdown_predicates::split_conjunctive_predicates;
     use crate::optimizer::rule::normalization::NormalizationRuleImpl;
     use crate::planner::operator::Operator;
     use crate::storage::rocksdb::RocksTransaction;
@@ -279,6 +398,59 @@ mod tests {
     use std::collections::Bound;
     use std::sync::Arc;
 
+    #[test]
+    fn test_push_or_predicate_into_union() -> Result<(), DatabaseError> {
+        let optimize = |sql: &str| {
+            HepOptimizer::new(select_sql_run(sql)?)
+                .batch(
+                    "test_push_or_predicate_into_union".to_string(),
+                    HepBatchStrategy::fix_point_topdown(10),
+                    vec![
+                        NormalizationRuleImpl::PushOrPredicateIntoUnion,
+                        NormalizationRuleImpl::PushPredicateIntoScan,
+                    ],
+                )
+                .find_best::<RocksTransaction>(None)
+        };
+        let best_plan = optimize("select * from t1 where c1 = 1 or c2 = 2")?;
+        let distinct_plan = &best_plan.childrens[0];
+
+        if let Operator::Aggregate(op) = &distinct_plan.operator {
+            debug_assert!(op.is_distinct);
+        } else {
+            unreachable!("Should be a aggregate operator")
+        }
+        let union_plan = &distinct_plan.childrens[0];
+        if let Operator::Union(_) = &union_plan.operator {
+        } else {
+            unreachable!("Should be a union operator")
+        }
+        for (filter_plan, index_i) in union_plan.childrens.iter().zip([0, 1]) {
+            if let Operator::TableScan(op) = &filter_plan.childrens[0].operator {
+                debug_assert!(op.index_infos[index_i].range.is_some());
+            } else {
+                unreachable!("Should be a table scan operator")
+            }
+        }
+
+        for sql in [
+            // the whole predicate can be served by an index
+            "select * from t1 where c1 = 1 or c1 = 2",
+            // no index on c4
+            "select * from t2 where c3 = 1 or c4 = 2",
+        ] {
+            let best_plan = optimize(sql)?;
+
+            debug_assert!(
+                matches!(best_plan.childrens[0].operator, Operator::Filter(_)),
+                "{}",
+                sql
+            );
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_push_predicate_into_scan() -> Result<(), DatabaseError> {
         // 1 - c2 < 0 => c2 > 1
@@ -441,4 +613,43 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_push_predicate_through_join_behind_guard() -> Result<(), DatabaseError> {
+        let plan = select_sql_run(
+            "select * from t1 inner join t2 on c1 = c3 where c3 <> 0 and c1 / c4 > 1 and c2 / c1 > 1",
+        )?;
+
+        let best_plan = HepOptimizer::new(plan)
+            .batch(
+                "test_push_predicate_through_join".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![NormalizationRuleImpl::PushPredicateThroughJoin],
+            )
+            .find_best::<RocksTransaction>(None)?;
+
+        // `c2 / c1 > 1` only uses `t1`, but it is behind `c1 / c4 > 1` which stays on the join
+        let filter = &best_plan.childrens[0];
+        if let Operator::Filter(op) = &filter.operator {
+            assert_eq!(split_conjunctive_predicates(&op.predicate).len(), 2);
+        } else {
+            unreachable!("Should be a filter operator")
+        }
+        let join = &filter.childrens[0];
+        assert!(matches!(join.operator, Operator::Join(_)));
+        assert!(!matches!(join.childrens[0].operator, Operator::Filter(_)));
+        if let Operator::Filter(op) = &join.childrens[1].operator {
+            assert!(matches!(
+                op.predicate,
+                ScalarExpression::Binary {
+                    op: BinaryOperator::NotEq,
+                    ..
+                }
+            ));
+        } else {
+            unreachable!("Should be a filter operator")
+        }
+
+        Ok(())
+    }
 }
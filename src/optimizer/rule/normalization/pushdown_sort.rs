@@ -0,0 +1,214 @@
+use crate::errors::DatabaseError;
+use crate::expression::range_detacher::Range;
+use crate::expression::ScalarExpression;
+use crate::optimizer::core::pattern::{Pattern, PatternChildrenPredicate};
+use crate::optimizer::core::rule::{MatchPattern, NormalizationRule};
+use crate::optimizer::heuristic::graph::{HepGraph, HepNodeId};
+use crate::planner::operator::sort::SortField;
+use crate::planner::operator::table_scan::ScanOrder;
+use crate::planner::operator::Operator;
+use crate::types::index::IndexInfo;
+use lazy_static::lazy_static;
+use std::collections::Bound;
+
+lazy_static! {
+    static ref PUSH_SORT_INTO_INDEX_SCAN_RULE: Pattern = {
+        Pattern {
+            predicate: |op| matches!(op, Operator::Sort(_)),
+            children: PatternChildrenPredicate::None,
+        }
+    };
+}
+
+/// Remove the `Sort` over a (filtered) scan when an index already returns the tuples in that
+/// order, read forward for `ASC` and backward for `DESC`:
+///
+/// `Sort(c DESC) -> [Filter ->] TableScan`
+/// =>
+/// `[Filter ->] TableScan(Order: index DESC)`
+///
+/// The sort fields must be a prefix of the columns of the index, all in the same direction since
+/// the columns of an index are all ascending. Null entries are at the start of an index, so a
+/// nullable column also needs `NULLS FIRST` for `ASC` and `NULLS LAST` for `DESC`.
+pub struct PushSortIntoIndexScan;
+
+impl PushSortIntoIndexScan {
+    fn scan_order(sort_fields: &[SortField]) -> Option<bool> {
+        let reverse = !sort_fields.first()?.asc;
+
+        sort_fields
+            .iter()
+            .all(|field| field.asc != reverse)
+            .then_some(reverse)
+    }
+}
+
+impl MatchPattern for PushSortIntoIndexScan {
+    fn pattern(&self) -> &Pattern {
+        &PUSH_SORT_INTO_INDEX_SCAN_RULE
+    }
+}
+
+impl NormalizationRule for PushSortIntoIndexScan {
+    fn apply(&self, node_id: HepNodeId, graph: &mut HepGraph) -> Result<(), DatabaseError> {
+        let Operator::Sort(sort_op) = graph.operator(node_id) else {
+            return Ok(());
+        };
+        if sort_op.limit.is_some() {
+            return Ok(());
+        }
+        let Some(reverse) = Self::scan_order(&sort_op.sort_fields) else {
+            return Ok(());
+        };
+        let mut columns = Vec::with_capacity(sort_op.sort_fields.len());
+        for SortField {
+            expr, nulls_first, ..
+        } in sort_op.sort_fields.iter()
+        {
+            let ScalarExpression::ColumnRef(column) = expr else {
+                return Ok(());
+            };
+            if column.nullable && *nulls_first == reverse {
+                return Ok(());
+            }
+            columns.push(column.clone());
+        }
+        let Some(child_id) = graph.eldest_child_at(node_id) else {
+            return Ok(());
+        };
+        let scan_id = match graph.operator(child_id) {
+            Operator::Filter(_) => match graph.eldest_child_at(child_id) {
+                Some(scan_id) => scan_id,
+                None => return Ok(()),
+            },
+            _ => child_id,
+        };
+        let Operator::TableScan(scan_op) = graph.operator_mut(scan_id) else {
+            return Ok(());
+        };
+        if scan_op.limit != (None, None)
+            || scan_op.order.is_some()
+            || !columns.iter().all(|column| {
+                scan_op
+                    .columns
+                    .iter()
+                    .any(|(_, scan_column)| scan_column == column)
+            })
+        {
+            return Ok(());
+        }
+        let column_ids = columns
+            .iter()
+            .map(|column| column.id())
+            .collect::<Option<Vec<_>>>();
+        let Some(IndexInfo { meta, range }) = scan_op
+            .index_infos
+            .iter()
+            .find(|index_info| {
+                index_info.meta.json_path.is_empty()
+                    && column_ids.as_ref().is_some_and(|column_ids| {
+                        index_info.meta.column_ids.starts_with(column_ids)
+                    })
+            })
+            .cloned()
+        else {
+            return Ok(());
+        };
+        // Tips: keep the index chosen by the `WHERE`, and the entries equal on the leading columns
+        // of a composite index are only ascending on the following ones
+        if scan_op
+            .index_infos
+            .iter()
+            .any(|index_info| index_info.meta != meta && index_info.range.is_some())
+            || (range.is_some() && meta.column_ids.len() > 1)
+        {
+            return Ok(());
+        }
+        let range = range.unwrap_or(Range::Scope {
+            min: Bound::Unbounded,
+            max: Bound::Unbounded,
+        });
+        scan_op.index_infos = vec![IndexInfo {
+            meta: meta.clone(),
+            range: Some(range),
+        }];
+        scan_op.order = Some(ScanOrder {
+            index: meta,
+            reverse,
+        });
+        graph.remove_node(node_id, false);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binder::test::select_sql_run;
+    use crate::errors::DatabaseError;
+    use crate::optimizer::heuristic::batch::HepBatchStrategy;
+    use crate::optimizer::heuristic::optimizer::HepOptimizer;
+    use crate::optimizer::rule::normalization::NormalizationRuleImpl;
+    use crate::planner::operator::table_scan::ScanOrder;
+    use crate::planner::operator::Operator;
+    use crate::planner::LogicalPlan;
+    use crate::storage::rocksdb::RocksTransaction;
+
+    fn optimize(sql: &str) -> Result<LogicalPlan, DatabaseError> {
+        HepOptimizer::new(select_sql_run(sql)?)
+            .batch(
+                "test_push_sort_into_index_scan".to_string(),
+                HepBatchStrategy::once_topdown(),
+                vec![NormalizationRuleImpl::PushSortIntoIndexScan],
+            )
+            .find_best::<RocksTransaction>(None)
+    }
+
+    #[test]
+    fn test_push_sort_into_index_scan() -> Result<(), DatabaseError> {
+        for (sql, expected_reverse) in [
+            ("select * from t1 order by c1 desc", true),
+            ("select * from t1 order by c1", false),
+            ("select * from t1 where c1 > 1 order by c1 desc", true),
+        ] {
+            let best_plan = optimize(sql)?;
+            let mut plan = &best_plan.childrens[0];
+
+            while !matches!(plan.operator, Operator::TableScan(_)) {
+                debug_assert!(!matches!(plan.operator, Operator::Sort(_)), "{}", sql);
+                plan = &plan.childrens[0];
+            }
+            if let Operator::TableScan(op) = &plan.operator {
+                debug_assert!(
+                    matches!(op.order, Some(ScanOrder { reverse, .. }) if reverse == expected_reverse),
+                    "{}",
+                    sql
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_sort_into_index_scan_ignored() -> Result<(), DatabaseError> {
+        for sql in [
+            // not an indexed column
+            "select * from t2 order by c4 desc",
+            // mixed directions
+            "select * from t1 order by c1 desc, c2",
+            // not a column
+            "select * from t1 order by c1 + 1 desc",
+        ] {
+            let best_plan = optimize(sql)?;
+
+            debug_assert!(
+                matches!(best_plan.childrens[0].operator, Operator::Sort(_)),
+                "{}",
+                sql
+            );
+        }
+
+        Ok(())
+    }
+}
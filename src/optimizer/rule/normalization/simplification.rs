@@ -61,6 +61,16 @@ impl ConstantCalculation {
                     field.expr.constant_calculation()?;
                 }
             }
+            Operator::Window(op) => {
+                for expr in &mut op.window_calls {
+                    expr.constant_calculation()?;
+                }
+            }
+            Operator::DistinctOn(op) => {
+                for expr in &mut op.on_exprs {
+                    expr.constant_calculation()?;
+                }
+            }
             _ => (),
         }
         for child_id in graph.children_at(node_id).collect_vec() {
@@ -255,8 +265,15 @@ mod test {
                 nullable: false,
                 desc: ColumnDesc {
                     column_datatype: LogicalType::Integer,
-                    is_primary: true,
+                    primary: Some(0),
                     is_unique: false,
+                    nulls_not_distinct: false,
+                    is_auto_increment: false,
+                    default_text: None,
+                    added_default: None,
+                    generated: None,
+                    comment: None,
+                    compression: None,
                     default: None,
                 },
             };
@@ -271,8 +288,15 @@ mod test {
                 nullable: false,
                 desc: ColumnDesc {
                     column_datatype: LogicalType::Integer,
-                    is_primary: false,
+                    primary: None,
                     is_unique: true,
+                    nulls_not_distinct: false,
+                    is_auto_increment: false,
+                    default_text: None,
+                    added_default: None,
+                    generated: None,
+                    comment: None,
+                    compression: None,
                     default: None,
                 },
             };
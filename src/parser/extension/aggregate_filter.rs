@@ -0,0 +1,99 @@
+use super::{find_matching_paren, is_keyword, non_whitespace_positions, Extension, MARKER_QUOTE};
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Token, TokenWithLocation};
+
+/// The name of the argument that [`move_aggregate_filter`] turns `FILTER (WHERE ...)` into.
+pub(crate) const AGGREGATE_FILTER: &str = "FILTER";
+
+fn is_filter(token: &TokenWithLocation) -> bool {
+    matches!(&token.token, Token::Word(word) if word.quote_style.is_none() && word.value.eq_ignore_ascii_case(AGGREGATE_FILTER))
+}
+
+/// `sqlparser` does not support `agg(...) FILTER (WHERE predicate)` yet, so the clause is
+/// moved here into the leading named argument ``agg(`FILTER` => (predicate), ...)``, which
+/// follows `DISTINCT` or `ALL` of the arguments if any.
+fn move_aggregate_filter(
+    tokens: Vec<TokenWithLocation>,
+) -> Result<Vec<TokenWithLocation>, ParserError> {
+    if !tokens.iter().any(is_filter) {
+        return Ok(tokens);
+    }
+    let positions = non_whitespace_positions(&tokens);
+    let nth = |pos: usize| positions.get(pos).map(|i| &tokens[*i]);
+    let matching_paren = |pos: usize, forward: bool| find_matching_paren(nth, pos, forward);
+    let mut removed = vec![false; tokens.len()];
+    // (the token the argument follows, the predicate, whether other arguments follow it)
+    let mut filters = Vec::new();
+
+    for pos in 1..positions.len() {
+        let (Some(filter), Some(l_paren), Some(where_)) = (nth(pos), nth(pos + 1), nth(pos + 2))
+        else {
+            continue;
+        };
+        if !is_filter(filter)
+            || l_paren.token != Token::LParen
+            || !is_keyword(where_, Keyword::WHERE)
+            || !matches!(nth(pos - 1).map(|token| &token.token), Some(Token::RParen))
+        {
+            continue;
+        }
+        let Some(args_l_paren) = matching_paren(pos - 1, false) else {
+            continue;
+        };
+        let r_paren = matching_paren(pos + 1, true).ok_or_else(|| {
+            ParserError::ParserError("Expected ), found: EOF after FILTER (WHERE".to_string())
+        })?;
+        for pos in pos..=r_paren {
+            removed[positions[pos]] = true;
+        }
+        let mut args_start = args_l_paren;
+        if nth(args_start + 1).is_some_and(|token| {
+            is_keyword(token, Keyword::DISTINCT) || is_keyword(token, Keyword::ALL)
+        }) {
+            args_start += 1;
+        }
+        let predicate = tokens[positions[pos + 2] + 1..positions[r_paren]].to_vec();
+
+        filters.push((positions[args_start], predicate, args_start + 1 < pos - 1));
+    }
+    let mut moved = Vec::with_capacity(tokens.len() + filters.len() * 5);
+
+    for (i, (token, removed)) in tokens.iter().zip(removed).enumerate() {
+        let (line, column) = (token.location.line, token.location.column);
+
+        if !removed {
+            moved.push(token.clone());
+        }
+        for (_, predicate, has_args) in filters.iter().filter(|(after, ..)| *after == i) {
+            moved.extend(
+                [
+                    Token::make_word(AGGREGATE_FILTER, Some(MARKER_QUOTE)),
+                    Token::RArrow,
+                    Token::LParen,
+                ]
+                .into_iter()
+                .map(|token| TokenWithLocation::new(token, line, column)),
+            );
+            moved.extend(predicate.iter().cloned());
+            moved.push(TokenWithLocation::new(Token::RParen, line, column));
+
+            if *has_args {
+                moved.push(TokenWithLocation::new(Token::Comma, line, column));
+            }
+        }
+    }
+    Ok(moved)
+}
+
+/// `FILTER (WHERE predicate)` of the aggregate functions.
+pub(super) struct AggregateFilter;
+
+impl Extension for AggregateFilter {
+    fn rewrite(
+        &mut self,
+        tokens: Vec<TokenWithLocation>,
+    ) -> Result<Vec<TokenWithLocation>, ParserError> {
+        move_aggregate_filter(tokens)
+    }
+}
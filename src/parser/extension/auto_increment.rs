@@ -0,0 +1,107 @@
+use super::{current_column_name, Extension};
+use sqlparser::ast::{ColumnDef, ColumnOption, ColumnOptionDef, Statement};
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Token, TokenWithLocation};
+use std::mem;
+
+/// The tokens of `AUTO_INCREMENT`, which is kept as [`ColumnOption::DialectSpecific`] of the
+/// column.
+pub(crate) fn auto_increment_tokens() -> Vec<Token> {
+    vec![Token::make_keyword("AUTO_INCREMENT")]
+}
+
+fn is_auto_increment(token: &TokenWithLocation) -> bool {
+    matches!(
+        &token.token,
+        Token::Word(word) if word.quote_style.is_none()
+            && (word.value.eq_ignore_ascii_case("auto_increment")
+                || word.value.eq_ignore_ascii_case("autoincrement"))
+    )
+}
+
+/// `sqlparser` only supports `AUTO_INCREMENT` of MySQL, so it is removed from the tokens here
+/// and returned as the names of the columns it is declared on, e.g. `id` of
+/// `CREATE TABLE t (id INT AUTO_INCREMENT, ...)`.
+fn strip_auto_increment(
+    tokens: Vec<TokenWithLocation>,
+) -> Result<(Vec<TokenWithLocation>, Vec<String>), ParserError> {
+    if !tokens.iter().any(is_auto_increment) {
+        return Ok((tokens, vec![]));
+    }
+    let mut stripped: Vec<TokenWithLocation> = Vec::with_capacity(tokens.len());
+    let mut column_names = Vec::new();
+
+    for token in tokens {
+        if !is_auto_increment(&token) {
+            stripped.push(token);
+            continue;
+        }
+        let column_name = current_column_name(&stripped).ok_or_else(|| {
+            ParserError::ParserError(
+                "`AUTO_INCREMENT` is only supported on the columns of `CREATE TABLE`".to_string(),
+            )
+        })?;
+        column_names.push(column_name);
+    }
+
+    Ok((stripped, column_names))
+}
+
+fn attach_auto_increment(
+    stmts: &mut [Statement],
+    column_names: Vec<String>,
+) -> Result<(), ParserError> {
+    let mut column_names = column_names.into_iter().peekable();
+
+    for stmt in stmts.iter_mut() {
+        let Statement::CreateTable { columns, .. } = stmt else {
+            continue;
+        };
+        for ColumnDef { name, options, .. } in columns.iter_mut() {
+            if column_names
+                .next_if(|column_name| column_name == &name.value)
+                .is_some()
+            {
+                // Tips: ahead of `PRIMARY KEY`, which skips the options following it
+                options.insert(
+                    0,
+                    ColumnOptionDef {
+                        name: None,
+                        option: ColumnOption::DialectSpecific(auto_increment_tokens()),
+                    },
+                );
+            }
+        }
+    }
+    if column_names.peek().is_some() {
+        return Err(ParserError::ParserError(
+            "`AUTO_INCREMENT` is only supported on the columns of `CREATE TABLE`".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// `AUTO_INCREMENT` of the columns.
+#[derive(Default)]
+pub(super) struct AutoIncrement {
+    column_names: Vec<String>,
+}
+
+impl Extension for AutoIncrement {
+    fn rewrite(
+        &mut self,
+        tokens: Vec<TokenWithLocation>,
+    ) -> Result<Vec<TokenWithLocation>, ParserError> {
+        let (tokens, column_names) = strip_auto_increment(tokens)?;
+        self.column_names = column_names;
+
+        Ok(tokens)
+    }
+
+    fn attach(&mut self, stmts: &mut [Statement]) -> Result<(), ParserError> {
+        if self.column_names.is_empty() {
+            return Ok(());
+        }
+        attach_auto_increment(stmts, mem::take(&mut self.column_names))
+    }
+}
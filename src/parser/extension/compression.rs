@@ -0,0 +1,128 @@
+use super::{current_column_name, is_keyword, is_word, non_whitespace_positions, Extension};
+use sqlparser::ast::{ColumnDef, ColumnOption, ColumnOptionDef, Statement};
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Token, TokenWithLocation};
+use std::mem;
+
+/// The tokens of `COMPRESSION method`, which is kept as [`ColumnOption::DialectSpecific`] of the
+/// column, see [`parse_compression`].
+fn compression_tokens(method: String) -> Vec<Token> {
+    vec![
+        Token::make_keyword("COMPRESSION"),
+        Token::make_word(&method, None),
+    ]
+}
+
+/// Parses the tokens kept by a compressed column into the name of its method, `None` if they
+/// are not the tokens of `COMPRESSION method`.
+pub(crate) fn parse_compression(tokens: &[Token]) -> Option<String> {
+    match tokens {
+        [compression, Token::Word(method)] if is_word(compression, "compression") => {
+            Some(method.value.clone())
+        }
+        _ => None,
+    }
+}
+
+/// `(column name, method)`
+type CompressedColumn = (String, String);
+
+/// `sqlparser` does not support `COMPRESSION method` of Postgres, so it is removed from the tokens
+/// of `CREATE TABLE` here and returned with the name of the column it is declared on, e.g.
+/// `(blob_col, lz4)` of `CREATE TABLE t (blob_col BYTEA COMPRESSION lz4)`.
+fn strip_compression(
+    tokens: Vec<TokenWithLocation>,
+) -> (Vec<TokenWithLocation>, Vec<CompressedColumn>) {
+    let positions = non_whitespace_positions(&tokens);
+    let is_create_table = positions.len() > 1
+        && is_keyword(&tokens[positions[0]], Keyword::CREATE)
+        && is_keyword(&tokens[positions[1]], Keyword::TABLE);
+    if !is_create_table
+        || !tokens
+            .iter()
+            .any(|token| is_word(&token.token, "compression"))
+    {
+        return (tokens, vec![]);
+    }
+    let mut stripped: Vec<TokenWithLocation> = Vec::with_capacity(tokens.len());
+    let mut compressed = Vec::new();
+    let mut removed_to = 0;
+
+    for (pos, i) in positions.iter().copied().enumerate() {
+        if i < removed_to {
+            continue;
+        }
+        // Tips: the columns of `CREATE TABLE ... AS SELECT` are aliases, not definitions
+        if is_keyword(&tokens[i], Keyword::SELECT) {
+            break;
+        }
+        let Some(Token::Word(method)) = positions.get(pos + 1).map(|j| &tokens[*j].token) else {
+            continue;
+        };
+        if !is_word(&tokens[i].token, "compression") {
+            continue;
+        }
+        stripped.extend(tokens[removed_to..i].iter().cloned());
+        removed_to = i;
+
+        // e.g. a column named `compression` is left to `sqlparser`
+        let Some(column_name) = current_column_name(&stripped) else {
+            continue;
+        };
+        compressed.push((column_name, method.value.clone()));
+        removed_to = positions[pos + 1] + 1;
+    }
+    stripped.extend(tokens[removed_to..].iter().cloned());
+
+    (stripped, compressed)
+}
+
+fn attach_compression(stmts: &mut [Statement], compressed: Vec<CompressedColumn>) {
+    let mut compressed = compressed.into_iter().peekable();
+
+    for stmt in stmts.iter_mut() {
+        let Statement::CreateTable { columns, .. } = stmt else {
+            continue;
+        };
+        for ColumnDef { name, options, .. } in columns.iter_mut() {
+            if let Some((_, method)) =
+                compressed.next_if(|(column_name, _)| column_name == &name.value)
+            {
+                // Tips: ahead of `PRIMARY KEY`, which skips the options following it
+                options.insert(
+                    0,
+                    ColumnOptionDef {
+                        name: None,
+                        option: ColumnOption::DialectSpecific(compression_tokens(method)),
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// `COMPRESSION method` of the columns.
+#[derive(Default)]
+pub(super) struct Compression {
+    columns: Vec<CompressedColumn>,
+}
+
+impl Extension for Compression {
+    fn rewrite(
+        &mut self,
+        tokens: Vec<TokenWithLocation>,
+    ) -> Result<Vec<TokenWithLocation>, ParserError> {
+        let (tokens, columns) = strip_compression(tokens);
+        self.columns = columns;
+
+        Ok(tokens)
+    }
+
+    fn attach(&mut self, stmts: &mut [Statement]) -> Result<(), ParserError> {
+        if !self.columns.is_empty() {
+            attach_compression(stmts, mem::take(&mut self.columns));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,251 @@
+use super::{current_element, is_keyword, is_word, non_whitespace_positions, Extension};
+use sqlparser::ast::{
+    AlterTableOperation, ColumnDef, ColumnOption, ColumnOptionDef, Statement, TableConstraint,
+};
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Token, TokenWithLocation};
+use std::collections::HashSet;
+use std::mem;
+
+/// The tokens of `DEFERRABLE INITIALLY DEFERRED`, which is kept as
+/// [`ColumnOption::DialectSpecific`] following the `REFERENCES` column option.
+pub(crate) fn deferred_tokens() -> Vec<Token> {
+    vec![
+        Token::make_keyword("DEFERRABLE"),
+        Token::make_keyword("INITIALLY"),
+        Token::make_keyword("DEFERRED"),
+    ]
+}
+
+/// `(n-th foreign key, whether it is a table constraint)`
+type DeferredForeignKey = (usize, bool);
+
+/// `sqlparser` does not support `[NOT] DEFERRABLE` and `INITIALLY DEFERRED | IMMEDIATE` of the
+/// foreign keys yet, so they are removed from the tokens here, and the foreign keys initially
+/// deferred are returned as `(n-th foreign key, whether it is a table constraint)`.
+fn strip_deferrable(
+    tokens: Vec<TokenWithLocation>,
+) -> Result<(Vec<TokenWithLocation>, Vec<DeferredForeignKey>), ParserError> {
+    if !tokens
+        .iter()
+        .any(|token| is_word(&token.token, "deferrable") || is_word(&token.token, "initially"))
+    {
+        return Ok((tokens, vec![]));
+    }
+    let positions = non_whitespace_positions(&tokens);
+    let mut removed = vec![false; tokens.len()];
+    let mut deferred = Vec::new();
+    // the counts of the foreign keys declared on the columns and as the table constraints
+    let mut counts = [0, 0];
+    // the last foreign key, with the first token of the column or the constraint declaring it
+    let mut foreign_key: Option<(DeferredForeignKey, Option<usize>)> = None;
+    let mut pos = 0;
+
+    while pos < positions.len() {
+        let nth = |offset: usize| positions.get(pos + offset).map(|i| &tokens[*i].token);
+
+        if nth(0) == Some(&Token::SemiColon) {
+            foreign_key = None;
+        }
+        if is_keyword(&tokens[positions[pos]], Keyword::REFERENCES) {
+            let first = current_element(&tokens[..positions[pos]]);
+            // e.g. `[CONSTRAINT name] FOREIGN KEY (c1) REFERENCES ...` of `ALTER TABLE`
+            let is_table_constraint = first.map_or(true, |i| {
+                is_keyword(&tokens[i], Keyword::FOREIGN)
+                    || is_keyword(&tokens[i], Keyword::CONSTRAINT)
+            });
+            let count = &mut counts[is_table_constraint as usize];
+
+            foreign_key = Some(((*count, is_table_constraint), first));
+            *count += 1;
+            pos += 1;
+            continue;
+        }
+        // `[NOT] DEFERRABLE` and `INITIALLY DEFERRED | IMMEDIATE`, in either order
+        let (mut len, mut deferrable, mut initially_deferred) = (0, None, None);
+
+        loop {
+            match (nth(len), nth(len + 1)) {
+                (Some(Token::Word(word)), Some(next))
+                    if word.keyword == Keyword::NOT
+                        && is_word(next, "deferrable")
+                        && deferrable.is_none() =>
+                {
+                    deferrable = Some(false);
+                    len += 2;
+                }
+                (Some(token), _) if is_word(token, "deferrable") && deferrable.is_none() => {
+                    deferrable = Some(true);
+                    len += 1;
+                }
+                (Some(token), Some(next))
+                    if is_word(token, "initially")
+                        && (is_word(next, "deferred") || is_word(next, "immediate"))
+                        && initially_deferred.is_none() =>
+                {
+                    initially_deferred = Some(is_word(next, "deferred"));
+                    len += 2;
+                }
+                _ => break,
+            }
+        }
+        if len == 0 {
+            pos += 1;
+            continue;
+        }
+        let Some((nth_foreign_key, _)) =
+            foreign_key.filter(|(_, first)| *first == current_element(&tokens[..positions[pos]]))
+        else {
+            return Err(ParserError::ParserError(
+                "`DEFERRABLE` is only supported on the foreign keys".to_string(),
+            ));
+        };
+        if initially_deferred == Some(true) {
+            if deferrable == Some(false) {
+                return Err(ParserError::ParserError(
+                    "constraint declared `INITIALLY DEFERRED` must be `DEFERRABLE`".to_string(),
+                ));
+            }
+            deferred.push(nth_foreign_key);
+        }
+        for offset in 0..len {
+            removed[positions[pos + offset]] = true;
+        }
+        pos += len;
+    }
+    let tokens = tokens
+        .into_iter()
+        .zip(removed)
+        .filter(|(_, removed)| !removed)
+        .map(|(token, _)| token)
+        .collect();
+
+    Ok((tokens, deferred))
+}
+
+/// Keeps [`deferred_tokens`] following the `REFERENCES` options of the columns initially
+/// deferred, where a table constraint initially deferred is moved to, as it is on one column.
+fn attach_deferred(
+    stmts: &mut [Statement],
+    deferred: Vec<DeferredForeignKey>,
+) -> Result<(), ParserError> {
+    let unsupported = || {
+        ParserError::ParserError(
+            "`INITIALLY DEFERRED` is only supported on the foreign keys of `CREATE TABLE`"
+                .to_string(),
+        )
+    };
+    let mut deferred = deferred.into_iter().collect::<HashSet<_>>();
+    let mut counts = [0, 0];
+    let deferred_option = || ColumnOptionDef {
+        name: None,
+        option: ColumnOption::DialectSpecific(deferred_tokens()),
+    };
+
+    for stmt in stmts.iter_mut() {
+        let (columns, constraints) = match stmt {
+            Statement::CreateTable {
+                columns,
+                constraints,
+                ..
+            } => (columns, constraints),
+            Statement::AlterTable {
+                operation: AlterTableOperation::AddConstraint(TableConstraint::ForeignKey { .. }),
+                ..
+            } => {
+                if deferred.remove(&(counts[1], true)) {
+                    return Err(unsupported());
+                }
+                counts[1] += 1;
+                continue;
+            }
+            _ => continue,
+        };
+        for ColumnDef { options, .. } in columns.iter_mut() {
+            let mut i = 0;
+
+            while i < options.len() {
+                if matches!(options[i].option, ColumnOption::ForeignKey { .. }) {
+                    if deferred.remove(&(counts[0], false)) {
+                        options.insert(i + 1, deferred_option());
+                        i += 1;
+                    }
+                    counts[0] += 1;
+                }
+                i += 1;
+            }
+        }
+        let mut i = 0;
+
+        while i < constraints.len() {
+            if !matches!(constraints[i], TableConstraint::ForeignKey { .. }) {
+                i += 1;
+                continue;
+            }
+            counts[1] += 1;
+            if !deferred.remove(&(counts[1] - 1, true)) {
+                i += 1;
+                continue;
+            }
+            let TableConstraint::ForeignKey {
+                name,
+                columns: column_names,
+                foreign_table,
+                referred_columns,
+                on_delete,
+                on_update,
+            } = constraints.remove(i)
+            else {
+                unreachable!()
+            };
+            let column = match column_names.as_slice() {
+                [column_name] => columns
+                    .iter_mut()
+                    .find(|column| column.name.value.eq_ignore_ascii_case(&column_name.value)),
+                _ => None,
+            }
+            .ok_or_else(unsupported)?;
+
+            column.options.push(ColumnOptionDef {
+                name,
+                option: ColumnOption::ForeignKey {
+                    foreign_table,
+                    referred_columns,
+                    on_delete,
+                    on_update,
+                },
+            });
+            column.options.push(deferred_option());
+        }
+    }
+    if !deferred.is_empty() {
+        return Err(unsupported());
+    }
+    Ok(())
+}
+
+/// `[NOT] DEFERRABLE` and `INITIALLY DEFERRED | IMMEDIATE` of the foreign keys.
+#[derive(Default)]
+pub(super) struct Deferrable {
+    deferred: Vec<DeferredForeignKey>,
+}
+
+impl Extension for Deferrable {
+    fn rewrite(
+        &mut self,
+        tokens: Vec<TokenWithLocation>,
+    ) -> Result<Vec<TokenWithLocation>, ParserError> {
+        let (tokens, deferred) = strip_deferrable(tokens)?;
+        self.deferred = deferred;
+
+        Ok(tokens)
+    }
+
+    fn attach(&mut self, stmts: &mut [Statement]) -> Result<(), ParserError> {
+        if self.deferred.is_empty() {
+            return Ok(());
+        }
+        attach_deferred(stmts, mem::take(&mut self.deferred))
+    }
+}
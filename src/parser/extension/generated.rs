@@ -0,0 +1,185 @@
+use super::{current_column_name, is_word, non_whitespace_positions, Extension};
+use crate::parser::DIALECT;
+use sqlparser::ast::{ColumnDef, ColumnOption, ColumnOptionDef, Expr, Statement};
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::tokenizer::{Token, TokenWithLocation};
+use std::mem;
+
+/// The tokens of `GENERATED ALWAYS AS (expr) STORED` or `VIRTUAL`, which is kept as
+/// [`ColumnOption::DialectSpecific`] of the column, see [`parse_generated`].
+fn generated_tokens(expr: Vec<Token>, is_stored: bool) -> Vec<Token> {
+    let mut tokens = vec![
+        Token::make_keyword("GENERATED"),
+        Token::make_keyword("ALWAYS"),
+        Token::make_keyword("AS"),
+        Token::LParen,
+    ];
+    tokens.extend(expr);
+    tokens.push(Token::RParen);
+    tokens.push(Token::make_keyword(if is_stored {
+        "STORED"
+    } else {
+        "VIRTUAL"
+    }));
+    tokens
+}
+
+/// Parses the tokens kept by a generated column into its expression and whether it is
+/// `STORED`, `None` if they are not the tokens of a generated column.
+pub(crate) fn parse_generated(tokens: &[Token]) -> Option<Result<(Expr, bool), ParserError>> {
+    if !tokens
+        .first()
+        .is_some_and(|token| is_word(token, "generated"))
+    {
+        return None;
+    }
+    let (Some(last), Some(expr)) = (
+        tokens.last(),
+        tokens
+            .len()
+            .checked_sub(2)
+            .and_then(|end| tokens.get(4..end)),
+    ) else {
+        return None;
+    };
+    let is_stored = is_word(last, "stored");
+
+    Some(
+        Parser::new(&DIALECT)
+            .with_tokens(expr.to_vec())
+            .parse_expr()
+            .map(|expr| (expr, is_stored)),
+    )
+}
+
+/// `(column name, tokens of generated_tokens)`
+type GeneratedColumn = (String, Vec<Token>);
+
+/// `sqlparser` does not support the generated columns yet, so `GENERATED ALWAYS AS (expr)
+/// [STORED | VIRTUAL]` is removed from the tokens here and returned with the name of the column
+/// it is declared on, as the tokens of [`generated_tokens`].
+fn strip_generated(
+    tokens: Vec<TokenWithLocation>,
+) -> Result<(Vec<TokenWithLocation>, Vec<GeneratedColumn>), ParserError> {
+    if !tokens
+        .iter()
+        .any(|token| is_word(&token.token, "generated"))
+    {
+        return Ok((tokens, vec![]));
+    }
+    let positions = non_whitespace_positions(&tokens);
+    let mut stripped: Vec<TokenWithLocation> = Vec::with_capacity(tokens.len());
+    let mut generated = Vec::new();
+    let mut removed_to = 0;
+
+    for (pos, i) in positions.iter().copied().enumerate() {
+        if i < removed_to {
+            continue;
+        }
+        let nth = |offset: usize| positions.get(pos + offset).map(|i| &tokens[*i].token);
+        // e.g. a column named `generated` or `GENERATED ... AS IDENTITY` is left to `sqlparser`
+        let is_generated = is_word(&tokens[i].token, "generated")
+            && nth(1).is_some_and(|token| is_word(token, "always"))
+            && nth(2).is_some_and(|token| is_word(token, "as"))
+            && nth(3) == Some(&Token::LParen);
+        if !is_generated {
+            continue;
+        }
+        stripped.extend(tokens[removed_to..i].iter().cloned());
+
+        let mut depth = 0;
+        let mut end = None;
+        for (offset, j) in positions[pos + 4..].iter().enumerate() {
+            match tokens[*j].token {
+                Token::LParen => depth += 1,
+                Token::RParen if depth == 0 => {
+                    end = Some((pos + 4 + offset, *j));
+                    break;
+                }
+                Token::RParen => depth -= 1,
+                _ => (),
+            }
+        }
+        let (end_pos, end) =
+            end.ok_or_else(|| ParserError::ParserError("expected `)`".to_string()))?;
+        let expr = tokens[positions[pos + 3] + 1..end]
+            .iter()
+            .map(|token| token.token.clone())
+            .collect();
+        let (is_stored, next) = match positions.get(end_pos + 1) {
+            Some(j) if is_word(&tokens[*j].token, "virtual") => (false, j + 1),
+            Some(j) if is_word(&tokens[*j].token, "stored") => (true, j + 1),
+            _ => (true, end + 1),
+        };
+        let column_name = current_column_name(&stripped).ok_or_else(|| {
+            ParserError::ParserError(
+                "`GENERATED ALWAYS AS` is only supported on the columns of `CREATE TABLE`"
+                    .to_string(),
+            )
+        })?;
+
+        generated.push((column_name, generated_tokens(expr, is_stored)));
+        removed_to = next;
+    }
+    stripped.extend(tokens[removed_to..].iter().cloned());
+
+    Ok((stripped, generated))
+}
+
+fn attach_generated(
+    stmts: &mut [Statement],
+    generated: Vec<GeneratedColumn>,
+) -> Result<(), ParserError> {
+    let mut generated = generated.into_iter().peekable();
+
+    for stmt in stmts.iter_mut() {
+        let Statement::CreateTable { columns, .. } = stmt else {
+            continue;
+        };
+        for ColumnDef { name, options, .. } in columns.iter_mut() {
+            if let Some((_, tokens)) =
+                generated.next_if(|(column_name, _)| column_name == &name.value)
+            {
+                // Tips: ahead of `PRIMARY KEY`, which skips the options following it
+                options.insert(
+                    0,
+                    ColumnOptionDef {
+                        name: None,
+                        option: ColumnOption::DialectSpecific(tokens),
+                    },
+                );
+            }
+        }
+    }
+    if generated.peek().is_some() {
+        return Err(ParserError::ParserError(
+            "`GENERATED ALWAYS AS` is only supported on the columns of `CREATE TABLE`".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// `GENERATED ALWAYS AS (expr) [STORED | VIRTUAL]` of the columns.
+#[derive(Default)]
+pub(super) struct Generated {
+    columns: Vec<GeneratedColumn>,
+}
+
+impl Extension for Generated {
+    fn rewrite(
+        &mut self,
+        tokens: Vec<TokenWithLocation>,
+    ) -> Result<Vec<TokenWithLocation>, ParserError> {
+        let (tokens, columns) = strip_generated(tokens)?;
+        self.columns = columns;
+
+        Ok(tokens)
+    }
+
+    fn attach(&mut self, stmts: &mut [Statement]) -> Result<(), ParserError> {
+        if self.columns.is_empty() {
+            return Ok(());
+        }
+        attach_generated(stmts, mem::take(&mut self.columns))
+    }
+}
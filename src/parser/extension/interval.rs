@@ -0,0 +1,81 @@
+use super::{is_keyword, non_whitespace_positions, Extension};
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Token, TokenWithLocation};
+
+fn is_interval_field(token: &TokenWithLocation) -> bool {
+    [
+        Keyword::YEAR,
+        Keyword::MONTH,
+        Keyword::WEEK,
+        Keyword::DAY,
+        Keyword::HOUR,
+        Keyword::MINUTE,
+        Keyword::SECOND,
+        Keyword::TO,
+    ]
+    .into_iter()
+    .any(|keyword| is_keyword(token, keyword))
+}
+
+/// `sqlparser` takes the operators following the literal of `INTERVAL 'literal' [field]` into
+/// the value of the interval, e.g. `INTERVAL '1 day' * 2` as `INTERVAL ('1 day' * 2)`, so the
+/// interval is parenthesized here as `(INTERVAL '1 day') * 2`.
+fn parenthesize_intervals(tokens: Vec<TokenWithLocation>) -> Vec<TokenWithLocation> {
+    if !tokens
+        .iter()
+        .any(|token| is_keyword(token, Keyword::INTERVAL))
+    {
+        return tokens;
+    }
+    let positions = non_whitespace_positions(&tokens);
+    let nth = |pos: usize| positions.get(pos).map(|i| &tokens[*i]);
+    let mut l_parens = Vec::new();
+    let mut r_parens = Vec::new();
+
+    for pos in 0..positions.len() {
+        let (Some(interval), Some(literal)) = (nth(pos), nth(pos + 1)) else {
+            continue;
+        };
+        if !is_keyword(interval, Keyword::INTERVAL)
+            || !matches!(
+                literal.token,
+                Token::SingleQuotedString(_) | Token::Number(..)
+            )
+        {
+            continue;
+        }
+        let mut last = pos + 1;
+        while nth(last + 1).is_some_and(is_interval_field) {
+            last += 1;
+        }
+        l_parens.push(positions[pos]);
+        r_parens.push(positions[last]);
+    }
+    let mut parenthesized = Vec::with_capacity(tokens.len() + l_parens.len() * 2);
+
+    for (i, token) in tokens.into_iter().enumerate() {
+        let (line, column) = (token.location.line, token.location.column);
+
+        if l_parens.contains(&i) {
+            parenthesized.push(TokenWithLocation::new(Token::LParen, line, column));
+        }
+        parenthesized.push(token);
+        if r_parens.contains(&i) {
+            parenthesized.push(TokenWithLocation::new(Token::RParen, line, column));
+        }
+    }
+    parenthesized
+}
+
+/// `INTERVAL 'literal' [field]` followed by the operators.
+pub(super) struct Intervals;
+
+impl Extension for Intervals {
+    fn rewrite(
+        &mut self,
+        tokens: Vec<TokenWithLocation>,
+    ) -> Result<Vec<TokenWithLocation>, ParserError> {
+        Ok(parenthesize_intervals(tokens))
+    }
+}
@@ -0,0 +1,173 @@
+//! The syntaxes `sqlparser` does not support yet, each of which is rewritten in the tokens into
+//! one it does before parsing, and restored onto the statements parsed if the rewrite cannot
+//! be kept in the tokens. The statements that cannot be rewritten into one it supports are
+//! parsed by [`parse_run_sql`](super::parse_run_sql) instead.
+
+mod aggregate_filter;
+mod auto_increment;
+mod compression;
+mod deferrable;
+mod generated;
+mod interval;
+mod nulls_distinct;
+mod ordinality;
+mod struct_fields;
+mod wildcard_options;
+mod with_data;
+
+use sqlparser::ast::Statement;
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Token, TokenWithLocation};
+
+pub(crate) use aggregate_filter::AGGREGATE_FILTER;
+pub(crate) use auto_increment::auto_increment_tokens;
+pub(crate) use compression::parse_compression;
+pub(crate) use deferrable::deferred_tokens;
+pub(crate) use generated::parse_generated;
+pub(crate) use nulls_distinct::nulls_distinct_tokens;
+pub(crate) use ordinality::ORDINALITY;
+pub(crate) use struct_fields::parse_struct_fields;
+pub(crate) use wildcard_options::wildcard_options;
+
+/// The quote of the identifiers an extension rewrites a syntax into, e.g. `` `FILTER` ``.
+///
+/// The tokenizer of [`parse_sql`](super::parse_sql) does not take it as a quote, so these
+/// identifiers cannot be written by the users, while the SQL displayed from the statements
+/// keeps them for [`parse_stored_sql`](super::parse_stored_sql).
+pub(crate) const MARKER_QUOTE: char = '`';
+
+pub(crate) trait Extension {
+    /// Rewrites the syntax in the tokens into one `sqlparser` supports, the tokens are returned
+    /// as they are if the syntax is not used.
+    fn rewrite(
+        &mut self,
+        tokens: Vec<TokenWithLocation>,
+    ) -> Result<Vec<TokenWithLocation>, ParserError>;
+
+    /// Restores what [`Extension::rewrite`] removed from the tokens onto the statements parsed.
+    fn attach(&mut self, _stmts: &mut [Statement]) -> Result<(), ParserError> {
+        Ok(())
+    }
+}
+
+pub(crate) struct Extensions(Vec<Box<dyn Extension>>);
+
+impl Extensions {
+    /// The extensions of the statements, in the order they rewrite the tokens.
+    pub(crate) fn statements() -> Self {
+        Extensions(vec![
+            Box::new(struct_fields::StructFields),
+            Box::new(ordinality::WithOrdinality),
+            Box::new(aggregate_filter::AggregateFilter),
+            Box::new(interval::Intervals),
+            // Tips: after the extensions of the expressions, which `REPLACE (expr AS c)` may use
+            Box::new(wildcard_options::WildcardOptions),
+            Box::<nulls_distinct::NullsDistinct>::default(),
+            Box::<auto_increment::AutoIncrement>::default(),
+            Box::<generated::Generated>::default(),
+            Box::<compression::Compression>::default(),
+            Box::<deferrable::Deferrable>::default(),
+            Box::<with_data::WithData>::default(),
+        ])
+    }
+
+    /// The extensions of an expression, in the order they rewrite the tokens.
+    pub(crate) fn expr() -> Self {
+        Extensions(vec![
+            Box::new(struct_fields::StructFields),
+            Box::new(aggregate_filter::AggregateFilter),
+            Box::new(interval::Intervals),
+        ])
+    }
+
+    pub(crate) fn rewrite(
+        &mut self,
+        tokens: Vec<TokenWithLocation>,
+    ) -> Result<Vec<TokenWithLocation>, ParserError> {
+        self.0
+            .iter_mut()
+            .try_fold(tokens, |tokens, extension| extension.rewrite(tokens))
+    }
+
+    pub(crate) fn attach(&mut self, stmts: &mut [Statement]) -> Result<(), ParserError> {
+        for extension in self.0.iter_mut() {
+            extension.attach(stmts)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_keyword(token: &TokenWithLocation, keyword: Keyword) -> bool {
+    matches!(&token.token, Token::Word(word) if word.keyword == keyword)
+}
+
+fn is_word(token: &Token, word: &str) -> bool {
+    matches!(token, Token::Word(next) if next.quote_style.is_none() && next.value.eq_ignore_ascii_case(word))
+}
+
+/// The positions of the tokens other than the whitespaces.
+fn non_whitespace_positions(tokens: &[TokenWithLocation]) -> Vec<usize> {
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| !matches!(token.token, Token::Whitespace(_)))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// The position of the first token of the column definition or the table constraint the tokens
+/// end in, which starts after the `(` or the `,` of the columns of the table.
+fn current_element(tokens: &[TokenWithLocation]) -> Option<usize> {
+    let mut depth = 0;
+    let mut start = None;
+
+    for (i, token) in tokens.iter().enumerate().rev() {
+        match token.token {
+            Token::RParen => depth += 1,
+            Token::LParen | Token::Comma if depth == 0 => {
+                start = Some(i + 1);
+                break;
+            }
+            Token::LParen => depth -= 1,
+            _ => (),
+        }
+    }
+    start.and_then(|start| {
+        (start..tokens.len()).find(|i| !matches!(tokens[*i].token, Token::Whitespace(_)))
+    })
+}
+
+/// The name of the column whose definition the tokens end in.
+fn current_column_name(tokens: &[TokenWithLocation]) -> Option<String> {
+    current_element(tokens).and_then(|i| match &tokens[i].token {
+        Token::Word(word) => Some(word.value.clone()),
+        _ => None,
+    })
+}
+
+/// The position of the parenthesis matching the one at `pos`, where `nth` is the `pos`-th token.
+fn find_matching_paren<'a>(
+    nth: impl Fn(usize) -> Option<&'a TokenWithLocation>,
+    mut pos: usize,
+    forward: bool,
+) -> Option<usize> {
+    let mut depth = 0;
+
+    loop {
+        match nth(pos).map(|token| &token.token) {
+            Some(Token::LParen) => depth += 1,
+            Some(Token::RParen) => depth -= 1,
+            Some(_) => (),
+            None => return None,
+        }
+        if depth == 0 {
+            return Some(pos);
+        }
+        pos = if forward {
+            pos + 1
+        } else {
+            pos.checked_sub(1)?
+        };
+    }
+}
@@ -0,0 +1,174 @@
+use super::{is_keyword, non_whitespace_positions, Extension};
+use sqlparser::ast::{AlterTableOperation, ColumnDef, ColumnOption, ColumnOptionDef, Statement};
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Token, TokenWithLocation};
+use std::mem;
+
+/// The tokens of `NULLS [NOT] DISTINCT`, which is kept as [`ColumnOption::DialectSpecific`]
+/// following the `UNIQUE` column option.
+pub(crate) fn nulls_distinct_tokens(nulls_not_distinct: bool) -> Vec<Token> {
+    if nulls_not_distinct {
+        vec![
+            Token::make_keyword("NULLS"),
+            Token::make_keyword("NOT"),
+            Token::make_keyword("DISTINCT"),
+        ]
+    } else {
+        vec![
+            Token::make_keyword("NULLS"),
+            Token::make_keyword("DISTINCT"),
+        ]
+    }
+}
+
+/// `(n-th UNIQUE column option, nulls_not_distinct)`
+type NullsDistinctModifier = (usize, bool);
+
+/// `sqlparser` does not support `UNIQUE NULLS [NOT] DISTINCT` yet, so the modifier is removed
+/// from the tokens here and returned as `(n-th UNIQUE column option, nulls_not_distinct)`.
+fn strip_nulls_distinct(
+    tokens: Vec<TokenWithLocation>,
+) -> Result<(Vec<TokenWithLocation>, Vec<NullsDistinctModifier>), ParserError> {
+    if !tokens.iter().any(|token| is_keyword(token, Keyword::NULLS)) {
+        return Ok((tokens, vec![]));
+    }
+    let positions = non_whitespace_positions(&tokens);
+    let mut removed = vec![false; tokens.len()];
+    let mut modifiers = Vec::new();
+    let mut unique_option_count = 0;
+
+    for (pos, i) in positions.iter().enumerate() {
+        if !is_keyword(&tokens[*i], Keyword::UNIQUE) {
+            continue;
+        }
+        let nth = |offset: usize| positions.get(pos + offset).map(|i| &tokens[*i]);
+        let is_create_index = pos > 0 && is_keyword(&tokens[positions[pos - 1]], Keyword::CREATE);
+        let (modifier_len, nulls_not_distinct) = match (nth(1), nth(2), nth(3)) {
+            (Some(nulls), Some(not), Some(distinct))
+                if is_keyword(nulls, Keyword::NULLS)
+                    && is_keyword(not, Keyword::NOT)
+                    && is_keyword(distinct, Keyword::DISTINCT) =>
+            {
+                (3, true)
+            }
+            (Some(nulls), Some(distinct), _)
+                if is_keyword(nulls, Keyword::NULLS) && is_keyword(distinct, Keyword::DISTINCT) =>
+            {
+                (2, false)
+            }
+            _ => (0, false),
+        };
+        let is_table_constraint = matches!(
+            nth(modifier_len + 1).map(|token| &token.token),
+            Some(Token::LParen)
+        );
+        if is_create_index || is_table_constraint {
+            if modifier_len > 0 {
+                return Err(ParserError::ParserError(
+                    "`NULLS [NOT] DISTINCT` is only supported on the `UNIQUE` column constraint"
+                        .to_string(),
+                ));
+            }
+            continue;
+        }
+        if modifier_len > 0 {
+            for offset in 1..=modifier_len {
+                removed[positions[pos + offset]] = true;
+            }
+            modifiers.push((unique_option_count, nulls_not_distinct));
+        }
+        unique_option_count += 1;
+    }
+    let tokens = tokens
+        .into_iter()
+        .zip(removed)
+        .filter(|(_, removed)| !removed)
+        .map(|(token, _)| token)
+        .collect();
+
+    Ok((tokens, modifiers))
+}
+
+fn attach_nulls_distinct(
+    stmts: &mut [Statement],
+    mut modifiers: Vec<NullsDistinctModifier>,
+) -> Result<(), ParserError> {
+    let mut column_defs = Vec::new();
+
+    for stmt in stmts.iter_mut() {
+        match stmt {
+            Statement::CreateTable { columns, .. } => column_defs.extend(columns.iter_mut()),
+            Statement::AlterTable {
+                operation: AlterTableOperation::AddColumn { column_def, .. },
+                ..
+            } => column_defs.push(column_def),
+            _ => (),
+        }
+    }
+    modifiers.reverse();
+    let mut unique_option_count = 0;
+
+    for ColumnDef { options, .. } in column_defs {
+        let mut i = 0;
+
+        while i < options.len() {
+            if matches!(
+                options[i].option,
+                ColumnOption::Unique { is_primary: false }
+            ) {
+                if let Some((_, nulls_not_distinct)) = modifiers
+                    .last()
+                    .filter(|(nth, _)| *nth == unique_option_count)
+                    .copied()
+                {
+                    modifiers.pop();
+                    options.insert(
+                        i + 1,
+                        ColumnOptionDef {
+                            name: None,
+                            option: ColumnOption::DialectSpecific(nulls_distinct_tokens(
+                                nulls_not_distinct,
+                            )),
+                        },
+                    );
+                    i += 1;
+                }
+                unique_option_count += 1;
+            }
+            i += 1;
+        }
+    }
+    if !modifiers.is_empty() {
+        return Err(ParserError::ParserError(
+            "`NULLS [NOT] DISTINCT` is only supported on the `UNIQUE` column constraint"
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// `UNIQUE NULLS [NOT] DISTINCT` of the columns.
+#[derive(Default)]
+pub(super) struct NullsDistinct {
+    modifiers: Vec<NullsDistinctModifier>,
+}
+
+impl Extension for NullsDistinct {
+    fn rewrite(
+        &mut self,
+        tokens: Vec<TokenWithLocation>,
+    ) -> Result<Vec<TokenWithLocation>, ParserError> {
+        let (tokens, modifiers) = strip_nulls_distinct(tokens)?;
+        self.modifiers = modifiers;
+
+        Ok(tokens)
+    }
+
+    fn attach(&mut self, stmts: &mut [Statement]) -> Result<(), ParserError> {
+        if self.modifiers.is_empty() {
+            return Ok(());
+        }
+        attach_nulls_distinct(stmts, mem::take(&mut self.modifiers))
+    }
+}
@@ -0,0 +1,104 @@
+use super::{find_matching_paren, is_keyword, non_whitespace_positions, Extension, MARKER_QUOTE};
+use sqlparser::keywords::{Keyword, RESERVED_FOR_TABLE_ALIAS};
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Token, TokenWithLocation};
+
+/// The table hint that [`move_with_ordinality`] turns `WITH ORDINALITY` into.
+pub(crate) const ORDINALITY: &str = "ORDINALITY";
+
+fn is_ordinality(token: &TokenWithLocation) -> bool {
+    matches!(&token.token, Token::Word(word) if word.quote_style.is_none() && word.value.eq_ignore_ascii_case(ORDINALITY))
+}
+
+/// `sqlparser` does not support `f(...) WITH ORDINALITY [AS t(...)]` yet, so the modifier is
+/// moved here behind the alias as the table hint ``f(...) [AS t(...)] WITH (`ORDINALITY`)``.
+/// `TABLE(f(...))` is unwrapped into `f(...)` on the way since only a table can have hints.
+fn move_with_ordinality(
+    tokens: Vec<TokenWithLocation>,
+) -> Result<Vec<TokenWithLocation>, ParserError> {
+    if !tokens.iter().any(is_ordinality) {
+        return Ok(tokens);
+    }
+    let positions = non_whitespace_positions(&tokens);
+    let nth = |pos: usize| positions.get(pos).map(|i| &tokens[*i]);
+    let matching_paren = |pos: usize, forward: bool| find_matching_paren(nth, pos, forward);
+    let mut removed = vec![false; tokens.len()];
+    let mut hints = Vec::new();
+
+    for pos in 1..positions.len() {
+        let (Some(with), Some(ordinality)) = (nth(pos), nth(pos + 1)) else {
+            continue;
+        };
+        if !is_keyword(with, Keyword::WITH)
+            || !is_ordinality(ordinality)
+            || !matches!(nth(pos - 1).map(|token| &token.token), Some(Token::RParen))
+        {
+            continue;
+        }
+        let Some(l_paren) = matching_paren(pos - 1, false) else {
+            continue;
+        };
+        // `TABLE(f(...))`
+        if l_paren > 0 && nth(l_paren - 1).is_some_and(|token| is_keyword(token, Keyword::TABLE)) {
+            for pos in [l_paren - 1, l_paren, pos - 1] {
+                removed[positions[pos]] = true;
+            }
+        }
+        removed[positions[pos]] = true;
+        removed[positions[pos + 1]] = true;
+
+        let mut last = pos + 1;
+        let is_alias = |token: &TokenWithLocation| match &token.token {
+            Token::Word(word) => {
+                word.quote_style.is_some() || !RESERVED_FOR_TABLE_ALIAS.contains(&word.keyword)
+            }
+            _ => false,
+        };
+        if nth(last + 1).is_some_and(|token| is_keyword(token, Keyword::AS)) {
+            last += 2;
+        } else if nth(last + 1).is_some_and(is_alias) {
+            last += 1;
+        }
+        if last > pos + 1 && matches!(nth(last + 1).map(|token| &token.token), Some(Token::LParen))
+        {
+            last = matching_paren(last + 1, true).ok_or_else(|| {
+                ParserError::ParserError("Expected ), found: EOF after the table alias".to_string())
+            })?;
+        }
+        hints.push(positions[last]);
+    }
+    let mut moved = Vec::with_capacity(tokens.len() + hints.len() * 4);
+
+    for (i, (token, removed)) in tokens.into_iter().zip(removed).enumerate() {
+        let (line, column) = (token.location.line, token.location.column);
+
+        if !removed {
+            moved.push(token);
+        }
+        if hints.contains(&i) {
+            moved.extend(
+                [
+                    Token::make_keyword("WITH"),
+                    Token::LParen,
+                    Token::make_word(ORDINALITY, Some(MARKER_QUOTE)),
+                    Token::RParen,
+                ]
+                .into_iter()
+                .map(|token| TokenWithLocation::new(token, line, column)),
+            );
+        }
+    }
+    Ok(moved)
+}
+
+/// `WITH ORDINALITY` of the table functions.
+pub(super) struct WithOrdinality;
+
+impl Extension for WithOrdinality {
+    fn rewrite(
+        &mut self,
+        tokens: Vec<TokenWithLocation>,
+    ) -> Result<Vec<TokenWithLocation>, ParserError> {
+        move_with_ordinality(tokens)
+    }
+}
@@ -0,0 +1,95 @@
+use super::Extension;
+use crate::parser::DIALECT;
+use sqlparser::ast::{DataType, Ident};
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::tokenizer::{Token, TokenWithLocation, Tokenizer};
+
+/// `sqlparser` does not support `STRUCT(name type, ...)` yet and parses it as a custom type
+/// whose modifiers can only be words, numbers or strings, so the fields are quoted here into a
+/// single string modifier: `STRUCT('name type, ...')`, see [`parse_struct_fields`]. It needs no
+/// [`MARKER_QUOTE`](super::MARKER_QUOTE) since a custom type named `STRUCT` has no other meaning.
+fn quote_struct_fields(
+    tokens: Vec<TokenWithLocation>,
+) -> Result<Vec<TokenWithLocation>, ParserError> {
+    let mut quoted = Vec::with_capacity(tokens.len());
+    let mut iter = tokens.into_iter().peekable();
+
+    while let Some(token) = iter.next() {
+        let is_struct =
+            matches!(&token.token, Token::Word(word) if word.value.eq_ignore_ascii_case("struct"));
+        quoted.push(token);
+
+        if !is_struct {
+            continue;
+        }
+        while matches!(
+            iter.peek().map(|token| &token.token),
+            Some(Token::Whitespace(_))
+        ) {
+            iter.next();
+        }
+        let Some(lparen) = iter.next_if(|token| token.token == Token::LParen) else {
+            continue;
+        };
+        let mut depth = 1;
+        let mut fields = String::new();
+
+        for token in iter.by_ref() {
+            match token.token {
+                Token::LParen => depth += 1,
+                Token::RParen => depth -= 1,
+                _ => (),
+            }
+            if depth == 0 {
+                break;
+            }
+            fields.push_str(&token.token.to_string());
+        }
+        if depth != 0 {
+            return Err(ParserError::ParserError(
+                "Expected ), found: EOF after STRUCT fields".to_string(),
+            ));
+        }
+        let (line, column) = (lparen.location.line, lparen.location.column);
+        quoted.push(lparen);
+        quoted.push(TokenWithLocation::new(
+            Token::SingleQuotedString(fields),
+            line,
+            column,
+        ));
+        quoted.push(TokenWithLocation::new(Token::RParen, line, column));
+    }
+    Ok(quoted)
+}
+
+/// Parse the fields of `STRUCT(name type, ...)` quoted by [`quote_struct_fields`].
+pub(crate) fn parse_struct_fields(fields: &str) -> Result<Vec<(Ident, DataType)>, ParserError> {
+    let tokens = Tokenizer::new(&DIALECT, fields).tokenize_with_location()?;
+    let mut parser = Parser::new(&DIALECT).with_tokens_with_locations(quote_struct_fields(tokens)?);
+    let mut struct_fields = Vec::new();
+
+    loop {
+        let name = parser.parse_identifier()?;
+        let data_type = parser.parse_data_type()?;
+        struct_fields.push((name, data_type));
+
+        if !parser.consume_token(&Token::Comma) {
+            break;
+        }
+    }
+    parser.expect_token(&Token::EOF)?;
+
+    Ok(struct_fields)
+}
+
+/// `STRUCT(name type, ...)` of the data types.
+pub(super) struct StructFields;
+
+impl Extension for StructFields {
+    fn rewrite(
+        &mut self,
+        tokens: Vec<TokenWithLocation>,
+    ) -> Result<Vec<TokenWithLocation>, ParserError> {
+        quote_struct_fields(tokens)
+    }
+}
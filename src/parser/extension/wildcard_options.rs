@@ -0,0 +1,156 @@
+use super::{is_keyword, Extension, MARKER_QUOTE};
+use crate::parser::{DIALECT, STORED_DIALECT};
+use sqlparser::ast::{ObjectName, SelectItem, WildcardAdditionalOptions};
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::tokenizer::{Token, TokenWithLocation, Tokenizer};
+
+/// `sqlparser` only supports `* EXCLUDE (...)` and `* REPLACE (... AS ...)` with other dialects,
+/// so the modifiers are parsed here and quoted into the last part of a qualified wildcard:
+/// ``[t.]`EXCLUDE (...) REPLACE (...)`.*``, see [`wildcard_options`].
+fn quote_wildcard_options(
+    tokens: Vec<TokenWithLocation>,
+) -> Result<Vec<TokenWithLocation>, ParserError> {
+    let is_modifier = |token: &TokenWithLocation| {
+        is_keyword(token, Keyword::EXCLUDE) || is_keyword(token, Keyword::REPLACE)
+    };
+    if !tokens.iter().any(is_modifier) {
+        return Ok(tokens);
+    }
+    let mut quoted: Vec<TokenWithLocation> = Vec::with_capacity(tokens.len());
+    let mut iter = tokens.into_iter().peekable();
+
+    while let Some(token) = iter.next() {
+        let is_wildcard = token.token == Token::Mul
+            && quoted
+                .iter()
+                .rev()
+                .find(|token| !matches!(token.token, Token::Whitespace(_)))
+                .is_some_and(|prev| {
+                    matches!(prev.token, Token::Comma | Token::Period)
+                        || is_keyword(prev, Keyword::SELECT)
+                        || is_keyword(prev, Keyword::DISTINCT)
+                        || is_keyword(prev, Keyword::ALL)
+                });
+        if !is_wildcard {
+            quoted.push(token);
+            continue;
+        }
+        let mut modifiers = vec![token.clone()];
+        let mut skipped = Vec::new();
+
+        loop {
+            while let Some(whitespace) =
+                iter.next_if(|token| matches!(token.token, Token::Whitespace(_)))
+            {
+                skipped.push(whitespace);
+            }
+            let Some(keyword) = iter.next_if(is_modifier) else {
+                break;
+            };
+            skipped.clear();
+            modifiers.push(keyword);
+
+            while iter
+                .peek()
+                .is_some_and(|token| matches!(token.token, Token::Whitespace(_)))
+            {
+                iter.next();
+            }
+            if iter.peek().map(|token| &token.token) != Some(&Token::LParen) {
+                // `EXCLUDE column`
+                modifiers.extend(iter.next());
+                continue;
+            }
+            let mut depth = 0;
+
+            for token in iter.by_ref() {
+                match token.token {
+                    Token::LParen => depth += 1,
+                    Token::RParen => depth -= 1,
+                    _ => (),
+                }
+                modifiers.push(token);
+
+                if depth == 0 {
+                    break;
+                }
+            }
+            if depth != 0 {
+                return Err(ParserError::ParserError(
+                    "Expected ), found: EOF after the wildcard modifiers".to_string(),
+                ));
+            }
+        }
+        if modifiers.len() == 1 {
+            quoted.push(token);
+            quoted.append(&mut skipped);
+            continue;
+        }
+        let options = parse_wildcard_options(modifiers)?;
+
+        let (line, column) = (token.location.line, token.location.column);
+        quoted.push(TokenWithLocation::new(
+            Token::make_word(options.to_string().trim_start(), Some(MARKER_QUOTE)),
+            line,
+            column,
+        ));
+        quoted.push(TokenWithLocation::new(Token::Period, line, column));
+        quoted.push(token);
+        quoted.append(&mut skipped);
+    }
+    Ok(quoted)
+}
+
+/// Parses the tokens of `* [EXCLUDE ...] [REPLACE ...]`.
+fn parse_wildcard_options(
+    tokens: Vec<TokenWithLocation>,
+) -> Result<WildcardAdditionalOptions, ParserError> {
+    let mut parser = Parser::new(&DIALECT).with_tokens_with_locations(tokens);
+
+    parser.expect_token(&Token::Mul)?;
+    let options = WildcardAdditionalOptions {
+        opt_exclude: parser.parse_optional_select_item_exclude()?,
+        opt_replace: parser.parse_optional_select_item_replace()?,
+        ..Default::default()
+    };
+    parser.expect_token(&Token::EOF)?;
+
+    Ok(options)
+}
+
+/// Restores the wildcard whose `EXCLUDE` and `REPLACE` modifiers are quoted by
+/// [`WildcardOptions`], returns `None` if `item` has no modifiers.
+pub(crate) fn wildcard_options(item: &SelectItem) -> Result<Option<SelectItem>, ParserError> {
+    let SelectItem::QualifiedWildcard(ObjectName(idents), _) = item else {
+        return Ok(None);
+    };
+    let Some((last, qualifier)) = idents
+        .split_last()
+        .filter(|(last, _)| last.quote_style == Some(MARKER_QUOTE))
+    else {
+        return Ok(None);
+    };
+    // Tips: the modifiers may keep the identifiers quoted by the other extensions
+    let tokens =
+        Tokenizer::new(&STORED_DIALECT, &format!("* {}", last.value)).tokenize_with_location()?;
+    let options = parse_wildcard_options(tokens)?;
+
+    Ok(Some(if qualifier.is_empty() {
+        SelectItem::Wildcard(options)
+    } else {
+        SelectItem::QualifiedWildcard(ObjectName(qualifier.to_vec()), options)
+    }))
+}
+
+/// `* EXCLUDE (...)` and `* REPLACE (... AS ...)` of the select items.
+pub(super) struct WildcardOptions;
+
+impl Extension for WildcardOptions {
+    fn rewrite(
+        &mut self,
+        tokens: Vec<TokenWithLocation>,
+    ) -> Result<Vec<TokenWithLocation>, ParserError> {
+        quote_wildcard_options(tokens)
+    }
+}
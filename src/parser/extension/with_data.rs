@@ -0,0 +1,115 @@
+use super::{is_keyword, is_word, Extension};
+use sqlparser::ast::{Expr, Statement, Value};
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Token, TokenWithLocation};
+use std::mem;
+
+/// `sqlparser` does not support `WITH [NO] DATA` ending `CREATE TABLE ... AS query`, so it is
+/// removed from the tokens here and returned as the positions of the statements it ends with
+/// whether the table is filled.
+fn strip_with_data(tokens: Vec<TokenWithLocation>) -> (Vec<TokenWithLocation>, Vec<(usize, bool)>) {
+    let mut stripped: Vec<TokenWithLocation> = Vec::with_capacity(tokens.len());
+    let mut with_data = Vec::new();
+    let mut nth = 0;
+    let mut is_empty = true;
+
+    for token in tokens {
+        if token.token != Token::SemiColon {
+            is_empty &= matches!(token.token, Token::Whitespace(_));
+            stripped.push(token);
+            continue;
+        }
+        if !is_empty {
+            if let Some(is_filled) = strip_trailing_with_data(&mut stripped) {
+                with_data.push((nth, is_filled));
+            }
+            nth += 1;
+            is_empty = true;
+        }
+        stripped.push(token);
+    }
+    if !is_empty {
+        if let Some(is_filled) = strip_trailing_with_data(&mut stripped) {
+            with_data.push((nth, is_filled));
+        }
+    }
+
+    (stripped, with_data)
+}
+
+/// Removes `WITH [NO] DATA` the tokens end in, and returns whether it is `WITH DATA`.
+fn strip_trailing_with_data(tokens: &mut Vec<TokenWithLocation>) -> Option<bool> {
+    let words = tokens
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, token)| !matches!(token.token, Token::Whitespace(_)))
+        .take(3)
+        .collect::<Vec<_>>();
+
+    let (start, is_filled) = match words.as_slice() {
+        [(_, data), (_, no), (i, with)]
+            if is_word(&data.token, "data")
+                && is_word(&no.token, "no")
+                && is_keyword(with, Keyword::WITH) =>
+        {
+            (*i, false)
+        }
+        [(_, data), (i, with), ..]
+            if is_word(&data.token, "data") && is_keyword(with, Keyword::WITH) =>
+        {
+            (*i, true)
+        }
+        _ => return None,
+    };
+    tokens.truncate(start);
+
+    Some(is_filled)
+}
+
+/// `WITH NO DATA` creates the table only, which is done by filling it with no rows of the query.
+fn attach_with_data(
+    stmts: &mut [Statement],
+    with_data: Vec<(usize, bool)>,
+) -> Result<(), ParserError> {
+    for (nth, is_filled) in with_data {
+        let Some(Statement::CreateTable {
+            query: Some(query), ..
+        }) = stmts.get_mut(nth)
+        else {
+            return Err(ParserError::ParserError(
+                "`WITH [NO] DATA` is only supported on `CREATE TABLE ... AS`".to_string(),
+            ));
+        };
+        if !is_filled {
+            query.limit = Some(Expr::Value(Value::Number("0".to_string(), false)));
+        }
+    }
+    Ok(())
+}
+
+/// `WITH [NO] DATA` of `CREATE TABLE ... AS query`.
+#[derive(Default)]
+pub(super) struct WithData {
+    with_data: Vec<(usize, bool)>,
+}
+
+impl Extension for WithData {
+    fn rewrite(
+        &mut self,
+        tokens: Vec<TokenWithLocation>,
+    ) -> Result<Vec<TokenWithLocation>, ParserError> {
+        let (tokens, with_data) = strip_with_data(tokens);
+        self.with_data = with_data;
+
+        Ok(tokens)
+    }
+
+    fn attach(&mut self, stmts: &mut [Statement]) -> Result<(), ParserError> {
+        if self.with_data.is_empty() {
+            return Ok(());
+        }
+        attach_with_data(stmts, mem::take(&mut self.with_data))
+    }
+}
@@ -1,8 +1,46 @@
+mod extension;
+
+use extension::Extensions;
+use sqlparser::ast::{Expr, ObjectName};
+use sqlparser::dialect::Dialect;
+use sqlparser::keywords::Keyword;
 use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::{Token, TokenWithLocation, Tokenizer};
 use sqlparser::{ast::Statement, dialect::PostgreSqlDialect, parser::Parser};
 
+pub(crate) use extension::{
+    auto_increment_tokens, deferred_tokens, nulls_distinct_tokens, parse_compression,
+    parse_generated, parse_struct_fields, wildcard_options, AGGREGATE_FILTER, MARKER_QUOTE,
+    ORDINALITY,
+};
+
 const DIALECT: PostgreSqlDialect = PostgreSqlDialect {};
 
+const STORED_DIALECT: StoredDialect = StoredDialect {};
+
+/// How deep statements, queries and expressions may nest while parsing, the default of
+/// `sqlparser`, past which [`ParserError::RecursionLimitExceeded`] is returned.
+pub const PARSER_RECURSION_LIMIT: usize = 50;
+
+/// Tokenizes the SQL displayed from the statements, which may keep the identifiers quoted by
+/// [`MARKER_QUOTE`], as [`DIALECT`] otherwise.
+#[derive(Debug)]
+struct StoredDialect {}
+
+impl Dialect for StoredDialect {
+    fn is_delimited_identifier_start(&self, ch: char) -> bool {
+        ch == '"' || ch == MARKER_QUOTE
+    }
+
+    fn is_identifier_start(&self, ch: char) -> bool {
+        DIALECT.is_identifier_start(ch)
+    }
+
+    fn is_identifier_part(&self, ch: char) -> bool {
+        DIALECT.is_identifier_part(ch)
+    }
+}
+
 /// Parse a string to a collection of statements.
 ///
 /// # Example
@@ -16,5 +54,223 @@ const DIALECT: PostgreSqlDialect = PostgreSqlDialect {};
 /// println!("{:?}", ast);
 /// ```
 pub fn parse_sql<S: AsRef<str>>(sql: S) -> Result<Vec<Statement>, ParserError> {
-    Parser::parse_sql(&DIALECT, sql.as_ref())
+    parse_statements(&DIALECT, sql.as_ref())
+}
+
+/// Parse the SQL displayed from the statements parsed, e.g. the query of a view kept as SQL.
+pub(crate) fn parse_stored_sql<S: AsRef<str>>(sql: S) -> Result<Vec<Statement>, ParserError> {
+    parse_statements(&STORED_DIALECT, sql.as_ref())
+}
+
+fn parse_statements(dialect: &dyn Dialect, sql: &str) -> Result<Vec<Statement>, ParserError> {
+    parse_tokens(Tokenizer::new(dialect, sql).tokenize_with_location()?)
+}
+
+fn parse_tokens(tokens: Vec<TokenWithLocation>) -> Result<Vec<Statement>, ParserError> {
+    let mut extensions = Extensions::statements();
+    let mut stmts = Parser::new(&DIALECT)
+        .with_recursion_limit(PARSER_RECURSION_LIMIT)
+        .with_tokens_with_locations(extensions.rewrite(tokens)?)
+        .parse_statements()?;
+    extensions.attach(&mut stmts)?;
+
+    Ok(stmts)
+}
+
+/// Parse a string to an expression, e.g. the predicate of a `CHECK` constraint kept as SQL.
+pub(crate) fn parse_expr<S: AsRef<str>>(sql: S) -> Result<Expr, ParserError> {
+    let tokens = Tokenizer::new(&STORED_DIALECT, sql.as_ref()).tokenize_with_location()?;
+
+    Parser::new(&DIALECT)
+        .with_recursion_limit(PARSER_RECURSION_LIMIT)
+        .with_tokens_with_locations(Extensions::expr().rewrite(tokens)?)
+        .parse_expr()
+}
+
+/// A statement `sqlparser` does not support yet, parsed by [`parse_run_sql`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum ExtendedStatement {
+    /// `NOTIFY channel [, 'payload']`
+    Notify { channel: String, payload: String },
+    /// `REFRESH MATERIALIZED VIEW name`
+    Refresh { name: ObjectName },
+    /// `TRUNCATE [TABLE] name RESTART IDENTITY | CONTINUE IDENTITY`, where `restart_identity`
+    /// is whether the sequence of the table is restarted
+    Truncate {
+        name: ObjectName,
+        restart_identity: bool,
+    },
+}
+
+/// The statements parsed by [`parse_run_sql`].
+#[derive(Debug)]
+pub enum ParsedSql {
+    Extended(ExtendedStatement),
+    Statements(Vec<Statement>),
+}
+
+/// Parse the SQL run by the sessions, which may also be one of [`ExtendedStatement`].
+///
+/// This is the only pre-parse stage: the SQL is tokenized once, the statements `sqlparser`
+/// does not support are parsed from the tokens first, and the other ones by `sqlparser` from
+/// the same tokens as [`parse_sql`] does. So both read the comments and the quoted identifiers
+/// the same way, e.g. `/* c */ NOTIFY "Events"` or a table named `"refresh"`.
+pub fn parse_run_sql<S: AsRef<str>>(sql: S) -> Result<ParsedSql, ParserError> {
+    let tokens = Tokenizer::new(&DIALECT, sql.as_ref()).tokenize_with_location()?;
+    let first_word = tokens.iter().find_map(|token| match &token.token {
+        Token::Whitespace(_) => None,
+        Token::Word(word) if word.quote_style.is_none() => Some(word.value.to_lowercase()),
+        _ => Some(String::new()),
+    });
+    if matches!(
+        first_word.as_deref(),
+        Some("notify" | "refresh" | "truncate")
+    ) {
+        let mut parser = Parser::new(&DIALECT).with_tokens_with_locations(tokens.clone());
+
+        if let Some(stmt) = parse_extended_statement(&mut parser)? {
+            return Ok(ParsedSql::Extended(stmt));
+        }
+    }
+    parse_tokens(tokens).map(ParsedSql::Statements)
+}
+
+fn parse_extended_statement(parser: &mut Parser) -> Result<Option<ExtendedStatement>, ParserError> {
+    let stmt = if parse_word(parser, "notify") {
+        parse_notify(parser)?
+    } else if parse_word(parser, "refresh") {
+        parse_refresh(parser)?
+    } else if parse_word(parser, "truncate") {
+        match parse_truncate(parser)? {
+            Some(stmt) => stmt,
+            // e.g. `TRUNCATE t`, which `sqlparser` supports
+            None => return Ok(None),
+        }
+    } else {
+        return Ok(None);
+    };
+    let _ = parser.consume_token(&Token::SemiColon);
+    let next = parser.next_token();
+    if next.token != Token::EOF {
+        return parser.expected("end of statement", next);
+    }
+
+    Ok(Some(stmt))
+}
+
+/// `NOTIFY channel [, 'payload']`, after `NOTIFY`.
+fn parse_notify(parser: &mut Parser) -> Result<ExtendedStatement, ParserError> {
+    let ident = parser.parse_identifier()?;
+    let channel = if ident.quote_style.is_some() {
+        ident.value
+    } else {
+        ident.value.to_lowercase()
+    };
+    let payload = if parser.consume_token(&Token::Comma) {
+        parser.parse_literal_string()?
+    } else {
+        String::new()
+    };
+
+    Ok(ExtendedStatement::Notify { channel, payload })
+}
+
+/// `REFRESH MATERIALIZED VIEW name`, after `REFRESH`.
+fn parse_refresh(parser: &mut Parser) -> Result<ExtendedStatement, ParserError> {
+    parser.expect_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW])?;
+    let name = parser.parse_object_name()?;
+
+    Ok(ExtendedStatement::Refresh { name })
+}
+
+/// `TRUNCATE [TABLE] name RESTART IDENTITY | CONTINUE IDENTITY`, after `TRUNCATE`. Returns
+/// `None` without either option.
+fn parse_truncate(parser: &mut Parser) -> Result<Option<ExtendedStatement>, ParserError> {
+    let _ = parser.parse_keyword(Keyword::TABLE);
+    let name = parser.parse_object_name()?;
+    let restart_identity = if parse_word(parser, "restart") {
+        true
+    } else if parse_word(parser, "continue") {
+        false
+    } else {
+        return Ok(None);
+    };
+    if !parse_word(parser, "identity") {
+        return parser.expected("IDENTITY", parser.peek_token());
+    }
+
+    Ok(Some(ExtendedStatement::Truncate {
+        name,
+        restart_identity,
+    }))
+}
+
+/// Consumes the next token if it is `word` unquoted, which may not be a keyword of
+/// `sqlparser`.
+fn parse_word(parser: &mut Parser, word: &str) -> bool {
+    let is_word = matches!(
+        &parser.peek_token().token,
+        Token::Word(next) if next.quote_style.is_none() && next.value.eq_ignore_ascii_case(word)
+    );
+    if is_word {
+        parser.next_token();
+    }
+    is_word
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::{parse_run_sql, ExtendedStatement, ParsedSql};
+    use sqlparser::ast::{Ident, ObjectName, Statement};
+    use sqlparser::parser::ParserError;
+
+    fn extended(sql: &str) -> Result<Option<ExtendedStatement>, ParserError> {
+        Ok(match parse_run_sql(sql)? {
+            ParsedSql::Extended(stmt) => Some(stmt),
+            ParsedSql::Statements(_) => None,
+        })
+    }
+
+    #[test]
+    fn test_parse_run_sql() -> Result<(), ParserError> {
+        debug_assert_eq!(
+            extended("/* c */ NOTIFY \"Events\", 'x';")?,
+            Some(ExtendedStatement::Notify {
+                channel: "Events".to_string(),
+                payload: "x".to_string(),
+            })
+        );
+        debug_assert_eq!(
+            extended("-- c\nnotify Events -- c")?,
+            Some(ExtendedStatement::Notify {
+                channel: "events".to_string(),
+                payload: "".to_string(),
+            })
+        );
+        debug_assert_eq!(
+            extended("refresh /* c */ materialized view \"Mv\"")?,
+            Some(ExtendedStatement::Refresh {
+                name: ObjectName(vec![Ident::with_quote('"', "Mv")]),
+            })
+        );
+        debug_assert_eq!(
+            extended("truncate table \"restart\" -- c\n restart identity")?,
+            Some(ExtendedStatement::Truncate {
+                name: ObjectName(vec![Ident::with_quote('"', "restart")]),
+                restart_identity: true,
+            })
+        );
+        // the quoted words are identifiers, not the keywords of the statements
+        debug_assert!(extended("truncate t \"restart\" identity").is_err());
+        debug_assert!(extended("\"notify\" x").is_err());
+
+        // the other statements are parsed by `sqlparser` from the same tokens
+        debug_assert!(matches!(
+            parse_run_sql("/* c */ truncate \"restart\"")?,
+            ParsedSql::Statements(stmts) if matches!(stmts[0], Statement::Truncate { .. })
+        ));
+        debug_assert!(extended("notify events, 'x' select").is_err());
+
+        Ok(())
+    }
 }
@@ -1,11 +1,15 @@
 pub mod operator;
 
-use crate::catalog::{ColumnCatalog, TableName};
-use crate::planner::operator::join::JoinType;
+use crate::catalog::{ColumnCatalog, ColumnRef, TableName};
+use crate::execution::dql::join::joins_nullable;
+use crate::planner::operator::join::{JoinCondition, JoinType};
+use crate::planner::operator::recursive_union::RecursiveUnionOperator;
 use crate::planner::operator::union::UnionOperator;
 use crate::planner::operator::values::ValuesOperator;
+use crate::planner::operator::work_table::WorkTableOperator;
 use crate::planner::operator::{Operator, PhysicalOption};
 use crate::types::tuple::SchemaRef;
+use crate::types::value::ValueRef;
 use itertools::Itertools;
 use std::sync::Arc;
 
@@ -32,6 +36,61 @@ impl LogicalPlan {
         self.childrens.get(index)
     }
 
+    /// Replaces the columns of the outer query referenced by this correlated subquery
+    /// with their values in the current outer row, see
+    /// [`ScalarExpression::bind_correlated_values`](crate::expression::ScalarExpression::bind_correlated_values).
+    pub(crate) fn bind_correlated_values(&mut self, values: &[(ColumnRef, ValueRef)]) {
+        match &mut self.operator {
+            Operator::Aggregate(op) => {
+                for expr in op.agg_calls.iter_mut().chain(op.groupby_exprs.iter_mut()) {
+                    expr.bind_correlated_values(values);
+                }
+            }
+            Operator::Filter(op) => op.predicate.bind_correlated_values(values),
+            Operator::Join(op) => {
+                if let JoinCondition::On { on, filter } = &mut op.on {
+                    for (left_expr, right_expr) in on {
+                        left_expr.bind_correlated_values(values);
+                        right_expr.bind_correlated_values(values);
+                    }
+                    if let Some(expr) = filter {
+                        expr.bind_correlated_values(values);
+                    }
+                }
+            }
+            Operator::Project(op) => {
+                for expr in op.exprs.iter_mut() {
+                    expr.bind_correlated_values(values);
+                }
+            }
+            Operator::Sort(op) => {
+                for sort_field in op.sort_fields.iter_mut() {
+                    sort_field.expr.bind_correlated_values(values);
+                }
+            }
+            Operator::Window(op) => {
+                for expr in op.window_calls.iter_mut() {
+                    expr.bind_correlated_values(values);
+                }
+            }
+            Operator::DistinctOn(op) => {
+                for expr in op.on_exprs.iter_mut() {
+                    expr.bind_correlated_values(values);
+                }
+            }
+            Operator::FunctionScan(op) => {
+                for expr in op.table_function.args.iter_mut() {
+                    expr.bind_correlated_values(values);
+                }
+            }
+            // the other operators have no expressions
+            _ => (),
+        }
+        for child in self.childrens.iter_mut() {
+            child.bind_correlated_values(values);
+        }
+    }
+
     pub fn referenced_table(&self) -> Vec<TableName> {
         fn collect_table(plan: &LogicalPlan, results: &mut Vec<TableName>) {
             if let Operator::TableScan(op) = &plan.operator {
@@ -50,9 +109,10 @@ impl LogicalPlan {
     pub fn output_schema(&mut self) -> &SchemaRef {
         self._output_schema_ref
             .get_or_insert_with(|| match &self.operator {
-                Operator::Filter(_) | Operator::Sort(_) | Operator::Limit(_) => {
-                    self.childrens[0].output_schema().clone()
-                }
+                Operator::Filter(_)
+                | Operator::Sort(_)
+                | Operator::Limit(_)
+                | Operator::DistinctOn(_) => self.childrens[0].output_schema().clone(),
                 Operator::Aggregate(op) => {
                     let out_columns = op
                         .agg_calls
@@ -66,10 +126,27 @@ impl LogicalPlan {
                     if matches!(op.join_type, JoinType::LeftSemi | JoinType::LeftAnti) {
                         return self.childrens[0].output_schema().clone();
                     }
+                    // Tips: the columns of the side padded with NULLs become nullable
+                    let (left_nullable, right_nullable) = joins_nullable(&op.join_type);
                     let out_columns = self
                         .childrens
                         .iter_mut()
-                        .flat_map(|children| Vec::clone(children.output_schema()))
+                        .zip([left_nullable, right_nullable])
+                        .flat_map(|(children, force_nullable)| {
+                            children
+                                .output_schema()
+                                .iter()
+                                .map(|column| {
+                                    if !force_nullable || column.nullable {
+                                        return column.clone();
+                                    }
+                                    let mut column = ColumnCatalog::clone(column);
+                                    column.nullable = true;
+
+                                    Arc::new(column)
+                                })
+                                .collect_vec()
+                        })
                         .collect_vec();
                     Arc::new(out_columns)
                 }
@@ -81,6 +158,11 @@ impl LogicalPlan {
                         .collect_vec();
                     Arc::new(out_columns)
                 }
+                Operator::Window(op) => {
+                    let mut out_columns = Vec::clone(self.childrens[0].output_schema());
+                    out_columns.extend(op.window_calls.iter().map(|expr| expr.output_column()));
+                    Arc::new(out_columns)
+                }
                 Operator::TableScan(op) => {
                     let out_columns = op
                         .columns
@@ -89,17 +171,20 @@ impl LogicalPlan {
                         .collect_vec();
                     Arc::new(out_columns)
                 }
-                // FIXME: redundant clone
-                Operator::FunctionScan(op) => op.table_function.output_schema().clone(),
+                Operator::FunctionScan(op) => op.output_schema(),
                 Operator::Values(ValuesOperator { schema_ref, .. })
                 | Operator::Union(UnionOperator {
                     left_schema_ref: schema_ref,
                     ..
-                }) => schema_ref.clone(),
+                })
+                | Operator::RecursiveUnion(RecursiveUnionOperator { schema_ref, .. })
+                | Operator::WorkTable(WorkTableOperator { schema_ref, .. }) => schema_ref.clone(),
                 Operator::Dummy => Arc::new(vec![]),
-                Operator::Show => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
-                    "TABLE".to_string(),
-                ))]),
+                Operator::Show => Arc::new(vec![
+                    Arc::new(ColumnCatalog::new_dummy("TABLE".to_string())),
+                    Arc::new(ColumnCatalog::new_dummy("TYPE".to_string())),
+                    Arc::new(ColumnCatalog::new_dummy("COMMENT".to_string())),
+                ]),
                 Operator::Explain => {
                     Arc::new(vec![Arc::new(ColumnCatalog::new_dummy("PLAN".to_string()))])
                 }
@@ -110,6 +195,8 @@ impl LogicalPlan {
                     Arc::new(ColumnCatalog::new_dummy("NULL".to_string())),
                     Arc::new(ColumnCatalog::new_dummy("Key".to_string())),
                     Arc::new(ColumnCatalog::new_dummy("DEFAULT".to_string())),
+                    Arc::new(ColumnCatalog::new_dummy("CHECK".to_string())),
+                    Arc::new(ColumnCatalog::new_dummy("COMMENT".to_string())),
                 ]),
                 Operator::Insert(_) => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
                     "INSERTED".to_string(),
@@ -126,9 +213,21 @@ impl LogicalPlan {
                 Operator::AddColumn(_) => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
                     "ADD COLUMN SUCCESS".to_string(),
                 ))]),
+                Operator::AlterColumn(_) => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
+                    "ALTER COLUMN SUCCESS".to_string(),
+                ))]),
                 Operator::DropColumn(_) => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
                     "DROP COLUMN SUCCESS".to_string(),
                 ))]),
+                Operator::AddCheck(_) | Operator::AddForeignKey(_) => Arc::new(vec![Arc::new(
+                    ColumnCatalog::new_dummy("ADD CONSTRAINT SUCCESS".to_string()),
+                )]),
+                Operator::DropConstraint(_) => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
+                    "DROP CONSTRAINT SUCCESS".to_string(),
+                ))]),
+                Operator::RenameTable(_) => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
+                    "RENAME TABLE SUCCESS".to_string(),
+                ))]),
                 Operator::CreateTable(_) => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
                     "CREATE TABLE SUCCESS".to_string(),
                 ))]),
@@ -138,13 +237,24 @@ impl LogicalPlan {
                 Operator::DropTable(_) => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
                     "DROP TABLE SUCCESS".to_string(),
                 ))]),
+                Operator::CreateView(_) => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
+                    "CREATE VIEW SUCCESS".to_string(),
+                ))]),
+                Operator::DropView(_) => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
+                    "DROP VIEW SUCCESS".to_string(),
+                ))]),
+                Operator::Comment(_) => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
+                    "COMMENT SUCCESS".to_string(),
+                ))]),
                 Operator::Truncate(_) => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
                     "TRUNCATE TABLE SUCCESS".to_string(),
                 ))]),
                 Operator::CopyFromFile(_) => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
                     "COPY FROM SOURCE".to_string(),
                 ))]),
-                Operator::CopyToFile(_) => todo!(),
+                Operator::CopyToFile(_) => Arc::new(vec![Arc::new(ColumnCatalog::new_dummy(
+                    "COPY TO TARGET".to_string(),
+                ))]),
             })
     }
 
@@ -0,0 +1,23 @@
+use crate::catalog::{CheckConstraint, TableName};
+use crate::expression::ScalarExpression;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct AddCheckOperator {
+    pub table_name: TableName,
+    /// The `CHECK` constraint added, bound against the columns of the table
+    pub check: (CheckConstraint, ScalarExpression),
+}
+
+impl fmt::Display for AddCheckOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Add Check {} -> {}: {}",
+            self.check.0.name, self.table_name, self.check.0.expr
+        )?;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
-use crate::catalog::{ColumnCatalog, TableName};
+use crate::catalog::{CheckConstraint, ColumnCatalog, TableName};
+use crate::expression::ScalarExpression;
 use std::fmt;
 use std::fmt::Formatter;
 
@@ -7,6 +8,8 @@ pub struct AddColumnOperator {
     pub table_name: TableName,
     pub if_not_exists: bool,
     pub column: ColumnCatalog,
+    /// The `CHECK` constraints of the column, bound against the columns of the table with it
+    pub checks: Vec<(CheckConstraint, ScalarExpression)>,
 }
 
 impl fmt::Display for AddColumnOperator {
@@ -0,0 +1,25 @@
+use crate::catalog::{ForeignKey, TableName};
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct AddForeignKeyOperator {
+    pub table_name: TableName,
+    pub foreign_key: ForeignKey,
+}
+
+impl fmt::Display for AddForeignKeyOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Add Foreign Key {} -> {}: {} references {}({})",
+            self.foreign_key.name,
+            self.table_name,
+            self.foreign_key.column,
+            self.foreign_key.ref_table,
+            self.foreign_key.ref_column
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use crate::catalog::TableName;
+use crate::types::LogicalType;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct AlterColumnOperator {
+    pub table_name: TableName,
+    pub column_name: String,
+    pub ty: LogicalType,
+}
+
+impl fmt::Display for AlterColumnOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Alter {} -> {}, Type: {}",
+            self.column_name, self.table_name, self.ty
+        )?;
+
+        Ok(())
+    }
+}
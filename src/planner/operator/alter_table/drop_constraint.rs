@@ -0,0 +1,22 @@
+use crate::catalog::TableName;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct DropConstraintOperator {
+    pub table_name: TableName,
+    pub constraint_name: String,
+    pub if_exists: bool,
+}
+
+impl fmt::Display for DropConstraintOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Drop Constraint {} On {}, If Exists: {}",
+            self.constraint_name, self.table_name, self.if_exists
+        )?;
+
+        Ok(())
+    }
+}
@@ -1,2 +1,7 @@
+pub mod add_check;
 pub mod add_column;
+pub mod add_foreign_key;
+pub mod alter_column;
 pub mod drop_column;
+pub mod drop_constraint;
+pub mod rename_table;
@@ -0,0 +1,17 @@
+use crate::catalog::TableName;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct RenameTableOperator {
+    pub table_name: TableName,
+    pub new_table_name: TableName,
+}
+
+impl fmt::Display for RenameTableOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Rename {} -> {}", self.table_name, self.new_table_name)?;
+
+        Ok(())
+    }
+}
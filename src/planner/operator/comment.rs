@@ -0,0 +1,24 @@
+use crate::catalog::TableName;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct CommentOperator {
+    pub table_name: TableName,
+    /// The column commented on, the table itself if `None`
+    pub column_name: Option<String>,
+    /// The comment is removed if `None`
+    pub comment: Option<String>,
+}
+
+impl fmt::Display for CommentOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Comment On {}", self.table_name)?;
+        if let Some(column_name) = &self.column_name {
+            write!(f, ".{}", column_name)?;
+        }
+        write!(f, " Is {}", self.comment.as_deref().unwrap_or("NULL"))?;
+
+        Ok(())
+    }
+}
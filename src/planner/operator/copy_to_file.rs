@@ -1,6 +1,16 @@
 use crate::binder::copy::ExtSource;
+use std::fmt;
+use std::fmt::Formatter;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct CopyToFileOperator {
-    pub source: ExtSource,
+    pub target: ExtSource,
+}
+
+impl fmt::Display for CopyToFileOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Copy To {}", self.target.path.display())?;
+
+        Ok(())
+    }
 }
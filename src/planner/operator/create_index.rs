@@ -1,4 +1,5 @@
 use crate::catalog::{ColumnRef, TableName};
+use crate::function::json::JsonPath;
 use crate::types::index::IndexType;
 use itertools::Itertools;
 use std::fmt;
@@ -12,6 +13,10 @@ pub struct CreateIndexOperator {
     pub index_name: String,
     pub if_not_exists: bool,
     pub ty: IndexType,
+    /// The path of a JSON path index on the only column, see [`IndexMeta::json_path`]
+    ///
+    /// [`IndexMeta::json_path`]: crate::types::index::IndexMeta::json_path
+    pub(crate) json_path: JsonPath,
 }
 
 impl fmt::Display for CreateIndexOperator {
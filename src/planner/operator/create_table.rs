@@ -1,4 +1,4 @@
-use crate::catalog::{ColumnCatalog, TableName};
+use crate::catalog::{CheckConstraint, ColumnCatalog, ForeignKey, TableName, UniqueKey};
 use itertools::Itertools;
 use std::fmt;
 use std::fmt::Formatter;
@@ -9,7 +9,13 @@ pub struct CreateTableOperator {
     pub table_name: TableName,
     /// List of columns of the table
     pub columns: Vec<ColumnCatalog>,
+    pub checks: Vec<CheckConstraint>,
+    /// The unique constraints on multiple columns
+    pub unique_keys: Vec<UniqueKey>,
+    pub foreign_keys: Vec<ForeignKey>,
     pub if_not_exists: bool,
+    /// The defining query when creating a materialized view
+    pub view_query: Option<String>,
 }
 
 impl fmt::Display for CreateTableOperator {
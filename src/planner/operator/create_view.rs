@@ -0,0 +1,21 @@
+use crate::catalog::View;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct CreateViewOperator {
+    pub view: View,
+    pub or_replace: bool,
+}
+
+impl fmt::Display for CreateViewOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Create View {} As {}, Or Replace: {}",
+            self.view.name, self.view.query, self.or_replace
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use crate::planner::LogicalPlan;
+use crate::{expression::ScalarExpression, planner::operator::Operator};
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::Formatter;
+
+/// Keeps the first row of each group of rows with equal `on_exprs`, whose input is sorted by them.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct DistinctOnOperator {
+    pub on_exprs: Vec<ScalarExpression>,
+}
+
+impl DistinctOnOperator {
+    pub fn build(children: LogicalPlan, on_exprs: Vec<ScalarExpression>) -> LogicalPlan {
+        LogicalPlan::new(
+            Operator::DistinctOn(DistinctOnOperator { on_exprs }),
+            vec![children],
+        )
+    }
+}
+
+impl fmt::Display for DistinctOnOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let on_exprs = self
+            .on_exprs
+            .iter()
+            .map(|expr| format!("{}", expr))
+            .join(", ");
+        write!(f, "DistinctOn [{}]", on_exprs)?;
+
+        Ok(())
+    }
+}
@@ -1,17 +1,23 @@
 use crate::catalog::TableName;
+use itertools::Itertools;
 use std::fmt;
 use std::fmt::Formatter;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct DropTableOperator {
-    /// Table name to insert to
-    pub table_name: TableName,
+    /// Tables to drop, in order
+    pub table_names: Vec<TableName>,
     pub if_exists: bool,
 }
 
 impl fmt::Display for DropTableOperator {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "Drop {}, If Exists: {}", self.table_name, self.if_exists)?;
+        write!(
+            f,
+            "Drop {}, If Exists: {}",
+            self.table_names.iter().join(", "),
+            self.if_exists
+        )?;
 
         Ok(())
     }
@@ -0,0 +1,24 @@
+use crate::catalog::TableName;
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct DropViewOperator {
+    /// Views to drop, in order
+    pub view_names: Vec<TableName>,
+    pub if_exists: bool,
+}
+
+impl fmt::Display for DropViewOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Drop View {}, If Exists: {}",
+            self.view_names.iter().join(", "),
+            self.if_exists
+        )?;
+
+        Ok(())
+    }
+}
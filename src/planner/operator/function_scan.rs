@@ -1,26 +1,48 @@
+use crate::catalog::ColumnRef;
 use crate::expression::function::table::TableFunction;
 use crate::planner::operator::Operator;
 use crate::planner::LogicalPlan;
+use crate::types::tuple::SchemaRef;
 use std::fmt;
 use std::fmt::Formatter;
+use std::sync::Arc;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct FunctionScanOperator {
     pub table_function: TableFunction,
+    /// `WITH ORDINALITY`, the column of the 1-based row number following the output of the function
+    pub ordinality: Option<ColumnRef>,
 }
 
 impl FunctionScanOperator {
-    pub fn build(table_function: TableFunction) -> LogicalPlan {
+    pub fn build(table_function: TableFunction, ordinality: Option<ColumnRef>) -> LogicalPlan {
         LogicalPlan::new(
-            Operator::FunctionScan(FunctionScanOperator { table_function }),
+            Operator::FunctionScan(FunctionScanOperator {
+                table_function,
+                ordinality,
+            }),
             vec![],
         )
     }
+
+    pub fn output_schema(&self) -> SchemaRef {
+        let schema = self.table_function.output_schema();
+
+        match &self.ordinality {
+            Some(ordinality) => {
+                Arc::new(schema.iter().cloned().chain([ordinality.clone()]).collect())
+            }
+            None => schema.clone(),
+        }
+    }
 }
 
 impl fmt::Display for FunctionScanOperator {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "Function Scan: {}", self.table_function.summary().name)?;
+        if self.ordinality.is_some() {
+            write!(f, " With Ordinality")?;
+        }
 
         Ok(())
     }
@@ -1,4 +1,5 @@
-use crate::catalog::TableName;
+use crate::catalog::{CheckConstraint, TableName};
+use crate::expression::ScalarExpression;
 use std::fmt;
 use std::fmt::Formatter;
 
@@ -6,6 +7,11 @@ use std::fmt::Formatter;
 pub struct InsertOperator {
     pub table_name: TableName,
     pub is_overwrite: bool,
+    /// The `CHECK` constraints of the table, bound against its columns
+    pub checks: Vec<(CheckConstraint, ScalarExpression)>,
+    /// The expressions of the generated columns of the table by their positions, bound against
+    /// its columns
+    pub generated: Vec<(usize, ScalarExpression)>,
 }
 
 impl fmt::Display for InsertOperator {
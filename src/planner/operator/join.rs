@@ -1,3 +1,4 @@
+use crate::catalog::ColumnRef;
 use crate::expression::ScalarExpression;
 use crate::planner::LogicalPlan;
 use itertools::Itertools;
@@ -13,6 +14,9 @@ pub enum JoinType {
     LeftOuter,
     LeftSemi,
     LeftAnti,
+    /// `LeftOuter` that fails if a row on the left matches more than one row on the right,
+    /// e.g. a scalar subquery in the projection
+    LeftSingle,
     RightOuter,
     Full,
     Cross,
@@ -32,6 +36,11 @@ pub enum JoinCondition {
 pub struct JoinOperator {
     pub on: JoinCondition,
     pub join_type: JoinType,
+    /// Columns of the left side referenced by a correlated subquery on the right side
+    pub correlated_columns: Vec<ColumnRef>,
+    /// Each row on the right matches at most one row on the left of a `LeftSemi` or `LeftAnti`
+    /// join, which keeps the duplicates of `INTERSECT ALL` and `EXCEPT ALL`
+    pub match_once: bool,
 }
 
 impl JoinOperator {
@@ -40,9 +49,24 @@ impl JoinOperator {
         right: LogicalPlan,
         on: JoinCondition,
         join_type: JoinType,
+    ) -> LogicalPlan {
+        Self::build_correlated(left, right, on, join_type, vec![])
+    }
+
+    pub fn build_correlated(
+        left: LogicalPlan,
+        right: LogicalPlan,
+        on: JoinCondition,
+        join_type: JoinType,
+        correlated_columns: Vec<ColumnRef>,
     ) -> LogicalPlan {
         LogicalPlan::new(
-            Operator::Join(JoinOperator { on, join_type }),
+            Operator::Join(JoinOperator {
+                on,
+                join_type,
+                correlated_columns,
+                match_once: false,
+            }),
             vec![left, right],
         )
     }
@@ -51,6 +75,18 @@ impl JoinOperator {
 impl fmt::Display for JoinOperator {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         write!(f, "{} Join{}", self.join_type, self.on)?;
+        if !self.correlated_columns.is_empty() {
+            let columns = self
+                .correlated_columns
+                .iter()
+                .map(|column| column.full_name())
+                .join(", ");
+
+            write!(f, ", Correlated: [{}]", columns)?;
+        }
+        if self.match_once {
+            write!(f, ", Match Once")?;
+        }
 
         Ok(())
     }
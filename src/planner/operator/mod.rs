@@ -1,25 +1,32 @@
 pub mod aggregate;
 pub mod alter_table;
 pub mod analyze;
+pub mod comment;
 pub mod copy_from_file;
 pub mod copy_to_file;
 pub mod create_index;
 pub mod create_table;
+pub mod create_view;
 pub mod delete;
 pub mod describe;
+pub mod distinct_on;
 pub mod drop_table;
+pub mod drop_view;
 pub mod filter;
 pub mod function_scan;
 pub mod insert;
 pub mod join;
 pub mod limit;
 pub mod project;
+pub mod recursive_union;
 pub mod sort;
 pub mod table_scan;
 pub mod truncate;
 pub mod union;
 pub mod update;
 pub mod values;
+pub mod window;
+pub mod work_table;
 
 use self::{
     aggregate::AggregateOperator, alter_table::add_column::AddColumnOperator,
@@ -28,22 +35,34 @@ use self::{
 };
 use crate::catalog::ColumnRef;
 use crate::expression::ScalarExpression;
+use crate::planner::operator::alter_table::add_check::AddCheckOperator;
+use crate::planner::operator::alter_table::add_foreign_key::AddForeignKeyOperator;
+use crate::planner::operator::alter_table::alter_column::AlterColumnOperator;
 use crate::planner::operator::alter_table::drop_column::DropColumnOperator;
+use crate::planner::operator::alter_table::drop_constraint::DropConstraintOperator;
+use crate::planner::operator::alter_table::rename_table::RenameTableOperator;
 use crate::planner::operator::analyze::AnalyzeOperator;
+use crate::planner::operator::comment::CommentOperator;
 use crate::planner::operator::copy_from_file::CopyFromFileOperator;
 use crate::planner::operator::copy_to_file::CopyToFileOperator;
 use crate::planner::operator::create_index::CreateIndexOperator;
 use crate::planner::operator::create_table::CreateTableOperator;
+use crate::planner::operator::create_view::CreateViewOperator;
 use crate::planner::operator::delete::DeleteOperator;
 use crate::planner::operator::describe::DescribeOperator;
+use crate::planner::operator::distinct_on::DistinctOnOperator;
 use crate::planner::operator::drop_table::DropTableOperator;
+use crate::planner::operator::drop_view::DropViewOperator;
 use crate::planner::operator::function_scan::FunctionScanOperator;
 use crate::planner::operator::insert::InsertOperator;
 use crate::planner::operator::join::JoinCondition;
+use crate::planner::operator::recursive_union::RecursiveUnionOperator;
 use crate::planner::operator::truncate::TruncateOperator;
 use crate::planner::operator::union::UnionOperator;
 use crate::planner::operator::update::UpdateOperator;
 use crate::planner::operator::values::ValuesOperator;
+use crate::planner::operator::window::WindowOperator;
+use crate::planner::operator::work_table::WorkTableOperator;
 use crate::types::index::IndexInfo;
 use itertools::Itertools;
 use std::fmt;
@@ -66,6 +85,10 @@ pub enum Operator {
     Explain,
     Describe(DescribeOperator),
     Union(UnionOperator),
+    RecursiveUnion(RecursiveUnionOperator),
+    WorkTable(WorkTableOperator),
+    Window(WindowOperator),
+    DistinctOn(DistinctOnOperator),
     // DML
     Insert(InsertOperator),
     Update(UpdateOperator),
@@ -73,10 +96,18 @@ pub enum Operator {
     Analyze(AnalyzeOperator),
     // DDL
     AddColumn(AddColumnOperator),
+    AlterColumn(AlterColumnOperator),
     DropColumn(DropColumnOperator),
+    AddCheck(AddCheckOperator),
+    AddForeignKey(AddForeignKeyOperator),
+    DropConstraint(DropConstraintOperator),
+    RenameTable(RenameTableOperator),
     CreateTable(CreateTableOperator),
     CreateIndex(CreateIndexOperator),
     DropTable(DropTableOperator),
+    CreateView(CreateViewOperator),
+    DropView(DropViewOperator),
+    Comment(CommentOperator),
     Truncate(TruncateOperator),
     // Copy
     CopyFromFile(CopyFromFileOperator),
@@ -102,6 +133,7 @@ pub enum PhysicalOption {
     Update,
     Delete,
     AddColumn,
+    AlterColumn,
     DropColumn,
     CreateTable,
     DropTable,
@@ -132,12 +164,17 @@ impl Operator {
                     .map(|(_, column)| ScalarExpression::ColumnRef(column))
                     .collect_vec(),
             ),
-            Operator::Sort(_) | Operator::Limit(_) => None,
+            Operator::Sort(_)
+            | Operator::Limit(_)
+            | Operator::Window(_)
+            | Operator::DistinctOn(_) => None,
             Operator::Values(ValuesOperator { schema_ref, .. })
             | Operator::Union(UnionOperator {
                 left_schema_ref: schema_ref,
                 ..
-            }) => Some(
+            })
+            | Operator::RecursiveUnion(RecursiveUnionOperator { schema_ref, .. })
+            | Operator::WorkTable(WorkTableOperator { schema_ref, .. }) => Some(
                 schema_ref
                     .iter()
                     .cloned()
@@ -145,9 +182,7 @@ impl Operator {
                     .collect_vec(),
             ),
             Operator::FunctionScan(op) => Some(
-                op.table_function
-                    .inner
-                    .output_schema()
+                op.output_schema()
                     .iter()
                     .map(|column| ScalarExpression::ColumnRef(column.clone()))
                     .collect_vec(),
@@ -160,10 +195,18 @@ impl Operator {
             | Operator::Delete(_)
             | Operator::Analyze(_)
             | Operator::AddColumn(_)
+            | Operator::AlterColumn(_)
             | Operator::DropColumn(_)
+            | Operator::AddCheck(_)
+            | Operator::AddForeignKey(_)
+            | Operator::DropConstraint(_)
+            | Operator::RenameTable(_)
             | Operator::CreateTable(_)
             | Operator::CreateIndex(_)
             | Operator::DropTable(_)
+            | Operator::CreateView(_)
+            | Operator::DropView(_)
+            | Operator::Comment(_)
             | Operator::Truncate(_)
             | Operator::CopyFromFile(_)
             | Operator::CopyToFile(_) => None,
@@ -192,6 +235,8 @@ impl Operator {
                         exprs.append(&mut filter_expr.referenced_columns(only_column_ref));
                     }
                 }
+                // the values of the right input are bound for each row of the left
+                exprs.extend(op.correlated_columns.iter().cloned());
                 exprs
             }
             Operator::Project(op) => op
@@ -217,7 +262,19 @@ impl Operator {
                 .map(|field| &field.expr)
                 .flat_map(|expr| expr.referenced_columns(only_column_ref))
                 .collect_vec(),
-            Operator::Values(ValuesOperator { schema_ref, .. }) => Vec::clone(schema_ref),
+            Operator::Window(op) => op
+                .window_calls
+                .iter()
+                .flat_map(|expr| expr.referenced_columns(only_column_ref))
+                .collect_vec(),
+            Operator::DistinctOn(op) => op
+                .on_exprs
+                .iter()
+                .flat_map(|expr| expr.referenced_columns(only_column_ref))
+                .collect_vec(),
+            Operator::Values(ValuesOperator { schema_ref, .. })
+            | Operator::RecursiveUnion(RecursiveUnionOperator { schema_ref, .. })
+            | Operator::WorkTable(WorkTableOperator { schema_ref, .. }) => Vec::clone(schema_ref),
             Operator::Union(UnionOperator {
                 left_schema_ref,
                 _right_schema_ref,
@@ -227,7 +284,7 @@ impl Operator {
                 .cloned()
                 .collect_vec(),
             Operator::Analyze(_) => vec![],
-            Operator::Delete(op) => vec![op.primary_key_column.clone()],
+            Operator::Delete(op) => op.primary_keys.clone(),
             Operator::Dummy
             | Operator::Limit(_)
             | Operator::Show
@@ -236,10 +293,18 @@ impl Operator {
             | Operator::Insert(_)
             | Operator::Update(_)
             | Operator::AddColumn(_)
+            | Operator::AlterColumn(_)
             | Operator::DropColumn(_)
+            | Operator::AddCheck(_)
+            | Operator::AddForeignKey(_)
+            | Operator::DropConstraint(_)
+            | Operator::RenameTable(_)
             | Operator::CreateTable(_)
             | Operator::CreateIndex(_)
             | Operator::DropTable(_)
+            | Operator::CreateView(_)
+            | Operator::DropView(_)
+            | Operator::Comment(_)
             | Operator::Truncate(_)
             | Operator::CopyFromFile(_)
             | Operator::CopyToFile(_) => vec![],
@@ -268,14 +333,26 @@ impl fmt::Display for Operator {
             Operator::Delete(op) => write!(f, "{}", op),
             Operator::Analyze(op) => write!(f, "{}", op),
             Operator::AddColumn(op) => write!(f, "{}", op),
+            Operator::AlterColumn(op) => write!(f, "{}", op),
             Operator::DropColumn(op) => write!(f, "{}", op),
+            Operator::AddCheck(op) => write!(f, "{}", op),
+            Operator::AddForeignKey(op) => write!(f, "{}", op),
+            Operator::DropConstraint(op) => write!(f, "{}", op),
+            Operator::RenameTable(op) => write!(f, "{}", op),
             Operator::CreateTable(op) => write!(f, "{}", op),
             Operator::CreateIndex(op) => write!(f, "{}", op),
             Operator::DropTable(op) => write!(f, "{}", op),
+            Operator::CreateView(op) => write!(f, "{}", op),
+            Operator::DropView(op) => write!(f, "{}", op),
+            Operator::Comment(op) => write!(f, "{}", op),
             Operator::Truncate(op) => write!(f, "{}", op),
             Operator::CopyFromFile(op) => write!(f, "{}", op),
-            Operator::CopyToFile(_) => todo!(),
+            Operator::CopyToFile(op) => write!(f, "{}", op),
             Operator::Union(op) => write!(f, "{}", op),
+            Operator::RecursiveUnion(op) => write!(f, "{}", op),
+            Operator::WorkTable(op) => write!(f, "{}", op),
+            Operator::Window(op) => write!(f, "{}", op),
+            Operator::DistinctOn(op) => write!(f, "{}", op),
         }
     }
 }
@@ -300,6 +377,7 @@ impl fmt::Display for PhysicalOption {
             PhysicalOption::Update => write!(f, "Update"),
             PhysicalOption::Delete => write!(f, "Delete"),
             PhysicalOption::AddColumn => write!(f, "AddColumn"),
+            PhysicalOption::AlterColumn => write!(f, "AlterColumn"),
             PhysicalOption::DropColumn => write!(f, "DropColumn"),
             PhysicalOption::CreateTable => write!(f, "CreateTable"),
             PhysicalOption::DropTable => write!(f, "DropTable"),
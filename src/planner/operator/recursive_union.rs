@@ -0,0 +1,38 @@
+use crate::catalog::TableName;
+use crate::types::tuple::SchemaRef;
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::Formatter;
+
+/// `WITH RECURSIVE`, whose children are the anchor and the recursive term.
+///
+/// The recursive term reads the rows of the last iteration by [`WorkTableOperator`]
+/// until it returns no more rows.
+///
+/// [`WorkTableOperator`]: crate::planner::operator::work_table::WorkTableOperator
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct RecursiveUnionOperator {
+    pub table_name: TableName,
+    // the output of the anchor, which is also the schema of the work table
+    pub schema_ref: SchemaRef,
+    pub is_all: bool,
+    pub max_iterations: usize,
+}
+
+impl fmt::Display for RecursiveUnionOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let schema = self
+            .schema_ref
+            .iter()
+            .map(|column| column.name().to_string())
+            .join(", ");
+
+        write!(
+            f,
+            "Recursive Union {}: [{}], Is All: {}",
+            self.table_name, schema, self.is_all
+        )?;
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use crate::catalog::{ColumnRef, TableCatalog, TableName};
 use crate::planner::LogicalPlan;
 use crate::storage::Bounds;
-use crate::types::index::IndexInfo;
+use crate::types::index::{IndexInfo, IndexMetaRef};
 use crate::types::ColumnId;
 use itertools::Itertools;
 use std::fmt;
@@ -12,7 +12,8 @@ use super::Operator;
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct TableScanOperator {
     pub(crate) table_name: TableName,
-    pub(crate) primary_key: ColumnId,
+    /// The columns of the primary key in the order of the key
+    pub(crate) primary_keys: Vec<ColumnId>,
     pub(crate) columns: Vec<(usize, ColumnRef)>,
     // Support push down limit.
     pub(crate) limit: Bounds,
@@ -20,22 +21,30 @@ pub struct TableScanOperator {
     // Support push down predicate.
     // If pre_where is simple predicate, for example:  a > 1 then can calculate directly when read data.
     pub(crate) index_infos: Vec<IndexInfo>,
+    // Set when the operators above rely on the order of an index, then only an index scan on it
+    // can implement the scan.
+    pub(crate) order: Option<ScanOrder>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct ScanOrder {
+    pub(crate) index: IndexMetaRef,
+    pub(crate) reverse: bool,
 }
 
 impl TableScanOperator {
     pub fn build(table_name: TableName, table_catalog: &TableCatalog) -> LogicalPlan {
-        let mut primary_key_option = None;
+        let primary_keys = table_catalog
+            .primary_keys()
+            .unwrap()
+            .into_iter()
+            .filter_map(|(_, column)| column.id())
+            .collect_vec();
         // Fill all Columns in TableCatalog by default
         let columns = table_catalog
             .columns()
             .enumerate()
-            .map(|(i, column)| {
-                if column.desc.is_primary {
-                    primary_key_option = column.id();
-                }
-
-                (i, column.clone())
-            })
+            .map(|(i, column)| (i, column.clone()))
             .collect_vec();
         let index_infos = table_catalog
             .indexes
@@ -50,9 +59,10 @@ impl TableScanOperator {
             Operator::TableScan(TableScanOperator {
                 index_infos,
                 table_name,
-                primary_key: primary_key_option.unwrap(),
+                primary_keys,
                 columns,
                 limit: (None, None),
+                order: None,
             }),
             vec![],
         )
@@ -79,6 +89,12 @@ impl fmt::Display for TableScanOperator {
         if let Some(offset) = offset {
             write!(f, ", Offset: {}", offset)?;
         }
+        if let Some(ScanOrder { index, reverse }) = &self.order {
+            write!(f, ", Order: {}", index)?;
+            if *reverse {
+                write!(f, " DESC")?;
+            }
+        }
 
         Ok(())
     }
@@ -6,6 +6,8 @@ use std::fmt::Formatter;
 pub struct TruncateOperator {
     /// Table name to insert to
     pub table_name: TableName,
+    /// `RESTART IDENTITY` restarts the sequence of the auto-increment column
+    pub restart_identity: bool,
 }
 
 impl fmt::Display for TruncateOperator {
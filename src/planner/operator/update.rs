@@ -1,10 +1,16 @@
-use crate::catalog::TableName;
+use crate::catalog::{CheckConstraint, TableName};
+use crate::expression::ScalarExpression;
 use std::fmt;
 use std::fmt::Formatter;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct UpdateOperator {
     pub table_name: TableName,
+    /// The `CHECK` constraints of the table, bound against its columns
+    pub checks: Vec<(CheckConstraint, ScalarExpression)>,
+    /// The expressions of the generated columns of the table by their positions, bound against
+    /// its columns
+    pub generated: Vec<(usize, ScalarExpression)>,
 }
 
 impl fmt::Display for UpdateOperator {
@@ -0,0 +1,28 @@
+use crate::catalog::TableName;
+use crate::types::tuple::SchemaRef;
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::Formatter;
+
+/// The rows of the last iteration of the recursive term of [`RecursiveUnionOperator`].
+///
+/// [`RecursiveUnionOperator`]: crate::planner::operator::recursive_union::RecursiveUnionOperator
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct WorkTableOperator {
+    pub table_name: TableName,
+    pub schema_ref: SchemaRef,
+}
+
+impl fmt::Display for WorkTableOperator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let schema = self
+            .schema_ref
+            .iter()
+            .map(|column| column.name().to_string())
+            .join(", ");
+
+        write!(f, "WorkTable {} -> [{}]", self.table_name, schema)?;
+
+        Ok(())
+    }
+}
@@ -34,6 +34,20 @@ impl Serialization for BinaryOperator {
             BinaryOperator::And => writer.write_all(&[15u8])?,
             BinaryOperator::Or => writer.write_all(&[16u8])?,
             BinaryOperator::Xor => writer.write_all(&[17u8])?,
+            BinaryOperator::ILike(escape_char) => {
+                writer.write_all(&[18u8])?;
+
+                escape_char.encode(writer)?;
+            }
+            BinaryOperator::NotILike(escape_char) => {
+                writer.write_all(&[19u8])?;
+
+                escape_char.encode(writer)?;
+            }
+            BinaryOperator::RegexMatch => writer.write_all(&[20u8])?,
+            BinaryOperator::NotRegexMatch => writer.write_all(&[21u8])?,
+            BinaryOperator::RegexIMatch => writer.write_all(&[22u8])?,
+            BinaryOperator::NotRegexIMatch => writer.write_all(&[23u8])?,
         }
 
         Ok(())
@@ -70,6 +84,20 @@ impl Serialization for BinaryOperator {
             15 => BinaryOperator::And,
             16 => BinaryOperator::Or,
             17 => BinaryOperator::Xor,
+            18 => {
+                let escape_char = Option::<char>::decode(reader)?;
+
+                BinaryOperator::ILike(escape_char)
+            }
+            19 => {
+                let escape_char = Option::<char>::decode(reader)?;
+
+                BinaryOperator::NotILike(escape_char)
+            }
+            20 => BinaryOperator::RegexMatch,
+            21 => BinaryOperator::NotRegexMatch,
+            22 => BinaryOperator::RegexIMatch,
+            23 => BinaryOperator::NotRegexIMatch,
             _ => unreachable!(),
         })
     }
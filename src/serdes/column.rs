@@ -1,8 +1,11 @@
-use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef, ColumnRelation, ColumnSummary};
+use crate::catalog::{
+    ColumnCatalog, ColumnCompression, ColumnDesc, ColumnRef, ColumnRelation, ColumnSummary,
+};
 use crate::errors::DatabaseError;
 use crate::expression::ScalarExpression;
 use crate::serdes::{ReferenceSerialization, ReferenceTables, Serialization};
 use crate::storage::{TableCache, Transaction};
+use crate::types::value::ValueRef;
 use crate::types::{ColumnId, LogicalType};
 use std::io::{Read, Write};
 use std::sync::Arc;
@@ -143,9 +146,16 @@ impl ReferenceSerialization for ColumnDesc {
         reference_tables: &mut ReferenceTables,
     ) -> Result<(), DatabaseError> {
         self.is_unique.encode(writer)?;
-        self.is_primary.encode(writer)?;
+        self.primary.map(|i| i as u32).encode(writer)?;
+        self.nulls_not_distinct.encode(writer)?;
+        self.is_auto_increment.encode(writer)?;
         self.column_datatype.encode(writer)?;
         self.default.encode(writer, is_direct, reference_tables)?;
+        self.default_text.encode(writer)?;
+        self.added_default.encode(writer)?;
+        self.generated.encode(writer)?;
+        self.comment.encode(writer)?;
+        self.compression.encode(writer)?;
 
         Ok(())
     }
@@ -156,17 +166,35 @@ impl ReferenceSerialization for ColumnDesc {
         reference_tables: &ReferenceTables,
     ) -> Result<Self, DatabaseError> {
         let is_unique = bool::decode(reader)?;
-        let is_primary = bool::decode(reader)?;
+        let primary = Option::<u32>::decode(reader)?;
+        let nulls_not_distinct = bool::decode(reader)?;
+        let is_auto_increment = bool::decode(reader)?;
         let column_datatype = LogicalType::decode(reader)?;
         let default = Option::<ScalarExpression>::decode(reader, drive, reference_tables)?;
-
-        ColumnDesc::new(column_datatype, is_primary, is_unique, default)
+        let default_text = Option::<String>::decode(reader)?;
+        let added_default = Option::<ValueRef>::decode(reader)?;
+        let generated = Option::<String>::decode(reader)?;
+        let comment = Option::<String>::decode(reader)?;
+        let compression = Option::<ColumnCompression>::decode(reader)?;
+
+        let mut desc = ColumnDesc::new(column_datatype, false, is_unique, default)?;
+        desc.primary = primary.map(|i| i as usize);
+        desc.nulls_not_distinct = nulls_not_distinct;
+        desc.is_auto_increment = is_auto_increment;
+        desc.default_text = default_text;
+        desc.added_default = added_default;
+        desc.generated = generated;
+        desc.comment = comment;
+        desc.compression = compression;
+        Ok(desc)
     }
 }
 
 #[cfg(test)]
 pub(crate) mod test {
-    use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRef, ColumnRelation, ColumnSummary};
+    use crate::catalog::{
+        ColumnCatalog, ColumnCompression, ColumnDesc, ColumnRef, ColumnRelation, ColumnSummary,
+    };
     use crate::db::test::build_table;
     use crate::errors::DatabaseError;
     use crate::expression::ScalarExpression;
@@ -208,8 +236,15 @@ pub(crate) mod test {
                 nullable: false,
                 desc: ColumnDesc {
                     column_datatype: LogicalType::Integer,
-                    is_primary: false,
+                    primary: None,
                     is_unique: false,
+                    nulls_not_distinct: false,
+                    is_auto_increment: false,
+                    default_text: None,
+                    added_default: None,
+                    generated: None,
+                    comment: None,
+                    compression: None,
                     default: None,
                 },
             });
@@ -245,8 +280,15 @@ pub(crate) mod test {
                 nullable: false,
                 desc: ColumnDesc {
                     column_datatype: LogicalType::Integer,
-                    is_primary: false,
+                    primary: None,
                     is_unique: false,
+                    nulls_not_distinct: false,
+                    is_auto_increment: false,
+                    default_text: None,
+                    added_default: None,
+                    generated: None,
+                    comment: None,
+                    compression: None,
                     default: Some(ScalarExpression::Constant(Arc::new(DataValue::UInt64(
                         Some(42),
                     )))),
@@ -332,8 +374,15 @@ pub(crate) mod test {
         let mut reference_tables = ReferenceTables::new();
         let desc = ColumnDesc {
             column_datatype: LogicalType::Integer,
-            is_primary: false,
+            primary: None,
             is_unique: false,
+            nulls_not_distinct: false,
+            is_auto_increment: false,
+            default_text: None,
+            added_default: Some(Arc::new(DataValue::UInt64(Some(7)))),
+            generated: None,
+            comment: None,
+            compression: Some(ColumnCompression::Lz4),
             default: Some(ScalarExpression::Constant(Arc::new(DataValue::UInt64(
                 Some(42),
             )))),
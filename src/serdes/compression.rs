@@ -0,0 +1,27 @@
+use crate::catalog::ColumnCompression;
+use crate::errors::DatabaseError;
+use crate::serdes::Serialization;
+use std::io::{Read, Write};
+
+impl Serialization for ColumnCompression {
+    type Error = DatabaseError;
+
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), Self::Error> {
+        let type_id = match self {
+            ColumnCompression::Lz4 => 0,
+        };
+        writer.write_all(&[type_id])?;
+
+        Ok(())
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, Self::Error> {
+        let mut one_byte = [0u8; 1];
+        reader.read_exact(&mut one_byte)?;
+
+        Ok(match one_byte[0] {
+            0 => ColumnCompression::Lz4,
+            _ => unreachable!(),
+        })
+    }
+}
@@ -0,0 +1,78 @@
+use crate::errors::DatabaseError;
+use crate::serdes::Serialization;
+use crate::types::value::DataValue;
+use crate::types::LogicalType;
+use std::io::{Read, Write};
+
+impl Serialization for DataValue {
+    type Error = DatabaseError;
+
+    fn encode<W: Write>(&self, writer: &mut W) -> Result<(), Self::Error> {
+        let logical_type = self.logical_type();
+
+        logical_type.encode(writer)?;
+        self.is_null().encode(writer)?;
+
+        if self.is_null() {
+            return Ok(());
+        }
+        if logical_type.raw_len().is_none() {
+            let mut bytes = Vec::new();
+            (self.to_raw(&mut bytes)? as u32).encode(writer)?;
+            writer.write_all(&bytes)?;
+        } else {
+            let _ = self.to_raw(writer)?;
+        }
+
+        Ok(())
+    }
+
+    fn decode<R: Read>(reader: &mut R) -> Result<Self, Self::Error> {
+        let logical_type = LogicalType::decode(reader)?;
+
+        if bool::decode(reader)? {
+            return Ok(DataValue::none(&logical_type));
+        }
+        let value_len = match logical_type.raw_len() {
+            None => u32::decode(reader)? as usize,
+            Some(len) => len,
+        };
+        let mut buf = vec![0u8; value_len];
+        reader.read_exact(&mut buf)?;
+
+        Ok(DataValue::from_raw(&buf, &logical_type))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use crate::errors::DatabaseError;
+    use crate::serdes::Serialization;
+    use crate::types::value::{DataValue, Utf8Type};
+    use sqlparser::ast::CharLengthUnits;
+    use std::io::{Cursor, Seek, SeekFrom};
+
+    #[test]
+    fn test_serialization() -> Result<(), DatabaseError> {
+        let mut cursor = Cursor::new(Vec::new());
+        let values = [
+            DataValue::Int32(None),
+            DataValue::Int32(Some(42)),
+            DataValue::Utf8 {
+                value: Some("hello".to_string()),
+                ty: Utf8Type::Variable(None),
+                unit: CharLengthUnits::Characters,
+            },
+        ];
+
+        for value in values.iter() {
+            value.encode(&mut cursor)?;
+        }
+        cursor.seek(SeekFrom::Start(0))?;
+        for value in values {
+            assert_eq!(DataValue::decode(&mut cursor)?, value);
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::errors::DatabaseError;
 use crate::serdes::Serialization;
-use crate::types::LogicalType;
+use crate::types::{ArrayType, LogicalType, StructType};
 use sqlparser::ast::CharLengthUnits;
 use std::io::{Read, Write};
 
@@ -35,7 +35,12 @@ impl Serialization for LogicalType {
             }
             LogicalType::Date => writer.write_all(&[15u8])?,
             LogicalType::DateTime => writer.write_all(&[16u8])?,
+            LogicalType::TimestampTz => writer.write_all(&[26u8])?,
             LogicalType::Time => writer.write_all(&[17u8])?,
+            LogicalType::Interval => writer.write_all(&[21u8])?,
+            LogicalType::Json => writer.write_all(&[22u8])?,
+            LogicalType::Uuid => writer.write_all(&[23u8])?,
+            LogicalType::Bytea => writer.write_all(&[24u8])?,
             LogicalType::Decimal(precision, scala) => {
                 writer.write_all(&[18u8])?;
 
@@ -43,6 +48,21 @@ impl Serialization for LogicalType {
                 scala.encode(writer)?;
             }
             LogicalType::Tuple => writer.write_all(&[19u8])?,
+            LogicalType::Struct(struct_type) => {
+                writer.write_all(&[20u8])?;
+
+                let fields = struct_type.fields();
+                (fields.len() as u32).encode(writer)?;
+                for (name, ty) in fields {
+                    name.encode(writer)?;
+                    ty.encode(writer)?;
+                }
+            }
+            LogicalType::Array(array_type) => {
+                writer.write_all(&[25u8])?;
+
+                array_type.element_type().encode(writer)?;
+            }
         }
 
         Ok(())
@@ -88,6 +108,21 @@ impl Serialization for LogicalType {
                 LogicalType::Decimal(precision, scala)
             }
             19 => LogicalType::Tuple,
+            20 => {
+                let len = u32::decode(reader)?;
+                let mut fields = Vec::with_capacity(len as usize);
+
+                for _ in 0..len {
+                    fields.push((String::decode(reader)?, LogicalType::decode(reader)?));
+                }
+                LogicalType::Struct(StructType::new(fields))
+            }
+            21 => LogicalType::Interval,
+            22 => LogicalType::Json,
+            23 => LogicalType::Uuid,
+            24 => LogicalType::Bytea,
+            25 => LogicalType::Array(ArrayType::new(LogicalType::decode(reader)?)),
+            26 => LogicalType::TimestampTz,
             _ => unreachable!(),
         })
     }
@@ -97,7 +132,7 @@ impl Serialization for LogicalType {
 pub(crate) mod test {
     use crate::errors::DatabaseError;
     use crate::serdes::Serialization;
-    use crate::types::LogicalType;
+    use crate::types::{ArrayType, LogicalType, StructType};
     use sqlparser::ast::CharLengthUnits;
     use std::io::{Cursor, Seek, SeekFrom};
 
@@ -154,12 +189,37 @@ pub(crate) mod test {
         )?;
         fn_assert(&mut cursor, LogicalType::Date)?;
         fn_assert(&mut cursor, LogicalType::DateTime)?;
+        fn_assert(&mut cursor, LogicalType::TimestampTz)?;
         fn_assert(&mut cursor, LogicalType::Time)?;
+        fn_assert(&mut cursor, LogicalType::Interval)?;
+        fn_assert(&mut cursor, LogicalType::Json)?;
+        fn_assert(&mut cursor, LogicalType::Uuid)?;
+        fn_assert(&mut cursor, LogicalType::Bytea)?;
         fn_assert(&mut cursor, LogicalType::Decimal(Some(4), Some(2)))?;
         fn_assert(&mut cursor, LogicalType::Decimal(Some(4), None))?;
         fn_assert(&mut cursor, LogicalType::Decimal(None, Some(2)))?;
         fn_assert(&mut cursor, LogicalType::Decimal(None, None))?;
         fn_assert(&mut cursor, LogicalType::Tuple)?;
+        fn_assert(
+            &mut cursor,
+            LogicalType::Struct(StructType::new(vec![
+                ("a".to_string(), LogicalType::Integer),
+                (
+                    "b".to_string(),
+                    LogicalType::Struct(StructType::new(vec![(
+                        "c".to_string(),
+                        LogicalType::Varchar(None, CharLengthUnits::Characters),
+                    )])),
+                ),
+            ])),
+        )?;
+        fn_assert(
+            &mut cursor,
+            LogicalType::Array(ArrayType::new(LogicalType::Varchar(
+                Some(10),
+                CharLengthUnits::Characters,
+            ))),
+        )?;
 
         Ok(())
     }
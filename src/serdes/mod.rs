@@ -3,7 +3,10 @@ mod binary_operator;
 mod boolean;
 mod char;
 mod char_length_units;
+mod collation;
 mod column;
+mod compression;
+mod data_value;
 mod evaluator;
 mod logic_type;
 mod num;
@@ -3,7 +3,9 @@ use crate::errors::DatabaseError;
 use crate::expression::agg::AggKind;
 use crate::expression::function::scala::ScalarFunction;
 use crate::expression::function::table::TableFunction;
-use crate::expression::{AliasType, BinaryOperator, ScalarExpression, UnaryOperator};
+use crate::expression::window::WindowKind;
+use crate::expression::{AliasType, BinaryOperator, Collation, ScalarExpression, UnaryOperator};
+use crate::planner::operator::sort::SortField;
 use crate::serdes::{ReferenceSerialization, ReferenceTables, Serialization};
 use crate::storage::{TableCache, Transaction};
 use crate::types::evaluator::{BinaryEvaluatorBox, UnaryEvaluatorBox};
@@ -24,21 +26,7 @@ impl ReferenceSerialization for ScalarExpression {
             ScalarExpression::Constant(value) => {
                 writer.write_all(&[0u8])?;
 
-                let logical_type = value.logical_type();
-
-                logical_type.encode(writer)?;
-                value.is_null().encode(writer)?;
-
-                if value.is_null() {
-                    return Ok(());
-                }
-                if logical_type.raw_len().is_none() {
-                    let mut bytes = Vec::new();
-                    (value.to_raw(&mut bytes)? as u32).encode(writer)?;
-                    writer.write_all(&bytes)?;
-                } else {
-                    let _ = value.to_raw(writer)?;
-                }
+                DataValue::encode(value, writer)?;
             }
             ScalarExpression::ColumnRef(column) => {
                 writer.write_all(&[1u8])?;
@@ -51,11 +39,12 @@ impl ReferenceSerialization for ScalarExpression {
                 expr.encode(writer, is_direct, reference_tables)?;
                 alias.encode(writer, is_direct, reference_tables)?;
             }
-            ScalarExpression::TypeCast { expr, ty } => {
+            ScalarExpression::TypeCast { expr, ty, is_try } => {
                 writer.write_all(&[3u8])?;
 
                 expr.encode(writer, is_direct, reference_tables)?;
                 ty.encode(writer)?;
+                is_try.encode(writer)?;
             }
             ScalarExpression::IsNull { expr, negated } => {
                 writer.write_all(&[4u8])?;
@@ -95,6 +84,7 @@ impl ReferenceSerialization for ScalarExpression {
                 distinct,
                 kind,
                 args,
+                filter,
                 ty,
             } => {
                 writer.write_all(&[7u8])?;
@@ -105,6 +95,7 @@ impl ReferenceSerialization for ScalarExpression {
                 for arg in args.iter() {
                     arg.encode(writer, is_direct, reference_tables)?
                 }
+                filter.encode(writer, is_direct, reference_tables)?;
                 ty.encode(writer)?;
             }
             ScalarExpression::In {
@@ -222,8 +213,15 @@ impl ReferenceSerialization for ScalarExpression {
                 right_expr.encode(writer, is_direct, reference_tables)?;
                 ty.encode(writer)?;
             }
-            ScalarExpression::Coalesce { exprs, ty } => {
-                writer.write_all(&[21u8])?;
+            ScalarExpression::Coalesce { exprs, ty }
+            | ScalarExpression::Greatest { exprs, ty }
+            | ScalarExpression::Least { exprs, ty } => {
+                let type_id = match self {
+                    ScalarExpression::Coalesce { .. } => 21u8,
+                    ScalarExpression::Greatest { .. } => 26u8,
+                    _ => 27u8,
+                };
+                writer.write_all(&[type_id])?;
 
                 (exprs.len() as u32).encode(writer)?;
                 for expr in exprs.iter() {
@@ -248,6 +246,50 @@ impl ReferenceSerialization for ScalarExpression {
                 else_expr.encode(writer, is_direct, reference_tables)?;
                 ty.encode(writer)?;
             }
+            ScalarExpression::FieldAccess { expr, index, ty } => {
+                writer.write_all(&[23u8])?;
+
+                expr.encode(writer, is_direct, reference_tables)?;
+                (*index as u32).encode(writer)?;
+                ty.encode(writer)?;
+            }
+            ScalarExpression::Collate { expr, collation } => {
+                writer.write_all(&[24u8])?;
+
+                expr.encode(writer, is_direct, reference_tables)?;
+                collation.encode(writer)?;
+            }
+            ScalarExpression::WindowCall {
+                kind,
+                args,
+                partition_by,
+                order_by,
+                ty,
+            } => {
+                writer.write_all(&[25u8])?;
+
+                kind.encode(writer)?;
+                (args.len() as u32).encode(writer)?;
+                for arg in args.iter() {
+                    arg.encode(writer, is_direct, reference_tables)?
+                }
+                (partition_by.len() as u32).encode(writer)?;
+                for expr in partition_by.iter() {
+                    expr.encode(writer, is_direct, reference_tables)?
+                }
+                (order_by.len() as u32).encode(writer)?;
+                for SortField {
+                    expr,
+                    asc,
+                    nulls_first,
+                } in order_by.iter()
+                {
+                    expr.encode(writer, is_direct, reference_tables)?;
+                    asc.encode(writer)?;
+                    nulls_first.encode(writer)?;
+                }
+                ty.encode(writer)?;
+            }
         }
 
         Ok(())
@@ -262,25 +304,7 @@ impl ReferenceSerialization for ScalarExpression {
         reader.read_exact(&mut type_bytes)?;
 
         Ok(match type_bytes[0] {
-            0 => {
-                let logical_type = LogicalType::decode(reader)?;
-                let is_null = bool::decode(reader)?;
-
-                let value = if is_null {
-                    DataValue::none(&logical_type)
-                } else {
-                    let value_len = match logical_type.raw_len() {
-                        None => u32::decode(reader)? as usize,
-                        Some(len) => len,
-                    };
-                    let mut buf = vec![0u8; value_len];
-                    reader.read_exact(&mut buf)?;
-
-                    DataValue::from_raw(&buf, &logical_type)
-                };
-
-                ScalarExpression::Constant(Arc::new(value))
-            }
+            0 => ScalarExpression::Constant(Arc::new(DataValue::decode(reader)?)),
             1 => {
                 let column_ref = ColumnRef::decode(reader, drive, reference_tables)?;
 
@@ -295,8 +319,9 @@ impl ReferenceSerialization for ScalarExpression {
             3 => {
                 let expr = Box::<ScalarExpression>::decode(reader, drive, reference_tables)?;
                 let ty = LogicalType::decode(reader)?;
+                let is_try = bool::decode(reader)?;
 
-                ScalarExpression::TypeCast { expr, ty }
+                ScalarExpression::TypeCast { expr, ty, is_try }
             }
             4 => {
                 let expr = Box::<ScalarExpression>::decode(reader, drive, reference_tables)?;
@@ -341,12 +366,15 @@ impl ReferenceSerialization for ScalarExpression {
                 for _ in 0..args_len {
                     args.push(ScalarExpression::decode(reader, drive, reference_tables)?);
                 }
+                let filter =
+                    Option::<Box<ScalarExpression>>::decode(reader, drive, reference_tables)?;
                 let ty = LogicalType::decode(reader)?;
 
                 ScalarExpression::AggCall {
                     distinct,
                     kind,
                     args,
+                    filter,
                     ty,
                 }
             }
@@ -472,7 +500,7 @@ impl ReferenceSerialization for ScalarExpression {
                     ty,
                 }
             }
-            21 => {
+            type_id @ (21 | 26 | 27) => {
                 let exprs_len = u32::decode(reader)? as usize;
 
                 let mut exprs = Vec::with_capacity(exprs_len);
@@ -481,7 +509,11 @@ impl ReferenceSerialization for ScalarExpression {
                 }
                 let ty = LogicalType::decode(reader)?;
 
-                ScalarExpression::Coalesce { exprs, ty }
+                match type_id {
+                    21 => ScalarExpression::Coalesce { exprs, ty },
+                    26 => ScalarExpression::Greatest { exprs, ty },
+                    _ => ScalarExpression::Least { exprs, ty },
+                }
             }
             22 => {
                 let operand_expr =
@@ -507,6 +539,53 @@ impl ReferenceSerialization for ScalarExpression {
                     ty,
                 }
             }
+            23 => {
+                let expr = Box::<ScalarExpression>::decode(reader, drive, reference_tables)?;
+                let index = u32::decode(reader)? as usize;
+                let ty = LogicalType::decode(reader)?;
+
+                ScalarExpression::FieldAccess { expr, index, ty }
+            }
+            24 => {
+                let expr = Box::<ScalarExpression>::decode(reader, drive, reference_tables)?;
+                let collation = Collation::decode(reader)?;
+
+                ScalarExpression::Collate { expr, collation }
+            }
+            25 => {
+                let kind = WindowKind::decode(reader)?;
+                let args_len = u32::decode(reader)? as usize;
+
+                let mut args = Vec::with_capacity(args_len);
+                for _ in 0..args_len {
+                    args.push(ScalarExpression::decode(reader, drive, reference_tables)?);
+                }
+                let partition_len = u32::decode(reader)? as usize;
+
+                let mut partition_by = Vec::with_capacity(partition_len);
+                for _ in 0..partition_len {
+                    partition_by.push(ScalarExpression::decode(reader, drive, reference_tables)?);
+                }
+                let order_len = u32::decode(reader)? as usize;
+
+                let mut order_by = Vec::with_capacity(order_len);
+                for _ in 0..order_len {
+                    let expr = ScalarExpression::decode(reader, drive, reference_tables)?;
+                    let asc = bool::decode(reader)?;
+                    let nulls_first = bool::decode(reader)?;
+
+                    order_by.push(SortField::new(expr, asc, nulls_first));
+                }
+                let ty = LogicalType::decode(reader)?;
+
+                ScalarExpression::WindowCall {
+                    kind,
+                    args,
+                    partition_by,
+                    order_by,
+                    ty,
+                }
+            }
             _ => unreachable!(),
         })
     }
@@ -520,9 +599,13 @@ pub(crate) mod test {
     use crate::expression::agg::AggKind;
     use crate::expression::function::scala::ScalarFunction;
     use crate::expression::function::table::TableFunction;
-    use crate::expression::{AliasType, BinaryOperator, ScalarExpression, UnaryOperator};
+    use crate::expression::window::WindowKind;
+    use crate::expression::{
+        AliasType, BinaryOperator, Collation, ScalarExpression, UnaryOperator,
+    };
     use crate::function::current_date::CurrentDate;
     use crate::function::numbers::Numbers;
+    use crate::planner::operator::sort::SortField;
     use crate::serdes::{ReferenceSerialization, ReferenceTables};
     use crate::storage::rocksdb::{RocksStorage, RocksTransaction};
     use crate::storage::{Storage, TableCache};
@@ -642,6 +725,38 @@ pub(crate) mod test {
             ScalarExpression::TypeCast {
                 expr: Box::new(ScalarExpression::Empty),
                 ty: LogicalType::Integer,
+                is_try: false,
+            },
+            Some((&transaction, &table_cache)),
+            &mut reference_tables,
+        )?;
+        fn_assert(
+            &mut cursor,
+            ScalarExpression::FieldAccess {
+                expr: Box::new(ScalarExpression::Empty),
+                index: 1,
+                ty: LogicalType::Integer,
+            },
+            Some((&transaction, &table_cache)),
+            &mut reference_tables,
+        )?;
+        fn_assert(
+            &mut cursor,
+            ScalarExpression::Collate {
+                expr: Box::new(ScalarExpression::Empty),
+                collation: Collation::NoCase,
+            },
+            Some((&transaction, &table_cache)),
+            &mut reference_tables,
+        )?;
+        fn_assert(
+            &mut cursor,
+            ScalarExpression::WindowCall {
+                kind: WindowKind::Aggregate(AggKind::Sum),
+                args: vec![ScalarExpression::Empty],
+                partition_by: vec![ScalarExpression::Empty],
+                order_by: vec![SortField::new(ScalarExpression::Empty, false, true)],
+                ty: LogicalType::Integer,
             },
             Some((&transaction, &table_cache)),
             &mut reference_tables,
@@ -707,6 +822,7 @@ pub(crate) mod test {
                 distinct: true,
                 kind: AggKind::Avg,
                 args: vec![ScalarExpression::Empty],
+                filter: Some(Box::new(ScalarExpression::Empty)),
                 ty: LogicalType::Integer,
             },
             Some((&transaction, &table_cache)),
@@ -872,6 +988,24 @@ pub(crate) mod test {
             Some((&transaction, &table_cache)),
             &mut reference_tables,
         )?;
+        fn_assert(
+            &mut cursor,
+            ScalarExpression::Greatest {
+                exprs: vec![ScalarExpression::Empty, ScalarExpression::Empty],
+                ty: LogicalType::Integer,
+            },
+            Some((&transaction, &table_cache)),
+            &mut reference_tables,
+        )?;
+        fn_assert(
+            &mut cursor,
+            ScalarExpression::Least {
+                exprs: vec![ScalarExpression::Empty, ScalarExpression::Empty],
+                ty: LogicalType::Integer,
+            },
+            Some((&transaction, &table_cache)),
+            &mut reference_tables,
+        )?;
         fn_assert(
             &mut cursor,
             ScalarExpression::CaseWhen {
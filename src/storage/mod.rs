@@ -1,29 +1,37 @@
 pub mod rocksdb;
-mod table_codec;
+pub(crate) mod table_codec;
 
-use crate::catalog::{ColumnCatalog, ColumnRef, TableCatalog, TableMeta, TableName};
+use crate::catalog::{
+    is_temp_table, temp_schema_of, CheckConstraint, ColumnCatalog, ColumnRef, ForeignKey,
+    TableCatalog, TableMeta, TableName, UniqueKey, View,
+};
 use crate::errors::DatabaseError;
 use crate::expression::range_detacher::Range;
+use crate::expression::ScalarExpression;
+use crate::function::json::JsonPath;
 use crate::optimizer::core::statistics_meta::{StatisticMetaLoader, StatisticsMeta};
 use crate::serdes::ReferenceTables;
-use crate::storage::table_codec::TableCodec;
-use crate::types::index::{Index, IndexId, IndexMetaRef, IndexType};
+use crate::storage::table_codec::{TableCodec, FORMAT_VERSION};
+use crate::types::index::{Index, IndexId, IndexMeta, IndexMetaRef, IndexType};
 use crate::types::tuple::{Tuple, TupleId};
 use crate::types::value::{DataValue, ValueRef};
 use crate::types::{ColumnId, LogicalType};
 use crate::utils::lru::ShardingLruCache;
 use bytes::Bytes;
+use integer_encoding::FixedInt;
 use itertools::Itertools;
 use std::collections::{Bound, VecDeque};
 use std::io::Cursor;
 use std::ops::SubAssign;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::{mem, slice};
 
 pub(crate) type StatisticsMetaCache = ShardingLruCache<(TableName, IndexId), StatisticsMeta>;
 pub(crate) type TableCache = ShardingLruCache<String, TableCatalog>;
 
-pub trait Storage: Clone {
+// Tips: `'static`, as the transactions kept by the sessions own a clone of their storage
+pub trait Storage: Clone + 'static {
     type TransactionType<'a>: Transaction
     where
         Self: 'a;
@@ -34,6 +42,19 @@ pub trait Storage: Clone {
 /// Optional bounds of the reader, of the form (offset, limit).
 pub(crate) type Bounds = (Option<usize>, Option<usize>);
 
+/// The number of the changes of the row count of a table, see [`Transaction::add_row_count`],
+/// from which they are folded into one.
+pub(crate) const ROW_COUNT_FOLD_THRESHOLD: usize = 64;
+
+static ROW_COUNT_DELTA_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The id of the next change of a row count, which increases in the order the changes are
+/// recorded. It continues from the highest id stored once the storage is opened, see
+/// [`Transaction::seed_row_count_delta_id`], and `0` is left to the changes folded.
+fn next_row_count_delta_id() -> u64 {
+    ROW_COUNT_DELTA_ID.fetch_add(1, Ordering::Relaxed) + 1
+}
+
 pub trait Transaction: Sized {
     type IterType<'a>: InnerIter
     where
@@ -56,8 +77,9 @@ pub trait Transaction: Sized {
             .table(table_cache, table_name.clone())
             .ok_or(DatabaseError::TableNotFound)?;
         let table_types = table.types();
+        let pk_indices = table.primary_key_indices()?;
         if columns.is_empty() {
-            let (i, column) = table.primary_key()?;
+            let (i, column) = table.primary_keys()?[0];
             columns.push((i, column.clone()));
         }
         let mut tuple_columns = Vec::with_capacity(columns.len());
@@ -74,6 +96,7 @@ pub trait Transaction: Sized {
             offset: bounds.0.unwrap_or(0),
             limit: bounds.1,
             table_types,
+            pk_indices,
             tuple_columns: Arc::new(tuple_columns),
             projections,
             iter,
@@ -96,6 +119,7 @@ pub trait Transaction: Sized {
             .table(table_cache, table_name.clone())
             .ok_or(DatabaseError::TableNotFound)?;
         let table_types = table.types();
+        let pk_indices = table.primary_key_indices()?;
         let table_name = table.name.as_str();
         let offset = offset_option.unwrap_or(0);
 
@@ -105,7 +129,7 @@ pub trait Transaction: Sized {
             tuple_columns.push(column);
             projections.push(projection);
         }
-        let inner = IndexImplEnum::instance(index_meta.ty);
+        let inner = IndexImplEnum::instance(&index_meta);
 
         Ok(IndexIter {
             offset,
@@ -116,10 +140,12 @@ pub trait Transaction: Sized {
                 index_meta,
                 table_name,
                 table_types,
+                pk_indices,
                 tx: self,
             },
             inner,
             ranges: VecDeque::from(ranges),
+            reverse: false,
             scope_iter: None,
         })
     }
@@ -131,9 +157,10 @@ pub trait Transaction: Sized {
         index_name: String,
         column_ids: Vec<ColumnId>,
         ty: IndexType,
+        json_path: JsonPath,
     ) -> Result<IndexId, DatabaseError> {
         if let Some(mut table) = self.table(table_cache, table_name.clone()).cloned() {
-            let index_meta = table.add_index_meta(index_name, column_ids, ty)?;
+            let index_meta = table.add_index_meta(index_name, column_ids, ty, json_path)?;
             let (key, value) = TableCodec::encode_index_meta(table_name, index_meta)?;
             self.set(key, value)?;
             table_cache.remove(table_name);
@@ -144,9 +171,12 @@ pub trait Transaction: Sized {
         }
     }
 
+    /// Adds the entry of the tuple to the index named `index_name`, which names the unique
+    /// constraint the entry violates.
     fn add_index(
         &mut self,
         table_name: &str,
+        index_name: &str,
         index: Index,
         tuple_id: &TupleId,
     ) -> Result<(), DatabaseError> {
@@ -155,10 +185,13 @@ pub trait Transaction: Sized {
         }
         let (key, value) = TableCodec::encode_index(table_name, &index, tuple_id)?;
 
-        if matches!(index.ty, IndexType::Unique) {
+        if matches!(index.ty, IndexType::Unique { .. }) {
             if let Some(bytes) = self.get(&key)? {
                 return if bytes != value {
-                    Err(DatabaseError::DuplicateUniqueValue)
+                    Err(DatabaseError::DuplicateUniqueValue(
+                        index_name.to_string(),
+                        index.column_values.iter().join(", "),
+                    ))
                 } else {
                     Ok(())
                 };
@@ -187,10 +220,10 @@ pub trait Transaction: Sized {
         &mut self,
         table_name: &str,
         tuple: Tuple,
-        types: &[LogicalType],
+        columns: &[ColumnRef],
         is_overwrite: bool,
     ) -> Result<(), DatabaseError> {
-        let (key, value) = TableCodec::encode_tuple(table_name, &tuple, types)?;
+        let (key, value) = TableCodec::encode_tuple(table_name, &tuple, columns)?;
 
         if !is_overwrite && self.get(&key)?.is_some() {
             return Err(DatabaseError::DuplicatePrimaryKey);
@@ -207,11 +240,140 @@ pub trait Transaction: Sized {
         Ok(())
     }
 
+    /// The exact number of tuples of the table, the sum of the changes recorded by
+    /// `add_row_count`.
+    ///
+    /// The count is exact as of the read: the changes are summed by one iterator, which sees the
+    /// changes of the committed transactions and of this one, and a fold of them either entirely
+    /// or not at all, as `compact_row_count` replaces them with their sum in one transaction.
+    ///
+    /// Tips: `None` if the table was created before the row count was maintained, or if a change
+    /// is unknown since an `INSERT OVERWRITE`, until the table is truncated
+    fn row_count(&self, table_name: &str) -> Result<Option<usize>, DatabaseError> {
+        let (min, max) = TableCodec::row_count_bound(table_name);
+        let mut iter = self.range(Bound::Included(&min), Bound::Included(&max))?;
+        let mut row_count = None;
+
+        while let Some((_, value)) = iter.try_next()? {
+            let Some(delta) = TableCodec::decode_row_count_delta(&value) else {
+                return Ok(None);
+            };
+            row_count = Some(row_count.unwrap_or(0) + delta);
+        }
+
+        Ok(row_count.map(|row_count| row_count.max(0) as usize))
+    }
+
+    /// Records a change of the row count of the table, `None` if it is unknown. Every change is
+    /// written under a key of its own instead of being added to the count, so that the concurrent
+    /// writers of a table never conflict on it. The write is a single put, the changes are
+    /// folded after this transaction commits, see [`Transaction::fold_row_count_on_commit`].
+    fn add_row_count(&mut self, table_name: &str, delta: Option<i64>) -> Result<(), DatabaseError> {
+        if delta == Some(0) {
+            return Ok(());
+        }
+        let (key, value) =
+            TableCodec::encode_row_count_delta(table_name, next_row_count_delta_id(), delta);
+        self.set(key, value)?;
+        self.fold_row_count_on_commit(table_name);
+
+        Ok(())
+    }
+
+    /// Folds the changes of the row count of the table with `compact_row_count` in a
+    /// transaction of its own once this one commits, if the table has
+    /// [`ROW_COUNT_FOLD_THRESHOLD`] changes by then. The fold is given up if it fails, e.g. on
+    /// the conflict with a concurrent fold, so that the statements of this transaction never
+    /// fail on it. The storages that cannot do so leave them to `ANALYZE`.
+    fn fold_row_count_on_commit(&mut self, _table_name: &str) {}
+
+    /// Continues the ids of the changes of the row counts from the highest one stored, so that
+    /// the changes recorded after a restart never replace the ones recorded before it.
+    fn seed_row_count_delta_id(&self) -> Result<(), DatabaseError> {
+        for meta in self.table_metas()? {
+            let (min, max) = TableCodec::row_count_bound(&meta.table_name);
+            let mut iter = self.range_rev(Bound::Included(&min), Bound::Included(&max))?;
+
+            if let Some((key, _)) = iter.try_next()? {
+                ROW_COUNT_DELTA_ID.fetch_max(
+                    TableCodec::decode_row_count_delta_id(&key),
+                    Ordering::Relaxed,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Folds the recorded changes of the row count into one, which keeps `row_count` cheap. The
+    /// changes are read for update, so that of two concurrent folds only the first to commit
+    /// succeeds, while the writers of the table recording new changes are not affected.
+    fn compact_row_count(&mut self, table_name: &str) -> Result<(), DatabaseError> {
+        let (min, max) = TableCodec::row_count_bound(table_name);
+        let mut iter = self.range(Bound::Included(&min), Bound::Included(&max))?;
+        let mut delta_keys = vec![];
+
+        while let Some((key, _)) = iter.try_next()? {
+            delta_keys.push(key);
+        }
+        drop(iter);
+
+        if delta_keys.len() < 2 {
+            return Ok(());
+        }
+        let mut row_count = Some(0);
+        for key in delta_keys {
+            if let Some(bytes) = self.get_for_update(&key)? {
+                row_count = row_count
+                    .zip(TableCodec::decode_row_count_delta(&bytes))
+                    .map(|(row_count, delta)| row_count + delta);
+                self.remove(&key)?;
+            }
+        }
+        let (key, value) = TableCodec::encode_row_count_delta(table_name, 0, row_count);
+        self.set(key, value)?;
+
+        Ok(())
+    }
+
+    /// Drops the recorded changes of the row count, which starts over at `0` if it was
+    /// maintained.
+    fn reset_row_count(&mut self, table_name: &str) -> Result<(), DatabaseError> {
+        let (min, max) = TableCodec::row_count_bound(table_name);
+
+        if self._drop_data(&min, &max)? > 0 {
+            let (key, value) = TableCodec::encode_row_count_delta(table_name, 0, Some(0));
+            self.set(key, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// The last value assigned to the auto-increment column of the table, `0` if none. The value
+    /// is read for update, so that the concurrent transactions assigning the same values
+    /// conflict on commit.
+    fn sequence_value(&self, table_name: &str) -> Result<i64, DatabaseError> {
+        Ok(self
+            .get_for_update(&TableCodec::encode_sequence_key(table_name))?
+            .map(|bytes| TableCodec::decode_sequence(&bytes))
+            .unwrap_or(0))
+    }
+
+    fn set_sequence_value(&mut self, table_name: &str, value: i64) -> Result<(), DatabaseError> {
+        let (key, value) = TableCodec::encode_sequence(table_name, value);
+        self.set(key, value)
+    }
+
+    /// Restarts the sequence of the table, e.g. `TRUNCATE t RESTART IDENTITY`.
+    fn reset_sequence(&mut self, table_name: &str) -> Result<(), DatabaseError> {
+        self.remove(&TableCodec::encode_sequence_key(table_name))
+    }
+
     fn add_column(
         &mut self,
         table_cache: &TableCache,
         table_name: &TableName,
         column: &ColumnCatalog,
+        checks: &[CheckConstraint],
         if_not_exists: bool,
     ) -> Result<ColumnId, DatabaseError> {
         if let Some(mut table) = self.table(table_cache, table_name.clone()).cloned() {
@@ -234,7 +396,10 @@ pub trait Transaction: Sized {
                 let meta_ref = table.add_index_meta(
                     format!("uk_{}", column.name()),
                     vec![col_id],
-                    IndexType::Unique,
+                    IndexType::Unique {
+                        nulls_not_distinct: column.desc.nulls_not_distinct,
+                    },
+                    vec![],
                 )?;
                 let (key, value) = TableCodec::encode_index_meta(table_name, meta_ref)?;
                 self.set(key, value)?;
@@ -243,6 +408,12 @@ pub trait Transaction: Sized {
             let column = table.get_column_by_id(&col_id).unwrap();
             let (key, value) = TableCodec::encode_column(column, &mut ReferenceTables::new())?;
             self.set(key, value)?;
+
+            if !checks.is_empty() {
+                self.update_table_meta(table_name, |table_meta| {
+                    table_meta.checks.extend_from_slice(checks)
+                })?;
+            }
             table_cache.remove(table_name);
 
             Ok(col_id)
@@ -261,6 +432,19 @@ pub trait Transaction: Sized {
         if let Some(table_catalog) = self.table(table_cache, table_name.clone()).cloned() {
             let column = table_catalog.get_column_by_name(column_name).unwrap();
 
+            for (child_name, foreign_key) in self.referencing_foreign_keys(table_name)? {
+                // Tips: the foreign key of the table on the column goes with it
+                let is_dropped = &child_name == table_name && foreign_key.column == column_name;
+
+                if foreign_key.ref_column == column_name && !is_dropped {
+                    return Err(DatabaseError::ForeignKeyDependency(
+                        format!("column {} of table {}", column_name, table_name),
+                        foreign_key.name,
+                        child_name.to_string(),
+                    ));
+                }
+            }
+
             let (key, _) = TableCodec::encode_column(column, &mut ReferenceTables::new())?;
             self.remove(&key)?;
 
@@ -276,6 +460,20 @@ pub trait Transaction: Sized {
 
                 self.remove_table_meta(meta_cache, table_name, index_meta.id)?;
             }
+            // the checks and the foreign keys on the column go with it
+            for foreign_key in table_catalog.foreign_keys() {
+                if foreign_key.column == column_name {
+                    table_cache.remove(&foreign_key.ref_table);
+                }
+            }
+            self.update_table_meta(table_name, |table_meta| {
+                table_meta
+                    .checks
+                    .retain(|check| !check.columns.iter().any(|name| name == column_name));
+                table_meta
+                    .foreign_keys
+                    .retain(|foreign_key| foreign_key.column != column_name);
+            })?;
             table_cache.remove(table_name);
 
             Ok(())
@@ -284,28 +482,360 @@ pub trait Transaction: Sized {
         }
     }
 
+    /// Rewrites the `TableMeta` of the table, e.g. its `CHECK` constraints.
+    fn update_table_meta<F: FnOnce(&mut TableMeta)>(
+        &mut self,
+        table_name: &str,
+        f: F,
+    ) -> Result<(), DatabaseError> {
+        let mut table_meta = self
+            .table_meta(table_name)?
+            .ok_or(DatabaseError::TableNotFound)?;
+        f(&mut table_meta);
+
+        let (key, value) = TableCodec::encode_root_table(&table_meta)?;
+        self.set(key, value)
+    }
+
+    /// Changes the type of the column in the catalog. The entries of the indexes on the column
+    /// are dropped, to be rebuilt from the rewritten tuples.
+    ///
+    /// Tips: the tuple id is the primary key, so changing its type drops the entries of every index
+    fn change_column_type(
+        &mut self,
+        table_cache: &TableCache,
+        meta_cache: &StatisticsMetaCache,
+        table_name: &TableName,
+        column_name: &str,
+        ty: LogicalType,
+    ) -> Result<(), DatabaseError> {
+        let table_catalog = self
+            .table(table_cache, table_name.clone())
+            .cloned()
+            .ok_or(DatabaseError::TableNotFound)?;
+        let column = table_catalog
+            .get_column_by_name(column_name)
+            .ok_or_else(|| DatabaseError::NotFound("column", column_name.to_string()))?;
+        let column_id = column.id().unwrap();
+        let primary = column.desc.primary;
+        let is_primary = primary.is_some();
+
+        // Tips: the keys of a foreign key are looked up by their encoding, which is of their type
+        for (child_name, foreign_key) in self.referencing_foreign_keys(table_name)? {
+            if foreign_key.ref_column == column_name {
+                return Err(DatabaseError::ForeignKeyDependency(
+                    format!("column {} of table {}", column_name, table_name),
+                    foreign_key.name,
+                    child_name.to_string(),
+                ));
+            }
+        }
+        if let Some(foreign_key) = table_catalog
+            .foreign_keys()
+            .find(|foreign_key| foreign_key.column == column_name)
+        {
+            return Err(DatabaseError::ForeignKeyDependency(
+                format!("column {} of table {}", column_name, table_name),
+                foreign_key.name.clone(),
+                table_name.to_string(),
+            ));
+        }
+
+        if is_primary {
+            TableCodec::check_primary_key_type(&ty)?;
+        }
+        let mut column = ColumnCatalog::clone(column);
+        if let Some(default) = column.desc.default.take() {
+            column.desc.default = Some(if default.return_type() != ty {
+                ScalarExpression::TypeCast {
+                    expr: Box::new(default),
+                    ty: ty.clone(),
+                    is_try: false,
+                }
+            } else {
+                default
+            });
+        }
+        column.desc.column_datatype = ty.clone();
+
+        let (key, value) =
+            TableCodec::encode_column(&Arc::new(column), &mut ReferenceTables::new())?;
+        self.set(key, value)?;
+
+        for index_meta in table_catalog.indexes() {
+            let is_on_column = index_meta.column_ids.contains(&column_id);
+
+            if !is_primary && !is_on_column {
+                continue;
+            }
+            if let Some(i) = primary {
+                let mut index_meta = IndexMeta::clone(index_meta);
+                index_meta.pk_types[i] = ty.clone();
+
+                let (key, value) = TableCodec::encode_index_meta(table_name, &index_meta)?;
+                self.set(key, value)?;
+            }
+            let (index_min, index_max) = TableCodec::index_bound(table_name, &index_meta.id);
+            self._drop_data(&index_min, &index_max)?;
+
+            if is_on_column {
+                self.remove_table_meta(meta_cache, table_name, index_meta.id)?;
+            }
+        }
+        table_cache.remove(table_name);
+
+        Ok(())
+    }
+
+    /// Drops the index of the `UNIQUE` constraint named `index_name`, unlike a plain index.
+    fn drop_unique_constraint(
+        &mut self,
+        table_cache: &TableCache,
+        meta_cache: &StatisticsMetaCache,
+        table_name: &TableName,
+        index_name: &str,
+        if_exists: bool,
+    ) -> Result<(), DatabaseError> {
+        let table_catalog = self
+            .table(table_cache, table_name.clone())
+            .cloned()
+            .ok_or(DatabaseError::TableNotFound)?;
+        // Tips: a plain index is not a constraint, so it cannot be dropped as one
+        let Some(index_meta) = table_catalog.indexes().find(|meta| {
+            meta.name == index_name
+                && matches!(meta.ty, IndexType::PrimaryKey | IndexType::Unique { .. })
+        }) else {
+            return if if_exists {
+                Ok(())
+            } else {
+                Err(DatabaseError::NotFound(
+                    "constraint",
+                    index_name.to_string(),
+                ))
+            };
+        };
+        if matches!(index_meta.ty, IndexType::PrimaryKey) {
+            return Err(DatabaseError::UnsupportedStmt(format!(
+                "drop primary key: {}",
+                index_name
+            )));
+        }
+        let (index_meta_key, _) = TableCodec::encode_index_meta(table_name, index_meta)?;
+        self.remove(&index_meta_key)?;
+
+        let (index_min, index_max) = TableCodec::index_bound(table_name, &index_meta.id);
+        self._drop_data(&index_min, &index_max)?;
+
+        self.remove_table_meta(meta_cache, table_name, index_meta.id)?;
+
+        // the column declared `UNIQUE` no longer has an index to guarantee it
+        if let [column_id] = index_meta.column_ids[..] {
+            if let Some(column) = table_catalog
+                .get_column_by_id(&column_id)
+                .filter(|column| column.desc.is_unique && index_meta.json_path.is_empty())
+            {
+                let mut column = ColumnCatalog::clone(column);
+                column.desc.is_unique = false;
+
+                let (key, value) =
+                    TableCodec::encode_column(&Arc::new(column), &mut ReferenceTables::new())?;
+                self.set(key, value)?;
+            }
+        }
+        table_cache.remove(table_name);
+
+        Ok(())
+    }
+
+    /// Keeps the `CHECK` constraint added to the table, whose existing rows are checked already.
+    fn add_check(
+        &mut self,
+        table_cache: &TableCache,
+        table_name: &TableName,
+        check: CheckConstraint,
+    ) -> Result<(), DatabaseError> {
+        self.update_table_meta(table_name, |table_meta| table_meta.checks.push(check))?;
+        table_cache.remove(table_name);
+
+        Ok(())
+    }
+
+    /// Keeps the `FOREIGN KEY` added to the table, whose existing rows are checked already.
+    fn add_foreign_key(
+        &mut self,
+        table_cache: &TableCache,
+        table_name: &TableName,
+        foreign_key: ForeignKey,
+    ) -> Result<(), DatabaseError> {
+        // Tips: the referenced table is reloaded to be referenced by the foreign key
+        table_cache.remove(&foreign_key.ref_table);
+        self.update_table_meta(table_name, |table_meta| {
+            table_meta.foreign_keys.push(foreign_key)
+        })?;
+        table_cache.remove(table_name);
+
+        Ok(())
+    }
+
+    /// Sets the comment of the table, or of its column `column_name` if given, which is removed
+    /// if `comment` is `None`.
+    fn set_comment(
+        &mut self,
+        table_cache: &TableCache,
+        table_name: &TableName,
+        column_name: Option<&str>,
+        comment: Option<String>,
+    ) -> Result<(), DatabaseError> {
+        let table_catalog = self
+            .table(table_cache, table_name.clone())
+            .ok_or(DatabaseError::TableNotFound)?;
+
+        match column_name {
+            Some(column_name) => {
+                let mut column = table_catalog
+                    .get_column_by_name(column_name)
+                    .map(|column| ColumnCatalog::clone(column))
+                    .ok_or_else(|| DatabaseError::NotFound("column", column_name.to_string()))?;
+                column.desc.comment = comment;
+
+                let (key, value) =
+                    TableCodec::encode_column(&Arc::new(column), &mut ReferenceTables::new())?;
+                self.set(key, value)?;
+            }
+            None => {
+                self.update_table_meta(table_name, |table_meta| table_meta.comment = comment)?
+            }
+        }
+        table_cache.remove(table_name);
+
+        Ok(())
+    }
+
+    /// Drops the constraint of the table named `constraint_name`, which is a `CHECK`, a
+    /// `FOREIGN KEY` or the index of a `UNIQUE` constraint.
+    fn drop_constraint(
+        &mut self,
+        table_cache: &TableCache,
+        meta_cache: &StatisticsMetaCache,
+        table_name: &TableName,
+        constraint_name: &str,
+        if_exists: bool,
+    ) -> Result<(), DatabaseError> {
+        let table_catalog = self
+            .table(table_cache, table_name.clone())
+            .cloned()
+            .ok_or(DatabaseError::TableNotFound)?;
+
+        if table_catalog
+            .checks()
+            .any(|check| check.name == constraint_name)
+        {
+            self.update_table_meta(table_name, |table_meta| {
+                table_meta
+                    .checks
+                    .retain(|check| check.name != constraint_name)
+            })?;
+        } else if let Some(foreign_key) = table_catalog
+            .foreign_keys()
+            .find(|foreign_key| foreign_key.name == constraint_name)
+        {
+            table_cache.remove(&foreign_key.ref_table);
+            self.update_table_meta(table_name, |table_meta| {
+                table_meta
+                    .foreign_keys
+                    .retain(|foreign_key| foreign_key.name != constraint_name)
+            })?;
+        } else if table_catalog.indexes().any(|index_meta| {
+            index_meta.name == constraint_name
+                && matches!(
+                    index_meta.ty,
+                    IndexType::PrimaryKey | IndexType::Unique { .. }
+                )
+        }) {
+            return self.drop_unique_constraint(
+                table_cache,
+                meta_cache,
+                table_name,
+                constraint_name,
+                false,
+            );
+        } else if !if_exists {
+            return Err(DatabaseError::NotFound(
+                "constraint",
+                constraint_name.to_string(),
+            ));
+        }
+        table_cache.remove(table_name);
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn create_table(
         &mut self,
         table_cache: &TableCache,
         table_name: TableName,
         columns: Vec<ColumnCatalog>,
+        checks: Vec<CheckConstraint>,
+        unique_keys: Vec<UniqueKey>,
+        foreign_keys: Vec<ForeignKey>,
         if_not_exists: bool,
     ) -> Result<TableName, DatabaseError> {
         let mut table_catalog = TableCatalog::new(table_name.clone(), columns)?;
-        let (_, column) = table_catalog.primary_key()?;
 
-        TableCodec::check_primary_key_type(column.datatype())?;
+        for (_, column) in table_catalog.primary_keys()? {
+            TableCodec::check_primary_key_type(column.datatype())?;
+        }
 
-        let (table_key, value) =
-            TableCodec::encode_root_table(&TableMeta::empty(table_name.clone()))?;
-        if self.get(&table_key)?.is_some() {
+        let (table_key, value) = TableCodec::encode_root_table(&TableMeta {
+            checks: checks.clone(),
+            foreign_keys: foreign_keys.clone(),
+            ..TableMeta::empty(table_name.clone())
+        })?;
+        if self.get(&table_key)?.is_some() || self.view(&table_name)?.is_some() {
             if if_not_exists {
                 return Ok(table_name);
             }
             return Err(DatabaseError::TableExists);
         }
         self.create_index_meta_from_column(&mut table_catalog)?;
+        for UniqueKey { name, columns } in unique_keys {
+            let column_ids = columns
+                .iter()
+                .map(|column_name| {
+                    table_catalog
+                        .get_column_id_by_name(column_name)
+                        .ok_or_else(|| DatabaseError::NotFound("column", column_name.clone()))
+                })
+                .try_collect()?;
+            let meta_ref = table_catalog.add_index_meta(
+                name,
+                column_ids,
+                IndexType::Unique {
+                    nulls_not_distinct: false,
+                },
+                vec![],
+            )?;
+            let (key, value) = TableCodec::encode_index_meta(&table_name, meta_ref)?;
+            self.set(key, value)?;
+        }
         self.set(table_key, value)?;
+        // the tables referenced by the foreign keys are loaded again with their new references
+        for foreign_key in foreign_keys.iter() {
+            if foreign_key.ref_table == table_name {
+                table_catalog
+                    .referenced_by
+                    .push((table_name.clone(), foreign_key.clone()));
+            } else {
+                table_cache.remove(&foreign_key.ref_table);
+            }
+        }
+        table_catalog.checks = checks;
+        table_catalog.foreign_keys = foreign_keys;
+
+        let (row_count_key, row_count_value) =
+            TableCodec::encode_row_count_delta(&table_name, 0, Some(0));
+        self.set(row_count_key, row_count_value)?;
 
         let mut reference_tables = ReferenceTables::new();
         for column in table_catalog.columns() {
@@ -318,19 +848,99 @@ pub trait Transaction: Sized {
         Ok(table_name)
     }
 
+    /// Creates the table storing a materialized view, which remembers `view_query`
+    /// to be recomputed by `REFRESH MATERIALIZED VIEW`.
+    fn create_materialized_view(
+        &mut self,
+        table_cache: &TableCache,
+        view_name: TableName,
+        columns: Vec<ColumnCatalog>,
+        view_query: String,
+    ) -> Result<TableName, DatabaseError> {
+        let view_name = self.create_table(
+            table_cache,
+            view_name,
+            columns,
+            vec![],
+            vec![],
+            vec![],
+            false,
+        )?;
+        let (table_key, value) = TableCodec::encode_root_table(&TableMeta {
+            view_query: Some(view_query),
+            ..TableMeta::empty(view_name.clone())
+        })?;
+        self.set(table_key, value)?;
+
+        Ok(view_name)
+    }
+
+    fn table_meta(&self, table_name: &str) -> Result<Option<TableMeta>, DatabaseError> {
+        self.get(&TableCodec::encode_root_table_key(table_name))?
+            .map(|bytes| TableCodec::decode_root_table(&bytes))
+            .transpose()
+    }
+
+    /// Creates the view, which shares the names of tables.
+    fn create_view(&mut self, view: View, or_replace: bool) -> Result<(), DatabaseError> {
+        if self.table_meta(&view.name)?.is_some() {
+            return Err(DatabaseError::TableExists);
+        }
+        let (view_key, value) = TableCodec::encode_view(&view)?;
+        if !or_replace && self.get(&view_key)?.is_some() {
+            return Err(DatabaseError::ViewExists);
+        }
+        self.set(view_key, value)?;
+
+        Ok(())
+    }
+
+    fn drop_view(&mut self, view_name: TableName, if_exists: bool) -> Result<(), DatabaseError> {
+        let view_key = TableCodec::encode_view_key(&view_name);
+        if self.get(&view_key)?.is_none() {
+            if if_exists {
+                return Ok(());
+            } else {
+                return Err(DatabaseError::ViewNotFound);
+            }
+        }
+        self.remove(&view_key)?;
+
+        Ok(())
+    }
+
+    fn view(&self, view_name: &str) -> Result<Option<View>, DatabaseError> {
+        self.get(&TableCodec::encode_view_key(view_name))?
+            .map(|bytes| TableCodec::decode_view(&bytes))
+            .transpose()
+    }
+
+    fn views(&self) -> Result<Vec<View>, DatabaseError> {
+        let mut views = vec![];
+        let (min, max) = TableCodec::view_bound();
+        let mut iter = self.range(Bound::Included(&min), Bound::Included(&max))?;
+
+        while let Some((_, value)) = iter.try_next()? {
+            views.push(TableCodec::decode_view(&value)?);
+        }
+
+        Ok(views)
+    }
+
     fn drop_table(
         &mut self,
         table_cache: &TableCache,
         table_name: TableName,
         if_exists: bool,
     ) -> Result<(), DatabaseError> {
-        if self.table(table_cache, table_name.clone()).is_none() {
+        let Some(table_catalog) = self.table(table_cache, table_name.clone()).cloned() else {
             if if_exists {
                 return Ok(());
             } else {
                 return Err(DatabaseError::TableNotFound);
             }
-        }
+        };
+        self.check_unreferenced(&table_name)?;
         self.drop_data(table_name.as_str())?;
 
         let (column_min, column_max) = TableCodec::columns_bound(table_name.as_str());
@@ -339,18 +949,196 @@ pub trait Transaction: Sized {
         let (index_meta_min, index_meta_max) = TableCodec::index_meta_bound(table_name.as_str());
         self._drop_data(&index_meta_min, &index_meta_max)?;
 
+        let (row_count_min, row_count_max) = TableCodec::row_count_bound(table_name.as_str());
+        self._drop_data(&row_count_min, &row_count_max)?;
+
+        self.reset_sequence(table_name.as_str())?;
         self.remove(&TableCodec::encode_root_table_key(table_name.as_str()))?;
+        for foreign_key in table_catalog.foreign_keys() {
+            table_cache.remove(&foreign_key.ref_table);
+        }
         table_cache.remove(&table_name);
 
         Ok(())
     }
 
-    fn drop_data(&mut self, table_name: &str) -> Result<(), DatabaseError> {
+    /// Upgrades the storage written in an older format to [`FORMAT_VERSION`], e.g. the rows
+    /// written before they start with the number of their columns, see [`Tuple::serialize_to`].
+    fn upgrade_format(&mut self) -> Result<(), DatabaseError> {
+        let version = self
+            .get(&TableCodec::encode_format_version_key())?
+            .map(|bytes| TableCodec::decode_format_version(&bytes))
+            .unwrap_or(0);
+
+        if version > FORMAT_VERSION {
+            return Err(DatabaseError::UnsupportedFormatVersion(
+                version,
+                FORMAT_VERSION,
+            ));
+        }
+        if version == 0 {
+            let mut table_names = Vec::new();
+            {
+                let (min, max) = TableCodec::root_table_bound();
+                let mut iter = self.range(Bound::Included(&min), Bound::Included(&max))?;
+
+                while let Some((key, _)) = iter.try_next()? {
+                    table_names.push(TableCodec::decode_root_table_key(&key)?);
+                }
+            }
+            for table_name in table_names {
+                let mut columns_len = 0_u16;
+                let mut rows = Vec::new();
+                {
+                    let (min, max) = TableCodec::columns_bound(&table_name);
+                    let mut iter = self.range(Bound::Included(&min), Bound::Included(&max))?;
+
+                    while iter.try_next()?.is_some() {
+                        columns_len += 1;
+                    }
+                    let (min, max) = TableCodec::tuple_bound(&table_name);
+                    let mut iter = self.range(Bound::Included(&min), Bound::Included(&max))?;
+
+                    while let Some(row) = iter.try_next()? {
+                        rows.push(row);
+                    }
+                }
+                // Tips: the rows have all the columns, as `ALTER TABLE` rewrote them
+                for (key, value) in rows {
+                    let mut bytes = columns_len.encode_fixed_vec();
+                    bytes.extend_from_slice(&value);
+
+                    self.set(key, Bytes::from(bytes))?;
+                }
+            }
+        }
+        let (key, value) = TableCodec::encode_format_version(FORMAT_VERSION);
+        self.set(key, value)
+    }
+
+    /// Drops the temporary tables in `temp_schema`, which are left by the
+    /// [`Session`](crate::db::Session) that created them, or the ones of every session if
+    /// `None`, e.g. of the sessions of a process which exited without dropping them.
+    fn drop_temp_tables(
+        &mut self,
+        table_cache: &TableCache,
+        temp_schema: Option<&str>,
+    ) -> Result<(), DatabaseError> {
+        let mut table_names = self
+            .table_metas()?
+            .into_iter()
+            .map(|meta| meta.table_name)
+            .filter(|table_name| match temp_schema {
+                Some(temp_schema) => temp_schema_of(table_name) == Some(temp_schema),
+                None => is_temp_table(table_name),
+            })
+            .collect_vec();
+
+        // Tips: a table referenced by the foreign keys of another one is dropped after it
+        while !table_names.is_empty() {
+            let len = table_names.len();
+
+            table_names.retain(|table_name| {
+                self.drop_table(table_cache, table_name.clone(), true)
+                    .is_err()
+            });
+            if table_names.len() == len {
+                return self.drop_table(table_cache, table_names[0].clone(), true);
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the table to the new name. The statistics of its indexes are dropped rather than
+    /// moved, `ANALYZE` has to be run again.
+    fn rename_table(
+        &mut self,
+        table_cache: &TableCache,
+        meta_cache: &StatisticsMetaCache,
+        table_name: &TableName,
+        new_table_name: TableName,
+    ) -> Result<(), DatabaseError> {
+        let table_catalog = self
+            .table(table_cache, table_name.clone())
+            .cloned()
+            .ok_or(DatabaseError::TableNotFound)?;
+        let table_meta = self
+            .table_meta(table_name)?
+            .ok_or(DatabaseError::TableNotFound)?;
+        if self.table_meta(&new_table_name)?.is_some() || self.view(&new_table_name)?.is_some() {
+            return Err(DatabaseError::TableExists);
+        }
+        // the tuples and the index entries are keyed by the table name followed by their own key
         let (tuple_min, tuple_max) = TableCodec::tuple_bound(table_name);
-        self._drop_data(&tuple_min, &tuple_max)?;
+        self._move_data(&tuple_min, &tuple_max, table_name, &new_table_name)?;
 
         let (index_min, index_max) = TableCodec::all_index_bound(table_name);
-        self._drop_data(&index_min, &index_max)?;
+        self._move_data(&index_min, &index_max, table_name, &new_table_name)?;
+
+        let (row_count_min, row_count_max) = TableCodec::row_count_bound(table_name);
+        self._move_data(&row_count_min, &row_count_max, table_name, &new_table_name)?;
+
+        if let Some(bytes) = self.get(&TableCodec::encode_sequence_key(table_name))? {
+            self.reset_sequence(table_name)?;
+            self.set_sequence_value(&new_table_name, TableCodec::decode_sequence(&bytes))?;
+        }
+        for column in table_catalog.columns() {
+            let (key, _) = TableCodec::encode_column(column, &mut ReferenceTables::new())?;
+            self.remove(&key)?;
+
+            let mut column = ColumnCatalog::clone(column);
+            column.set_ref_table(new_table_name.clone(), column.id().unwrap());
+
+            let (key, value) =
+                TableCodec::encode_column(&Arc::new(column), &mut ReferenceTables::new())?;
+            self.set(key, value)?;
+        }
+        for index_meta in table_catalog.indexes() {
+            let (key, _) = TableCodec::encode_index_meta(table_name, index_meta)?;
+            self.remove(&key)?;
+            self.remove_table_meta(meta_cache, table_name, index_meta.id)?;
+
+            let mut index_meta = IndexMeta::clone(index_meta);
+            index_meta.table_name = new_table_name.clone();
+
+            let (key, value) = TableCodec::encode_index_meta(&new_table_name, &index_meta)?;
+            self.set(key, value)?;
+        }
+        self.remove(&TableCodec::encode_root_table_key(table_name))?;
+
+        let mut table_meta = TableMeta {
+            table_name: new_table_name.clone(),
+            ..table_meta
+        };
+        for foreign_key in table_meta.foreign_keys.iter_mut() {
+            if &foreign_key.ref_table == table_name {
+                foreign_key.ref_table = new_table_name.clone();
+            }
+        }
+        let (table_key, value) = TableCodec::encode_root_table(&table_meta)?;
+        self.set(table_key, value)?;
+
+        for (child_name, _) in self.referencing_foreign_keys(table_name)? {
+            self.update_table_meta(&child_name, |child_meta| {
+                for foreign_key in child_meta.foreign_keys.iter_mut() {
+                    if &foreign_key.ref_table == table_name {
+                        foreign_key.ref_table = new_table_name.clone();
+                    }
+                }
+            })?;
+            table_cache.remove(&child_name);
+        }
+        for foreign_key in table_catalog.foreign_keys() {
+            table_cache.remove(&foreign_key.ref_table);
+        }
+        table_cache.remove(table_name);
+        table_cache.remove(&new_table_name);
+
+        Ok(())
+    }
+
+    fn drop_data(&mut self, table_name: &str) -> Result<(), DatabaseError> {
+        self.clear_data(table_name)?;
 
         let (statistics_min, statistics_max) = TableCodec::statistics_bound(table_name);
         self._drop_data(&statistics_min, &statistics_max)?;
@@ -358,6 +1146,20 @@ pub trait Transaction: Sized {
         Ok(())
     }
 
+    /// Remove all the tuples and index entries of the table by their key ranges, without decoding
+    /// them, returns the number of removed tuples.
+    fn clear_data(&mut self, table_name: &str) -> Result<usize, DatabaseError> {
+        let (tuple_min, tuple_max) = TableCodec::tuple_bound(table_name);
+        let tuple_count = self._drop_data(&tuple_min, &tuple_max)?;
+
+        let (index_min, index_max) = TableCodec::all_index_bound(table_name);
+        self._drop_data(&index_min, &index_max)?;
+
+        self.reset_row_count(table_name)?;
+
+        Ok(tuple_count)
+    }
+
     fn table<'a>(
         &'a self,
         table_cache: &'a TableCache,
@@ -367,12 +1169,65 @@ pub trait Transaction: Sized {
             .get_or_insert(table_name.to_string(), |_| {
                 // `TableCache` is not theoretically used in `table_collect` because ColumnCatalog should not depend on other Column
                 let (columns, indexes) = self.table_collect(table_name.clone())?;
+                // e.g. the table of a view, which has been dropped
+                if columns.is_empty() {
+                    return Err(DatabaseError::TableNotFound);
+                }
+                let (checks, foreign_keys, comment) = self
+                    .table_meta(&table_name)?
+                    .map(|meta| (meta.checks, meta.foreign_keys, meta.comment))
+                    .unwrap_or_default();
+                let mut table = TableCatalog::reload(
+                    table_name.clone(),
+                    columns,
+                    indexes,
+                    checks,
+                    foreign_keys,
+                )?;
+                table.referenced_by = self.referencing_foreign_keys(&table_name)?;
+                table.comment = comment;
 
-                TableCatalog::reload(table_name.clone(), columns, indexes)
+                Ok(table)
             })
             .ok()
     }
 
+    /// The foreign keys referencing the table, with the tables they belong to.
+    fn referencing_foreign_keys(
+        &self,
+        table_name: &str,
+    ) -> Result<Vec<(TableName, ForeignKey)>, DatabaseError> {
+        Ok(self
+            .table_metas()?
+            .into_iter()
+            .flat_map(|meta| {
+                let child_name = meta.table_name;
+
+                meta.foreign_keys
+                    .into_iter()
+                    .filter(|foreign_key| foreign_key.ref_table.as_str() == table_name)
+                    .map(move |foreign_key| (child_name.clone(), foreign_key))
+            })
+            .collect_vec())
+    }
+
+    /// Fails if the rows of the table may be referenced by the foreign keys of other tables,
+    /// which are dropped first.
+    fn check_unreferenced(&self, table_name: &TableName) -> Result<(), DatabaseError> {
+        if let Some((child_name, foreign_key)) = self
+            .referencing_foreign_keys(table_name)?
+            .into_iter()
+            .find(|(child_name, _)| child_name != table_name)
+        {
+            return Err(DatabaseError::ForeignKeyDependency(
+                format!("table {}", table_name),
+                foreign_key.name,
+                child_name.to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     fn table_metas(&self) -> Result<Vec<TableMeta>, DatabaseError> {
         let mut metas = vec![];
         let (min, max) = TableCodec::root_table_bound();
@@ -464,7 +1319,7 @@ pub trait Transaction: Sized {
         Ok((columns, index_metas))
     }
 
-    fn _drop_data(&mut self, min: &[u8], max: &[u8]) -> Result<(), DatabaseError> {
+    fn _drop_data(&mut self, min: &[u8], max: &[u8]) -> Result<usize, DatabaseError> {
         let mut iter = self.range(Bound::Included(min), Bound::Included(max))?;
         let mut data_keys = vec![];
 
@@ -473,10 +1328,38 @@ pub trait Transaction: Sized {
         }
         drop(iter);
 
+        let count = data_keys.len();
         for key in data_keys {
             self.remove(&key)?
         }
 
+        Ok(count)
+    }
+
+    /// Rewrites the keys in the range, which start with `table_name`, to start with `new_table_name`.
+    fn _move_data(
+        &mut self,
+        min: &[u8],
+        max: &[u8],
+        table_name: &str,
+        new_table_name: &str,
+    ) -> Result<(), DatabaseError> {
+        let mut iter = self.range(Bound::Included(min), Bound::Included(max))?;
+        let mut data = vec![];
+
+        while let Some((key, value)) = iter.try_next()? {
+            data.push((key, value));
+        }
+        drop(iter);
+
+        for (key, value) in data {
+            self.remove(&key)?;
+
+            let mut new_key = new_table_name.as_bytes().to_vec();
+            new_key.extend_from_slice(&key[table_name.len()..]);
+            self.set(Bytes::from(new_key), value)?;
+        }
+
         Ok(())
     }
 
@@ -485,29 +1368,41 @@ pub trait Transaction: Sized {
         table: &mut TableCatalog,
     ) -> Result<(), DatabaseError> {
         let table_name = table.name.clone();
+        let primary_keys = table
+            .primary_keys()?
+            .into_iter()
+            .map(|(_, column)| column.clone())
+            .collect_vec();
         let index_column = table
             .columns()
-            .filter(|column| column.desc.is_primary || column.desc.is_unique)
-            .map(|column| (column.id().unwrap(), column.clone()))
+            .filter(|column| column.desc.is_primary() || column.desc.is_unique)
+            .cloned()
             .collect_vec();
 
-        for (col_id, col) in index_column {
-            let is_primary = col.desc.is_primary;
-            let index_ty = if is_primary {
-                IndexType::PrimaryKey
-            } else if col.desc.is_unique {
-                IndexType::Unique
-            } else {
-                continue;
+        for col in index_column {
+            let (name, column_ids, index_ty) = match col.desc.primary {
+                // e.g. `pk_c1_c2` of `PRIMARY KEY (c1, c2)`, in place of the first column of the key
+                Some(0) => (
+                    format!(
+                        "pk_{}",
+                        primary_keys.iter().map(|column| column.name()).join("_")
+                    ),
+                    primary_keys
+                        .iter()
+                        .map(|column| column.id().unwrap())
+                        .collect_vec(),
+                    IndexType::PrimaryKey,
+                ),
+                Some(_) => continue,
+                None => (
+                    format!("uk_{}", col.name()),
+                    vec![col.id().unwrap()],
+                    IndexType::Unique {
+                        nulls_not_distinct: col.desc.nulls_not_distinct,
+                    },
+                ),
             };
-            // FIXME: composite indexes may exist on future
-            let prefix = if is_primary { "pk" } else { "uk" };
-
-            let meta_ref = table.add_index_meta(
-                format!("{}_{}", prefix, col.name()),
-                vec![col_id],
-                index_ty,
-            )?;
+            let meta_ref = table.add_index_meta(name, column_ids, index_ty, vec![])?;
             let (key, value) = TableCodec::encode_index_meta(&table_name, meta_ref)?;
             self.set(key, value)?;
         }
@@ -516,6 +1411,12 @@ pub trait Transaction: Sized {
 
     fn get(&self, key: &[u8]) -> Result<Option<Bytes>, DatabaseError>;
 
+    /// Gets the value of the key, whose change by another transaction fails this transaction on
+    /// commit.
+    fn get_for_update(&self, key: &[u8]) -> Result<Option<Bytes>, DatabaseError> {
+        self.get(key)
+    }
+
     fn set(&mut self, key: Bytes, value: Bytes) -> Result<(), DatabaseError>;
 
     fn remove(&mut self, key: &[u8]) -> Result<(), DatabaseError>;
@@ -526,6 +1427,13 @@ pub trait Transaction: Sized {
         max: Bound<&[u8]>,
     ) -> Result<Self::IterType<'a>, DatabaseError>;
 
+    /// Same as [`Transaction::range`], but yields the keys from `max` down to `min`.
+    fn range_rev<'a>(
+        &'a self,
+        min: Bound<&[u8]>,
+        max: Bound<&[u8]>,
+    ) -> Result<Self::IterType<'a>, DatabaseError>;
+
     fn commit(self) -> Result<(), DatabaseError>;
 }
 
@@ -558,10 +1466,14 @@ enum IndexImplEnum {
 }
 
 impl IndexImplEnum {
-    fn instance(index_type: IndexType) -> IndexImplEnum {
-        match index_type {
+    fn instance(index_meta: &IndexMeta) -> IndexImplEnum {
+        match index_meta.ty {
             IndexType::PrimaryKey => IndexImplEnum::PrimaryKey(PrimaryKeyIndexImpl),
-            IndexType::Unique => IndexImplEnum::Unique(UniqueIndexImpl),
+            IndexType::Unique { .. } if index_meta.column_ids.len() == 1 => {
+                IndexImplEnum::Unique(UniqueIndexImpl)
+            }
+            // e.g. `UNIQUE (c1, c2)` is scanned by the prefix of its columns like a composite index
+            IndexType::Unique { .. } => IndexImplEnum::Composite(CompositeIndexImpl),
             IndexType::Normal => IndexImplEnum::Normal(NormalIndexImpl),
             IndexType::Composite => IndexImplEnum::Composite(CompositeIndexImpl),
         }
@@ -580,6 +1492,7 @@ struct IndexImplParams<'a, T: Transaction> {
     index_meta: IndexMetaRef,
     table_name: &'a str,
     table_types: Vec<LogicalType>,
+    pk_indices: Vec<usize>,
     tx: &'a T,
 }
 
@@ -590,6 +1503,7 @@ impl<T: Transaction> IndexImplParams<'_, T> {
         Ok(self.tx.get(&key)?.map(|bytes| {
             TableCodec::decode_tuple(
                 &self.table_types,
+                &self.pk_indices,
                 &self.projections,
                 &self.tuple_schema_ref,
                 &bytes,
@@ -653,6 +1567,7 @@ impl<T: Transaction> IndexImpl<T> for PrimaryKeyIndexImpl {
     ) -> Result<Tuple, DatabaseError> {
         Ok(TableCodec::decode_tuple(
             &params.table_types,
+            &params.pk_indices,
             &params.projections,
             &params.tuple_schema_ref,
             bytes,
@@ -664,6 +1579,20 @@ impl<T: Transaction> IndexImpl<T> for PrimaryKeyIndexImpl {
         value: &ValueRef,
         params: &IndexImplParams<'a, T>,
     ) -> Result<IndexResult<'a, T>, DatabaseError> {
+        // e.g. `a = 1` of the composite primary key `(a, b)`
+        if matches!(
+            value.as_ref(),
+            DataValue::Tuple(Some(values)) if values.len() < params.index_meta.column_ids.len()
+        ) {
+            let min = self.bound_key(params, value, false)?;
+            let max = self.bound_key(params, value, true)?;
+
+            let iter = params.tx.range(
+                Bound::Included(min.as_slice()),
+                Bound::Included(max.as_slice()),
+            )?;
+            return Ok(IndexResult::Scope(iter));
+        }
         let bytes = params
             .tx
             .get(&TableCodec::encode_tuple_key(params.table_name, value)?)?
@@ -672,6 +1601,7 @@ impl<T: Transaction> IndexImpl<T> for PrimaryKeyIndexImpl {
             })?;
         let tuple = TableCodec::decode_tuple(
             &params.table_types,
+            &params.pk_indices,
             &params.projections,
             &params.tuple_schema_ref,
             &bytes,
@@ -683,9 +1613,9 @@ impl<T: Transaction> IndexImpl<T> for PrimaryKeyIndexImpl {
         &self,
         params: &IndexImplParams<T>,
         val: &ValueRef,
-        _: bool,
+        is_upper: bool,
     ) -> Result<Vec<u8>, DatabaseError> {
-        TableCodec::encode_tuple_key(params.table_name, val)
+        TableCodec::encode_tuple_bound_key(params.table_name, val, is_upper)
     }
 }
 
@@ -693,7 +1623,7 @@ fn secondary_index_lookup<T: Transaction>(
     bytes: &Bytes,
     params: &IndexImplParams<T>,
 ) -> Result<Tuple, DatabaseError> {
-    let tuple_id = TableCodec::decode_index(bytes, &params.index_meta.pk_ty);
+    let tuple_id = TableCodec::decode_index(bytes, &params.index_meta.pk_types);
     params
         .get_tuple_by_id(&tuple_id)?
         .ok_or_else(|| DatabaseError::NotFound("index's tuple_id", tuple_id.to_string()))
@@ -713,13 +1643,29 @@ impl<T: Transaction> IndexImpl<T> for UniqueIndexImpl {
         value: &ValueRef,
         params: &IndexImplParams<'a, T>,
     ) -> Result<IndexResult<'a, T>, DatabaseError> {
+        if value.is_null() {
+            // Tips: NULLs may be distinct, so they are stored with the tuple id like a normal index
+            let index = Index::new(
+                params.index_meta.id,
+                slice::from_ref(value),
+                params.index_meta.ty,
+            );
+            let min = TableCodec::encode_index_bound_key(params.table_name, &index, false)?;
+            let max = TableCodec::encode_index_bound_key(params.table_name, &index, true)?;
+
+            let iter = params.tx.range(
+                Bound::Included(min.as_slice()),
+                Bound::Included(max.as_slice()),
+            )?;
+            return Ok(IndexResult::Scope(iter));
+        }
         let bytes = params
             .tx
             .get(&self.bound_key(params, value, false)?)?
             .ok_or_else(|| {
                 DatabaseError::NotFound("secondary index", format!("index_value -> {}", value))
             })?;
-        let tuple_id = TableCodec::decode_index(&bytes, &params.index_meta.pk_ty);
+        let tuple_id = TableCodec::decode_index(&bytes, &params.index_meta.pk_types);
         let tuple = params.get_tuple_by_id(&tuple_id)?.ok_or_else(|| {
             DatabaseError::NotFound("secondary index", format!("tuple_id -> {}", value))
         })?;
@@ -735,7 +1681,7 @@ impl<T: Transaction> IndexImpl<T> for UniqueIndexImpl {
         let index = Index::new(
             params.index_meta.id,
             slice::from_ref(value),
-            IndexType::Unique,
+            params.index_meta.ty,
         );
 
         TableCodec::encode_index_key(params.table_name, &index, None)
@@ -827,6 +1773,7 @@ pub struct TupleIter<'a, T: Transaction + 'a> {
     offset: usize,
     limit: Option<usize>,
     table_types: Vec<LogicalType>,
+    pk_indices: Vec<usize>,
     tuple_columns: Arc<Vec<ColumnRef>>,
     projections: Vec<usize>,
     iter: T::IterType<'a>,
@@ -849,6 +1796,7 @@ impl<'a, T: Transaction + 'a> Iter for TupleIter<'a, T> {
         while let Some((_, value)) = self.iter.try_next()? {
             let tuple = TableCodec::decode_tuple(
                 &self.table_types,
+                &self.pk_indices,
                 &self.projections,
                 &self.tuple_columns,
                 &value,
@@ -873,10 +1821,18 @@ pub struct IndexIter<'a, T: Transaction> {
     inner: IndexImplEnum,
     // for buffering data
     ranges: VecDeque<Range>,
+    // read the ranges and the entries of each range in descending order
+    reverse: bool,
     scope_iter: Option<T::IterType<'a>>,
 }
 
 impl<'a, T: Transaction + 'a> IndexIter<'a, T> {
+    /// Read the ranges, and the entries of each range, in descending order.
+    pub(crate) fn rev(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
     fn offset_move(offset: &mut usize) -> bool {
         if *offset > 0 {
             offset.sub_assign(1);
@@ -921,7 +1877,12 @@ impl<T: Transaction> Iter for IndexIter<'_, T> {
             self.scope_iter = None;
         }
 
-        if let Some(binary) = self.ranges.pop_front() {
+        let range = if self.reverse {
+            self.ranges.pop_back()
+        } else {
+            self.ranges.pop_front()
+        };
+        if let Some(binary) = range {
             match binary {
                 Range::Scope { min, max } => {
                     let table_name = self.params.table_name;
@@ -959,10 +1920,15 @@ impl<T: Transaction> Iter for IndexIter<'_, T> {
                     let mut encode_max = bound_encode(max, true)?;
                     check_bound(&mut encode_max, bound_max);
 
-                    let iter = self.params.tx.range(
+                    let (min, max) = (
                         encode_min.as_ref().map(Vec::as_slice),
                         encode_max.as_ref().map(Vec::as_slice),
-                    )?;
+                    );
+                    let iter = if self.reverse {
+                        self.params.tx.range_rev(min, max)?
+                    } else {
+                        self.params.tx.range(min, max)?
+                    };
                     self.scope_iter = Some(iter);
                 }
                 Range::Eq(val) => match self.inner.eq_to_res(&val, &self.params)? {
@@ -1001,7 +1967,8 @@ mod test {
     use crate::storage::rocksdb::{RocksStorage, RocksTransaction};
     use crate::storage::table_codec::TableCodec;
     use crate::storage::{
-        IndexIter, InnerIter, Iter, StatisticsMetaCache, Storage, TableCache, Transaction,
+        next_row_count_delta_id, IndexIter, InnerIter, Iter, StatisticsMetaCache, Storage,
+        TableCache, Transaction, ROW_COUNT_FOLD_THRESHOLD,
     };
     use crate::types::index::{Index, IndexMeta, IndexType};
     use crate::types::tuple::Tuple;
@@ -1096,7 +2063,7 @@ mod test {
                 primary_key_index_meta.table_name,
                 Arc::new("t1".to_string())
             );
-            assert_eq!(primary_key_index_meta.pk_ty, LogicalType::Integer);
+            assert_eq!(primary_key_index_meta.pk_types, vec![LogicalType::Integer]);
             assert_eq!(primary_key_index_meta.name, "pk_c1".to_string());
             assert_eq!(primary_key_index_meta.ty, IndexType::PrimaryKey);
 
@@ -1176,24 +2143,21 @@ mod test {
                 &Arc::new("t1".to_string()),
                 "i1".to_string(),
                 vec![2],
-                IndexType::Normal
+                IndexType::Normal,
+                vec![],
             )
             .is_err());
 
         build_table(&table_cache, &mut transaction)?;
 
         let tuples = build_tuples();
+        let columns = transaction
+            .table(&table_cache, Arc::new("t1".to_string()))
+            .unwrap()
+            .schema_ref()
+            .clone();
         for tuple in tuples.iter().cloned() {
-            transaction.append_tuple(
-                "t1",
-                tuple,
-                &[
-                    LogicalType::Integer,
-                    LogicalType::Boolean,
-                    LogicalType::Integer,
-                ],
-                false,
-            )?;
+            transaction.append_tuple("t1", tuple, &columns, false)?;
         }
         {
             let mut tuple_iter = transaction.read(
@@ -1257,7 +2221,8 @@ mod test {
                 &Arc::new("t1".to_string()),
                 "i1".to_string(),
                 vec![2],
-                IndexType::Normal
+                IndexType::Normal,
+                vec![],
             )
             .is_err());
 
@@ -1269,6 +2234,7 @@ mod test {
             "i1".to_string(),
             vec![2],
             IndexType::Normal,
+            vec![],
         )?;
         let _ = transaction.add_index_meta(
             &table_cache,
@@ -1276,6 +2242,7 @@ mod test {
             "i2".to_string(),
             vec![2, 1],
             IndexType::Composite,
+            vec![],
         )?;
 
         let fn_assert = |transaction: &mut RocksTransaction,
@@ -1289,7 +2256,7 @@ mod test {
             assert_eq!(i1_meta.id, 1);
             assert_eq!(i1_meta.column_ids, vec![2]);
             assert_eq!(i1_meta.table_name, Arc::new("t1".to_string()));
-            assert_eq!(i1_meta.pk_ty, LogicalType::Integer);
+            assert_eq!(i1_meta.pk_types, vec![LogicalType::Integer]);
             assert_eq!(i1_meta.name, "i1".to_string());
             assert_eq!(i1_meta.ty, IndexType::Normal);
 
@@ -1297,7 +2264,7 @@ mod test {
             assert_eq!(i2_meta.id, 2);
             assert_eq!(i2_meta.column_ids, vec![2, 1]);
             assert_eq!(i2_meta.table_name, Arc::new("t1".to_string()));
-            assert_eq!(i2_meta.pk_ty, LogicalType::Integer);
+            assert_eq!(i2_meta.pk_types, vec![LogicalType::Integer]);
             assert_eq!(i2_meta.name, "i2".to_string());
             assert_eq!(i2_meta.ty, IndexType::Composite);
 
@@ -1339,9 +2306,10 @@ mod test {
                     id: 1,
                     column_ids: vec![2],
                     table_name: Arc::new("t1".to_string()),
-                    pk_ty: LogicalType::Integer,
+                    pk_types: vec![LogicalType::Integer],
                     name: "i1".to_string(),
                     ty: IndexType::Normal,
+                    json_path: vec![],
                 }),
                 vec![Range::Scope {
                     min: Bound::Unbounded,
@@ -1363,6 +2331,7 @@ mod test {
             "i1".to_string(),
             vec![2],
             IndexType::Normal,
+            vec![],
         )?;
 
         let tuples = build_tuples();
@@ -1381,19 +2350,15 @@ mod test {
             ),
         ];
         for (tuple_id, index) in indexes.iter().cloned() {
-            transaction.add_index("t1", index, &tuple_id)?;
+            transaction.add_index("t1", "idx_c3", index, &tuple_id)?;
         }
+        let columns = transaction
+            .table(&table_cache, Arc::new("t1".to_string()))
+            .unwrap()
+            .schema_ref()
+            .clone();
         for tuple in tuples.iter().cloned() {
-            transaction.append_tuple(
-                "t1",
-                tuple,
-                &[
-                    LogicalType::Integer,
-                    LogicalType::Boolean,
-                    LogicalType::Integer,
-                ],
-                false,
-            )?;
+            transaction.append_tuple("t1", tuple, &columns, false)?;
         }
         {
             let mut index_iter = build_index_iter(&transaction, &table_cache)?;
@@ -1449,14 +2414,14 @@ mod test {
             ColumnDesc::new(LogicalType::Integer, false, false, None)?,
         );
         let new_column_id =
-            transaction.add_column(&table_cache, &table_name, &new_column, false)?;
+            transaction.add_column(&table_cache, &table_name, &new_column, &[], false)?;
         {
             assert!(transaction
-                .add_column(&table_cache, &table_name, &new_column, false,)
+                .add_column(&table_cache, &table_name, &new_column, &[], false)
                 .is_err());
             assert_eq!(
                 new_column_id,
-                transaction.add_column(&table_cache, &table_name, &new_column, true,)?
+                transaction.add_column(&table_cache, &table_name, &new_column, &[], true)?
             );
         }
         {
@@ -1483,4 +2448,121 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_table_rename() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = RocksStorage::new(temp_dir.path())?;
+        let mut transaction = storage.transaction()?;
+        let table_cache = Arc::new(ShardingLruCache::new(4, 1, RandomState::new())?);
+        let meta_cache = StatisticsMetaCache::new(4, 1, RandomState::new())?;
+
+        build_table(&table_cache, &mut transaction)?;
+        let table_name = Arc::new("t1".to_string());
+        let new_table_name = Arc::new("t2".to_string());
+
+        let tuples = build_tuples();
+        let columns = transaction
+            .table(&table_cache, table_name.clone())
+            .unwrap()
+            .schema_ref()
+            .clone();
+        for tuple in tuples.iter().cloned() {
+            transaction.append_tuple(&table_name, tuple, &columns, false)?;
+        }
+        transaction.add_row_count(&table_name, Some(tuples.len() as i64))?;
+        let (key, value) = TableCodec::encode_statistics_path(&table_name, 0, "path".to_string());
+        transaction.set(key, value)?;
+
+        transaction.rename_table(
+            &table_cache,
+            &meta_cache,
+            &table_name,
+            new_table_name.clone(),
+        )?;
+        assert!(transaction
+            .table(&table_cache, table_name.clone())
+            .is_none());
+        assert!(transaction.table_meta_path(&table_name, 0)?.is_none());
+        assert!(transaction.table_meta_path(&new_table_name, 0)?.is_none());
+        {
+            let table = transaction
+                .table(&table_cache, new_table_name.clone())
+                .unwrap();
+            assert!(table
+                .columns()
+                .all(|column| column.table_name() == Some(&new_table_name)));
+            assert!(table
+                .indexes()
+                .all(|index_meta| index_meta.table_name == new_table_name));
+        }
+        let mut iter = transaction.read(
+            &table_cache,
+            new_table_name.clone(),
+            (None, None),
+            full_columns(),
+        )?;
+        for tuple in tuples {
+            assert_eq!(iter.next_tuple()?.unwrap().values, tuple.values);
+        }
+        assert!(iter.next_tuple()?.is_none());
+        drop(iter);
+        assert_eq!(transaction.row_count(&new_table_name)?, Some(3));
+        assert_eq!(transaction.row_count(&table_name)?, None);
+
+        build_table(&table_cache, &mut transaction)?;
+        assert!(transaction
+            .rename_table(&table_cache, &meta_cache, &table_name, new_table_name)
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_row_count_fold() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let storage = RocksStorage::new(temp_dir.path())?;
+        let delta_count = |transaction: &RocksTransaction| -> Result<usize, DatabaseError> {
+            let (min, max) = TableCodec::row_count_bound("t1");
+            let mut iter = transaction.range(Bound::Included(&min), Bound::Included(&max))?;
+            let mut count = 0;
+            while iter.try_next()?.is_some() {
+                count += 1;
+            }
+            Ok(count)
+        };
+        let table_cache = Arc::new(ShardingLruCache::new(4, 1, RandomState::new())?);
+        let mut transaction = storage.transaction()?;
+        build_table(&table_cache, &mut transaction)?;
+        // e.g. a change recorded before a restart, the ids continue from it
+        let stored_id = next_row_count_delta_id() + 1_000_000;
+        let (key, value) = TableCodec::encode_row_count_delta("t1", stored_id, Some(0));
+        transaction.set(key, value)?;
+        transaction.seed_row_count_delta_id()?;
+        assert!(next_row_count_delta_id() > stored_id);
+        transaction.commit()?;
+
+        // with the one of the creation of the table and the one above
+        let mut transaction = storage.transaction()?;
+        for _ in 3..ROW_COUNT_FOLD_THRESHOLD {
+            transaction.add_row_count("t1", Some(2))?;
+        }
+        transaction.commit()?;
+
+        let mut transaction = storage.transaction()?;
+        assert_eq!(delta_count(&transaction)?, ROW_COUNT_FOLD_THRESHOLD - 1);
+        transaction.add_row_count("t1", Some(-1))?;
+        // the changes are folded once committed
+        assert_eq!(delta_count(&transaction)?, ROW_COUNT_FOLD_THRESHOLD);
+        transaction.commit()?;
+
+        let transaction = storage.transaction()?;
+        assert_eq!(delta_count(&transaction)?, 1);
+        assert_eq!(
+            transaction.row_count("t1")?,
+            Some((ROW_COUNT_FOLD_THRESHOLD - 3) * 2 - 1)
+        );
+
+        Ok(())
+    }
 }
@@ -1,5 +1,6 @@
 use crate::errors::DatabaseError;
-use crate::storage::{InnerIter, Storage, Transaction};
+use crate::storage::table_codec::TableCodec;
+use crate::storage::{InnerIter, Storage, Transaction, ROW_COUNT_FOLD_THRESHOLD};
 use bytes::Bytes;
 use rocksdb::{DBIteratorWithThreadMode, Direction, IteratorMode, OptimisticTransactionDB};
 use std::collections::Bound;
@@ -36,12 +37,17 @@ impl Storage for RocksStorage {
     fn transaction(&self) -> Result<Self::TransactionType<'_>, DatabaseError> {
         Ok(RocksTransaction {
             tx: self.inner.transaction(),
+            db: &self.inner,
+            row_counts_to_fold: vec![],
         })
     }
 }
 
 pub struct RocksTransaction<'db> {
     tx: rocksdb::Transaction<'db, OptimisticTransactionDB>,
+    db: &'db OptimisticTransactionDB,
+    // the tables whose changes of the row count are folded once committed
+    row_counts_to_fold: Vec<String>,
 }
 
 impl<'txn> Transaction for RocksTransaction<'txn> {
@@ -53,6 +59,10 @@ impl<'txn> Transaction for RocksTransaction<'txn> {
         Ok(self.tx.get(key)?.map(Bytes::from))
     }
 
+    fn get_for_update(&self, key: &[u8]) -> Result<Option<Bytes>, DatabaseError> {
+        Ok(self.tx.get_for_update(key, true)?.map(Bytes::from))
+    }
+
     fn set(&mut self, key: Bytes, value: Bytes) -> Result<(), DatabaseError> {
         self.tx.put(key, value)?;
 
@@ -71,13 +81,6 @@ impl<'txn> Transaction for RocksTransaction<'txn> {
         min: Bound<&[u8]>,
         max: Bound<&[u8]>,
     ) -> Result<Self::IterType<'a>, DatabaseError> {
-        fn bound_to_include(bound: Bound<&[u8]>) -> Option<&[u8]> {
-            match bound {
-                Bound::Included(bytes) | Bound::Excluded(bytes) => Some(bytes),
-                Bound::Unbounded => None,
-            }
-        }
-
         let lower = bound_to_include(min)
             .map(|bytes| IteratorMode::From(bytes, Direction::Forward))
             .unwrap_or(IteratorMode::Start);
@@ -86,19 +89,83 @@ impl<'txn> Transaction for RocksTransaction<'txn> {
         Ok(RocksIter {
             lower: min.map(|bytes| bytes.to_vec()),
             upper: max.map(|bytes| bytes.to_vec()),
+            reverse: false,
+            iter,
+        })
+    }
+
+    fn range_rev<'a>(
+        &'a self,
+        min: Bound<&[u8]>,
+        max: Bound<&[u8]>,
+    ) -> Result<Self::IterType<'a>, DatabaseError> {
+        let upper = bound_to_include(max)
+            .map(|bytes| IteratorMode::From(bytes, Direction::Reverse))
+            .unwrap_or(IteratorMode::End);
+        let iter = self.tx.iterator(upper);
+
+        Ok(RocksIter {
+            lower: min.map(|bytes| bytes.to_vec()),
+            upper: max.map(|bytes| bytes.to_vec()),
+            reverse: true,
             iter,
         })
     }
 
+    fn fold_row_count_on_commit(&mut self, table_name: &str) {
+        if !self
+            .row_counts_to_fold
+            .iter()
+            .any(|name| name == table_name)
+        {
+            self.row_counts_to_fold.push(table_name.to_string());
+        }
+    }
+
     fn commit(self) -> Result<(), DatabaseError> {
         self.tx.commit()?;
+
+        for table_name in self.row_counts_to_fold {
+            let mut transaction = RocksTransaction {
+                tx: self.db.transaction(),
+                db: self.db,
+                row_counts_to_fold: vec![],
+            };
+            // Tips: the changes are left to the next fold if this one fails
+            if matches!(transaction.is_row_count_to_fold(&table_name), Ok(true))
+                && transaction.compact_row_count(&table_name).is_ok()
+            {
+                let _ = transaction.tx.commit();
+            }
+        }
         Ok(())
     }
 }
 
+impl RocksTransaction<'_> {
+    fn is_row_count_to_fold(&self, table_name: &str) -> Result<bool, DatabaseError> {
+        let (min, max) = TableCodec::row_count_bound(table_name);
+        let mut iter = self.range(Bound::Included(&min), Bound::Included(&max))?;
+        let mut delta_count = 0;
+
+        while delta_count < ROW_COUNT_FOLD_THRESHOLD && iter.try_next()?.is_some() {
+            delta_count += 1;
+        }
+        Ok(delta_count >= ROW_COUNT_FOLD_THRESHOLD)
+    }
+}
+
+fn bound_to_include(bound: Bound<&[u8]>) -> Option<&[u8]> {
+    match bound {
+        Bound::Included(bytes) | Bound::Excluded(bytes) => Some(bytes),
+        Bound::Unbounded => None,
+    }
+}
+
 pub struct RocksIter<'txn, 'iter> {
     lower: Bound<Vec<u8>>,
     upper: Bound<Vec<u8>>,
+    reverse: bool,
     iter: DBIteratorWithThreadMode<'iter, rocksdb::Transaction<'txn, OptimisticTransactionDB>>,
 }
 
@@ -111,15 +178,21 @@ impl InnerIter for RocksIter<'_, '_> {
                 Bound::Excluded(ref upper) => key.as_ref() < upper.as_slice(),
                 Bound::Unbounded => true,
             };
-            if !upper_bound_check {
-                break;
-            }
             let lower_bound_check = match &self.lower {
                 Bound::Included(ref lower) => key.as_ref() >= lower.as_slice(),
                 Bound::Excluded(ref lower) => key.as_ref() > lower.as_slice(),
                 Bound::Unbounded => true,
             };
-            if lower_bound_check {
+            // Tips: keys past the end stop the iteration, keys before its start are skipped
+            let (in_range, past_end) = if self.reverse {
+                (upper_bound_check, !lower_bound_check)
+            } else {
+                (lower_bound_check, !upper_bound_check)
+            };
+            if past_end {
+                break;
+            }
+            if in_range {
                 return Ok(Some((Bytes::from(key), Bytes::from(value))));
             }
         }
@@ -175,6 +248,9 @@ mod test {
             &table_cache,
             Arc::new("test".to_string()),
             source_columns,
+            vec![],
+            vec![],
+            vec![],
             false,
         )?;
 
@@ -184,6 +260,11 @@ mod test {
             .unwrap()
             .get_column_id_by_name(&"c1".to_string())
             .is_some());
+        let columns = transaction
+            .table(&table_cache, Arc::new("test".to_string()))
+            .unwrap()
+            .schema_ref()
+            .clone();
 
         transaction.append_tuple(
             &"test".to_string(),
@@ -194,7 +275,7 @@ mod test {
                     Arc::new(DataValue::Boolean(Some(true))),
                 ],
             },
-            &[LogicalType::Integer, LogicalType::Boolean],
+            &columns,
             false,
         )?;
         transaction.append_tuple(
@@ -206,7 +287,7 @@ mod test {
                     Arc::new(DataValue::Boolean(Some(false))),
                 ],
             },
-            &[LogicalType::Integer, LogicalType::Boolean],
+            &columns,
             false,
         )?;
 
@@ -259,12 +340,14 @@ mod test {
                     id: 0,
                     column_ids: vec![0],
                     table_name,
-                    pk_ty: LogicalType::Integer,
+                    pk_types: vec![LogicalType::Integer],
                     name: "pk_a".to_string(),
                     ty: IndexType::PrimaryKey,
+                    json_path: vec![],
                 }),
                 table_name: &table.name,
                 table_types: table.types(),
+                pk_indices: table.primary_key_indices()?,
                 tx: &transaction,
             },
             ranges: VecDeque::from(vec![
@@ -274,6 +357,7 @@ mod test {
                     max: Bound::Included(Arc::new(DataValue::Int32(Some(4)))),
                 },
             ]),
+            reverse: false,
             scope_iter: None,
             inner: IndexImplEnum::PrimaryKey(PrimaryKeyIndexImpl),
         };
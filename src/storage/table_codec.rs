@@ -1,4 +1,4 @@
-use crate::catalog::{ColumnRef, ColumnRelation, TableMeta};
+use crate::catalog::{ColumnRef, ColumnRelation, TableMeta, View};
 use crate::errors::DatabaseError;
 use crate::serdes::{ReferenceSerialization, ReferenceTables};
 use crate::storage::Transaction;
@@ -17,8 +17,17 @@ const BOUND_MAX_TAG: u8 = 1;
 
 lazy_static! {
     static ref ROOT_BYTES: Vec<u8> = b"Root".to_vec();
+    static ref VIEW_BYTES: Vec<u8> = b"View".to_vec();
+    static ref FORMAT_VERSION_BYTES: Vec<u8> = b"FormatVersion".to_vec();
 }
 
+/// The version of the format of the storage, which is checked whenever it is opened:
+///
+/// - `0`: the storages written before the format is versioned
+/// - `1`: the rows start with the number of the columns they are written with, see
+///   [`Tuple::serialize_to`]
+pub const FORMAT_VERSION: u16 = 1;
+
 #[derive(Clone)]
 pub struct TableCodec {}
 
@@ -28,8 +37,11 @@ enum CodecType {
     IndexMeta,
     Index,
     Statistics,
+    RowCount,
+    Sequence,
     Tuple,
     Root,
+    View,
 }
 
 impl TableCodec {
@@ -46,6 +58,7 @@ impl TableCodec {
                 | LogicalType::UBigint
                 | LogicalType::Char(..)
                 | LogicalType::Varchar(..)
+                | LogicalType::Uuid
         ) {
             return Err(DatabaseError::InvalidType);
         }
@@ -71,6 +84,12 @@ impl TableCodec {
             CodecType::Statistics => {
                 table_bytes.push(b'4');
             }
+            CodecType::RowCount => {
+                table_bytes.push(b'5');
+            }
+            CodecType::Sequence => {
+                table_bytes.push(b'6');
+            }
             CodecType::Tuple => {
                 table_bytes.push(b'8');
             }
@@ -79,6 +98,13 @@ impl TableCodec {
                 bytes.push(BOUND_MIN_TAG);
                 bytes.append(&mut table_bytes);
 
+                return bytes;
+            }
+            CodecType::View => {
+                let mut bytes = VIEW_BYTES.clone();
+                bytes.push(BOUND_MIN_TAG);
+                bytes.append(&mut table_bytes);
+
                 return bytes;
             }
         }
@@ -143,6 +169,17 @@ impl TableCodec {
         (op(BOUND_MIN_TAG), op(BOUND_MAX_TAG))
     }
 
+    pub fn view_bound() -> (Vec<u8>, Vec<u8>) {
+        let op = |bound_id| {
+            let mut key_prefix = VIEW_BYTES.clone();
+
+            key_prefix.push(bound_id);
+            key_prefix
+        };
+
+        (op(BOUND_MIN_TAG), op(BOUND_MAX_TAG))
+    }
+
     pub fn table_bound(table_name: &str) -> (Vec<u8>, Vec<u8>) {
         let mut column_prefix = Self::key_prefix(CodecType::Column, table_name);
         column_prefix.push(BOUND_MIN_TAG);
@@ -180,19 +217,26 @@ impl TableCodec {
     pub fn encode_tuple(
         table_name: &str,
         tuple: &Tuple,
-        types: &[LogicalType],
+        columns: &[ColumnRef],
     ) -> Result<(Bytes, Bytes), DatabaseError> {
         let tuple_id = tuple.id.clone().ok_or(DatabaseError::PrimaryKeyNotFound)?;
         let key = Self::encode_tuple_key(table_name, &tuple_id)?;
 
-        Ok((Bytes::from(key), Bytes::from(tuple.serialize_to(types)?)))
+        Ok((Bytes::from(key), Bytes::from(tuple.serialize_to(columns)?)))
     }
 
+    /// Tips: the key of a composite primary key is the concatenation of the keys of its values
     pub fn encode_tuple_key(
         table_name: &str,
         tuple_id: &TupleId,
     ) -> Result<Vec<u8>, DatabaseError> {
-        Self::check_primary_key_type(&tuple_id.logical_type())?;
+        if let DataValue::Tuple(Some(values)) = tuple_id.as_ref() {
+            for value in values {
+                Self::check_primary_key_type(&value.logical_type())?;
+            }
+        } else {
+            Self::check_primary_key_type(&tuple_id.logical_type())?;
+        }
 
         let mut key_prefix = Self::key_prefix(CodecType::Tuple, table_name);
         key_prefix.push(BOUND_MIN_TAG);
@@ -202,13 +246,31 @@ impl TableCodec {
         Ok(key_prefix)
     }
 
+    /// The bound of the keys of the tuples whose composite primary key starts with the values of
+    /// `tuple_id`, the upper bound is after all of them.
+    pub fn encode_tuple_bound_key(
+        table_name: &str,
+        tuple_id: &TupleId,
+        is_upper: bool,
+    ) -> Result<Vec<u8>, DatabaseError> {
+        let mut key = Self::encode_tuple_key(table_name, tuple_id)?;
+
+        if is_upper && matches!(tuple_id.as_ref(), DataValue::Tuple(_)) {
+            if let Some(last) = key.last_mut() {
+                *last = BOUND_MAX_TAG
+            }
+        }
+        Ok(key)
+    }
+
     pub fn decode_tuple(
         table_types: &[LogicalType],
+        pk_indices: &[usize],
         projections: &[usize],
         schema: &Schema,
         bytes: &[u8],
     ) -> Tuple {
-        Tuple::deserialize_from(table_types, projections, schema, bytes)
+        Tuple::deserialize_from(table_types, pk_indices, projections, schema, bytes)
     }
 
     /// Key: {TableName}{INDEX_META_TAG}{BOUND_MIN_TAG}{IndexID}
@@ -240,7 +302,8 @@ impl TableCodec {
     /// Value: TupleID
     ///
     /// Tips: The unique index has only one ColumnID and one corresponding DataValue,
-    /// so it can be positioned directly.
+    /// so it can be positioned directly. Unless `NULLS NOT DISTINCT`, a NULL is encoded
+    /// with the TupleId like the NonUnique Index, so that NULLs never collide.
     pub fn encode_index(
         name: &str,
         index: &Index,
@@ -289,15 +352,25 @@ impl TableCodec {
         let mut key_prefix = Self::_encode_index_key(name, index)?;
 
         if let Some(tuple_id) = tuple_id {
-            if matches!(index.ty, IndexType::Normal | IndexType::Composite) {
+            let is_distinct_null = matches!(
+                index.ty,
+                IndexType::Unique {
+                    nulls_not_distinct: false
+                }
+            ) && index.column_values.iter().any(|value| value.is_null());
+
+            if matches!(index.ty, IndexType::Normal | IndexType::Composite) || is_distinct_null {
                 tuple_id.to_raw(&mut key_prefix)?;
             }
         }
         Ok(key_prefix)
     }
 
-    pub fn decode_index(bytes: &[u8], primary_key_ty: &LogicalType) -> TupleId {
-        Arc::new(DataValue::from_raw(bytes, primary_key_ty))
+    pub fn decode_index(bytes: &[u8], pk_types: &[LogicalType]) -> TupleId {
+        Arc::new(match pk_types {
+            [ty] => DataValue::from_raw(bytes, ty),
+            types => DataValue::tuple_from_raw(bytes, types),
+        })
     }
 
     /// Key: {TableName}{COLUMN_TAG}{BOUND_MIN_TAG}{ColumnId}
@@ -365,6 +438,62 @@ impl TableCodec {
         Ok(String::from_utf8(bytes.to_vec())?)
     }
 
+    pub fn row_count_bound(table_name: &str) -> (Vec<u8>, Vec<u8>) {
+        let op = |bound_id| {
+            let mut key_prefix = Self::key_prefix(CodecType::RowCount, table_name);
+
+            key_prefix.push(bound_id);
+            key_prefix
+        };
+
+        (op(BOUND_MIN_TAG), op(BOUND_MAX_TAG))
+    }
+
+    /// Key: {TableName}{ROW_COUNT_TAG}{BOUND_MIN_TAG}{DeltaId}
+    /// Value: The change of the row count, `i64::MIN` if unknown
+    pub fn encode_row_count_delta(
+        table_name: &str,
+        delta_id: u64,
+        delta: Option<i64>,
+    ) -> (Bytes, Bytes) {
+        let mut key = Self::key_prefix(CodecType::RowCount, table_name);
+
+        key.push(BOUND_MIN_TAG);
+        key.extend_from_slice(&delta_id.to_be_bytes());
+
+        (
+            Bytes::from(key),
+            Bytes::from(delta.unwrap_or(i64::MIN).encode_fixed_vec()),
+        )
+    }
+
+    pub fn decode_row_count_delta(bytes: &[u8]) -> Option<i64> {
+        Some(i64::decode_fixed(bytes)).filter(|delta| *delta != i64::MIN)
+    }
+
+    pub fn decode_row_count_delta_id(key: &[u8]) -> u64 {
+        let mut delta_id = [0; 8];
+        delta_id.copy_from_slice(&key[key.len() - 8..]);
+
+        u64::from_be_bytes(delta_id)
+    }
+
+    /// Key: {TableName}{SEQUENCE_TAG}
+    /// Value: The last value assigned to the auto-increment column
+    pub fn encode_sequence(table_name: &str, value: i64) -> (Bytes, Bytes) {
+        let key = Self::encode_sequence_key(table_name);
+
+        (Bytes::from(key), Bytes::from(value.encode_fixed_vec()))
+    }
+
+    pub fn encode_sequence_key(table_name: &str) -> Vec<u8> {
+        Self::key_prefix(CodecType::Sequence, table_name)
+    }
+
+    pub fn decode_sequence(bytes: &[u8]) -> i64 {
+        i64::decode_fixed(bytes)
+    }
+
     /// Key: Root{BOUND_MIN_TAG}{TableName}
     /// Value: TableMeta
     pub fn encode_root_table(meta: &TableMeta) -> Result<(Bytes, Bytes), DatabaseError> {
@@ -380,11 +509,51 @@ impl TableCodec {
     pub fn decode_root_table(bytes: &[u8]) -> Result<TableMeta, DatabaseError> {
         Ok(bincode::deserialize(bytes)?)
     }
+
+    /// The name of the table of the key encoded by [`TableCodec::encode_root_table_key`].
+    pub fn decode_root_table_key(key: &[u8]) -> Result<String, DatabaseError> {
+        Ok(String::from_utf8(key[ROOT_BYTES.len() + 1..].to_vec())?)
+    }
+
+    /// Key: View{BOUND_MIN_TAG}{ViewName}
+    /// Value: View
+    pub fn encode_view(view: &View) -> Result<(Bytes, Bytes), DatabaseError> {
+        let key = Self::encode_view_key(&view.name);
+
+        Ok((Bytes::from(key), Bytes::from(bincode::serialize(view)?)))
+    }
+
+    pub fn encode_view_key(view_name: &str) -> Vec<u8> {
+        Self::key_prefix(CodecType::View, view_name)
+    }
+
+    pub fn decode_view(bytes: &[u8]) -> Result<View, DatabaseError> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+
+    /// Key: FormatVersion
+    /// Value: The version of the format of the storage, see [`FORMAT_VERSION`]
+    pub fn encode_format_version(version: u16) -> (Bytes, Bytes) {
+        (
+            Bytes::from(Self::encode_format_version_key()),
+            Bytes::from(version.encode_fixed_vec()),
+        )
+    }
+
+    pub fn encode_format_version_key() -> Vec<u8> {
+        FORMAT_VERSION_BYTES.clone()
+    }
+
+    pub fn decode_format_version(bytes: &[u8]) -> u16 {
+        u16::decode_fixed(bytes)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::catalog::{ColumnCatalog, ColumnDesc, ColumnRelation, TableCatalog, TableMeta};
+    use crate::catalog::{
+        ColumnCatalog, ColumnDesc, ColumnRelation, TableCatalog, TableMeta, View,
+    };
     use crate::errors::DatabaseError;
     use crate::serdes::ReferenceTables;
     use crate::storage::rocksdb::RocksTransaction;
@@ -396,6 +565,7 @@ mod tests {
     use bytes::Bytes;
     use itertools::Itertools;
     use rust_decimal::Decimal;
+    use sqlparser::ast::CharLengthUnits;
     use std::collections::BTreeSet;
     use std::io::Cursor;
     use std::ops::Bound;
@@ -429,15 +599,12 @@ mod tests {
                 Arc::new(DataValue::Decimal(Some(Decimal::new(1, 0)))),
             ],
         };
-        let (_, bytes) = TableCodec::encode_tuple(
-            &table_catalog.name,
-            &tuple,
-            &[LogicalType::Integer, LogicalType::Decimal(None, None)],
-        )?;
+        let (_, bytes) =
+            TableCodec::encode_tuple(&table_catalog.name, &tuple, table_catalog.schema_ref())?;
         let schema = table_catalog.schema_ref();
 
         debug_assert_eq!(
-            TableCodec::decode_tuple(&table_catalog.types(), &[0, 1], schema, &bytes),
+            TableCodec::decode_tuple(&table_catalog.types(), &[0], &[0, 1], schema, &bytes),
             tuple
         );
 
@@ -449,12 +616,33 @@ mod tests {
         let table_catalog = build_table_codec();
         let (_, bytes) = TableCodec::encode_root_table(&TableMeta {
             table_name: table_catalog.name.clone(),
+            view_query: None,
+            checks: vec![],
+            foreign_keys: vec![],
+            comment: Some("orders fact table".to_string()),
         })
         .unwrap();
 
         let table_meta = TableCodec::decode_root_table(&bytes).unwrap();
 
         debug_assert_eq!(table_meta.table_name.as_str(), table_catalog.name.as_str());
+        debug_assert_eq!(table_meta.comment.as_deref(), Some("orders fact table"));
+    }
+
+    #[test]
+    fn test_view_codec() -> Result<(), DatabaseError> {
+        let view = View {
+            name: Arc::new("v1".to_string()),
+            columns: vec!["a".to_string()],
+            query: "SELECT c1 FROM t1".to_string(),
+        };
+        let (key, bytes) = TableCodec::encode_view(&view)?;
+        let (min, max) = TableCodec::view_bound();
+
+        assert!(key.as_ref() > min.as_slice() && key.as_ref() < max.as_slice());
+        assert_eq!(TableCodec::decode_view(&bytes)?, view);
+
+        Ok(())
     }
 
     #[test]
@@ -466,15 +654,39 @@ mod tests {
         debug_assert_eq!(path, decode_path);
     }
 
+    #[test]
+    fn test_table_codec_row_count() {
+        let (min, max) = TableCodec::row_count_bound("t1");
+        let (key, bytes) = TableCodec::encode_row_count_delta("t1", 233, Some(-2));
+
+        debug_assert!(key.as_ref() > min.as_slice() && key.as_ref() < max.as_slice());
+        debug_assert_eq!(TableCodec::decode_row_count_delta(&bytes), Some(-2));
+
+        debug_assert_eq!(TableCodec::decode_row_count_delta_id(&key), 233);
+
+        let (_, bytes) = TableCodec::encode_row_count_delta("t1", 234, None);
+        debug_assert_eq!(TableCodec::decode_row_count_delta(&bytes), None);
+    }
+
+    #[test]
+    fn test_table_codec_sequence() {
+        let (key, bytes) = TableCodec::encode_sequence("t1", 42);
+
+        debug_assert_eq!(key.to_vec(), TableCodec::encode_sequence_key("t1"));
+        debug_assert_eq!(TableCodec::decode_sequence(&bytes), 42);
+        debug_assert!(key.as_ref() > TableCodec::row_count_bound("t1").1.as_slice());
+    }
+
     #[test]
     fn test_table_codec_index_meta() -> Result<(), DatabaseError> {
         let index_meta = IndexMeta {
             id: 0,
             column_ids: vec![0],
             table_name: Arc::new("T1".to_string()),
-            pk_ty: LogicalType::Integer,
+            pk_types: vec![LogicalType::Integer],
             name: "index_1".to_string(),
             ty: IndexType::PrimaryKey,
+            json_path: vec![],
         };
         let (_, bytes) = TableCodec::encode_index_meta(&"T1".to_string(), &index_meta)?;
 
@@ -492,7 +704,26 @@ mod tests {
         let (_, bytes) = TableCodec::encode_index(&table_catalog.name, &index, &tuple_id)?;
 
         debug_assert_eq!(
-            TableCodec::decode_index(&bytes, &tuple_id.logical_type()),
+            TableCodec::decode_index(&bytes, &[tuple_id.logical_type()]),
+            tuple_id
+        );
+
+        // the id of a tuple with a composite primary key
+        let tuple_id = Arc::new(DataValue::Tuple(Some(vec![
+            Arc::new(DataValue::Int32(Some(0))),
+            Arc::new(DataValue::from("a".to_string())),
+        ])));
+        let index = Index::new(1, slice::from_ref(&value), IndexType::Normal);
+        let (_, bytes) = TableCodec::encode_index(&table_catalog.name, &index, &tuple_id)?;
+
+        debug_assert_eq!(
+            TableCodec::decode_index(
+                &bytes,
+                &[
+                    LogicalType::Integer,
+                    LogicalType::Varchar(None, CharLengthUnits::Characters)
+                ]
+            ),
             tuple_id
         );
 
@@ -534,8 +765,15 @@ mod tests {
                 false,
                 ColumnDesc {
                     column_datatype: LogicalType::Invalid,
-                    is_primary: false,
+                    primary: None,
                     is_unique: false,
+                    nulls_not_distinct: false,
+                    is_auto_increment: false,
+                    default_text: None,
+                    added_default: None,
+                    generated: None,
+                    comment: None,
+                    compression: None,
                     default: None,
                 },
             );
@@ -586,9 +824,10 @@ mod tests {
                 id: index_id as u32,
                 column_ids: vec![],
                 table_name: Arc::new(table_name.to_string()),
-                pk_ty: LogicalType::Integer,
+                pk_types: vec![LogicalType::Integer],
                 name: "".to_string(),
                 ty: IndexType::PrimaryKey,
+                json_path: vec![],
             };
 
             let (key, _) =
@@ -764,6 +1003,45 @@ mod tests {
         debug_assert_eq!(vec[2], &op(DataValue::Int32(Some(2)), "T1"));
     }
 
+    #[test]
+    fn test_table_codec_composite_tuple_bound() -> Result<(), DatabaseError> {
+        let mut set = BTreeSet::new();
+        let op = |values: &[i32]| {
+            let tuple_id = DataValue::Tuple(Some(
+                values
+                    .iter()
+                    .map(|v| Arc::new(DataValue::Int32(Some(*v))))
+                    .collect_vec(),
+            ));
+            Arc::new(tuple_id)
+        };
+
+        for key in [[0, 2], [1, -1], [1, 0], [1, 3], [2, 0]] {
+            set.insert(TableCodec::encode_tuple_key("T1", &op(&key))?);
+        }
+        // the keys starting with `1`
+        let min = TableCodec::encode_tuple_bound_key("T1", &op(&[1]), false)?;
+        let max = TableCodec::encode_tuple_bound_key("T1", &op(&[1]), true)?;
+
+        let vec = set
+            .range::<Vec<u8>, (Bound<&Vec<u8>>, Bound<&Vec<u8>>)>((
+                Bound::Included(&min),
+                Bound::Included(&max),
+            ))
+            .collect_vec();
+
+        debug_assert_eq!(
+            vec,
+            [
+                &TableCodec::encode_tuple_key("T1", &op(&[1, -1]))?,
+                &TableCodec::encode_tuple_key("T1", &op(&[1, 0]))?,
+                &TableCodec::encode_tuple_key("T1", &op(&[1, 3]))?,
+            ]
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_root_codec_name_bound() {
         let mut set = BTreeSet::new();
@@ -0,0 +1,181 @@
+use crate::errors::DatabaseError;
+use crate::types::value::ValueRef;
+
+/// Writes the elements in the text form of an array, e.g. `{a,"b c",NULL}`, where an element is
+/// quoted if it is empty, is `NULL` or has whitespace or any of `{}",\`, and `"` and `\` are
+/// escaped by `\` in the quotes.
+pub fn format(elements: &[ValueRef]) -> String {
+    let mut text = String::from("{");
+
+    for (i, element) in elements.iter().enumerate() {
+        if i != 0 {
+            text.push(',');
+        }
+        if element.is_null() {
+            text.push_str("NULL");
+            continue;
+        }
+        let element = element.to_string();
+
+        if element.is_empty()
+            || element.eq_ignore_ascii_case("null")
+            || element
+                .chars()
+                .any(|c| c.is_whitespace() || matches!(c, '{' | '}' | '"' | ',' | '\\'))
+        {
+            text.push('"');
+            for c in element.chars() {
+                if matches!(c, '"' | '\\') {
+                    text.push('\\');
+                }
+                text.push(c);
+            }
+            text.push('"');
+        } else {
+            text.push_str(&element);
+        }
+    }
+    text.push('}');
+    text
+}
+
+/// Parses the text form written by [`format`] into the texts of the elements, `None` for `NULL`.
+/// The whitespace around the elements is ignored, and an unquoted element may escape a character
+/// by `\` too.
+pub fn parse(text: &str) -> Result<Vec<Option<String>>, DatabaseError> {
+    let invalid = || DatabaseError::InvalidArray(text.to_string());
+    let mut chars = text
+        .trim()
+        .strip_prefix('{')
+        .and_then(|rest| rest.strip_suffix('}'))
+        .ok_or_else(invalid)?
+        .chars()
+        .peekable();
+    let mut elements = Vec::new();
+
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+    if chars.peek().is_none() {
+        return Ok(elements);
+    }
+    loop {
+        let mut element = String::new();
+        let mut is_quoted = false;
+
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            is_quoted = true;
+
+            loop {
+                match chars.next().ok_or_else(invalid)? {
+                    '"' => break,
+                    '\\' => element.push(chars.next().ok_or_else(invalid)?),
+                    c => element.push(c),
+                }
+            }
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        } else {
+            // Tips: the whitespace at the end is trimmed, but not if escaped
+            let mut len = 0;
+
+            while let Some(c) = chars.peek().copied() {
+                match c {
+                    ',' => break,
+                    '{' | '}' | '"' => return Err(invalid()),
+                    '\\' => {
+                        chars.next();
+                        element.push(chars.next().ok_or_else(invalid)?);
+                        len = element.len();
+                        continue;
+                    }
+                    c => element.push(c),
+                }
+                chars.next();
+                if !c.is_whitespace() {
+                    len = element.len();
+                }
+            }
+            element.truncate(len);
+
+            if element.is_empty() {
+                return Err(invalid());
+            }
+        }
+        elements.push((is_quoted || !element.eq_ignore_ascii_case("null")).then_some(element));
+
+        match chars.next() {
+            Some(',') => continue,
+            None => break,
+            Some(_) => return Err(invalid()),
+        }
+    }
+    Ok(elements)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors::DatabaseError;
+    use crate::types::array::{format, parse};
+    use crate::types::value::DataValue;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_format() {
+        let elements = vec![
+            Arc::new(DataValue::from("a".to_string())),
+            Arc::new(DataValue::from("b c".to_string())),
+            Arc::new(DataValue::from("".to_string())),
+            Arc::new(DataValue::from("NULL".to_string())),
+            Arc::new(DataValue::from("x\"y\\z".to_string())),
+            Arc::new(DataValue::from(None::<String>)),
+        ];
+
+        assert_eq!(format(&elements), r#"{a,"b c","","NULL","x\"y\\z",NULL}"#);
+        assert_eq!(format(&[Arc::new(DataValue::Int32(Some(-1)))]), "{-1}");
+        assert_eq!(format(&[]), "{}");
+    }
+
+    #[test]
+    fn test_parse() -> Result<(), DatabaseError> {
+        let some = |text: &str| Some(text.to_string());
+
+        assert_eq!(
+            parse(r#"{a,"b c","","NULL","x\"y\\z",NULL}"#)?,
+            vec![
+                some("a"),
+                some("b c"),
+                some(""),
+                some("NULL"),
+                some("x\"y\\z"),
+                None
+            ]
+        );
+        assert_eq!(
+            parse(" { 1 , 2,3 ,null } ")?,
+            vec![some("1"), some("2"), some("3"), None]
+        );
+        assert_eq!(parse(r"{a\ ,b\,c}")?, vec![some("a "), some("b,c")]);
+        assert_eq!(parse("{}")?, vec![]);
+        assert_eq!(parse("{ }")?, vec![]);
+
+        for text in [
+            "",
+            "a",
+            "{a",
+            "a}",
+            "{a,}",
+            "{,a}",
+            "{{1},{2}}",
+            "{\"a}",
+            "{\"a\"b}",
+        ] {
+            assert!(
+                matches!(parse(text), Err(DatabaseError::InvalidArray(_))),
+                "{}",
+                text
+            );
+        }
+
+        Ok(())
+    }
+}
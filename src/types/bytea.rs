@@ -0,0 +1,91 @@
+use crate::errors::DatabaseError;
+use std::fmt::Write;
+
+/// Writes `bytes` in the hex form of `\x` followed by two lowercase hexadecimal digits for each
+/// byte, e.g. `\xdeadbeef`.
+pub fn format(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(2 + bytes.len() * 2);
+
+    text.push_str("\\x");
+    for byte in bytes {
+        let _ = write!(text, "{:02x}", byte);
+    }
+    text
+}
+
+/// Parses `text` in the hex form written by [`format`], where the digits are in any case and
+/// may be separated by whitespace between the bytes, or else in the escape form, where each byte
+/// is the byte of a character, `\\` or `\` followed by three octal digits.
+pub fn parse(text: &str) -> Result<Vec<u8>, DatabaseError> {
+    let invalid = || DatabaseError::InvalidBytea(text.to_string());
+
+    if let Some(digits) = text.strip_prefix("\\x") {
+        return parse_hex(digits).ok_or_else(invalid);
+    }
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+
+    while let Some((&byte, after)) = rest.split_first() {
+        if byte != b'\\' {
+            bytes.push(byte);
+            rest = after;
+            continue;
+        }
+        match after {
+            [b'\\', after @ ..] => {
+                bytes.push(b'\\');
+                rest = after;
+            }
+            [d_1 @ b'0'..=b'3', d_2 @ b'0'..=b'7', d_3 @ b'0'..=b'7', after @ ..] => {
+                bytes.push(((d_1 - b'0') << 6) | ((d_2 - b'0') << 3) | (d_3 - b'0'));
+                rest = after;
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    Ok(bytes)
+}
+
+/// Parses the pairs of hexadecimal digits of `X'DEADBEEF'` or of the hex form.
+pub(crate) fn parse_hex(digits: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(digits.len() / 2);
+    let mut digits = digits
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace())
+        .map(|c| c.to_digit(16));
+
+    while let Some(high) = digits.next() {
+        let low = digits.next()??;
+        bytes.push((high? * 16 + low) as u8);
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors::DatabaseError;
+    use crate::types::bytea::{format, parse, parse_hex};
+
+    #[test]
+    fn test_format_and_parse() -> Result<(), DatabaseError> {
+        let bytes = vec![0xde, 0xad, 0x00, 0xbe, 0xef];
+
+        assert_eq!(format(&bytes), "\\xdead00beef");
+        assert_eq!(format(&[]), "\\x");
+        assert_eq!(parse("\\xDEAD00beef")?, bytes);
+        assert_eq!(parse("\\xde ad 00 be ef")?, bytes);
+        assert_eq!(parse("\\x")?, Vec::<u8>::new());
+        assert_eq!(parse("ab\\\\c\\000\\377")?, b"ab\\c\x00\xff".to_vec());
+        assert_eq!(parse_hex("0aFF"), Some(vec![0x0a, 0xff]));
+
+        for invalid in ["\\xabc", "\\xzz", "a\\b", "\\400", "\\12"] {
+            assert!(matches!(
+                parse(invalid),
+                Err(DatabaseError::InvalidBytea(_))
+            ));
+        }
+        assert_eq!(parse_hex("a"), None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use crate::types::evaluator::BinaryEvaluator;
+use crate::types::evaluator::DataValue;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct ByteaGtBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct ByteaGtEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct ByteaLtBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct ByteaLtEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct ByteaEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct ByteaNotEqBinaryEvaluator;
+
+fn unpack_bytea(value: &DataValue) -> Option<&[u8]> {
+    match value {
+        DataValue::Bytea(value) => value.as_deref(),
+        _ => None,
+    }
+}
+
+macro_rules! bytea_compare_evaluator {
+    ($evaluator:ident, $op:tt) => {
+        #[typetag::serde]
+        impl BinaryEvaluator for $evaluator {
+            // Tips: the bytes are compared one by one, and a prefix is less than the whole
+            fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+                let value = match (unpack_bytea(left), unpack_bytea(right)) {
+                    (Some(v1), Some(v2)) => Some(v1 $op v2),
+                    _ => None,
+                };
+                DataValue::Boolean(value)
+            }
+        }
+    };
+}
+
+bytea_compare_evaluator!(ByteaGtBinaryEvaluator, >);
+bytea_compare_evaluator!(ByteaGtEqBinaryEvaluator, >=);
+bytea_compare_evaluator!(ByteaLtBinaryEvaluator, <);
+bytea_compare_evaluator!(ByteaLtEqBinaryEvaluator, <=);
+bytea_compare_evaluator!(ByteaEqBinaryEvaluator, ==);
+bytea_compare_evaluator!(ByteaNotEqBinaryEvaluator, !=);
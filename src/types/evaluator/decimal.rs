@@ -1,16 +1,35 @@
+use crate::errors::DatabaseError;
 use crate::types::evaluator::BinaryEvaluator;
 use crate::types::evaluator::DataValue;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::hint;
 
+/// `+` of decimals, rounded to `scale`, the scale of the result type.
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
-pub struct DecimalPlusBinaryEvaluator;
+pub struct DecimalPlusBinaryEvaluator {
+    pub scale: Option<u8>,
+}
+/// `-` of decimals, rounded to `scale`, the scale of the result type.
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
-pub struct DecimalMinusBinaryEvaluator;
+pub struct DecimalMinusBinaryEvaluator {
+    pub scale: Option<u8>,
+}
+/// `*` of decimals, rounded to `scale`, the scale of the result type.
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
-pub struct DecimalMultiplyBinaryEvaluator;
+pub struct DecimalMultiplyBinaryEvaluator {
+    pub scale: Option<u8>,
+}
+/// `/` of decimals, rounded to `scale`, the scale of the result type.
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
-pub struct DecimalDivideBinaryEvaluator;
+pub struct DecimalDivideBinaryEvaluator {
+    pub scale: Option<u8>,
+}
+/// `%` of decimals, rounded to `scale`, the scale of the result type.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DecimalModBinaryEvaluator {
+    pub scale: Option<u8>,
+}
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub struct DecimalGtBinaryEvaluator;
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
@@ -23,93 +42,66 @@ pub struct DecimalLtEqBinaryEvaluator;
 pub struct DecimalEqBinaryEvaluator;
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub struct DecimalNotEqBinaryEvaluator;
-#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
-pub struct DecimalModBinaryEvaluator;
 
-#[typetag::serde]
-impl BinaryEvaluator for DecimalPlusBinaryEvaluator {
-    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
-        let left = match left {
-            DataValue::Decimal(value) => value,
-            DataValue::Null => &None,
-            _ => unsafe { hint::unreachable_unchecked() },
-        };
-        let right = match right {
-            DataValue::Decimal(value) => value,
-            DataValue::Null => &None,
-            _ => unsafe { hint::unreachable_unchecked() },
-        };
-        let value = if let (Some(v1), Some(v2)) = (left, right) {
-            Some(v1 + v2)
-        } else {
-            None
-        };
-        DataValue::Decimal(value)
+fn unpack_decimal(value: &DataValue) -> Option<&Decimal> {
+    match value {
+        DataValue::Decimal(value) => value.as_ref(),
+        _ => None,
     }
 }
-#[typetag::serde]
-impl BinaryEvaluator for DecimalMinusBinaryEvaluator {
-    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
-        let left = match left {
-            DataValue::Decimal(value) => value,
-            DataValue::Null => &None,
-            _ => unsafe { hint::unreachable_unchecked() },
-        };
-        let right = match right {
-            DataValue::Decimal(value) => value,
-            DataValue::Null => &None,
-            _ => unsafe { hint::unreachable_unchecked() },
-        };
-        let value = if let (Some(v1), Some(v2)) = (left, right) {
-            Some(v1 - v2)
-        } else {
-            None
-        };
-        DataValue::Decimal(value)
-    }
+
+macro_rules! decimal_arithmetic_evaluator {
+    ($evaluator:ident, $compute:expr) => {
+        #[typetag::serde]
+        impl BinaryEvaluator for $evaluator {
+            // Tips: the result that can not be computed is NULL here, see `checked_binary_eval`
+            fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+                self.checked_binary_eval(left, right)
+                    .unwrap_or(DataValue::Decimal(None))
+            }
+
+            fn checked_binary_eval(
+                &self,
+                left: &DataValue,
+                right: &DataValue,
+            ) -> Result<DataValue, DatabaseError> {
+                let (Some(v1), Some(v2)) = (unpack_decimal(left), unpack_decimal(right)) else {
+                    return Ok(DataValue::Decimal(None));
+                };
+                let compute: fn(&Decimal, &Decimal) -> Result<Decimal, DatabaseError> = $compute;
+                let mut value = compute(v1, v2)?;
+                DataValue::decimal_round(&self.scale, &mut value);
+
+                Ok(DataValue::Decimal(Some(value)))
+            }
+        }
+    };
 }
-#[typetag::serde]
-impl BinaryEvaluator for DecimalMultiplyBinaryEvaluator {
-    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
-        let left = match left {
-            DataValue::Decimal(value) => value,
-            DataValue::Null => &None,
-            _ => unsafe { hint::unreachable_unchecked() },
-        };
-        let right = match right {
-            DataValue::Decimal(value) => value,
-            DataValue::Null => &None,
-            _ => unsafe { hint::unreachable_unchecked() },
-        };
-        let value = if let (Some(v1), Some(v2)) = (left, right) {
-            Some(v1 * v2)
-        } else {
-            None
-        };
-        DataValue::Decimal(value)
+
+decimal_arithmetic_evaluator!(DecimalPlusBinaryEvaluator, |v1, v2| v1
+    .checked_add(*v2)
+    .ok_or_else(|| DatabaseError::ArithmeticOverflow(format!("{} + {}", v1, v2))));
+decimal_arithmetic_evaluator!(DecimalMinusBinaryEvaluator, |v1, v2| v1
+    .checked_sub(*v2)
+    .ok_or_else(|| DatabaseError::ArithmeticOverflow(format!("{} - {}", v1, v2))));
+decimal_arithmetic_evaluator!(DecimalMultiplyBinaryEvaluator, |v1, v2| v1
+    .checked_mul(*v2)
+    .ok_or_else(|| DatabaseError::ArithmeticOverflow(format!("{} * {}", v1, v2))));
+decimal_arithmetic_evaluator!(DecimalDivideBinaryEvaluator, |v1, v2| {
+    if v2.is_zero() {
+        return Err(DatabaseError::DivisionByZero);
     }
-}
-#[typetag::serde]
-impl BinaryEvaluator for DecimalDivideBinaryEvaluator {
-    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
-        let left = match left {
-            DataValue::Decimal(value) => value,
-            DataValue::Null => &None,
-            _ => unsafe { hint::unreachable_unchecked() },
-        };
-        let right = match right {
-            DataValue::Decimal(value) => value,
-            DataValue::Null => &None,
-            _ => unsafe { hint::unreachable_unchecked() },
-        };
-        let value = if let (Some(v1), Some(v2)) = (left, right) {
-            Some(v1 / v2)
-        } else {
-            None
-        };
-        DataValue::Decimal(value)
+    v1.checked_div(*v2)
+        .ok_or_else(|| DatabaseError::ArithmeticOverflow(format!("{} / {}", v1, v2)))
+});
+decimal_arithmetic_evaluator!(DecimalModBinaryEvaluator, |v1, v2| {
+    if v2.is_zero() {
+        return Err(DatabaseError::DivisionByZero);
     }
-}
+    v1.checked_rem(*v2)
+        .ok_or_else(|| DatabaseError::ArithmeticOverflow(format!("{} % {}", v1, v2)))
+});
+
 #[typetag::serde]
 impl BinaryEvaluator for DecimalGtBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
@@ -236,24 +228,3 @@ impl BinaryEvaluator for DecimalNotEqBinaryEvaluator {
         DataValue::Boolean(value)
     }
 }
-#[typetag::serde]
-impl BinaryEvaluator for DecimalModBinaryEvaluator {
-    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
-        let left = match left {
-            DataValue::Decimal(value) => value,
-            DataValue::Null => &None,
-            _ => unsafe { hint::unreachable_unchecked() },
-        };
-        let right = match right {
-            DataValue::Decimal(value) => value,
-            DataValue::Null => &None,
-            _ => unsafe { hint::unreachable_unchecked() },
-        };
-        let value = if let (Some(v1), Some(v2)) = (left, right) {
-            Some(v1 % v2)
-        } else {
-            None
-        };
-        DataValue::Decimal(value)
-    }
-}
@@ -0,0 +1,51 @@
+use crate::types::evaluator::BinaryEvaluator;
+use crate::types::evaluator::DataValue;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct CharGtBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct CharGtEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct CharLtBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct CharLtEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct CharEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct CharNotEqBinaryEvaluator;
+
+/// The string without its trailing spaces, as a `CHAR` is compared.
+fn pad_space(value: &DataValue) -> Option<&str> {
+    if let DataValue::Utf8 {
+        value: Some(value), ..
+    } = value
+    {
+        Some(value.trim_end_matches(' '))
+    } else {
+        None
+    }
+}
+
+macro_rules! char_compare_evaluator {
+    ($evaluator:ident, $op:tt) => {
+        #[typetag::serde]
+        impl BinaryEvaluator for $evaluator {
+            fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+                let value = if let (Some(v1), Some(v2)) = (pad_space(left), pad_space(right)) {
+                    Some(v1 $op v2)
+                } else {
+                    None
+                };
+                DataValue::Boolean(value)
+            }
+        }
+    };
+}
+
+char_compare_evaluator!(CharGtBinaryEvaluator, >);
+char_compare_evaluator!(CharGtEqBinaryEvaluator, >=);
+char_compare_evaluator!(CharLtBinaryEvaluator, <);
+char_compare_evaluator!(CharLtEqBinaryEvaluator, <=);
+char_compare_evaluator!(CharEqBinaryEvaluator, ==);
+char_compare_evaluator!(CharNotEqBinaryEvaluator, !=);
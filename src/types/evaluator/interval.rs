@@ -0,0 +1,249 @@
+use crate::errors::DatabaseError;
+use crate::types::evaluator::BinaryEvaluator;
+use crate::types::evaluator::DataValue;
+use crate::types::interval::Interval;
+use crate::types::LogicalType;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct IntervalPlusBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct IntervalMinusBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct IntervalGtBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct IntervalGtEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct IntervalLtBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct IntervalLtEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct IntervalEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct IntervalNotEqBinaryEvaluator;
+/// `date ± interval` and `interval + date`, the interval is added at midnight and the time of
+/// the result is truncated
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DateIntervalBinaryEvaluator {
+    pub(crate) is_minus: bool,
+    pub(crate) interval_first: bool,
+}
+/// `datetime ± interval` and `interval + datetime`
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DateTimeIntervalBinaryEvaluator {
+    pub(crate) is_minus: bool,
+    pub(crate) interval_first: bool,
+}
+/// `datetime - datetime`, in days and microseconds
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct DateTimeDiffBinaryEvaluator;
+/// `interval * integer` and `integer * interval`
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct IntervalMultiplyBinaryEvaluator {
+    pub(crate) interval_first: bool,
+}
+
+fn unpack_interval(value: &DataValue) -> Option<Interval> {
+    match value {
+        DataValue::Interval(value) => *value,
+        _ => None,
+    }
+}
+
+/// The interval and the other operand, in this order.
+fn split_operands<'a>(
+    left: &'a DataValue,
+    right: &'a DataValue,
+    interval_first: bool,
+) -> (Option<Interval>, &'a DataValue) {
+    if interval_first {
+        (unpack_interval(left), right)
+    } else {
+        (unpack_interval(right), left)
+    }
+}
+
+/// The arithmetic of the intervals is `None` only if it overflows when both operands are not
+/// null, which fails here instead.
+fn check_overflow(
+    value: DataValue,
+    left: &DataValue,
+    op: &str,
+    right: &DataValue,
+) -> Result<DataValue, DatabaseError> {
+    if value.is_null() && !left.is_null() && !right.is_null() {
+        return Err(DatabaseError::ArithmeticOverflow(format!(
+            "{} {} {}",
+            left, op, right
+        )));
+    }
+    Ok(value)
+}
+
+macro_rules! interval_compare_evaluator {
+    ($evaluator:ident, $op:tt) => {
+        #[typetag::serde]
+        impl BinaryEvaluator for $evaluator {
+            fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+                let value = if let (Some(v1), Some(v2)) = (unpack_interval(left), unpack_interval(right)) {
+                    Some(v1 $op v2)
+                } else {
+                    None
+                };
+                DataValue::Boolean(value)
+            }
+        }
+    };
+}
+
+interval_compare_evaluator!(IntervalGtBinaryEvaluator, >);
+interval_compare_evaluator!(IntervalGtEqBinaryEvaluator, >=);
+interval_compare_evaluator!(IntervalLtBinaryEvaluator, <);
+interval_compare_evaluator!(IntervalLtEqBinaryEvaluator, <=);
+interval_compare_evaluator!(IntervalEqBinaryEvaluator, ==);
+interval_compare_evaluator!(IntervalNotEqBinaryEvaluator, !=);
+
+#[typetag::serde]
+impl BinaryEvaluator for IntervalPlusBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+        let value = if let (Some(v1), Some(v2)) = (unpack_interval(left), unpack_interval(right)) {
+            v1.checked_add(&v2)
+        } else {
+            None
+        };
+        DataValue::Interval(value)
+    }
+
+    fn checked_binary_eval(
+        &self,
+        left: &DataValue,
+        right: &DataValue,
+    ) -> Result<DataValue, DatabaseError> {
+        check_overflow(self.binary_eval(left, right), left, "+", right)
+    }
+}
+
+#[typetag::serde]
+impl BinaryEvaluator for IntervalMinusBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+        let value = if let (Some(v1), Some(v2)) = (unpack_interval(left), unpack_interval(right)) {
+            v1.checked_sub(&v2)
+        } else {
+            None
+        };
+        DataValue::Interval(value)
+    }
+
+    fn checked_binary_eval(
+        &self,
+        left: &DataValue,
+        right: &DataValue,
+    ) -> Result<DataValue, DatabaseError> {
+        check_overflow(self.binary_eval(left, right), left, "-", right)
+    }
+}
+
+#[typetag::serde]
+impl BinaryEvaluator for DateIntervalBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+        let (interval, date) = split_operands(left, right, self.interval_first);
+        let interval = if self.is_minus {
+            interval.and_then(|interval| interval.checked_neg())
+        } else {
+            interval
+        };
+        let value = if let (Some(interval), DataValue::Date32(Some(date))) = (interval, date) {
+            NaiveDate::from_num_days_from_ce_opt(*date)
+                .and_then(|date| interval.add_to(date.and_time(NaiveTime::MIN)))
+                .map(|date_time| date_time.date().num_days_from_ce())
+        } else {
+            None
+        };
+        DataValue::Date32(value)
+    }
+
+    fn checked_binary_eval(
+        &self,
+        left: &DataValue,
+        right: &DataValue,
+    ) -> Result<DataValue, DatabaseError> {
+        check_overflow(
+            self.binary_eval(left, right),
+            left,
+            if self.is_minus { "-" } else { "+" },
+            right,
+        )
+    }
+}
+
+#[typetag::serde]
+impl BinaryEvaluator for DateTimeIntervalBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+        let (interval, date_time) = split_operands(left, right, self.interval_first);
+        let interval = if self.is_minus {
+            interval.and_then(|interval| interval.checked_neg())
+        } else {
+            interval
+        };
+        let value =
+            if let (Some(interval), DataValue::Date64(Some(date_time))) = (interval, date_time) {
+                DateTime::from_timestamp(*date_time, 0)
+                    .and_then(|date_time| interval.add_to(date_time.naive_utc()))
+                    .map(|date_time| date_time.and_utc().timestamp())
+            } else {
+                None
+            };
+        DataValue::Date64(value)
+    }
+
+    fn checked_binary_eval(
+        &self,
+        left: &DataValue,
+        right: &DataValue,
+    ) -> Result<DataValue, DatabaseError> {
+        check_overflow(
+            self.binary_eval(left, right),
+            left,
+            if self.is_minus { "-" } else { "+" },
+            right,
+        )
+    }
+}
+
+#[typetag::serde]
+impl BinaryEvaluator for DateTimeDiffBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+        let value = if let (Some(end), Some(start)) = (left.datetime(), right.datetime()) {
+            Interval::between(end, start)
+        } else {
+            None
+        };
+        DataValue::Interval(value)
+    }
+}
+
+#[typetag::serde]
+impl BinaryEvaluator for IntervalMultiplyBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+        let (interval, n) = split_operands(left, right, self.interval_first);
+        let n = DataValue::clone(n)
+            .cast(&LogicalType::Bigint)
+            .ok()
+            .and_then(|n| n.i64());
+        let value = if let (Some(interval), Some(n)) = (interval, n) {
+            interval.checked_mul(n)
+        } else {
+            None
+        };
+        DataValue::Interval(value)
+    }
+
+    fn checked_binary_eval(
+        &self,
+        left: &DataValue,
+        right: &DataValue,
+    ) -> Result<DataValue, DatabaseError> {
+        check_overflow(self.binary_eval(left, right), left, "*", right)
+    }
+}
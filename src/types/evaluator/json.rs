@@ -0,0 +1,35 @@
+use crate::types::evaluator::BinaryEvaluator;
+use crate::types::evaluator::DataValue;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct JsonEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct JsonNotEqBinaryEvaluator;
+
+fn unpack_json(value: &DataValue) -> Option<&str> {
+    match value {
+        DataValue::Json(value) => value.as_deref(),
+        _ => None,
+    }
+}
+
+macro_rules! json_compare_evaluator {
+    ($evaluator:ident, $op:tt) => {
+        #[typetag::serde]
+        impl BinaryEvaluator for $evaluator {
+            // Tips: the JSON values are normalized, so they are compared by their bytes
+            fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+                let value = if let (Some(v1), Some(v2)) = (unpack_json(left), unpack_json(right)) {
+                    Some(v1 $op v2)
+                } else {
+                    None
+                };
+                DataValue::Boolean(value)
+            }
+        }
+    };
+}
+
+json_compare_evaluator!(JsonEqBinaryEvaluator, ==);
+json_compare_evaluator!(JsonNotEqBinaryEvaluator, !=);
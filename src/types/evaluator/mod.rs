@@ -1,36 +1,47 @@
 pub mod boolean;
+pub mod bytea;
 pub mod date;
 pub mod datetime;
 pub mod decimal;
+pub mod fixed_char;
 pub mod float32;
 pub mod float64;
 pub mod int16;
 pub mod int32;
 pub mod int64;
 pub mod int8;
+pub mod interval;
+pub mod json;
 pub mod null;
 pub mod time;
+pub mod timestamp_tz;
 pub mod tuple;
 pub mod uint16;
 pub mod uint32;
 pub mod uint64;
 pub mod uint8;
 pub mod utf8;
+pub mod uuid;
 
 use crate::errors::DatabaseError;
 use crate::expression::{BinaryOperator, UnaryOperator};
 use crate::types::evaluator::boolean::*;
+use crate::types::evaluator::bytea::*;
 use crate::types::evaluator::date::*;
 use crate::types::evaluator::datetime::*;
 use crate::types::evaluator::decimal::*;
+use crate::types::evaluator::fixed_char::*;
 use crate::types::evaluator::float32::*;
 use crate::types::evaluator::float64::*;
 use crate::types::evaluator::int16::*;
 use crate::types::evaluator::int32::*;
 use crate::types::evaluator::int64::*;
 use crate::types::evaluator::int8::*;
+use crate::types::evaluator::interval::*;
+use crate::types::evaluator::json::*;
 use crate::types::evaluator::null::NullBinaryEvaluator;
 use crate::types::evaluator::time::*;
+use crate::types::evaluator::timestamp_tz::*;
 use crate::types::evaluator::tuple::{
     TupleEqBinaryEvaluator, TupleGtBinaryEvaluator, TupleGtEqBinaryEvaluator,
     TupleLtBinaryEvaluator, TupleLtEqBinaryEvaluator, TupleNotEqBinaryEvaluator,
@@ -44,22 +55,142 @@ use crate::types::evaluator::utf8::{
     Utf8EqBinaryEvaluator, Utf8GtBinaryEvaluator, Utf8GtEqBinaryEvaluator, Utf8LtBinaryEvaluator,
     Utf8LtEqBinaryEvaluator, Utf8NotEqBinaryEvaluator, Utf8StringConcatBinaryEvaluator,
 };
+use crate::types::evaluator::uuid::*;
 use crate::types::value::DataValue;
 use crate::types::LogicalType;
 use paste::paste;
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 #[typetag::serde(tag = "binary")]
 pub trait BinaryEvaluator: Send + Sync + Debug {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue;
+
+    /// Like [`BinaryEvaluator::binary_eval`], but fails if the result can not be computed, e.g.
+    /// on the numeric overflow of integers or decimals.
+    fn checked_binary_eval(
+        &self,
+        left: &DataValue,
+        right: &DataValue,
+    ) -> Result<DataValue, DatabaseError> {
+        Ok(self.binary_eval(left, right))
+    }
 }
 
 #[typetag::serde(tag = "unary")]
 pub trait UnaryEvaluator: Send + Sync + Debug {
     fn unary_eval(&self, value: &DataValue) -> DataValue;
+
+    /// Like [`UnaryEvaluator::unary_eval`], but fails if the result can not be computed, e.g.
+    /// on the negation of the minimum integer.
+    fn checked_unary_eval(&self, value: &DataValue) -> Result<DataValue, DatabaseError> {
+        Ok(self.unary_eval(value))
+    }
+}
+
+/// The arithmetic of the values of the numeric evaluators, `None` if the result overflows. The
+/// floats never overflow here, they are infinite instead.
+pub trait CheckedArithmetic: Copy + Display {
+    fn is_zero(&self) -> bool;
+
+    fn checked_plus(self, rhs: Self) -> Option<Self>;
+
+    fn checked_minus(self, rhs: Self) -> Option<Self>;
+
+    fn checked_multiply(self, rhs: Self) -> Option<Self>;
+
+    fn checked_modulo(self, rhs: Self) -> Option<Self>;
+
+    fn checked_negate(self) -> Option<Self>;
+}
+
+macro_rules! integer_checked_arithmetic {
+    ($($ty:ty),*) => {
+        $(
+            impl CheckedArithmetic for $ty {
+                fn is_zero(&self) -> bool {
+                    *self == 0
+                }
+
+                fn checked_plus(self, rhs: Self) -> Option<Self> {
+                    self.checked_add(rhs)
+                }
+
+                fn checked_minus(self, rhs: Self) -> Option<Self> {
+                    self.checked_sub(rhs)
+                }
+
+                fn checked_multiply(self, rhs: Self) -> Option<Self> {
+                    self.checked_mul(rhs)
+                }
+
+                fn checked_modulo(self, rhs: Self) -> Option<Self> {
+                    self.checked_rem(rhs)
+                }
+
+                fn checked_negate(self) -> Option<Self> {
+                    self.checked_neg()
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! float_checked_arithmetic {
+    ($($ty:ty),*) => {
+        $(
+            impl CheckedArithmetic for $ty {
+                fn is_zero(&self) -> bool {
+                    *self == 0.0
+                }
+
+                fn checked_plus(self, rhs: Self) -> Option<Self> {
+                    Some(self + rhs)
+                }
+
+                fn checked_minus(self, rhs: Self) -> Option<Self> {
+                    Some(self - rhs)
+                }
+
+                fn checked_multiply(self, rhs: Self) -> Option<Self> {
+                    Some(self * rhs)
+                }
+
+                fn checked_modulo(self, rhs: Self) -> Option<Self> {
+                    Some(self % rhs)
+                }
+
+                fn checked_negate(self) -> Option<Self> {
+                    Some(-self)
+                }
+            }
+        )*
+    };
+}
+
+integer_checked_arithmetic!(i8, i16, i32, i64, u8, u16, u32, u64);
+float_checked_arithmetic!(f32, f64);
+
+/// Computes `left op right` by `compute`, `None` if either is `None`. Fails with
+/// [`DatabaseError::DivisionByZero`] if `right` is zero for `/` and `%`, or with
+/// [`DatabaseError::ArithmeticOverflow`] if the result overflows.
+pub fn checked_arithmetic<T: CheckedArithmetic>(
+    left: &Option<T>,
+    right: &Option<T>,
+    op: BinaryOperator,
+    compute: fn(T, T) -> Option<T>,
+) -> Result<Option<T>, DatabaseError> {
+    let (Some(v1), Some(v2)) = (left, right) else {
+        return Ok(None);
+    };
+    if matches!(op, BinaryOperator::Divide | BinaryOperator::Modulo) && v2.is_zero() {
+        return Err(DatabaseError::DivisionByZero);
+    }
+    compute(*v1, *v2)
+        .map(Some)
+        .ok_or_else(|| DatabaseError::ArithmeticOverflow(format!("{} {} {}", v1, op, v2)))
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -69,6 +200,14 @@ impl BinaryEvaluatorBox {
     pub fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
         self.0.binary_eval(left, right)
     }
+
+    pub fn checked_binary_eval(
+        &self,
+        left: &DataValue,
+        right: &DataValue,
+    ) -> Result<DataValue, DatabaseError> {
+        self.0.checked_binary_eval(left, right)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -78,6 +217,10 @@ impl UnaryEvaluatorBox {
     pub fn unary_eval(&self, value: &DataValue) -> DataValue {
         self.0.unary_eval(value)
     }
+
+    pub fn checked_unary_eval(&self, value: &DataValue) -> Result<DataValue, DatabaseError> {
+        self.0.checked_unary_eval(value)
+    }
 }
 
 impl PartialEq for BinaryEvaluatorBox {
@@ -192,7 +335,44 @@ impl EvaluatorFactory {
             LogicalType::Date => numeric_binary_evaluator!(Date, op, LogicalType::Date),
             LogicalType::DateTime => numeric_binary_evaluator!(DateTime, op, LogicalType::DateTime),
             LogicalType::Time => numeric_binary_evaluator!(Time, op, LogicalType::Time),
-            LogicalType::Decimal(_, _) => numeric_binary_evaluator!(Decimal, op, ty),
+            LogicalType::Decimal(_, scale) => match op {
+                BinaryOperator::Plus => {
+                    Ok(BinaryEvaluatorBox(Arc::new(DecimalPlusBinaryEvaluator {
+                        scale,
+                    })))
+                }
+                BinaryOperator::Minus => {
+                    Ok(BinaryEvaluatorBox(Arc::new(DecimalMinusBinaryEvaluator {
+                        scale,
+                    })))
+                }
+                BinaryOperator::Multiply => Ok(BinaryEvaluatorBox(Arc::new(
+                    DecimalMultiplyBinaryEvaluator { scale },
+                ))),
+                BinaryOperator::Divide => {
+                    Ok(BinaryEvaluatorBox(Arc::new(DecimalDivideBinaryEvaluator {
+                        scale,
+                    })))
+                }
+                BinaryOperator::Modulo => {
+                    Ok(BinaryEvaluatorBox(Arc::new(DecimalModBinaryEvaluator {
+                        scale,
+                    })))
+                }
+                BinaryOperator::Gt => Ok(BinaryEvaluatorBox(Arc::new(DecimalGtBinaryEvaluator))),
+                BinaryOperator::GtEq => {
+                    Ok(BinaryEvaluatorBox(Arc::new(DecimalGtEqBinaryEvaluator)))
+                }
+                BinaryOperator::Lt => Ok(BinaryEvaluatorBox(Arc::new(DecimalLtBinaryEvaluator))),
+                BinaryOperator::LtEq => {
+                    Ok(BinaryEvaluatorBox(Arc::new(DecimalLtEqBinaryEvaluator)))
+                }
+                BinaryOperator::Eq => Ok(BinaryEvaluatorBox(Arc::new(DecimalEqBinaryEvaluator))),
+                BinaryOperator::NotEq => {
+                    Ok(BinaryEvaluatorBox(Arc::new(DecimalNotEqBinaryEvaluator)))
+                }
+                _ => Err(DatabaseError::UnsupportedBinaryOperator(ty, op)),
+            },
             LogicalType::Boolean => match op {
                 BinaryOperator::And => Ok(BinaryEvaluatorBox(Arc::new(BooleanAndBinaryEvaluator))),
                 BinaryOperator::Or => Ok(BinaryEvaluatorBox(Arc::new(BooleanOrBinaryEvaluator))),
@@ -225,11 +405,100 @@ impl EvaluatorFactory {
                         escape_char,
                     })))
                 }
+                BinaryOperator::ILike(escape_char) => {
+                    Ok(BinaryEvaluatorBox(Arc::new(Utf8ILikeBinaryEvaluator {
+                        escape_char,
+                    })))
+                }
+                BinaryOperator::NotILike(escape_char) => {
+                    Ok(BinaryEvaluatorBox(Arc::new(Utf8NotILikeBinaryEvaluator {
+                        escape_char,
+                    })))
+                }
+                BinaryOperator::RegexMatch => {
+                    Ok(BinaryEvaluatorBox(Arc::new(Utf8RegexMatchBinaryEvaluator)))
+                }
+                BinaryOperator::NotRegexMatch => Ok(BinaryEvaluatorBox(Arc::new(
+                    Utf8NotRegexMatchBinaryEvaluator,
+                ))),
+                BinaryOperator::RegexIMatch => {
+                    Ok(BinaryEvaluatorBox(Arc::new(Utf8RegexIMatchBinaryEvaluator)))
+                }
+                BinaryOperator::NotRegexIMatch => Ok(BinaryEvaluatorBox(Arc::new(
+                    Utf8NotRegexIMatchBinaryEvaluator,
+                ))),
+                _ => Err(DatabaseError::UnsupportedBinaryOperator(ty, op)),
+            },
+            LogicalType::Interval => match op {
+                BinaryOperator::Plus => {
+                    Ok(BinaryEvaluatorBox(Arc::new(IntervalPlusBinaryEvaluator)))
+                }
+                BinaryOperator::Minus => {
+                    Ok(BinaryEvaluatorBox(Arc::new(IntervalMinusBinaryEvaluator)))
+                }
+                BinaryOperator::Gt => Ok(BinaryEvaluatorBox(Arc::new(IntervalGtBinaryEvaluator))),
+                BinaryOperator::GtEq => {
+                    Ok(BinaryEvaluatorBox(Arc::new(IntervalGtEqBinaryEvaluator)))
+                }
+                BinaryOperator::Lt => Ok(BinaryEvaluatorBox(Arc::new(IntervalLtBinaryEvaluator))),
+                BinaryOperator::LtEq => {
+                    Ok(BinaryEvaluatorBox(Arc::new(IntervalLtEqBinaryEvaluator)))
+                }
+                BinaryOperator::Eq => Ok(BinaryEvaluatorBox(Arc::new(IntervalEqBinaryEvaluator))),
+                BinaryOperator::NotEq => {
+                    Ok(BinaryEvaluatorBox(Arc::new(IntervalNotEqBinaryEvaluator)))
+                }
+                _ => Err(DatabaseError::UnsupportedBinaryOperator(ty, op)),
+            },
+            LogicalType::Json => match op {
+                BinaryOperator::Eq => Ok(BinaryEvaluatorBox(Arc::new(JsonEqBinaryEvaluator))),
+                BinaryOperator::NotEq => Ok(BinaryEvaluatorBox(Arc::new(JsonNotEqBinaryEvaluator))),
+                _ => Err(DatabaseError::UnsupportedBinaryOperator(ty, op)),
+            },
+            LogicalType::TimestampTz => match op {
+                BinaryOperator::Gt => {
+                    Ok(BinaryEvaluatorBox(Arc::new(TimestampTzGtBinaryEvaluator)))
+                }
+                BinaryOperator::GtEq => {
+                    Ok(BinaryEvaluatorBox(Arc::new(TimestampTzGtEqBinaryEvaluator)))
+                }
+                BinaryOperator::Lt => {
+                    Ok(BinaryEvaluatorBox(Arc::new(TimestampTzLtBinaryEvaluator)))
+                }
+                BinaryOperator::LtEq => {
+                    Ok(BinaryEvaluatorBox(Arc::new(TimestampTzLtEqBinaryEvaluator)))
+                }
+                BinaryOperator::Eq => {
+                    Ok(BinaryEvaluatorBox(Arc::new(TimestampTzEqBinaryEvaluator)))
+                }
+                BinaryOperator::NotEq => Ok(BinaryEvaluatorBox(Arc::new(
+                    TimestampTzNotEqBinaryEvaluator,
+                ))),
+                _ => Err(DatabaseError::UnsupportedBinaryOperator(ty, op)),
+            },
+            LogicalType::Uuid => match op {
+                BinaryOperator::Gt => Ok(BinaryEvaluatorBox(Arc::new(UuidGtBinaryEvaluator))),
+                BinaryOperator::GtEq => Ok(BinaryEvaluatorBox(Arc::new(UuidGtEqBinaryEvaluator))),
+                BinaryOperator::Lt => Ok(BinaryEvaluatorBox(Arc::new(UuidLtBinaryEvaluator))),
+                BinaryOperator::LtEq => Ok(BinaryEvaluatorBox(Arc::new(UuidLtEqBinaryEvaluator))),
+                BinaryOperator::Eq => Ok(BinaryEvaluatorBox(Arc::new(UuidEqBinaryEvaluator))),
+                BinaryOperator::NotEq => Ok(BinaryEvaluatorBox(Arc::new(UuidNotEqBinaryEvaluator))),
+                _ => Err(DatabaseError::UnsupportedBinaryOperator(ty, op)),
+            },
+            LogicalType::Bytea => match op {
+                BinaryOperator::Gt => Ok(BinaryEvaluatorBox(Arc::new(ByteaGtBinaryEvaluator))),
+                BinaryOperator::GtEq => Ok(BinaryEvaluatorBox(Arc::new(ByteaGtEqBinaryEvaluator))),
+                BinaryOperator::Lt => Ok(BinaryEvaluatorBox(Arc::new(ByteaLtBinaryEvaluator))),
+                BinaryOperator::LtEq => Ok(BinaryEvaluatorBox(Arc::new(ByteaLtEqBinaryEvaluator))),
+                BinaryOperator::Eq => Ok(BinaryEvaluatorBox(Arc::new(ByteaEqBinaryEvaluator))),
+                BinaryOperator::NotEq => {
+                    Ok(BinaryEvaluatorBox(Arc::new(ByteaNotEqBinaryEvaluator)))
+                }
                 _ => Err(DatabaseError::UnsupportedBinaryOperator(ty, op)),
             },
             LogicalType::SqlNull => Ok(BinaryEvaluatorBox(Arc::new(NullBinaryEvaluator))),
             LogicalType::Invalid => Err(DatabaseError::InvalidType),
-            LogicalType::Tuple => match op {
+            LogicalType::Tuple | LogicalType::Struct(_) => match op {
                 BinaryOperator::Eq => Ok(BinaryEvaluatorBox(Arc::new(TupleEqBinaryEvaluator))),
                 BinaryOperator::NotEq => {
                     Ok(BinaryEvaluatorBox(Arc::new(TupleNotEqBinaryEvaluator)))
@@ -240,8 +509,98 @@ impl EvaluatorFactory {
                 BinaryOperator::LtEq => Ok(BinaryEvaluatorBox(Arc::new(TupleLtEqBinaryEvaluator))),
                 _ => Err(DatabaseError::UnsupportedBinaryOperator(ty, op)),
             },
+            // Tips: arrays are compared element by element, but they are not ordered
+            LogicalType::Array(_) => match op {
+                BinaryOperator::Eq => Ok(BinaryEvaluatorBox(Arc::new(TupleEqBinaryEvaluator))),
+                BinaryOperator::NotEq => {
+                    Ok(BinaryEvaluatorBox(Arc::new(TupleNotEqBinaryEvaluator)))
+                }
+                _ => Err(DatabaseError::UnsupportedBinaryOperator(ty, op)),
+            },
         }
     }
+
+    /// Creates the evaluator of `left op right` between an interval and a value of another
+    /// type, of the subtraction of two `DateTime`s, or of the comparison of a `CHAR` with a
+    /// string, whose operands are not cast to a common type, along with the type of its result.
+    pub fn interval_binary_create(
+        op: &BinaryOperator,
+        left: &LogicalType,
+        right: &LogicalType,
+    ) -> Option<(LogicalType, BinaryEvaluatorBox)> {
+        let is_integer = |ty: &LogicalType| ty.is_signed_numeric() || ty.is_unsigned_numeric();
+        let is_minus = matches!(op, BinaryOperator::Minus);
+
+        let (ty, evaluator): (LogicalType, Arc<dyn BinaryEvaluator>) = match (op, left, right) {
+            (
+                BinaryOperator::Plus | BinaryOperator::Minus,
+                LogicalType::Date,
+                LogicalType::Interval,
+            ) => (
+                LogicalType::Date,
+                Arc::new(DateIntervalBinaryEvaluator {
+                    is_minus,
+                    interval_first: false,
+                }),
+            ),
+            (BinaryOperator::Plus, LogicalType::Interval, LogicalType::Date) => (
+                LogicalType::Date,
+                Arc::new(DateIntervalBinaryEvaluator {
+                    is_minus,
+                    interval_first: true,
+                }),
+            ),
+            (
+                BinaryOperator::Plus | BinaryOperator::Minus,
+                LogicalType::DateTime,
+                LogicalType::Interval,
+            ) => (
+                LogicalType::DateTime,
+                Arc::new(DateTimeIntervalBinaryEvaluator {
+                    is_minus,
+                    interval_first: false,
+                }),
+            ),
+            (BinaryOperator::Plus, LogicalType::Interval, LogicalType::DateTime) => (
+                LogicalType::DateTime,
+                Arc::new(DateTimeIntervalBinaryEvaluator {
+                    is_minus,
+                    interval_first: true,
+                }),
+            ),
+            (BinaryOperator::Minus, LogicalType::DateTime, LogicalType::DateTime) => {
+                (LogicalType::Interval, Arc::new(DateTimeDiffBinaryEvaluator))
+            }
+            (BinaryOperator::Multiply, LogicalType::Interval, ty) if is_integer(ty) => (
+                LogicalType::Interval,
+                Arc::new(IntervalMultiplyBinaryEvaluator {
+                    interval_first: true,
+                }),
+            ),
+            (BinaryOperator::Multiply, ty, LogicalType::Interval) if is_integer(ty) => (
+                LogicalType::Interval,
+                Arc::new(IntervalMultiplyBinaryEvaluator {
+                    interval_first: false,
+                }),
+            ),
+            // Tips: the trailing spaces are insignificant whatever the lengths of the strings are
+            (op, LogicalType::Char(..), LogicalType::Char(..) | LogicalType::Varchar(..))
+            | (op, LogicalType::Varchar(..), LogicalType::Char(..)) => (
+                LogicalType::Boolean,
+                match op {
+                    BinaryOperator::Gt => Arc::new(CharGtBinaryEvaluator),
+                    BinaryOperator::GtEq => Arc::new(CharGtEqBinaryEvaluator),
+                    BinaryOperator::Lt => Arc::new(CharLtBinaryEvaluator),
+                    BinaryOperator::LtEq => Arc::new(CharLtEqBinaryEvaluator),
+                    BinaryOperator::Eq => Arc::new(CharEqBinaryEvaluator),
+                    BinaryOperator::NotEq => Arc::new(CharNotEqBinaryEvaluator),
+                    _ => return None,
+                },
+            ),
+            _ => return None,
+        };
+        Some((ty, BinaryEvaluatorBox(evaluator)))
+    }
 }
 
 #[macro_export]
@@ -261,13 +620,28 @@ macro_rules! numeric_unary_evaluator_definition {
             }
             #[typetag::serde]
             impl UnaryEvaluator for [<$value_type MinusUnaryEvaluator>] {
+                // Tips: the result that overflows is NULL here, see `checked_unary_eval`
                 fn unary_eval(&self, value: &DataValue) -> DataValue {
+                    self.checked_unary_eval(value).unwrap_or($compute_type(None))
+                }
+
+                fn checked_unary_eval(
+                    &self,
+                    value: &DataValue,
+                ) -> Result<DataValue, $crate::errors::DatabaseError> {
                     let value = match value {
                         $compute_type(value) => value,
                         DataValue::Null => &None,
                         _ => unsafe { hint::unreachable_unchecked() },
                     };
-                    $compute_type(value.map(|v| -v))
+                    let value = value
+                        .map(|v| {
+                            $crate::types::evaluator::CheckedArithmetic::checked_negate(v).ok_or_else(
+                                || $crate::errors::DatabaseError::ArithmeticOverflow(format!("-({})", v)),
+                            )
+                        })
+                        .transpose()?;
+                    Ok($compute_type(value))
                 }
             }
         }
@@ -303,7 +677,16 @@ macro_rules! numeric_binary_evaluator_definition {
 
             #[typetag::serde]
             impl BinaryEvaluator for [<$value_type PlusBinaryEvaluator>] {
+                // Tips: the result that overflows is NULL here, see `checked_binary_eval`
                 fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+                    self.checked_binary_eval(left, right).unwrap_or($compute_type(None))
+                }
+
+                fn checked_binary_eval(
+                    &self,
+                    left: &DataValue,
+                    right: &DataValue,
+                ) -> Result<DataValue, $crate::errors::DatabaseError> {
                     let left = match left {
                         $compute_type(value) => value,
                         DataValue::Null => &None,
@@ -314,17 +697,27 @@ macro_rules! numeric_binary_evaluator_definition {
                         DataValue::Null => &None,
                         _ => unsafe { hint::unreachable_unchecked() },
                     };
-                    let value = if let (Some(v1), Some(v2)) = (left, right) {
-                        Some(v1 + v2)
-                    } else {
-                        None
-                    };
-                    $compute_type(value)
+                    let value = $crate::types::evaluator::checked_arithmetic(
+                        left,
+                        right,
+                        $crate::expression::BinaryOperator::Plus,
+                        $crate::types::evaluator::CheckedArithmetic::checked_plus,
+                    )?;
+                    Ok($compute_type(value))
                 }
             }
             #[typetag::serde]
             impl BinaryEvaluator for [<$value_type MinusBinaryEvaluator>] {
+                // Tips: the result that overflows is NULL here, see `checked_binary_eval`
                 fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+                    self.checked_binary_eval(left, right).unwrap_or($compute_type(None))
+                }
+
+                fn checked_binary_eval(
+                    &self,
+                    left: &DataValue,
+                    right: &DataValue,
+                ) -> Result<DataValue, $crate::errors::DatabaseError> {
                     let left = match left {
                         $compute_type(value) => value,
                         DataValue::Null => &None,
@@ -335,17 +728,27 @@ macro_rules! numeric_binary_evaluator_definition {
                         DataValue::Null => &None,
                         _ => unsafe { hint::unreachable_unchecked() },
                     };
-                    let value = if let (Some(v1), Some(v2)) = (left, right) {
-                        Some(v1 - v2)
-                    } else {
-                        None
-                    };
-                    $compute_type(value)
+                    let value = $crate::types::evaluator::checked_arithmetic(
+                        left,
+                        right,
+                        $crate::expression::BinaryOperator::Minus,
+                        $crate::types::evaluator::CheckedArithmetic::checked_minus,
+                    )?;
+                    Ok($compute_type(value))
                 }
             }
             #[typetag::serde]
             impl BinaryEvaluator for [<$value_type MultiplyBinaryEvaluator>] {
+                // Tips: the result that overflows is NULL here, see `checked_binary_eval`
                 fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+                    self.checked_binary_eval(left, right).unwrap_or($compute_type(None))
+                }
+
+                fn checked_binary_eval(
+                    &self,
+                    left: &DataValue,
+                    right: &DataValue,
+                ) -> Result<DataValue, $crate::errors::DatabaseError> {
                     let left = match left {
                         $compute_type(value) => value,
                         DataValue::Null => &None,
@@ -356,17 +759,27 @@ macro_rules! numeric_binary_evaluator_definition {
                         DataValue::Null => &None,
                         _ => unsafe { hint::unreachable_unchecked() },
                     };
-                    let value = if let (Some(v1), Some(v2)) = (left, right) {
-                        Some(v1 * v2)
-                    } else {
-                        None
-                    };
-                    $compute_type(value)
+                    let value = $crate::types::evaluator::checked_arithmetic(
+                        left,
+                        right,
+                        $crate::expression::BinaryOperator::Multiply,
+                        $crate::types::evaluator::CheckedArithmetic::checked_multiply,
+                    )?;
+                    Ok($compute_type(value))
                 }
             }
             #[typetag::serde]
             impl BinaryEvaluator for [<$value_type DivideBinaryEvaluator>] {
+                // Tips: the division by zero is NULL here, see `checked_binary_eval`
                 fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+                    self.checked_binary_eval(left, right).unwrap_or(DataValue::Float64(None))
+                }
+
+                fn checked_binary_eval(
+                    &self,
+                    left: &DataValue,
+                    right: &DataValue,
+                ) -> Result<DataValue, $crate::errors::DatabaseError> {
                     let left = match left {
                         $compute_type(value) => value,
                         DataValue::Null => &None,
@@ -378,11 +791,14 @@ macro_rules! numeric_binary_evaluator_definition {
                         _ => unsafe { hint::unreachable_unchecked() },
                     };
                     let value = if let (Some(v1), Some(v2)) = (left, right) {
+                        if $crate::types::evaluator::CheckedArithmetic::is_zero(v2) {
+                            return Err($crate::errors::DatabaseError::DivisionByZero);
+                        }
                         Some(*v1 as f64 / *v2 as f64)
                     } else {
                         None
                     };
-                    DataValue::Float64(value)
+                    Ok(DataValue::Float64(value))
                 }
             }
             #[typetag::serde]
@@ -513,7 +929,16 @@ macro_rules! numeric_binary_evaluator_definition {
             }
             #[typetag::serde]
             impl BinaryEvaluator for [<$value_type ModBinaryEvaluator>] {
+                // Tips: the result that overflows is NULL here, see `checked_binary_eval`
                 fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+                    self.checked_binary_eval(left, right).unwrap_or($compute_type(None))
+                }
+
+                fn checked_binary_eval(
+                    &self,
+                    left: &DataValue,
+                    right: &DataValue,
+                ) -> Result<DataValue, $crate::errors::DatabaseError> {
                     let left = match left {
                         $compute_type(value) => value,
                         DataValue::Null => &None,
@@ -524,12 +949,13 @@ macro_rules! numeric_binary_evaluator_definition {
                         DataValue::Null => &None,
                         _ => unsafe { hint::unreachable_unchecked() },
                     };
-                    let value = if let (Some(v1), Some(v2)) = (left, right) {
-                        Some(v1 % v2)
-                    } else {
-                        None
-                    };
-                    $compute_type(value)
+                    let value = $crate::types::evaluator::checked_arithmetic(
+                        left,
+                        right,
+                        $crate::expression::BinaryOperator::Modulo,
+                        $crate::types::evaluator::CheckedArithmetic::checked_modulo,
+                    )?;
+                    Ok($compute_type(value))
                 }
             }
         }
@@ -539,7 +965,7 @@ macro_rules! numeric_binary_evaluator_definition {
 #[cfg(test)]
 mod test {
     use crate::errors::DatabaseError;
-    use crate::expression::BinaryOperator;
+    use crate::expression::{BinaryOperator, UnaryOperator};
     use crate::types::evaluator::EvaluatorFactory;
     use crate::types::value::{DataValue, Utf8Type};
     use crate::types::LogicalType;
@@ -797,6 +1223,98 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_arithmetic_overflow() -> Result<(), DatabaseError> {
+        let overflow = |ty: LogicalType, op: BinaryOperator, left: DataValue, right: DataValue| {
+            let evaluator = EvaluatorFactory::binary_create(ty, op).unwrap();
+
+            // the unchecked result is NULL
+            assert!(evaluator.binary_eval(&left, &right).is_null());
+            evaluator.checked_binary_eval(&left, &right)
+        };
+
+        assert!(matches!(
+            overflow(
+                LogicalType::Integer,
+                BinaryOperator::Plus,
+                DataValue::Int32(Some(i32::MAX)),
+                DataValue::Int32(Some(1))
+            ),
+            Err(DatabaseError::ArithmeticOverflow(message)) if message == "2147483647 + 1"
+        ));
+        assert!(matches!(
+            overflow(
+                LogicalType::Tinyint,
+                BinaryOperator::Minus,
+                DataValue::Int8(Some(i8::MIN)),
+                DataValue::Int8(Some(1))
+            ),
+            Err(DatabaseError::ArithmeticOverflow(_))
+        ));
+        assert!(matches!(
+            overflow(
+                LogicalType::Bigint,
+                BinaryOperator::Multiply,
+                DataValue::Int64(Some(i64::MAX)),
+                DataValue::Int64(Some(2))
+            ),
+            Err(DatabaseError::ArithmeticOverflow(_))
+        ));
+        assert!(matches!(
+            overflow(
+                LogicalType::Integer,
+                BinaryOperator::Modulo,
+                DataValue::Int32(Some(i32::MIN)),
+                DataValue::Int32(Some(-1))
+            ),
+            Err(DatabaseError::ArithmeticOverflow(_))
+        ));
+        assert!(matches!(
+            overflow(
+                LogicalType::UInteger,
+                BinaryOperator::Minus,
+                DataValue::UInt32(Some(0)),
+                DataValue::UInt32(Some(1))
+            ),
+            Err(DatabaseError::ArithmeticOverflow(_))
+        ));
+        assert!(matches!(
+            overflow(
+                LogicalType::Integer,
+                BinaryOperator::Divide,
+                DataValue::Int32(Some(1)),
+                DataValue::Int32(Some(0))
+            ),
+            Err(DatabaseError::DivisionByZero)
+        ));
+        assert!(matches!(
+            overflow(
+                LogicalType::Smallint,
+                BinaryOperator::Modulo,
+                DataValue::Int16(Some(1)),
+                DataValue::Int16(Some(0))
+            ),
+            Err(DatabaseError::DivisionByZero)
+        ));
+
+        let minus_evaluator =
+            EvaluatorFactory::unary_create(LogicalType::Integer, UnaryOperator::Minus)?;
+        assert!(matches!(
+            minus_evaluator.checked_unary_eval(&DataValue::Int32(Some(i32::MIN))),
+            Err(DatabaseError::ArithmeticOverflow(message)) if message == "-(-2147483648)"
+        ));
+        assert_eq!(
+            minus_evaluator.checked_unary_eval(&DataValue::Int32(Some(i32::MAX)))?,
+            DataValue::Int32(Some(-i32::MAX))
+        );
+        assert_eq!(
+            minus_evaluator.checked_unary_eval(&DataValue::Int32(None))?,
+            DataValue::Int32(None)
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_binary_op_i32_compare() -> Result<(), DatabaseError> {
         let evaluator = EvaluatorFactory::binary_create(LogicalType::Integer, BinaryOperator::Gt)?;
@@ -0,0 +1,39 @@
+use crate::types::evaluator::BinaryEvaluator;
+use crate::types::evaluator::DataValue;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct TimestampTzGtBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct TimestampTzGtEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct TimestampTzLtBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct TimestampTzLtEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct TimestampTzEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct TimestampTzNotEqBinaryEvaluator;
+
+macro_rules! timestamp_tz_compare_evaluator {
+    ($evaluator:ident, $op:tt) => {
+        #[typetag::serde]
+        impl BinaryEvaluator for $evaluator {
+            fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+                let value = if let (Some(v1), Some(v2)) = (left.timestamp_tz(), right.timestamp_tz()) {
+                    Some(v1 $op v2)
+                } else {
+                    None
+                };
+                DataValue::Boolean(value)
+            }
+        }
+    };
+}
+
+timestamp_tz_compare_evaluator!(TimestampTzGtBinaryEvaluator, >);
+timestamp_tz_compare_evaluator!(TimestampTzGtEqBinaryEvaluator, >=);
+timestamp_tz_compare_evaluator!(TimestampTzLtBinaryEvaluator, <);
+timestamp_tz_compare_evaluator!(TimestampTzLtEqBinaryEvaluator, <=);
+timestamp_tz_compare_evaluator!(TimestampTzEqBinaryEvaluator, ==);
+timestamp_tz_compare_evaluator!(TimestampTzNotEqBinaryEvaluator, !=);
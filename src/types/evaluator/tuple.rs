@@ -38,12 +38,16 @@ fn tuple_cmp(v1: &[ValueRef], v2: &[ValueRef]) -> Option<Ordering> {
 impl BinaryEvaluator for TupleEqBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
         let left = match left {
-            DataValue::Tuple(value) => value,
+            DataValue::Tuple(value)
+            | DataValue::Struct { values: value, .. }
+            | DataValue::Array { values: value, .. } => value,
             DataValue::Null => &None,
             _ => unsafe { hint::unreachable_unchecked() },
         };
         let right = match right {
-            DataValue::Tuple(value) => value,
+            DataValue::Tuple(value)
+            | DataValue::Struct { values: value, .. }
+            | DataValue::Array { values: value, .. } => value,
             DataValue::Null => &None,
             _ => unsafe { hint::unreachable_unchecked() },
         };
@@ -58,12 +62,16 @@ impl BinaryEvaluator for TupleEqBinaryEvaluator {
 impl BinaryEvaluator for TupleNotEqBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
         let left = match left {
-            DataValue::Tuple(value) => value,
+            DataValue::Tuple(value)
+            | DataValue::Struct { values: value, .. }
+            | DataValue::Array { values: value, .. } => value,
             DataValue::Null => &None,
             _ => unsafe { hint::unreachable_unchecked() },
         };
         let right = match right {
-            DataValue::Tuple(value) => value,
+            DataValue::Tuple(value)
+            | DataValue::Struct { values: value, .. }
+            | DataValue::Array { values: value, .. } => value,
             DataValue::Null => &None,
             _ => unsafe { hint::unreachable_unchecked() },
         };
@@ -78,12 +86,12 @@ impl BinaryEvaluator for TupleNotEqBinaryEvaluator {
 impl BinaryEvaluator for TupleGtBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
         let left = match left {
-            DataValue::Tuple(value) => value,
+            DataValue::Tuple(value) | DataValue::Struct { values: value, .. } => value,
             DataValue::Null => &None,
             _ => unsafe { hint::unreachable_unchecked() },
         };
         let right = match right {
-            DataValue::Tuple(value) => value,
+            DataValue::Tuple(value) | DataValue::Struct { values: value, .. } => value,
             DataValue::Null => &None,
             _ => unsafe { hint::unreachable_unchecked() },
         };
@@ -98,12 +106,12 @@ impl BinaryEvaluator for TupleGtBinaryEvaluator {
 impl BinaryEvaluator for TupleGtEqBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
         let left = match left {
-            DataValue::Tuple(value) => value,
+            DataValue::Tuple(value) | DataValue::Struct { values: value, .. } => value,
             DataValue::Null => &None,
             _ => unsafe { hint::unreachable_unchecked() },
         };
         let right = match right {
-            DataValue::Tuple(value) => value,
+            DataValue::Tuple(value) | DataValue::Struct { values: value, .. } => value,
             DataValue::Null => &None,
             _ => unsafe { hint::unreachable_unchecked() },
         };
@@ -118,12 +126,12 @@ impl BinaryEvaluator for TupleGtEqBinaryEvaluator {
 impl BinaryEvaluator for TupleLtBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
         let left = match left {
-            DataValue::Tuple(value) => value,
+            DataValue::Tuple(value) | DataValue::Struct { values: value, .. } => value,
             DataValue::Null => &None,
             _ => unsafe { hint::unreachable_unchecked() },
         };
         let right = match right {
-            DataValue::Tuple(value) => value,
+            DataValue::Tuple(value) | DataValue::Struct { values: value, .. } => value,
             DataValue::Null => &None,
             _ => unsafe { hint::unreachable_unchecked() },
         };
@@ -138,12 +146,12 @@ impl BinaryEvaluator for TupleLtBinaryEvaluator {
 impl BinaryEvaluator for TupleLtEqBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
         let left = match left {
-            DataValue::Tuple(value) => value,
+            DataValue::Tuple(value) | DataValue::Struct { values: value, .. } => value,
             DataValue::Null => &None,
             _ => unsafe { hint::unreachable_unchecked() },
         };
         let right = match right {
-            DataValue::Tuple(value) => value,
+            DataValue::Tuple(value) | DataValue::Struct { values: value, .. } => value,
             DataValue::Null => &None,
             _ => unsafe { hint::unreachable_unchecked() },
         };
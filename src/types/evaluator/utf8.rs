@@ -1,11 +1,25 @@
+use crate::errors::DatabaseError;
 use crate::types::evaluator::BinaryEvaluator;
 use crate::types::evaluator::DataValue;
 use crate::types::value::Utf8Type;
-use regex::Regex;
+use crate::utils::lru::LruCache;
+use itertools::Itertools;
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 use sqlparser::ast::CharLengthUnits;
+use std::cell::RefCell;
 use std::hint;
 
+/// the number of compiled regular expressions kept on each thread
+const REGEX_CACHE_SIZE: usize = 128;
+
+thread_local! {
+    // the regular expressions compiled on this thread by their pattern and case-insensitivity,
+    // so that a literal pattern is compiled once rather than for each row
+    static REGEX_CACHE: RefCell<LruCache<(String, bool), Regex>> =
+        RefCell::new(LruCache::new(REGEX_CACHE_SIZE).unwrap());
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub struct Utf8GtBinaryEvaluator;
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
@@ -28,6 +42,22 @@ pub struct Utf8LikeBinaryEvaluator {
 pub struct Utf8NotLikeBinaryEvaluator {
     pub(crate) escape_char: Option<char>,
 }
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct Utf8ILikeBinaryEvaluator {
+    pub(crate) escape_char: Option<char>,
+}
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct Utf8NotILikeBinaryEvaluator {
+    pub(crate) escape_char: Option<char>,
+}
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct Utf8RegexMatchBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct Utf8NotRegexMatchBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct Utf8RegexIMatchBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct Utf8NotRegexIMatchBinaryEvaluator;
 
 #[typetag::serde]
 impl BinaryEvaluator for Utf8GtBinaryEvaluator {
@@ -182,63 +212,199 @@ impl BinaryEvaluator for Utf8StringConcatBinaryEvaluator {
 #[typetag::serde]
 impl BinaryEvaluator for Utf8LikeBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
-        let value = match left {
-            DataValue::Utf8 { value, .. } => value,
-            DataValue::Null => &None,
-            _ => unsafe { hint::unreachable_unchecked() },
-        };
-        let pattern = match right {
-            DataValue::Utf8 { value, .. } => value,
-            DataValue::Null => &None,
-            _ => unsafe { hint::unreachable_unchecked() },
-        };
-        let is_match = if let (Some(value), Some(pattern)) = (value, pattern) {
-            string_like(value, pattern, self.escape_char)
-        } else {
-            return DataValue::Boolean(None);
-        };
-
-        DataValue::Boolean(Some(is_match))
+        DataValue::Boolean(like_eval(left, right, self.escape_char, false))
     }
 }
 #[typetag::serde]
 impl BinaryEvaluator for Utf8NotLikeBinaryEvaluator {
     fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
-        let value = match left {
-            DataValue::Utf8 { value, .. } => value,
-            DataValue::Null => &None,
-            _ => unsafe { hint::unreachable_unchecked() },
-        };
-        let pattern = match right {
-            DataValue::Utf8 { value, .. } => value,
-            DataValue::Null => &None,
-            _ => unsafe { hint::unreachable_unchecked() },
-        };
-        let is_match = if let (Some(value), Some(pattern)) = (value, pattern) {
-            string_like(value, pattern, self.escape_char)
-        } else {
-            return DataValue::Boolean(None);
-        };
+        DataValue::Boolean(
+            like_eval(left, right, self.escape_char, false).map(|is_match| !is_match),
+        )
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for Utf8ILikeBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+        DataValue::Boolean(like_eval(left, right, self.escape_char, true))
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for Utf8NotILikeBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+        DataValue::Boolean(like_eval(left, right, self.escape_char, true).map(|is_match| !is_match))
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for Utf8RegexMatchBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+        DataValue::Boolean(regex_match_eval(left, right, false))
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for Utf8NotRegexMatchBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+        DataValue::Boolean(regex_match_eval(left, right, false).map(|is_match| !is_match))
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for Utf8RegexIMatchBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+        DataValue::Boolean(regex_match_eval(left, right, true))
+    }
+}
+#[typetag::serde]
+impl BinaryEvaluator for Utf8NotRegexIMatchBinaryEvaluator {
+    fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+        DataValue::Boolean(regex_match_eval(left, right, true).map(|is_match| !is_match))
+    }
+}
 
-        DataValue::Boolean(Some(!is_match))
+fn like_eval(
+    left: &DataValue,
+    right: &DataValue,
+    escape_char: Option<char>,
+    case_insensitive: bool,
+) -> Option<bool> {
+    let value = match left {
+        DataValue::Utf8 { value, .. } => value,
+        DataValue::Null => &None,
+        _ => unsafe { hint::unreachable_unchecked() },
+    };
+    let pattern = match right {
+        DataValue::Utf8 { value, .. } => value,
+        DataValue::Null => &None,
+        _ => unsafe { hint::unreachable_unchecked() },
+    };
+    if let (Some(value), Some(pattern)) = (value, pattern) {
+        Some(string_like(value, pattern, escape_char, case_insensitive))
+    } else {
+        None
     }
 }
 
-fn string_like(value: &str, pattern: &str, escape_char: Option<char>) -> bool {
-    let mut regex_pattern = String::new();
-    let mut chars = pattern.chars().peekable();
+enum LikeToken {
+    /// `%`
+    Any,
+    /// `_`
+    One,
+    Char(char),
+}
+
+/// Splits `pattern` into wildcards and the characters it matches literally, including the
+/// wildcards after `escape_char`.
+fn like_tokens(pattern: &str, escape_char: Option<char>) -> Result<Vec<LikeToken>, DatabaseError> {
+    let mut tokens = Vec::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+
     while let Some(c) = chars.next() {
-        if matches!(escape_char.map(|escape_c| escape_c == c), Some(true)) {
-            if let Some(next_char) = chars.next() {
-                regex_pattern.push(next_char);
+        let token = if Some(c) == escape_char {
+            let Some(escaped) = chars.next() else {
+                return Err(DatabaseError::InvalidLikePattern(format!(
+                    "`{}` ends with the escape character `{}`",
+                    pattern, c
+                )));
+            };
+            LikeToken::Char(escaped)
+        } else {
+            match c {
+                '%' => LikeToken::Any,
+                '_' => LikeToken::One,
+                c => LikeToken::Char(c),
+            }
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Checks the pattern of `LIKE` before it is evaluated, which can not fail.
+pub(crate) fn check_like_pattern(
+    pattern: &str,
+    escape_char: Option<char>,
+) -> Result<(), DatabaseError> {
+    like_tokens(pattern, escape_char).map(|_| ())
+}
+
+fn string_like(
+    value: &str,
+    pattern: &str,
+    escape_char: Option<char>,
+    case_insensitive: bool,
+) -> bool {
+    let Ok(tokens) = like_tokens(pattern, escape_char) else {
+        return false;
+    };
+    let chars = value.chars().collect_vec();
+    let char_eq = |c_1: char, c_2: char| {
+        c_1 == c_2 || (case_insensitive && c_1.to_lowercase().eq(c_2.to_lowercase()))
+    };
+    let (mut i, mut j) = (0, 0);
+    // the token after the last `%` and the character from which it is matched
+    let mut last_any = None;
+
+    while i < chars.len() {
+        let is_match = match tokens.get(j) {
+            Some(LikeToken::Any) => {
+                last_any = Some((j + 1, i));
+                j += 1;
+                continue;
             }
-        } else if c == '%' {
-            regex_pattern.push_str(".*");
-        } else if c == '_' {
-            regex_pattern.push('.');
+            Some(LikeToken::One) => true,
+            Some(LikeToken::Char(c)) => char_eq(*c, chars[i]),
+            None => false,
+        };
+        if is_match {
+            i += 1;
+            j += 1;
+        } else if let Some((any_j, any_i)) = last_any {
+            // Tips: `%` takes one more character and the tokens after it are matched again
+            last_any = Some((any_j, any_i + 1));
+            i = any_i + 1;
+            j = any_j;
         } else {
-            regex_pattern.push(c);
+            return false;
         }
     }
-    Regex::new(&regex_pattern).unwrap().is_match(value)
+    tokens[j..]
+        .iter()
+        .all(|token| matches!(token, LikeToken::Any))
+}
+
+fn regex_match_eval(left: &DataValue, right: &DataValue, case_insensitive: bool) -> Option<bool> {
+    let value = match left {
+        DataValue::Utf8 { value, .. } => value,
+        DataValue::Null => &None,
+        _ => unsafe { hint::unreachable_unchecked() },
+    };
+    let pattern = match right {
+        DataValue::Utf8 { value, .. } => value,
+        DataValue::Null => &None,
+        _ => unsafe { hint::unreachable_unchecked() },
+    };
+    if let (Some(value), Some(pattern)) = (value, pattern) {
+        // Tips: the pattern has been checked by `check_regex` before it is evaluated
+        Some(regex(pattern, case_insensitive).is_ok_and(|regex| regex.is_match(value)))
+    } else {
+        None
+    }
+}
+
+/// The compiled `pattern`, which is taken from the cache of this thread if it has been compiled.
+pub(crate) fn regex(pattern: &str, case_insensitive: bool) -> Result<Regex, DatabaseError> {
+    REGEX_CACHE.with_borrow_mut(|cache| {
+        cache
+            .get_or_insert((pattern.to_string(), case_insensitive), |(pattern, _)| {
+                RegexBuilder::new(pattern)
+                    .case_insensitive(case_insensitive)
+                    .build()
+                    .map_err(|err| DatabaseError::InvalidRegex(err.to_string()))
+            })
+            .cloned()
+    })
+}
+
+/// Checks the pattern of `~` before it is evaluated, which can not fail.
+pub(crate) fn check_regex(pattern: &str, case_insensitive: bool) -> Result<(), DatabaseError> {
+    regex(pattern, case_insensitive).map(|_| ())
 }
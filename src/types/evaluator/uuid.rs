@@ -0,0 +1,39 @@
+use crate::types::evaluator::BinaryEvaluator;
+use crate::types::evaluator::DataValue;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct UuidGtBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct UuidGtEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct UuidLtBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct UuidLtEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct UuidEqBinaryEvaluator;
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct UuidNotEqBinaryEvaluator;
+
+macro_rules! uuid_compare_evaluator {
+    ($evaluator:ident, $op:tt) => {
+        #[typetag::serde]
+        impl BinaryEvaluator for $evaluator {
+            fn binary_eval(&self, left: &DataValue, right: &DataValue) -> DataValue {
+                let value = if let (Some(v1), Some(v2)) = (left.uuid(), right.uuid()) {
+                    Some(v1 $op v2)
+                } else {
+                    None
+                };
+                DataValue::Boolean(value)
+            }
+        }
+    };
+}
+
+uuid_compare_evaluator!(UuidGtBinaryEvaluator, >);
+uuid_compare_evaluator!(UuidGtEqBinaryEvaluator, >=);
+uuid_compare_evaluator!(UuidLtBinaryEvaluator, <);
+uuid_compare_evaluator!(UuidLtEqBinaryEvaluator, <=);
+uuid_compare_evaluator!(UuidEqBinaryEvaluator, ==);
+uuid_compare_evaluator!(UuidNotEqBinaryEvaluator, !=);
@@ -2,6 +2,7 @@ use crate::catalog::{TableCatalog, TableName};
 use crate::errors::DatabaseError;
 use crate::expression::range_detacher::Range;
 use crate::expression::ScalarExpression;
+use crate::function::json::{json_path_expr, JsonPath};
 use crate::types::value::ValueRef;
 use crate::types::{ColumnId, LogicalType};
 use serde::{Deserialize, Serialize};
@@ -15,7 +16,11 @@ pub type IndexMetaRef = Arc<IndexMeta>;
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash)]
 pub enum IndexType {
     PrimaryKey,
-    Unique,
+    /// `nulls_not_distinct` makes NULLs collide with each other like any other value,
+    /// by default NULLs are distinct and never violate the uniqueness.
+    Unique {
+        nulls_not_distinct: bool,
+    },
     Normal,
     Composite,
 }
@@ -31,12 +36,18 @@ pub struct IndexMeta {
     pub id: IndexId,
     pub column_ids: Vec<ColumnId>,
     pub table_name: TableName,
-    pub pk_ty: LogicalType,
+    /// The types of the columns of the primary key in the order of the key
+    pub pk_types: Vec<LogicalType>,
     pub name: String,
     pub ty: IndexType,
+    /// The path of a JSON path index on its only column, e.g. `->> 'email'` of
+    /// `CREATE INDEX idx ON docs ((data ->> 'email'))`, whose entries are the values extracted
+    /// by it instead of the values of the column, empty for the other indexes
+    pub(crate) json_path: JsonPath,
 }
 
 impl IndexMeta {
+    /// The expressions the entries of the index are the values of, see [`IndexMeta::json_path`].
     pub(crate) fn column_exprs(
         &self,
         table: &TableCatalog,
@@ -45,7 +56,7 @@ impl IndexMeta {
 
         for column_id in self.column_ids.iter() {
             if let Some(column) = table.get_column_by_id(column_id) {
-                exprs.push(ScalarExpression::ColumnRef(column.clone()));
+                exprs.push(json_path_expr(column.clone(), &self.json_path)?);
             } else {
                 return Err(DatabaseError::NotFound(
                     "Column by id",
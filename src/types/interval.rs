@@ -0,0 +1,325 @@
+use crate::errors::DatabaseError;
+use chrono::{Duration, Months, NaiveDateTime};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::Formatter;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SECOND;
+const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
+/// Tips: a month is taken as 30 days only when intervals are compared
+const DAYS_PER_MONTH: i128 = 30;
+
+/// A span of time, whose months, days and microseconds are kept apart because the length of a
+/// month depends on the date it is added to.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub micros: i64,
+}
+
+impl Interval {
+    pub fn new(months: i32, days: i32, micros: i64) -> Self {
+        Interval {
+            months,
+            days,
+            micros,
+        }
+    }
+
+    /// The interval from `start` to `end` in days and microseconds, e.g. `1 day 02:00:00`.
+    pub fn between(end: NaiveDateTime, start: NaiveDateTime) -> Option<Self> {
+        let micros = (end - start).num_microseconds()?;
+
+        Some(Interval {
+            months: 0,
+            days: i32::try_from(micros / MICROS_PER_DAY).ok()?,
+            micros: micros % MICROS_PER_DAY,
+        })
+    }
+
+    /// The length in microseconds with 30 days in a month, by which the intervals are ordered.
+    pub(crate) fn total_micros(&self) -> i128 {
+        (self.months as i128 * DAYS_PER_MONTH + self.days as i128) * MICROS_PER_DAY as i128
+            + self.micros as i128
+    }
+
+    pub fn checked_add(&self, other: &Interval) -> Option<Interval> {
+        Some(Interval {
+            months: self.months.checked_add(other.months)?,
+            days: self.days.checked_add(other.days)?,
+            micros: self.micros.checked_add(other.micros)?,
+        })
+    }
+
+    pub fn checked_sub(&self, other: &Interval) -> Option<Interval> {
+        self.checked_add(&other.checked_neg()?)
+    }
+
+    pub fn checked_neg(&self) -> Option<Interval> {
+        Some(Interval {
+            months: self.months.checked_neg()?,
+            days: self.days.checked_neg()?,
+            micros: self.micros.checked_neg()?,
+        })
+    }
+
+    pub fn checked_mul(&self, n: i64) -> Option<Interval> {
+        Some(Interval {
+            months: i32::try_from((self.months as i64).checked_mul(n)?).ok()?,
+            days: i32::try_from((self.days as i64).checked_mul(n)?).ok()?,
+            micros: self.micros.checked_mul(n)?,
+        })
+    }
+
+    /// Adds the months first, then the days and the microseconds.
+    ///
+    /// A day of the month that does not exist in the resulting month is clamped to its last
+    /// day, e.g. `2024-01-31 + 1 month` is `2024-02-29`.
+    pub fn add_to(&self, datetime: NaiveDateTime) -> Option<NaiveDateTime> {
+        let datetime = if self.months >= 0 {
+            datetime.checked_add_months(Months::new(self.months as u32))?
+        } else {
+            datetime.checked_sub_months(Months::new(self.months.unsigned_abs()))?
+        };
+
+        datetime
+            .checked_add_signed(Duration::days(self.days as i64))?
+            .checked_add_signed(Duration::microseconds(self.micros))
+    }
+
+    /// Parses a number of `unit_micros`, e.g. the `1.5` of `1.5 hours`.
+    fn parse_micros(value: &str, unit_micros: i64) -> Option<i64> {
+        Decimal::from_str(value)
+            .ok()?
+            .checked_mul(Decimal::from(unit_micros))?
+            .round()
+            .to_i64()
+    }
+
+    /// Parses `[+-]HH:MM[:SS[.ffffff]]` as microseconds.
+    fn parse_time(value: &str) -> Option<i64> {
+        let (is_negative, value) = match value.strip_prefix('-') {
+            Some(value) => (true, value),
+            None => (false, value.strip_prefix('+').unwrap_or(value)),
+        };
+        let parts = value.split(':').collect::<Vec<_>>();
+        if !(2..=3).contains(&parts.len()) || parts.iter().any(|part| part.starts_with(['-', '+']))
+        {
+            return None;
+        }
+        let hours = i64::from_str(parts[0]).ok()?;
+        let minutes = i64::from_str(parts[1])
+            .ok()
+            .filter(|minutes| *minutes < 60)?;
+        let seconds = match parts.get(2) {
+            Some(seconds) => Self::parse_micros(seconds, MICROS_PER_SECOND)
+                .filter(|micros| *micros < 60 * MICROS_PER_SECOND)?,
+            None => 0,
+        };
+        let micros = hours
+            .checked_mul(MICROS_PER_HOUR)?
+            .checked_add(minutes * MICROS_PER_MINUTE + seconds)?;
+
+        Some(if is_negative { -micros } else { micros })
+    }
+}
+
+impl FromStr for Interval {
+    type Err = DatabaseError;
+
+    /// Parses the pairs of a number and a unit, and a time, e.g. `1 year 2 months 3 days` or
+    /// `-1 day 04:05:06.5`, the units may be in the plural.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fn_invalid = || DatabaseError::InvalidInterval(s.to_string());
+        let mut interval = Interval::default();
+        let mut tokens = s.split_whitespace().peekable();
+
+        if tokens.peek().is_none() {
+            return Err(fn_invalid());
+        }
+        while let Some(token) = tokens.next() {
+            let part = if token.contains(':') {
+                Interval::new(0, 0, Self::parse_time(token).ok_or_else(fn_invalid)?)
+            } else {
+                let unit = tokens.next().ok_or_else(fn_invalid)?.to_lowercase();
+                let unit = unit.strip_suffix('s').unwrap_or(&unit);
+                let fn_int = |n: i32| i32::from_str(token).ok().and_then(|v| v.checked_mul(n));
+                let fn_micros = |n: i64| Self::parse_micros(token, n);
+
+                match unit {
+                    "year" => fn_int(12).map(|months| Interval::new(months, 0, 0)),
+                    "month" | "mon" => fn_int(1).map(|months| Interval::new(months, 0, 0)),
+                    "week" => fn_int(7).map(|days| Interval::new(0, days, 0)),
+                    "day" => fn_int(1).map(|days| Interval::new(0, days, 0)),
+                    "hour" => fn_micros(MICROS_PER_HOUR).map(|micros| Interval::new(0, 0, micros)),
+                    "minute" | "min" => {
+                        fn_micros(MICROS_PER_MINUTE).map(|micros| Interval::new(0, 0, micros))
+                    }
+                    "second" | "sec" => {
+                        fn_micros(MICROS_PER_SECOND).map(|micros| Interval::new(0, 0, micros))
+                    }
+                    _ => None,
+                }
+                .ok_or_else(fn_invalid)?
+            };
+            interval = interval.checked_add(&part).ok_or_else(fn_invalid)?;
+        }
+
+        Ok(interval)
+    }
+}
+
+impl PartialEq for Interval {
+    fn eq(&self, other: &Self) -> bool {
+        self.total_micros() == other.total_micros()
+    }
+}
+
+impl Eq for Interval {}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.total_micros().cmp(&other.total_micros())
+    }
+}
+
+impl Hash for Interval {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.total_micros().hash(state)
+    }
+}
+
+impl fmt::Display for Interval {
+    /// e.g. `1 year 2 months -3 days 04:05:06.5`, the time is left out when it is zero.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let fn_unit = |value: i32, unit: &str| {
+            format!("{} {}{}", value, unit, if value == 1 { "" } else { "s" })
+        };
+        let mut parts = Vec::new();
+
+        if self.months / 12 != 0 {
+            parts.push(fn_unit(self.months / 12, "year"));
+        }
+        if self.months % 12 != 0 {
+            parts.push(fn_unit(self.months % 12, "month"));
+        }
+        if self.days != 0 {
+            parts.push(fn_unit(self.days, "day"));
+        }
+        if self.micros != 0 || parts.is_empty() {
+            let sign = if self.micros < 0 { "-" } else { "" };
+            let micros = self.micros.unsigned_abs();
+            let seconds = micros / MICROS_PER_SECOND as u64;
+            let mut time = format!(
+                "{}{:02}:{:02}:{:02}",
+                sign,
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            );
+            let fraction = micros % MICROS_PER_SECOND as u64;
+
+            if fraction != 0 {
+                time.push_str(format!(".{:06}", fraction).trim_end_matches('0'));
+            }
+            parts.push(time);
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors::DatabaseError;
+    use crate::types::interval::Interval;
+    use chrono::NaiveDate;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_and_display() -> Result<(), DatabaseError> {
+        let interval = Interval::from_str("1 year 2 months 3 days 04:05:06.5")?;
+        assert_eq!(
+            (interval.months, interval.days, interval.micros),
+            (14, 3, 14_706_500_000)
+        );
+        assert_eq!(interval.to_string(), "1 year 2 months 3 days 04:05:06.5");
+
+        let interval = Interval::from_str("-1 Years 2 WEEKS 1.5 hours")?;
+        assert_eq!((interval.months, interval.days), (-12, 14));
+        assert_eq!(interval.to_string(), "-1 years 14 days 01:30:00");
+
+        assert_eq!(Interval::from_str("-00:00:01")?.to_string(), "-00:00:01");
+        assert_eq!(Interval::from_str("0 days")?.to_string(), "00:00:00");
+        assert_eq!(Interval::from_str("90 minutes")?.to_string(), "01:30:00");
+
+        assert!(Interval::from_str("").is_err());
+        assert!(Interval::from_str("1").is_err());
+        assert!(Interval::from_str("1 fortnight").is_err());
+        assert!(Interval::from_str("1.5 days").is_err());
+        assert!(Interval::from_str("01:60:00").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ordering() -> Result<(), DatabaseError> {
+        assert_eq!(
+            Interval::from_str("1 month")?,
+            Interval::from_str("30 days")?
+        );
+        assert!(Interval::from_str("1 day")? > Interval::from_str("23:59:59")?);
+        assert!(Interval::from_str("-1 day")? < Interval::from_str("0 days")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_to() -> Result<(), DatabaseError> {
+        let datetime = |y, m, d| {
+            NaiveDate::from_ymd_opt(y, m, d)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        };
+        let month = Interval::from_str("1 month")?;
+
+        // Tips: the day is clamped to the end of the month
+        assert_eq!(
+            month.add_to(datetime(2024, 1, 31)),
+            Some(datetime(2024, 2, 29))
+        );
+        assert_eq!(
+            month.add_to(datetime(2023, 1, 31)),
+            Some(datetime(2023, 2, 28))
+        );
+        assert_eq!(
+            month.checked_neg().unwrap().add_to(datetime(2024, 3, 31)),
+            Some(datetime(2024, 2, 29))
+        );
+        // and the months are added before the days
+        assert_eq!(
+            Interval::from_str("1 month 1 day")?.add_to(datetime(2024, 1, 31)),
+            Some(datetime(2024, 3, 1))
+        );
+        assert_eq!(
+            Interval::between(datetime(2024, 3, 2), datetime(2024, 3, 1)),
+            Some(Interval::new(0, 1, 0))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use crate::errors::DatabaseError;
+use serde_json::Value;
+
+/// Parses `text` as JSON and writes it back without whitespace and with the keys of each object
+/// sorted, so that equal JSON values are equal byte by byte, e.g. `{ "b": 1, "a": [1, 2] }` is
+/// `{"a":[1,2],"b":1}`.
+///
+/// The error of an invalid JSON tells the line and the column where it is found.
+pub fn normalize(text: &str) -> Result<String, DatabaseError> {
+    Ok(parse(text)?.to_string())
+}
+
+pub(crate) fn parse(text: &str) -> Result<Value, DatabaseError> {
+    serde_json::from_str(text).map_err(|err| DatabaseError::InvalidJson(err.to_string()))
+}
+
+/// A step of a JSON path, see [`parse_path`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PathStep {
+    Key(String),
+    /// counted from the end of the array if negative
+    Index(i64),
+}
+
+impl PathStep {
+    pub(crate) fn get<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        match (self, value) {
+            (PathStep::Key(key), Value::Object(object)) => object.get(key),
+            (PathStep::Index(i), Value::Array(array)) => {
+                let i = if *i < 0 {
+                    array.len().checked_sub(i.unsigned_abs() as usize)?
+                } else {
+                    *i as usize
+                };
+                array.get(i)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parses a path like `$.a.b[0]` or `$."a b"[1]`, which starts with `$` for the whole value and
+/// goes on with `.key`, `."key"` or `[index]`.
+pub(crate) fn parse_path(path: &str) -> Result<Vec<PathStep>, DatabaseError> {
+    let invalid = || DatabaseError::InvalidParameter("JSON path".to_string(), path.to_string());
+    let Some(mut rest) = path.trim().strip_prefix('$') else {
+        return Err(invalid());
+    };
+    let mut steps = Vec::new();
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            if let Some(quoted) = after.strip_prefix('"') {
+                let end = quoted.find('"').ok_or_else(invalid)?;
+                steps.push(PathStep::Key(quoted[..end].to_string()));
+                rest = &quoted[end + 1..];
+            } else {
+                let end = after.find(['.', '[']).unwrap_or(after.len());
+                if end == 0 {
+                    return Err(invalid());
+                }
+                steps.push(PathStep::Key(after[..end].to_string()));
+                rest = &after[end..];
+            }
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').ok_or_else(invalid)?;
+            let i = after[..end].trim().parse().map_err(|_| invalid())?;
+            steps.push(PathStep::Index(i));
+            rest = &after[end + 1..];
+        } else {
+            return Err(invalid());
+        }
+    }
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors::DatabaseError;
+    use crate::types::json::{normalize, parse_path, PathStep};
+
+    #[test]
+    fn test_normalize() -> Result<(), DatabaseError> {
+        assert_eq!(
+            normalize(" { \"b\" : 1, \"a\" : [1, 2.5, null, \"x\"] } ")?,
+            r#"{"a":[1,2.5,null,"x"],"b":1}"#
+        );
+        assert_eq!(normalize("\"text\"")?, "\"text\"");
+
+        let err = normalize("{\"a\": 1,\n \"b\" 2}").unwrap_err();
+        assert!(err.to_string().contains("line 2 column 6"), "{}", err);
+        assert!(matches!(normalize(""), Err(DatabaseError::InvalidJson(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_path() -> Result<(), DatabaseError> {
+        assert_eq!(parse_path("$")?, vec![]);
+        assert_eq!(
+            parse_path("$.a.b[0].\"c d\"[-1]")?,
+            vec![
+                PathStep::Key("a".to_string()),
+                PathStep::Key("b".to_string()),
+                PathStep::Index(0),
+                PathStep::Key("c d".to_string()),
+                PathStep::Index(-1),
+            ]
+        );
+        for path in ["a", "$.", "$..a", "$[a]", "$[0", "$.\"a", "$a"] {
+            assert!(parse_path(path).is_err(), "{}", path);
+        }
+
+        Ok(())
+    }
+}
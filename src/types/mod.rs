@@ -1,26 +1,41 @@
+pub mod array;
+pub mod bytea;
 pub mod evaluator;
 pub mod index;
+pub mod interval;
+pub mod json;
+pub mod timestamp_tz;
 pub mod tuple;
 pub mod tuple_builder;
+pub mod uuid;
 pub mod value;
 
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use itertools::Itertools;
 use rust_decimal::Decimal;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::any::TypeId;
-use std::cmp;
+use std::sync::Arc;
+use std::{cmp, fmt};
 
 use crate::errors::DatabaseError;
+use crate::expression::BinaryOperator;
+use crate::parser::parse_struct_fields;
 use sqlparser::ast::{CharLengthUnits, ExactNumberInfo, TimezoneInfo};
 use strum_macros::AsRefStr;
 
 pub type ColumnId = u32;
 
+/// The max precision of `rust_decimal::Decimal`, of which any value of 28 digits fits.
+pub const MAX_DECIMAL_PRECISION: u8 = 28;
+/// The max scale of `rust_decimal::Decimal`.
+pub const MAX_DECIMAL_SCALE: u8 = 28;
+/// The scale added to the larger scale of the operands for the result of a `DECIMAL` division.
+pub const DIVIDE_DECIMAL_SCALE_INCREMENT: u8 = 4;
+
 /// Sqlrs type conversion:
 /// sqlparser::ast::DataType -> LogicalType -> arrow::datatypes::DataType
-#[derive(
-    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, AsRefStr, Serialize, Deserialize,
-)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, AsRefStr, Serialize, Deserialize)]
 pub enum LogicalType {
     Invalid,
     SqlNull,
@@ -39,10 +54,98 @@ pub enum LogicalType {
     Varchar(Option<u32>, CharLengthUnits),
     Date,
     DateTime,
+    // the microseconds since UNIX epoch in UTC, see `DataValue::TimestampTz`
+    TimestampTz,
     Time,
+    // interval (months, days, microseconds)
+    Interval,
     // decimal (precision, scale)
     Decimal(Option<u8>, Option<u8>),
+    // stored as the normalized text, see `json::normalize`
+    Json,
+    Uuid,
+    Bytea,
     Tuple,
+    Struct(StructType),
+    Array(ArrayType),
+}
+
+/// The named fields of a struct, ordered as declared.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StructType(Arc<[(String, LogicalType)]>);
+
+impl StructType {
+    pub fn new(fields: Vec<(String, LogicalType)>) -> Self {
+        StructType(fields.into())
+    }
+
+    pub fn fields(&self) -> &[(String, LogicalType)] {
+        &self.0
+    }
+
+    /// The position and the type of the field `name`.
+    pub fn field(&self, name: &str) -> Option<(usize, &LogicalType)> {
+        self.0
+            .iter()
+            .find_position(|(field_name, _)| field_name == name)
+            .map(|(i, (_, ty))| (i, ty))
+    }
+}
+
+impl Serialize for StructType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.fields().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StructType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(StructType::new(Vec::deserialize(deserializer)?))
+    }
+}
+
+impl fmt::Display for StructType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields = self
+            .0
+            .iter()
+            .map(|(name, ty)| format!("{} {}", name, ty))
+            .join(", ");
+
+        write!(f, "STRUCT({})", fields)
+    }
+}
+
+/// The type of the elements of a one-dimensional array.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ArrayType(Arc<LogicalType>);
+
+impl ArrayType {
+    pub fn new(element_type: LogicalType) -> Self {
+        ArrayType(Arc::new(element_type))
+    }
+
+    pub fn element_type(&self) -> &LogicalType {
+        &self.0
+    }
+}
+
+impl Serialize for ArrayType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.element_type().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ArrayType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(ArrayType::new(LogicalType::deserialize(deserializer)?))
+    }
+}
+
+impl fmt::Display for ArrayType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[]", self.0)
+    }
 }
 
 impl LogicalType {
@@ -107,7 +210,14 @@ impl LogicalType {
             LogicalType::Decimal(_, _) => Some(16),
             LogicalType::Date => Some(4),
             LogicalType::DateTime => Some(8),
+            LogicalType::TimestampTz => Some(8),
             LogicalType::Time => Some(4),
+            LogicalType::Interval => Some(16),
+            LogicalType::Json => None,
+            LogicalType::Uuid => Some(16),
+            LogicalType::Bytea => None,
+            LogicalType::Struct(_) => None,
+            LogicalType::Array(_) => None,
             LogicalType::Invalid | LogicalType::Tuple => unreachable!(),
         }
     }
@@ -167,22 +277,124 @@ impl LogicalType {
         matches!(self, LogicalType::Float | LogicalType::Double)
     }
 
+    /// Whether the values of the type can be sorted, and so be the keys of an index.
+    pub fn is_orderable(&self) -> bool {
+        !matches!(self, LogicalType::Json | LogicalType::Array(_))
+    }
+
+    /// The precision and scale of the numbers of an integer or decimal type, `None` if they are
+    /// not limited.
+    fn decimal_precision_scale(&self) -> Option<(Option<u8>, Option<u8>)> {
+        Some(match self {
+            LogicalType::Tinyint | LogicalType::UTinyint => (Some(3), Some(0)),
+            LogicalType::Smallint | LogicalType::USmallint => (Some(5), Some(0)),
+            LogicalType::Integer | LogicalType::UInteger => (Some(10), Some(0)),
+            LogicalType::Bigint => (Some(19), Some(0)),
+            LogicalType::UBigint => (Some(20), Some(0)),
+            LogicalType::Decimal(precision, scale) => (*precision, *scale),
+            _ => return None,
+        })
+    }
+
+    /// The type of `left op right` for an arithmetic `op` with a decimal on either side, or
+    /// `None` if there is no decimal or the other side is not a number.
+    ///
+    /// - `+` and `-` keep the larger scale, and may need a digit more before the point
+    /// - `*` adds the scales, and the digits before the point
+    /// - `/` adds [`DIVIDE_DECIMAL_SCALE_INCREMENT`] to the larger scale
+    /// - `%` keeps the larger scale
+    ///
+    /// An integer is a decimal of scale 0, e.g. `INTEGER` is `DECIMAL(10, 0)`, and a float makes
+    /// the result a `DOUBLE`. The precision and scale are at most [`MAX_DECIMAL_PRECISION`] and
+    /// [`MAX_DECIMAL_SCALE`], and not limited if either side is not.
+    pub fn decimal_arithmetic_type(
+        op: &BinaryOperator,
+        left: &LogicalType,
+        right: &LogicalType,
+    ) -> Option<LogicalType> {
+        if !matches!(
+            op,
+            BinaryOperator::Plus
+                | BinaryOperator::Minus
+                | BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Modulo
+        ) || !matches!(
+            (left, right),
+            (LogicalType::Decimal(..), _) | (_, LogicalType::Decimal(..))
+        ) {
+            return None;
+        }
+        if left.is_floating_point_numeric() || right.is_floating_point_numeric() {
+            return Some(LogicalType::Double);
+        }
+        let ((left_precision, left_scale), (right_precision, right_scale)) = (
+            left.decimal_precision_scale()?,
+            right.decimal_precision_scale()?,
+        );
+        let (Some(left_scale), Some(right_scale)) = (left_scale, right_scale) else {
+            return Some(LogicalType::Decimal(None, None));
+        };
+        // the digits before the point
+        let integer_digits =
+            left_precision
+                .zip(right_precision)
+                .map(|(left_precision, right_precision)| {
+                    (
+                        left_precision.saturating_sub(left_scale) as u32,
+                        right_precision.saturating_sub(right_scale) as u32,
+                    )
+                });
+        let (left_scale, right_scale) = (left_scale as u32, right_scale as u32);
+        let (integer_digits, scale) = match op {
+            BinaryOperator::Plus | BinaryOperator::Minus => (
+                integer_digits.map(|(left, right)| cmp::max(left, right) + 1),
+                cmp::max(left_scale, right_scale),
+            ),
+            BinaryOperator::Multiply => (
+                integer_digits.map(|(left, right)| left + right),
+                left_scale + right_scale,
+            ),
+            // Tips: the quotient is the largest when divided by the smallest unit of the scale
+            BinaryOperator::Divide => (
+                integer_digits.map(|(left, _)| left + right_scale),
+                cmp::max(left_scale, right_scale) + DIVIDE_DECIMAL_SCALE_INCREMENT as u32,
+            ),
+            _ => (
+                integer_digits.map(|(left, right)| cmp::min(left, right)),
+                cmp::max(left_scale, right_scale),
+            ),
+        };
+        let scale = cmp::min(scale, MAX_DECIMAL_SCALE as u32);
+        let precision = integer_digits
+            .map(|digits| cmp::min(digits + scale, MAX_DECIMAL_PRECISION as u32) as u8);
+
+        Some(LogicalType::Decimal(precision, Some(scale as u8)))
+    }
+
     pub fn max_logical_type(
         left: &LogicalType,
         right: &LogicalType,
     ) -> Result<LogicalType, DatabaseError> {
         if left == right {
-            return Ok(*left);
+            return Ok(left.clone());
         }
         match (left, right) {
             // SqlNull type can be cast to anything
-            (LogicalType::SqlNull, _) => return Ok(*right),
-            (_, LogicalType::SqlNull) => return Ok(*left),
+            (LogicalType::SqlNull, _) => return Ok(right.clone()),
+            (_, LogicalType::SqlNull) => return Ok(left.clone()),
             _ => {}
         }
         if left.is_numeric() && right.is_numeric() {
             return LogicalType::combine_numeric_types(left, right);
         }
+        // e.g. `struct_column = (1, 'a')`
+        if let (LogicalType::Struct(_), LogicalType::Tuple) = (left, right) {
+            return Ok(left.clone());
+        }
+        if let (LogicalType::Tuple, LogicalType::Struct(_)) = (left, right) {
+            return Ok(right.clone());
+        }
         if matches!(
             (left, right),
             (LogicalType::Date, LogicalType::Varchar(..))
@@ -203,6 +415,19 @@ impl LogicalType {
         ) {
             return Ok(LogicalType::DateTime);
         }
+        // Tips: the timestamps without time zone are taken at the time zone of the session
+        if matches!(
+            (left, right),
+            (
+                LogicalType::TimestampTz,
+                LogicalType::Date | LogicalType::DateTime | LogicalType::Varchar(..)
+            ) | (
+                LogicalType::Date | LogicalType::DateTime | LogicalType::Varchar(..),
+                LogicalType::TimestampTz
+            )
+        ) {
+            return Ok(LogicalType::TimestampTz);
+        }
         if let (LogicalType::Char(..), LogicalType::Varchar(..))
         | (LogicalType::Varchar(..), LogicalType::Char(..))
         | (LogicalType::Char(..), LogicalType::Char(..))
@@ -210,7 +435,7 @@ impl LogicalType {
         {
             return Ok(LogicalType::Varchar(None, CharLengthUnits::Characters));
         }
-        Err(DatabaseError::Incomparable(*left, *right))
+        Err(DatabaseError::Incomparable(left.clone(), right.clone()))
     }
 
     fn combine_numeric_types(
@@ -218,7 +443,7 @@ impl LogicalType {
         right: &LogicalType,
     ) -> Result<LogicalType, DatabaseError> {
         if left == right {
-            return Ok(*left);
+            return Ok(left.clone());
         }
         if left.is_signed_numeric() && right.is_unsigned_numeric() {
             // this method is symmetric
@@ -228,22 +453,48 @@ impl LogicalType {
         }
 
         if LogicalType::can_implicit_cast(left, right) {
-            return Ok(*right);
+            return Ok(right.clone());
         }
         if LogicalType::can_implicit_cast(right, left) {
-            return Ok(*left);
+            return Ok(left.clone());
         }
         // we can't cast implicitly either way and types are not equal
-        // this happens when left is signed and right is unsigned
-        // e.g. INTEGER and UINTEGER
+        // this happens when left is unsigned and right is signed
+        // e.g. UINTEGER and INTEGER
         // in this case we need to upcast to make sure the types fit
         match (left, right) {
-            (LogicalType::Bigint, _) | (_, LogicalType::UBigint) => Ok(LogicalType::Double),
-            (LogicalType::Integer, _) | (_, LogicalType::UInteger) => Ok(LogicalType::Bigint),
-            (LogicalType::Smallint, _) | (_, LogicalType::USmallint) => Ok(LogicalType::Integer),
-            (LogicalType::Tinyint, _) | (_, LogicalType::UTinyint) => Ok(LogicalType::Smallint),
-            _ => Err(DatabaseError::Incomparable(*left, *right)),
+            (LogicalType::UBigint, _) | (_, LogicalType::Bigint) => Ok(LogicalType::Double),
+            (LogicalType::UInteger, _) | (_, LogicalType::Integer) => Ok(LogicalType::Bigint),
+            (LogicalType::USmallint, _) | (_, LogicalType::Smallint) => Ok(LogicalType::Integer),
+            (LogicalType::UTinyint, _) | (_, LogicalType::Tinyint) => Ok(LogicalType::Smallint),
+            _ => Err(DatabaseError::Incomparable(left.clone(), right.clone())),
+        }
+    }
+
+    /// Whether a value of type `from` can be stored in a column of type `to`, numbers and strings
+    /// are cast to any type of their kind, which fails on the values out of range or too long,
+    /// strings are parsed into JSON, UUIDs, bytes and arrays, and arrays are cast element by
+    /// element.
+    pub fn can_assign(from: &LogicalType, to: &LogicalType) -> bool {
+        if let (LogicalType::Array(from), LogicalType::Array(to)) = (from, to) {
+            return LogicalType::can_assign(from.element_type(), to.element_type());
         }
+        let is_number =
+            |ty: &LogicalType| ty.is_numeric() || matches!(ty, LogicalType::Decimal(..));
+        let is_string =
+            |ty: &LogicalType| matches!(ty, LogicalType::Char(..) | LogicalType::Varchar(..));
+
+        LogicalType::can_implicit_cast(from, to)
+            || (is_number(from) && is_number(to))
+            || (is_string(from)
+                && (is_string(to)
+                    || matches!(
+                        to,
+                        LogicalType::Json
+                            | LogicalType::Uuid
+                            | LogicalType::Bytea
+                            | LogicalType::Array(_)
+                    )))
     }
 
     pub fn can_implicit_cast(from: &LogicalType, to: &LogicalType) -> bool {
@@ -308,19 +559,35 @@ impl LogicalType {
             LogicalType::Varchar(..) => false,
             LogicalType::Date => matches!(
                 to,
-                LogicalType::DateTime | LogicalType::Varchar(..) | LogicalType::Char(..)
+                LogicalType::DateTime
+                    | LogicalType::TimestampTz
+                    | LogicalType::Varchar(..)
+                    | LogicalType::Char(..)
             ),
             LogicalType::DateTime => matches!(
                 to,
                 LogicalType::Date
+                    | LogicalType::TimestampTz
+                    | LogicalType::Time
+                    | LogicalType::Varchar(..)
+                    | LogicalType::Char(..)
+            ),
+            LogicalType::TimestampTz => matches!(
+                to,
+                LogicalType::Date
+                    | LogicalType::DateTime
                     | LogicalType::Time
                     | LogicalType::Varchar(..)
                     | LogicalType::Char(..)
             ),
-            LogicalType::Time => {
+            LogicalType::Time | LogicalType::Interval | LogicalType::Json | LogicalType::Uuid => {
                 matches!(to, LogicalType::Varchar(..) | LogicalType::Char(..))
             }
-            LogicalType::Decimal(_, _) | LogicalType::Tuple => false,
+            LogicalType::Decimal(_, _)
+            | LogicalType::Bytea
+            | LogicalType::Tuple
+            | LogicalType::Struct(_)
+            | LogicalType::Array(_) => false,
         }
     }
 }
@@ -386,6 +653,17 @@ impl TryFrom<sqlparser::ast::DataType> for LogicalType {
                 }
                 Ok(LogicalType::DateTime)
             }
+            sqlparser::ast::DataType::Timestamp(precision, info) => {
+                if precision.is_some() {
+                    return Err(DatabaseError::UnsupportedStmt(
+                        "timestamp's precision".to_string(),
+                    ));
+                }
+                match info {
+                    TimezoneInfo::None | TimezoneInfo::WithoutTimeZone => Ok(LogicalType::DateTime),
+                    TimezoneInfo::WithTimeZone | TimezoneInfo::Tz => Ok(LogicalType::TimestampTz),
+                }
+            }
             sqlparser::ast::DataType::Time(precision, info) => {
                 if precision.is_some() {
                     return Err(DatabaseError::UnsupportedStmt(
@@ -399,6 +677,12 @@ impl TryFrom<sqlparser::ast::DataType> for LogicalType {
                 }
                 Ok(LogicalType::Time)
             }
+            sqlparser::ast::DataType::Interval => Ok(LogicalType::Interval),
+            sqlparser::ast::DataType::JSON => Ok(LogicalType::Json),
+            sqlparser::ast::DataType::Uuid => Ok(LogicalType::Uuid),
+            sqlparser::ast::DataType::Bytea | sqlparser::ast::DataType::Blob(_) => {
+                Ok(LogicalType::Bytea)
+            }
             sqlparser::ast::DataType::Decimal(info) | sqlparser::ast::DataType::Dec(info) => {
                 match info {
                     ExactNumberInfo::None => Ok(Self::Decimal(None, None)),
@@ -408,6 +692,30 @@ impl TryFrom<sqlparser::ast::DataType> for LogicalType {
                     }
                 }
             }
+            // `STRUCT(name type, ...)`, see `parser::quote_struct_fields`
+            sqlparser::ast::DataType::Custom(name, modifiers)
+                if name.to_string().eq_ignore_ascii_case("struct") && modifiers.len() == 1 =>
+            {
+                let mut fields = Vec::new();
+
+                for (name, data_type) in parse_struct_fields(&modifiers[0])? {
+                    let name = name.value.to_lowercase();
+                    if fields.iter().any(|(field_name, _)| field_name == &name) {
+                        return Err(DatabaseError::DuplicateColumn(name));
+                    }
+                    fields.push((name, LogicalType::try_from(data_type)?));
+                }
+                Ok(LogicalType::Struct(StructType::new(fields)))
+            }
+            // e.g. `VARCHAR[]`
+            sqlparser::ast::DataType::Array(Some(element_type)) => {
+                match LogicalType::try_from(*element_type)? {
+                    LogicalType::Array(_) => Err(DatabaseError::UnsupportedStmt(
+                        "multidimensional arrays".to_string(),
+                    )),
+                    element_type => Ok(LogicalType::Array(ArrayType::new(element_type))),
+                }
+            }
             other => Err(DatabaseError::UnsupportedStmt(other.to_string())),
         }
     }
@@ -415,6 +723,10 @@ impl TryFrom<sqlparser::ast::DataType> for LogicalType {
 
 impl std::fmt::Display for LogicalType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.as_ref().to_uppercase())
+        match self {
+            LogicalType::Struct(struct_type) => write!(f, "{}", struct_type),
+            LogicalType::Array(array_type) => write!(f, "{}", array_type),
+            _ => write!(f, "{}", self.as_ref().to_uppercase()),
+        }
     }
 }
@@ -0,0 +1,172 @@
+use crate::errors::DatabaseError;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime};
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+
+/// Parses a time zone given as an offset from UTC such as `+08`, `+0530`, `-05:30` or `UTC`.
+pub fn parse_time_zone(text: &str) -> Result<FixedOffset, DatabaseError> {
+    let invalid = || DatabaseError::InvalidTimeZone(text.to_string());
+    let text = text.trim();
+
+    if ["utc", "gmt", "z"]
+        .iter()
+        .any(|name| text.eq_ignore_ascii_case(name))
+    {
+        return Ok(FixedOffset::east_opt(0).unwrap());
+    }
+    let (sign, digits) = match text.as_bytes().first() {
+        Some(b'+') => (1, &text[1..]),
+        Some(b'-') => (-1, &text[1..]),
+        _ => return Err(invalid()),
+    };
+    let digits = digits.replace(':', "");
+    if !matches!(digits.len(), 1 | 2 | 4) || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let (hours, minutes) = digits.split_at(digits.len().min(2));
+    let hours: i32 = hours.parse().map_err(|_| invalid())?;
+    let minutes: i32 = if minutes.is_empty() {
+        0
+    } else {
+        minutes.parse().map_err(|_| invalid())?
+    };
+    if minutes >= 60 {
+        return Err(invalid());
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(invalid)
+}
+
+/// Writes the offset of a time zone like `+08`, or like `-05:30` if it has minutes.
+pub fn format_time_zone(offset: i32) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let (hours, minutes) = (offset.abs() / 3600, offset.abs() % 3600 / 60);
+
+    if minutes == 0 {
+        format!("{}{:02}", sign, hours)
+    } else {
+        format!("{}{:02}:{:02}", sign, hours, minutes)
+    }
+}
+
+/// The microseconds since UNIX epoch of `date_time` at the time zone of `offset`.
+pub fn from_local(date_time: NaiveDateTime, offset: i32) -> i64 {
+    date_time.and_utc().timestamp_micros() - offset as i64 * MICROS_PER_SECOND
+}
+
+/// The date and time at the time zone of `offset` of the microseconds since UNIX epoch.
+pub fn to_local(micros: i64, offset: i32) -> Option<NaiveDateTime> {
+    let micros = micros.checked_add(offset as i64 * MICROS_PER_SECOND)?;
+
+    DateTime::from_timestamp_micros(micros).map(|date_time| date_time.naive_utc())
+}
+
+/// Parses a timestamp such as `2024-05-01 12:00:00+08` into the microseconds since UNIX epoch,
+/// the timestamp without an offset is taken at `time_zone`.
+pub fn parse(text: &str, time_zone: FixedOffset) -> Result<i64, DatabaseError> {
+    let text = text.trim();
+    // Tips: the offset is searched after the date, whose `-`s are not signs
+    let split = text
+        .find([' ', 'T', 't'])
+        .and_then(|start| {
+            text[start + 1..]
+                .find(|c: char| matches!(c, '+' | '-') || c.is_ascii_alphabetic())
+                .map(|i| start + 1 + i)
+        })
+        .unwrap_or(text.len());
+    let (date_time, zone) = text.split_at(split);
+    let offset = if zone.trim().is_empty() {
+        time_zone
+    } else {
+        parse_time_zone(zone)?
+    };
+    let date_time = date_time.trim().replacen(['T', 't'], " ", 1);
+    let date_time = NaiveDateTime::parse_from_str(&date_time, "%Y-%m-%d %H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(&date_time, "%Y-%m-%d %H:%M"))
+        .or_else(|_| {
+            NaiveDate::parse_from_str(&date_time, "%Y-%m-%d")
+                .map(|date| date.and_hms_opt(0, 0, 0).unwrap())
+        })?;
+
+    Ok(from_local(date_time, offset.local_minus_utc()))
+}
+
+/// Writes the microseconds since UNIX epoch at the time zone of `offset`, e.g.
+/// `2024-05-01 12:00:00+08`, with the fractions of the second if there are any.
+pub fn format(micros: i64, offset: i32) -> Option<String> {
+    to_local(micros, offset).map(|date_time| {
+        format!(
+            "{}{}",
+            date_time.format("%Y-%m-%d %H:%M:%S%.f"),
+            format_time_zone(offset)
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors::DatabaseError;
+    use crate::types::timestamp_tz::{format, format_time_zone, parse, parse_time_zone};
+    use chrono::FixedOffset;
+
+    #[test]
+    fn test_time_zone() -> Result<(), DatabaseError> {
+        for (text, offset) in [
+            ("UTC", 0),
+            ("z", 0),
+            ("+08", 8 * 3600),
+            ("+8", 8 * 3600),
+            ("-05:30", -(5 * 3600 + 30 * 60)),
+            ("+0530", 5 * 3600 + 30 * 60),
+        ] {
+            assert_eq!(parse_time_zone(text)?.local_minus_utc(), offset, "{}", text);
+        }
+        for text in ["", "08", "+123", "+08:60", "+25", "Asia/Shanghai"] {
+            assert!(
+                matches!(
+                    parse_time_zone(text),
+                    Err(DatabaseError::InvalidTimeZone(_))
+                ),
+                "{}",
+                text
+            );
+        }
+        assert_eq!(format_time_zone(0), "+00");
+        assert_eq!(format_time_zone(8 * 3600), "+08");
+        assert_eq!(format_time_zone(-(5 * 3600 + 30 * 60)), "-05:30");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_and_format() -> Result<(), DatabaseError> {
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let east_8 = FixedOffset::east_opt(8 * 3600).unwrap();
+        let micros = parse("2024-05-01 12:00:00+08", utc)?;
+
+        assert_eq!(micros, parse("2024-05-01 04:00:00", utc)?);
+        assert_eq!(micros, parse("2024-05-01T04:00:00Z", east_8)?);
+        assert_eq!(micros, parse("2024-05-01 12:00:00", east_8)?);
+        assert_eq!(micros, parse("2024-05-01 01:00:00 -03:00", east_8)?);
+        assert_eq!(
+            parse("2024-05-01", east_8)?,
+            parse("2024-04-30 16:00", utc)?
+        );
+
+        assert_eq!(format(micros, 0).unwrap(), "2024-05-01 04:00:00+00");
+        assert_eq!(
+            format(micros + 500_000, 8 * 3600).unwrap(),
+            "2024-05-01 12:00:00.500+08"
+        );
+        assert_eq!(
+            format(micros, -(2 * 3600 + 30 * 60)).unwrap(),
+            "2024-05-01 01:30:00-02:30"
+        );
+
+        for text in ["", "2024-05-01 12:00:00+", "2024-13-01", "12:00:00"] {
+            assert!(parse(text, utc).is_err(), "{}", text);
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::catalog::ColumnRef;
+use crate::catalog::{ColumnCompression, ColumnRef};
 use crate::errors::DatabaseError;
 use crate::types::value::{DataValue, ValueRef};
 use crate::types::LogicalType;
@@ -18,13 +18,48 @@ lazy_static! {
 }
 
 const BITS_MAX_INDEX: usize = 8;
+/// The row format version is the number of columns the row was written with, so that the
+/// columns appended by `ALTER TABLE ADD COLUMN` afterwards are filled lazily when read, see
+/// [`FORMAT_VERSION`](crate::storage::table_codec::FORMAT_VERSION) of the storage.
+const FORMAT_VERSION_LEN: usize = 2;
+/// The bit of the length of a variable length value set if the value is compressed, see
+/// [`ColumnDesc::compression`](crate::catalog::ColumnDesc::compression).
+const COMPRESSED_BIT: u32 = 1 << 31;
 
 pub type TupleId = ValueRef;
 pub type Schema = Vec<ColumnRef>;
 pub type SchemaRef = Arc<Schema>;
 
 pub fn types(schema: &Schema) -> Vec<LogicalType> {
-    schema.iter().map(|column| *column.datatype()).collect_vec()
+    schema
+        .iter()
+        .map(|column| column.datatype().clone())
+        .collect_vec()
+}
+
+/// The id of the tuple with the values of its primary key, which are a `DataValue::Tuple` in the
+/// order of the key if the primary key is composite.
+pub fn tuple_id(mut keys: Vec<ValueRef>) -> TupleId {
+    debug_assert!(!keys.is_empty());
+
+    if keys.len() == 1 {
+        keys.pop().unwrap()
+    } else {
+        Arc::new(DataValue::Tuple(Some(keys)))
+    }
+}
+
+/// The id of the tuple with the `values` of all the columns of its table in `schema`.
+pub fn tuple_id_of(schema: &Schema, values: &[ValueRef]) -> Option<TupleId> {
+    let keys = schema
+        .iter()
+        .zip(values)
+        .filter_map(|(column, value)| Some((column.desc.primary?, value.clone())))
+        .sorted_by_key(|(i, _)| *i)
+        .map(|(_, value)| value)
+        .collect_vec();
+
+    (!keys.is_empty()).then(|| tuple_id(keys))
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -36,6 +71,7 @@ pub struct Tuple {
 impl Tuple {
     pub fn deserialize_from(
         table_types: &[LogicalType],
+        pk_indices: &[usize],
         projections: &[usize],
         schema: &Schema,
         bytes: &[u8],
@@ -49,20 +85,32 @@ impl Tuple {
 
         let values_len = schema.len();
         let mut tuple_values = Vec::with_capacity(values_len);
-        let bits_len = (values_len + BITS_MAX_INDEX) / BITS_MAX_INDEX;
-        let mut id_option = None;
+        let version = u16::decode_fixed(&bytes[0..FORMAT_VERSION_LEN]) as usize;
+        let bits_len = (version + BITS_MAX_INDEX) / BITS_MAX_INDEX;
+        let bits = &bytes[FORMAT_VERSION_LEN..FORMAT_VERSION_LEN + bits_len];
 
         let mut projection_i = 0;
-        let mut pos = bits_len;
+        let mut pos = FORMAT_VERSION_LEN + bits_len;
 
         for (i, logic_type) in table_types.iter().enumerate() {
             if projection_i >= values_len {
                 break;
             }
-            if is_none(bytes[i / BITS_MAX_INDEX], i % BITS_MAX_INDEX) {
+            if i >= version {
+                // the column is added after the row was written, see `ColumnDesc::added_default`
+                if projections[projection_i] == i {
+                    let value = schema[projection_i]
+                        .desc
+                        .added_default
+                        .clone()
+                        .unwrap_or_else(|| Arc::new(DataValue::none(logic_type)));
+                    tuple_values.push(value);
+                    projection_i += 1;
+                }
+            } else if is_none(bits[i / BITS_MAX_INDEX], i % BITS_MAX_INDEX) {
                 if projections[projection_i] == i {
                     tuple_values.push(Arc::new(DataValue::none(logic_type)));
-                    Self::values_push(schema, &tuple_values, &mut id_option, &mut projection_i);
+                    projection_i += 1;
                 }
             } else if let Some(len) = logic_type.raw_len() {
                 /// fixed length (e.g.: int)
@@ -71,46 +119,56 @@ impl Tuple {
                         &bytes[pos..pos + len],
                         logic_type,
                     )));
-                    Self::values_push(schema, &tuple_values, &mut id_option, &mut projection_i);
+                    projection_i += 1;
                 }
                 pos += len;
             } else {
                 /// variable length (e.g.: varchar)
-                let len = u32::decode_fixed(&bytes[pos..pos + 4]) as usize;
+                let len = u32::decode_fixed(&bytes[pos..pos + 4]);
+                let is_compressed = len & COMPRESSED_BIT != 0;
+                let len = (len & !COMPRESSED_BIT) as usize;
                 pos += 4;
                 if projections[projection_i] == i {
-                    tuple_values.push(Arc::new(DataValue::from_raw(
-                        &bytes[pos..pos + len],
-                        logic_type,
-                    )));
-                    Self::values_push(schema, &tuple_values, &mut id_option, &mut projection_i);
+                    let raw = &bytes[pos..pos + len];
+                    // Tips: the empty string is written as no bytes
+                    tuple_values.push(Arc::new(if len == 0 {
+                        DataValue::init(logic_type)
+                    } else if is_compressed {
+                        let raw = ColumnCompression::decompress(raw)
+                            .expect("the compressed value is corrupted");
+                        DataValue::from_raw(&raw, logic_type)
+                    } else {
+                        DataValue::from_raw(raw, logic_type)
+                    }));
+                    projection_i += 1;
                 }
                 pos += len;
             }
         }
 
+        // Tips: the tuple has an id only if all the columns of its primary key are read
+        let id = pk_indices
+            .iter()
+            .map(|pk_index| {
+                projections
+                    .binary_search(pk_index)
+                    .ok()
+                    .map(|i| tuple_values[i].clone())
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(tuple_id);
+
         Tuple {
-            id: id_option,
+            id,
             values: tuple_values,
         }
     }
 
-    fn values_push(
-        tuple_columns: &Schema,
-        tuple_values: &[ValueRef],
-        id_option: &mut Option<Arc<DataValue>>,
-        projection_i: &mut usize,
-    ) {
-        if tuple_columns[*projection_i].desc.is_primary {
-            let _ = id_option.replace(tuple_values[*projection_i].clone());
-        }
-        *projection_i += 1;
-    }
-
-    /// e.g.: bits(u8)..|data_0(len for utf8_1)|utf8_0|data_1|
-    /// Tips: all len is u32
-    pub fn serialize_to(&self, types: &[LogicalType]) -> Result<Vec<u8>, DatabaseError> {
-        debug_assert_eq!(self.values.len(), types.len());
+    /// e.g.: version(u16)|bits(u8)..|data_0(len for utf8_1)|utf8_0|data_1|
+    /// Tips: all len is u32, whose highest bit is set if the value is compressed, as the values
+    /// of the columns with a compression are if it makes them smaller
+    pub fn serialize_to(&self, columns: &[ColumnRef]) -> Result<Vec<u8>, DatabaseError> {
+        debug_assert_eq!(self.values.len(), columns.len());
 
         fn flip_bit(bits: u8, i: usize) -> u8 {
             bits | (1 << (7 - i))
@@ -118,19 +176,31 @@ impl Tuple {
 
         let values_len = self.values.len();
         let bits_len = (values_len + BITS_MAX_INDEX) / BITS_MAX_INDEX;
-        let mut bytes = vec![0_u8; bits_len];
+        let mut bytes = (values_len as u16).encode_fixed_vec();
+        bytes.resize(FORMAT_VERSION_LEN + bits_len, 0);
 
         for (i, value) in self.values.iter().enumerate() {
             if value.is_null() {
-                bytes[i / BITS_MAX_INDEX] = flip_bit(bytes[i / BITS_MAX_INDEX], i % BITS_MAX_INDEX);
+                let bits_i = FORMAT_VERSION_LEN + i / BITS_MAX_INDEX;
+                bytes[bits_i] = flip_bit(bytes[bits_i], i % BITS_MAX_INDEX);
             } else {
-                let logical_type = types[i];
+                let column = &columns[i];
                 let value_len = value.to_raw(&mut bytes)?;
 
-                if logical_type.raw_len().is_none() {
+                if column.datatype().raw_len().is_none() {
                     let index = bytes.len() - value_len;
+                    let mut len = value_len as u32;
+
+                    if let Some(compression) = column.desc.compression {
+                        let compressed = compression.compress(&bytes[index..]);
 
-                    bytes.splice(index..index, (value_len as u32).encode_fixed_vec());
+                        if compressed.len() < value_len {
+                            bytes.truncate(index);
+                            bytes.extend(compressed);
+                            len = (bytes.len() - index) as u32 | COMPRESSED_BIT;
+                        }
+                    }
+                    bytes.splice(index..index, len.encode_fixed_vec());
                 }
             }
         }
@@ -365,21 +435,22 @@ mod tests {
         ];
         let types = columns
             .iter()
-            .map(|column| *column.datatype())
+            .map(|column| column.datatype().clone())
             .collect_vec();
-        let columns = Arc::new(columns);
 
         let tuple_0 = Tuple::deserialize_from(
             &types,
+            &[0],
             &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
             &columns,
-            &tuples[0].serialize_to(&types).unwrap(),
+            &tuples[0].serialize_to(&columns).unwrap(),
         );
         let tuple_1 = Tuple::deserialize_from(
             &types,
+            &[0],
             &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
             &columns,
-            &tuples[1].serialize_to(&types).unwrap(),
+            &tuples[1].serialize_to(&columns).unwrap(),
         );
 
         debug_assert_eq!(tuples[0], tuple_0);
@@ -1,6 +1,7 @@
 use crate::errors::DatabaseError;
-use crate::types::tuple::{Schema, Tuple};
+use crate::types::tuple::{tuple_id_of, Schema, Tuple};
 use crate::types::value::{DataValue, Utf8Type};
+use crate::types::LogicalType;
 use sqlparser::ast::CharLengthUnits;
 use std::sync::Arc;
 
@@ -28,21 +29,25 @@ impl<'a> TupleBuilder<'a> {
         row: impl IntoIterator<Item = &'b str>,
     ) -> Result<Tuple, DatabaseError> {
         let mut values = Vec::with_capacity(self.schema.len());
-        let mut primary_key = None;
 
         for (i, value) in row.into_iter().enumerate() {
+            let ty = self.schema[i].datatype();
+            // Tips: `NULL` is exported as an empty field, which is an empty string in a string
+            // column
             let data_value = Arc::new(
-                DataValue::Utf8 {
-                    value: Some(value.to_string()),
-                    ty: Utf8Type::Variable(None),
-                    unit: CharLengthUnits::Characters,
-                }
-                .cast(self.schema[i].datatype())?,
+                if value.is_empty()
+                    && !matches!(ty, LogicalType::Char(..) | LogicalType::Varchar(..))
+                {
+                    DataValue::none(ty)
+                } else {
+                    DataValue::Utf8 {
+                        value: Some(value.to_string()),
+                        ty: Utf8Type::Variable(None),
+                        unit: CharLengthUnits::Characters,
+                    }
+                    .cast(ty)?
+                },
             );
-
-            if primary_key.is_none() && self.schema[i].desc.is_primary {
-                primary_key = Some(data_value.clone());
-            }
             values.push(data_value);
         }
         if values.len() != self.schema.len() {
@@ -50,7 +55,7 @@ impl<'a> TupleBuilder<'a> {
         }
 
         Ok(Tuple {
-            id: primary_key,
+            id: tuple_id_of(self.schema, &values),
             values,
         })
     }
@@ -0,0 +1,121 @@
+use crate::errors::DatabaseError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+/// A UUID, ordered by its bytes like the 128 bits unsigned integer in big endian it is kept as.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct Uuid(u128);
+
+impl Uuid {
+    pub fn from_u128(value: u128) -> Self {
+        Uuid(value)
+    }
+
+    pub fn as_u128(&self) -> u128 {
+        self.0
+    }
+
+    /// A random UUID of version 4.
+    pub fn new_v4() -> Self {
+        let value = rand::random::<u128>();
+
+        // Tips: the 4 bits of the version and the 2 bits of the variant `10`
+        Uuid((value & !(0xF << 76) & !(0x3 << 62)) | (0x4 << 76) | (0x2 << 62))
+    }
+}
+
+/// Parses the 32 hexadecimal digits of a UUID in any case, grouped by hyphens as
+/// `a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11` or not grouped at all.
+impl FromStr for Uuid {
+    type Err = DatabaseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DatabaseError::InvalidUuid(s.to_string());
+        let digits = match s.len() {
+            32 => s.to_string(),
+            36 => {
+                let bytes = s.as_bytes();
+                if [8, 13, 18, 23].iter().any(|i| bytes[*i] != b'-') {
+                    return Err(invalid());
+                }
+                s.replace('-', "")
+            }
+            _ => return Err(invalid()),
+        };
+        if digits.len() != 32 || !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        u128::from_str_radix(&digits, 16)
+            .map(Uuid)
+            .map_err(|_| invalid())
+    }
+}
+
+/// The canonical form of lowercase digits grouped by hyphens.
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let digits = format!("{:032x}", self.0);
+
+        write!(
+            f,
+            "{}-{}-{}-{}-{}",
+            &digits[0..8],
+            &digits[8..12],
+            &digits[12..16],
+            &digits[16..20],
+            &digits[20..32]
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors::DatabaseError;
+    use crate::types::uuid::Uuid;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_and_display() -> Result<(), DatabaseError> {
+        let uuid = Uuid::from_str("A0EEBC99-9C0B-4EF8-BB6D-6BB9BD380A11")?;
+
+        assert_eq!(uuid.as_u128(), 0xa0eebc999c0b4ef8bb6d6bb9bd380a11);
+        assert_eq!(uuid.to_string(), "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11");
+        assert_eq!(Uuid::from_str("a0eebc999c0b4ef8bb6d6bb9bd380a11")?, uuid);
+        assert_eq!(
+            Uuid::from_u128(1).to_string(),
+            "00000000-0000-0000-0000-000000000001"
+        );
+
+        for invalid in [
+            "",
+            "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a1",
+            "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a1g",
+            "a0eebc999-c0b-4ef8-bb6d-6bb9bd380a11",
+            "+0eebc999c0b4ef8bb6d6bb9bd380a11",
+        ] {
+            assert!(matches!(
+                Uuid::from_str(invalid),
+                Err(DatabaseError::InvalidUuid(_))
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_v4() {
+        let (uuid_1, uuid_2) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert_ne!(uuid_1, uuid_2);
+        for uuid in [uuid_1, uuid_2] {
+            let uuid = uuid.to_string();
+
+            assert_eq!(&uuid[14..15], "4");
+            assert!(matches!(&uuid[19..20], "8" | "9" | "a" | "b"));
+        }
+    }
+}
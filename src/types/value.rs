@@ -1,6 +1,7 @@
 use crate::errors::DatabaseError;
+use crate::execution::statement_time_zone;
 use chrono::format::{DelayedFormat, StrftimeItems};
-use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
 use integer_encoding::{FixedInt, FixedIntWriter};
 use lazy_static::lazy_static;
 use ordered_float::OrderedFloat;
@@ -12,11 +13,18 @@ use std::cmp::Ordering;
 use std::fmt::Formatter;
 use std::hash::Hash;
 use std::io::Write;
+use std::num::TryFromIntError;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::{cmp, fmt, mem};
 
-use super::LogicalType;
+use super::array;
+use super::bytea;
+use super::interval::Interval;
+use super::json;
+use super::timestamp_tz;
+use super::uuid::Uuid;
+use super::{ArrayType, LogicalType, StructType};
 
 lazy_static! {
     pub static ref NULL_VALUE: ValueRef = Arc::new(DataValue::Null);
@@ -62,9 +70,31 @@ pub enum DataValue {
     Date32(Option<i32>),
     /// Date stored as a signed 64bit int timestamp since UNIX epoch 1970-01-01
     Date64(Option<i64>),
+    /// An instant stored as the microseconds since UNIX epoch, which is displayed and converted
+    /// to the types without time zone at `offset`, the seconds east of UTC of the time zone of
+    /// the statement it is made in, see [`DataValue::timestamp_tz_of`]
+    TimestampTz {
+        value: Option<i64>,
+        offset: i32,
+    },
     Time(Option<u32>),
+    Interval(Option<Interval>),
     Decimal(Option<Decimal>),
+    /// The normalized text of a JSON value, see [`DataValue::json`]
+    Json(Option<String>),
+    Uuid(Option<Uuid>),
+    Bytea(Option<Vec<u8>>),
     Tuple(Option<Vec<ValueRef>>),
+    /// Field values ordered as the fields of `ty`
+    Struct {
+        values: Option<Vec<ValueRef>>,
+        ty: StructType,
+    },
+    /// The elements of a one-dimensional array, each of the element type of `ty`
+    Array {
+        values: Option<Vec<ValueRef>>,
+        ty: ArrayType,
+    },
 }
 
 macro_rules! generate_get_option {
@@ -95,7 +125,11 @@ generate_get_option!(DataValue,
     u16 : UInt16(Option<u16>),
     u32 : UInt32(Option<u32>),
     u64 : UInt64(Option<u64>),
-    decimal : Decimal(Option<Decimal>)
+    decimal : Decimal(Option<Decimal>),
+    interval : Interval(Option<Interval>),
+    json : Json(Option<String>),
+    uuid : Uuid(Option<Uuid>),
+    bytea : Bytea(Option<Vec<u8>>)
 );
 
 impl PartialEq for DataValue {
@@ -145,12 +179,26 @@ impl PartialEq for DataValue {
             (Date32(_), _) => false,
             (Date64(v1), Date64(v2)) => v1.eq(v2),
             (Date64(_), _) => false,
+            (TimestampTz { value: v1, .. }, TimestampTz { value: v2, .. }) => v1.eq(v2),
+            (TimestampTz { .. }, _) => false,
             (Time(v1), Time(v2)) => v1.eq(v2),
             (Time(_), _) => false,
+            (Interval(v1), Interval(v2)) => v1.eq(v2),
+            (Interval(_), _) => false,
             (Decimal(v1), Decimal(v2)) => v1.eq(v2),
             (Decimal(_), _) => false,
+            (Json(v1), Json(v2)) => v1.eq(v2),
+            (Json(_), _) => false,
+            (Uuid(v1), Uuid(v2)) => v1.eq(v2),
+            (Uuid(_), _) => false,
+            (Bytea(v1), Bytea(v2)) => v1.eq(v2),
+            (Bytea(_), _) => false,
             (Tuple(values_1), Tuple(values_2)) => values_1.eq(values_2),
             (Tuple(_), _) => false,
+            (Struct { values: v1, .. }, Struct { values: v2, .. }) => v1.eq(v2),
+            (Struct { .. }, _) => false,
+            (Array { values: v1, .. }, Array { values: v2, .. }) => v1.eq(v2),
+            (Array { .. }, _) => false,
         }
     }
 }
@@ -197,11 +245,26 @@ impl PartialOrd for DataValue {
             (Date32(_), _) => None,
             (Date64(v1), Date64(v2)) => v1.partial_cmp(v2),
             (Date64(_), _) => None,
+            // Tips: the instants are compared whatever their offsets are
+            (TimestampTz { value: v1, .. }, TimestampTz { value: v2, .. }) => v1.partial_cmp(v2),
+            (TimestampTz { .. }, _) => None,
             (Time(v1), Time(v2)) => v1.partial_cmp(v2),
             (Time(_), _) => None,
+            (Interval(v1), Interval(v2)) => v1.partial_cmp(v2),
+            (Interval(_), _) => None,
             (Decimal(v1), Decimal(v2)) => v1.partial_cmp(v2),
             (Decimal(_), _) => None,
+            // Tips: JSON values are not ordered
+            (Json(_), _) => None,
+            (Uuid(v1), Uuid(v2)) => v1.partial_cmp(v2),
+            (Uuid(_), _) => None,
+            (Bytea(v1), Bytea(v2)) => v1.partial_cmp(v2),
+            (Bytea(_), _) => None,
             (Tuple(_), _) => None,
+            (Struct { values: v1, .. }, Struct { values: v2, .. }) => v1.partial_cmp(v2),
+            (Struct { .. }, _) => None,
+            // Tips: arrays are not ordered
+            (Array { .. }, _) => None,
         }
     }
 }
@@ -239,9 +302,14 @@ impl Hash for DataValue {
             Null => 1.hash(state),
             Date32(v) => v.hash(state),
             Date64(v) => v.hash(state),
+            TimestampTz { value: v, .. } => v.hash(state),
             Time(v) => v.hash(state),
+            Interval(v) => v.hash(state),
             Decimal(v) => v.hash(state),
-            Tuple(values) => {
+            Json(v) => v.hash(state),
+            Uuid(v) => v.hash(state),
+            Bytea(v) => v.hash(state),
+            Tuple(values) | Struct { values, .. } | Array { values, .. } => {
                 for v in values {
                     v.hash(state)
                 }
@@ -253,7 +321,12 @@ macro_rules! varchar_cast {
     ($value:expr, $len:expr, $ty:expr, $unit:expr) => {
         $value
             .map(|v| {
-                let string_value = format!("{}", v);
+                let mut string_value = format!("{}", v);
+                // Tips: the trailing spaces of a `CHAR` are insignificant, so they are truncated
+                // rather than making the value too long
+                if matches!($ty, Utf8Type::Fixed(_)) {
+                    string_value.truncate(string_value.trim_end_matches(' ').len());
+                }
                 if let Some(len) = $len {
                     if Self::check_string_len(&string_value, *len as usize, $unit) {
                         return Err(DatabaseError::TooLong);
@@ -311,6 +384,59 @@ impl DataValue {
         }
     }
 
+    pub fn timestamp_tz(&self) -> Option<DateTime<FixedOffset>> {
+        if let DataValue::TimestampTz {
+            value: Some(val),
+            offset,
+        } = self
+        {
+            DateTime::from_timestamp_micros(*val)
+                .zip(FixedOffset::east_opt(*offset))
+                .map(|(date_time, offset)| date_time.with_timezone(&offset))
+        } else {
+            None
+        }
+    }
+
+    /// A timestamp with time zone of the microseconds since UNIX epoch, at the time zone of the
+    /// statement on this thread.
+    pub fn timestamp_tz_of(value: Option<i64>) -> DataValue {
+        DataValue::TimestampTz {
+            value,
+            offset: statement_time_zone().local_minus_utc(),
+        }
+    }
+
+    /// The value whose timestamps with time zone, also the ones nested in it, are displayed at
+    /// `time_zone` instead of the time zone they were made in, e.g. the results of a statement
+    /// rendered at the time zone of its session.
+    pub(crate) fn at_time_zone(value: &ValueRef, time_zone: FixedOffset) -> ValueRef {
+        let fn_values = |values: &Option<Vec<ValueRef>>| {
+            values.as_ref().map(|values| {
+                values
+                    .iter()
+                    .map(|value| DataValue::at_time_zone(value, time_zone))
+                    .collect()
+            })
+        };
+        match value.as_ref() {
+            DataValue::TimestampTz { value, .. } => Arc::new(DataValue::TimestampTz {
+                value: *value,
+                offset: time_zone.local_minus_utc(),
+            }),
+            DataValue::Tuple(values) => Arc::new(DataValue::Tuple(fn_values(values))),
+            DataValue::Struct { values, ty } => Arc::new(DataValue::Struct {
+                values: fn_values(values),
+                ty: ty.clone(),
+            }),
+            DataValue::Array { values, ty } => Arc::new(DataValue::Array {
+                values: fn_values(values),
+                ty: ty.clone(),
+            }),
+            _ => value.clone(),
+        }
+    }
+
     pub fn time(&self) -> Option<NaiveTime> {
         if let DataValue::Time(Some(val)) = self {
             NaiveTime::from_num_seconds_from_midnight_opt(*val, 0)
@@ -361,16 +487,22 @@ impl DataValue {
                     unit: CharLengthUnits::Octets,
                 },
             ) => Self::check_string_len(val, *len as usize, CharLengthUnits::Octets),
-            (LogicalType::Decimal(full_len, scale_len), DataValue::Decimal(Some(val))) => {
-                if let Some(len) = full_len {
-                    if val.mantissa().ilog10() + 1 > *len as u32 {
-                        return Err(DatabaseError::TooLong);
-                    }
-                }
-                if let Some(len) = scale_len {
-                    if val.scale() > *len as u32 {
-                        return Err(DatabaseError::TooLong);
-                    }
+            // Tips: the digits after the point are rounded to the scale, so only the digits
+            // before the point are limited, by the precision minus the scale
+            (LogicalType::Decimal(Some(precision), scale), DataValue::Decimal(Some(val))) => {
+                let mut decimal = *val;
+                Self::decimal_round(scale, &mut decimal);
+
+                let integer_digits = decimal
+                    .mantissa()
+                    .unsigned_abs()
+                    .checked_ilog10()
+                    .map_or(0, |digits| (digits + 1).saturating_sub(decimal.scale()));
+                if integer_digits > precision.saturating_sub(scale.unwrap_or(0)) as u32 {
+                    return Err(DatabaseError::ArithmeticOverflow(format!(
+                        "{} exceeds the precision {}",
+                        val, precision
+                    )));
                 }
                 false
             }
@@ -392,10 +524,40 @@ impl DataValue {
         value.and_then(|v| Self::date_time_format(v).map(|fmt| format!("{}", fmt)))
     }
 
+    fn format_timestamp_tz(value: Option<i64>, offset: i32) -> Option<String> {
+        value.and_then(|v| timestamp_tz::format(v, offset))
+    }
+
     fn format_time(value: Option<u32>) -> Option<String> {
         value.and_then(|v| Self::time_format(v).map(|fmt| format!("{}", fmt)))
     }
 
+    /// The memory taken by the value, with the heap memory of its text, bytes and elements.
+    pub(crate) fn memory_size(&self) -> usize {
+        let heap_size = match self {
+            DataValue::Utf8 {
+                value: Some(value), ..
+            }
+            | DataValue::Json(Some(value)) => value.len(),
+            DataValue::Bytea(Some(bytes)) => bytes.len(),
+            DataValue::Tuple(Some(values))
+            | DataValue::Struct {
+                values: Some(values),
+                ..
+            }
+            | DataValue::Array {
+                values: Some(values),
+                ..
+            } => values
+                .iter()
+                .map(|value| mem::size_of::<ValueRef>() + value.memory_size())
+                .sum(),
+            _ => 0,
+        };
+
+        mem::size_of::<DataValue>() + heap_size
+    }
+
     pub fn is_null(&self) -> bool {
         match self {
             DataValue::Null => true,
@@ -413,9 +575,16 @@ impl DataValue {
             DataValue::Utf8 { value, .. } => value.is_none(),
             DataValue::Date32(value) => value.is_none(),
             DataValue::Date64(value) => value.is_none(),
+            DataValue::TimestampTz { value, .. } => value.is_none(),
             DataValue::Time(value) => value.is_none(),
+            DataValue::Interval(value) => value.is_none(),
             DataValue::Decimal(value) => value.is_none(),
+            DataValue::Json(value) => value.is_none(),
+            DataValue::Uuid(value) => value.is_none(),
+            DataValue::Bytea(value) => value.is_none(),
             DataValue::Tuple(value) => value.is_none(),
+            DataValue::Struct { values, .. } => values.is_none(),
+            DataValue::Array { values, .. } => values.is_none(),
         }
     }
 
@@ -446,9 +615,22 @@ impl DataValue {
             },
             LogicalType::Date => DataValue::Date32(None),
             LogicalType::DateTime => DataValue::Date64(None),
+            LogicalType::TimestampTz => DataValue::timestamp_tz_of(None),
             LogicalType::Time => DataValue::Time(None),
+            LogicalType::Interval => DataValue::Interval(None),
             LogicalType::Decimal(_, _) => DataValue::Decimal(None),
+            LogicalType::Json => DataValue::Json(None),
+            LogicalType::Uuid => DataValue::Uuid(None),
+            LogicalType::Bytea => DataValue::Bytea(None),
             LogicalType::Tuple => DataValue::Tuple(None),
+            LogicalType::Struct(ty) => DataValue::Struct {
+                values: None,
+                ty: ty.clone(),
+            },
+            LogicalType::Array(ty) => DataValue::Array {
+                values: None,
+                ty: ty.clone(),
+            },
         }
     }
 
@@ -479,9 +661,27 @@ impl DataValue {
             },
             LogicalType::Date => DataValue::Date32(Some(UNIX_DATETIME.num_days_from_ce())),
             LogicalType::DateTime => DataValue::Date64(Some(UNIX_DATETIME.and_utc().timestamp())),
+            LogicalType::TimestampTz => DataValue::timestamp_tz_of(Some(0)),
             LogicalType::Time => DataValue::Time(Some(UNIX_TIME.num_seconds_from_midnight())),
+            LogicalType::Interval => DataValue::Interval(Some(Interval::default())),
             LogicalType::Decimal(_, _) => DataValue::Decimal(Some(Decimal::new(0, 0))),
+            LogicalType::Json => DataValue::Json(Some("null".to_string())),
+            LogicalType::Uuid => DataValue::Uuid(Some(Uuid::default())),
+            LogicalType::Bytea => DataValue::Bytea(Some(Vec::new())),
             LogicalType::Tuple => DataValue::Tuple(Some(vec![])),
+            LogicalType::Struct(ty) => DataValue::Struct {
+                values: Some(
+                    ty.fields()
+                        .iter()
+                        .map(|(_, ty)| Arc::new(DataValue::init(ty)))
+                        .collect(),
+                ),
+                ty: ty.clone(),
+            },
+            LogicalType::Array(ty) => DataValue::Array {
+                values: Some(vec![]),
+                ty: ty.clone(),
+            },
         }
     }
 
@@ -583,7 +783,7 @@ impl DataValue {
                     return Ok(writer.write_fixedint(*v)?);
                 }
             }
-            DataValue::Date64(v) => {
+            DataValue::Date64(v) | DataValue::TimestampTz { value: v, .. } => {
                 if let Some(v) = v {
                     return Ok(writer.write_fixedint(*v)?);
                 }
@@ -593,17 +793,138 @@ impl DataValue {
                     return Ok(writer.write_fixedint(*v)?);
                 }
             }
+            DataValue::Interval(v) => {
+                if let Some(v) = v {
+                    writer.write_fixedint(v.months)?;
+                    writer.write_fixedint(v.days)?;
+                    writer.write_fixedint(v.micros)?;
+                    return Ok(16);
+                }
+            }
             DataValue::Decimal(v) => {
                 if let Some(v) = v {
                     writer.write_all(&v.serialize())?;
                     return Ok(16);
                 }
             }
-            DataValue::Tuple(_) => unreachable!(),
+            DataValue::Json(v) => {
+                if let Some(v) = v {
+                    writer.write_all(v.as_bytes())?;
+                    return Ok(v.len());
+                }
+            }
+            DataValue::Uuid(v) => {
+                if let Some(v) = v {
+                    writer.write_all(&v.as_u128().to_be_bytes())?;
+                    return Ok(16);
+                }
+            }
+            DataValue::Bytea(v) => {
+                if let Some(v) = v {
+                    writer.write_all(v)?;
+                    return Ok(v.len());
+                }
+            }
+            // e.g. the id of a tuple with a composite primary key
+            DataValue::Tuple(Some(values)) => {
+                let mut bytes = Vec::new();
+
+                for value in values {
+                    Self::element_to_raw(value, &value.logical_type(), &mut bytes)?;
+                }
+                writer.write_all(&bytes)?;
+                return Ok(bytes.len());
+            }
+            DataValue::Tuple(None) => (),
+            DataValue::Struct {
+                values: Some(values),
+                ty,
+            } => {
+                let mut bytes = Vec::new();
+
+                for (value, (_, field_type)) in values.iter().zip(ty.fields()) {
+                    Self::element_to_raw(value, field_type, &mut bytes)?;
+                }
+                writer.write_all(&bytes)?;
+                return Ok(bytes.len());
+            }
+            DataValue::Struct { values: None, .. } => (),
+            DataValue::Array {
+                values: Some(values),
+                ty,
+            } => {
+                // e.g.: len(u32)|element|...
+                let mut bytes = (values.len() as u32).encode_fixed_vec();
+
+                for value in values {
+                    Self::element_to_raw(value, ty.element_type(), &mut bytes)?;
+                }
+                writer.write_all(&bytes)?;
+                return Ok(bytes.len());
+            }
+            DataValue::Array { values: None, .. } => (),
         }
         Ok(0)
     }
 
+    /// Writes a field of a struct or an element of an array,
+    /// e.g.: is_null(u8)|len(u32, only for the variable length)|data
+    fn element_to_raw(
+        value: &DataValue,
+        ty: &LogicalType,
+        bytes: &mut Vec<u8>,
+    ) -> Result<(), DatabaseError> {
+        if value.is_null() {
+            bytes.push(0u8);
+            return Ok(());
+        }
+        bytes.push(1u8);
+        let index = bytes.len();
+        let value_len = value.to_raw(bytes)?;
+
+        if ty.raw_len().is_none() {
+            bytes.splice(index..index, (value_len as u32).encode_fixed_vec());
+        }
+        Ok(())
+    }
+
+    /// Reads a value written by [`DataValue::element_to_raw`] at `pos`, and moves `pos` past it.
+    fn element_from_raw(bytes: &[u8], pos: &mut usize, ty: &LogicalType) -> ValueRef {
+        let is_null = bytes[*pos] == 0;
+        *pos += 1;
+
+        if is_null {
+            return Arc::new(DataValue::none(ty));
+        }
+        let len = match ty.raw_len() {
+            Some(len) => len,
+            None => {
+                let len = u32::decode_fixed(&bytes[*pos..*pos + 4]) as usize;
+                *pos += 4;
+                len
+            }
+        };
+        let value_bytes = &bytes[*pos..*pos + len];
+        *pos += len;
+        // Tips: the empty string is written as no bytes
+        Arc::new(if value_bytes.is_empty() {
+            DataValue::init(ty)
+        } else {
+            DataValue::from_raw(value_bytes, ty)
+        })
+    }
+
+    /// Reads a tuple written by [`DataValue::to_raw`], whose values are of `types`.
+    pub fn tuple_from_raw(bytes: &[u8], types: &[LogicalType]) -> Self {
+        let mut pos = 0;
+        let values = types
+            .iter()
+            .map(|ty| Self::element_from_raw(bytes, &mut pos, ty))
+            .collect();
+
+        DataValue::Tuple(Some(values))
+    }
+
     pub fn from_raw(bytes: &[u8], ty: &LogicalType) -> Self {
         match ty {
             LogicalType::Invalid => panic!("invalid logical type"),
@@ -673,14 +994,60 @@ impl DataValue {
             LogicalType::DateTime => {
                 DataValue::Date64((!bytes.is_empty()).then(|| i64::decode_fixed(bytes)))
             }
+            LogicalType::TimestampTz => {
+                DataValue::timestamp_tz_of((!bytes.is_empty()).then(|| i64::decode_fixed(bytes)))
+            }
             LogicalType::Time => {
                 DataValue::Time((!bytes.is_empty()).then(|| u32::decode_fixed(bytes)))
             }
+            LogicalType::Interval => DataValue::Interval((!bytes.is_empty()).then(|| {
+                Interval::new(
+                    i32::decode_fixed(&bytes[0..4]),
+                    i32::decode_fixed(&bytes[4..8]),
+                    i64::decode_fixed(&bytes[8..16]),
+                )
+            })),
             LogicalType::Decimal(_, _) => DataValue::Decimal(
                 (!bytes.is_empty())
                     .then(|| Decimal::deserialize(<[u8; 16]>::try_from(bytes).unwrap())),
             ),
+            LogicalType::Json => DataValue::Json(
+                (!bytes.is_empty()).then(|| String::from_utf8(bytes.to_owned()).unwrap()),
+            ),
+            LogicalType::Uuid => DataValue::Uuid((!bytes.is_empty()).then(|| {
+                Uuid::from_u128(u128::from_be_bytes(<[u8; 16]>::try_from(bytes).unwrap()))
+            })),
+            // Tips: NULL is never read, so no bytes are the empty bytes
+            LogicalType::Bytea => DataValue::Bytea(Some(bytes.to_vec())),
             LogicalType::Tuple => unreachable!(),
+            LogicalType::Struct(ty) => {
+                if bytes.is_empty() {
+                    return DataValue::none(&LogicalType::Struct(ty.clone()));
+                }
+                let mut pos = 0;
+                let values = ty
+                    .fields()
+                    .iter()
+                    .map(|(_, field_type)| Self::element_from_raw(bytes, &mut pos, field_type))
+                    .collect();
+
+                DataValue::Struct {
+                    values: Some(values),
+                    ty: ty.clone(),
+                }
+            }
+            LogicalType::Array(ty) => {
+                let len = u32::decode_fixed(&bytes[0..4]) as usize;
+                let mut pos = 4;
+                let values = (0..len)
+                    .map(|_| Self::element_from_raw(bytes, &mut pos, ty.element_type()))
+                    .collect();
+
+                DataValue::Array {
+                    values: Some(values),
+                    ty: ty.clone(),
+                }
+            }
         }
     }
 
@@ -710,9 +1077,16 @@ impl DataValue {
             } => LogicalType::Char(*len, *unit),
             DataValue::Date32(_) => LogicalType::Date,
             DataValue::Date64(_) => LogicalType::DateTime,
+            DataValue::TimestampTz { .. } => LogicalType::TimestampTz,
             DataValue::Time(_) => LogicalType::Time,
+            DataValue::Interval(_) => LogicalType::Interval,
             DataValue::Decimal(_) => LogicalType::Decimal(None, None),
+            DataValue::Json(_) => LogicalType::Json,
+            DataValue::Uuid(_) => LogicalType::Uuid,
+            DataValue::Bytea(_) => LogicalType::Bytea,
             DataValue::Tuple(_) => LogicalType::Tuple,
+            DataValue::Struct { ty, .. } => LogicalType::Struct(ty.clone()),
+            DataValue::Array { ty, .. } => LogicalType::Array(ty.clone()),
         }
     }
 
@@ -733,8 +1107,8 @@ impl DataValue {
     // Refer: https://github.com/facebook/mysql-5.6/wiki/MyRocks-record-format#memcomparable-format
     fn encode_bytes(b: &mut Vec<u8>, data: &[u8]) {
         let d_len = data.len();
-        let realloc_size = (d_len / ENCODE_GROUP_SIZE + 1) * (ENCODE_GROUP_SIZE + 1);
-        Self::realloc_bytes(b, realloc_size);
+        // Tips: only the capacity is reserved, the groups are appended after the bytes in `b`
+        b.reserve((d_len / ENCODE_GROUP_SIZE + 1) * (ENCODE_GROUP_SIZE + 1));
 
         let mut idx = 0;
         while idx <= d_len {
@@ -755,12 +1129,29 @@ impl DataValue {
         }
     }
 
-    fn realloc_bytes(b: &mut Vec<u8>, size: usize) {
-        let len = b.len();
+    // Encodes a decimal as its sign, then the exponent and the digits of
+    // `0.{digits} * 10^{exponent}` without the trailing zeros, so that the equal values of any
+    // scale are encoded the same, e.g. `1.5` and `1.50` are `0.15 * 10^1`. The exponent and
+    // the digits of a negative value are inverted, as the larger they are the smaller it is.
+    fn encode_decimal(b: &mut Vec<u8>, decimal: &Decimal) {
+        let decimal = decimal.normalize();
 
-        if size > len {
-            b.reserve(size - len);
-            b.resize(size, 0);
+        if decimal.is_zero() {
+            b.push(1);
+            return;
+        }
+        let digits = decimal.mantissa().unsigned_abs().to_string();
+        // Tips: in `-27..=29` for the 28 digits at most after the point
+        let exponent = digits.len() as i32 - decimal.scale() as i32;
+        let mut bytes = vec![(exponent + 128) as u8];
+        Self::encode_bytes(&mut bytes, digits.as_bytes());
+
+        if decimal.is_sign_negative() {
+            b.push(0);
+            b.extend(bytes.iter().map(|byte| !byte));
+        } else {
+            b.push(2);
+            b.extend(bytes);
         }
     }
 
@@ -771,14 +1162,30 @@ impl DataValue {
             DataValue::Int32(Some(v)) | DataValue::Date32(Some(v)) => {
                 encode_u!(b, *v as u32 ^ 0x80000000_u32)
             }
-            DataValue::Int64(Some(v)) | DataValue::Date64(Some(v)) => {
+            DataValue::Int64(Some(v))
+            | DataValue::Date64(Some(v))
+            | DataValue::TimestampTz { value: Some(v), .. } => {
                 encode_u!(b, *v as u64 ^ 0x8000000000000000_u64)
             }
             DataValue::UInt8(Some(v)) => encode_u!(b, v),
             DataValue::UInt16(Some(v)) => encode_u!(b, v),
             DataValue::UInt32(Some(v)) | DataValue::Time(Some(v)) => encode_u!(b, v),
             DataValue::UInt64(Some(v)) => encode_u!(b, v),
+            DataValue::Interval(Some(v)) => {
+                encode_u!(
+                    b,
+                    v.total_micros() as u128 ^ 0x8000_0000_0000_0000_0000_0000_0000_0000_u128
+                )
+            }
+            DataValue::Uuid(Some(v)) => encode_u!(b, v.as_u128()),
+            // e.g. `'ab'` and `'ab   '` are the same key of a `CHAR`
+            DataValue::Utf8 {
+                value: Some(v),
+                ty: Utf8Type::Fixed(_),
+                ..
+            } => Self::encode_bytes(b, v.trim_end_matches(' ').as_bytes()),
             DataValue::Utf8 { value: Some(v), .. } => Self::encode_bytes(b, v.as_bytes()),
+            DataValue::Bytea(Some(v)) => Self::encode_bytes(b, v),
             DataValue::Boolean(Some(v)) => b.push(if *v { b'1' } else { b'0' }),
             DataValue::Float32(Some(f)) => {
                 let mut u = f.to_bits();
@@ -803,8 +1210,12 @@ impl DataValue {
                 encode_u!(b, u);
             }
             DataValue::Null => (),
-            DataValue::Decimal(Some(_v)) => todo!(),
-            DataValue::Tuple(Some(values)) => {
+            DataValue::Decimal(Some(v)) => Self::encode_decimal(b, v),
+            DataValue::Tuple(Some(values))
+            | DataValue::Struct {
+                values: Some(values),
+                ..
+            } => {
                 for v in values.iter() {
                     v.memcomparable_encode(b)?;
                     b.push(0u8);
@@ -831,8 +1242,26 @@ impl DataValue {
         }
     }
 
+    /// Casts like [`DataValue::cast`] for `TRY_CAST`, but a value that can not be cast becomes
+    /// the NULL of `to`.
+    pub fn cast_or_null(self, to: &LogicalType) -> DataValue {
+        self.cast(to).unwrap_or_else(|_| DataValue::none(to))
+    }
+
     pub fn cast(self, to: &LogicalType) -> Result<DataValue, DatabaseError> {
-        let value = match self {
+        // Tips: the integers out of the range of `to` overflow, e.g. `CAST(2147483648 AS INTEGER)`
+        let value = self.cast_value(to).map_err(|err| match err {
+            DatabaseError::TryFromInt(_) => {
+                DatabaseError::ArithmeticOverflow(format!("cast to {}", to))
+            }
+            err => err,
+        })?;
+        value.check_len(to)?;
+        Ok(value)
+    }
+
+    fn cast_value(self, to: &LogicalType) -> Result<DataValue, DatabaseError> {
+        match self {
             DataValue::Null => match to {
                 LogicalType::Invalid => Err(DatabaseError::CastFail),
                 LogicalType::SqlNull => Ok(DataValue::Null),
@@ -859,9 +1288,22 @@ impl DataValue {
                 }),
                 LogicalType::Date => Ok(DataValue::Date32(None)),
                 LogicalType::DateTime => Ok(DataValue::Date64(None)),
+                LogicalType::TimestampTz => Ok(DataValue::timestamp_tz_of(None)),
                 LogicalType::Time => Ok(DataValue::Time(None)),
+                LogicalType::Interval => Ok(DataValue::Interval(None)),
                 LogicalType::Decimal(_, _) => Ok(DataValue::Decimal(None)),
+                LogicalType::Json => Ok(DataValue::Json(None)),
+                LogicalType::Uuid => Ok(DataValue::Uuid(None)),
+                LogicalType::Bytea => Ok(DataValue::Bytea(None)),
                 LogicalType::Tuple => Ok(DataValue::Tuple(None)),
+                LogicalType::Struct(ty) => Ok(DataValue::Struct {
+                    values: None,
+                    ty: ty.clone(),
+                }),
+                LogicalType::Array(ty) => Ok(DataValue::Array {
+                    values: None,
+                    ty: ty.clone(),
+                }),
             },
             DataValue::Boolean(value) => match to {
                 LogicalType::SqlNull => Ok(DataValue::Null),
@@ -899,7 +1341,7 @@ impl DataValue {
                         .map(|v| {
                             let mut decimal =
                                 Decimal::from_f32(v).ok_or(DatabaseError::CastFail)?;
-                            Self::decimal_round_f(option, &mut decimal);
+                            Self::decimal_round(option, &mut decimal);
 
                             Ok::<Decimal, DatabaseError>(decimal)
                         })
@@ -922,7 +1364,7 @@ impl DataValue {
                         .map(|v| {
                             let mut decimal =
                                 Decimal::from_f64(v).ok_or(DatabaseError::CastFail)?;
-                            Self::decimal_round_f(option, &mut decimal);
+                            Self::decimal_round(option, &mut decimal);
 
                             Ok::<Decimal, DatabaseError>(decimal)
                         })
@@ -956,7 +1398,7 @@ impl DataValue {
                 }
                 LogicalType::Decimal(_, option) => Ok(DataValue::Decimal(value.map(|v| {
                     let mut decimal = Decimal::from(v);
-                    Self::decimal_round_i(option, &mut decimal);
+                    Self::decimal_round(option, &mut decimal);
 
                     decimal
                 }))),
@@ -989,7 +1431,7 @@ impl DataValue {
                 }
                 LogicalType::Decimal(_, option) => Ok(DataValue::Decimal(value.map(|v| {
                     let mut decimal = Decimal::from(v);
-                    Self::decimal_round_i(option, &mut decimal);
+                    Self::decimal_round(option, &mut decimal);
 
                     decimal
                 }))),
@@ -1024,11 +1466,12 @@ impl DataValue {
                 }
                 LogicalType::Decimal(_, option) => Ok(DataValue::Decimal(value.map(|v| {
                     let mut decimal = Decimal::from(v);
-                    Self::decimal_round_i(option, &mut decimal);
+                    Self::decimal_round(option, &mut decimal);
 
                     decimal
                 }))),
                 LogicalType::Boolean => numeric_to_boolean!(value),
+                LogicalType::DateTime => Ok(DataValue::Date64(value.map(|v| v.into()))),
                 _ => Err(DatabaseError::CastFail),
             },
             DataValue::Int64(value) => match to {
@@ -1059,11 +1502,12 @@ impl DataValue {
                 }
                 LogicalType::Decimal(_, option) => Ok(DataValue::Decimal(value.map(|v| {
                     let mut decimal = Decimal::from(v);
-                    Self::decimal_round_i(option, &mut decimal);
+                    Self::decimal_round(option, &mut decimal);
 
                     decimal
                 }))),
                 LogicalType::Boolean => numeric_to_boolean!(value),
+                LogicalType::DateTime => Ok(DataValue::Date64(value)),
                 _ => Err(DatabaseError::CastFail),
             },
             DataValue::UInt8(value) => match to {
@@ -1086,7 +1530,7 @@ impl DataValue {
                 }
                 LogicalType::Decimal(_, option) => Ok(DataValue::Decimal(value.map(|v| {
                     let mut decimal = Decimal::from(v);
-                    Self::decimal_round_i(option, &mut decimal);
+                    Self::decimal_round(option, &mut decimal);
 
                     decimal
                 }))),
@@ -1115,7 +1559,7 @@ impl DataValue {
                 }
                 LogicalType::Decimal(_, option) => Ok(DataValue::Decimal(value.map(|v| {
                     let mut decimal = Decimal::from(v);
-                    Self::decimal_round_i(option, &mut decimal);
+                    Self::decimal_round(option, &mut decimal);
 
                     decimal
                 }))),
@@ -1146,7 +1590,7 @@ impl DataValue {
                 }
                 LogicalType::Decimal(_, option) => Ok(DataValue::Decimal(value.map(|v| {
                     let mut decimal = Decimal::from(v);
-                    Self::decimal_round_i(option, &mut decimal);
+                    Self::decimal_round(option, &mut decimal);
 
                     decimal
                 }))),
@@ -1179,7 +1623,7 @@ impl DataValue {
                 }
                 LogicalType::Decimal(_, option) => Ok(DataValue::Decimal(value.map(|v| {
                     let mut decimal = Decimal::from(v);
-                    Self::decimal_round_i(option, &mut decimal);
+                    Self::decimal_round(option, &mut decimal);
 
                     decimal
                 }))),
@@ -1252,6 +1696,11 @@ impl DataValue {
 
                     Ok(DataValue::Date64(option))
                 }
+                LogicalType::TimestampTz => Ok(DataValue::timestamp_tz_of(
+                    value
+                        .map(|v| timestamp_tz::parse(&v, statement_time_zone()))
+                        .transpose()?,
+                )),
                 LogicalType::Time => {
                     let option = value
                         .map(|v| {
@@ -1262,9 +1711,32 @@ impl DataValue {
 
                     Ok(DataValue::Time(option))
                 }
-                LogicalType::Decimal(_, _) => Ok(DataValue::Decimal(
-                    value.map(|v| Decimal::from_str(&v)).transpose()?,
+                LogicalType::Interval => Ok(DataValue::Interval(
+                    value.map(|v| Interval::from_str(&v)).transpose()?,
+                )),
+                LogicalType::Decimal(_, option) => Ok(DataValue::Decimal(
+                    value
+                        .map(|v| {
+                            let mut decimal = Decimal::from_str(&v)?;
+                            Self::decimal_round(option, &mut decimal);
+
+                            Ok::<Decimal, DatabaseError>(decimal)
+                        })
+                        .transpose()?,
+                )),
+                LogicalType::Json => Ok(DataValue::Json(
+                    value.map(|v| json::normalize(&v)).transpose()?,
+                )),
+                LogicalType::Uuid => Ok(DataValue::Uuid(
+                    value.map(|v| Uuid::from_str(v.trim())).transpose()?,
                 )),
+                LogicalType::Bytea => Ok(DataValue::Bytea(
+                    value.map(|v| bytea::parse(&v)).transpose()?,
+                )),
+                LogicalType::Array(ty) => Ok(DataValue::Array {
+                    values: value.map(|v| Self::parse_elements(&v, ty)).transpose()?,
+                    ty: ty.clone(),
+                }),
                 _ => Err(DatabaseError::CastFail),
             },
             DataValue::Date32(value) => match to {
@@ -1295,6 +1767,19 @@ impl DataValue {
 
                     Ok(DataValue::Date64(option))
                 }
+                LogicalType::TimestampTz => {
+                    let offset = statement_time_zone().local_minus_utc();
+                    let option = value.and_then(|v| {
+                        NaiveDate::from_num_days_from_ce_opt(v)
+                            .and_then(|date| date.and_hms_opt(0, 0, 0))
+                            .map(|date_time| timestamp_tz::from_local(date_time, offset))
+                    });
+
+                    Ok(DataValue::TimestampTz {
+                        value: option,
+                        offset,
+                    })
+                }
                 _ => Err(DatabaseError::CastFail),
             },
             DataValue::Date64(value) => match to {
@@ -1333,6 +1818,57 @@ impl DataValue {
 
                     Ok(DataValue::Time(option))
                 }
+                LogicalType::TimestampTz => {
+                    let offset = statement_time_zone().local_minus_utc();
+                    let option = value.and_then(|v| {
+                        DateTime::from_timestamp(v, 0).map(|date_time| {
+                            timestamp_tz::from_local(date_time.naive_utc(), offset)
+                        })
+                    });
+
+                    Ok(DataValue::TimestampTz {
+                        value: option,
+                        offset,
+                    })
+                }
+                // Tips: `DateTime` is stored as seconds since the Unix epoch
+                LogicalType::Bigint => Ok(DataValue::Int64(value)),
+                _ => Err(DatabaseError::CastFail),
+            },
+            DataValue::TimestampTz { value, offset } => match to {
+                LogicalType::SqlNull => Ok(DataValue::Null),
+                LogicalType::Char(len, unit) => {
+                    varchar_cast!(
+                        Self::format_timestamp_tz(value, offset),
+                        Some(len),
+                        Utf8Type::Fixed(*len),
+                        *unit
+                    )
+                }
+                LogicalType::Varchar(len, unit) => {
+                    varchar_cast!(
+                        Self::format_timestamp_tz(value, offset),
+                        len,
+                        Utf8Type::Variable(*len),
+                        *unit
+                    )
+                }
+                LogicalType::Date => Ok(DataValue::Date32(
+                    value
+                        .and_then(|v| timestamp_tz::to_local(v, offset))
+                        .map(|date_time| date_time.date().num_days_from_ce()),
+                )),
+                LogicalType::DateTime => Ok(DataValue::Date64(
+                    value
+                        .and_then(|v| timestamp_tz::to_local(v, offset))
+                        .map(|date_time| date_time.and_utc().timestamp()),
+                )),
+                LogicalType::TimestampTz => Ok(DataValue::TimestampTz { value, offset }),
+                LogicalType::Time => Ok(DataValue::Time(
+                    value
+                        .and_then(|v| timestamp_tz::to_local(v, offset))
+                        .map(|date_time| date_time.time().num_seconds_from_midnight()),
+                )),
                 _ => Err(DatabaseError::CastFail),
             },
             DataValue::Time(value) => match to {
@@ -1355,26 +1891,183 @@ impl DataValue {
                 }
                 _ => Err(DatabaseError::CastFail),
             },
+            DataValue::Interval(value) => match to {
+                LogicalType::SqlNull => Ok(DataValue::Null),
+                LogicalType::Char(len, unit) => {
+                    varchar_cast!(value, Some(len), Utf8Type::Fixed(*len), *unit)
+                }
+                LogicalType::Varchar(len, unit) => {
+                    varchar_cast!(value, len, Utf8Type::Variable(*len), *unit)
+                }
+                LogicalType::Interval => Ok(DataValue::Interval(value)),
+                _ => Err(DatabaseError::CastFail),
+            },
             DataValue::Decimal(value) => match to {
                 LogicalType::SqlNull => Ok(DataValue::Null),
+                LogicalType::Tinyint => Ok(DataValue::Int8(
+                    value.map(Self::decimal_to_integer).transpose()?,
+                )),
+                LogicalType::UTinyint => Ok(DataValue::UInt8(
+                    value.map(Self::decimal_to_integer).transpose()?,
+                )),
+                LogicalType::Smallint => Ok(DataValue::Int16(
+                    value.map(Self::decimal_to_integer).transpose()?,
+                )),
+                LogicalType::USmallint => Ok(DataValue::UInt16(
+                    value.map(Self::decimal_to_integer).transpose()?,
+                )),
+                LogicalType::Integer => Ok(DataValue::Int32(
+                    value.map(Self::decimal_to_integer).transpose()?,
+                )),
+                LogicalType::UInteger => Ok(DataValue::UInt32(
+                    value.map(Self::decimal_to_integer).transpose()?,
+                )),
+                LogicalType::Bigint => Ok(DataValue::Int64(
+                    value.map(Self::decimal_to_integer).transpose()?,
+                )),
+                LogicalType::UBigint => Ok(DataValue::UInt64(
+                    value.map(Self::decimal_to_integer).transpose()?,
+                )),
                 LogicalType::Float => Ok(DataValue::Float32(value.and_then(|v| v.to_f32()))),
                 LogicalType::Double => Ok(DataValue::Float64(value.and_then(|v| v.to_f64()))),
-                LogicalType::Decimal(_, _) => Ok(DataValue::Decimal(value)),
+                LogicalType::Decimal(_, option) => Ok(DataValue::Decimal(value.map(|mut v| {
+                    Self::decimal_round(option, &mut v);
+
+                    v
+                }))),
+                LogicalType::Char(len, unit) => {
+                    varchar_cast!(value, Some(len), Utf8Type::Fixed(*len), *unit)
+                }
+                LogicalType::Varchar(len, unit) => {
+                    varchar_cast!(value, len, Utf8Type::Variable(*len), *unit)
+                }
+                _ => Err(DatabaseError::CastFail),
+            },
+            DataValue::Json(value) => match to {
+                LogicalType::SqlNull => Ok(DataValue::Null),
                 LogicalType::Char(len, unit) => {
                     varchar_cast!(value, Some(len), Utf8Type::Fixed(*len), *unit)
                 }
                 LogicalType::Varchar(len, unit) => {
                     varchar_cast!(value, len, Utf8Type::Variable(*len), *unit)
                 }
+                LogicalType::Json => Ok(DataValue::Json(value)),
                 _ => Err(DatabaseError::CastFail),
             },
-            DataValue::Tuple(values) => match to {
+            DataValue::Uuid(value) => match to {
+                LogicalType::SqlNull => Ok(DataValue::Null),
+                LogicalType::Char(len, unit) => {
+                    varchar_cast!(value, Some(len), Utf8Type::Fixed(*len), *unit)
+                }
+                LogicalType::Varchar(len, unit) => {
+                    varchar_cast!(value, len, Utf8Type::Variable(*len), *unit)
+                }
+                LogicalType::Uuid => Ok(DataValue::Uuid(value)),
+                _ => Err(DatabaseError::CastFail),
+            },
+            DataValue::Bytea(value) => match to {
+                LogicalType::SqlNull => Ok(DataValue::Null),
+                LogicalType::Char(len, unit) => {
+                    let value = value.as_deref().map(bytea::format);
+                    varchar_cast!(value, Some(len), Utf8Type::Fixed(*len), *unit)
+                }
+                LogicalType::Varchar(len, unit) => {
+                    let value = value.as_deref().map(bytea::format);
+                    varchar_cast!(value, len, Utf8Type::Variable(*len), *unit)
+                }
+                LogicalType::Bytea => Ok(DataValue::Bytea(value)),
+                _ => Err(DatabaseError::CastFail),
+            },
+            DataValue::Tuple(values) | DataValue::Struct { values, .. } => match to {
                 LogicalType::Tuple => Ok(DataValue::Tuple(values)),
+                LogicalType::Struct(ty) => Ok(DataValue::Struct {
+                    values: values
+                        .map(|values| Self::cast_fields(values, ty))
+                        .transpose()?,
+                    ty: ty.clone(),
+                }),
+                // e.g. `ARRAY[1, 2]`
+                LogicalType::Array(ty) => Ok(DataValue::Array {
+                    values: values
+                        .map(|values| Self::cast_elements(values, ty))
+                        .transpose()?,
+                    ty: ty.clone(),
+                }),
                 _ => Err(DatabaseError::CastFail),
             },
-        }?;
-        value.check_len(to)?;
-        Ok(value)
+            DataValue::Array { values, .. } => match to {
+                LogicalType::SqlNull => Ok(DataValue::Null),
+                LogicalType::Char(len, unit) => {
+                    let value = values.as_deref().map(array::format);
+                    varchar_cast!(value, Some(len), Utf8Type::Fixed(*len), *unit)
+                }
+                LogicalType::Varchar(len, unit) => {
+                    let value = values.as_deref().map(array::format);
+                    varchar_cast!(value, len, Utf8Type::Variable(*len), *unit)
+                }
+                LogicalType::Array(ty) => Ok(DataValue::Array {
+                    values: values
+                        .map(|values| Self::cast_elements(values, ty))
+                        .transpose()?,
+                    ty: ty.clone(),
+                }),
+                _ => Err(DatabaseError::CastFail),
+            },
+        }
+    }
+
+    /// Cast the values of a tuple or a struct to the fields of `ty` by position.
+    fn cast_fields(values: Vec<ValueRef>, ty: &StructType) -> Result<Vec<ValueRef>, DatabaseError> {
+        if values.len() != ty.fields().len() {
+            return Err(DatabaseError::CastFail);
+        }
+        values
+            .into_iter()
+            .zip(ty.fields())
+            .map(|(value, (_, field_type))| {
+                if &value.logical_type() == field_type {
+                    Ok(value)
+                } else {
+                    Ok(Arc::new(DataValue::clone(&value).cast(field_type)?))
+                }
+            })
+            .collect()
+    }
+
+    /// Cast the values of a tuple or an array to the element type of `ty`.
+    fn cast_elements(
+        values: Vec<ValueRef>,
+        ty: &ArrayType,
+    ) -> Result<Vec<ValueRef>, DatabaseError> {
+        let element_type = ty.element_type();
+
+        values
+            .into_iter()
+            .map(|value| {
+                if &value.logical_type() == element_type {
+                    Ok(value)
+                } else {
+                    Ok(Arc::new(DataValue::clone(&value).cast(element_type)?))
+                }
+            })
+            .collect()
+    }
+
+    /// Parses the text form of an array, see [`array::parse`].
+    fn parse_elements(text: &str, ty: &ArrayType) -> Result<Vec<ValueRef>, DatabaseError> {
+        let element_type = ty.element_type();
+
+        array::parse(text)?
+            .into_iter()
+            .map(|element| {
+                let value = DataValue::Utf8 {
+                    value: element,
+                    ty: Utf8Type::Variable(None),
+                    unit: CharLengthUnits::Characters,
+                };
+                Ok(Arc::new(value.cast(element_type)?))
+            })
+            .collect()
     }
 
     pub fn common_prefix_length(&self, target: &DataValue) -> Option<usize> {
@@ -1410,19 +2103,27 @@ impl DataValue {
         Some(0)
     }
 
-    fn decimal_round_i(option: &Option<u8>, decimal: &mut Decimal) {
-        if let Some(scale) = option {
-            let new_decimal = decimal.trunc_with_scale(*scale as u32);
-            let _ = mem::replace(decimal, new_decimal);
-        }
+    /// Rounds `decimal` half away from zero to the scale of a `DECIMAL(_, scale)`, and pads it
+    /// with zeros to the scale.
+    /// Rounds `decimal` half away from zero to an integer of `T`.
+    fn decimal_to_integer<T: TryFrom<i128, Error = TryFromIntError>>(
+        decimal: Decimal,
+    ) -> Result<T, DatabaseError> {
+        let integer = decimal
+            .round_dp_with_strategy(0, rust_decimal::RoundingStrategy::MidpointAwayFromZero)
+            .to_i128()
+            .ok_or(DatabaseError::CastFail)?;
+
+        Ok(T::try_from(integer)?)
     }
 
-    fn decimal_round_f(option: &Option<u8>, decimal: &mut Decimal) {
-        if let Some(scale) = option {
-            let new_decimal = decimal.round_dp_with_strategy(
+    pub(crate) fn decimal_round(scale: &Option<u8>, decimal: &mut Decimal) {
+        if let Some(scale) = scale {
+            let mut new_decimal = decimal.round_dp_with_strategy(
                 *scale as u32,
                 rust_decimal::RoundingStrategy::MidpointAwayFromZero,
             );
+            new_decimal.rescale(*scale as u32);
             let _ = mem::replace(decimal, new_decimal);
         }
     }
@@ -1562,8 +2263,15 @@ impl fmt::Display for DataValue {
             DataValue::Null => write!(f, "null")?,
             DataValue::Date32(e) => format_option!(f, e.and_then(DataValue::date_format))?,
             DataValue::Date64(e) => format_option!(f, e.and_then(DataValue::date_time_format))?,
+            DataValue::TimestampTz { value, offset } => {
+                format_option!(f, DataValue::format_timestamp_tz(*value, *offset))?
+            }
             DataValue::Time(e) => format_option!(f, e.and_then(DataValue::time_format))?,
+            DataValue::Interval(e) => format_option!(f, e)?,
             DataValue::Decimal(e) => format_option!(f, e.as_ref().map(DataValue::decimal_format))?,
+            DataValue::Json(e) => format_option!(f, e)?,
+            DataValue::Uuid(e) => format_option!(f, e)?,
+            DataValue::Bytea(e) => format_option!(f, e.as_deref().map(bytea::format))?,
             DataValue::Tuple(e) => {
                 write!(f, "(")?;
                 if let Some(values) = e {
@@ -1578,6 +2286,26 @@ impl fmt::Display for DataValue {
                 }
                 write!(f, ")")?;
             }
+            DataValue::Struct { values: None, .. } => write!(f, "null")?,
+            DataValue::Struct {
+                values: Some(values),
+                ty,
+            } => {
+                write!(f, "{{")?;
+                let len = values.len();
+
+                for (i, (value, (name, _))) in values.iter().zip(ty.fields()).enumerate() {
+                    write!(f, "{}: ", name)?;
+                    value.fmt(f)?;
+                    if len != i + 1 {
+                        write!(f, ", ")?;
+                    }
+                }
+                write!(f, "}}")?;
+            }
+            DataValue::Array { values, .. } => {
+                format_option!(f, values.as_deref().map(array::format))?
+            }
         };
         Ok(())
     }
@@ -1602,9 +2330,16 @@ impl fmt::Debug for DataValue {
             DataValue::Null => write!(f, "null"),
             DataValue::Date32(_) => write!(f, "Date32({})", self),
             DataValue::Date64(_) => write!(f, "Date64({})", self),
+            DataValue::TimestampTz { .. } => write!(f, "TimestampTz({})", self),
             DataValue::Time(_) => write!(f, "Time({})", self),
+            DataValue::Interval(_) => write!(f, "Interval({})", self),
             DataValue::Decimal(_) => write!(f, "Decimal({})", self),
+            DataValue::Json(_) => write!(f, "Json({})", self),
+            DataValue::Uuid(_) => write!(f, "Uuid({})", self),
+            DataValue::Bytea(_) => write!(f, "Bytea({})", self),
             DataValue::Tuple(_) => write!(f, "Tuple({})", self),
+            DataValue::Struct { .. } => write!(f, "Struct({})", self),
+            DataValue::Array { .. } => write!(f, "Array({})", self),
         }
     }
 }
@@ -1612,9 +2347,29 @@ impl fmt::Debug for DataValue {
 #[cfg(test)]
 mod test {
     use crate::errors::DatabaseError;
-    use crate::types::value::DataValue;
+    use crate::types::value::{DataValue, Utf8Type};
+    use crate::types::{ArrayType, LogicalType};
+    use chrono::FixedOffset;
+    use itertools::Itertools;
+    use rust_decimal::Decimal;
+    use sqlparser::ast::CharLengthUnits;
+    use std::str::FromStr;
     use std::sync::Arc;
 
+    #[test]
+    fn test_at_time_zone() {
+        let value = Arc::new(DataValue::Array {
+            values: Some(vec![Arc::new(DataValue::TimestampTz {
+                value: Some(1_714_536_000_000_000),
+                offset: 0,
+            })]),
+            ty: ArrayType::new(LogicalType::TimestampTz),
+        });
+        let value = DataValue::at_time_zone(&value, FixedOffset::east_opt(8 * 3600).unwrap());
+
+        debug_assert_eq!(value.to_string(), "{\"2024-05-01 12:00:00+08\"}");
+    }
+
     #[test]
     fn test_mem_comparable_int() -> Result<(), DatabaseError> {
         let mut key_i8_1 = Vec::new();
@@ -1703,6 +2458,120 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_mem_comparable_bytea() -> Result<(), DatabaseError> {
+        let encode = |bytes: &[u8]| -> Result<Vec<u8>, DatabaseError> {
+            let mut key = Vec::new();
+            DataValue::Bytea(Some(bytes.to_vec())).memcomparable_encode(&mut key)?;
+            Ok(key)
+        };
+        let long = vec![0xAB; 4096];
+        let mut longer = long.clone();
+        longer.push(0);
+
+        // the embedded and trailing zero bytes are kept, and a prefix is less than the whole
+        assert!(encode(&[])? < encode(&[0])?);
+        assert!(encode(&[0])? < encode(&[0, 0])?);
+        assert!(encode(&[1, 0, 2])? < encode(&[1, 0, 3])?);
+        assert!(encode(&[0xFF; 8])? < encode(&[0xFF; 9])?);
+        assert!(encode(&long)? < encode(&longer)?);
+        assert!(encode(&longer)? < encode(&[0xAC])?);
+        assert_eq!(encode(&[1, 2, 3, 0])?, vec![1, 2, 3, 0, 0, 0, 0, 0, 251]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mem_comparable_char() -> Result<(), DatabaseError> {
+        let encode = |text: &str, ty: Utf8Type| -> Result<Vec<u8>, DatabaseError> {
+            let mut key = Vec::new();
+            DataValue::Utf8 {
+                value: Some(text.to_string()),
+                ty,
+                unit: CharLengthUnits::Characters,
+            }
+            .memcomparable_encode(&mut key)?;
+            Ok(key)
+        };
+
+        assert_eq!(
+            encode("ab", Utf8Type::Fixed(5))?,
+            encode("ab   ", Utf8Type::Fixed(5))?
+        );
+        assert!(encode("ab", Utf8Type::Fixed(5))? < encode("ab c", Utf8Type::Fixed(5))?);
+        assert_ne!(
+            encode("ab", Utf8Type::Variable(None))?,
+            encode("ab   ", Utf8Type::Variable(None))?
+        );
+
+        let value = DataValue::Utf8 {
+            value: Some("ab   ".to_string()),
+            ty: Utf8Type::Variable(None),
+            unit: CharLengthUnits::Characters,
+        };
+        assert_eq!(
+            value
+                .clone()
+                .cast(&LogicalType::Char(2, CharLengthUnits::Characters))?,
+            DataValue::Utf8 {
+                value: Some("ab".to_string()),
+                ty: Utf8Type::Fixed(2),
+                unit: CharLengthUnits::Characters,
+            }
+        );
+        assert!(matches!(
+            value.cast(&LogicalType::Char(1, CharLengthUnits::Characters)),
+            Err(DatabaseError::TooLong)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mem_comparable_decimal() -> Result<(), DatabaseError> {
+        let encode = |text: &str| -> Result<Vec<u8>, DatabaseError> {
+            let mut key = Vec::new();
+            DataValue::Decimal(Some(Decimal::from_str(text)?)).memcomparable_encode(&mut key)?;
+            Ok(key)
+        };
+        let ordered = [
+            "-79228162514264337593543950335",
+            "-100.5",
+            "-100",
+            "-10.5",
+            "-1.25",
+            "-1",
+            "-0.0000000000000000000000000001",
+            "0",
+            "0.0000000000000000000000000001",
+            "0.05",
+            "0.5",
+            "1",
+            "1.25",
+            "1.5",
+            "10",
+            "10.5",
+            "100",
+            "100.5",
+            "105",
+            "79228162514264337593543950335",
+        ];
+        for (smaller, larger) in ordered.iter().tuple_windows() {
+            assert!(
+                encode(smaller)? < encode(larger)?,
+                "{} < {}",
+                smaller,
+                larger
+            );
+        }
+        // the equal values of any scale
+        assert_eq!(encode("1.5")?, encode("1.500")?);
+        assert_eq!(encode("-10")?, encode("-10.00")?);
+        assert_eq!(encode("0")?, encode("-0.00")?);
+
+        Ok(())
+    }
+
     #[test]
     fn test_mem_comparable_tuple() -> Result<(), DatabaseError> {
         let mut key_tuple_1 = Vec::new();
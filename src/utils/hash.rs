@@ -0,0 +1,25 @@
+use ahash::{AHasher, RandomState};
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+const FIXED_STATE: RandomState = RandomState::with_seeds(
+    0x243f_6a88_85a3_08d3,
+    0x1319_8a2e_0370_7344,
+    0xa409_3822_299f_31d0,
+    0x082e_fa98_ec4e_6c89,
+);
+
+/// Builds hashers with fixed seeds, so the hash tables of the executors hash and
+/// iterate their keys the same way on every run and with every storage.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct FixedState;
+
+impl BuildHasher for FixedState {
+    type Hasher = AHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        FIXED_STATE.build_hasher()
+    }
+}
+
+pub(crate) type FixedHashMap<K, V> = HashMap<K, V, FixedState>;
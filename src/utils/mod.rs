@@ -1,2 +1,3 @@
 pub(crate) mod bit_vector;
+pub(crate) mod hash;
 pub(crate) mod lru;
@@ -3,7 +3,9 @@ fn main() {}
 #[cfg(test)]
 mod test {
     use fnck_sql::catalog::column::{ColumnCatalog, ColumnDesc, ColumnRelation};
+    use fnck_sql::db::DataBaseBuilder;
     use fnck_sql::errors::DatabaseError;
+    use fnck_sql::expression::function::aggregate::AggregateFunctionImpl;
     use fnck_sql::expression::function::scala::ScalarFunctionImpl;
     use fnck_sql::expression::function::table::TableFunctionImpl;
     use fnck_sql::expression::function::FunctionSummary;
@@ -14,9 +16,10 @@ mod test {
     use fnck_sql::types::value::ValueRef;
     use fnck_sql::types::value::{DataValue, Utf8Type};
     use fnck_sql::types::LogicalType;
-    use fnck_sql::{implement_from_tuple, scala_function, table_function};
+    use fnck_sql::{aggregate_function, implement_from_tuple, scala_function, table_function};
     use sqlparser::ast::CharLengthUnits;
     use std::sync::Arc;
+    use tempfile::TempDir;
 
     fn build_tuple() -> (Tuple, SchemaRef) {
         let schema_ref = Arc::new(vec![
@@ -33,7 +36,8 @@ mod test {
                     false,
                     false,
                     None,
-                ).unwrap(),
+                )
+                .unwrap(),
             )),
         ]);
         let values = vec![
@@ -100,6 +104,23 @@ mod test {
             }))) as Box<dyn Iterator<Item = Result<Tuple, DatabaseError>>>)
     }));
 
+    aggregate_function!(GeometricMean::geometric_mean(LogicalType::Double) -> LogicalType::Double => (f64, i32) {
+        init: || (0.0, 0),
+        accumulate: |state: &mut (f64, i32), value: &ValueRef| {
+            state.0 += value.double().unwrap().ln();
+            state.1 += 1;
+            Ok(())
+        },
+        merge: |state: &mut (f64, i32), other: &(f64, i32)| {
+            state.0 += other.0;
+            state.1 += other.1;
+            Ok(())
+        },
+        finalize: |state: &(f64, i32)| {
+            Ok(DataValue::Float64((state.1 > 0).then(|| (state.0 / state.1 as f64).exp())))
+        }
+    });
+
     #[test]
     fn test_scala_function() -> Result<(), DatabaseError> {
         let function = MyScalaFunction::new();
@@ -197,4 +218,50 @@ mod test {
 
         Ok(())
     }
+
+    fn assert_double(value: &DataValue, expected: f64) {
+        let value = value.double().unwrap();
+
+        assert!((value - expected).abs() < 1e-9, "{} != {}", value, expected);
+    }
+
+    #[test]
+    fn test_aggregate_function() -> Result<(), DatabaseError> {
+        let temp_dir = TempDir::new().expect("unable to create temporary working directory");
+        let fnck_sql = DataBaseBuilder::path(temp_dir.path())
+            .register_aggregate_function(GeometricMean::new())
+            .build()?;
+
+        let _ = fnck_sql.run("create table t1 (id int primary key, k int, v double)")?;
+        let _ = fnck_sql.run(
+            "insert into t1 values (0, 1, 1.0), (1, 1, 4.0), (2, 2, 2.0), (3, 2, 8.0), (4, 2, null), (5, 3, null)",
+        )?;
+
+        let (schema, tuples) =
+            fnck_sql.run("select k, geometric_mean(v) from t1 group by k order by k")?;
+        assert_eq!(schema[1].name(), "geometric_mean(t1.v)");
+        assert_eq!(tuples.len(), 3);
+        assert_double(&tuples[0].values[1], 2.0);
+        assert_double(&tuples[1].values[1], 4.0);
+        assert!(tuples[2].values[1].is_null());
+
+        let (_, tuples) = fnck_sql.run("select geometric_mean(v) from t1")?;
+        assert_double(&tuples[0].values[0], 8.0_f64.sqrt());
+
+        // the partial states of two halves are merged
+        let function = GeometricMean::new();
+        let values = [1.0, 4.0, 2.0, 8.0].map(|v| Arc::new(DataValue::Float64(Some(v))));
+        let mut left = function.accumulator();
+        let mut right = function.accumulator();
+        for value in &values[..2] {
+            left.update_value(value)?;
+        }
+        for value in &values[2..] {
+            right.update_value(value)?;
+        }
+        left.merge(right.as_ref())?;
+        assert_double(left.evaluate()?.as_ref(), 8.0_f64.sqrt());
+
+        Ok(())
+    }
 }
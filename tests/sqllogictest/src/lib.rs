@@ -1,20 +1,20 @@
-use fnck_sql::db::Database;
+use fnck_sql::db::Session;
 use fnck_sql::errors::DatabaseError;
 use fnck_sql::storage::rocksdb::RocksStorage;
 use sqllogictest::{DBOutput, DefaultColumnType, DB};
 use std::time::Instant;
 
-pub struct SQLBase {
-    pub db: Database<RocksStorage>,
+pub struct SQLBase<'a> {
+    pub session: Session<'a, RocksStorage>,
 }
 
-impl DB for SQLBase {
+impl DB for SQLBase<'_> {
     type Error = DatabaseError;
     type ColumnType = DefaultColumnType;
 
     fn run(&mut self, sql: &str) -> Result<DBOutput<Self::ColumnType>, Self::Error> {
         let start = Instant::now();
-        let (schema, tuples) = self.db.run(sql)?;
+        let (schema, tuples) = self.session.run(sql)?;
         println!("|— Input SQL: {}", sql);
         println!(" |— time spent: {:?}", start.elapsed());
 
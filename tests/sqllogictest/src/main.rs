@@ -39,7 +39,9 @@ fn main() {
         let db = DataBaseBuilder::path(temp_dir.path())
             .build()
             .expect("init db error");
-        let mut tester = Runner::new(SQLBase { db });
+        let mut tester = Runner::new(SQLBase {
+            session: db.session(),
+        });
 
         if let Err(err) = tester.run_file(filepath) {
             panic!("test error: {}", err);